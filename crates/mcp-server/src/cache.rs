@@ -0,0 +1,108 @@
+//! 查询结果缓存
+//!
+//! 交互式使用时同一个问题常被反复询问。检索类工具的结果按「工具名 + 参数」缓存，
+//! 并记录写入时索引的版本号（`CodeIndex::generation`）；索引有任何更新时整个缓存失效。
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+/// 默认缓存的查询条数
+pub const DEFAULT_CAPACITY: usize = 64;
+
+/// 容量固定的 LRU 缓存
+#[derive(Debug)]
+pub struct QueryCache {
+    capacity: usize,
+    /// 缓存内容对应的索引版本号
+    generation: u64,
+    /// 键 → (最近使用时刻, 结果)
+    entries: HashMap<String, (u64, Value)>,
+    clock: u64,
+}
+
+impl Default for QueryCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl QueryCache {
+    /// 创建缓存（容量为 0 时不缓存）
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            generation: 0,
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    /// 缓存键：工具名与参数（JSON 对象的键有序，相同参数得到相同的键）
+    pub fn key(tool: &str, args: &Value) -> String {
+        format!("{}:{}", tool, args)
+    }
+
+    /// 查找缓存的结果；索引版本号变化时先清空缓存
+    pub fn get(&mut self, key: &str, generation: u64) -> Option<Value> {
+        self.sync(generation);
+        self.clock += 1;
+        let (used, value) = self.entries.get_mut(key)?;
+        *used = self.clock;
+        Some(value.clone())
+    }
+
+    /// 写入结果，满时淘汰最久未使用的一条
+    pub fn insert(&mut self, key: String, generation: u64, value: Value) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.sync(generation);
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (used, _))| *used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.clock += 1;
+        self.entries.insert(key, (self.clock, value));
+    }
+
+    fn sync(&mut self, generation: u64) {
+        if generation != self.generation {
+            self.entries.clear();
+            self.generation = generation;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_lru_and_invalidation() {
+        let mut cache = QueryCache::new(2);
+        cache.insert("a".to_string(), 1, json!(1));
+        cache.insert("b".to_string(), 1, json!(2));
+        // 访问 a 后 b 成为最久未使用的
+        assert_eq!(cache.get("a", 1), Some(json!(1)));
+        cache.insert("c".to_string(), 1, json!(3));
+        assert_eq!(cache.get("b", 1), None);
+        assert_eq!(cache.entries.len(), 2);
+
+        // 索引更新后全部失效
+        assert_eq!(cache.get("a", 2), None);
+        assert!(cache.entries.is_empty());
+
+        assert_eq!(
+            QueryCache::key("query_symbols", &json!({ "query": "a", "limit": 5 })),
+            QueryCache::key("query_symbols", &json!({ "limit": 5, "query": "a" }))
+        );
+    }
+}
@@ -0,0 +1,46 @@
+//! Synapse MCP 服务器
+//!
+//! 通过 stdio 提供 MCP（JSON-RPC 2.0，每行一条消息）接口，MCP 客户端无需 Node 层即可
+//! 解析文件、索引目录和查询符号：
+//!
+//! ```json
+//! {"mcpServers": {"synapse": {"command": "synapse-mcp"}}}
+//! ```
+//!
+//! stdout 只输出协议消息，日志写到 stderr。
+
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+
+mod cache;
+mod server;
+mod tools;
+
+use server::Server;
+
+fn main() -> ExitCode {
+    let mut server = Server::new();
+    let mut stdout = io::stdout().lock();
+
+    for line in io::stdin().lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("Failed to read stdin: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(response) = server.handle_line(&line) {
+            if writeln!(stdout, "{}", response).and_then(|_| stdout.flush()).is_err() {
+                // 客户端已关闭管道
+                return ExitCode::SUCCESS;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,156 @@
+//! JSON-RPC 分发（initialize / tools/list / tools/call / ping）
+
+use serde_json::{json, Value};
+
+use crate::tools::{self, Workspace};
+
+/// 服务器实现的 MCP 协议版本
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+// JSON-RPC 错误码
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// MCP 服务器状态（解析器与内存索引在多次调用间保留）
+#[derive(Default)]
+pub struct Server {
+    workspace: Workspace,
+}
+
+impl Server {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 处理一行输入，返回需要写回的响应（通知没有响应）
+    pub fn handle_line(&mut self, line: &str) -> Option<String> {
+        let response = match serde_json::from_str::<Value>(line) {
+            Ok(message) => self.handle(message)?,
+            Err(e) => error_response(Value::Null, PARSE_ERROR, format!("Parse error: {}", e)),
+        };
+        Some(response.to_string())
+    }
+
+    /// 处理一条 JSON-RPC 消息
+    pub fn handle(&mut self, message: Value) -> Option<Value> {
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            let id = message.get("id").cloned().unwrap_or(Value::Null);
+            return Some(error_response(id, INVALID_REQUEST, "Invalid request: missing method"));
+        };
+        let params = message.get("params").cloned().unwrap_or_else(|| json!({}));
+
+        // 没有 id 的是通知（`notifications/initialized` 等），不回复
+        let id = message.get("id").cloned()?;
+
+        let result = match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "synapse-mcp", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tools::definitions() })),
+            "tools/call" => self.call_tool(&params),
+            _ => Err((METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+        };
+
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, message),
+        })
+    }
+
+    /// 工具自身的失败以 `isError` 结果返回（客户端可展示给模型），参数缺失为协议错误
+    fn call_tool(&mut self, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or((INVALID_PARAMS, "Missing tool name".to_string()))?;
+        let arguments = params.get("arguments").cloned().unwrap_or_else(|| json!({}));
+        if !tools::exists(name) {
+            return Err((INVALID_PARAMS, format!("Unknown tool: {}", name)));
+        }
+
+        Ok(match self.workspace.call(name, &arguments) {
+            Ok(text) => json!({ "content": [{ "type": "text", "text": text }] }),
+            Err(e) => json!({ "content": [{ "type": "text", "text": e }], "isError": true }),
+        })
+    }
+}
+
+fn error_response(id: Value, code: i64, message: impl Into<String>) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message.into() } })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(server: &mut Server, id: u64, method: &str, params: Value) -> Value {
+        server
+            .handle(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .expect("response")
+    }
+
+    fn tool_text(response: &Value) -> &str {
+        response["result"]["content"][0]["text"].as_str().unwrap()
+    }
+
+    #[test]
+    fn test_handshake_and_tool_list() {
+        let mut server = Server::new();
+        let init = request(&mut server, 1, "initialize", json!({ "protocolVersion": PROTOCOL_VERSION }));
+        assert_eq!(init["result"]["serverInfo"]["name"], "synapse-mcp");
+        assert!(server
+            .handle(json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+            .is_none());
+
+        let list = request(&mut server, 2, "tools/list", json!({}));
+        let names: Vec<&str> = list["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["parse_file", "index_directory", "query_symbols", "set_overlay", "clear_overlay", "map_lines", "get_entity"]
+        );
+    }
+
+    #[test]
+    fn test_protocol_errors() {
+        let mut server = Server::new();
+        let response: Value = serde_json::from_str(&server.handle_line("{not json").unwrap()).unwrap();
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+
+        assert_eq!(request(&mut server, 1, "resources/list", json!({}))["error"]["code"], METHOD_NOT_FOUND);
+        let unknown = request(&mut server, 2, "tools/call", json!({ "name": "rm_rf" }));
+        assert_eq!(unknown["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_parse_file_tool() {
+        let mut server = Server::new();
+        let response = request(
+            &mut server,
+            1,
+            "tools/call",
+            json!({ "name": "parse_file", "arguments": { "path": "a.ts", "content": "export function add() {}\n" } }),
+        );
+        let result: Value = serde_json::from_str(tool_text(&response)).unwrap();
+        assert_eq!(result["language"], "TypeScript");
+        assert_eq!(result["entityInfo"][0]["name"], "add");
+
+        // 工具失败以 isError 返回
+        let missing = request(
+            &mut server,
+            2,
+            "tools/call",
+            json!({ "name": "parse_file", "arguments": { "path": "/no/such/file.ts" } }),
+        );
+        assert_eq!(missing["result"]["isError"], true);
+    }
+}
@@ -0,0 +1,488 @@
+//! MCP 工具：解析文件、索引目录、查询符号
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use synapse_parser::{
+    decode_source, normalize_path, CodeIndex, FileRole, IndexedEntity, LanguageManager, Overlay, ProjectScanner,
+    ScanOptions,
+};
+
+use crate::cache::QueryCache;
+
+/// `query_symbols` 默认返回的条数
+const DEFAULT_LIMIT: usize = 50;
+
+/// 工具共享的状态：解析器、跨调用保留的内存索引与未保存的编辑器内容
+#[derive(Default)]
+pub struct Workspace {
+    manager: LanguageManager,
+    index: CodeIndex,
+    overlay: Overlay,
+    /// 已索引的根目录（已规范化），其下的覆盖内容变化时同步更新索引
+    roots: Vec<String>,
+    /// 检索类工具的结果缓存（索引更新后失效）
+    cache: QueryCache,
+}
+
+/// 工具列表（`tools/list`）
+pub fn definitions() -> Vec<Value> {
+    vec![
+        json!({
+            "name": "parse_file",
+            "description": "Parse a source file and return its entities, imports, exports and calls as JSON.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "File path (used to detect the language)" },
+                    "content": { "type": "string", "description": "Source code; read from `path` when omitted" }
+                },
+                "required": ["path"]
+            }
+        }),
+        json!({
+            "name": "index_directory",
+            "description": "Scan a directory (respecting .gitignore), parse all supported files and add them to the in-memory index.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Project root directory" },
+                    "include": { "type": "array", "items": { "type": "string" }, "description": "Only parse files matching these globs" },
+                    "exclude": { "type": "array", "items": { "type": "string" }, "description": "Skip files and directories matching these globs" }
+                },
+                "required": ["path"]
+            }
+        }),
+        json!({
+            "name": "query_symbols",
+            "description": "Search indexed symbols by name (case-insensitive substring); exact matches and frequently called symbols first.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Name or part of a name" },
+                    "kind": { "type": "string", "description": "Entity kind filter (function, class, method, ...)" },
+                    "path": { "type": "string", "description": "Only search under this file or directory" },
+                    "role": { "type": "string", "enum": ["source", "test", "config", "script", "docs"], "description": "Only search files with this role (e.g. `source` for production code)" },
+                    "limit": { "type": "integer", "description": "Maximum results (default 50)" }
+                },
+                "required": ["query"]
+            }
+        }),
+        json!({
+            "name": "set_overlay",
+            "description": "Use unsaved editor contents for a file in place of what is on disk; indexed directories are updated immediately.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "File path, in the same form as the indexed directory (usually absolute)" },
+                    "content": { "type": "string", "description": "Unsaved file contents" }
+                },
+                "required": ["path", "content"]
+            }
+        }),
+        json!({
+            "name": "clear_overlay",
+            "description": "Drop unsaved contents for a file (or for all files when `path` is omitted) and go back to the file on disk.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "File path; omit to clear every overlay" }
+                }
+            }
+        }),
+        json!({
+            "name": "map_lines",
+            "description": "Translate line numbers between the file on disk and its unsaved overlay (null for lines that were edited, added or deleted).",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "File path" },
+                    "lines": { "type": "array", "items": { "type": "integer" }, "description": "1-based line numbers" },
+                    "toDisk": { "type": "boolean", "description": "Map overlay lines back to disk lines (default: disk to overlay)" }
+                },
+                "required": ["path", "lines"]
+            }
+        }),
+        json!({
+            "name": "get_entity",
+            "description": "Return an indexed entity, including its source text and a Markdown hover (signature, doc, location), by ID (as returned by query_symbols).",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string", "description": "Entity ID" }
+                },
+                "required": ["id"]
+            }
+        }),
+    ]
+}
+
+/// 是否为已知工具
+pub fn exists(name: &str) -> bool {
+    matches!(
+        name,
+        "parse_file" | "index_directory" | "query_symbols" | "set_overlay" | "clear_overlay" | "map_lines" | "get_entity"
+    )
+}
+
+/// 结果只取决于参数与索引内容、值得缓存的工具
+fn is_cacheable(name: &str) -> bool {
+    matches!(name, "query_symbols")
+}
+
+impl Workspace {
+    /// 调用工具，返回文本结果（JSON）
+    pub fn call(&mut self, name: &str, args: &Value) -> Result<String, String> {
+        let cache_key = is_cacheable(name).then(|| QueryCache::key(name, args));
+        if let Some(output) = cache_key.as_ref().and_then(|key| self.cache.get(key, self.index.generation())) {
+            return serde_json::to_string(&output).map_err(|e| e.to_string());
+        }
+
+        let output = match name {
+            "parse_file" => self.parse_file(args)?,
+            "index_directory" => self.index_directory(args)?,
+            "query_symbols" => self.query_symbols(args)?,
+            "set_overlay" => self.set_overlay(args)?,
+            "clear_overlay" => self.clear_overlay(args)?,
+            "map_lines" => self.map_lines(args)?,
+            "get_entity" => self.get_entity(args)?,
+            _ => return Err(format!("Unknown tool: {}", name)),
+        };
+        if let Some(key) = cache_key {
+            self.cache.insert(key, self.index.generation(), output.clone());
+        }
+        serde_json::to_string(&output).map_err(|e| e.to_string())
+    }
+
+    fn parse_file(&mut self, args: &Value) -> Result<Value, String> {
+        let path = str_arg(args, "path")?;
+        let result = match (args.get("content").and_then(Value::as_str), self.overlay.get(path)) {
+            (Some(content), _) | (None, Some(content)) => self.manager.parse_file(path, content)?,
+            // 磁盘文件按字节解析，非 UTF-8 内容解码后在 errors 中说明
+            (None, None) => {
+                let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+                self.manager.parse_file_bytes(path, &bytes)?
+            }
+        };
+        serde_json::to_value(result).map_err(|e| e.to_string())
+    }
+
+    fn index_directory(&mut self, args: &Value) -> Result<Value, String> {
+        let root = str_arg(args, "path")?;
+        let options = ScanOptions {
+            include: str_list_arg(args, "include")?,
+            exclude: str_list_arg(args, "exclude")?,
+            ..Default::default()
+        };
+        let scan = ProjectScanner::new(options).with_overlay(self.overlay.clone()).scan(root)?;
+        self.index.insert_batch(scan.results);
+        let root = normalize_path(root);
+        if !self.roots.contains(&root) {
+            self.roots.push(root);
+        }
+        Ok(json!({
+            "stats": scan.stats,
+            "fileCount": self.index.file_count(),
+            "entityCount": self.index.entity_count(),
+        }))
+    }
+
+    fn query_symbols(&mut self, args: &Value) -> Result<Value, String> {
+        let query = str_arg(args, "query")?.to_lowercase();
+        let kind = args.get("kind").and_then(Value::as_str);
+        let path = args.get("path").and_then(Value::as_str).unwrap_or("");
+        let role = args
+            .get("role")
+            .and_then(Value::as_str)
+            .map(str::parse::<FileRole>)
+            .transpose()?;
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .map_or(DEFAULT_LIMIT, |limit| limit as usize);
+
+        let mut matches: Vec<(bool, &IndexedEntity)> = self
+            .index
+            .entities_in(path)
+            .filter(|entity| kind.is_none_or(|kind| entity.kind == kind))
+            .filter(|entity| role.is_none_or(|role| entity.rank_hints.role == role))
+            .filter_map(|entity| {
+                let name = entity.name.as_deref()?.to_lowercase();
+                name.contains(&query).then_some((name == query, entity))
+            })
+            .collect();
+        matches.sort_by(|(a_exact, a), (b_exact, b)| {
+            b_exact
+                .cmp(a_exact)
+                .then(b.rank_hints.caller_count.cmp(&a.rank_hints.caller_count))
+                .then(a.id.cmp(&b.id))
+        });
+
+        let total = matches.len();
+        let symbols: Vec<Value> = matches
+            .into_iter()
+            .take(limit)
+            .map(|(_, entity)| {
+                json!({
+                    "id": entity.id,
+                    "name": entity.name,
+                    "kind": entity.kind,
+                    "filePath": entity.file_path,
+                    "range": entity.range,
+                    "isExported": entity.rank_hints.is_exported,
+                    "callerCount": entity.rank_hints.caller_count,
+                    "role": entity.rank_hints.role,
+                })
+            })
+            .collect();
+        Ok(json!({ "total": total, "symbols": symbols }))
+    }
+
+    fn set_overlay(&mut self, args: &Value) -> Result<Value, String> {
+        let path = str_arg(args, "path")?;
+        let content = str_arg(args, "content")?;
+        self.overlay.set(path, content);
+        let reindexed = self.is_indexed(path) && self.reindex(path, content);
+        Ok(json!({ "overlays": self.overlay.len(), "reindexed": reindexed }))
+    }
+
+    fn clear_overlay(&mut self, args: &Value) -> Result<Value, String> {
+        let paths: Vec<String> = match args.get("path").and_then(Value::as_str) {
+            Some(path) => vec![path.to_string()],
+            None => self.overlay.paths().map(str::to_string).collect(),
+        };
+        let mut cleared = 0;
+        for path in paths {
+            if !self.overlay.clear(&path) {
+                continue;
+            }
+            cleared += 1;
+            if self.is_indexed(&path) {
+                // 回到磁盘内容；从未保存过的文件从索引中移除
+                match fs::read(&path) {
+                    Ok(bytes) => {
+                        self.reindex(&path, &decode_source(&bytes).text);
+                    }
+                    Err(_) => {
+                        self.index.remove(&path);
+                    }
+                }
+            }
+        }
+        Ok(json!({ "cleared": cleared, "overlays": self.overlay.len() }))
+    }
+
+    fn map_lines(&mut self, args: &Value) -> Result<Value, String> {
+        let path = str_arg(args, "path")?;
+        let to_disk = args.get("toDisk").and_then(Value::as_bool).unwrap_or(false);
+        let lines = args
+            .get("lines")
+            .and_then(Value::as_array)
+            .and_then(|items| items.iter().map(Value::as_u64).collect::<Option<Vec<_>>>())
+            .ok_or("Argument lines must be an array of line numbers")?;
+        let map = self
+            .overlay
+            .position_map(path)
+            .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+        // 没有未保存内容时两个版本相同
+        let mapped: Vec<Option<usize>> = lines
+            .into_iter()
+            .map(|line| {
+                let line = line as usize;
+                match &map {
+                    Some(map) if to_disk => map.to_disk(line),
+                    Some(map) => map.to_overlay(line),
+                    None => Some(line),
+                }
+            })
+            .collect();
+        Ok(json!({ "lines": mapped, "hasOverlay": map.is_some() }))
+    }
+
+    /// 文件是否位于已索引的根目录下
+    fn is_indexed(&self, path: &str) -> bool {
+        let path = normalize_path(path);
+        self.roots
+            .iter()
+            .any(|root| root == "." || Path::new(&path).starts_with(Path::new(root)))
+    }
+
+    /// 用给定内容重新解析并写入索引（不支持的语言返回 false）
+    fn reindex(&mut self, path: &str, content: &str) -> bool {
+        match self.manager.parse_file(path, content) {
+            Ok(result) => {
+                self.index.insert(result);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn get_entity(&mut self, args: &Value) -> Result<Value, String> {
+        let id = str_arg(args, "id")?;
+        let entity = self.index.entity(id).ok_or_else(|| format!("Entity not found: {}", id))?;
+        let mut value = serde_json::to_value(entity).map_err(|e| e.to_string())?;
+        value["hover"] = Value::String(entity.hover_markdown());
+        Ok(value)
+    }
+}
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> Result<&'a str, String> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("Missing required argument: {}", key))
+}
+
+fn str_list_arg(args: &Value, key: &str) -> Result<Vec<String>, String> {
+    match args.get(key) {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| item.as_str().map(str::to_string))
+            .collect::<Option<_>>()
+            .ok_or_else(|| format!("Argument {} must be an array of strings", key)),
+        Some(_) => Err(format!("Argument {} must be an array of strings", key)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::Deref;
+    use std::path::{Path, PathBuf};
+
+    /// 测试项目的临时目录，离开作用域时删除
+    struct TempProject(PathBuf);
+
+    impl Deref for TempProject {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempProject {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// 每个测试用独立的目录，以免并行运行时互相删除
+    fn temp_project(name: &str) -> TempProject {
+        let root = std::env::temp_dir().join(format!("synapse-mcp-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/math.ts"), "export function add(a: number) { return a; }\nexport function addAll() {}\n").unwrap();
+        fs::write(root.join("src/app.ts"), "export class App {}\n").unwrap();
+        TempProject(root)
+    }
+
+    #[test]
+    fn test_index_and_query() {
+        let root = temp_project("query");
+        let mut workspace = Workspace::default();
+
+        let indexed: Value = serde_json::from_str(
+            &workspace
+                .call("index_directory", &json!({ "path": root.to_string_lossy() }))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(indexed["fileCount"], 2);
+
+        let found: Value =
+            serde_json::from_str(&workspace.call("query_symbols", &json!({ "query": "ADD" })).unwrap()).unwrap();
+        assert_eq!(found["total"], 2);
+        // 完全匹配排在前面
+        assert_eq!(found["symbols"][0]["name"], "add");
+
+        let id = found["symbols"][0]["id"].as_str().unwrap();
+        let entity: Value = serde_json::from_str(&workspace.call("get_entity", &json!({ "id": id })).unwrap()).unwrap();
+        assert!(entity["text"].as_str().unwrap().starts_with("export function add"));
+        assert!(entity["hover"].as_str().unwrap().starts_with("```typescript\nfunction add"));
+
+        let classes: Value = serde_json::from_str(
+            &workspace
+                .call("query_symbols", &json!({ "query": "", "kind": "class", "limit": 1 }))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(classes["symbols"][0]["name"], "App");
+
+        let by_role = |workspace: &mut Workspace, role: &str| -> Value {
+            serde_json::from_str(&workspace.call("query_symbols", &json!({ "query": "add", "role": role })).unwrap())
+                .unwrap()
+        };
+        assert_eq!(by_role(&mut workspace, "source")["symbols"][0]["role"], "source");
+        assert_eq!(by_role(&mut workspace, "test")["total"], 0);
+        assert!(workspace
+            .call("query_symbols", &json!({ "query": "add", "role": "vendor" }))
+            .unwrap_err()
+            .contains("Unknown file role"));
+
+        assert!(workspace.call("query_symbols", &json!({})).unwrap_err().contains("query"));
+    }
+
+    #[test]
+    fn test_overlay_updates_index() {
+        let project = temp_project("overlay");
+        let root = project.join("overlay");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("saved.ts"), "export function saved() {}\n").unwrap();
+        let path = |name: &str| root.join(name).to_string_lossy().into_owned();
+        let mut workspace = Workspace::default();
+        workspace.call("index_directory", &json!({ "path": root.to_string_lossy() })).unwrap();
+
+        let query = |workspace: &mut Workspace, name: &str| -> u64 {
+            let found: Value =
+                serde_json::from_str(&workspace.call("query_symbols", &json!({ "query": name })).unwrap()).unwrap();
+            found["total"].as_u64().unwrap()
+        };
+
+        // 未保存的修改与新文件立即可查
+        workspace
+            .call("set_overlay", &json!({ "path": path("saved.ts"), "content": "export function edited() {}\n" }))
+            .unwrap();
+        workspace
+            .call("set_overlay", &json!({ "path": path("draft.ts"), "content": "export class Draft {}\n" }))
+            .unwrap();
+        assert_eq!((query(&mut workspace, "edited"), query(&mut workspace, "saved")), (1, 0));
+        assert_eq!(query(&mut workspace, "Draft"), 1);
+        let parsed: Value =
+            serde_json::from_str(&workspace.call("parse_file", &json!({ "path": path("draft.ts") })).unwrap()).unwrap();
+        assert_eq!(parsed["entityInfo"][0]["name"], "Draft");
+
+        // 清除后回到磁盘内容，从未保存的文件被移除
+        let cleared: Value = serde_json::from_str(&workspace.call("clear_overlay", &json!({})).unwrap()).unwrap();
+        assert_eq!(cleared["cleared"], 2);
+        assert_eq!((query(&mut workspace, "edited"), query(&mut workspace, "saved")), (0, 1));
+        assert_eq!(query(&mut workspace, "Draft"), 0);
+    }
+
+    #[test]
+    fn test_map_lines() {
+        let project = temp_project("map");
+        let root = project.join("map");
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("a.ts").to_string_lossy().into_owned();
+        fs::write(&path, "function a() {}\nfunction b() {}\n").unwrap();
+        let mut workspace = Workspace::default();
+        let map = |workspace: &mut Workspace, args: Value| -> Value {
+            let mapped: Value = serde_json::from_str(&workspace.call("map_lines", &args).unwrap()).unwrap();
+            mapped["lines"].clone()
+        };
+
+        assert_eq!(map(&mut workspace, json!({ "path": path, "lines": [2] })), json!([2]));
+        workspace
+            .call("set_overlay", &json!({ "path": path, "content": "// header\nfunction a() {}\nfunction b() {}\n" }))
+            .unwrap();
+        assert_eq!(map(&mut workspace, json!({ "path": path, "lines": [1, 2] })), json!([2, 3]));
+        assert_eq!(
+            map(&mut workspace, json!({ "path": path, "lines": [1, 3], "toDisk": true })),
+            json!([null, 2])
+        );
+    }
+}
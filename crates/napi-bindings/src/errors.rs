@@ -0,0 +1,138 @@
+use napi::bindgen_prelude::*;
+use napi::{Env, JsError};
+
+/// 错误类型（JS 侧 `error.code`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// 无法识别文件语言（或该语言未编译进当前构建）
+    UnsupportedLanguage,
+    /// 语法或查询加载失败
+    LanguageLoad,
+    /// tree-sitter 未产出语法树（超时、取消等）
+    Parse,
+    /// 调用参数不合法
+    InvalidInput,
+    /// 结果序列化失败
+    Serialization,
+}
+
+impl ErrorKind {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::UnsupportedLanguage => "ERR_UNSUPPORTED_LANGUAGE",
+            Self::LanguageLoad => "ERR_LANGUAGE_LOAD",
+            Self::Parse => "ERR_PARSE",
+            Self::InvalidInput => "ERR_INVALID_INPUT",
+            Self::Serialization => "ERR_SERIALIZATION",
+        }
+    }
+
+    /// 重试是否可能成功（语言不支持、参数错误重试也无用）
+    pub fn retryable(self) -> bool {
+        matches!(self, Self::Parse)
+    }
+
+    /// 根据 Rust 侧错误信息归类
+    fn classify(reason: &str) -> Self {
+        if reason.starts_with("Unsupported file type") {
+            Self::UnsupportedLanguage
+        } else if reason.starts_with("File too large")
+            || reason.starts_with("Failed to read")
+            || reason.starts_with("Binary file")
+        {
+            Self::InvalidInput
+        } else if reason.starts_with("Failed to set language")
+            || reason.starts_with("Failed to create query")
+            || reason.starts_with("Failed to load")
+        {
+            Self::LanguageLoad
+        } else {
+            Self::Parse
+        }
+    }
+}
+
+/// 带结构化信息的 NAPI 错误
+///
+/// 抛到 JS 侧时是一个普通 `Error`，额外带有 `code`、`kind`、`retryable`，
+/// 以及可选的 `file`、`line`、`language` 属性。
+#[derive(Debug, Clone)]
+pub struct NativeError {
+    pub kind: ErrorKind,
+    pub reason: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub language: Option<String>,
+}
+
+impl NativeError {
+    pub fn new(kind: ErrorKind, reason: impl Into<String>) -> Self {
+        Self {
+            kind,
+            reason: reason.into(),
+            file: None,
+            line: None,
+            language: None,
+        }
+    }
+
+    /// 从解析器返回的错误信息构造（自动归类，并尝试提取 `line N`）
+    pub fn from_reason(reason: String) -> Self {
+        let line = line_from_reason(&reason);
+        let mut error = Self::new(ErrorKind::classify(&reason), reason);
+        error.line = line;
+        error
+    }
+
+    pub fn with_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// 转换为可抛出的 napi Error
+    pub fn into_napi(self, env: Env) -> Error {
+        let fallback = Error::from_reason(self.reason.clone());
+        self.build(env).unwrap_or(fallback)
+    }
+
+    fn build(self, env: Env) -> Result<Error> {
+        let js_error = JsError::from(Error::new(self.kind.code(), self.reason));
+        let mut object = js_error.into_unknown(env).coerce_to_object()?;
+
+        object.set_named_property("kind", kind_name(self.kind))?;
+        object.set_named_property("retryable", self.kind.retryable())?;
+        if let Some(file) = self.file {
+            object.set_named_property("file", file)?;
+        }
+        if let Some(line) = self.line {
+            object.set_named_property("line", line)?;
+        }
+        if let Some(language) = self.language {
+            object.set_named_property("language", language)?;
+        }
+
+        Ok(Error::from(object.into_unknown()))
+    }
+}
+
+fn kind_name(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::UnsupportedLanguage => "unsupportedLanguage",
+        ErrorKind::LanguageLoad => "languageLoad",
+        ErrorKind::Parse => "parse",
+        ErrorKind::InvalidInput => "invalidInput",
+        ErrorKind::Serialization => "serialization",
+    }
+}
+
+/// 提取信息中的 `line N`（如 "Syntax error at line 3"）
+fn line_from_reason(reason: &str) -> Option<u32> {
+    let rest = &reason[reason.find("line ")? + "line ".len()..];
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
@@ -1,12 +1,68 @@
 #![deny(clippy::all)]
 
+mod errors;
+mod tasks;
+
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Env, JsFunction};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use napi_derive::napi;
 use synapse_parser::{
+    apply_entity_hook,
+    content_hash,
+    render_diagram,
+    BodyMode,
+    ChunkOptions,
+    CodeIndex as RustCodeIndex,
+    CodeMapOptions,
+    IndexSnapshot as RustIndexSnapshot,
+    IndexStore as RustIndexStore,
+    CreditGate,
+    DedupMode,
+    DiagramFormat,
+    EntityCapOptions as RustEntityCapOptions,
+    EntityContext,
+    EntityOrder,
+    FileRole,
+    GraphFilter,
+    HeaderLanguage,
     LanguageManager as RustLanguageManager,
     LegacyASTParser as RustParser,
+    MetricsOptions as RustMetricsOptions,
+    Overlay as RustOverlay,
+    ParseOptions as RustParseOptions,
+    ParserConfig as RustParserConfig,
+    PathMode,
+    PositionMap,
+    ProjectScanner as RustProjectScanner,
+    ProjectWatcher as RustProjectWatcher,
+    SamplingOptions as RustSamplingOptions,
+    ScanOptions as RustScanOptions,
+    StreamingIndexer as RustStreamingIndexer,
+    Subgraph,
+    SummaryCache as RustSummaryCache,
+    SupportedLanguage,
+    TagRules as RustTagRules,
+    ThrottleOptions as RustThrottleOptions,
+    Tokenizer,
+    WatchOptions as RustWatchOptions,
 };
 
+use errors::{ErrorKind, NativeError};
+use tasks::{CompactTask, ParseFileTask, ParseFilesBatchTask, ScanProjectTask};
+
+/// 序列化为 JSON，失败时抛出 ERR_SERIALIZATION
+fn to_json<T: serde::Serialize + ?Sized>(env: Env, value: &T) -> Result<String> {
+    serde_json::to_string(value)
+        .map_err(|e| NativeError::new(ErrorKind::Serialization, e.to_string()).into_napi(env))
+}
+
+/// `parse_files_streaming` 回调队列长度（JS 侧积压超过该数量时解析线程暂停）
+const STREAMING_QUEUE_SIZE: usize = 256;
+
 /// NAPI AST Parser（旧版 - 保持向后兼容）
 #[napi(js_name = "ASTParser")]
 pub struct ASTParser {
@@ -21,7 +77,7 @@ impl ASTParser {
     /// 创建新的解析器实例
     #[napi(constructor)]
     pub fn new() -> Result<Self> {
-        let inner = RustParser::new().map_err(|e| Error::from_reason(e))?;
+        let inner = RustParser::new().map_err(Error::from_reason)?;
         Ok(Self { inner })
     }
 
@@ -36,14 +92,14 @@ impl ASTParser {
     /// 
     /// 返回 JSON 序列化的 ParseResult
     #[napi]
-    pub fn parse_file(&mut self, file_path: String, source_code: String) -> Result<String> {
+    pub fn parse_file(&mut self, env: Env, file_path: String, source_code: String) -> Result<String> {
         let result = self
             .inner
             .parse_file(&file_path, &source_code)
-            .map_err(|e| Error::from_reason(e))?;
+            .map_err(|e| legacy_error(env, e, &file_path))?;
 
         // 序列化为 JSON
-        serde_json::to_string(&result).map_err(|e| Error::from_reason(e.to_string()))
+        to_json(env, &result)
     }
 
     /// 获取支持的文件扩展名
@@ -67,12 +123,12 @@ impl ASTParser {
     /// 
     /// 返回 JSON 数组，每个元素为解析结果
     #[napi]
-    pub fn parse_files_batch(&mut self, files: Vec<Vec<String>>) -> Result<Vec<String>> {
+    pub fn parse_files_batch(&mut self, env: Env, files: Vec<Vec<String>>) -> Result<Vec<String>> {
         files
             .into_iter()
             .map(|file_info| {
                 if file_info.len() != 2 {
-                    return Err(Error::from_reason("Each file must have [path, content]"));
+                    return Err(invalid_batch_item(env));
                 }
                 
                 let file_path = &file_info[0];
@@ -81,14 +137,26 @@ impl ASTParser {
                 let result = self
                     .inner
                     .parse_file(file_path, source_code)
-                    .map_err(|e| Error::from_reason(e))?;
+                    .map_err(|e| legacy_error(env, e, file_path))?;
 
-                serde_json::to_string(&result).map_err(|e| Error::from_reason(e.to_string()))
+                to_json(env, &result)
             })
             .collect()
     }
 }
 
+/// 旧版解析器只支持 TypeScript/JavaScript
+fn legacy_error(env: Env, reason: String, file_path: &str) -> Error {
+    NativeError::from_reason(reason)
+        .with_file(file_path)
+        .with_language(Some("TypeScript".to_string()))
+        .into_napi(env)
+}
+
+fn invalid_batch_item(env: Env) -> Error {
+    NativeError::new(ErrorKind::InvalidInput, "Each file must have [path, content]").into_napi(env)
+}
+
 /// 性能基准测试辅助函数
 /// 
 /// # Arguments
@@ -103,7 +171,7 @@ impl ASTParser {
 pub fn benchmark_parse(source_code: String, iterations: u32) -> Result<f64> {
     use std::time::Instant;
 
-    let mut parser = RustParser::new().map_err(|e| Error::from_reason(e))?;
+    let mut parser = RustParser::new().map_err(Error::from_reason)?;
 
     let start = Instant::now();
     for _ in 0..iterations {
@@ -134,12 +202,12 @@ pub struct ParseStats {
 }
 
 #[napi]
-pub fn get_parse_stats(source_code: String) -> Result<ParseStats> {
-    let mut parser = RustParser::new().map_err(|e| Error::from_reason(e))?;
+pub fn get_parse_stats(env: Env, source_code: String) -> Result<ParseStats> {
+    let mut parser = RustParser::new().map_err(Error::from_reason)?;
     
     let result = parser
         .parse_file("temp.ts", &source_code)
-        .map_err(|e| Error::from_reason(e))?;
+        .map_err(|e| legacy_error(env, e, "temp.ts"))?;
 
     let mut stats = ParseStats {
         functions: 0,
@@ -164,10 +232,242 @@ pub fn get_parse_stats(source_code: String) -> Result<ParseStats> {
 
 // ==================== 新版多语言 API ====================
 
+/// 解析选项（对应 Rust 侧 ParseOptions，未提供的字段使用默认值）
+#[napi(object)]
+pub struct ParseOptions {
+    /// 允许 Flow 类型注解（文件头带 `@flow` 时自动开启）
+    pub flow: Option<bool>,
+    /// 允许旧版装饰器语法
+    pub legacy_decorators: Option<bool>,
+    /// 输出路径处理方式："preserve"（默认）/ "relative" / "anonymized"
+    #[napi(ts_type = "'preserve' | 'relative' | 'anonymized'")]
+    pub path_mode: Option<String>,
+    /// 项目根目录（relative / anonymized 模式据此计算相对路径）
+    pub project_root: Option<String>,
+    /// 解析符号链接后再输出路径
+    pub resolve_symlinks: Option<bool>,
+    /// 大文件采样（不提供时总是完整提取）
+    pub sampling: Option<SamplingOptions>,
+    /// `.h` 头文件的语言："auto"（默认，按内容判断）/ "c" / "cpp" / "objc"（不解析）
+    #[napi(ts_type = "'auto' | 'c' | 'cpp' | 'objc'")]
+    pub header_language: Option<String>,
+    /// 为每个实体计算行数、字节数与 token 数（不提供时不计算）
+    pub metrics: Option<MetricsOptions>,
+    /// 为函数 / 方法计算圈复杂度、最大嵌套深度与语句数（`complexity` 字段）
+    pub complexity: Option<bool>,
+    /// 为嵌套在类型 / 命名空间中的实体填写限定名 `qualifiedName`
+    pub qualified_names: Option<bool>,
+    /// 额外按代码片段文本去重（默认只按位置去重，文本相同的不同实体都保留）
+    pub dedup_text: Option<bool>,
+    /// Markdown 围栏代码块按语言标记解析，块内实体并入结果
+    pub parse_code_blocks: Option<bool>,
+    /// 识别 React 组件（TS / JS），每个组件额外输出一个 `component` 实体
+    pub react_components: Option<bool>,
+    /// 代码片段长度上限，超出时保留签名与首尾若干行（不提供时不截断）
+    pub entity_cap: Option<EntityCapOptions>,
+    /// 提取行为：注释、函数体、长度与文件大小上限、去重方式
+    pub config: Option<ParserConfig>,
+    /// 实体顺序："capture"（默认，捕获顺序）/ "position"（按源码位置）/ "kind"（按类型分组，组内按位置）
+    #[napi(ts_type = "'capture' | 'position' | 'kind'")]
+    pub entity_order: Option<String>,
+    /// 按路径解析（parsePath / parsePaths）时，不小于该大小（字节）的文件以内存映射读取（不提供时总是普通读取）
+    pub mmap_threshold: Option<u32>,
+}
+
+/// 提取行为配置（对应 Rust 侧 ParserConfig，未提供的字段沿用各语言的默认做法）
+#[napi(object)]
+pub struct ParserConfig {
+    /// 提取注释实体（默认 true）
+    pub include_comments: Option<bool>,
+    /// 函数 / 方法片段："language"（默认，由各语言决定）/ "signatures"（只有签名）/ "full"（含函数体）
+    #[napi(ts_type = "'language' | 'signatures' | 'full'")]
+    pub bodies: Option<String>,
+    /// 片段长度上限（字符数）；entityCap 已提供时以其为准
+    pub max_entity_length: Option<u32>,
+    /// 超过该大小（字节）的文件不解析，抛出 ERR_INVALID_INPUT
+    pub max_file_size: Option<u32>,
+    /// 去重方式："location"（默认，按位置）/ "text"（额外按片段文本）
+    #[napi(ts_type = "'location' | 'text'")]
+    pub dedup: Option<String>,
+    /// 注释的去重方式（不提供时同 dedup），只在单个文件内去重
+    #[napi(ts_type = "'location' | 'text'")]
+    pub comment_dedup: Option<String>,
+}
+
+impl ParserConfig {
+    fn into_rust(self, env: Env) -> Result<RustParserConfig> {
+        let bodies = match self.bodies.as_deref() {
+            None | Some("language") => BodyMode::Language,
+            Some("signatures") => BodyMode::Signatures,
+            Some("full") => BodyMode::Full,
+            Some(other) => {
+                return Err(NativeError::new(ErrorKind::InvalidInput, format!("Unknown body mode: {}", other))
+                    .into_napi(env))
+            }
+        };
+        let dedup = match self.dedup.as_deref() {
+            None => DedupMode::Location,
+            Some(mode) => parse_dedup_mode(mode, env)?,
+        };
+        let comment_dedup = self
+            .comment_dedup
+            .as_deref()
+            .map(|mode| parse_dedup_mode(mode, env))
+            .transpose()?;
+        Ok(RustParserConfig {
+            include_comments: self.include_comments.unwrap_or(true),
+            bodies,
+            max_entity_length: self.max_entity_length.map(|n| n as usize),
+            max_file_size: self.max_file_size.map(|n| n as usize),
+            dedup,
+            comment_dedup,
+        })
+    }
+}
+
+fn parse_dedup_mode(mode: &str, env: Env) -> Result<DedupMode> {
+    match mode {
+        "location" => Ok(DedupMode::Location),
+        "text" => Ok(DedupMode::Text),
+        other => Err(NativeError::new(ErrorKind::InvalidInput, format!("Unknown dedup mode: {}", other)).into_napi(env)),
+    }
+}
+
+/// 实体规模指标选项
+#[napi(object)]
+pub struct MetricsOptions {
+    /// token 估算方式："bpe"（默认，近似 tiktoken）/ "chars" / "words"
+    #[napi(ts_type = "'bpe' | 'chars' | 'words'")]
+    pub tokenizer: Option<String>,
+    /// "chars" 方式每个 token 对应的字符数（默认 4）
+    pub chars_per_token: Option<u32>,
+}
+
+impl MetricsOptions {
+    fn into_rust(self, env: Env) -> Result<RustMetricsOptions> {
+        let tokenizer = match self.tokenizer.as_deref() {
+            None | Some("bpe") => Tokenizer::Bpe,
+            Some("chars") => Tokenizer::Chars,
+            Some("words") => Tokenizer::Words,
+            Some(other) => {
+                return Err(NativeError::new(ErrorKind::InvalidInput, format!("Unknown tokenizer: {}", other))
+                    .into_napi(env))
+            }
+        };
+        let defaults = RustMetricsOptions::default();
+        Ok(RustMetricsOptions {
+            tokenizer,
+            chars_per_token: self.chars_per_token.map_or(defaults.chars_per_token, |n| n as usize),
+        })
+    }
+}
+
+/// 代码片段长度上限选项
+#[napi(object)]
+pub struct EntityCapOptions {
+    /// 默认上限（字符数，默认 8000）
+    pub max_chars: Option<u32>,
+    /// 按实体类型覆盖上限（如 `{ method: 2000 }`），0 表示该类型不截断
+    pub per_kind: Option<HashMap<String, u32>>,
+    /// 截断时保留的开头行数（默认 20，签名更长时保留完整签名）
+    pub head_lines: Option<u32>,
+    /// 截断时保留的结尾行数（默认 5）
+    pub tail_lines: Option<u32>,
+}
+
+impl From<EntityCapOptions> for RustEntityCapOptions {
+    fn from(options: EntityCapOptions) -> Self {
+        let defaults = RustEntityCapOptions::default();
+        Self {
+            max_chars: options.max_chars.map_or(defaults.max_chars, |n| n as usize),
+            per_kind: options
+                .per_kind
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(kind, cap)| (kind, cap as usize))
+                .collect(),
+            head_lines: options.head_lines.map_or(defaults.head_lines, |n| n as usize),
+            tail_lines: options.tail_lines.map_or(defaults.tail_lines, |n| n as usize),
+        }
+    }
+}
+
+/// 大文件采样选项
+#[napi(object)]
+pub struct SamplingOptions {
+    /// 超过该大小（字节）的文件启用采样（默认 1 MiB）
+    pub threshold: Option<u32>,
+    /// 首尾各完整提取的字节数（默认 64 KiB）
+    pub sample_bytes: Option<u32>,
+}
+
+impl From<SamplingOptions> for RustSamplingOptions {
+    fn from(options: SamplingOptions) -> Self {
+        let defaults = RustSamplingOptions::default();
+        Self {
+            threshold: options.threshold.map_or(defaults.threshold, |n| n as usize),
+            sample_bytes: options.sample_bytes.map_or(defaults.sample_bytes, |n| n as usize),
+        }
+    }
+}
+
+impl ParseOptions {
+    fn into_rust(self, env: Env) -> Result<RustParseOptions> {
+        let path_mode = match self.path_mode.as_deref() {
+            None | Some("preserve") => PathMode::Preserve,
+            Some("relative") => PathMode::Relative,
+            Some("anonymized") => PathMode::Anonymized,
+            Some(other) => {
+                return Err(NativeError::new(ErrorKind::InvalidInput, format!("Unknown path mode: {}", other))
+                    .into_napi(env))
+            }
+        };
+        let header_language = match self.header_language.as_deref() {
+            None | Some("auto") => HeaderLanguage::Auto,
+            Some("c") => HeaderLanguage::C,
+            Some("cpp") => HeaderLanguage::Cpp,
+            Some("objc") => HeaderLanguage::Objc,
+            Some(other) => {
+                return Err(NativeError::new(ErrorKind::InvalidInput, format!("Unknown header language: {}", other))
+                    .into_napi(env))
+            }
+        };
+        let entity_order = match self.entity_order.as_deref() {
+            None | Some("capture") => EntityOrder::Capture,
+            Some("position") => EntityOrder::Position,
+            Some("kind") => EntityOrder::Kind,
+            Some(other) => {
+                return Err(NativeError::new(ErrorKind::InvalidInput, format!("Unknown entity order: {}", other))
+                    .into_napi(env))
+            }
+        };
+        Ok(RustParseOptions {
+            flow: self.flow.unwrap_or(false),
+            legacy_decorators: self.legacy_decorators.unwrap_or(false),
+            path_mode,
+            project_root: self.project_root,
+            resolve_symlinks: self.resolve_symlinks.unwrap_or(false),
+            sampling: self.sampling.map(RustSamplingOptions::from),
+            header_language,
+            metrics: self.metrics.map(|metrics| metrics.into_rust(env)).transpose()?,
+            complexity: self.complexity.unwrap_or(false),
+            qualified_names: self.qualified_names.unwrap_or(false),
+            dedup_text: self.dedup_text.unwrap_or(false),
+            parse_code_blocks: self.parse_code_blocks.unwrap_or(false),
+            react_components: self.react_components.unwrap_or(false),
+            entity_cap: self.entity_cap.map(RustEntityCapOptions::from),
+            config: self.config.map(|config| config.into_rust(env)).transpose()?.unwrap_or_default(),
+            entity_order,
+            mmap_threshold: self.mmap_threshold.map(u64::from),
+        })
+    }
+}
+
 /// 多语言解析器管理器（新版 API）
 #[napi]
 pub struct LanguageManager {
-    inner: RustLanguageManager,
+    /// 与异步解析任务共享；修改配置时若仍有任务持有，先分叉一份（见 `manager_mut`）
+    inner: Arc<RustLanguageManager>,
 }
 
 #[napi]
@@ -176,19 +476,147 @@ impl LanguageManager {
     #[napi(constructor)]
     pub fn new() -> Result<Self> {
         Ok(Self {
-            inner: RustLanguageManager::new(),
+            inner: Arc::new(RustLanguageManager::new()),
         })
     }
 
+    /// 设置解析选项
+    #[napi]
+    pub fn set_options(&mut self, env: Env, options: ParseOptions) -> Result<()> {
+        let options = options.into_rust(env)?;
+        self.manager_mut().set_options(options);
+        Ok(())
+    }
+
     /// 根据文件路径自动检测语言并解析
     #[napi]
-    pub fn parse_file(&mut self, file_path: String, source_code: String) -> Result<String> {
+    pub fn parse_file(&mut self, env: Env, file_path: String, source_code: String) -> Result<String> {
         let result = self
             .inner
-            .parse_file(&file_path, &source_code)
-            .map_err(|e| Error::from_reason(e))?;
+            .parse_file_shared(&file_path, &source_code)
+            .map_err(|e| self.error(env, e, &file_path))?;
 
-        serde_json::to_string(&result).map_err(|e| Error::from_reason(e.to_string()))
+        to_json(env, &result)
+    }
+
+    /// 内容变化时才解析：内容哈希与 previous_hash 相同时返回 null，
+    /// 否则返回 JSON 序列化的 ParseResult（contentHash 为本次的哈希，供下次传入）
+    #[napi]
+    pub fn parse_if_changed(
+        &self,
+        env: Env,
+        file_path: String,
+        source_code: String,
+        previous_hash: Option<String>,
+    ) -> Result<Option<String>> {
+        let result = self
+            .inner
+            .parse_if_changed(&file_path, &source_code, previous_hash.as_deref())
+            .map_err(|e| self.error(env, e, &file_path))?;
+
+        result.map(|result| to_json(env, &result)).transpose()
+    }
+
+    /// 按路径在 Rust 侧读取并解析（文件内容不经过 JS），返回 JSON 序列化的 ParseResult
+    #[napi]
+    pub fn parse_path(&self, env: Env, file_path: String) -> Result<String> {
+        let result = self
+            .inner
+            .parse_path(&file_path)
+            .map_err(|e| self.error(env, e, &file_path))?;
+
+        to_json(env, &result)
+    }
+
+    /// 按路径批量解析（结果顺序与输入一致，读取或解析失败的文件被跳过）
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - 文件路径列表
+    /// * `throttle` - 可选的线程数 / CPU / IO 限制
+    #[napi]
+    pub fn parse_paths(&self, env: Env, paths: Vec<String>, throttle: Option<ThrottleOptions>) -> Result<Vec<String>> {
+        let throttle = throttle.map(RustThrottleOptions::from).unwrap_or_default();
+        let results = self
+            .inner
+            .parse_paths_with(paths, &throttle)
+            .map_err(|e| NativeError::from_reason(e).into_napi(env))?;
+
+        results.iter().map(|r| to_json(env, r)).collect()
+    }
+
+    /// 解析原始文件内容（Buffer）：非 UTF-8 内容（Windows-1252、UTF-16、混合编码）解码后解析，
+    /// 编码转换与字节替换记入结果的 errors
+    #[napi]
+    pub fn parse_file_bytes(&mut self, env: Env, file_path: String, content: Buffer) -> Result<String> {
+        let result = self
+            .inner
+            .parse_file_bytes_shared(&file_path, &content)
+            .map_err(|e| self.error(env, e, &file_path))?;
+
+        to_json(env, &result)
+    }
+
+    /// 解析并返回结构化实体（kind / name / range / signature / language / text）的 JSON 数组
+    #[napi]
+    pub fn parse_file_structured(&mut self, env: Env, file_path: String, source_code: String) -> Result<String> {
+        let result = self
+            .inner
+            .parse_file_shared(&file_path, &source_code)
+            .map_err(|e| self.error(env, e, &file_path))?;
+
+        to_json(env, &result.structured_entities())
+    }
+
+    /// 解析并返回按嵌套关系组装的实体树 JSON（每个节点为结构化实体加 `children`）
+    #[napi]
+    pub fn parse_file_tree(&mut self, env: Env, file_path: String, source_code: String) -> Result<String> {
+        let result = self
+            .inner
+            .parse_file_shared(&file_path, &source_code)
+            .map_err(|e| self.error(env, e, &file_path))?;
+
+        to_json(env, &result.entity_tree())
+    }
+
+    /// 解析并对每个实体调用 JS 钩子生成摘要
+    ///
+    /// 钩子参数为 `{ language, filePath, kind, name?, doc?, text }`，
+    /// 返回字符串时写入 `entityInfo[i].summary`，其他返回值视为跳过。
+    #[napi(
+        ts_args_type = "filePath: string, sourceCode: string, hook: (entity: { language: string, filePath: string, kind: string, name?: string, doc?: string, text: string }) => string | null | undefined"
+    )]
+    pub fn parse_file_with_hook(
+        &mut self,
+        env: Env,
+        file_path: String,
+        source_code: String,
+        hook: JsFunction,
+    ) -> Result<String> {
+        let mut result = self
+            .inner
+            .parse_file_shared(&file_path, &source_code)
+            .map_err(|e| self.error(env, e, &file_path))?;
+
+        let mut hook_error = None;
+        let mut call_hook = |entity: &EntityContext| {
+            if hook_error.is_some() {
+                return None;
+            }
+            match call_entity_hook(env, &hook, entity) {
+                Ok(summary) => summary,
+                Err(e) => {
+                    hook_error = Some(e);
+                    None
+                }
+            }
+        };
+        apply_entity_hook(&mut call_hook, &mut result);
+        if let Some(e) = hook_error {
+            return Err(e);
+        }
+
+        to_json(env, &result)
     }
 
     /// 批量解析文件（性能优化版本）
@@ -201,7 +629,7 @@ impl LanguageManager {
     /// 
     /// 返回 JSON 数组，每个元素为解析结果
     #[napi]
-    pub fn parse_files_batch(&mut self, files: Vec<Vec<String>>) -> Result<Vec<String>> {
+    pub fn parse_files_batch(&mut self, env: Env, files: Vec<Vec<String>>) -> Result<Vec<String>> {
         let files_tuple: Vec<(String, String)> = files
             .into_iter()
             .filter_map(|file_info| {
@@ -215,13 +643,181 @@ impl LanguageManager {
 
         let results = self
             .inner
-            .parse_files_batch(files_tuple)
-            .map_err(|e| Error::from_reason(e))?;
+            .parse_files_batch_shared(files_tuple)
+            .map_err(|e| NativeError::from_reason(e).into_napi(env))?;
 
-        results
-            .iter()
-            .map(|r| serde_json::to_string(r).map_err(|e| Error::from_reason(e.to_string())))
-            .collect()
+        results.iter().map(|r| to_json(env, r)).collect()
+    }
+
+    /// 批量解析并按语言分组，返回 JSON 对象：语言名 -> ParseResult 数组
+    /// （按语言名排序，组内保持输入顺序）
+    #[napi]
+    pub fn parse_files_batch_by_language(&mut self, env: Env, files: Vec<Vec<String>>) -> Result<String> {
+        let files_tuple = files
+            .into_iter()
+            .filter_map(|file_info| match <[String; 2]>::try_from(file_info) {
+                Ok([path, content]) => Some((path, content)),
+                Err(_) => None,
+            })
+            .collect();
+
+        let groups = self
+            .manager_mut()
+            .parse_files_batch_by_language(files_tuple)
+            .map_err(|e| NativeError::from_reason(e).into_napi(env))?;
+
+        to_json(env, &groups)
+    }
+
+    /// 异步解析单个文件（在线程池中执行，返回 Promise<string>）
+    ///
+    /// 使用当前的解析选项、自定义 query 与语言包。
+    #[napi]
+    pub fn parse_file_async(&self, file_path: String, source_code: String) -> AsyncTask<ParseFileTask> {
+        AsyncTask::new(ParseFileTask::new(Arc::clone(&self.inner), file_path, source_code))
+    }
+
+    /// 异步批量解析（在线程池中执行，返回 Promise<string[]>）
+    #[napi]
+    pub fn parse_files_batch_async(&self, env: Env, files: Vec<Vec<String>>) -> Result<AsyncTask<ParseFilesBatchTask>> {
+        let files_tuple = files
+            .into_iter()
+            .map(|file_info| match <[String; 2]>::try_from(file_info) {
+                Ok([path, content]) => Ok((path, content)),
+                Err(_) => Err(invalid_batch_item(env)),
+            })
+            .collect::<Result<_>>()?;
+        Ok(AsyncTask::new(ParseFilesBatchTask::new(Arc::clone(&self.inner), files_tuple)))
+    }
+
+    /// 并行批量解析文件（多线程，结果顺序与输入一致）
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - 文件列表，每个元素为 [file_path, source_code]
+    /// * `throttle` - 可选的线程数 / CPU / IO 限制
+    #[napi]
+    pub fn parse_files_batch_parallel(
+        &self,
+        env: Env,
+        files: Vec<Vec<String>>,
+        throttle: Option<ThrottleOptions>,
+    ) -> Result<Vec<String>> {
+        if files.iter().any(|file_info| file_info.len() != 2) {
+            return Err(invalid_batch_item(env));
+        }
+        let files_tuple: Vec<(String, String)> = files
+            .into_iter()
+            .map(|mut file_info| {
+                let source_code = file_info.pop().unwrap_or_default();
+                let file_path = file_info.pop().unwrap_or_default();
+                (file_path, source_code)
+            })
+            .collect();
+
+        let throttle = throttle.map(RustThrottleOptions::from).unwrap_or_default();
+        let results = self
+            .inner
+            .parse_files_batch_parallel_with(files_tuple, &throttle)
+            .map_err(|e| NativeError::from_reason(e).into_napi(env))?;
+
+        results.iter().map(|r| to_json(env, r)).collect()
+    }
+
+    /// 并行批量解析，每个文件完成后立即回调，不在内存中累积全部结果
+    ///
+    /// 在后台线程中执行并立即返回。回调队列有界：JS 侧处理不过来时解析线程会暂停。
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - 文件列表，每个元素为 [file_path, source_code]
+    /// * `on_result` - 每个文件回调一次，参数为 FileOutcome JSON（`{filePath, result?, error?, progress}`）
+    /// * `on_done` - 全部完成后回调，参数为 BatchProgress JSON（`{completed, failed, total}`）
+    /// * `throttle` - 可选的线程数 / CPU / IO 限制
+    #[napi(
+        ts_args_type = "files: Array<[string, string]>, onResult: (err: Error | null, outcome: string) => void, onDone: (err: Error | null, progress: string) => void, throttle?: ThrottleOptions"
+    )]
+    pub fn parse_files_streaming(
+        &self,
+        env: Env,
+        files: Vec<Vec<String>>,
+        on_result: JsFunction,
+        on_done: JsFunction,
+        throttle: Option<ThrottleOptions>,
+    ) -> Result<()> {
+        if files.iter().any(|file_info| file_info.len() != 2) {
+            return Err(invalid_batch_item(env));
+        }
+        let files: Vec<(String, String)> = files
+            .into_iter()
+            .map(|mut file_info| {
+                let source_code = file_info.pop().unwrap_or_default();
+                let file_path = file_info.pop().unwrap_or_default();
+                (file_path, source_code)
+            })
+            .collect();
+
+        let on_result: ThreadsafeFunction<String> = on_result
+            .create_threadsafe_function(STREAMING_QUEUE_SIZE, |ctx: ThreadSafeCallContext<String>| Ok(vec![ctx.value]))?;
+        let on_done: ThreadsafeFunction<String> =
+            on_done.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| Ok(vec![ctx.value]))?;
+        let manager = self.inner.fork();
+        let throttle = throttle.map(RustThrottleOptions::from).unwrap_or_default();
+
+        std::thread::spawn(move || {
+            let progress = manager.parse_files_streaming(files, &throttle, |outcome| {
+                let json = serde_json::to_string(&outcome).map_err(|e| Error::new(Status::GenericFailure, e.to_string()));
+                // 队列满时阻塞解析线程，形成背压
+                on_result.call(json, ThreadsafeFunctionCallMode::Blocking);
+            });
+            let progress = progress
+                .and_then(|progress| serde_json::to_string(&progress).map_err(|e| e.to_string()))
+                .map_err(|e| Error::new(Status::GenericFailure, e));
+            on_done.call(progress, ThreadsafeFunctionCallMode::NonBlocking);
+        });
+
+        Ok(())
+    }
+
+    /// 注册自定义 tree-sitter query，覆盖该语言的内置 query
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - 语言名（不区分大小写，支持 `ts` / `py` 等别名）
+    /// * `query` - query 文本，按语法编译校验，失败时保留原有 query
+    #[napi]
+    pub fn set_query(&mut self, env: Env, language: String, query: String) -> Result<()> {
+        let lang = parse_language(env, &language)?;
+        self.manager_mut().set_query(lang, &query).map_err(|e| {
+            NativeError::new(ErrorKind::LanguageLoad, e)
+                .with_language(Some(lang.to_string()))
+                .into_napi(env)
+        })
+    }
+
+    /// 恢复语言的内置 query
+    #[napi]
+    pub fn reset_query(&mut self, env: Env, language: String) -> Result<()> {
+        let lang = parse_language(env, &language)?;
+        self.manager_mut().reset_query(lang);
+        Ok(())
+    }
+
+    /// 语言的内置 query（可在其基础上追加捕获后传给 `setQuery`）
+    #[napi]
+    pub fn get_default_query(env: Env, language: String) -> Result<String> {
+        let lang = parse_language(env, &language)?;
+        Ok(RustLanguageManager::default_query(lang).to_string())
+    }
+
+    /// 从动态库加载第三方语言包，返回语言名
+    ///
+    /// 语言包处理的扩展名优先于内置语言；ABI 见 LANGUAGE_SUPPORT.md 的 "Language Packs"
+    #[napi]
+    pub fn load_language_pack(&mut self, env: Env, path: String) -> Result<String> {
+        self.manager_mut()
+            .load_language_pack(&path)
+            .map_err(|e| NativeError::new(ErrorKind::LanguageLoad, e).into_napi(env))
     }
 
     /// 根据文件路径猜测语言
@@ -230,6 +826,12 @@ impl LanguageManager {
         self.inner.guess_language(&file_path).map(|lang| format!("{}", lang))
     }
 
+    /// 根据路径与内容判断语言（`.h` 在 C / C++ 之间选择，Objective-C 返回 null）
+    #[napi]
+    pub fn detect_language(&self, file_path: String, source_code: String) -> Option<String> {
+        self.inner.detect_language(&file_path, &source_code).map(|lang| lang.to_string())
+    }
+
     /// 获取支持的语言列表
     #[napi]
     pub fn get_supported_languages() -> Vec<String> {
@@ -238,4 +840,936 @@ impl LanguageManager {
             .map(|lang| format!("{}", lang))
             .collect()
     }
+
+    /// 附带文件与语言信息的错误
+    fn error(&self, env: Env, reason: String, file_path: &str) -> Error {
+        NativeError::from_reason(reason)
+            .with_file(&self.inner.display_path(file_path))
+            .with_language(self.inner.guess_language(file_path).map(|lang| lang.to_string()))
+            .into_napi(env)
+    }
+
+    /// 可修改的管理器：仍有异步任务持有时先分叉一份，进行中的任务继续使用原来的配置
+    fn manager_mut(&mut self) -> &mut RustLanguageManager {
+        if Arc::get_mut(&mut self.inner).is_none() {
+            self.inner = Arc::new(self.inner.fork());
+        }
+        Arc::get_mut(&mut self.inner).expect("forked manager is not shared")
+    }
+}
+
+/// 同步调用 JS 实体钩子，返回字符串摘要
+fn call_entity_hook(env: Env, hook: &JsFunction, entity: &EntityContext) -> Result<Option<String>> {
+    let mut arg = env.create_object()?;
+    arg.set_named_property("language", entity.language)?;
+    arg.set_named_property("filePath", entity.file_path)?;
+    arg.set_named_property("kind", entity.kind)?;
+    if let Some(name) = entity.name {
+        arg.set_named_property("name", name)?;
+    }
+    if let Some(doc) = entity.doc {
+        arg.set_named_property("doc", doc)?;
+    }
+    arg.set_named_property("text", entity.text)?;
+
+    let value = hook.call(None, &[arg])?;
+    if value.get_type()? != ValueType::String {
+        return Ok(None);
+    }
+    Ok(Some(value.coerce_to_string()?.into_utf8()?.into_owned()?))
+}
+
+// ==================== 内存代码索引 ====================
+
+/// 代码索引（按文件存储 LanguageManager 的解析结果，附带检索排序提示）
+#[napi]
+pub struct CodeIndex {
+    inner: RustCodeIndex,
+}
+
+impl Default for CodeIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[napi]
+impl CodeIndex {
+    /// 创建空索引
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self {
+            inner: RustCodeIndex::new(),
+        }
+    }
+
+    /// 写入解析结果
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - LanguageManager 返回的 JSON 字符串数组
+    #[napi]
+    pub fn insert_results(&mut self, env: Env, results: Vec<String>) -> Result<()> {
+        let parsed = results
+            .iter()
+            .map(|json| {
+                serde_json::from_str(json)
+                    .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e.to_string()).into_napi(env))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.inner.insert_batch(parsed);
+        Ok(())
+    }
+
+    /// 移除文件（留下墓碑，导出时带上删除记录，直到 `compact`）
+    #[napi]
+    pub fn remove_file(&mut self, file_path: String) -> bool {
+        self.inner.remove(&file_path)
+    }
+
+    /// 移除单个实体（留下墓碑）
+    #[napi]
+    pub fn remove_entity(&mut self, id: String) -> bool {
+        self.inner.remove_entity(&id)
+    }
+
+    /// 丢弃所有墓碑，在后台线程释放内存，返回回收统计（files / entities / bytes）的 JSON
+    #[napi]
+    pub fn compact(&mut self) -> AsyncTask<CompactTask> {
+        AsyncTask::new(CompactTask::new(self.inner.take_tombstones()))
+    }
+
+    /// 返回所有实体（含 rankHints）的 JSON 数组
+    #[napi]
+    pub fn entities(&self, env: Env) -> Result<String> {
+        let entities: Vec<_> = self.inner.entities().collect();
+        to_json(env, &entities)
+    }
+
+    /// 当前内容的只读快照：之后的写入（包括重建索引期间）不影响快照上的查询
+    #[napi]
+    pub fn snapshot(&self) -> IndexSnapshot {
+        IndexSnapshot {
+            inner: self.inner.snapshot(),
+        }
+    }
+
+    /// 文件或目录（路径前缀）下的实体 JSON 数组
+    #[napi]
+    pub fn entities_in(&self, env: Env, path_prefix: String) -> Result<String> {
+        let entities: Vec<_> = self.inner.entities_in(&path_prefix).collect();
+        to_json(env, &entities)
+    }
+
+    /// 带有指定标签的实体 JSON 数组
+    #[napi]
+    pub fn entities_with_tag(&self, env: Env, tag: String) -> Result<String> {
+        let entities: Vec<_> = self.inner.entities_with_tag(&tag).collect();
+        to_json(env, &entities)
+    }
+
+    /// 指定角色（source / test / config / script / docs）的文件中的实体 JSON 数组
+    #[napi]
+    pub fn entities_with_role(&self, env: Env, role: String) -> Result<String> {
+        let role = parse_file_role(env, &role)?;
+        let entities: Vec<_> = self.inner.entities_with_role(role).collect();
+        to_json(env, &entities)
+    }
+
+    /// 实体的悬停提示（签名 + 文档 + 定义位置，Markdown），不存在时返回 null
+    #[napi]
+    pub fn hover_info(&self, id: String) -> Option<String> {
+        self.inner.hover_info(&id)
+    }
+
+    /// 批量生成悬停提示，与 `ids` 按下标对应
+    #[napi]
+    pub fn hover_infos(&self, ids: Vec<String>) -> Vec<Option<String>> {
+        self.inner.hover_infos(ids.iter().map(String::as_str))
+    }
+
+    /// 设置标签规则（`{"rules": [{glob, kind, name, regex, tags}]}`），已有实体重新打标签
+    #[napi]
+    pub fn set_tag_rules(&mut self, env: Env, rules: String) -> Result<()> {
+        let rules = RustTagRules::from_json(&rules)
+            .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e).into_napi(env))?;
+        self.inner.set_tag_rules(rules);
+        Ok(())
+    }
+
+    /// 设置图过滤器（`{"excludeTests": true, "excludeGenerated": true}`）
+    ///
+    /// 被排除的文件仍可检索，但不参与调用边与 callerCount。
+    #[napi]
+    pub fn set_graph_filter(&mut self, env: Env, filter: String) -> Result<()> {
+        let filter = parse_graph_filter(env, &filter)?;
+        self.inner.set_graph_filter(filter);
+        Ok(())
+    }
+
+    /// 从规则文件加载标签规则
+    #[napi]
+    pub fn load_tag_rules(&mut self, env: Env, path: String) -> Result<()> {
+        let rules = RustTagRules::load(&path)
+            .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e).with_file(&path).into_napi(env))?;
+        self.inner.set_tag_rules(rules);
+        Ok(())
+    }
+
+    /// 索引统计（节点 / 边数量、存储大小、最大的文件与实体、构建耗时）的 JSON
+    #[napi]
+    pub fn index_stats(&self, env: Env) -> Result<String> {
+        to_json(env, &self.inner.index_stats())
+    }
+
+    /// 导出整个索引为 NDJSON（先节点后边）
+    #[napi]
+    pub fn export_ndjson(&self, env: Env, path: String) -> Result<()> {
+        self.inner
+            .export_ndjson(&path)
+            .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e).with_file(&path).into_napi(env))
+    }
+
+    /// 导出供向量库导入的分块 JSONL，返回写出的分块数
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - 输出文件路径
+    /// * `options` - 可选的 ChunkOptions JSON（`{"kinds": ["function"], "roles": ["source"], "excludeTests": true, "maxChars": 4000}`）
+    #[napi]
+    pub fn export_chunks_jsonl(&self, env: Env, path: String, options: Option<String>) -> Result<u32> {
+        let options: ChunkOptions = match options {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e.to_string()).into_napi(env))?,
+            None => ChunkOptions::default(),
+        };
+        self.inner
+            .export_chunks_jsonl(&path, &options)
+            .map(|count| count as u32)
+            .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e).with_file(&path).into_napi(env))
+    }
+
+    /// 渲染以给定文件为中心的子图（包含关系与调用）
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - 文件路径列表
+    /// * `format` - "mermaid" 或 "d2"
+    #[napi]
+    pub fn file_diagram(&self, env: Env, paths: Vec<String>, format: String) -> Result<String> {
+        let format = parse_diagram_format(env, &format)?;
+        let subgraph = self.inner.file_subgraph(paths.iter().map(String::as_str));
+        Ok(render_diagram(&subgraph, format))
+    }
+
+    /// 导出为 Neo4j Cypher 脚本
+    #[napi]
+    pub fn export_cypher(&self, env: Env, path: String) -> Result<()> {
+        self.inner
+            .export_cypher(&path)
+            .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e).with_file(&path).into_napi(env))
+    }
+
+    /// 导出静态 HTML 报告（文件树、符号搜索、依赖、统计表）
+    #[napi]
+    pub fn export_html_report(&self, env: Env, path: String, title: Option<String>) -> Result<()> {
+        let title = title.unwrap_or_else(|| "Code Report".to_string());
+        self.inner
+            .export_html_report(&path, &title)
+            .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e).with_file(&path).into_napi(env))
+    }
+
+    /// 从 NDJSON 导入，返回导入的文件数
+    #[napi]
+    pub fn import_ndjson(&mut self, env: Env, path: String) -> Result<u32> {
+        self.inner
+            .import_ndjson(&path)
+            .map(|count| count as u32)
+            .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e).with_file(&path).into_napi(env))
+    }
+}
+
+/// 代码索引的只读快照（`CodeIndex.snapshot()` 创建）
+#[napi]
+pub struct IndexSnapshot {
+    inner: RustIndexSnapshot,
+}
+
+#[napi]
+impl IndexSnapshot {
+    /// 文件数量
+    #[napi]
+    pub fn file_count(&self) -> u32 {
+        self.inner.file_count() as u32
+    }
+
+    /// 实体数量
+    #[napi]
+    pub fn entity_count(&self) -> u32 {
+        self.inner.entity_count() as u32
+    }
+
+    /// 所有实体的 JSON 数组
+    #[napi]
+    pub fn entities(&self, env: Env) -> Result<String> {
+        let entities: Vec<_> = self.inner.entities().collect();
+        to_json(env, &entities)
+    }
+
+    /// 文件或目录（路径前缀）下的实体 JSON 数组
+    #[napi]
+    pub fn entities_in(&self, env: Env, path_prefix: String) -> Result<String> {
+        let entities: Vec<_> = self.inner.entities_in(&path_prefix).collect();
+        to_json(env, &entities)
+    }
+
+    /// 带有指定标签的实体 JSON 数组
+    #[napi]
+    pub fn entities_with_tag(&self, env: Env, tag: String) -> Result<String> {
+        let entities: Vec<_> = self.inner.entities_with_tag(&tag).collect();
+        to_json(env, &entities)
+    }
+
+    /// 指定角色（source / test / config / script / docs）的文件中的实体 JSON 数组
+    #[napi]
+    pub fn entities_with_role(&self, env: Env, role: String) -> Result<String> {
+        let role = parse_file_role(env, &role)?;
+        let entities: Vec<_> = self.inner.entities_with_role(role).collect();
+        to_json(env, &entities)
+    }
+
+    /// 按 ID 查找实体，不存在时返回 null
+    #[napi]
+    pub fn entity(&self, env: Env, id: String) -> Result<Option<String>> {
+        self.inner.entity(&id).map(|entity| to_json(env, entity)).transpose()
+    }
+
+    /// 实体的悬停提示（签名 + 文档 + 定义位置，Markdown），不存在时返回 null
+    #[napi]
+    pub fn hover_info(&self, id: String) -> Option<String> {
+        self.inner.hover_info(&id)
+    }
+
+    /// 批量生成悬停提示，与 `ids` 按下标对应
+    #[napi]
+    pub fn hover_infos(&self, ids: Vec<String>) -> Vec<Option<String>> {
+        self.inner.hover_infos(ids.iter().map(String::as_str))
+    }
+
+    /// 文件内的实体 JSON 数组，文件不存在时返回 null
+    #[napi]
+    pub fn file_entities(&self, env: Env, file_path: String) -> Result<Option<String>> {
+        self.inner.file(&file_path).map(|file| to_json(env, &file.entities)).transpose()
+    }
+}
+
+/// 渲染子图为 Mermaid / D2 文本
+///
+/// # Arguments
+///
+/// * `subgraph` - `{ nodes: [{id, label, kind}], edges: [{from, to, kind}] }` 的 JSON，
+///   kind 为 contains / calls / extends / implements / imports
+/// * `format` - "mermaid" 或 "d2"
+#[napi]
+pub fn render_subgraph(env: Env, subgraph: String, format: String) -> Result<String> {
+    let format = parse_diagram_format(env, &format)?;
+    let subgraph: Subgraph = serde_json::from_str(&subgraph)
+        .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e.to_string()).into_napi(env))?;
+    Ok(render_diagram(&subgraph, format))
+}
+
+/// 解析一批文件之间的导入，返回 JSON 序列化的 SymbolGraph
+///
+/// # Arguments
+///
+/// * `results` - LanguageManager 返回的 ParseResult JSON 字符串数组
+/// * `filter` - 可选的 GraphFilter JSON（`{"excludeTests": true, "excludeGenerated": true, "excludeBarrels": true}`）
+#[napi]
+pub fn build_symbol_graph(env: Env, results: Vec<String>, filter: Option<String>) -> Result<String> {
+    let parsed = results
+        .iter()
+        .map(|json| {
+            serde_json::from_str(json)
+                .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e.to_string()).into_napi(env))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let filter = match filter {
+        Some(json) => parse_graph_filter(env, &json)?,
+        None => GraphFilter::default(),
+    };
+    to_json(env, &synapse_parser::build_symbol_graph_filtered(&parsed, &filter))
+}
+
+/// 估算文本的 token 数（与 ParseOptions.metrics 的计算方式一致）
+#[napi]
+pub fn count_tokens(env: Env, text: String, options: Option<MetricsOptions>) -> Result<u32> {
+    let options = match options {
+        Some(options) => options.into_rust(env)?,
+        None => RustMetricsOptions::default(),
+    };
+    Ok(synapse_parser::count_tokens(&text, &options) as u32)
+}
+
+/// 汇总一批文件对外部包的导入，返回 JSON 序列化的 PackageUsage 数组（按导入次数降序）
+///
+/// # Arguments
+///
+/// * `results` - LanguageManager 返回的 ParseResult JSON 字符串数组
+/// * `filter` - 可选的 GraphFilter JSON，被排除的文件不计入
+#[napi]
+pub fn import_summary(env: Env, results: Vec<String>, filter: Option<String>) -> Result<String> {
+    let parsed = results
+        .iter()
+        .map(|json| {
+            serde_json::from_str(json)
+                .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e.to_string()).into_napi(env))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let filter = match filter {
+        Some(json) => parse_graph_filter(env, &json)?,
+        None => GraphFilter::default(),
+    };
+    to_json(env, &synapse_parser::import_summary_filtered(&parsed, &filter))
+}
+
+/// 把一批解析结果渲染为 Markdown 代码地图（文件树 + 签名），用于 LLM 上下文打包
+///
+/// # Arguments
+///
+/// * `results` - LanguageManager 返回的 ParseResult JSON 字符串数组
+/// * `options` - 可选的 CodeMapOptions JSON（`{"title": "...", "exportedOnly": true, "includeDocs": false}`）
+#[napi]
+pub fn render_code_map(env: Env, results: Vec<String>, options: Option<String>) -> Result<String> {
+    let parsed = results
+        .iter()
+        .map(|json| {
+            serde_json::from_str(json)
+                .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e.to_string()).into_napi(env))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let options: CodeMapOptions = match options {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e.to_string()).into_napi(env))?,
+        None => CodeMapOptions::default(),
+    };
+    Ok(synapse_parser::render_code_map(&parsed, &options))
+}
+
+/// 在磁盘版本与编辑器中未保存的版本之间换算行号（从 1 开始）
+///
+/// 修改、新增或删除的行映射为 null。
+///
+/// # Arguments
+///
+/// * `disk` - 磁盘上的文件内容（索引所依据的版本）
+/// * `overlay` - 编辑器缓冲区内容
+/// * `lines` - 待换算的行号
+/// * `to_disk` - 为 true 时把缓冲区行号换算回磁盘行号
+#[napi]
+pub fn map_overlay_lines(disk: String, overlay: String, lines: Vec<u32>, to_disk: Option<bool>) -> Vec<Option<u32>> {
+    let map = PositionMap::between(&disk, &overlay);
+    lines
+        .into_iter()
+        .map(|line| {
+            let mapped = if to_disk.unwrap_or(false) {
+                map.to_disk(line as usize)
+            } else {
+                map.to_overlay(line as usize)
+            };
+            mapped.map(|line| line as u32)
+        })
+        .collect()
+}
+
+/// 按文件名得到语言与分类标记（复合扩展名 `.d.ts` / `.test.ts` / `.min.js` / `.stories.tsx`，不区分大小写）
+///
+/// 返回 PathInfo JSON：`{"language", "extension", "compoundExtension", "isDeclaration", "isTest",
+/// "isMinified", "isStory", "isGenerated"}`，无法识别语言时 `language` 为 null。
+#[napi]
+pub fn classify_path(env: Env, file_path: String) -> Result<String> {
+    to_json(env, &synapse_parser::classify_path(&file_path))
+}
+
+fn parse_graph_filter(env: Env, json: &str) -> Result<GraphFilter> {
+    serde_json::from_str(json).map_err(|e| NativeError::new(ErrorKind::InvalidInput, e.to_string()).into_napi(env))
+}
+
+fn parse_language(env: Env, language: &str) -> Result<SupportedLanguage> {
+    language
+        .parse()
+        .map_err(|e: String| NativeError::new(ErrorKind::UnsupportedLanguage, e).into_napi(env))
+}
+
+fn parse_file_role(env: Env, role: &str) -> Result<FileRole> {
+    role
+        .parse()
+        .map_err(|e: String| NativeError::new(ErrorKind::InvalidInput, e).into_napi(env))
+}
+
+fn parse_diagram_format(env: Env, format: &str) -> Result<DiagramFormat> {
+    format
+        .parse()
+        .map_err(|e: String| NativeError::new(ErrorKind::InvalidInput, e).into_napi(env))
+}
+
+// ==================== 摘要缓存 ====================
+
+/// 按实体内容哈希缓存摘要 / 向量（JSON Lines 持久化）
+#[napi]
+pub struct SummaryCache {
+    inner: RustSummaryCache,
+}
+
+#[napi]
+impl SummaryCache {
+    /// 打开缓存；不传路径时为内存缓存
+    #[napi(constructor)]
+    pub fn new(env: Env, path: Option<String>) -> Result<Self> {
+        let inner = match path {
+            Some(path) => RustSummaryCache::open(&path)
+                .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e).with_file(&path).into_napi(env))?,
+            None => RustSummaryCache::new(),
+        };
+        Ok(Self { inner })
+    }
+
+    /// 计算实体内容哈希
+    #[napi]
+    pub fn content_hash(text: String) -> String {
+        content_hash(&text)
+    }
+
+    /// 按实体文本查找摘要
+    #[napi]
+    pub fn get_summary(&self, text: String) -> Option<String> {
+        self.inner.get(&text).and_then(|entry| entry.summary.clone())
+    }
+
+    /// 按实体文本查找向量
+    #[napi]
+    pub fn get_embedding(&self, text: String) -> Option<Vec<f64>> {
+        self.inner
+            .get(&text)
+            .and_then(|entry| entry.embedding.as_ref())
+            .map(|embedding| embedding.iter().map(|&v| f64::from(v)).collect())
+    }
+
+    /// 写入摘要
+    #[napi]
+    pub fn put_summary(&mut self, text: String, summary: String) {
+        self.inner.put_summary(&text, summary);
+    }
+
+    /// 写入向量
+    #[napi]
+    pub fn put_embedding(&mut self, text: String, embedding: Vec<f64>) {
+        self.inner.put_embedding(&text, embedding.into_iter().map(|v| v as f32).collect());
+    }
+
+    /// 条目数量
+    #[napi(getter)]
+    pub fn size(&self) -> u32 {
+        self.inner.len() as u32
+    }
+
+    /// 保存到磁盘
+    #[napi]
+    pub fn save(&mut self, env: Env) -> Result<()> {
+        self.inner
+            .save()
+            .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e).into_napi(env))
+    }
+}
+
+// ==================== 流式索引 ====================
+
+/// 节流选项（后台索引时限制 CPU / IO）
+#[napi(object)]
+pub struct ThrottleOptions {
+    /// 最大工作线程数
+    pub max_threads: Option<u32>,
+    /// 类似 nice 的让步等级（0-19）
+    pub nice: Option<u32>,
+    /// 读取速率上限（字节/秒）
+    pub io_bytes_per_sec: Option<u32>,
+}
+
+impl From<ThrottleOptions> for RustThrottleOptions {
+    fn from(options: ThrottleOptions) -> Self {
+        Self {
+            max_threads: options.max_threads.map(|n| n as usize),
+            nice: options.nice.unwrap_or(0).min(19) as u8,
+            io_bytes_per_sec: options.io_bytes_per_sec.map(u64::from),
+        }
+    }
+}
+
+/// 流式索引器（带信用流控的批量解析）
+///
+/// 后台线程分批解析文件并回调 `onBatch`；JS 每处理完一个批次调用 `ack()` 归还信用，
+/// 未确认的批次数达到 `maxInFlight` 时后台线程暂停，避免慢速写入端导致 Rust 侧无限缓存。
+#[napi]
+pub struct StreamingIndexer {
+    batch_size: u32,
+    max_in_flight: u32,
+    options: RustParseOptions,
+    throttle: RustThrottleOptions,
+    gate: Option<CreditGate>,
+    /// 后台线程是否仍在运行（取消后线程可能还在处理当前批次）
+    running: Arc<AtomicBool>,
+}
+
+#[napi]
+impl StreamingIndexer {
+    /// 创建流式索引器
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_size` - 每批文件数（默认 64）
+    /// * `max_in_flight` - 允许同时未确认的批次数（默认 4）
+    #[napi(constructor)]
+    pub fn new(batch_size: Option<u32>, max_in_flight: Option<u32>) -> Self {
+        Self {
+            batch_size: batch_size.unwrap_or(64),
+            max_in_flight: max_in_flight.unwrap_or(4),
+            options: RustParseOptions::default(),
+            throttle: RustThrottleOptions::default(),
+            gate: None,
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// 设置解析选项（对之后的 start 生效）
+    #[napi]
+    pub fn set_options(&mut self, env: Env, options: ParseOptions) -> Result<()> {
+        self.options = options.into_rust(env)?;
+        Ok(())
+    }
+
+    /// 设置节流选项（对之后的 start 生效）
+    #[napi]
+    pub fn set_throttle(&mut self, throttle: ThrottleOptions) {
+        self.throttle = throttle.into();
+    }
+
+    /// 开始流式解析
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - 文件列表，每个元素为 [file_path, source_code]
+    /// * `on_batch` - 每个批次回调一次，参数为 ParseResult JSON 数组
+    /// * `on_done` - 结束时回调，参数为 StreamStats JSON
+    #[napi(
+        ts_args_type = "files: Array<[string, string]>, onBatch: (err: Error | null, results: string[]) => void, onDone: (err: Error | null, stats: string) => void"
+    )]
+    pub fn start(
+        &mut self,
+        env: Env,
+        files: Vec<Vec<String>>,
+        on_batch: JsFunction,
+        on_done: JsFunction,
+    ) -> Result<()> {
+        if self.running.load(Ordering::SeqCst) {
+            return Err(NativeError::new(ErrorKind::InvalidInput, "Streaming indexer is already running").into_napi(env));
+        }
+        if files.iter().any(|file_info| file_info.len() != 2) {
+            return Err(invalid_batch_item(env));
+        }
+
+        let on_batch: ThreadsafeFunction<Vec<String>> =
+            on_batch.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Vec<String>>| Ok(vec![ctx.value]))?;
+        let on_done: ThreadsafeFunction<String> =
+            on_done.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| Ok(vec![ctx.value]))?;
+
+        let indexer = RustStreamingIndexer::new(self.batch_size as usize, self.max_in_flight as usize)
+            .with_throttle(self.throttle.clone());
+        let gate = indexer.gate();
+        self.gate = Some(gate.clone());
+        self.running.store(true, Ordering::SeqCst);
+        let running = self.running.clone();
+        let options = self.options.clone();
+        let files: Vec<(String, String)> = files
+            .into_iter()
+            .map(|mut file_info| {
+                let source_code = file_info.pop().unwrap_or_default();
+                let file_path = file_info.pop().unwrap_or_default();
+                (file_path, source_code)
+            })
+            .collect();
+
+        std::thread::spawn(move || {
+            let mut manager = RustLanguageManager::with_options(options);
+            let stats = indexer.run(&mut manager, files, |batch| {
+                let json = batch
+                    .iter()
+                    .map(serde_json::to_string)
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(|e| Error::new(Status::GenericFailure, e.to_string()));
+                on_batch.call(json, ThreadsafeFunctionCallMode::NonBlocking);
+            });
+            // 线程结束后才允许再次 start
+            gate.close();
+            running.store(false, Ordering::SeqCst);
+
+            let stats = serde_json::to_string(&stats).map_err(|e| Error::new(Status::GenericFailure, e.to_string()));
+            on_done.call(stats, ThreadsafeFunctionCallMode::NonBlocking);
+        });
+
+        Ok(())
+    }
+
+    /// 确认已处理的批次数，归还信用
+    #[napi]
+    pub fn ack(&self, batches: Option<u32>) {
+        if let Some(gate) = &self.gate {
+            gate.ack(batches.unwrap_or(1) as usize);
+        }
+    }
+
+    /// 取消流式解析（当前批次回调后停止；`onDone` 回调之后才能再次 start）
+    #[napi]
+    pub fn cancel(&self) {
+        if let Some(gate) = &self.gate {
+            gate.close();
+        }
+    }
+
+    /// 当前可用信用
+    #[napi]
+    pub fn available_credits(&self) -> u32 {
+        self.gate.as_ref().map_or(0, |gate| gate.available() as u32)
+    }
+}
+
+// ==================== 项目扫描 ====================
+
+/// 项目扫描选项（未提供的字段使用默认值）
+#[napi(object)]
+pub struct ScanOptions {
+    /// 最大目录深度（默认 64，0 表示不限制）
+    pub max_depth: Option<u32>,
+    /// 是否跟随符号链接（默认 true）
+    pub follow_symlinks: Option<bool>,
+    /// 只解析匹配这些 glob 的文件（相对根目录）
+    pub include: Option<Vec<String>>,
+    /// 排除匹配这些 glob 的文件和目录
+    pub exclude: Option<Vec<String>>,
+    /// 遵循 `.gitignore`（默认 true）
+    pub respect_gitignore: Option<bool>,
+    /// 超过该大小（字节）的文件不解析（默认 2 MiB，0 表示不限制）
+    pub max_file_size: Option<u32>,
+    /// 为图片、二进制、文档等未解析文件生成轻量节点（默认 true）
+    pub include_assets: Option<bool>,
+    /// 把目录 README / OWNERS 摘要附加到实体的 `context`（默认 true）
+    pub directory_context: Option<bool>,
+}
+
+impl From<ScanOptions> for RustScanOptions {
+    fn from(options: ScanOptions) -> Self {
+        let defaults = RustScanOptions::default();
+        let mut walk = defaults.walk;
+        if let Some(depth) = options.max_depth {
+            walk.max_depth = (depth > 0).then_some(depth as usize);
+        }
+        walk.follow_symlinks = options.follow_symlinks.unwrap_or(walk.follow_symlinks);
+        Self {
+            walk,
+            include: options.include.unwrap_or_default(),
+            exclude: options.exclude.unwrap_or_default(),
+            respect_gitignore: options.respect_gitignore.unwrap_or(defaults.respect_gitignore),
+            max_file_size: match options.max_file_size {
+                Some(0) => None,
+                Some(size) => Some(u64::from(size)),
+                None => defaults.max_file_size,
+            },
+            include_assets: options.include_assets.unwrap_or(defaults.include_assets),
+            directory_context: options.directory_context.unwrap_or(defaults.directory_context),
+        }
+    }
+}
+
+/// 项目扫描器：在 Rust 侧遍历、过滤、读取并批量解析整个目录
+#[napi]
+pub struct ProjectScanner {
+    scan_options: RustScanOptions,
+    options: RustParseOptions,
+    throttle: RustThrottleOptions,
+    overlay: RustOverlay,
+    store: Option<RustIndexStore>,
+}
+
+#[napi]
+impl ProjectScanner {
+    /// 创建扫描器
+    #[napi(constructor)]
+    pub fn new(options: Option<ScanOptions>) -> Self {
+        Self {
+            scan_options: options.map(RustScanOptions::from).unwrap_or_default(),
+            options: RustParseOptions::default(),
+            throttle: RustThrottleOptions::default(),
+            overlay: RustOverlay::new(),
+            store: None,
+        }
+    }
+
+    /// 打开持久化存储目录：再次扫描时内容未变的文件复用上次的解析结果
+    #[napi]
+    pub fn set_store(&mut self, env: Env, path: String) -> Result<()> {
+        let store = RustIndexStore::open(&path)
+            .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e).with_file(&path).into_napi(env))?;
+        self.store = Some(store);
+        Ok(())
+    }
+
+    /// 使用编辑器中未保存的内容代替磁盘文件（路径与扫描根目录使用同一种形式）
+    #[napi]
+    pub fn set_overlay(&mut self, path: String, content: String) {
+        self.overlay.set(&path, content);
+    }
+
+    /// 移除文件的未保存内容；不传路径时全部移除
+    #[napi]
+    pub fn clear_overlay(&mut self, path: Option<String>) -> bool {
+        match path {
+            Some(path) => self.overlay.clear(&path),
+            None => {
+                let had_overlay = !self.overlay.is_empty();
+                self.overlay.clear_all();
+                had_overlay
+            }
+        }
+    }
+
+    /// 设置解析选项（未指定 projectRoot 时使用扫描根目录）
+    #[napi]
+    pub fn set_options(&mut self, env: Env, options: ParseOptions) -> Result<()> {
+        self.options = options.into_rust(env)?;
+        Ok(())
+    }
+
+    /// 设置节流选项
+    #[napi]
+    pub fn set_throttle(&mut self, throttle: ThrottleOptions) {
+        self.throttle = throttle.into();
+    }
+
+    /// 扫描根目录（在线程池中执行），返回 JSON 序列化的 ScanResult
+    #[napi]
+    pub fn scan(&self, root: String) -> AsyncTask<ScanProjectTask> {
+        let mut scanner = RustProjectScanner::new(self.scan_options.clone())
+            .with_parse_options(self.options.clone())
+            .with_throttle(self.throttle.clone())
+            .with_overlay(self.overlay.clone());
+        if let Some(store) = &self.store {
+            scanner = scanner.with_store(store.clone());
+        }
+        AsyncTask::new(ScanProjectTask::new(scanner, root))
+    }
+
+    /// 估算扫描根目录的耗时与索引大小（只遍历目录，不读取、不解析文件），返回 JSON 序列化的 IndexEstimate
+    #[napi]
+    pub fn estimate(&self, env: Env, root: String) -> Result<String> {
+        let scanner = RustProjectScanner::new(self.scan_options.clone()).with_throttle(self.throttle.clone());
+        let estimate = scanner
+            .estimate(&root)
+            .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e).with_file(&root).into_napi(env))?;
+        to_json(env, &estimate)
+    }
+}
+
+/// 项目监听选项（过滤规则同 ScanOptions）
+#[napi(object)]
+pub struct WatchOptions {
+    /// 只解析匹配这些 glob 的文件（相对根目录）
+    pub include: Option<Vec<String>>,
+    /// 排除匹配这些 glob 的文件和目录
+    pub exclude: Option<Vec<String>>,
+    /// 遵循 `.gitignore`（默认 true）
+    pub respect_gitignore: Option<bool>,
+    /// 超过该大小（字节）的文件不解析（默认 2 MiB，0 表示不限制）
+    pub max_file_size: Option<u32>,
+    /// 静默间隔（毫秒，默认 200）：最后一次变化后这么久没有新的变化才处理这一批
+    pub debounce_ms: Option<u32>,
+}
+
+impl From<WatchOptions> for RustWatchOptions {
+    fn from(options: WatchOptions) -> Self {
+        let defaults = RustWatchOptions::default();
+        Self {
+            include: options.include.unwrap_or_default(),
+            exclude: options.exclude.unwrap_or_default(),
+            respect_gitignore: options.respect_gitignore.unwrap_or(defaults.respect_gitignore),
+            max_file_size: match options.max_file_size {
+                Some(0) => None,
+                Some(size) => Some(u64::from(size)),
+                None => defaults.max_file_size,
+            },
+            debounce_ms: options.debounce_ms.map_or(defaults.debounce_ms, u64::from),
+        }
+    }
+}
+
+/// 项目监听器：监听根目录，变化的文件去抖后在 Rust 侧读取并重新解析，通过回调交出结果
+#[napi]
+pub struct ProjectWatcher {
+    watch_options: RustWatchOptions,
+    options: RustParseOptions,
+    inner: Option<RustProjectWatcher>,
+}
+
+#[napi]
+impl ProjectWatcher {
+    /// 创建监听器
+    #[napi(constructor)]
+    pub fn new(options: Option<WatchOptions>) -> Self {
+        Self {
+            watch_options: options.map(RustWatchOptions::from).unwrap_or_default(),
+            options: RustParseOptions::default(),
+            inner: None,
+        }
+    }
+
+    /// 设置解析选项（未指定 projectRoot 时使用监听的根目录；下次 start 时生效）
+    #[napi]
+    pub fn set_options(&mut self, env: Env, options: ParseOptions) -> Result<()> {
+        self.options = options.into_rust(env)?;
+        Ok(())
+    }
+
+    /// 开始监听
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - 项目根目录
+    /// * `on_change` - 每批变化回调一次，参数为 WatchEvent JSON（changed / removed / failed）
+    #[napi(ts_args_type = "root: string, onChange: (err: Error | null, event: string) => void")]
+    pub fn start(&mut self, env: Env, root: String, on_change: JsFunction) -> Result<()> {
+        if self.is_running() {
+            return Err(NativeError::new(ErrorKind::InvalidInput, "Watcher is already running").into_napi(env));
+        }
+        let on_change: ThreadsafeFunction<String> =
+            on_change.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| Ok(vec![ctx.value]))?;
+
+        let mut watcher = RustProjectWatcher::new(self.watch_options.clone()).with_parse_options(self.options.clone());
+        watcher
+            .start(&root, move |event| {
+                let json = serde_json::to_string(&event).map_err(|e| Error::new(Status::GenericFailure, e.to_string()));
+                on_change.call(json, ThreadsafeFunctionCallMode::NonBlocking);
+            })
+            .map_err(|e| NativeError::new(ErrorKind::InvalidInput, e).with_file(&root).into_napi(env))?;
+        self.inner = Some(watcher);
+        Ok(())
+    }
+
+    /// 停止监听（正在处理的一批完成后不再回调）
+    #[napi]
+    pub fn stop(&mut self) {
+        self.inner = None;
+    }
+
+    /// 是否正在监听
+    #[napi(getter)]
+    pub fn is_running(&self) -> bool {
+        self.inner.as_ref().is_some_and(RustProjectWatcher::is_running)
+    }
 }
@@ -0,0 +1,125 @@
+use napi::bindgen_prelude::*;
+use napi::Env;
+use std::sync::Arc;
+use synapse_parser::{
+    CompactStats, LanguageManager as RustLanguageManager, ParseResult, ProjectScanner as RustProjectScanner,
+    ScanResult, Tombstones,
+};
+
+use crate::errors::NativeError;
+use crate::to_json;
+
+/// 在 libuv 线程池中解析单个文件（不阻塞事件循环）
+///
+/// 任务与调用方共享同一个 LanguageManager（自定义 query、语言包一致），
+/// 多个任务并发时从其 Parser 池中各取一个 Parser。
+pub struct ParseFileTask {
+    manager: Arc<RustLanguageManager>,
+    file_path: String,
+    source_code: String,
+}
+
+impl ParseFileTask {
+    pub fn new(manager: Arc<RustLanguageManager>, file_path: String, source_code: String) -> Self {
+        Self { manager, file_path, source_code }
+    }
+}
+
+impl Task for ParseFileTask {
+    type Output = std::result::Result<ParseResult, NativeError>;
+    type JsValue = String;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let manager = &self.manager;
+        Ok(manager.parse_file_shared(&self.file_path, &self.source_code).map_err(|e| {
+            NativeError::from_reason(e)
+                .with_file(&manager.display_path(&self.file_path))
+                .with_language(manager.guess_language(&self.file_path).map(|lang| lang.to_string()))
+        }))
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        let result = output.map_err(|e| e.into_napi(env))?;
+        to_json(env, &result)
+    }
+}
+
+/// 在 libuv 线程池中批量解析（与 `parseFilesBatch` 相同：失败的文件被跳过）
+pub struct ParseFilesBatchTask {
+    manager: Arc<RustLanguageManager>,
+    files: Vec<(String, String)>,
+}
+
+impl ParseFilesBatchTask {
+    pub fn new(manager: Arc<RustLanguageManager>, files: Vec<(String, String)>) -> Self {
+        Self { manager, files }
+    }
+}
+
+impl Task for ParseFilesBatchTask {
+    type Output = std::result::Result<Vec<ParseResult>, NativeError>;
+    type JsValue = Vec<String>;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let files = std::mem::take(&mut self.files);
+        Ok(self.manager.parse_files_batch_shared(files).map_err(NativeError::from_reason))
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        let results = output.map_err(|e| e.into_napi(env))?;
+        results.iter().map(|r| to_json(env, r)).collect()
+    }
+}
+
+/// 在 libuv 线程池中扫描并解析整个项目目录
+pub struct ScanProjectTask {
+    scanner: RustProjectScanner,
+    root: String,
+}
+
+impl ScanProjectTask {
+    pub fn new(scanner: RustProjectScanner, root: String) -> Self {
+        Self { scanner, root }
+    }
+}
+
+impl Task for ScanProjectTask {
+    type Output = std::result::Result<ScanResult, NativeError>;
+    type JsValue = String;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(self
+            .scanner
+            .scan(&self.root)
+            .map_err(|e| NativeError::from_reason(e).with_file(&self.root)))
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        let result = output.map_err(|e| e.into_napi(env))?;
+        to_json(env, &result)
+    }
+}
+
+/// 在 libuv 线程池中释放索引取出的墓碑（索引本身在调用时已清空墓碑）
+pub struct CompactTask {
+    tombstones: Option<Tombstones>,
+}
+
+impl CompactTask {
+    pub fn new(tombstones: Tombstones) -> Self {
+        Self { tombstones: Some(tombstones) }
+    }
+}
+
+impl Task for CompactTask {
+    type Output = CompactStats;
+    type JsValue = String;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(self.tombstones.take().map(|tombstones| tombstones.stats()).unwrap_or_default())
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        to_json(env, &output)
+    }
+}
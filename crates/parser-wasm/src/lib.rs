@@ -0,0 +1,6 @@
+//! synapse-parser 的 WebAssembly 构建入口
+//!
+//! wasm-pack 要求 cdylib，而解析器 crate 只产出 rlib，以免每个下游构建都链接一份动态库。
+//! 绑定本身在 synapse-parser 的 `wasm` feature 中，本 crate 只负责链接。
+
+use synapse_parser as _;
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use crate::index::hover::{fence_language, first_line};
+use crate::types::{EntityInfo, ParseResult};
+
+/// 不列入代码地图的实体类型
+const SKIPPED_KINDS: &[&str] = &["comment", "import", "export"];
+
+/// 代码地图选项
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CodeMapOptions {
+    /// 一级标题
+    pub title: String,
+    /// 只列出公开 API（导出的实体）
+    pub exported_only: bool,
+    /// 在签名上方以注释形式附加文档注释的首行
+    pub include_docs: bool,
+}
+
+impl Default for CodeMapOptions {
+    fn default() -> Self {
+        Self {
+            title: "Code Map".to_string(),
+            exported_only: false,
+            include_docs: true,
+        }
+    }
+}
+
+/// 目录树节点（目录与文件按名字排序）
+#[derive(Default)]
+struct TreeNode<'a> {
+    dirs: BTreeMap<&'a str, TreeNode<'a>>,
+    files: Vec<&'a str>,
+}
+
+/// 把一批解析结果渲染为 repomix 风格的 Markdown 代码地图：文件树 + 每个文件的签名
+///
+/// 嵌套的实体（类中的方法等）按范围包含关系缩进，供 LLM 上下文打包直接使用。
+pub fn render_code_map(results: &[ParseResult], options: &CodeMapOptions) -> String {
+    let mut results: Vec<&ParseResult> = results.iter().collect();
+    results.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", options.title);
+
+    out.push_str("## File Tree\n\n```\n");
+    let mut root = TreeNode::default();
+    for result in &results {
+        let mut parts: Vec<&str> = result.file_path.split(['/', '\\']).filter(|p| !p.is_empty()).collect();
+        let Some(name) = parts.pop() else {
+            continue;
+        };
+        let mut node = &mut root;
+        for dir in parts {
+            node = node.dirs.entry(dir).or_default();
+        }
+        node.files.push(name);
+    }
+    write_tree(&root, 0, &mut out);
+    out.push_str("```\n\n## Signatures\n");
+
+    for result in results {
+        let lines = signature_lines(result, options);
+        if lines.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out, "\n### {}\n", result.file_path);
+        let _ = writeln!(out, "```{}", fence_language(&result.language));
+        for line in lines {
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out.push_str("```\n");
+    }
+    out
+}
+
+fn write_tree(node: &TreeNode, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    for (dir, child) in &node.dirs {
+        let _ = writeln!(out, "{}{}/", indent, dir);
+        write_tree(child, depth + 1, out);
+    }
+    for name in &node.files {
+        let _ = writeln!(out, "{}{}", indent, name);
+    }
+}
+
+/// 文件中列入地图的签名行（已按嵌套缩进）
+fn signature_lines(result: &ParseResult, options: &CodeMapOptions) -> Vec<String> {
+    let mut entities: Vec<(&EntityInfo, &str)> = result
+        .entity_info
+        .iter()
+        .zip(&result.entities)
+        .map(|(info, text)| (info, text.as_str()))
+        .filter(|(info, _)| !SKIPPED_KINDS.contains(&info.kind.as_str()))
+        .filter(|(info, _)| !options.exported_only || info.is_exported)
+        .collect();
+    entities.sort_by(|(a, _), (b, _)| a.range.start.cmp(&b.range.start).then(b.range.end.cmp(&a.range.end)));
+
+    let comment = comment_prefix(&result.language);
+    let mut lines = Vec::new();
+    // 仍包含当前实体的外层实体的结束行
+    let mut open: Vec<usize> = Vec::new();
+    for (info, text) in entities {
+        while open.last().is_some_and(|&end| end < info.range.start) {
+            open.pop();
+        }
+        let indent = "  ".repeat(open.len());
+        open.push(info.range.end);
+
+        if options.include_docs {
+            if let Some(doc) = info.doc.as_deref().and_then(|doc| doc.lines().map(str::trim).find(|l| !l.is_empty())) {
+                lines.push(format!("{}{} {}", indent, comment, doc));
+            }
+        }
+        let signature = info.signature.as_deref().unwrap_or_else(|| first_line(text));
+        lines.push(format!("{}{}", indent, signature));
+    }
+    lines
+}
+
+/// 文档行使用的行注释符号
+fn comment_prefix(language: &str) -> &'static str {
+    match language {
+        "Python" | "Ruby" => "#",
+        "SQL" => "--",
+        _ => "//",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LanguageManager;
+
+    #[test]
+    fn test_render_code_map() {
+        let mut manager = LanguageManager::new();
+        let results = vec![
+            manager
+                .parse_file(
+                    "src/util/math.ts",
+                    "/** Adds two numbers. */\nexport function add(a: number, b: number): number {\n  return a + b;\n}\n",
+                )
+                .unwrap(),
+            manager
+                .parse_file(
+                    "src/shape.ts",
+                    "export class Shape {\n  area(): number {\n    return 0;\n  }\n}\nfunction internal() {}\n",
+                )
+                .unwrap(),
+        ];
+
+        let map = render_code_map(&results, &CodeMapOptions::default());
+        assert!(map.starts_with("# Code Map\n\n## File Tree\n\n```\nsrc/\n  util/\n    math.ts\n  shape.ts\n```\n"));
+        assert!(map.contains("### src/util/math.ts\n\n```typescript\n// Adds two numbers.\nfunction add(a: number, b: number): number\n```\n"));
+        // 方法缩进在类之下
+        let shape = &map[map.find("### src/shape.ts").unwrap()..];
+        let class_line = shape.lines().find(|l| l.contains("class Shape")).unwrap();
+        let method_line = shape.lines().find(|l| l.contains("area()")).unwrap();
+        assert!(!class_line.starts_with(' ') && method_line.starts_with("  "));
+
+        let exported = render_code_map(
+            &results,
+            &CodeMapOptions {
+                exported_only: true,
+                include_docs: false,
+                ..Default::default()
+            },
+        );
+        assert!(!exported.contains("internal") && !exported.contains("Adds two numbers"));
+    }
+}
@@ -0,0 +1,249 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::str::FromStr;
+
+use crate::index::CodeIndex;
+
+/// 图表格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagramFormat {
+    Mermaid,
+    D2,
+}
+
+impl FromStr for DiagramFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "mermaid" | "mmd" => Ok(Self::Mermaid),
+            "d2" => Ok(Self::D2),
+            _ => Err(format!("Unknown diagram format: {}", s)),
+        }
+    }
+}
+
+/// 边的类型（决定箭头样式和标签）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EdgeKind {
+    Contains,
+    Calls,
+    Extends,
+    Implements,
+    Imports,
+}
+
+impl EdgeKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Contains => "contains",
+            Self::Calls => "calls",
+            Self::Extends => "extends",
+            Self::Implements => "implements",
+            Self::Imports => "imports",
+        }
+    }
+}
+
+/// 子图节点
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagramNode {
+    pub id: String,
+    pub label: String,
+    /// 节点类型（file / class / function ...），用于选择形状
+    pub kind: String,
+}
+
+/// 子图边
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagramEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+}
+
+/// 待渲染的子图（类继承、调用路径、模块依赖等）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subgraph {
+    pub nodes: Vec<DiagramNode>,
+    pub edges: Vec<DiagramEdge>,
+}
+
+impl Subgraph {
+    /// 创建空子图
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加节点（同 ID 只保留第一次）
+    pub fn add_node(&mut self, id: impl Into<String>, label: impl Into<String>, kind: impl Into<String>) {
+        let id = id.into();
+        if !self.nodes.iter().any(|n| n.id == id) {
+            self.nodes.push(DiagramNode { id, label: label.into(), kind: kind.into() });
+        }
+    }
+
+    /// 添加边（重复边忽略）
+    pub fn add_edge(&mut self, from: impl Into<String>, to: impl Into<String>, kind: EdgeKind) {
+        let edge = DiagramEdge { from: from.into(), to: to.into(), kind };
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+}
+
+impl CodeIndex {
+    /// 以给定文件为中心构建子图：文件、其中的实体、包含关系，以及这些文件发出的调用
+    ///
+    /// 被调用的实体即使位于其他文件也会加入子图。
+    pub fn file_subgraph<'a>(&self, paths: impl IntoIterator<Item = &'a str>) -> Subgraph {
+        let paths: HashSet<&str> = paths.into_iter().collect();
+        let mut graph = Subgraph::new();
+
+        for file in self.files().filter(|f| paths.contains(f.path.as_str())) {
+            graph.add_node(&file.path, &file.path, "file");
+            for entity in &file.entities {
+                if entity.kind == "comment" {
+                    continue;
+                }
+                graph.add_node(&entity.id, entity_label(entity.name.as_deref(), &entity.kind), &entity.kind);
+                graph.add_edge(&file.path, &entity.id, EdgeKind::Contains);
+            }
+        }
+
+        let entities: HashMap<&str, (&str, &str, Option<&str>)> = self
+            .entities()
+            .map(|e| (e.id.as_str(), (e.kind.as_str(), e.file_path.as_str(), e.name.as_deref())))
+            .collect();
+        for (from, to, _) in self.call_edges() {
+            if !paths.contains(from) {
+                continue;
+            }
+            if let Some((kind, _, name)) = entities.get(to) {
+                graph.add_node(to, entity_label(*name, kind), *kind);
+                graph.add_edge(from, to, EdgeKind::Calls);
+            }
+        }
+
+        graph
+    }
+}
+
+fn entity_label(name: Option<&str>, kind: &str) -> String {
+    match name {
+        Some(name) => format!("{} {}", kind, name),
+        None => kind.to_string(),
+    }
+}
+
+/// 将子图渲染为 Mermaid 或 D2 文本
+pub fn render_diagram(subgraph: &Subgraph, format: DiagramFormat) -> String {
+    match format {
+        DiagramFormat::Mermaid => render_mermaid(subgraph),
+        DiagramFormat::D2 => render_d2(subgraph),
+    }
+}
+
+fn render_mermaid(subgraph: &Subgraph) -> String {
+    // Mermaid 的节点 ID 不能含特殊字符，按出现顺序编号
+    let ids: HashMap<&str, String> = subgraph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.id.as_str(), format!("n{}", i)))
+        .collect();
+
+    let mut out = String::from("graph LR\n");
+    for node in &subgraph.nodes {
+        let label = node.label.replace('"', "#quot;");
+        let (open, close) = match node.kind.as_str() {
+            "file" | "module" | "namespace" | "package" => ("[/", "/]"),
+            "class" | "struct" | "interface" | "trait" | "enum" => ("[[", "]]"),
+            _ => ("(", ")"),
+        };
+        let _ = writeln!(out, "    {}{}\"{}\"{}", ids[node.id.as_str()], open, label, close);
+    }
+    for edge in &subgraph.edges {
+        let (Some(from), Some(to)) = (ids.get(edge.from.as_str()), ids.get(edge.to.as_str())) else {
+            continue;
+        };
+        let arrow = match edge.kind {
+            EdgeKind::Contains => "---",
+            EdgeKind::Extends | EdgeKind::Implements => "-.->",
+            _ => "-->",
+        };
+        let _ = writeln!(out, "    {} {}|{}| {}", from, arrow, edge.kind.label(), to);
+    }
+    out
+}
+
+fn render_d2(subgraph: &Subgraph) -> String {
+    let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+
+    let mut out = String::new();
+    for node in &subgraph.nodes {
+        let _ = writeln!(out, "{}: {}", quote(&node.id), quote(&node.label));
+        let shape = match node.kind.as_str() {
+            "file" | "module" | "namespace" | "package" => Some("page"),
+            "class" | "struct" | "interface" | "trait" | "enum" => Some("class"),
+            _ => None,
+        };
+        if let Some(shape) = shape {
+            let _ = writeln!(out, "{}.shape: {}", quote(&node.id), shape);
+        }
+    }
+    for edge in &subgraph.edges {
+        let style = match edge.kind {
+            EdgeKind::Extends | EdgeKind::Implements => " {style.stroke-dash: 3}",
+            _ => "",
+        };
+        let _ = writeln!(
+            out,
+            "{} -> {}: {}{}",
+            quote(&edge.from),
+            quote(&edge.to),
+            edge.kind.label(),
+            style
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hierarchy() -> Subgraph {
+        let mut graph = Subgraph::new();
+        graph.add_node("Animal", "class Animal", "class");
+        graph.add_node("Dog", "class \"Dog\"", "class");
+        graph.add_edge("Dog", "Animal", EdgeKind::Extends);
+        graph
+    }
+
+    #[test]
+    fn test_render_mermaid() {
+        let text = render_diagram(&hierarchy(), DiagramFormat::Mermaid);
+        assert_eq!(
+            text,
+            "graph LR\n    n0[[\"class Animal\"]]\n    n1[[\"class #quot;Dog#quot;\"]]\n    n1 -.->|extends| n0\n"
+        );
+    }
+
+    #[test]
+    fn test_render_d2() {
+        let text = render_diagram(&hierarchy(), DiagramFormat::D2);
+        assert!(text.contains("\"Dog\": \"class \\\"Dog\\\"\"\n"));
+        assert!(text.contains("\"Dog\".shape: class\n"));
+        assert!(text.contains("\"Dog\" -> \"Animal\": extends {style.stroke-dash: 3}\n"));
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("Mermaid".parse::<DiagramFormat>(), Ok(DiagramFormat::Mermaid));
+        assert!("svg".parse::<DiagramFormat>().is_err());
+    }
+}
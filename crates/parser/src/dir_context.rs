@@ -0,0 +1,250 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// README 摘要的最大字符数
+const MAX_README_CHARS: usize = 300;
+/// 读取上下文文件的最大字节数
+const MAX_READ_BYTES: u64 = 64 * 1024;
+
+/// 目录的上下文（README / OWNERS 摘要），附加到该目录及子目录中的实体
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryContext {
+    /// 相对根目录的目录路径（根目录为空字符串）
+    pub dir: String,
+    /// README 的标题与首段
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readme: Option<String>,
+    /// OWNERS 中列出的负责人
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub owners: Vec<String>,
+}
+
+/// 上下文文件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ContextFile {
+    Readme,
+    Owners,
+}
+
+/// 按文件名识别上下文文件（`README`、`README.md` 等，`OWNERS`）
+pub(crate) fn context_file_kind(file_name: &str) -> Option<ContextFile> {
+    let lower = file_name.to_ascii_lowercase();
+    let stem = lower.split_once('.').map_or(lower.as_str(), |(stem, ext)| {
+        if matches!(ext, "md" | "markdown" | "rst" | "txt") {
+            stem
+        } else {
+            ""
+        }
+    });
+    match stem {
+        "readme" => Some(ContextFile::Readme),
+        "owners" if !lower.contains('.') => Some(ContextFile::Owners),
+        _ => None,
+    }
+}
+
+/// 按目录收集的上下文，查询时取最近的祖先目录
+#[derive(Debug, Default)]
+pub(crate) struct DirectoryContexts {
+    dirs: BTreeMap<String, DirectoryContext>,
+}
+
+impl DirectoryContexts {
+    /// 读取上下文文件并记录到所在目录；读取失败或内容为空时忽略
+    pub(crate) fn add_file(&mut self, relative_path: &str, path: &Path, kind: ContextFile) {
+        let Some(text) = read_head(path) else {
+            return;
+        };
+        let dir = relative_path.rfind('/').map_or("", |i| &relative_path[..i]);
+        match kind {
+            ContextFile::Readme => {
+                let Some(summary) = readme_summary(&text) else {
+                    return;
+                };
+                let context = self.entry(dir);
+                // 同一目录有多个 README 时保留先遇到的
+                context.readme.get_or_insert(summary);
+            }
+            ContextFile::Owners => {
+                let owners = parse_owners(&text);
+                if !owners.is_empty() {
+                    self.entry(dir).owners = owners;
+                }
+            }
+        }
+    }
+
+    fn entry(&mut self, dir: &str) -> &mut DirectoryContext {
+        self.dirs.entry(dir.to_string()).or_insert_with(|| DirectoryContext {
+            dir: dir.to_string(),
+            ..Default::default()
+        })
+    }
+
+    /// 文件的上下文文本：最近的 README 摘要与最近的 OWNERS，都没有时为 None
+    pub(crate) fn context_for(&self, relative_path: &str) -> Option<String> {
+        let mut readme = None;
+        let mut owners = None;
+        let mut dir = relative_path;
+        while readme.is_none() || owners.is_none() {
+            dir = dir.rfind('/').map_or("", |i| &dir[..i]);
+            if let Some(context) = self.dirs.get(dir) {
+                readme = readme.or(context.readme.as_deref());
+                owners = owners.or((!context.owners.is_empty()).then_some(&context.owners));
+            }
+            if dir.is_empty() {
+                break;
+            }
+        }
+
+        let mut parts = Vec::new();
+        parts.extend(readme.map(str::to_string));
+        parts.extend(owners.map(|owners| format!("Owners: {}", owners.join(", "))));
+        (!parts.is_empty()).then(|| parts.join("\n"))
+    }
+
+    pub(crate) fn into_vec(self) -> Vec<DirectoryContext> {
+        self.dirs.into_values().collect()
+    }
+}
+
+fn read_head(path: &Path) -> Option<String> {
+    let mut bytes = Vec::new();
+    File::open(path).ok()?.take(MAX_READ_BYTES).read_to_end(&mut bytes).ok()?;
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// README 摘要：第一个标题与其后的第一段正文（跳过徽章、图片、HTML、代码块），空白折叠后截断
+fn readme_summary(text: &str) -> Option<String> {
+    let mut title = None;
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut in_code = false;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with("```") || line.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        if is_setext_underline(line) {
+            // 单行段落下的下划线表示它是标题
+            if title.is_none() && paragraph.len() == 1 {
+                title = paragraph.pop();
+            }
+            continue;
+        }
+        if line.is_empty() {
+            if !paragraph.is_empty() {
+                break;
+            }
+            continue;
+        }
+        if let Some(heading) = line.strip_prefix('#') {
+            if !paragraph.is_empty() {
+                break;
+            }
+            title.get_or_insert(heading.trim_start_matches('#').trim());
+            continue;
+        }
+        if line.starts_with("[![") || line.starts_with("![") || line.starts_with('<') {
+            continue;
+        }
+        paragraph.push(line);
+    }
+
+    let mut summary = title.map(str::to_string).unwrap_or_default();
+    if !paragraph.is_empty() {
+        if !summary.is_empty() {
+            summary.push_str(": ");
+        }
+        summary.push_str(&paragraph.join(" ").split_whitespace().collect::<Vec<_>>().join(" "));
+    }
+    if summary.is_empty() {
+        return None;
+    }
+    if let Some((cut, _)) = summary.char_indices().nth(MAX_README_CHARS) {
+        summary.truncate(cut);
+        summary.push('…');
+    }
+    Some(summary)
+}
+
+/// reStructuredText / Setext 标题下划线
+fn is_setext_underline(line: &str) -> bool {
+    line.len() >= 3 && line.chars().all(|c| matches!(c, '=' | '-' | '~' | '*'))
+}
+
+/// OWNERS 中的负责人（忽略注释、`set noparent`、`per-file`、`file:` 等指令）
+fn parse_owners(text: &str) -> Vec<String> {
+    let mut owners: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() || line.contains(['=', ':']) || line.starts_with("set ") || line.starts_with("per-file") {
+            continue;
+        }
+        if line == "*" || line.contains(char::is_whitespace) {
+            continue;
+        }
+        if !owners.iter().any(|owner| owner == line) {
+            owners.push(line.to_string());
+        }
+    }
+    owners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_file_kind() {
+        assert_eq!(context_file_kind("README.md"), Some(ContextFile::Readme));
+        assert_eq!(context_file_kind("readme"), Some(ContextFile::Readme));
+        assert_eq!(context_file_kind("Readme.rst"), Some(ContextFile::Readme));
+        assert_eq!(context_file_kind("OWNERS"), Some(ContextFile::Owners));
+        assert_eq!(context_file_kind("README.ts"), None);
+        assert_eq!(context_file_kind("OWNERS.md"), None);
+        assert_eq!(context_file_kind("readme_utils.py"), None);
+    }
+
+    #[test]
+    fn test_readme_summary() {
+        let text = "# Billing\n\n[![build](x.svg)](ci)\n\nHandles invoices\nand refunds.\n\n## Usage\n\nMore text.\n";
+        assert_eq!(readme_summary(text).as_deref(), Some("Billing: Handles invoices and refunds."));
+
+        let rst = "Payments\n========\n\n```\ncode\n```\nCharges cards.\n";
+        assert_eq!(readme_summary(rst).as_deref(), Some("Payments: Charges cards."));
+
+        let long = format!("# T\n\n{}\n", "word ".repeat(200));
+        let summary = readme_summary(&long).unwrap();
+        assert_eq!(summary.chars().count(), MAX_README_CHARS + 1);
+        assert!(summary.ends_with('…'));
+        assert_eq!(readme_summary("\n\n<p align=\"center\"></p>\n"), None);
+    }
+
+    #[test]
+    fn test_parse_owners() {
+        let text = "# reviewers\nset noparent\nalice@example.com\nbob@example.com  # backup\nper-file *.sql=dba@example.com\nfile:../OWNERS\nalice@example.com\n";
+        assert_eq!(parse_owners(text), vec!["alice@example.com", "bob@example.com"]);
+    }
+
+    #[test]
+    fn test_context_for_nearest_ancestor() {
+        let mut contexts = DirectoryContexts::default();
+        contexts.entry("").readme = Some("Project".to_string());
+        contexts.entry("src/billing").readme = Some("Billing".to_string());
+        contexts.entry("src").owners = vec!["alice".to_string()];
+
+        assert_eq!(contexts.context_for("src/billing/invoice.ts").as_deref(), Some("Billing\nOwners: alice"));
+        assert_eq!(contexts.context_for("src/main.ts").as_deref(), Some("Project\nOwners: alice"));
+        assert_eq!(contexts.context_for("main.ts").as_deref(), Some("Project"));
+        assert_eq!(DirectoryContexts::default().context_for("a/b.ts"), None);
+    }
+}
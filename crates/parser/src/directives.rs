@@ -0,0 +1,213 @@
+//! 文件级指令：`"use client"` / `"use strict"` 等指令序言，以及文件头的编译器 / 工具 pragma
+//!
+//! 路由与打包语义（Next.js 的客户端 / 服务端组件、Flow / JSX 运行时、Go 构建约束）取决于这些指令，
+//! 它们只在文件开头有效，因此只扫描第一行代码之前的内容（指令本身除外）。
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::types::{Directive, DirectiveKind};
+
+/// 最多扫描的文件头行数
+const DIRECTIVE_HEADER_LINES: usize = 50;
+
+/// 注释中的工具 pragma（`// @flow`、`/** @jsx h */`）
+const COMMENT_PRAGMAS: &[&str] = &[
+    "@flow",
+    "@noflow",
+    "@ts-check",
+    "@ts-nocheck",
+    "@jsx",
+    "@jsxFrag",
+    "@jsxImportSource",
+    "@jsxRuntime",
+    "@format",
+    "@noformat",
+];
+
+/// 注释中以 `名字: 值` / `名字 值` 书写的工具指令
+const COMMENT_DIRECTIVES: &[&str] = &["eslint-disable", "eslint-env", "type: ignore", "mypy:", "pylint:"];
+
+lazy_static! {
+    /// 指令序言：独占一行的 `'use xxx'` 字符串语句
+    static ref PROLOGUE: Regex = Regex::new(r#"^(['"])(use [\w ]+)['"];?$"#).unwrap();
+    /// PEP 263 源码编码声明（`# -*- coding: utf-8 -*-`、`# vim: set fileencoding=utf-8 :`）
+    static ref CODING: Regex = Regex::new(r"coding[:=]\s*([-\w.]+)").unwrap();
+}
+
+/// 提取文件开头的指令（按出现顺序）
+///
+/// 扫描到第一行普通代码为止：空行、注释、Python 模块 docstring 与指令本身之后可以继续出现指令。
+pub fn file_directives(source_code: &str) -> Vec<Directive> {
+    let mut directives = Vec::new();
+    let mut in_block_comment = false;
+    let mut in_docstring: Option<&str> = None;
+
+    for (index, line) in source_code.lines().take(DIRECTIVE_HEADER_LINES).enumerate() {
+        let line_number = index + 1;
+        let trimmed = line.trim();
+
+        if let Some(quote) = in_docstring {
+            if trimmed.contains(quote) {
+                in_docstring = None;
+            }
+            continue;
+        }
+        if in_block_comment {
+            in_block_comment = !trimmed.contains("*/");
+            directives.extend(comment_pragma(trimmed, line_number));
+            continue;
+        }
+        if trimmed.is_empty() || (index == 0 && trimmed.starts_with("#!")) {
+            continue;
+        }
+
+        if let Some(directive) = code_directive(trimmed, line_number) {
+            directives.push(directive);
+        } else if trimmed.starts_with("//") || trimmed.starts_with('#') {
+            directives.extend(comment_pragma(trimmed, line_number));
+        } else if trimmed.starts_with("/*") {
+            in_block_comment = !trimmed.contains("*/");
+            directives.extend(comment_pragma(trimmed, line_number));
+        } else if let Some(quote) = ["\"\"\"", "'''"].into_iter().find(|q| trimmed.starts_with(q)) {
+            // 单行 docstring 的开闭引号在同一行
+            if !trimmed[quote.len()..].contains(quote) {
+                in_docstring = Some(quote);
+            }
+        } else {
+            break;
+        }
+    }
+    directives
+}
+
+/// 代码形式的指令：指令序言、`#pragma`、Rust 内部属性、Python `from __future__ import`
+fn code_directive(line: &str, line_number: usize) -> Option<Directive> {
+    if let Some(captures) = PROLOGUE.captures(line) {
+        return Some(directive(&captures[2], None, DirectiveKind::Prologue, line_number));
+    }
+    if let Some(rest) = line.strip_prefix("#pragma") {
+        return Some(directive("pragma", non_empty(rest), DirectiveKind::Pragma, line_number));
+    }
+    if let Some(rest) = line.strip_prefix("#![") {
+        let attribute = rest.trim_end_matches(']');
+        let (name, value) = match attribute.split_once('(') {
+            Some((name, args)) => (name, non_empty(args.trim_end_matches(')'))),
+            None => (attribute, None),
+        };
+        return Some(directive(name.trim(), value, DirectiveKind::Pragma, line_number));
+    }
+    if let Some(features) = line.strip_prefix("from __future__ import") {
+        let features = features.trim().trim_matches(|c| c == '(' || c == ')');
+        return Some(directive("__future__", non_empty(features), DirectiveKind::Pragma, line_number));
+    }
+    None
+}
+
+/// 注释中的 pragma：Go 构建约束、编码声明、`@flow` 等工具标记
+fn comment_pragma(line: &str, line_number: usize) -> Option<Directive> {
+    // `//go:build` 中间不能有空格，先于去除注释符号判断
+    if let Some(rest) = line.strip_prefix("//go:") {
+        let (name, value) = rest.split_once(' ').unwrap_or((rest, ""));
+        return Some(directive(&format!("go:{}", name), non_empty(value), DirectiveKind::Pragma, line_number));
+    }
+
+    let body = line
+        .trim_start_matches("//")
+        .trim_start_matches("/*")
+        .trim_start_matches('#')
+        .trim_start_matches('*')
+        .trim_end_matches("*/")
+        .trim();
+    if let Some(rest) = body.strip_prefix("+build") {
+        return Some(directive("+build", non_empty(rest), DirectiveKind::Pragma, line_number));
+    }
+    if let Some(captures) = CODING.captures(body).filter(|_| line.starts_with('#')) {
+        return Some(directive("coding", Some(captures[1].to_string()), DirectiveKind::Pragma, line_number));
+    }
+    if let Some(name) = COMMENT_DIRECTIVES.iter().find(|name| body.starts_with(*name)) {
+        let name = name.trim_end_matches(':');
+        return Some(directive(name, non_empty(&body[name.len()..]), DirectiveKind::Pragma, line_number));
+    }
+
+    let (name, value) = body.split_once(char::is_whitespace).unwrap_or((body, ""));
+    COMMENT_PRAGMAS
+        .contains(&name)
+        .then(|| directive(name, non_empty(value), DirectiveKind::Pragma, line_number))
+}
+
+fn directive(name: &str, value: Option<String>, kind: DirectiveKind, line: usize) -> Directive {
+    Directive {
+        name: name.to_string(),
+        value,
+        kind,
+        line,
+    }
+}
+
+/// 去掉首尾空白与 `:` 分隔符，空串为 None
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim().trim_start_matches(':').trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(source: &str) -> Vec<(String, Option<String>)> {
+        file_directives(source)
+            .into_iter()
+            .map(|directive| (directive.name, directive.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_prologue_directives() {
+        let directives = file_directives("// Copyright\n'use client';\n\nimport React from 'react';\n'use strict';\n");
+        assert_eq!(directives.len(), 1);
+        assert_eq!(directives[0].name, "use client");
+        assert_eq!(directives[0].kind, DirectiveKind::Prologue);
+        assert_eq!(directives[0].line, 2);
+
+        assert_eq!(names("\"use server\"\nexport async function save() {}\n")[0].0, "use server");
+    }
+
+    #[test]
+    fn test_comment_pragmas() {
+        assert_eq!(
+            names("/**\n * @flow strict\n * @jsx h\n */\n// @ts-nocheck\n/* eslint-disable no-console */\nconst a = 1;\n"),
+            vec![
+                ("@flow".to_string(), Some("strict".to_string())),
+                ("@jsx".to_string(), Some("h".to_string())),
+                ("@ts-nocheck".to_string(), None),
+                ("eslint-disable".to_string(), Some("no-console".to_string())),
+            ]
+        );
+        // 代码之后的注释不算
+        assert!(names("const a = 1;\n// @flow\n").is_empty());
+    }
+
+    #[test]
+    fn test_language_pragmas() {
+        assert_eq!(
+            names("//go:build linux && amd64\n// +build linux\n\npackage main\n"),
+            vec![
+                ("go:build".to_string(), Some("linux && amd64".to_string())),
+                ("+build".to_string(), Some("linux".to_string())),
+            ]
+        );
+        assert_eq!(
+            names("#!/usr/bin/env python\n# -*- coding: utf-8 -*-\n\"\"\"Module docs.\"\"\"\nfrom __future__ import annotations\nimport os\n"),
+            vec![
+                ("coding".to_string(), Some("utf-8".to_string())),
+                ("__future__".to_string(), Some("annotations".to_string())),
+            ]
+        );
+        assert_eq!(
+            names("//! crate docs\n#![no_std]\n#![allow(dead_code)]\nuse core::fmt;\n"),
+            vec![("no_std".to_string(), None), ("allow".to_string(), Some("dead_code".to_string()))]
+        );
+        assert_eq!(names("#pragma once\n#include <stdio.h>\n"), vec![("pragma".to_string(), Some("once".to_string()))]);
+    }
+}
@@ -0,0 +1,84 @@
+use tree_sitter::Node;
+
+/// 各语法中的注释节点类型
+const COMMENT_KINDS: &[&str] = &["comment", "line_comment", "block_comment", "multiline_comment"];
+
+/// 包在定义外层的节点：注释写在它们之前（`export function`、Python 装饰器、`const f = () => {}`）
+const WRAPPER_KINDS: &[&str] = &[
+    "export_statement",
+    "ambient_declaration",
+    "decorated_definition",
+    "lexical_declaration",
+    "variable_declaration",
+];
+
+/// 注释与定义之间可以隔着的节点（Rust 属性、装饰器、注解）
+const ATTRIBUTE_KINDS: &[&str] = &["attribute_item", "decorator", "annotation", "marker_annotation"];
+
+/// 紧贴在定义之前的注释（JSDoc、Javadoc、Rust `///`、Go / Python `#` 注释等），去掉注释符号
+///
+/// 连续多条注释合并；与定义之间有空行的注释不算。
+pub(crate) fn leading_comment(def: Node, source_code: &str) -> Option<String> {
+    let mut target = def;
+    while let Some(parent) = target.parent().filter(|parent| WRAPPER_KINDS.contains(&parent.kind())) {
+        target = parent;
+    }
+
+    let mut comments = Vec::new();
+    let mut row = target.start_position().row;
+    let mut sibling = target.prev_sibling();
+    while let Some(node) = sibling {
+        // 行注释节点可能包含行尾换行，此时结束位置在下一行行首
+        let end = node.end_position();
+        let last_row = if end.column == 0 && end.row > node.start_position().row { end.row - 1 } else { end.row };
+        if last_row + 1 < row {
+            break;
+        }
+        if COMMENT_KINDS.contains(&node.kind()) {
+            comments.push(source_code[node.byte_range()].trim_end());
+        } else if !ATTRIBUTE_KINDS.contains(&node.kind()) {
+            break;
+        }
+        row = node.start_position().row;
+        sibling = node.prev_sibling();
+    }
+
+    comments.reverse();
+    let text = clean_comment(&comments.join("\n"));
+    (!text.is_empty()).then_some(text)
+}
+
+/// 去掉注释符号（`/** */`、`///`、`//`、`#`、Nim `##`、Erlang `%%`、行首 `*`）与首尾空行
+pub(crate) fn clean_comment(text: &str) -> String {
+    trim_lines(text.lines().map(|line| {
+        let line = line.trim();
+        let line = line.strip_suffix("*/").unwrap_or(line);
+        ["/**", "/*!", "/*", "///", "//!", "//", "##", "#", "%%%", "%%", "%", "*"]
+            .iter()
+            .find_map(|marker| line.strip_prefix(marker))
+            .unwrap_or(line)
+    }))
+}
+
+/// 去掉每行首尾空白与首尾空行（docstring 的缩进）
+pub(crate) fn trim_lines<'a>(lines: impl Iterator<Item = &'a str>) -> String {
+    let lines: Vec<&str> = lines.map(str::trim).collect();
+    let start = lines.iter().position(|line| !line.is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|line| !line.is_empty()).map_or(start, |i| i + 1);
+    lines[start..end].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_comment() {
+        assert_eq!(clean_comment("/**\n * Adds numbers.\n *\n * @param a first\n */"), "Adds numbers.\n\n@param a first");
+        assert_eq!(clean_comment("/// Parses input.\n/// Returns tokens."), "Parses input.\nReturns tokens.");
+        assert_eq!(clean_comment("# helper"), "helper");
+        assert_eq!(clean_comment("## Greets someone."), "Greets someone.");
+        assert_eq!(clean_comment("%% Starts the server.\n%% Links to caller."), "Starts the server.\nLinks to caller.");
+        assert_eq!(clean_comment("/* */"), "");
+    }
+}
@@ -0,0 +1,132 @@
+//! 源码解码：非 UTF-8（Latin-1 / Windows-1252、UTF-16）与混合编码文件
+//!
+//! 解码从不失败；与原始字节不一致之处以 `ParseError` 形式附加到解析结果，而不是让整个文件解析失败。
+
+use encoding_rs::{Encoding, WINDOWS_1252};
+
+use crate::types::{ParseError, Range};
+
+/// 解码后的源码
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedSource {
+    pub text: String,
+    /// 实际使用的编码（`UTF-8`、`windows-1252`、`UTF-16LE` 等）
+    pub encoding: &'static str,
+    /// 替换为 U+FFFD 的非法字节序列数
+    pub replacements: usize,
+    /// 首个非 UTF-8 字节所在的行（从 1 开始）
+    first_invalid_line: Option<usize>,
+}
+
+impl DecodedSource {
+    /// 按非 UTF-8 方式解码或发生替换时的提示
+    pub fn note(&self) -> Option<ParseError> {
+        let line = self.first_invalid_line?;
+        let message = if self.replacements > 0 {
+            format!(
+                "Replaced {} invalid UTF-8 byte sequence(s) with U+FFFD",
+                self.replacements
+            )
+        } else {
+            format!("File is not valid UTF-8; decoded as {}", self.encoding)
+        };
+        Some(ParseError {
+            message,
+            range: Some(Range { start: line, end: line }),
+        })
+    }
+}
+
+/// 解码源文件内容
+///
+/// - 带 BOM 的按 BOM 指定的编码（UTF-8 / UTF-16LE / UTF-16BE）
+/// - 合法 UTF-8 原样使用
+/// - 含合法多字节 UTF-8 字符的（混合编码）按 UTF-8 解码，非法字节替换为 U+FFFD
+/// - 其余按 Windows-1252（Latin-1 的超集）解码
+pub fn decode_source(bytes: &[u8]) -> DecodedSource {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(bytes) {
+        let body = &bytes[bom_len..];
+        let (text, had_errors) = encoding.decode_without_bom_handling(body);
+        let replacements = if had_errors { text.matches('\u{FFFD}').count() } else { 0 };
+        return DecodedSource {
+            // 替换的位置按解码后的文本定位
+            first_invalid_line: had_errors.then(|| line_of(text.as_bytes(), text.find('\u{FFFD}').unwrap_or(0))),
+            text: text.into_owned(),
+            encoding: encoding.name(),
+            replacements,
+        };
+    }
+
+    let mut invalid = 0;
+    let mut first_invalid = None;
+    let mut has_multibyte = false;
+    let mut offset = 0;
+    for chunk in bytes.utf8_chunks() {
+        has_multibyte |= !chunk.valid().is_ascii();
+        offset += chunk.valid().len();
+        if !chunk.invalid().is_empty() {
+            invalid += 1;
+            first_invalid.get_or_insert(offset);
+            offset += chunk.invalid().len();
+        }
+    }
+    let first_invalid_line = first_invalid.map(|offset| line_of(bytes, offset));
+
+    if invalid == 0 || has_multibyte {
+        return DecodedSource {
+            text: String::from_utf8_lossy(bytes).into_owned(),
+            encoding: "UTF-8",
+            replacements: invalid,
+            first_invalid_line,
+        };
+    }
+    let (text, _) = WINDOWS_1252.decode_without_bom_handling(bytes);
+    DecodedSource {
+        text: text.into_owned(),
+        encoding: WINDOWS_1252.name(),
+        replacements: 0,
+        first_invalid_line,
+    }
+}
+
+/// 没有 BOM 却含 NUL 字节的内容视为二进制（UTF-16 文本由 BOM 识别）
+pub(crate) fn looks_binary(bytes: &[u8]) -> bool {
+    Encoding::for_bom(bytes).is_none() && bytes.contains(&0)
+}
+
+fn line_of(bytes: &[u8], offset: usize) -> usize {
+    bytes[..offset].iter().filter(|&&b| b == b'\n').count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_source() {
+        let utf8 = decode_source("const s = 'héllo';\n".as_bytes());
+        assert_eq!((utf8.text.as_str(), utf8.encoding), ("const s = 'héllo';\n", "UTF-8"));
+        assert!(utf8.note().is_none());
+
+        // Windows-1252：é 为 0xE9，’ 为 0x92
+        let latin = decode_source(b"// caf\xe9\nconst s = 'it\x92s';\n");
+        assert_eq!(latin.text, "// café\nconst s = 'it’s';\n");
+        assert_eq!(latin.encoding, "windows-1252");
+        let note = latin.note().unwrap();
+        assert_eq!(note.message, "File is not valid UTF-8; decoded as windows-1252");
+        assert_eq!(note.range.unwrap().start, 1);
+
+        // 混合编码：已有合法的 UTF-8 字符时只替换非法字节
+        let mut bytes = "// héllo\nconst a = 1;\n".as_bytes().to_vec();
+        bytes.extend_from_slice(b"\xff\n");
+        let mixed = decode_source(&bytes);
+        assert_eq!(mixed.encoding, "UTF-8");
+        assert_eq!(mixed.replacements, 1);
+        assert!(mixed.text.ends_with("1;\n\u{FFFD}\n"));
+        assert_eq!(mixed.note().unwrap().range.unwrap().start, 3);
+
+        let utf16 = decode_source(b"\xff\xfea\x00=\x001\x00");
+        assert_eq!((utf16.text.as_str(), utf16.encoding), ("a=1", "UTF-16LE"));
+        assert!(!looks_binary(b"\xff\xfea\x00") && looks_binary(b"\x7fELF\x00\x00"));
+    }
+}
@@ -0,0 +1,180 @@
+//! 索引开销估算（见 `ProjectScanner::estimate`）
+//!
+//! 只遍历目录、按扩展名识别语言并累计文件大小，不读取内容，大型仓库也能很快给出结果。
+//! 耗时与索引大小按各语言每线程吞吐量的经验值推算，用于开始长时间索引之前提示用户，
+//! 只反映量级，不是精确预测。
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::ext_to_lang::guess_language;
+use crate::language::SupportedLanguage;
+use crate::scanner::{build_globs, GitignoreStack, ProjectScanner, ScanOptions};
+
+/// 每个文件的固定开销（读取、建树、结果组装），微秒
+const FILE_OVERHEAD_US: u64 = 150;
+
+/// 单个语言的估算
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageEstimate {
+    pub files: usize,
+    pub bytes: u64,
+    /// 单线程解析耗时（毫秒）
+    pub estimated_ms: u64,
+    /// 索引中的大小（字节）
+    pub estimated_index_bytes: u64,
+}
+
+/// 索引开销估算
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexEstimate {
+    /// 语言 -> 估算（只含会被解析的文件）
+    pub languages: BTreeMap<String, LanguageEstimate>,
+    /// 会被解析的文件数
+    pub files: usize,
+    /// 会被解析的源码总字节数
+    pub bytes: u64,
+    /// 超过 `max_file_size`、不会解析的文件
+    pub too_large: usize,
+    /// 按节流选项使用的线程数
+    pub threads: usize,
+    /// 预计总耗时（毫秒，已按线程数并行）
+    pub estimated_ms: u64,
+    /// 预计索引大小（字节）
+    pub estimated_index_bytes: u64,
+    /// 本次估算的耗时（毫秒）
+    pub elapsed_ms: u64,
+}
+
+/// 每线程吞吐量（字节/毫秒）与索引大小相对源码大小的百分比
+///
+/// 语法越复杂、实体越密集，解析越慢、索引越大；配置与文档文件实体稀疏。
+fn throughput(lang: SupportedLanguage) -> (u64, u64) {
+    match lang {
+        SupportedLanguage::TypeScript | SupportedLanguage::JavaScript => (1500, 150),
+        #[cfg(feature = "python")]
+        SupportedLanguage::Python => (2000, 140),
+        #[cfg(feature = "go")]
+        SupportedLanguage::Go => (2000, 140),
+        #[cfg(feature = "rust-lang")]
+        SupportedLanguage::Rust => (1200, 150),
+        #[cfg(feature = "java")]
+        SupportedLanguage::Java => (1500, 150),
+        #[cfg(feature = "cpp")]
+        SupportedLanguage::Cpp => (800, 150),
+        #[cfg(feature = "config-files")]
+        SupportedLanguage::Json | SupportedLanguage::Yaml | SupportedLanguage::Toml => (4000, 60),
+        #[cfg(feature = "markdown")]
+        SupportedLanguage::Markdown => (3000, 80),
+        #[allow(unreachable_patterns)]
+        _ => (1500, 120),
+    }
+}
+
+/// 单个文件的单线程解析耗时（微秒）
+fn file_cost_us(lang: SupportedLanguage, size: u64) -> u64 {
+    let (bytes_per_ms, _) = throughput(lang);
+    size * 1000 / bytes_per_ms + FILE_OVERHEAD_US
+}
+
+/// 用默认扫描选项估算根目录的索引开销
+pub fn estimate_index(root: impl AsRef<Path>) -> Result<IndexEstimate, String> {
+    ProjectScanner::new(ScanOptions::default()).estimate(root)
+}
+
+impl ProjectScanner {
+    /// 估算 `scan` 的耗时与索引大小：过滤规则与 `scan` 一致，但不读取、不解析文件
+    pub fn estimate(&self, root: impl AsRef<Path>) -> Result<IndexEstimate, String> {
+        let started = Instant::now();
+        let root = root.as_ref();
+        let options = self.options();
+        let include = build_globs(&options.include)?;
+        let exclude = build_globs(&options.exclude)?;
+        let mut gitignores = GitignoreStack::new(root);
+        let walked = self.walk(root, exclude.as_ref(), &mut gitignores)?;
+
+        let mut estimate = IndexEstimate::default();
+        let mut total_us = 0;
+        let mut largest_us = 0;
+        for file in walked.files {
+            if include.as_ref().is_some_and(|set| !set.is_match(&file.relative_path)) {
+                continue;
+            }
+            let Some(lang) = guess_language(&file.relative_path) else {
+                continue;
+            };
+            if options.max_file_size.is_some_and(|max| file.size > max) {
+                estimate.too_large += 1;
+                continue;
+            }
+
+            let cost_us = file_cost_us(lang, file.size);
+            let index_bytes = file.size * throughput(lang).1 / 100;
+            let language = estimate.languages.entry(lang.to_string()).or_default();
+            language.files += 1;
+            language.bytes += file.size;
+            language.estimated_ms += cost_us;
+            language.estimated_index_bytes += index_bytes;
+            estimate.files += 1;
+            estimate.bytes += file.size;
+            estimate.estimated_index_bytes += index_bytes;
+            total_us += cost_us;
+            largest_us = largest_us.max(cost_us);
+        }
+        // 各语言先按微秒累计，避免小文件逐个取整为 0
+        for language in estimate.languages.values_mut() {
+            language.estimated_ms /= 1000;
+        }
+
+        #[cfg(feature = "parallel")]
+        let threads = self.throttle().thread_count();
+        #[cfg(not(feature = "parallel"))]
+        let threads = 1;
+        estimate.threads = threads;
+        // 并行时受最大文件限制：单个文件只能由一个线程解析
+        estimate.estimated_ms = (total_us / threads as u64).max(largest_us) / 1000;
+        estimate.elapsed_ms = started.elapsed().as_millis() as u64;
+        Ok(estimate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::throttle::ThrottleOptions;
+    use crate::test_util::temp_dir;
+    use std::fs;
+
+    #[test]
+    fn test_estimate_counts_parsed_files() {
+        let root = temp_dir("estimate", "counts");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("dist")).unwrap();
+        fs::write(root.join(".gitignore"), "dist/\n").unwrap();
+        fs::write(root.join("src/app.ts"), "x".repeat(300_000)).unwrap();
+        fs::write(root.join("src/util.ts"), "export const a = 1;\n").unwrap();
+        fs::write(root.join("src/huge.ts"), "x".repeat(4 * 1024 * 1024)).unwrap();
+        fs::write(root.join("dist/bundle.js"), "x".repeat(1000)).unwrap();
+        fs::write(root.join("logo.png"), [0u8; 16]).unwrap();
+
+        let scanner = ProjectScanner::new(ScanOptions::default()).with_throttle(ThrottleOptions {
+            max_threads: Some(1),
+            ..Default::default()
+        });
+        let estimate = scanner.estimate(&root).unwrap();
+        assert_eq!(estimate.files, 2);
+        assert_eq!(estimate.too_large, 1);
+        assert_eq!(estimate.threads, 1);
+        assert_eq!(estimate.bytes, 300_000 + 20);
+        let typescript = &estimate.languages["TypeScript"];
+        assert_eq!(typescript.files, 2);
+        // 300 KB 按 1500 字节/毫秒约 200 毫秒，另加每个文件的固定开销
+        assert_eq!(typescript.estimated_ms, 200);
+        assert_eq!(estimate.estimated_ms, 200);
+        assert_eq!(estimate.estimated_index_bytes, (300_000 + 20) * 150 / 100);
+    }
+}
@@ -1,7 +1,10 @@
 use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Serialize, Serializer};
 use std::collections::HashMap;
 
 use crate::language::SupportedLanguage;
+use crate::types::{FileRole, HeaderLanguage};
 
 lazy_static! {
     /// 文件扩展名到语言的映射 (基于 repomix)
@@ -42,6 +45,11 @@ lazy_static! {
             m.insert("java", SupportedLanguage::Java);
         }
         
+        #[cfg(feature = "php")]
+        {
+            m.insert("php", SupportedLanguage::PHP);
+        }
+        
         #[cfg(feature = "c-lang")]
         {
             m.insert("c", SupportedLanguage::C);
@@ -55,6 +63,8 @@ lazy_static! {
             m.insert("cxx", SupportedLanguage::Cpp);
             m.insert("hpp", SupportedLanguage::Cpp);
             m.insert("hxx", SupportedLanguage::Cpp);
+            // 未启用 C 时 `.h` 按 C++ 解析
+            m.entry("h").or_insert(SupportedLanguage::Cpp);
         }
         
         #[cfg(feature = "swift")]
@@ -79,6 +89,52 @@ lazy_static! {
             m.insert("vue", SupportedLanguage::Vue);
         }
         
+        #[cfg(feature = "sql")]
+        {
+            m.insert("sql", SupportedLanguage::Sql);
+        }
+        
+        #[cfg(feature = "graphql")]
+        {
+            m.insert("graphql", SupportedLanguage::GraphQL);
+            m.insert("gql", SupportedLanguage::GraphQL);
+        }
+        
+        #[cfg(feature = "proto")]
+        m.insert("proto", SupportedLanguage::Protobuf);
+        
+        #[cfg(feature = "elixir")]
+        {
+            m.insert("ex", SupportedLanguage::Elixir);
+            m.insert("exs", SupportedLanguage::Elixir);
+        }
+        
+        #[cfg(feature = "erlang")]
+        {
+            m.insert("erl", SupportedLanguage::Erlang);
+            m.insert("hrl", SupportedLanguage::Erlang);
+        }
+        
+        #[cfg(feature = "zig")]
+        m.insert("zig", SupportedLanguage::Zig);
+        
+        #[cfg(feature = "nim")]
+        m.insert("nim", SupportedLanguage::Nim);
+        
+        #[cfg(feature = "config-files")]
+        {
+            m.insert("json", SupportedLanguage::Json);
+            m.insert("yaml", SupportedLanguage::Yaml);
+            m.insert("yml", SupportedLanguage::Yaml);
+            m.insert("toml", SupportedLanguage::Toml);
+        }
+        
+        #[cfg(feature = "markdown")]
+        {
+            m.insert("md", SupportedLanguage::Markdown);
+            m.insert("markdown", SupportedLanguage::Markdown);
+        }
+        
         m
     };
 }
@@ -95,6 +151,251 @@ pub fn guess_language(file_path: &str) -> Option<SupportedLanguage> {
     EXT_TO_LANG.get(ext.as_str()).copied()
 }
 
+/// 根据路径约定判断是否为测试文件
+pub fn is_test_file(file_path: &str) -> bool {
+    let normalized = file_path.replace('\\', "/").to_lowercase();
+    let file_name = normalized.rsplit('/').next().unwrap_or(&normalized);
+
+    let in_test_dir = normalized
+        .split('/')
+        .any(|segment| matches!(segment, "test" | "tests" | "__tests__" | "spec" | "specs"));
+
+    in_test_dir
+        || file_name.contains(".test.")
+        || file_name.contains(".spec.")
+        || file_name.contains(".e2e.")
+        || file_name.ends_with("_test.go")
+        || file_name.ends_with("_test.py")
+        || (file_name.starts_with("test_") && file_name.ends_with(".py"))
+        || file_name.ends_with("test.java")
+        || file_name.ends_with("tests.java")
+        || file_name.ends_with("_test.dart")
+        || file_name.ends_with("_spec.rb")
+        || file_name.ends_with("_test.rb")
+        // C# / PHP / Swift 的 `UserTest.cs` 约定区分大小写（排除 `Latest.cs`）
+        || ["Test.cs", "Tests.cs", "Test.php", "Test.swift", "Tests.swift"]
+            .iter()
+            .any(|suffix| file_path.ends_with(suffix))
+}
+
+/// 根据路径约定判断是否为生成代码（protobuf 输出、`__generated__` 目录等）
+pub fn is_generated_path(file_path: &str) -> bool {
+    let normalized = file_path.replace('\\', "/").to_lowercase();
+    let file_name = normalized.rsplit('/').next().unwrap_or(&normalized);
+
+    normalized.split('/').any(|segment| segment == "__generated__" || segment == "generated")
+        || [
+            ".pb.go", "_pb2.py", "_pb2_grpc.py", ".pb.cc", ".pb.h", "_pb.js", "_pb.d.ts", ".pb.swift", "_pb.rb",
+            ".g.dart", ".freezed.dart", ".g.cs", ".designer.cs",
+        ]
+            .iter()
+            .any(|suffix| file_name.ends_with(suffix))
+        || file_name.contains(".generated.")
+        || file_name.contains(".gen.")
+        // 包管理器的锁文件
+        || ["package-lock.json", "npm-shrinkwrap.json", "pnpm-lock.yaml"].contains(&file_name)
+}
+
+/// 文件头中表示生成代码的标记（小写比较）
+const GENERATED_MARKERS: &[&str] = &[
+    "@generated",
+    // Go：`// Code generated by protoc-gen-go. DO NOT EDIT.`
+    "do not edit",
+    // Dart build_runner：`GENERATED CODE - DO NOT MODIFY BY HAND`
+    "do not modify by hand",
+    // protoc 各语言输出
+    "generated by the protocol buffer compiler",
+    "generated by protoc",
+    // swagger-codegen / openapi-generator 横幅
+    "swagger-codegen",
+    "swagger codegen",
+    "openapi-generator",
+    "openapi generator",
+    "auto generated by openapi",
+    // C# / .NET：`<auto-generated>`
+    "<auto-generated",
+];
+
+/// 只检查文件开头的行数
+const GENERATED_HEADER_LINES: usize = 40;
+
+/// 按路径与内容判断语言：`.h` 按 `header_language`（`Auto` 时看内容）在 C / C++ 之间选择，
+/// Objective-C 头文件返回 None
+pub fn guess_language_for_source(
+    file_path: &str,
+    source_code: &str,
+    header_language: HeaderLanguage,
+) -> Option<SupportedLanguage> {
+    let is_header = std::path::Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("h"));
+    if !is_header {
+        return guess_language(file_path);
+    }
+
+    let dialect = match header_language {
+        HeaderLanguage::Auto => detect_header_language(source_code),
+        other => other,
+    };
+    // 对应语言未编译进当前构建时退回另一种
+    let c = EXT_TO_LANG.get("c").copied();
+    let cpp = EXT_TO_LANG.get("cpp").copied();
+    match dialect {
+        HeaderLanguage::Cpp => cpp.or(c),
+        HeaderLanguage::Objc => None,
+        HeaderLanguage::C | HeaderLanguage::Auto => c.or(cpp),
+    }
+}
+
+lazy_static! {
+    /// Objective-C 指令（行首）
+    static ref OBJC_MARKERS: Regex =
+        Regex::new(r"(?m)^\s*(@interface|@protocol|@implementation|@class|@property|@end\b|#import\s)").unwrap();
+    /// C 中不存在的 C++ 语法（行首关键字，注释行以 `//` / `*` 开头不会匹配）
+    static ref CPP_MARKERS: Regex = Regex::new(
+        r"(?m)^\s*(template\s*<|namespace\s+\w|class\s+\w|using\s+namespace\s|(public|private|protected)\s*:|virtual\s)|\bstd::"
+    )
+    .unwrap();
+}
+
+/// 按内容判断 `.h` 头文件的语言（返回 C / Cpp / Objc）
+pub fn detect_header_language(source_code: &str) -> HeaderLanguage {
+    if OBJC_MARKERS.is_match(source_code) {
+        HeaderLanguage::Objc
+    } else if CPP_MARKERS.is_match(source_code) {
+        HeaderLanguage::Cpp
+    } else {
+        HeaderLanguage::C
+    }
+}
+
+/// 根据文件头注释判断是否为生成代码（`@generated`、`DO NOT EDIT`、protoc 与 swagger-codegen 横幅）
+///
+/// 只看文件开头的注释行，避免代码中出现的同名字符串被误判。
+pub fn has_generated_marker(source_code: &str) -> bool {
+    source_code
+        .lines()
+        .take(GENERATED_HEADER_LINES)
+        .map(str::trim_start)
+        .filter(|line| {
+            ["//", "/*", "*", "#", "--", "<!--", "\"\"\"", "'''"]
+                .iter()
+                .any(|marker| line.starts_with(marker))
+        })
+        .any(|line| {
+            let line = line.to_lowercase();
+            GENERATED_MARKERS.iter().any(|marker| line.contains(marker))
+        })
+}
+
+/// 是否为 TypeScript 声明文件（`.d.ts` / `.d.mts` / `.d.cts`）
+pub fn is_declaration_file(file_path: &str) -> bool {
+    let lower = file_path.to_lowercase();
+    [".d.ts", ".d.mts", ".d.cts"]
+        .iter()
+        .any(|suffix| lower.ends_with(suffix))
+}
+
+/// 复合扩展名中位于基础扩展名之前的限定段（`.d.ts`、`.test.ts`、`.min.js` 等）
+const EXTENSION_QUALIFIERS: &[&str] = &["d", "test", "spec", "e2e", "min", "stories", "story"];
+
+/// 按文件名得到的语言与分类标记，下游过滤无需再解析文件名
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathInfo {
+    #[serde(serialize_with = "serialize_language")]
+    pub language: Option<SupportedLanguage>,
+    /// 小写的基础扩展名（`ts`），没有扩展名时为空
+    pub extension: String,
+    /// 小写的复合扩展名（`d.ts`、`test.tsx`、`min.js`），没有限定段时同 `extension`
+    pub compound_extension: String,
+    /// TypeScript 声明文件
+    pub is_declaration: bool,
+    pub is_test: bool,
+    /// 压缩产物（`.min.js` / `.min.css`）
+    pub is_minified: bool,
+    /// Storybook 故事文件（`.stories.tsx` / `.story.js`）
+    pub is_story: bool,
+    /// 生成代码（仅路径约定，文件头标记见 `has_generated_marker`）
+    pub is_generated: bool,
+}
+
+fn serialize_language<S: Serializer>(language: &Option<SupportedLanguage>, serializer: S) -> Result<S::Ok, S::Error> {
+    match language {
+        Some(language) => serializer.serialize_some(&language.to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// 解析文件名的复合扩展名并给出分类标记（不区分大小写）
+pub fn classify_path(file_path: &str) -> PathInfo {
+    let name = file_path.rsplit(['/', '\\']).next().unwrap_or(file_path).to_lowercase();
+    // 首段是文件名主体（隐藏文件的前导 `.` 属于主体：`.eslintrc.js` 的扩展名是 `js`）
+    let segments: Vec<&str> = name.trim_start_matches('.').split('.').skip(1).collect();
+    let extension = segments.last().copied().unwrap_or_default().to_string();
+    let qualifier = match segments.as_slice() {
+        [.., qualifier, _] if EXTENSION_QUALIFIERS.contains(qualifier) => Some(*qualifier),
+        _ => None,
+    };
+    let compound_extension = match qualifier {
+        Some(qualifier) => format!("{}.{}", qualifier, extension),
+        None => extension.clone(),
+    };
+
+    PathInfo {
+        language: guess_language(file_path),
+        is_declaration: is_declaration_file(file_path),
+        is_test: is_test_file(file_path),
+        is_minified: qualifier == Some("min"),
+        is_story: matches!(qualifier, Some("stories" | "story")),
+        is_generated: is_generated_path(file_path),
+        extension,
+        compound_extension,
+    }
+}
+
+/// 配置文件的文件名（小写）
+const CONFIG_FILE_NAMES: &[&str] = &[
+    "setup.py", "conftest.py", "noxfile.py", "settings.py", "gemfile", "podfile", "package.swift",
+    "build.gradle", "build.gradle.kts", "settings.gradle", "settings.gradle.kts", "cmakelists.txt",
+];
+
+/// 脚本文件的文件名（小写）
+const SCRIPT_FILE_NAMES: &[&str] = &["gulpfile.js", "gruntfile.js", "rakefile", "manage.py", "fabfile.py", "tasks.py"];
+
+/// 推断文件角色：先按路径约定（测试、配置、文档目录、脚本目录），再看内容（shebang 为脚本）
+pub fn file_role(file_path: &str, source_code: &str) -> FileRole {
+    let normalized = file_path.replace('\\', "/").to_lowercase();
+    let file_name = normalized.rsplit('/').next().unwrap_or(&normalized);
+    let dirs: Vec<&str> = normalized.split('/').collect();
+    let dirs = &dirs[..dirs.len() - 1];
+
+    if is_test_file(file_path) {
+        return FileRole::Test;
+    }
+    // `vite.config.ts`、`jest.setup.js`（不含扩展名时的最后一段为 config / conf / rc）
+    let stem = file_name.rsplit_once('.').map_or(file_name, |(stem, _)| stem);
+    let is_config = CONFIG_FILE_NAMES.contains(&file_name)
+        || stem.ends_with(".config")
+        || stem.ends_with(".conf")
+        || (file_name.starts_with('.') && stem.ends_with("rc"))
+        || dirs.iter().any(|dir| matches!(*dir, "config" | "configs" | ".github" | ".circleci"));
+    if is_config {
+        return FileRole::Config;
+    }
+    if dirs.iter().any(|dir| matches!(*dir, "docs" | "doc" | "documentation" | "examples" | "example")) {
+        return FileRole::Docs;
+    }
+    if SCRIPT_FILE_NAMES.contains(&file_name)
+        || dirs.iter().any(|dir| matches!(*dir, "scripts" | "script" | "bin"))
+        || source_code.starts_with("#!")
+    {
+        return FileRole::Script;
+    }
+    FileRole::Source
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,6 +418,113 @@ mod tests {
         assert_eq!(guess_language("file.py"), Some(SupportedLanguage::Python));
     }
 
+    #[test]
+    fn test_declaration_file() {
+        assert!(is_declaration_file("types/index.d.ts"));
+        assert!(is_declaration_file("lib.d.mts"));
+        assert!(!is_declaration_file("index.ts"));
+        assert!(!is_declaration_file("d.ts.js"));
+    }
+
+    #[test]
+    fn test_test_and_generated_paths() {
+        assert!(is_test_file("src/__tests__/parser.ts"));
+        assert!(is_test_file("pkg/server_test.go"));
+        assert!(is_test_file("app/user.spec.js"));
+        assert!(is_test_file("tests/test_models.py"));
+        assert!(!is_test_file("src/contest.ts"));
+        assert!(is_test_file("spec/models/user_spec.rb"));
+        assert!(is_test_file("App/UserServiceTests.cs"));
+        assert!(!is_test_file("src/Latest.cs"));
+
+        assert!(is_generated_path("api/service.pb.go"));
+        assert!(is_generated_path("src/__generated__/schema.ts"));
+        assert!(!is_generated_path("src/generator.ts"));
+        assert!(is_generated_path("web/api/user_pb.js"));
+        assert!(is_generated_path("Forms/Main.Designer.cs"));
+    }
+
+    #[test]
+    fn test_generated_markers() {
+        assert!(has_generated_marker("// Code generated by protoc-gen-go. DO NOT EDIT.\npackage api\n"));
+        assert!(has_generated_marker("# -*- coding: utf-8 -*-\n# Generated by the protocol buffer compiler.  DO NOT EDIT!\n"));
+        assert!(has_generated_marker("/**\n * @generated SignedSource<<abc>>\n */\n"));
+        assert!(has_generated_marker("// GENERATED CODE - DO NOT MODIFY BY HAND\npart of 'user.dart';\n"));
+        assert!(has_generated_marker("/*\n * NOTE: This class is auto generated by the swagger code generator program.\n * https://github.com/swagger-api/swagger-codegen.git\n */\n"));
+        assert!(has_generated_marker("//------------------------------------------------------------------------------\n// <auto-generated>\n"));
+        // 代码中的字符串不算
+        assert!(!has_generated_marker("const banner = '@generated';\nfunction f() {}\n"));
+        assert!(!has_generated_marker("// Handwritten helper\nexport function f() {}\n"));
+    }
+
+    #[test]
+    fn test_detect_header_language() {
+        let c = "#ifndef UTIL_H\n#define UTIL_H\n#ifdef __cplusplus\nextern \"C\" {\n#endif\nstruct point { int x; };\nint add(int a, int b);\n";
+        let cpp = "#pragma once\n#include <string>\n\nnamespace geo {\nclass Shape {\npublic:\n  virtual double area() const = 0;\n};\n}\n";
+        let objc = "#import <Foundation/Foundation.h>\n\n@interface Shape : NSObject\n- (double)area;\n@end\n";
+        assert_eq!(detect_header_language(c), HeaderLanguage::C);
+        assert_eq!(detect_header_language(cpp), HeaderLanguage::Cpp);
+        assert_eq!(detect_header_language(objc), HeaderLanguage::Objc);
+        assert_eq!(detect_header_language("// class Foo is declared elsewhere\nint f(void);\n"), HeaderLanguage::C);
+
+        assert_eq!(guess_language_for_source("geo.h", objc, HeaderLanguage::Auto), None);
+        assert_eq!(guess_language_for_source("file.ts", objc, HeaderLanguage::Auto), Some(SupportedLanguage::TypeScript));
+        #[cfg(all(feature = "c-lang", feature = "cpp"))]
+        {
+            assert_eq!(guess_language_for_source("geo.H", cpp, HeaderLanguage::Auto), Some(SupportedLanguage::Cpp));
+            assert_eq!(guess_language_for_source("geo.h", cpp, HeaderLanguage::C), Some(SupportedLanguage::C));
+            assert_eq!(guess_language_for_source("util.h", c, HeaderLanguage::Cpp), Some(SupportedLanguage::Cpp));
+        }
+    }
+
+    #[test]
+    fn test_classify_path() {
+        let info = classify_path("types/Index.D.TS");
+        assert_eq!(info.language, Some(SupportedLanguage::TypeScript));
+        assert_eq!((info.extension.as_str(), info.compound_extension.as_str()), ("ts", "d.ts"));
+        assert!(info.is_declaration && !info.is_test);
+
+        let story = classify_path("src/components/Button.Stories.tsx");
+        assert_eq!(story.compound_extension, "stories.tsx");
+        assert!(story.is_story && !story.is_test && !story.is_minified);
+
+        let minified = classify_path("vendor\\jquery-3.7.1.MIN.js");
+        assert_eq!(minified.language, Some(SupportedLanguage::JavaScript));
+        assert!(minified.is_minified && !minified.is_story);
+
+        assert!(classify_path("app/user.spec.js").is_test);
+        assert!(classify_path("e2e/login.e2e.ts").is_test);
+        assert_eq!(classify_path("api.version.ts").compound_extension, "ts");
+
+        let dotfile = classify_path(".eslintrc.js");
+        assert_eq!((dotfile.extension.as_str(), dotfile.compound_extension.as_str()), ("js", "js"));
+        assert!(classify_path(".gitignore").extension.is_empty());
+        let bare = classify_path("Makefile");
+        assert!(bare.language.is_none() && bare.extension.is_empty());
+        assert_eq!(
+            serde_json::to_value(classify_path("a.test.ts")).unwrap()["compoundExtension"],
+            "test.ts"
+        );
+    }
+
+    #[test]
+    fn test_file_role() {
+        assert_eq!(file_role("src/server.ts", ""), FileRole::Source);
+        assert_eq!(file_role("src/__tests__/server.ts", ""), FileRole::Test);
+        assert_eq!(file_role("web/vite.config.ts", ""), FileRole::Config);
+        assert_eq!(file_role(".eslintrc.js", ""), FileRole::Config);
+        assert_eq!(file_role("setup.py", ""), FileRole::Config);
+        assert_eq!(file_role("config/routes.rb", ""), FileRole::Config);
+        assert_eq!(file_role("docs/examples/usage.py", ""), FileRole::Docs);
+        assert_eq!(file_role("scripts\\release.ts", ""), FileRole::Script);
+        assert_eq!(file_role("tools/deploy.py", "#!/usr/bin/env python3\nimport sys\n"), FileRole::Script);
+        assert_eq!(file_role("Gulpfile.js", ""), FileRole::Script);
+        // 文件名中的 config 不算
+        assert_eq!(file_role("src/config.ts", ""), FileRole::Source);
+        assert_eq!("Docs".parse::<FileRole>(), Ok(FileRole::Docs));
+        assert!("vendor".parse::<FileRole>().is_err());
+    }
+
     #[test]
     fn test_guess_unknown() {
         assert_eq!(guess_language("file.unknown"), None);
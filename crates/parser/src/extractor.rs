@@ -1,11 +1,16 @@
 use tree_sitter::Node;
 
+use crate::doc_comment::leading_comment;
+use crate::ext_to_lang::is_declaration_file;
+use crate::strategies::TypeScriptStrategy;
 use crate::types::*;
 
 /// 代码实体提取器
 pub struct CodeEntityExtractor<'a> {
     file_path: &'a str,
     source_code: &'a str,
+    /// `.d.ts` 声明文件：所有实体均视为环境声明
+    is_declaration_file: bool,
 }
 
 impl<'a> CodeEntityExtractor<'a> {
@@ -13,42 +18,65 @@ impl<'a> CodeEntityExtractor<'a> {
         Self {
             file_path,
             source_code,
+            is_declaration_file: is_declaration_file(file_path),
         }
     }
 
     /// 提取所有代码实体
     pub fn extract(&self, node: Node, result: &mut LegacyParseResult) {
-        self.visit_node(node, result, false);
+        self.visit_node(node, result, false, self.is_declaration_file);
     }
 
     /// 递归访问节点
-    fn visit_node(&self, node: Node, result: &mut LegacyParseResult, is_exported: bool) {
+    fn visit_node(&self, node: Node, result: &mut LegacyParseResult, is_exported: bool, is_ambient: bool) {
         match node.kind() {
             "export_statement" => {
                 // 处理 export 语句
                 let mut cursor = node.walk();
                 for child in node.children(&mut cursor) {
-                    self.visit_node(child, result, true);
+                    self.visit_node(child, result, true, is_ambient);
                 }
             }
-            "function_declaration" | "method_definition" | "method_declaration" => {
-                if let Some(entity) = self.extract_function(node, is_exported) {
+            "ambient_declaration" => {
+                // declare ... 语句：内部声明均为环境声明
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    self.visit_node(child, result, is_exported, true);
+                }
+            }
+            "module" | "internal_module" => {
+                if let Some(entity) = self.extract_module(node, is_exported, is_ambient) {
+                    result.entities.push(CodeEntity::Module(entity));
+                }
+                match node.child_by_field_name("body") {
+                    Some(body) => self.visit_node(body, result, false, is_ambient),
+                    // Python 的根节点同名 `module`，没有 body 字段，直接遍历子节点
+                    None => {
+                        let mut cursor = node.walk();
+                        for child in node.children(&mut cursor) {
+                            self.visit_node(child, result, is_exported, is_ambient);
+                        }
+                    }
+                }
+            }
+            "function_declaration" | "function_signature" | "method_definition" | "method_declaration" => {
+                if let Some(entity) = self.extract_function(node, is_exported, is_ambient) {
                     result.entities.push(CodeEntity::Function(entity));
                 }
             }
-            "class_declaration" => {
-                if let Some(entity) = self.extract_class(node, is_exported) {
+            "class_declaration" | "class_definition" => {
+                if let Some(entity) = self.extract_class(node, is_exported, is_ambient) {
                     result.entities.push(CodeEntity::Class(entity));
                 }
             }
             "interface_declaration" => {
-                if let Some(entity) = self.extract_interface(node, is_exported) {
+                if let Some(entity) = self.extract_interface(node, is_exported, is_ambient) {
                     result.entities.push(CodeEntity::Interface(entity));
                 }
             }
             "lexical_declaration" => {
                 // const/let 变量声明
-                self.extract_variables(node, is_exported, result);
+                self.extract_variables(node, is_exported, is_ambient, result);
             }
             "import_statement" | "import_declaration" => {
                 if let Some(import) = self.extract_import(node) {
@@ -59,14 +87,14 @@ impl<'a> CodeEntityExtractor<'a> {
                 // 递归处理子节点
                 let mut cursor = node.walk();
                 for child in node.children(&mut cursor) {
-                    self.visit_node(child, result, is_exported);
+                    self.visit_node(child, result, is_exported, is_ambient);
                 }
             }
         }
     }
 
     /// 提取函数
-    fn extract_function(&self, node: Node, is_exported: bool) -> Option<FunctionEntity> {
+    fn extract_function(&self, node: Node, is_exported: bool, is_ambient: bool) -> Option<FunctionEntity> {
         let name = self.get_function_name(node)?;
         let range = Range {
             start: node.start_position().row + 1,
@@ -74,7 +102,12 @@ impl<'a> CodeEntityExtractor<'a> {
         };
 
         let signature = self.get_node_text(node);
-        let calls = self.extract_function_calls(node);
+        // 环境声明没有函数体，不参与调用图
+        let calls = if is_ambient {
+            Vec::new()
+        } else {
+            self.extract_function_calls(node)
+        };
         let comments = self.extract_leading_comment(node);
         let annotations = self.extract_annotations(node);
 
@@ -87,13 +120,14 @@ impl<'a> CodeEntityExtractor<'a> {
             return_type: None,      // TODO: 返回类型提取
             calls,
             is_exported,
+            is_ambient,
             comments,
             annotations,
         })
     }
 
     /// 提取类
-    fn extract_class(&self, node: Node, is_exported: bool) -> Option<ClassEntity> {
+    fn extract_class(&self, node: Node, is_exported: bool, is_ambient: bool) -> Option<ClassEntity> {
         let name = node
             .child_by_field_name("name")
             .map(|n| self.get_node_text(n))?;
@@ -107,17 +141,19 @@ impl<'a> CodeEntityExtractor<'a> {
         let extends = self.extract_class_extends(node);
         let implements = self.extract_class_implements(node);
 
-        // 提取方法
+        // 提取方法和属性
         let mut methods = Vec::new();
+        let mut properties = Vec::new();
         if let Some(body) = node.child_by_field_name("body") {
             let mut cursor = body.walk();
             for child in body.children(&mut cursor) {
-                // 支持 TypeScript (method_definition) 和 Java (method_declaration)
-                if child.kind() == "method_definition" || child.kind() == "method_declaration" {
-                    if let Some(method) = self.extract_function(child, false) {
+                // 支持 TypeScript (method_definition / method_signature) 和 Java (method_declaration)
+                if matches!(child.kind(), "method_definition" | "method_signature" | "method_declaration") {
+                    if let Some(method) = self.extract_function(child, false, is_ambient) {
                         methods.push(method);
                     }
                 }
+                self.extract_properties(child, &mut properties);
             }
         }
 
@@ -131,15 +167,145 @@ impl<'a> CodeEntityExtractor<'a> {
             extends,
             implements,
             methods,
-            properties: Vec::new(), // TODO: 属性提取
+            properties,
             is_exported,
+            is_ambient,
             comments,
             annotations,
         })
     }
 
+    /// 提取类体中一个成员声明的属性
+    ///
+    /// - TypeScript：字段声明，以及构造函数中带访问修饰符或 `readonly` 的参数属性
+    /// - Java：字段声明（一条声明可含多个变量），未写修饰符时为包级可见
+    /// - Python：类体中的赋值；`_name` 为私有约定，未标注类型或标注 `ClassVar` 的是类属性（static），
+    ///   只有类型标注的（如 dataclass 字段）是实例属性
+    fn extract_properties(&self, member: Node, properties: &mut Vec<PropertyEntity>) {
+        match member.kind() {
+            "public_field_definition" => {
+                if let Some(name) = member.child_by_field_name("name") {
+                    properties.push(self.ts_property(member, name));
+                }
+            }
+            "method_definition" if self.get_function_name(member).as_deref() == Some("constructor") => {
+                let Some(params) = member.child_by_field_name("parameters") else {
+                    return;
+                };
+                let mut cursor = params.walk();
+                for param in params.named_children(&mut cursor) {
+                    let mut modifiers = param.walk();
+                    let is_property = param
+                        .children(&mut modifiers)
+                        .any(|c| matches!(c.kind(), "accessibility_modifier" | "readonly"));
+                    if let (true, Some(name)) = (is_property, param.child_by_field_name("pattern")) {
+                        properties.push(self.ts_property(param, name));
+                    }
+                }
+            }
+            "field_declaration" => {
+                let (mut visibility, mut is_static, mut is_readonly) = (Visibility::Internal, false, false);
+                let mut cursor = member.walk();
+                if let Some(modifiers) = member.children(&mut cursor).find(|c| c.kind() == "modifiers") {
+                    let mut cursor = modifiers.walk();
+                    for modifier in modifiers.children(&mut cursor) {
+                        match modifier.kind() {
+                            "public" => visibility = Visibility::Public,
+                            "private" => visibility = Visibility::Private,
+                            "protected" => visibility = Visibility::Protected,
+                            "static" => is_static = true,
+                            "final" => is_readonly = true,
+                            _ => {}
+                        }
+                    }
+                }
+                let prop_type = member.child_by_field_name("type").map(|t| self.get_node_text(t));
+                let mut cursor = member.walk();
+                for declarator in member.children_by_field_name("declarator", &mut cursor) {
+                    if let Some(name) = declarator.child_by_field_name("name") {
+                        properties.push(PropertyEntity {
+                            name: self.get_node_text(name),
+                            range: self.node_range(declarator),
+                            prop_type: prop_type.clone(),
+                            is_static,
+                            visibility: visibility.clone(),
+                            is_readonly,
+                        });
+                    }
+                }
+            }
+            "expression_statement" => {
+                let Some(assignment) = member.named_child(0).filter(|c| c.kind() == "assignment") else {
+                    return;
+                };
+                let Some(left) = assignment.child_by_field_name("left").filter(|l| l.kind() == "identifier") else {
+                    return;
+                };
+                let name = self.get_node_text(left);
+                let prop_type = assignment.child_by_field_name("type").map(|t| self.get_node_text(t));
+                let annotated = |wrapper: &str| {
+                    prop_type.as_deref().is_some_and(|t| {
+                        let t = t.strip_prefix("typing.").unwrap_or(t);
+                        t == wrapper || t.starts_with(&format!("{}[", wrapper))
+                    })
+                };
+                let is_dunder = name.starts_with("__") && name.ends_with("__");
+                properties.push(PropertyEntity {
+                    visibility: if name.starts_with('_') && !is_dunder {
+                        Visibility::Private
+                    } else {
+                        Visibility::Public
+                    },
+                    is_static: prop_type.is_none() || annotated("ClassVar"),
+                    is_readonly: annotated("Final"),
+                    range: self.node_range(member),
+                    prop_type,
+                    name,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// TypeScript 字段或参数属性：访问修饰符（`#name` 为私有）、`static`、`readonly` 与类型标注
+    fn ts_property(&self, member: Node, name: Node) -> PropertyEntity {
+        let mut visibility = if name.kind() == "private_property_identifier" {
+            Visibility::Private
+        } else {
+            Visibility::Public
+        };
+        let (mut is_static, mut is_readonly) = (false, false);
+        let mut cursor = member.walk();
+        for child in member.children(&mut cursor) {
+            match child.kind() {
+                "accessibility_modifier" => {
+                    visibility = match self.get_node_text(child).as_str() {
+                        "private" => Visibility::Private,
+                        "protected" => Visibility::Protected,
+                        _ => Visibility::Public,
+                    }
+                }
+                "static" => is_static = true,
+                "readonly" => is_readonly = true,
+                _ => {}
+            }
+        }
+        let prop_type = member
+            .child_by_field_name("type")
+            .map(|t| self.get_node_text(t).trim_start_matches(':').trim().to_string());
+
+        PropertyEntity {
+            name: self.get_node_text(name),
+            range: self.node_range(member),
+            prop_type,
+            is_static,
+            visibility,
+            is_readonly,
+        }
+    }
+
     /// 提取接口
-    fn extract_interface(&self, node: Node, is_exported: bool) -> Option<InterfaceEntity> {
+    fn extract_interface(&self, node: Node, is_exported: bool, is_ambient: bool) -> Option<InterfaceEntity> {
         let name = node
             .child_by_field_name("name")
             .map(|n| self.get_node_text(n))?;
@@ -159,37 +325,152 @@ impl<'a> CodeEntityExtractor<'a> {
             extends,
             methods: Vec::new(), // TODO: 方法签名提取
             is_exported,
+            is_ambient,
             comments,
         })
     }
 
-    /// 提取变量
-    fn extract_variables(&self, _node: Node, _is_exported: bool, _result: &mut LegacyParseResult) {
-        // TODO: 实现变量提取
-    }
+    /// 提取模块（`declare module 'x'` / `namespace X`），只记录声明本身，不含模块体
+    fn extract_module(&self, node: Node, is_exported: bool, is_ambient: bool) -> Option<ModuleEntity> {
+        let name = node
+            .child_by_field_name("name")
+            .map(|n| self.get_node_text(n))?;
 
-    /// 提取 import 声明
-    fn extract_import(&self, node: Node) -> Option<ImportDeclaration> {
-        let source = node
-            .child_by_field_name("source")
-            .map(|n| {
-                let text = self.get_node_text(n);
-                // 移除引号
-                text.trim_matches(|c| c == '"' || c == '\'').to_string()
-            })?;
-
-        // TODO: 提取 import specifiers
-        let specifiers = Vec::new();
-        let is_type_only = false; // TODO: 检测 type import
-
-        Some(ImportDeclaration {
-            source,
-            specifiers,
+        Some(ModuleEntity {
+            name: name.trim_matches(|c| c == '"' || c == '\'').to_string(),
             file_path: self.file_path.to_string(),
-            is_type_only,
+            range: Range {
+                start: node.start_position().row + 1,
+                end: node.end_position().row + 1,
+            },
+            is_exported,
+            is_ambient,
         })
     }
 
+    /// 提取顶层 `const` / `let` 声明（一条声明可含多个变量，解构时每个绑定名各为一个变量）
+    ///
+    /// 值为箭头函数或函数表达式的变量（`const run = () => {}`）记为函数；其余变量的类型取类型标注，
+    /// 没有标注时按字面量、`new` 表达式与 `as` 断言推断。函数体内的局部变量不提取。
+    fn extract_variables(&self, node: Node, is_exported: bool, is_ambient: bool, result: &mut LegacyParseResult) {
+        if !Self::is_top_level(node) {
+            return;
+        }
+        let keyword = node.child_by_field_name("kind").map(|k| self.get_node_text(k));
+        let is_const = keyword.as_deref() == Some("const");
+        let mut cursor = node.walk();
+        for declarator in node.named_children(&mut cursor).filter(|c| c.kind() == "variable_declarator") {
+            let Some(pattern) = declarator.child_by_field_name("name") else {
+                continue;
+            };
+            let value = declarator.child_by_field_name("value");
+            if let Some(function) = value.filter(|v| matches!(v.kind(), "arrow_function" | "function_expression")) {
+                if pattern.kind() == "identifier" {
+                    result.entities.push(CodeEntity::Function(FunctionEntity {
+                        name: self.get_node_text(pattern),
+                        file_path: self.file_path.to_string(),
+                        range: self.node_range(declarator),
+                        signature: format!("{} {}", keyword.as_deref().unwrap_or("const"), self.get_node_text(declarator)),
+                        parameters: Vec::new(),
+                        return_type: None,
+                        calls: self.extract_function_calls(function),
+                        is_exported,
+                        is_ambient,
+                        comments: self.extract_leading_comment(node),
+                        annotations: Vec::new(),
+                    }));
+                    continue;
+                }
+            }
+
+            let var_type = declarator
+                .child_by_field_name("type")
+                .map(|t| self.get_node_text(t).trim_start_matches(':').trim().to_string())
+                .or_else(|| value.and_then(|v| self.infer_type(v)));
+            let mut names = Vec::new();
+            Self::binding_names(pattern, &mut names);
+            for name in names {
+                result.entities.push(CodeEntity::Variable(VariableEntity {
+                    name: self.get_node_text(name),
+                    file_path: self.file_path.to_string(),
+                    range: self.node_range(declarator),
+                    // 解构出的变量类型无法从整体的类型推断
+                    var_type: if pattern.kind() == "identifier" { var_type.clone() } else { None },
+                    is_exported,
+                    is_const,
+                    is_ambient,
+                }));
+            }
+        }
+    }
+
+    /// 是否为文件或 `namespace` / `declare module` 体中的顶层声明
+    fn is_top_level(node: Node) -> bool {
+        let mut parent = node.parent();
+        while let Some(p) = parent.filter(|p| matches!(p.kind(), "export_statement" | "ambient_declaration")) {
+            parent = p.parent();
+        }
+        match parent {
+            Some(p) if p.kind() == "program" => true,
+            Some(p) if p.kind() == "statement_block" => p
+                .parent()
+                .is_some_and(|owner| matches!(owner.kind(), "module" | "internal_module")),
+            _ => false,
+        }
+    }
+
+    /// 解构模式中绑定的变量名（`{ a, b: c, ...rest }` 为 `a`、`c`、`rest`，默认值中的标识符不算）
+    fn binding_names<'t>(pattern: Node<'t>, names: &mut Vec<Node<'t>>) {
+        match pattern.kind() {
+            "identifier" | "shorthand_property_identifier_pattern" => names.push(pattern),
+            "pair_pattern" => {
+                if let Some(value) = pattern.child_by_field_name("value") {
+                    Self::binding_names(value, names);
+                }
+            }
+            "object_assignment_pattern" | "assignment_pattern" => {
+                if let Some(left) = pattern.child_by_field_name("left") {
+                    Self::binding_names(left, names);
+                }
+            }
+            "object_pattern" | "array_pattern" | "rest_pattern" => {
+                let mut cursor = pattern.walk();
+                for child in pattern.named_children(&mut cursor) {
+                    Self::binding_names(child, names);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 由初始值推断变量类型：字面量、`new Foo<T>()` 与 `x as Foo`（`as const` 不算）
+    fn infer_type(&self, value: Node) -> Option<String> {
+        let inferred = match value.kind() {
+            "string" | "template_string" => "string",
+            "number" if self.get_node_text(value).ends_with('n') => "bigint",
+            "number" => "number",
+            "true" | "false" => "boolean",
+            "regex" => "RegExp",
+            "new_expression" => {
+                let constructor = value.child_by_field_name("constructor")?;
+                let type_arguments = value.child_by_field_name("type_arguments").map(|t| self.get_node_text(t));
+                return Some(self.get_node_text(constructor) + type_arguments.as_deref().unwrap_or(""));
+            }
+            "as_expression" => {
+                let target = value.named_child(1)?;
+                let target = self.get_node_text(target);
+                return (target != "const").then_some(target);
+            }
+            _ => return None,
+        };
+        Some(inferred.to_string())
+    }
+
+    /// 提取 import 声明（同 `TypeScriptStrategy`：具名导入、`import type` 与 `type` 修饰的具名导入）
+    fn extract_import(&self, node: Node) -> Option<ImportDeclaration> {
+        TypeScriptStrategy.import_declaration(node, self.source_code, self.file_path)
+    }
+
     /// 提取函数调用
     fn extract_function_calls(&self, node: Node) -> Vec<String> {
         let mut calls = Vec::new();
@@ -204,7 +485,7 @@ impl<'a> CodeEntityExtractor<'a> {
             if let Some(function) = node.child_by_field_name("function") {
                 let call_name = self.get_node_text(function);
                 // 只保留简单的函数名，去掉链式调用
-                let simple_name = call_name.split('.').last().unwrap_or(&call_name);
+                let simple_name = call_name.split('.').next_back().unwrap_or(&call_name);
                 calls.push(simple_name.to_string());
             }
         }
@@ -248,15 +529,22 @@ impl<'a> CodeEntityExtractor<'a> {
             .map(|n| self.get_node_text(n))
     }
 
+    /// 节点的行范围（从 1 开始）
+    fn node_range(&self, node: Node) -> Range {
+        Range {
+            start: node.start_position().row + 1,
+            end: node.end_position().row + 1,
+        }
+    }
+
     /// 获取节点文本
     fn get_node_text(&self, node: Node) -> String {
         self.source_code[node.byte_range()].to_string()
     }
 
-    /// 提取前置注释
-    fn extract_leading_comment(&self, _node: Node) -> Option<String> {
-        // TODO: 实现 JSDoc 注释提取
-        None
+    /// 提取前置注释（JSDoc / Javadoc），去掉注释符号
+    fn extract_leading_comment(&self, node: Node) -> Option<String> {
+        leading_comment(node, self.source_code)
     }
 
     /// 提取节点的注解（Java annotations / TypeScript decorators）
@@ -328,6 +616,25 @@ mod tests {
         parser.parse(code, None).unwrap()
     }
 
+    /// 解析后返回第一个类的属性
+    fn class_properties(file_path: &str, code: &str, tree: Tree) -> Vec<PropertyEntity> {
+        let mut result = LegacyParseResult {
+            entities: Vec::new(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            errors: Vec::new(),
+        };
+        CodeEntityExtractor::new(file_path, code).extract(tree.root_node(), &mut result);
+        result
+            .entities
+            .into_iter()
+            .find_map(|e| match e {
+                CodeEntity::Class(c) => Some(c.properties),
+                _ => None,
+            })
+            .unwrap()
+    }
+
     #[test]
     fn test_extract_function() {
         let code = r#"
@@ -348,4 +655,196 @@ function hello() {
         extractor.extract(root, &mut result);
         assert_eq!(result.entities.len(), 1);
     }
+
+    #[test]
+    fn test_declaration_file_entities_are_ambient() {
+        let code = r#"
+declare module 'foo' {
+    export function bar(x: number): string;
+}
+declare function greet(name: string): void;
+export class Widget {
+    render(): void;
+}
+        "#;
+        let tree = parse_code(code);
+        let extractor = CodeEntityExtractor::new("types/index.d.ts", code);
+        let mut result = LegacyParseResult {
+            entities: Vec::new(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        extractor.extract(tree.root_node(), &mut result);
+
+        let module = result.entities.iter().find_map(|e| match e {
+            CodeEntity::Module(m) => Some(m),
+            _ => None,
+        });
+        assert_eq!(module.map(|m| m.name.as_str()), Some("foo"));
+
+        let functions: Vec<_> = result
+            .entities
+            .iter()
+            .filter_map(|e| match e {
+                CodeEntity::Function(f) => Some(f),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(functions.len(), 2);
+        assert!(functions.iter().all(|f| f.is_ambient && f.calls.is_empty()));
+    }
+
+    #[test]
+    fn test_extract_type_only_imports() {
+        let code = "import type { Config } from './config';\nimport { type A, b } from './a';\n";
+        let tree = parse_code(code);
+        let mut result = LegacyParseResult {
+            entities: Vec::new(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            errors: Vec::new(),
+        };
+        CodeEntityExtractor::new("app.ts", code).extract(tree.root_node(), &mut result);
+
+        assert_eq!(result.imports.len(), 2);
+        assert!(result.imports[0].is_type_only);
+        assert_eq!(result.imports[0].specifiers, vec!["Config"]);
+        assert!(!result.imports[1].is_type_only);
+        assert_eq!(result.imports[1].specifiers, vec!["A", "b"]);
+        assert_eq!(result.imports[1].type_specifiers, vec!["A"]);
+    }
+
+    #[test]
+    fn test_extract_variables() {
+        let code = r#"
+export const MAX_RETRIES: number = 3;
+let counter = 0, label = `run`;
+const cache = new Map<string, User>();
+const { host, port: serverPort, ...rest } = config;
+/** 加载用户 */
+export const loadUser = async (id: string) => fetchUser(id);
+const format = function (value) { return String(value); };
+const mode = 'dev' as Mode;
+function main() {
+    const local = 1;
+}
+        "#;
+        let tree = parse_code(code);
+        let mut result = LegacyParseResult {
+            entities: Vec::new(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            errors: Vec::new(),
+        };
+        CodeEntityExtractor::new("app.ts", code).extract(tree.root_node(), &mut result);
+
+        let variables: Vec<_> = result
+            .entities
+            .iter()
+            .filter_map(|e| match e {
+                CodeEntity::Variable(v) => Some((v.name.as_str(), v.var_type.as_deref(), v.is_exported, v.is_const)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            variables,
+            vec![
+                ("MAX_RETRIES", Some("number"), true, true),
+                ("counter", Some("number"), false, false),
+                ("label", Some("string"), false, false),
+                ("cache", Some("Map<string, User>"), false, true),
+                ("host", None, false, true),
+                ("serverPort", None, false, true),
+                ("rest", None, false, true),
+                ("mode", Some("Mode"), false, true),
+            ]
+        );
+
+        let functions: Vec<_> = result
+            .entities
+            .iter()
+            .filter_map(|e| match e {
+                CodeEntity::Function(f) => Some(f),
+                _ => None,
+            })
+            .collect();
+        let names: Vec<_> = functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["loadUser", "format", "main"]);
+        assert!(functions[0].is_exported && !functions[1].is_exported);
+        assert_eq!(functions[0].calls, vec!["fetchUser"]);
+        assert_eq!(functions[0].comments.as_deref(), Some("加载用户"));
+    }
+
+    #[test]
+    fn test_extract_class_properties() {
+        let code = r#"
+class UserService {
+    private readonly repository: Repository;
+    static instances = 0;
+    #secret = '';
+    name?: string;
+
+    constructor(protected logger: Logger, readonly id: string, plain: number) {}
+}
+        "#;
+        let props = class_properties("test.ts", code, parse_code(code));
+        let summary: Vec<_> = props
+            .iter()
+            .map(|p| (p.name.as_str(), p.prop_type.as_deref(), p.is_static, p.is_readonly))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("repository", Some("Repository"), false, true),
+                ("instances", None, true, false),
+                ("#secret", None, false, false),
+                ("name", Some("string"), false, false),
+                ("logger", Some("Logger"), false, false),
+                ("id", Some("string"), false, true),
+            ]
+        );
+        assert!(matches!(props[0].visibility, Visibility::Private));
+        assert!(matches!(props[2].visibility, Visibility::Private));
+        assert!(matches!(props[4].visibility, Visibility::Protected));
+        assert!(matches!(props[5].visibility, Visibility::Public));
+    }
+
+    #[cfg(feature = "java")]
+    #[test]
+    fn test_extract_java_fields() {
+        let code = "class Account {\n    private static final int LIMIT = 3, MAX = 5;\n    String owner;\n    public double balance;\n}\n";
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_java::LANGUAGE.into()).unwrap();
+        let props = class_properties("Account.java", code, parser.parse(code, None).unwrap());
+        let names: Vec<_> = props.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["LIMIT", "MAX", "owner", "balance"]);
+        assert!(props[1].is_static && props[1].is_readonly && matches!(props[1].visibility, Visibility::Private));
+        assert_eq!(props[2].prop_type.as_deref(), Some("String"));
+        assert!(matches!(props[2].visibility, Visibility::Internal));
+        assert!(matches!(props[3].visibility, Visibility::Public));
+    }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_extract_python_attributes() {
+        let code = "class Config:\n    registry = {}\n    limit: ClassVar[int] = 3\n    VERSION: Final = '1'\n    name: str\n    _cache: dict = {}\n\n    def load(self):\n        pass\n";
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_python::LANGUAGE.into()).unwrap();
+        let props = class_properties("config.py", code, parser.parse(code, None).unwrap());
+        let summary: Vec<_> = props.iter().map(|p| (p.name.as_str(), p.is_static, p.is_readonly)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("registry", true, false),
+                ("limit", true, false),
+                ("VERSION", false, true),
+                ("name", false, false),
+                ("_cache", false, false),
+            ]
+        );
+        assert!(matches!(props[4].visibility, Visibility::Private));
+        assert_eq!(props[3].prop_type.as_deref(), Some("str"));
+    }
 }
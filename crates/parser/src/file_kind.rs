@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// 嗅探二进制内容时读取的字节数
+const SNIFF_BYTES: usize = 8 * 1024;
+
+/// 未解析文件的类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FileKind {
+    /// 可识别语言的源码，但未解析（如超过大小限制）
+    Source,
+    /// 其他文本文件（文档、配置、数据）
+    Text,
+    /// Jupyter notebook
+    Notebook,
+    Image,
+    /// 音频 / 视频
+    Media,
+    Font,
+    Archive,
+    /// PDF、Office 文档等
+    Document,
+    /// 其他二进制文件（可执行文件、库、数据库等）
+    Binary,
+}
+
+/// 未解析文件的轻量节点，使索引拥有完整的文件清单
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetFile {
+    pub path: String,
+    pub size: u64,
+    pub kind: FileKind,
+}
+
+/// 按扩展名识别文件类别，未知扩展名返回 None
+pub fn kind_from_extension(path: &str) -> Option<FileKind> {
+    let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let ext = name.rsplit_once('.')?.1.to_ascii_lowercase();
+    let kind = match ext.as_str() {
+        "ipynb" => FileKind::Notebook,
+        "png" | "jpg" | "jpeg" | "gif" | "bmp" | "ico" | "webp" | "tif" | "tiff" | "psd" | "heic" | "avif" => {
+            FileKind::Image
+        }
+        // SVG 是文本，但在文件清单中按图片处理
+        "svg" => FileKind::Image,
+        "mp3" | "wav" | "ogg" | "flac" | "aac" | "m4a" | "mp4" | "mov" | "avi" | "mkv" | "webm" => FileKind::Media,
+        "woff" | "woff2" | "ttf" | "otf" | "eot" => FileKind::Font,
+        "zip" | "tar" | "gz" | "tgz" | "bz2" | "xz" | "7z" | "rar" | "jar" | "war" | "whl" | "zst" => {
+            FileKind::Archive
+        }
+        "pdf" | "doc" | "docx" | "xls" | "xlsx" | "ppt" | "pptx" | "odt" => FileKind::Document,
+        "exe" | "dll" | "so" | "dylib" | "a" | "lib" | "o" | "obj" | "class" | "pyc" | "wasm" | "bin" | "dat"
+        | "db" | "sqlite" | "node" => FileKind::Binary,
+        "md" | "markdown" | "txt" | "rst" | "json" | "yaml" | "yml" | "toml" | "xml" | "html" | "htm" | "csv"
+        | "ini" | "cfg" | "lock" | "sh" | "env" => FileKind::Text,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+/// 按内容判断是否为二进制：出现 NUL 字节或不是合法 UTF-8
+pub fn is_binary_content(head: &[u8]) -> bool {
+    if head.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(head) {
+        Ok(_) => false,
+        // 截断在多字节字符中间不算
+        Err(e) => e.error_len().is_some(),
+    }
+}
+
+/// 识别文件类别：先看扩展名，未知时读取文件头嗅探
+pub fn classify_file(path: &Path) -> FileKind {
+    if let Some(kind) = kind_from_extension(&path.to_string_lossy()) {
+        return kind;
+    }
+
+    let mut head = Vec::with_capacity(SNIFF_BYTES);
+    let read = File::open(path).and_then(|file| file.take(SNIFF_BYTES as u64).read_to_end(&mut head));
+    match read {
+        Ok(_) if !is_binary_content(&head) => FileKind::Text,
+        _ => FileKind::Binary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind_from_extension() {
+        assert_eq!(kind_from_extension("assets/Logo.PNG"), Some(FileKind::Image));
+        assert_eq!(kind_from_extension("dist/app.tar.gz"), Some(FileKind::Archive));
+        assert_eq!(kind_from_extension("notes/analysis.ipynb"), Some(FileKind::Notebook));
+        assert_eq!(kind_from_extension("README.md"), Some(FileKind::Text));
+        assert_eq!(kind_from_extension("Makefile"), None);
+    }
+
+    #[test]
+    fn test_is_binary_content() {
+        assert!(!is_binary_content(b"plain text\n"));
+        assert!(!is_binary_content("中文".as_bytes()));
+        // 多字节字符被截断
+        assert!(!is_binary_content(&"中文".as_bytes()[..4]));
+        assert!(is_binary_content(b"\x7fELF\x02\x01\x01\x00"));
+        assert!(is_binary_content(&[0xff, 0xfe, 0x41]));
+    }
+}
@@ -0,0 +1,95 @@
+//! 在 Rust 侧读取源文件（见 `LanguageManager::parse_path`）
+//!
+//! 调用方只传路径，源码不经过 JS 读取、也不在 NAPI 边界上复制。启用 `mmap` 时，
+//! 不小于 `ParseOptions::mmap_threshold` 的文件以内存映射读取，省去一次整文件的读缓冲。
+
+use std::fs::{self, File};
+use std::path::Path;
+
+use crate::encoding::{decode_source, looks_binary, DecodedSource};
+
+/// 读取并解码源文件；二进制内容返回错误
+pub(crate) fn read_source(path: &Path, mmap_threshold: Option<u64>) -> Result<DecodedSource, String> {
+    let read_error = |e: std::io::Error| format!("Failed to read {}: {}", path.display(), e);
+    let file = File::open(path).map_err(read_error)?;
+    let size = file.metadata().map_err(read_error)?.len();
+
+    if mmap_threshold.is_some_and(|threshold| size > 0 && size >= threshold) {
+        decode_mapped(&file, path)
+    } else {
+        decode_bytes(&fs::read(path).map_err(read_error)?, path)
+    }
+}
+
+fn decode_bytes(bytes: &[u8], path: &Path) -> Result<DecodedSource, String> {
+    if looks_binary(bytes) {
+        return Err(format!("Binary file: {}", path.display()));
+    }
+    Ok(decode_source(bytes))
+}
+
+#[cfg(feature = "mmap")]
+fn decode_mapped(file: &File, path: &Path) -> Result<DecodedSource, String> {
+    // SAFETY: 映射只在本函数内读取，解码后立即释放；解析期间文件被截断或改写时，
+    // 最坏情况是读到不完整的内容（与读取过程中文件被修改相同）
+    let map = unsafe { memmap2::Mmap::map(file) }
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    decode_bytes(&map, path)
+}
+
+/// 未启用 `mmap` 时按普通方式读取
+#[cfg(not(feature = "mmap"))]
+fn decode_mapped(_file: &File, path: &Path) -> Result<DecodedSource, String> {
+    decode_bytes(
+        &fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?,
+        path,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use crate::{LanguageManager, ParseOptions};
+
+    #[test]
+    fn test_read_source() {
+        let dir = temp_dir("reader", "source");
+        let text = dir.join("a.ts");
+        fs::write(&text, "export const a = 1;\n").unwrap();
+        let binary = dir.join("b.ts");
+        fs::write(&binary, b"\x00\x01\x02").unwrap();
+
+        // 普通读取与内存映射读取得到相同的内容
+        assert_eq!(read_source(&text, None).unwrap().text, "export const a = 1;\n");
+        assert_eq!(read_source(&text, Some(1)).unwrap().text, "export const a = 1;\n");
+        assert!(read_source(&binary, Some(1)).unwrap_err().starts_with("Binary file"));
+        assert!(read_source(&dir.join("missing.ts"), None).unwrap_err().starts_with("Failed to read"));
+    }
+
+    #[test]
+    fn test_parse_path() {
+        let dir = temp_dir("reader", "parse-path");
+        let code = "export function load() {}\nexport class Store {}\n";
+        let file = dir.join("store.ts").to_string_lossy().into_owned();
+        fs::write(&file, code).unwrap();
+
+        let manager = LanguageManager::new();
+        let expected = manager.parse_file_shared(&file, code).unwrap();
+        assert_eq!(manager.parse_path(&file).unwrap().entities, expected.entities);
+
+        // 按阈值内存映射读取，结果相同
+        let mapped = LanguageManager::with_options(ParseOptions {
+            mmap_threshold: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(mapped.parse_path(&file).unwrap().entities, expected.entities);
+
+        // 读取失败的文件在批量解析中被跳过，单个解析时返回错误
+        let missing = dir.join("missing.ts").to_string_lossy().into_owned();
+        assert!(manager.parse_path(&missing).unwrap_err().starts_with("Failed to read"));
+        let results = manager.parse_paths(vec![missing, file.clone()]).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].file_path, expected.file_path);
+    }
+}
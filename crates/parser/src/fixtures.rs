@@ -0,0 +1,40 @@
+//! 规范样例的快照投影（`fixtures` feature，`cargo xtask gen-fixtures` 与 tests/fixtures_test.rs 共用）
+
+use serde_json::{json, Value};
+
+use crate::ext_to_lang::EXT_TO_LANG;
+use crate::language::SupportedLanguage;
+use crate::types::ParseResult;
+
+/// 快照投影：只保留稳定的字段
+pub fn fixture_snapshot(result: &ParseResult) -> Value {
+    let entities: Vec<Value> = result
+        .entities
+        .iter()
+        .zip(&result.entity_info)
+        .map(|(text, info)| {
+            json!({
+                "kind": info.kind,
+                "name": info.name,
+                "range": info.range,
+                "isExported": info.is_exported,
+                "text": text,
+            })
+        })
+        .collect();
+
+    json!({
+        "language": result.language,
+        "entities": entities,
+        "errors": result.errors,
+    })
+}
+
+/// 语言的样例文件扩展名：已启用的扩展名中最短的一个（同长度取字母序）
+pub fn fixture_extension(language: SupportedLanguage) -> Option<&'static str> {
+    EXT_TO_LANG
+        .iter()
+        .filter(|(_, lang)| **lang == language)
+        .map(|(ext, _)| *ext)
+        .min_by_key(|ext| (ext.len(), *ext))
+}
@@ -0,0 +1,3 @@
+//! 框架相关的分析：在语言策略提取实体之后运行的附加 pass，由 `ParseOptions` 中的开关启用
+
+pub(crate) mod react;
@@ -0,0 +1,300 @@
+//! React 组件识别（`ParseOptions::react_components`，TS / JS）
+//!
+//! - 函数组件：名字首字母大写、函数体中有 JSX 的函数声明，以及赋给变量的箭头函数 / 函数表达式
+//!   （含 `memo(...)`、`forwardRef(...)` 包装）
+//! - 类组件：继承 `Component` / `PureComponent`（可带 `React.` 前缀）的类
+//!
+//! props 类型依次取变量的 `FC<Props>` 标注、包装调用的类型参数（`forwardRef<Ref, Props>`）与第一个参数的类型标注。
+
+use tree_sitter::Node;
+
+use crate::strategies::get_node_text;
+use crate::types::{ComponentInfo, ComponentKind};
+
+/// 类组件的基类
+const CLASS_BASES: &[&str] = &["Component", "PureComponent", "React.Component", "React.PureComponent"];
+
+/// JSX 元素（片段 `<>` 是没有名字的 `jsx_element`）
+const JSX_KINDS: &[&str] = &["jsx_element", "jsx_self_closing_element"];
+
+/// 包装函数组件的调用
+const WRAPPERS: &[&str] = &["memo", "forwardRef", "React.memo", "React.forwardRef"];
+
+/// 变量标注中表示函数组件的类型（`React.FC<Props>`），第一个类型参数为 props
+const FUNCTION_COMPONENT_TYPES: &[&str] = &["FC", "VFC", "FunctionComponent", "ComponentType"];
+
+/// 识别出的组件
+pub(crate) struct Component<'t> {
+    /// 组件名的节点（与 query 捕获的名字节点相同，定义节点由 `definition_node` 得到）
+    pub name: Node<'t>,
+    /// 组件片段：从定义所在行的行首到函数体 / 类体之前
+    pub text: String,
+    pub info: ComponentInfo,
+}
+
+/// 按源码顺序识别文件中的组件
+pub(crate) fn components<'t>(root: Node<'t>, source_code: &str) -> Vec<Component<'t>> {
+    let mut components = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+
+        let component = match node.kind() {
+            "function_declaration" => function_component(node, node, None, source_code),
+            "variable_declarator" => variable_component(node, source_code),
+            "class_declaration" => class_component(node, source_code),
+            _ => None,
+        };
+        components.extend(component);
+    }
+    components
+}
+
+/// 函数组件：`def` 为函数声明或变量声明，`function` 为其中的函数
+fn function_component<'t>(
+    def: Node<'t>,
+    function: Node<'t>,
+    props_type: Option<String>,
+    source_code: &str,
+) -> Option<Component<'t>> {
+    let name = def.child_by_field_name("name").filter(|name| is_component_name(*name, source_code))?;
+    let body = function.child_by_field_name("body")?;
+    if descendants(body, JSX_KINDS).is_empty() {
+        return None;
+    }
+    let props_type = props_type.or_else(|| {
+        let parameter = function.child_by_field_name("parameters")?.named_child(0)?;
+        type_annotation(parameter, source_code)
+    });
+    Some(Component {
+        name,
+        text: header(def, body, source_code),
+        info: ComponentInfo {
+            kind: ComponentKind::Function,
+            props_type,
+            hooks: hooks(body, source_code),
+            children: elements(body, source_code),
+        },
+    })
+}
+
+/// 赋给变量的函数组件：`const Card = (props: CardProps) => ...`、`const Input = forwardRef<Ref, Props>(...)`
+fn variable_component<'t>(declarator: Node<'t>, source_code: &str) -> Option<Component<'t>> {
+    let mut props_type = declarator
+        .child_by_field_name("type")
+        .and_then(|annotation| annotation.named_child(0))
+        .filter(|ty| ty.kind() == "generic_type")
+        .filter(|ty| {
+            ty.child_by_field_name("name").is_some_and(|name| {
+                let name = get_node_text(name, source_code);
+                FUNCTION_COMPONENT_TYPES.contains(&name.rsplit('.').next().unwrap_or(name))
+            })
+        })
+        .and_then(|ty| type_argument(ty, 0, source_code));
+
+    // 逐层去掉 `memo(forwardRef(...))` 包装
+    let mut value = declarator.child_by_field_name("value")?;
+    while value.kind() == "call_expression" {
+        let wrapper = get_node_text(value.child_by_field_name("function")?, source_code);
+        if !WRAPPERS.contains(&wrapper) {
+            return None;
+        }
+        // `forwardRef<Ref, Props>` 的 props 是第二个类型参数
+        let index = usize::from(wrapper.ends_with("forwardRef"));
+        props_type = props_type.or_else(|| type_argument(value, index, source_code));
+        value = value.child_by_field_name("arguments")?.named_child(0)?;
+    }
+    if !matches!(value.kind(), "arrow_function" | "function_expression") {
+        return None;
+    }
+    function_component(declarator, value, props_type, source_code)
+}
+
+/// 类组件：`class Panel extends React.Component<PanelProps, State>`
+fn class_component<'t>(class: Node<'t>, source_code: &str) -> Option<Component<'t>> {
+    let name = class.child_by_field_name("name").filter(|name| is_component_name(*name, source_code))?;
+    let mut cursor = class.walk();
+    let heritage = class.children(&mut cursor).find(|child| child.kind() == "class_heritage")?;
+    // TypeScript 的基类在 `extends_clause` 中，JavaScript 直接是 `class_heritage` 的子节点
+    let mut cursor = heritage.walk();
+    let extends = heritage
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "extends_clause");
+    let (base, props_type) = match extends {
+        Some(clause) => (
+            clause.child_by_field_name("value")?,
+            clause
+                .child_by_field_name("type_arguments")
+                .and_then(|arguments| arguments.named_child(0))
+                .map(|ty| get_node_text(ty, source_code).to_string()),
+        ),
+        None => (heritage.named_child(0)?, None),
+    };
+    if !CLASS_BASES.contains(&get_node_text(base, source_code)) {
+        return None;
+    }
+    let body = class.child_by_field_name("body")?;
+    Some(Component {
+        name,
+        text: header(class, body, source_code),
+        info: ComponentInfo {
+            kind: ComponentKind::Class,
+            props_type,
+            hooks: Vec::new(),
+            children: elements(body, source_code),
+        },
+    })
+}
+
+/// 组件名首字母大写（小写开头的在 JSX 中被当作内置标签）
+fn is_component_name(name: Node, source_code: &str) -> bool {
+    matches!(name.kind(), "identifier" | "type_identifier")
+        && get_node_text(name, source_code).starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// 参数的类型标注（`{ title }: CardProps` 为 `CardProps`）
+fn type_annotation(parameter: Node, source_code: &str) -> Option<String> {
+    let annotation = parameter.child_by_field_name("type")?;
+    Some(get_node_text(annotation, source_code).trim_start_matches(':').trim().to_string())
+}
+
+/// 泛型类型或调用的第 `index` 个类型参数
+fn type_argument(node: Node, index: usize, source_code: &str) -> Option<String> {
+    let mut cursor = node.walk();
+    let arguments = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "type_arguments")?;
+    Some(get_node_text(arguments.named_child(index)?, source_code).to_string())
+}
+
+/// 定义所在行的行首到函数体 / 类体之前
+fn header(def: Node, body: Node, source_code: &str) -> String {
+    let start = source_code[..def.start_byte()].rfind('\n').map_or(0, |i| i + 1);
+    source_code[start..body.start_byte()].trim().to_string()
+}
+
+/// 调用的 hook：名字为 `use` 或以 `use` 加大写字母开头（`useState`、`React.useEffect`）
+fn hooks(body: Node, source_code: &str) -> Vec<String> {
+    let mut hooks = Vec::new();
+    for call in descendants(body, &["call_expression"]) {
+        let Some(function) = call.child_by_field_name("function") else {
+            continue;
+        };
+        let name = get_node_text(function, source_code);
+        let name = name.rsplit('.').next().unwrap_or(name);
+        let is_hook = name == "use"
+            || name
+                .strip_prefix("use")
+                .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()));
+        if is_hook && !hooks.iter().any(|hook| hook == name) {
+            hooks.push(name.to_string());
+        }
+    }
+    hooks
+}
+
+/// 渲染的 JSX 元素名（片段 `<>` 不计）
+fn elements(body: Node, source_code: &str) -> Vec<String> {
+    let mut elements = Vec::new();
+    for node in descendants(body, JSX_KINDS) {
+        let name = match node.kind() {
+            "jsx_element" => node.child_by_field_name("open_tag").and_then(|tag| tag.child_by_field_name("name")),
+            _ => node.child_by_field_name("name"),
+        };
+        let Some(name) = name else {
+            continue;
+        };
+        let name = get_node_text(name, source_code);
+        if !elements.iter().any(|element| element == name) {
+            elements.push(name.to_string());
+        }
+    }
+    elements
+}
+
+/// 节点之下（含自身）所有指定类型的节点，按源码顺序
+fn descendants<'t>(node: Node<'t>, kinds: &[&str]) -> Vec<Node<'t>> {
+    let mut found = Vec::new();
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        if kinds.contains(&node.kind()) {
+            found.push(node);
+        }
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tree_sitter::Parser;
+
+    fn parse_components(source_code: &str) -> Vec<(String, ComponentInfo)> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_typescript::LANGUAGE_TSX.into())
+            .unwrap();
+        let tree = parser.parse(source_code, None).unwrap();
+        components(tree.root_node(), source_code)
+            .into_iter()
+            .map(|component| (get_node_text(component.name, source_code).to_string(), component.info))
+            .collect()
+    }
+
+    #[test]
+    fn test_function_components() {
+        let code = r#"
+export function UserList({ users }: UserListProps) {
+  const [filter, setFilter] = useState('');
+  useEffect(() => {}, [filter]);
+  return <ul>{users.map(u => <UserCard key={u.id} user={u} />)}</ul>;
+}
+export const Header: React.FC<HeaderProps> = ({ title }) => <h1>{title}</h1>;
+const Input = forwardRef<HTMLInputElement, InputProps>((props, ref) => {
+  const theme = React.useContext(Theme);
+  return <input ref={ref} />;
+});
+const Badge = memo((props: BadgeProps) => <><span /></>);
+function formatName(user: User) { return user.name; }
+function helper() { return <div />; }
+"#;
+        let components = parse_components(code);
+        let names: Vec<_> = components.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["UserList", "Header", "Input", "Badge"]);
+
+        let user_list = &components[0].1;
+        assert_eq!(user_list.kind, ComponentKind::Function);
+        assert_eq!(user_list.props_type.as_deref(), Some("UserListProps"));
+        assert_eq!(user_list.hooks, ["useState", "useEffect"]);
+        assert_eq!(user_list.children, ["ul", "UserCard"]);
+        assert_eq!(components[1].1.props_type.as_deref(), Some("HeaderProps"));
+        assert_eq!(components[2].1.props_type.as_deref(), Some("InputProps"));
+        assert_eq!(components[2].1.hooks, ["useContext"]);
+        assert_eq!(components[3].1.props_type.as_deref(), Some("BadgeProps"));
+        assert_eq!(components[3].1.children, ["span"]);
+    }
+
+    #[test]
+    fn test_class_components() {
+        let code = r#"
+class Panel extends React.Component<PanelProps, PanelState> {
+  render() {
+    return <section><Title /></section>;
+  }
+}
+class Store extends Base {}
+"#;
+        let components = parse_components(code);
+        assert_eq!(components.len(), 1);
+        let (name, info) = &components[0];
+        assert_eq!(name, "Panel");
+        assert_eq!(info.kind, ComponentKind::Class);
+        assert_eq!(info.props_type.as_deref(), Some("PanelProps"));
+        assert_eq!(info.children, ["section", "Title"]);
+    }
+}
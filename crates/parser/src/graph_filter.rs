@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ext_to_lang::{is_generated_path, is_test_file};
+
+/// 调用 / 导入图中排除的文件
+///
+/// 被排除的文件仍然写入索引、参与检索，只是不产生也不接收图中的边，
+/// 避免测试与生成代码扭曲调用次数、中心度和影响面分析。默认不排除。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GraphFilter {
+    /// 排除测试文件与测试目录（`_test.go`、`*.spec.ts`、`tests/` 等）
+    pub exclude_tests: bool,
+    /// 排除生成代码（protobuf 输出、`*.g.dart`、`__generated__/` 等）
+    pub exclude_generated: bool,
+    /// 排除桶文件（见 `ParseResult::is_barrel`），经由桶文件的导入直接连到定义所在的文件
+    ///
+    /// 只作用于跨文件引用图（`build_symbol_graph_filtered`）；桶文件没有定义，不影响调用计数。
+    pub exclude_barrels: bool,
+}
+
+impl GraphFilter {
+    /// 同时排除测试与生成代码
+    pub fn all() -> Self {
+        Self {
+            exclude_tests: true,
+            exclude_generated: true,
+            exclude_barrels: false,
+        }
+    }
+
+    /// 文件是否被排除在图之外（只按路径约定判断）
+    pub fn excludes(&self, file_path: &str) -> bool {
+        self.excludes_file(file_path, false)
+    }
+
+    /// 同 `excludes`，`is_generated` 为文件内容检测的结果（见 `ParseResult::is_generated`）
+    pub fn excludes_file(&self, file_path: &str, is_generated: bool) -> bool {
+        (self.exclude_tests && is_test_file(file_path))
+            || (self.exclude_generated && (is_generated || is_generated_path(file_path)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_excludes() {
+        let filter = GraphFilter {
+            exclude_tests: true,
+            ..Default::default()
+        };
+        assert!(filter.excludes("pkg/server_test.go"));
+        assert!(!filter.excludes("api/service.pb.go"));
+        assert!(GraphFilter::all().excludes("lib/model.g.dart"));
+        assert!(!GraphFilter::all().excludes("src/service.ts"));
+        assert!(!GraphFilter::default().excludes("tests/test_models.py"));
+        assert!(GraphFilter::all().excludes_file("src/client.ts", true));
+        assert!(!filter.excludes_file("src/client.ts", true));
+    }
+}
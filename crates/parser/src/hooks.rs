@@ -0,0 +1,161 @@
+use crate::types::ParseResult;
+
+/// 传给实体钩子的上下文
+#[derive(Debug, Clone, Copy)]
+pub struct EntityContext<'a> {
+    /// 语言显示名（如 "TypeScript"）
+    pub language: &'a str,
+    pub file_path: &'a str,
+    /// 实体类型（function / class / comment ...）
+    pub kind: &'a str,
+    pub name: Option<&'a str>,
+    /// 紧贴在定义之前的注释（注释实体本身则为其文本）
+    pub doc: Option<&'a str>,
+    /// 实体文本
+    pub text: &'a str,
+}
+
+/// 实体后处理钩子：在结果返回（入库）前为每个实体生成摘要
+///
+/// 返回 `Some` 时写入 `EntityInfo.summary`，返回 `None` 表示跳过。
+/// 闭包 `FnMut(&EntityContext) -> Option<String>` 自动实现该 trait。
+pub trait EntityHook {
+    fn process(&mut self, entity: &EntityContext) -> Option<String>;
+}
+
+impl<F> EntityHook for F
+where
+    F: FnMut(&EntityContext) -> Option<String>,
+{
+    fn process(&mut self, entity: &EntityContext) -> Option<String> {
+        self(entity)
+    }
+}
+
+/// 对解析结果逐个实体调用钩子
+pub fn apply_entity_hook(hook: &mut dyn EntityHook, result: &mut ParseResult) {
+    for i in 0..result.entities.len() {
+        let Some(info) = result.entity_info.get(i) else {
+            break;
+        };
+
+        let text = result.entities[i].as_str();
+        let doc = if info.kind == "comment" {
+            Some(text)
+        } else if let Some(doc) = info.doc.as_deref() {
+            Some(doc)
+        } else if result.language == "Python" {
+            python_docstring(text).or_else(|| attached_doc(result, i))
+        } else {
+            attached_doc(result, i)
+        };
+
+        let context = EntityContext {
+            language: &result.language,
+            file_path: &result.file_path,
+            kind: &info.kind,
+            name: info.name.as_deref(),
+            doc,
+            text,
+        };
+        let summary = hook.process(&context);
+        result.entity_info[i].summary = summary;
+    }
+}
+
+/// 查找紧贴在第 `index` 个实体之前（结束行相邻）的注释实体
+fn attached_doc(result: &ParseResult, index: usize) -> Option<&str> {
+    let start = result.entity_info[index].range.start;
+    result
+        .entity_info
+        .iter()
+        .zip(&result.entities)
+        .filter(|(info, _)| info.kind == "comment" && info.range.end + 1 == start)
+        .map(|(_, text)| text.as_str())
+        .next_back()
+}
+
+/// Python 的文档写在定义体的第一个字符串里
+fn python_docstring(text: &str) -> Option<&str> {
+    let body = text.lines().skip(1).map(str::trim).find(|line| !line.is_empty())?;
+    let quote = ["\"\"\"", "'''"].into_iter().find(|q| body.starts_with(q))?;
+
+    let start = text.find(body)?;
+    let rest = &text[start + quote.len()..];
+    let end = rest.find(quote)?;
+    Some(rest[..end].trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EntityInfo, Range};
+
+    fn info(kind: &str, name: Option<&str>, start: usize, end: usize) -> EntityInfo {
+        EntityInfo {
+            kind: kind.to_string(),
+            name: name.map(str::to_string),
+            range: Range { start, end },
+            is_exported: false,
+            visibility: None,
+            signature: None,
+            doc: None,
+            summary: None,
+            context: None,
+            metrics: None,
+            complexity: None,
+            is_truncated: false,
+            raw_signature: None,
+            parent: None,
+            qualified_name: None,
+            constraints: Vec::new(),
+            annotations: Vec::new(),
+            component: None,
+        }
+    }
+
+    #[test]
+    fn test_hook_receives_attached_doc() {
+        let mut result = ParseResult {
+            file_path: "a.ts".to_string(),
+            language: "TypeScript".to_string(),
+            entities: vec!["/** Adds numbers */".to_string(), "function add() {}".to_string()],
+            entity_info: vec![info("comment", None, 1, 1), info("function", Some("add"), 2, 2)],
+            imports: Vec::new(),
+            exports: Vec::new(),
+            dynamic_imports: Vec::new(),
+            calls: Vec::new(),
+            element_usages: Vec::new(),
+            modifications: Vec::new(),
+            directives: Vec::new(),
+            links: Vec::new(),
+            type_relations: Vec::new(),
+            errors: Vec::new(),
+            is_declaration_file: false,
+            is_partial: false,
+            is_generated: false,
+            is_test: false,
+            is_minified: false,
+            is_story: false,
+            is_barrel: false,
+            role: Default::default(),
+            content_hash: None,
+            summary: Default::default(),
+        };
+
+        let mut hook = |entity: &EntityContext| {
+            (entity.kind == "function").then(|| format!("{}: {}", entity.name.unwrap(), entity.doc.unwrap_or("")))
+        };
+        apply_entity_hook(&mut hook, &mut result);
+
+        assert_eq!(result.entity_info[0].summary, None);
+        assert_eq!(result.entity_info[1].summary.as_deref(), Some("add: /** Adds numbers */"));
+    }
+
+    #[test]
+    fn test_python_docstring() {
+        let text = "def greet(name):\n    \"\"\"Say hello.\"\"\"\n    return name";
+        assert_eq!(python_docstring(text), Some("Say hello."));
+        assert_eq!(python_docstring("def f():\n    pass"), None);
+    }
+}
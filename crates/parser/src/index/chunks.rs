@@ -0,0 +1,259 @@
+//! 向量化用的分块 JSONL 导出
+//!
+//! 每行一个分块，可直接交给常见向量库的加载器（LangChain `JSONLoader`、LlamaIndex 等）：
+//!
+//! ```text
+//! {"id":"src/a.ts:1:function:add","text":"export function add() {...}","metadata":{"filePath":"src/a.ts","language":"TypeScript","kind":"function","name":"add","startLine":1,"endLine":3,...}}
+//! ```
+//!
+//! 超过 `max_chars` 的实体按行切成多块，ID 追加 `#序号`，`metadata` 中带 `part` / `parts`
+//! 与该块自己的行范围。
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use super::{CodeIndex, IndexedEntity};
+use crate::types::FileRole;
+
+/// 不适合单独向量化的实体类型（未指定 `kinds` 时跳过）
+const SKIPPED_KINDS: &[&str] = &["comment", "import", "export", "use", "include", "package"];
+
+/// 分块导出选项
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChunkOptions {
+    /// 只导出这些类型的实体（空为除注释、导入等之外的全部）
+    pub kinds: Vec<String>,
+    /// 只导出这些角色的文件中的实体（空为全部）
+    pub roles: Vec<FileRole>,
+    pub exclude_tests: bool,
+    pub exclude_generated: bool,
+    /// 单块最大字符数，超过时按行切分（0 为不切分）
+    pub max_chars: usize,
+}
+
+impl Default for ChunkOptions {
+    fn default() -> Self {
+        Self {
+            kinds: Vec::new(),
+            roles: Vec::new(),
+            exclude_tests: false,
+            exclude_generated: false,
+            max_chars: 8000,
+        }
+    }
+}
+
+/// 一行输出
+#[derive(Serialize)]
+struct ChunkRecord<'a> {
+    id: String,
+    text: &'a str,
+    metadata: ChunkMetadata<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChunkMetadata<'a> {
+    /// 所属实体的 ID（切分后的各块相同）
+    entity_id: &'a str,
+    file_path: &'a str,
+    language: &'a str,
+    kind: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+    start_line: usize,
+    end_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    doc: Option<&'a str>,
+    is_exported: bool,
+    caller_count: usize,
+    role: FileRole,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    tags: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parts: Option<usize>,
+}
+
+impl ChunkOptions {
+    fn includes(&self, entity: &IndexedEntity) -> bool {
+        let kind_ok = if self.kinds.is_empty() {
+            !SKIPPED_KINDS.contains(&entity.kind.as_str())
+        } else {
+            self.kinds.contains(&entity.kind)
+        };
+        kind_ok
+            && (self.roles.is_empty() || self.roles.contains(&entity.rank_hints.role))
+            && !(self.exclude_tests && entity.rank_hints.is_test)
+            && !(self.exclude_generated && entity.rank_hints.is_generated)
+            && !entity.text.trim().is_empty()
+    }
+}
+
+impl CodeIndex {
+    /// 导出为分块 JSONL 文件，返回写出的分块数
+    pub fn export_chunks_jsonl(&self, path: impl AsRef<Path>, options: &ChunkOptions) -> Result<usize, String> {
+        let path = path.as_ref();
+        let file = fs::File::create(path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        let mut writer = BufWriter::new(file);
+        let count = self.write_chunks_jsonl(&mut writer, options)?;
+        writer.flush().map_err(|e| e.to_string())?;
+        Ok(count)
+    }
+
+    /// 以 JSONL 写出分块（按文件路径、文件内顺序），返回写出的分块数
+    pub fn write_chunks_jsonl(&self, writer: &mut impl Write, options: &ChunkOptions) -> Result<usize, String> {
+        let mut count = 0;
+        for entity in self.entities().filter(|entity| options.includes(entity)) {
+            let pieces = split_lines(&entity.text, options.max_chars);
+            let parts = pieces.len();
+            for (i, (text, first_line, last_line)) in pieces.into_iter().enumerate() {
+                let split = parts > 1;
+                let record = ChunkRecord {
+                    id: if split { format!("{}#{}", entity.id, i + 1) } else { entity.id.clone() },
+                    text,
+                    metadata: ChunkMetadata {
+                        entity_id: &entity.id,
+                        file_path: &entity.file_path,
+                        language: &entity.language,
+                        kind: &entity.kind,
+                        name: entity.name.as_deref(),
+                        start_line: entity.range.start + first_line,
+                        end_line: if split { entity.range.start + last_line } else { entity.range.end },
+                        signature: entity.signature.as_deref(),
+                        doc: entity.doc.as_deref(),
+                        is_exported: entity.rank_hints.is_exported,
+                        caller_count: entity.rank_hints.caller_count,
+                        role: entity.rank_hints.role,
+                        tags: &entity.tags,
+                        part: split.then_some(i + 1),
+                        parts: split.then_some(parts),
+                    },
+                };
+                serde_json::to_writer(&mut *writer, &record).map_err(|e| e.to_string())?;
+                writer.write_all(b"\n").map_err(|e| e.to_string())?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+}
+
+/// 按行把文本切成不超过 `max_chars` 个字符的块：(文本, 首行偏移, 末行偏移)，偏移从 0 开始
+///
+/// 单行超长时在字符边界处硬切。
+fn split_lines(text: &str, max_chars: usize) -> Vec<(&str, usize, usize)> {
+    if max_chars == 0 || text.chars().count() <= max_chars {
+        return vec![(text, 0, text.lines().count().saturating_sub(1))];
+    }
+
+    let mut pieces = Vec::new();
+    // 当前块的起始字节、首行、字符数；offset 为下一行的起始字节
+    let (mut start, mut first_line, mut chars) = (0, 0, 0);
+    let mut offset = 0;
+    for (line_no, line) in text.split_inclusive('\n').enumerate() {
+        let line_start = offset;
+        offset += line.len();
+        let line_chars = line.chars().count();
+
+        if chars > 0 && chars + line_chars > max_chars {
+            pieces.push((&text[start..line_start], first_line, line_no - 1));
+            (start, first_line, chars) = (line_start, line_no, 0);
+        }
+        if line_chars > max_chars {
+            // 此时当前块从本行开始；切出整段，不足 max_chars 的剩余部分留在当前块
+            for (cut, _) in line.char_indices().step_by(max_chars).skip(1) {
+                pieces.push((&text[start..line_start + cut], line_no, line_no));
+                start = line_start + cut;
+            }
+            first_line = line_no;
+            chars = line_chars - (line_chars - 1) / max_chars * max_chars;
+        } else {
+            chars += line_chars;
+        }
+    }
+    if start < text.len() {
+        pieces.push((&text[start..], first_line, text.lines().count().saturating_sub(1)));
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LanguageManager;
+    use serde_json::Value;
+
+    #[test]
+    fn test_write_chunks_jsonl() {
+        let mut manager = LanguageManager::new();
+        let mut index = CodeIndex::new();
+        index.insert(
+            manager
+                .parse_file(
+                    "src/math.ts",
+                    "import { x } from './x';\n/** Adds. */\nexport function add(a: number) {\n  return a;\n}\n",
+                )
+                .unwrap(),
+        );
+        index.insert(manager.parse_file("src/math.test.ts", "function check() {}\n").unwrap());
+
+        let mut out = Vec::new();
+        let options = ChunkOptions {
+            exclude_tests: true,
+            ..Default::default()
+        };
+        assert_eq!(index.write_chunks_jsonl(&mut out, &options).unwrap(), 1);
+        let chunk: Value = serde_json::from_slice(out.split(|&b| b == b'\n').next().unwrap()).unwrap();
+        assert_eq!(chunk["id"], "src/math.ts:3:function:add");
+        assert!(chunk["text"].as_str().unwrap().starts_with("export function add"));
+        assert_eq!(chunk["metadata"]["name"], "add");
+        assert_eq!(chunk["metadata"]["doc"], "Adds.");
+        assert_eq!((chunk["metadata"]["startLine"].as_u64(), chunk["metadata"]["endLine"].as_u64()), (Some(3), Some(5)));
+        assert_eq!(chunk["metadata"]["role"], "source");
+        assert!(chunk["metadata"].get("part").is_none());
+    }
+
+    #[cfg(feature = "java")]
+    #[test]
+    fn test_split_long_entities() {
+        // Java 方法的代码片段包含方法体
+        let mut index = CodeIndex::new();
+        let java = "class A {\n  int f() {\n    int x = 1;\n    return x;\n  }\n}\n";
+        index.insert(LanguageManager::new().parse_file("src/A.java", java).unwrap());
+        let mut out = Vec::new();
+        let options = ChunkOptions {
+            kinds: vec!["method".to_string()],
+            roles: vec![FileRole::Source],
+            max_chars: 26,
+            ..Default::default()
+        };
+        assert_eq!(index.write_chunks_jsonl(&mut out, &options).unwrap(), 2);
+        let chunks: Vec<Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(chunks[0]["id"], "src/A.java:2:method:f#1");
+        assert_eq!(chunks[0]["text"], "int f() {\n    int x = 1;\n");
+        assert_eq!(chunks[1]["text"], "    return x;\n  }");
+        assert_eq!(chunks[1]["metadata"]["parts"], 2);
+        assert_eq!((chunks[1]["metadata"]["startLine"].as_u64(), chunks[1]["metadata"]["endLine"].as_u64()), (Some(4), Some(5)));
+    }
+
+    #[test]
+    fn test_split_long_line() {
+        let pieces = split_lines("abcdefgh\nij", 3);
+        let texts: Vec<&str> = pieces.iter().map(|(text, _, _)| *text).collect();
+        assert_eq!(texts, vec!["abc", "def", "gh\n", "ij"]);
+        assert_eq!(pieces[3].1, 1);
+        assert_eq!(texts.concat(), "abcdefgh\nij");
+    }
+}
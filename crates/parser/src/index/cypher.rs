@@ -0,0 +1,153 @@
+//! 生成 Neo4j 可直接执行的 Cypher 脚本
+//!
+//! 图模型与 NDJSON 导出一致：
+//! - `(:File {path, language, isGenerated})`
+//! - `(:Entity {id, filePath, language, kind, name, startLine, endLine, text, isExported, callerCount, isTest, isGenerated})`
+//! - `(:File)-[:CONTAINS]->(:Entity)`
+//! - `(:File)-[:CALLS {count}]->(:Entity)`
+//!
+//! 所有写入都用 `MERGE`，重复执行脚本是幂等的。尚未压缩的墓碑以 `DETACH DELETE` 写在节点之前。
+
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use super::CodeIndex;
+
+impl CodeIndex {
+    /// 导出为 Cypher 脚本文件
+    pub fn export_cypher(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let file = fs::File::create(path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        let mut writer = BufWriter::new(file);
+        self.write_cypher(&mut writer)?;
+        writer.flush().map_err(|e| e.to_string())
+    }
+
+    /// 以 Cypher 语句写出（约束、节点、边）
+    pub fn write_cypher(&self, writer: &mut impl Write) -> Result<(), String> {
+        let mut write = |statement: String| -> Result<(), String> {
+            writer
+                .write_all(statement.as_bytes())
+                .and_then(|_| writer.write_all(b";\n"))
+                .map_err(|e| e.to_string())
+        };
+
+        write("CREATE CONSTRAINT file_path IF NOT EXISTS FOR (f:File) REQUIRE f.path IS UNIQUE".to_string())?;
+        write("CREATE CONSTRAINT entity_id IF NOT EXISTS FOR (e:Entity) REQUIRE e.id IS UNIQUE".to_string())?;
+
+        for path in self.tombstones.files() {
+            write(format!(
+                "MATCH (f:File {{path: {}}}) OPTIONAL MATCH (f)-[:CONTAINS]->(e:Entity) DETACH DELETE f, e",
+                quote(path)
+            ))?;
+        }
+        for id in self.tombstones.entities() {
+            write(format!("MATCH (e:Entity {{id: {}}}) DETACH DELETE e", quote(id)))?;
+        }
+
+        for file in self.files() {
+            write(format!(
+                "MERGE (f:File {{path: {}}}) SET f.language = {}, f.isGenerated = {}, f.role = {}",
+                quote(&file.path),
+                quote(&file.language),
+                file.is_generated,
+                quote(file.role.as_str())
+            ))?;
+        }
+
+        for entity in self.entities() {
+            let name = entity.name.as_deref().map_or_else(|| "null".to_string(), quote);
+            let tags = entity.tags.iter().map(|tag| quote(tag)).collect::<Vec<_>>().join(", ");
+            write(format!(
+                "MERGE (e:Entity {{id: {}}}) SET e.filePath = {}, e.language = {}, e.kind = {}, e.name = {}, \
+                 e.startLine = {}, e.endLine = {}, e.text = {}, e.isExported = {}, e.callerCount = {}, \
+                 e.isTest = {}, e.isGenerated = {}, e.role = {}, e.tags = [{}]",
+                quote(&entity.id),
+                quote(&entity.file_path),
+                quote(&entity.language),
+                quote(&entity.kind),
+                name,
+                entity.range.start,
+                entity.range.end,
+                quote(&entity.text),
+                entity.rank_hints.is_exported,
+                entity.rank_hints.caller_count,
+                entity.rank_hints.is_test,
+                entity.rank_hints.is_generated,
+                quote(entity.rank_hints.role.as_str()),
+                tags,
+            ))?;
+        }
+
+        for entity in self.entities() {
+            write(format!(
+                "MATCH (f:File {{path: {}}}), (e:Entity {{id: {}}}) MERGE (f)-[:CONTAINS]->(e)",
+                quote(&entity.file_path),
+                quote(&entity.id)
+            ))?;
+        }
+
+        for (from, to, count) in self.call_edges() {
+            write(format!(
+                "MATCH (f:File {{path: {}}}), (e:Entity {{id: {}}}) MERGE (f)-[r:CALLS]->(e) SET r.count = {}",
+                quote(from),
+                quote(to),
+                count
+            ))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Cypher 单引号字符串字面量
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '\'' => quoted.push_str("\\'"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LanguageManager;
+
+    #[test]
+    fn test_quote_escapes() {
+        assert_eq!(quote("it's\na \\ b"), "'it\\'s\\na \\\\ b'");
+    }
+
+    #[test]
+    fn test_write_cypher() {
+        let mut manager = LanguageManager::new();
+        let mut index = CodeIndex::new();
+        index.insert(manager.parse_file("src/a.ts", "export function a() {}\n").unwrap());
+
+        let mut buffer = Vec::new();
+        index.write_cypher(&mut buffer).unwrap();
+        let script = String::from_utf8(buffer).unwrap();
+
+        assert!(script.contains("MERGE (f:File {path: 'src/a.ts'}) SET f.language = 'TypeScript', f.isGenerated = false, f.role = 'source';"));
+        assert!(script.contains("MERGE (e:Entity {id: 'src/a.ts:1:function:a'})"));
+        assert!(script.contains("MERGE (f)-[:CONTAINS]->(e);"));
+
+        index.insert(manager.parse_file("src/a.ts", "export function b() {}\n").unwrap());
+        let mut buffer = Vec::new();
+        index.write_cypher(&mut buffer).unwrap();
+        let script = String::from_utf8(buffer).unwrap();
+        assert!(script.contains("MATCH (e:Entity {id: 'src/a.ts:1:function:a'}) DETACH DELETE e;"));
+    }
+}
@@ -0,0 +1,76 @@
+//! 悬停提示：签名 + 文档 + 定义位置（Markdown）
+
+use super::IndexedEntity;
+
+impl IndexedEntity {
+    /// 渲染悬停提示：带语言标记的签名代码块、文档注释、定义位置
+    ///
+    /// 没有提取到签名时（变量、属性等）取定义的首行。
+    pub fn hover_markdown(&self) -> String {
+        let signature = self
+            .signature
+            .clone()
+            .unwrap_or_else(|| first_line(&self.text).to_string());
+        let mut out = format!("```{}\n{}\n```\n", fence_language(&self.language), signature);
+
+        if let Some(doc) = self.doc.as_deref().filter(|doc| !doc.trim().is_empty()) {
+            out.push('\n');
+            out.push_str(doc.trim());
+            out.push('\n');
+        }
+
+        out.push_str("\n---\n\n");
+        out.push_str(&format!("*{}*", self.kind));
+        if let Some(name) = &self.name {
+            out.push_str(&format!(" `{}`", name));
+        }
+        out.push_str(&format!(" in `{}:{}`\n", self.file_path, self.range.start));
+        out
+    }
+}
+
+/// Markdown 代码块的语言标记
+pub(crate) fn fence_language(language: &str) -> String {
+    match language {
+        "C++" => "cpp".to_string(),
+        "C#" => "csharp".to_string(),
+        other => other.to_lowercase(),
+    }
+}
+
+/// 定义文本的首个非空行，去掉行尾的 `{`
+pub(crate) fn first_line(text: &str) -> &str {
+    let line = text.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("");
+    line.strip_suffix('{').map_or(line, str::trim_end)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::index::CodeIndex;
+    use crate::LanguageManager;
+
+    #[test]
+    fn test_hover_markdown() {
+        let code = "/** Adds two numbers. */\nexport function add(a: number, b: number): number {\n  return a + b;\n}\n";
+        let mut index = CodeIndex::new();
+        index.insert(LanguageManager::new().parse_file("src/math.ts", code).unwrap());
+
+        let add = index.entities().find(|e| e.name.as_deref() == Some("add")).unwrap();
+        assert_eq!(
+            index.hover_info(&add.id).unwrap(),
+            "```typescript\nfunction add(a: number, b: number): number\n```\n\nAdds two numbers.\n\n---\n\n*function* `add` in `src/math.ts:2`\n"
+        );
+
+        // 没有签名时取首行，去掉行尾的 `{`
+        let mut unsigned = add.clone();
+        unsigned.signature = None;
+        unsigned.doc = None;
+        unsigned.language = "C++".to_string();
+        unsigned.text = "\nint add(int a, int b) {\n  return a + b;\n}".to_string();
+        assert!(unsigned.hover_markdown().starts_with("```cpp\nint add(int a, int b)\n```\n\n---\n"));
+
+        let hovers = index.hover_infos([add.id.as_str(), "missing"]);
+        assert!(hovers[0].is_some());
+        assert_eq!(hovers[1], None);
+    }
+}
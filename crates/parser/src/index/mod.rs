@@ -0,0 +1,651 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use crate::ext_to_lang::{is_generated_path, is_test_file};
+use crate::graph_filter::GraphFilter;
+use crate::paths::normalize_path;
+use crate::types::{FileRole, ParseResult, Range};
+
+mod chunks;
+mod cypher;
+pub(crate) mod hover;
+mod ndjson;
+mod shared;
+mod snapshot;
+mod stats;
+mod tags;
+mod tombstones;
+
+pub use chunks::ChunkOptions;
+pub use shared::SharedIndex;
+pub use snapshot::IndexSnapshot;
+pub use stats::{IndexStats, SizedItem};
+pub use tags::{TagRule, TagRules};
+pub use tombstones::{CompactStats, Tombstones};
+
+/// 检索排序提示（组合多个维度，下游无需再关联多个报告）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankHints {
+    pub is_exported: bool,
+    /// 实体所占行数
+    pub loc: usize,
+    /// 索引中引用（调用）该名字的次数
+    pub caller_count: usize,
+    pub is_test: bool,
+    pub is_generated: bool,
+    /// 所在文件的角色
+    #[serde(default)]
+    pub role: FileRole,
+}
+
+/// 索引中的实体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedEntity {
+    /// 稳定 ID：`路径:起始行:类型:名字`
+    pub id: String,
+    pub file_path: String,
+    pub language: String,
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub range: Range,
+    pub text: String,
+    /// 声明签名（见 `EntityInfo::signature`）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub signature: Option<String>,
+    /// 文档注释（见 `EntityInfo::doc`）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub doc: Option<String>,
+    /// 标签规则命中的标签（见 `CodeIndex::set_tag_rules`）
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub tags: Vec<String>,
+    /// 实体内的调用：被调用名 → 次数（来自 `ParseResult::calls`，递归调用不计）
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub calls: BTreeMap<String, usize>,
+    pub rank_hints: RankHints,
+}
+
+/// 索引中的文件记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedFile {
+    pub path: String,
+    pub language: String,
+    /// 生成代码（路径约定或文件头标记，见 `ParseResult::is_generated`）
+    #[serde(default)]
+    pub is_generated: bool,
+    /// 文件角色（见 `ParseResult::role`）
+    #[serde(default)]
+    pub role: FileRole,
+    pub entities: Vec<IndexedEntity>,
+    /// 本文件中出现的调用名及次数（各实体的 `calls` 加上顶层调用，用于计算 caller_count）
+    #[serde(skip)]
+    call_refs: HashMap<String, usize>,
+}
+
+/// 内存代码索引：按文件路径有序存储解析结果
+#[derive(Debug, Default)]
+pub struct CodeIndex {
+    /// 当前内容（读取方法都委托给它，`snapshot` 返回它的副本）
+    current: IndexSnapshot,
+    /// 全局调用名计数（图过滤器未排除的文件的 call_refs 之和）
+    call_counts: HashMap<String, usize>,
+    /// 实体名 → 定义该名字的文件路径（刷新 caller_count 时只访问受影响的文件）
+    definitions: HashMap<String, BTreeSet<String>>,
+    /// 调用计数有变化、尚未刷新 caller_count 的名字
+    stale_names: HashSet<String>,
+    /// 新写入、尚未刷新 caller_count 的文件
+    stale_files: HashSet<String>,
+    /// 写入时应用的标签规则
+    tag_rules: TagRules,
+    /// 写入、导入累计耗时（见 `index_stats`）
+    build_time: Duration,
+    /// 已删除、尚未压缩的文件与实体（见 `compact`）
+    tombstones: Tombstones,
+    /// 内容版本号，每次写入后递增（见 `generation`）
+    generation: u64,
+}
+
+impl CodeIndex {
+    /// 创建空索引
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置标签规则，并重新计算已有实体的标签
+    pub fn set_tag_rules(&mut self, rules: TagRules) {
+        self.tag_rules = rules;
+        for path in self.current.paths() {
+            if let Some(file) = self.current.get_mut(&path) {
+                for entity in &mut file.entities {
+                    entity.tags = self.tag_rules.tags_for(entity);
+                }
+            }
+        }
+        self.generation += 1;
+    }
+
+    /// 内容版本号：写入、删除、导入或更改标签规则 / 图过滤器后递增，
+    /// 调用方可据此判断缓存的查询结果是否过期
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// 当前的图过滤器
+    pub fn graph_filter(&self) -> &GraphFilter {
+        self.current.graph_filter()
+    }
+
+    /// 设置图过滤器：被排除的文件保留在索引中，但不参与调用边与 caller_count
+    pub fn set_graph_filter(&mut self, filter: GraphFilter) {
+        self.current.set_graph_filter(filter);
+        self.call_counts.clear();
+        let files: Vec<(String, bool, HashMap<String, usize>)> = self
+            .current
+            .files()
+            .map(|file| (file.path.clone(), file.is_generated, file.call_refs.clone()))
+            .collect();
+        for (path, is_generated, call_refs) in &files {
+            self.count_call_refs(path, *is_generated, call_refs);
+        }
+        // 排除的文件中的实体也要归零，所有文件都需刷新
+        self.stale_files.extend(files.into_iter().map(|(path, _, _)| path));
+        self.refresh_rank_hints();
+    }
+
+    /// 当前内容的只读快照，之后的写入不影响它
+    pub fn snapshot(&self) -> IndexSnapshot {
+        self.current.clone()
+    }
+
+    /// 写入（或替换）一个文件的解析结果
+    pub fn insert(&mut self, result: ParseResult) {
+        let started = Instant::now();
+        self.insert_without_refresh(result);
+        self.refresh_rank_hints();
+        self.build_time += started.elapsed();
+    }
+
+    /// 批量写入解析结果，最后统一刷新排序提示
+    pub fn insert_batch(&mut self, results: impl IntoIterator<Item = ParseResult>) {
+        let started = Instant::now();
+        for result in results {
+            self.insert_without_refresh(result);
+        }
+        self.refresh_rank_hints();
+        self.build_time += started.elapsed();
+    }
+
+    /// 移除文件（留下墓碑），返回是否存在
+    pub fn remove(&mut self, path: &str) -> bool {
+        let Some(file) = self.delete_file(&normalize_path(path)) else {
+            return false;
+        };
+        self.bury_file(file);
+        self.refresh_rank_hints();
+        true
+    }
+
+    /// 文件数量
+    pub fn file_count(&self) -> usize {
+        self.current.file_count()
+    }
+
+    /// 实体数量
+    pub fn entity_count(&self) -> usize {
+        self.current.entity_count()
+    }
+
+    /// 按路径获取文件
+    pub fn file(&self, path: &str) -> Option<&IndexedFile> {
+        self.current.file(path)
+    }
+
+    /// 所有文件（按路径排序）
+    pub fn files(&self) -> impl Iterator<Item = &IndexedFile> {
+        self.current.files()
+    }
+
+    /// 所有实体（按文件路径、文件内顺序）
+    pub fn entities(&self) -> impl Iterator<Item = &IndexedEntity> {
+        self.current.entities()
+    }
+
+    /// 路径前缀（文件或目录）下的所有实体，见 `IndexSnapshot::files_in`
+    pub fn entities_in(&self, path_prefix: &str) -> impl Iterator<Item = &IndexedEntity> {
+        self.current.entities_in(path_prefix)
+    }
+
+    /// 带有指定标签的实体
+    pub fn entities_with_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a IndexedEntity> + 'a {
+        self.current.entities_with_tag(tag)
+    }
+
+    /// 指定角色的文件中的实体（如只看生产代码）
+    pub fn entities_with_role(&self, role: FileRole) -> impl Iterator<Item = &IndexedEntity> {
+        self.current.entities_with_role(role)
+    }
+
+    /// 按 ID 查找实体
+    pub fn entity(&self, id: &str) -> Option<&IndexedEntity> {
+        self.current.entity(id)
+    }
+
+    /// 实体的悬停提示（Markdown），见 `IndexedEntity::hover_markdown`
+    pub fn hover_info(&self, id: &str) -> Option<String> {
+        self.current.hover_info(id)
+    }
+
+    /// 批量生成悬停提示，与 `ids` 按下标对应
+    pub fn hover_infos<'a>(&self, ids: impl IntoIterator<Item = &'a str>) -> Vec<Option<String>> {
+        self.current.hover_infos(ids)
+    }
+
+    /// 文件到被调用实体的边：(文件路径, 实体 ID, 调用次数)，见 `IndexSnapshot::call_edges`
+    pub fn call_edges(&self) -> Vec<(&str, &str, usize)> {
+        self.current.call_edges()
+    }
+
+    fn insert_without_refresh(&mut self, mut result: ParseResult) {
+        // 不同平台产生的路径统一后再作为 key，保证索引内容一致
+        result.file_path = normalize_path(&result.file_path);
+        let old = self.delete_file(&result.file_path);
+
+        let is_test = is_test_file(&result.file_path);
+        let role = result.role;
+        let is_generated = result.is_generated || is_generated_path(&result.file_path);
+        let mut call_refs: HashMap<String, usize> = HashMap::new();
+        let mut entity_calls: Vec<BTreeMap<String, usize>> = vec![BTreeMap::new(); result.entities.len()];
+        for call in &result.calls {
+            // 递归调用不算作该名字的调用方
+            if call.caller.as_deref() == Some(call.callee.as_str()) {
+                continue;
+            }
+            *call_refs.entry(call.callee.clone()).or_default() += 1;
+            // 归属范围与名字一致的实体；顶层调用只计入文件
+            let owner = result.entity_info.iter().position(|info| {
+                call.caller.is_some() && info.name == call.caller && call.caller_range.as_ref() == Some(&info.range)
+            });
+            if let Some(calls) = owner.and_then(|i| entity_calls.get_mut(i)) {
+                *calls.entry(call.callee.clone()).or_default() += 1;
+            }
+        }
+        let mut entities = Vec::with_capacity(result.entities.len());
+
+        for ((i, text), calls) in result.entities.into_iter().enumerate().zip(entity_calls) {
+            let info = result.entity_info.get(i);
+            let kind = info.map(|info| info.kind.clone()).unwrap_or_else(|| "unknown".to_string());
+            let name = info.and_then(|info| info.name.clone());
+            let range = info.map(|info| info.range.clone()).unwrap_or(Range { start: 0, end: 0 });
+            let is_exported = info.is_some_and(|info| info.is_exported);
+            let signature = info.and_then(|info| info.signature.clone());
+            let doc = info.and_then(|info| info.doc.clone());
+
+            let mut entity = IndexedEntity {
+                id: entity_id(&result.file_path, &range, &kind, name.as_deref()),
+                file_path: result.file_path.clone(),
+                language: result.language.clone(),
+                kind,
+                name,
+                rank_hints: RankHints {
+                    is_exported,
+                    loc: range.end.saturating_sub(range.start) + 1,
+                    caller_count: 0,
+                    is_test,
+                    is_generated,
+                    role,
+                },
+                range,
+                text,
+                signature,
+                doc,
+                tags: Vec::new(),
+                calls,
+            };
+            entity.tags = self.tag_rules.tags_for(&entity);
+            entities.push(entity);
+        }
+
+        let file = IndexedFile {
+            path: result.file_path,
+            language: result.language,
+            is_generated,
+            role,
+            entities,
+            call_refs,
+        };
+        self.bury_replaced(old, &file);
+        self.put_file(file);
+    }
+
+    /// 写入文件记录：计入调用计数与名字索引，待刷新 caller_count
+    pub(super) fn put_file(&mut self, file: IndexedFile) {
+        self.count_call_refs(&file.path, file.is_generated, &file.call_refs);
+        for name in file.entities.iter().filter_map(|entity| entity.name.as_ref()) {
+            self.definitions.entry(name.clone()).or_default().insert(file.path.clone());
+        }
+        self.stale_files.insert(file.path.clone());
+        self.current.put(file);
+    }
+
+    /// 删除文件记录：撤销其调用计数与名字索引，返回删除前的记录
+    pub(super) fn delete_file(&mut self, path: &str) -> Option<IndexedFile> {
+        let file = self.current.delete(path)?;
+        self.forget_call_refs(&file.path, file.is_generated, &file.call_refs);
+        for entity in &file.entities {
+            if let Some(name) = &entity.name {
+                self.forget_definition(name, &file.path);
+            }
+        }
+        self.stale_files.remove(&file.path);
+        Some(file)
+    }
+
+    /// 名字不再由该文件定义
+    pub(super) fn forget_definition(&mut self, name: &str, path: &str) {
+        if let Some(paths) = self.definitions.get_mut(name) {
+            paths.remove(path);
+            if paths.is_empty() {
+                self.definitions.remove(name);
+            }
+        }
+    }
+
+    /// 文件的调用计入全局计数（图过滤器排除的文件不计）
+    fn count_call_refs(&mut self, path: &str, is_generated: bool, call_refs: &HashMap<String, usize>) {
+        if self.current.graph_filter().excludes_file(path, is_generated) {
+            return;
+        }
+        for (name, count) in call_refs {
+            *self.call_counts.entry(name.clone()).or_default() += count;
+            self.stale_names.insert(name.clone());
+        }
+    }
+
+    fn forget_call_refs(&mut self, path: &str, is_generated: bool, call_refs: &HashMap<String, usize>) {
+        if self.current.graph_filter().excludes_file(path, is_generated) {
+            return;
+        }
+        for (name, count) in call_refs {
+            self.stale_names.insert(name.clone());
+            if let Some(total) = self.call_counts.get_mut(name) {
+                *total = total.saturating_sub(*count);
+                if *total == 0 {
+                    self.call_counts.remove(name);
+                }
+            }
+        }
+    }
+
+    /// 根据全局调用计数刷新 caller_count（图过滤器排除的文件中的实体为 0）
+    ///
+    /// 只访问调用计数有变化的名字所在的文件与新写入的文件，其中也只复制计数有变化的文件，
+    /// 其余文件仍与快照共享。
+    ///
+    /// 所有改变内容的写入最后都经过这里，内容版本号在此递增。
+    fn refresh_rank_hints(&mut self) {
+        self.generation += 1;
+        let mut paths: BTreeSet<String> = self.stale_files.drain().collect();
+        for name in self.stale_names.drain() {
+            paths.extend(self.definitions.get(&name).into_iter().flatten().cloned());
+        }
+
+        let filter = self.current.graph_filter().clone();
+        let caller_count = |entity: &IndexedEntity| {
+            if filter.excludes_file(&entity.file_path, entity.rank_hints.is_generated) {
+                return 0;
+            }
+            entity
+                .name
+                .as_ref()
+                .and_then(|name| self.call_counts.get(name))
+                .copied()
+                .unwrap_or(0)
+        };
+        let stale: Vec<String> = paths
+            .into_iter()
+            .filter(|path| {
+                self.current.file(path).is_some_and(|file| {
+                    file.entities.iter().any(|entity| entity.rank_hints.caller_count != caller_count(entity))
+                })
+            })
+            .collect();
+        for path in stale {
+            if let Some(file) = self.current.get_mut(&path) {
+                for entity in &mut file.entities {
+                    entity.rank_hints.caller_count = caller_count(entity);
+                }
+            }
+        }
+    }
+}
+
+/// 生成实体 ID
+pub fn entity_id(file_path: &str, range: &Range, kind: &str, name: Option<&str>) -> String {
+    format!("{}:{}:{}:{}", file_path, range.start, kind, name.unwrap_or(""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CallReference, EntityInfo};
+
+    /// 测试用解析结果：每个 (名字, 片段) 为一个 TypeScript 函数实体，第 i 个实体位于第 i + 1 行
+    pub(super) fn result(path: &str, entities: &[(&str, &str)]) -> ParseResult {
+        ParseResult {
+            file_path: path.to_string(),
+            language: "TypeScript".to_string(),
+            entities: entities.iter().map(|(_, text)| text.to_string()).collect(),
+            entity_info: entities
+                .iter()
+                .enumerate()
+                .map(|(i, (name, _))| EntityInfo {
+                    kind: "function".to_string(),
+                    name: Some(name.to_string()),
+                    range: Range { start: i + 1, end: i + 1 },
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// 给测试用解析结果加上调用：每个 (调用方, 被调用名) 记为调用方实体中的一次调用
+    pub(super) fn with_calls(mut result: ParseResult, calls: &[(&str, &str)]) -> ParseResult {
+        for (caller, callee) in calls {
+            let info = result.entity_info.iter().find(|info| info.name.as_deref() == Some(*caller)).unwrap();
+            result.calls.push(CallReference {
+                caller: info.name.clone(),
+                caller_range: Some(info.range.clone()),
+                callee: callee.to_string(),
+                range: info.range.clone(),
+            });
+        }
+        result
+    }
+
+    fn exported(mut result: ParseResult) -> ParseResult {
+        for info in &mut result.entity_info {
+            info.is_exported = true;
+        }
+        result
+    }
+
+    #[test]
+    fn test_rank_hints() {
+        let mut index = CodeIndex::new();
+        let mut helper = exported(result("src/a.ts", &[("helper", "function helper()")]));
+        helper.entity_info[0].range = Range { start: 1, end: 3 };
+        index.insert(helper);
+        index.insert(with_calls(
+            result("src/__tests__/b.test.ts", &[("run", "function run() { helper(); helper(); }")]),
+            &[("run", "helper"), ("run", "helper")],
+        ));
+
+        let helper = index.entities().find(|e| e.name.as_deref() == Some("helper")).unwrap();
+        assert_eq!(
+            helper.rank_hints,
+            RankHints {
+                is_exported: true,
+                loc: 3,
+                caller_count: 2,
+                is_test: false,
+                is_generated: false,
+                role: FileRole::Source,
+            }
+        );
+        let run = index.entities().find(|e| e.name.as_deref() == Some("run")).unwrap();
+        assert!(run.rank_hints.is_test);
+
+        // 移除调用方后 caller_count 归零
+        let helper_id = helper.id.clone();
+        assert!(index.remove("src/__tests__/b.test.ts"));
+        let helper = index.entity(&helper_id).unwrap();
+        assert_eq!(helper.rank_hints.caller_count, 0);
+    }
+
+    #[test]
+    fn test_graph_filter() {
+        let mut index = CodeIndex::new();
+        index.insert(exported(result("src/a.ts", &[("helper", "function helper()")])));
+        index.insert(exported(with_calls(result("src/b.ts", &[("main", "function main() { helper(); }")]), &[("main", "helper")])));
+        index.insert(with_calls(
+            result("src/b.test.ts", &[("run", "function run() { helper(); main(); }")]),
+            &[("run", "helper"), ("run", "main")],
+        ));
+        index.insert(exported(with_calls(
+            result("src/__generated__/api.ts", &[("call", "function call() { helper(); }")]),
+            &[("call", "helper")],
+        )));
+        let helper_id = "src/a.ts:1:function:helper";
+        assert_eq!(index.entity(helper_id).unwrap().rank_hints.caller_count, 3);
+
+        index.set_graph_filter(GraphFilter::all());
+        assert_eq!(index.entity(helper_id).unwrap().rank_hints.caller_count, 1);
+        assert_eq!(index.call_edges(), vec![("src/b.ts", helper_id, 1)]);
+        // 被排除的文件仍可检索
+        assert_eq!(index.file_count(), 4);
+        assert!(index.entity("src/b.test.ts:1:function:run").is_some());
+
+        // 之后写入、删除的排除文件不影响计数
+        index.insert(with_calls(result("tests/c.ts", &[("check", "function check() { helper(); }")]), &[("check", "helper")]));
+        index.remove("src/b.test.ts");
+        assert_eq!(index.entity(helper_id).unwrap().rank_hints.caller_count, 1);
+        assert_eq!(index.snapshot().call_edges().len(), 1);
+
+        // 文件头标记识别出的生成代码同样排除
+        let mut client =
+            exported(with_calls(result("src/client.ts", &[("fetch", "function fetch() { helper(); }")]), &[("fetch", "helper")]));
+        client.is_generated = true;
+        index.insert(client);
+        assert_eq!(index.entity(helper_id).unwrap().rank_hints.caller_count, 1);
+        assert!(index.file("src/client.ts").unwrap().is_generated);
+
+        index.set_graph_filter(GraphFilter::default());
+        assert_eq!(index.entity(helper_id).unwrap().rank_hints.caller_count, 4);
+    }
+
+    #[test]
+    fn test_incremental_caller_counts() {
+        let mut index = CodeIndex::new();
+        // 调用方先于定义写入：定义写入时取得已有的计数
+        index.insert(with_calls(result("src/b.ts", &[("main", "function main() { helper(); }")]), &[("main", "helper")]));
+        index.insert(result("src/a.ts", &[("helper", "function helper()"), ("other", "function other()")]));
+        assert_eq!(index.entity("src/a.ts:1:function:helper").unwrap().rank_hints.caller_count, 1);
+
+        // 只有定义了受影响名字的文件被复制，其余文件仍与快照共享
+        index.insert(result("src/c.ts", &[("unrelated", "function unrelated()")]));
+        let before = index.snapshot();
+        index.insert(with_calls(
+            result("src/b.ts", &[("main", "function main() { helper(); helper(); }")]),
+            &[("main", "helper"), ("main", "helper")],
+        ));
+        assert_eq!(index.entity("src/a.ts:1:function:helper").unwrap().rank_hints.caller_count, 2);
+        let after = index.snapshot();
+        assert!(std::ptr::eq(before.file("src/c.ts").unwrap(), after.file("src/c.ts").unwrap()));
+
+        // 被调用名的另一份定义删除后，剩下的定义仍会刷新
+        index.insert(result("src/d.ts", &[("helper", "function helper()")]));
+        assert!(index.remove_entity("src/d.ts:1:function:helper"));
+        index.insert(result("src/b.ts", &[("main", "function main() {}")]));
+        assert_eq!(index.entity("src/a.ts:1:function:helper").unwrap().rank_hints.caller_count, 0);
+    }
+
+    #[test]
+    fn test_tag_rules() {
+        let mut index = CodeIndex::new();
+        index.insert(exported(result("src/api/users.ts", &[("getUser", "function getUser()")])));
+        index.insert(result("src/legacy/old.ts", &[("oldThing", "function oldThing()")]));
+        assert_eq!(index.entities_with_tag("api-layer").count(), 0);
+
+        // 设置规则后已有实体重新打标签，新写入的实体也会打标签
+        index.set_tag_rules(
+            TagRules::from_json(r#"{"rules": [{"glob": "src/api/**", "tags": ["api-layer"]}]}"#).unwrap(),
+        );
+        let api: Vec<_> = index.entities_with_tag("api-layer").filter_map(|e| e.name.as_deref()).collect();
+        assert_eq!(api, vec!["getUser"]);
+        index.insert(exported(result("src/api/orders.ts", &[("getOrder", "function getOrder()")])));
+        assert_eq!(index.entities_with_tag("api-layer").count(), 2);
+        assert!(index.entities().find(|e| e.name.as_deref() == Some("oldThing")).unwrap().tags.is_empty());
+    }
+
+    #[test]
+    fn test_entities_with_role() {
+        let mut index = CodeIndex::new();
+        index.insert(exported(result("src/a.ts", &[("helper", "function helper()")])));
+        let mut script = result("scripts/release.ts", &[("release", "function release()")]);
+        script.role = FileRole::Script;
+        index.insert(script);
+
+        let source: Vec<_> = index.entities_with_role(FileRole::Source).filter_map(|e| e.name.as_deref()).collect();
+        assert_eq!(source, vec!["helper"]);
+        assert_eq!(index.file("scripts/release.ts").unwrap().role, FileRole::Script);
+        assert_eq!(index.entity("scripts/release.ts:1:function:release").unwrap().rank_hints.role, FileRole::Script);
+        assert_eq!(index.entities_with_role(FileRole::Docs).count(), 0);
+    }
+
+    #[test]
+    fn test_reinsert_replaces_file() {
+        let mut index = CodeIndex::new();
+        index.insert(result("a.ts", &[("x", "function x()"), ("y", "function y()")]));
+        index.insert(result("a.ts", &[("x", "function x()")]));
+        assert_eq!(index.file_count(), 1);
+        assert_eq!(index.entity_count(), 1);
+    }
+
+    #[test]
+    fn test_generation_advances_on_writes() {
+        let mut index = CodeIndex::new();
+        let initial = index.generation();
+        index.insert(result("a.ts", &[("x", "function x()")]));
+        let inserted = index.generation();
+        assert!(inserted > initial);
+        // 读取不改变版本号，删除不存在的文件也不改变
+        let _ = index.entities().count();
+        assert!(!index.remove("missing.ts"));
+        assert_eq!(index.generation(), inserted);
+        assert!(index.remove("a.ts"));
+        assert!(index.generation() > inserted);
+    }
+
+    #[test]
+    fn test_paths_normalized_across_platforms() {
+        let entities = &[("x", "function x()")];
+        let mut posix = CodeIndex::new();
+        posix.insert(exported(result("src/lib/a.ts", entities)));
+        let mut windows = CodeIndex::new();
+        windows.insert(exported(result(".\\src\\lib\\a.ts", entities)));
+
+        let dump = |index: &CodeIndex| {
+            let mut out = Vec::new();
+            index.write_ndjson(&mut out).unwrap();
+            out
+        };
+        assert_eq!(dump(&posix), dump(&windows));
+        assert!(windows.file("src\\lib\\a.ts").is_some());
+        assert!(windows.remove("./src/lib/a.ts"));
+    }
+}
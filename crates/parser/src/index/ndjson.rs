@@ -0,0 +1,288 @@
+//! 索引的 NDJSON 导入导出
+//!
+//! 每行一个 JSON 对象，先输出全部节点，再输出全部边：
+//!
+//! ```text
+//! {"type":"node","label":"File","id":"<path>","properties":{"path":..,"language":..,"callRefs":{"name":count}}}
+//! {"type":"node","label":"Entity","id":"<entity id>","properties":{<IndexedEntity 字段>}}
+//! {"type":"edge","label":"CONTAINS","from":"<path>","to":"<entity id>"}
+//! {"type":"edge","label":"CALLS","from":"<path>","to":"<entity id>","properties":{"count":n}}
+//! {"type":"tombstone","label":"File","id":"<path>"}
+//! {"type":"tombstone","label":"Entity","id":"<entity id>"}
+//! ```
+//!
+//! `CALLS` 边由文件的 `callRefs` 按名字解析得到，只用于下游消费；
+//! 导入时以节点为准，边会被忽略（重新由 callRefs 计算）。
+//!
+//! 末尾的 `tombstone` 记录是尚未压缩的删除（文件墓碑同时删除其实体），导入时据此删除已有的文件与实体。
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use super::{CodeIndex, IndexedEntity, IndexedFile};
+use crate::types::FileRole;
+
+/// NDJSON 中的一行
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Record {
+    Node(NodeRecord),
+    Edge(EdgeRecord),
+    Tombstone(TombstoneRecord),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "label", content = "properties")]
+enum NodeProperties {
+    File(FileProperties),
+    Entity(Box<IndexedEntity>),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NodeRecord {
+    id: String,
+    #[serde(flatten)]
+    properties: NodeProperties,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileProperties {
+    path: String,
+    language: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    is_generated: bool,
+    #[serde(default)]
+    role: FileRole,
+    #[serde(default)]
+    call_refs: HashMap<String, usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct EdgeRecord {
+    label: String,
+    from: String,
+    to: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    properties: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TombstoneRecord {
+    /// `File` 或 `Entity`
+    label: String,
+    id: String,
+}
+
+impl CodeIndex {
+    /// 导出为 NDJSON 文件
+    pub fn export_ndjson(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let file = fs::File::create(path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        let mut writer = BufWriter::new(file);
+        self.write_ndjson(&mut writer)?;
+        writer.flush().map_err(|e| e.to_string())
+    }
+
+    /// 以 NDJSON 写出（节点在前、边在后，最后是墓碑）
+    pub fn write_ndjson(&self, writer: &mut impl Write) -> Result<(), String> {
+        let mut write = |record: &Record| -> Result<(), String> {
+            serde_json::to_writer(&mut *writer, record).map_err(|e| e.to_string())?;
+            writer.write_all(b"\n").map_err(|e| e.to_string())
+        };
+
+        for file in self.files() {
+            write(&Record::Node(NodeRecord {
+                id: file.path.clone(),
+                properties: NodeProperties::File(FileProperties {
+                    path: file.path.clone(),
+                    language: file.language.clone(),
+                    is_generated: file.is_generated,
+                    role: file.role,
+                    call_refs: file.call_refs.clone(),
+                }),
+            }))?;
+            for entity in &file.entities {
+                write(&Record::Node(NodeRecord {
+                    id: entity.id.clone(),
+                    properties: NodeProperties::Entity(Box::new(entity.clone())),
+                }))?;
+            }
+        }
+
+        for file in self.files() {
+            for entity in &file.entities {
+                write(&Record::Edge(EdgeRecord {
+                    label: "CONTAINS".to_string(),
+                    from: file.path.clone(),
+                    to: entity.id.clone(),
+                    properties: None,
+                }))?;
+            }
+        }
+
+        for (from, to, count) in self.call_edges() {
+            write(&Record::Edge(EdgeRecord {
+                label: "CALLS".to_string(),
+                from: from.to_string(),
+                to: to.to_string(),
+                properties: Some(serde_json::json!({ "count": count })),
+            }))?;
+        }
+
+        let files = self.tombstones.files().map(|id| ("File", id));
+        let entities = self.tombstones.entities().map(|id| ("Entity", id));
+        for (label, id) in files.chain(entities) {
+            write(&Record::Tombstone(TombstoneRecord {
+                label: label.to_string(),
+                id: id.to_string(),
+            }))?;
+        }
+
+        Ok(())
+    }
+
+    /// 从 NDJSON 文件导入（同路径文件被替换），返回导入的文件数
+    pub fn import_ndjson(&mut self, path: impl AsRef<Path>) -> Result<usize, String> {
+        let path = path.as_ref();
+        let file = fs::File::open(path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        self.read_ndjson(BufReader::new(file))
+    }
+
+    /// 读取 NDJSON，返回导入的文件数
+    pub fn read_ndjson(&mut self, reader: impl BufRead) -> Result<usize, String> {
+        let started = Instant::now();
+        let mut files: Vec<IndexedFile> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        let mut orphans: Vec<IndexedEntity> = Vec::new();
+        let mut tombstones: Vec<TombstoneRecord> = Vec::new();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| e.to_string())?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: Record = serde_json::from_str(&line)
+                .map_err(|e| format!("Invalid NDJSON record at line {}: {}", i + 1, e))?;
+
+            match record {
+                Record::Node(NodeRecord { properties: NodeProperties::File(props), .. }) => {
+                    positions.insert(props.path.clone(), files.len());
+                    files.push(IndexedFile {
+                        path: props.path,
+                        language: props.language,
+                        is_generated: props.is_generated,
+                        role: props.role,
+                        entities: Vec::new(),
+                        call_refs: props.call_refs,
+                    });
+                }
+                Record::Node(NodeRecord { properties: NodeProperties::Entity(entity), .. }) => {
+                    match positions.get(&entity.file_path) {
+                        Some(&pos) => files[pos].entities.push(*entity),
+                        None => orphans.push(*entity),
+                    }
+                }
+                Record::Edge(_) => {}
+                Record::Tombstone(tombstone) => {
+                    if !matches!(tombstone.label.as_str(), "File" | "Entity") {
+                        return Err(format!("Unknown tombstone label {} at line {}", tombstone.label, i + 1));
+                    }
+                    tombstones.push(tombstone);
+                }
+            }
+        }
+
+        if let Some(entity) = orphans.first() {
+            return Err(format!("Entity {} references unknown file {}", entity.id, entity.file_path));
+        }
+
+        let count = files.len();
+        for file in files {
+            let old = self.delete_file(&file.path);
+            self.bury_replaced(old, &file);
+            self.put_file(file);
+        }
+        for tombstone in tombstones {
+            if tombstone.label == "File" {
+                if let Some(file) = self.delete_file(&tombstone.id) {
+                    self.bury_file(file);
+                }
+            } else {
+                self.remove_entity(&tombstone.id);
+            }
+        }
+        self.refresh_rank_hints();
+        self.build_time += started.elapsed();
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::index::tests::{result, with_calls};
+    use crate::CodeIndex;
+
+    #[test]
+    fn test_ndjson_roundtrip() {
+        let mut index = CodeIndex::new();
+        index.insert(result("a.ts", &[("a", "function a() {}")]));
+        index.insert(with_calls(result("b.ts", &[("b", "function b() { a(); }")]), &[("b", "a")]));
+
+        let mut buffer = Vec::new();
+        index.write_ndjson(&mut buffer).unwrap();
+        let text = String::from_utf8(buffer.clone()).unwrap();
+
+        // 节点在前，边在后
+        let types: Vec<&str> = text
+            .lines()
+            .map(|line| if line.starts_with("{\"type\":\"node\"") { "node" } else { "edge" })
+            .collect();
+        assert!(types.windows(2).all(|w| !(w[0] == "edge" && w[1] == "node")));
+        assert!(text.contains("\"label\":\"CALLS\""));
+
+        let mut imported = CodeIndex::new();
+        assert_eq!(imported.read_ndjson(buffer.as_slice()).unwrap(), 2);
+        assert_eq!(imported.entity_count(), index.entity_count());
+
+        let a = imported.entities().find(|e| e.name.as_deref() == Some("a")).unwrap();
+        assert_eq!(a.rank_hints.caller_count, 1);
+    }
+
+    #[test]
+    fn test_ndjson_tombstones_propagate_deletes() {
+        let mut index = CodeIndex::new();
+        index.insert(result("a.ts", &[("a", "function a() {}")]));
+        index.insert(with_calls(result("b.ts", &[("b", "function b() { a(); }")]), &[("b", "a")]));
+        let mut base = Vec::new();
+        index.write_ndjson(&mut base).unwrap();
+
+        index.remove("b.ts");
+        let mut delta = Vec::new();
+        index.write_ndjson(&mut delta).unwrap();
+        let text = String::from_utf8(delta.clone()).unwrap();
+        assert!(text.ends_with("{\"type\":\"tombstone\",\"label\":\"File\",\"id\":\"b.ts\"}\n"));
+
+        let mut replica = CodeIndex::new();
+        replica.read_ndjson(base.as_slice()).unwrap();
+        replica.read_ndjson(delta.as_slice()).unwrap();
+        assert_eq!(replica.file_count(), 1);
+        assert_eq!(replica.entities().next().unwrap().rank_hints.caller_count, 0);
+
+        // 压缩后不再导出删除记录
+        index.compact();
+        let mut compacted = Vec::new();
+        index.write_ndjson(&mut compacted).unwrap();
+        assert!(!String::from_utf8(compacted).unwrap().contains("tombstone"));
+
+        let bad = "{\"type\":\"tombstone\",\"label\":\"Edge\",\"id\":\"x\"}\n";
+        assert!(CodeIndex::new().read_ndjson(bad.as_bytes()).is_err());
+    }
+}
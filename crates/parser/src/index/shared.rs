@@ -0,0 +1,91 @@
+//! 单写多读的共享索引
+//!
+//! 写入方独占 `CodeIndex`，每次写入完成后发布新的快照；读取方只取走已发布快照的
+//! `Arc`，不等待写入（发布与读取只在交换指针时短暂持有锁），查询延迟不受索引进度影响。
+
+use std::sync::{Arc, Mutex, RwLock};
+
+use super::{CodeIndex, IndexSnapshot};
+
+/// 可跨线程共享的索引句柄
+#[derive(Debug, Default)]
+pub struct SharedIndex {
+    /// 串行化写入方
+    writer: Mutex<CodeIndex>,
+    /// 最近一次发布的快照
+    published: RwLock<Arc<IndexSnapshot>>,
+}
+
+impl SharedIndex {
+    /// 包装已有索引并发布其当前内容
+    pub fn new(index: CodeIndex) -> Self {
+        let published = RwLock::new(Arc::new(index.snapshot()));
+        Self {
+            writer: Mutex::new(index),
+            published,
+        }
+    }
+
+    /// 最近一次发布的快照（写入进行中时看到的是写入前的完整内容）
+    pub fn reader(&self) -> Arc<IndexSnapshot> {
+        // 锁中毒只可能来自写入方 panic，已发布的快照仍然完整
+        self.published.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// 执行一次写入，完成后发布新快照
+    ///
+    /// 写入方之间互斥；`f` 中的多次修改对读取方整体可见。
+    pub fn write<T>(&self, f: impl FnOnce(&mut CodeIndex) -> T) -> T {
+        let mut index = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let output = f(&mut index);
+        let snapshot = Arc::new(index.snapshot());
+        *self.published.write().unwrap_or_else(|e| e.into_inner()) = snapshot;
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::tests::result;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+
+    #[test]
+    fn test_readers_see_whole_batches() {
+        let index = Arc::new(SharedIndex::default());
+        let done = Arc::new(AtomicBool::new(false));
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let (index, done) = (index.clone(), done.clone());
+                thread::spawn(move || {
+                    while !done.load(Ordering::Relaxed) {
+                        // 每批写入两个文件：读取方看到的文件数总是偶数
+                        let snapshot = index.reader();
+                        assert_eq!(snapshot.file_count() % 2, 0);
+                        assert_eq!(snapshot.files().count(), snapshot.file_count());
+                    }
+                })
+            })
+            .collect();
+
+        for i in 0..200 {
+            index.write(|index| {
+                index.insert_batch(vec![
+                    result(&format!("src/a{i}.ts"), &[("a", "function a() {}")]),
+                    result(&format!("src/b{i}.ts"), &[("b", "function b() {}")]),
+                ]);
+            });
+        }
+        done.store(true, Ordering::Relaxed);
+        for reader in readers {
+            reader.join().unwrap();
+        }
+
+        let snapshot = index.reader();
+        assert_eq!(snapshot.file_count(), 400);
+        assert!(snapshot.layer_count() > 1);
+        assert!(snapshot.entity("src/a199.ts:1:function:a").is_some());
+    }
+}
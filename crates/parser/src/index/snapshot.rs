@@ -0,0 +1,381 @@
+//! 只读快照与分段存储
+//!
+//! 文件记录以 `Arc` 共享，按类似 LSM 的方式分层存放：
+//! - memtable：最近写入的文件（以及删除标记），容量有限；
+//! - segments：写满后冻结的 memtable，不可变，以 `Arc` 在快照间共享。
+//!
+//! 查找时从新到旧逐层查找，新层覆盖旧层。创建快照只复制 memtable 与 segment 指针，
+//! 代价与索引大小无关；之后的写入只进入写入方自己的 memtable，快照看到的始终是
+//! 创建时的完整文件（不会看到写了一半的更新）。相邻 segment 按大小合并（size-tiered），
+//! 合并到最底层时丢弃删除标记。
+
+use std::collections::{btree_map, BTreeMap, HashMap};
+use std::iter::Peekable;
+use std::ops::Bound;
+use std::sync::Arc;
+
+use super::{IndexedEntity, IndexedFile};
+use crate::graph_filter::GraphFilter;
+use crate::paths::normalize_path;
+use crate::types::FileRole;
+
+/// memtable 冻结为 segment 的文件数
+const MEMTABLE_LIMIT: usize = 256;
+
+/// 路径 -> 文件记录，`None` 为删除标记
+type Segment = BTreeMap<String, Option<Arc<IndexedFile>>>;
+
+/// 索引的只读视图（`CodeIndex::snapshot`），可跨线程共享
+#[derive(Debug, Clone, Default)]
+pub struct IndexSnapshot {
+    /// 不可变分层（旧在前、新在后）
+    segments: Vec<Arc<Segment>>,
+    memtable: Segment,
+    /// 现存文件数
+    len: usize,
+    /// 现存文件中的实体数
+    entities: usize,
+    /// 不参与调用边的文件（见 `CodeIndex::set_graph_filter`）
+    graph_filter: GraphFilter,
+}
+
+impl IndexSnapshot {
+    /// 文件数量
+    pub fn file_count(&self) -> usize {
+        self.len
+    }
+
+    /// 实体数量
+    pub fn entity_count(&self) -> usize {
+        self.entities
+    }
+
+    /// 按路径获取文件
+    pub fn file(&self, path: &str) -> Option<&IndexedFile> {
+        self.get(&normalize_path(path)).map(Arc::as_ref)
+    }
+
+    /// 所有文件（按路径排序）
+    pub fn files(&self) -> impl Iterator<Item = &IndexedFile> {
+        self.live().map(|(_, file)| file.as_ref())
+    }
+
+    /// 所有实体（按文件路径、文件内顺序）
+    pub fn entities(&self) -> impl Iterator<Item = &IndexedEntity> {
+        self.files().flat_map(|f| f.entities.iter())
+    }
+
+    /// 路径前缀下的文件：前缀为文件路径时只含该文件，为目录时含其下所有文件（空前缀为全部）
+    ///
+    /// 各层都按路径有序，只扫描前缀对应的区间，不遍历整个索引。
+    pub fn files_in(&self, path_prefix: &str) -> impl Iterator<Item = &IndexedFile> {
+        let prefix = normalize_path(path_prefix);
+        let prefix = if prefix == "." { "" } else { prefix.trim_end_matches('/') };
+        self.live_in(prefix).map(|(_, file)| file.as_ref())
+    }
+
+    /// 路径前缀（文件或目录）下的所有实体，按文件路径、文件内顺序
+    pub fn entities_in(&self, path_prefix: &str) -> impl Iterator<Item = &IndexedEntity> {
+        self.files_in(path_prefix).flat_map(|f| f.entities.iter())
+    }
+
+    /// 带有指定标签的实体
+    pub fn entities_with_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a IndexedEntity> + 'a {
+        self.entities().filter(move |e| e.tags.iter().any(|t| t == tag))
+    }
+
+    /// 指定角色的文件中的实体
+    pub fn entities_with_role(&self, role: FileRole) -> impl Iterator<Item = &IndexedEntity> {
+        self.files().filter(move |f| f.role == role).flat_map(|f| f.entities.iter())
+    }
+
+    /// 按 ID 查找实体
+    pub fn entity(&self, id: &str) -> Option<&IndexedEntity> {
+        // 路径本身可能含 ':'，依次尝试每个 ':' 之前的前缀作为文件路径
+        id.match_indices(':')
+            .rev()
+            .filter_map(|(i, _)| self.get(&id[..i]))
+            .find_map(|file| file.entities.iter().find(|e| e.id == id))
+    }
+
+    /// 实体的悬停提示（Markdown）
+    pub fn hover_info(&self, id: &str) -> Option<String> {
+        self.entity(id).map(IndexedEntity::hover_markdown)
+    }
+
+    /// 批量生成悬停提示，与 `ids` 按下标对应（找不到的实体为 None）
+    pub fn hover_infos<'a>(&self, ids: impl IntoIterator<Item = &'a str>) -> Vec<Option<String>> {
+        ids.into_iter().map(|id| self.hover_info(id)).collect()
+    }
+
+    /// 文件到被调用实体的边：(文件路径, 实体 ID, 调用次数)
+    ///
+    /// 调用名按实体名字解析，同名实体都会连边；图过滤器排除的文件两端都不连边。
+    pub fn call_edges(&self) -> Vec<(&str, &str, usize)> {
+        let files = || {
+            self.live()
+                .filter(|(path, file)| !self.graph_filter.excludes_file(path, file.is_generated))
+                .map(|(_, file)| file)
+        };
+        let mut by_name: HashMap<&str, Vec<&str>> = HashMap::new();
+        for entity in files().flat_map(|f| f.entities.iter()) {
+            if let Some(name) = &entity.name {
+                by_name.entry(name).or_default().push(&entity.id);
+            }
+        }
+
+        let mut edges = Vec::new();
+        for file in files() {
+            let mut calls: Vec<_> = file.call_refs.iter().collect();
+            calls.sort();
+            for (name, &count) in calls {
+                for target in by_name.get(name.as_str()).into_iter().flatten() {
+                    edges.push((file.path.as_str(), *target, count));
+                }
+            }
+        }
+        edges
+    }
+
+    /// 图过滤器
+    pub fn graph_filter(&self) -> &GraphFilter {
+        &self.graph_filter
+    }
+
+    pub(super) fn set_graph_filter(&mut self, filter: GraphFilter) {
+        self.graph_filter = filter;
+    }
+
+    /// 分层数（segment 数加 memtable）
+    pub fn layer_count(&self) -> usize {
+        self.segments.len() + 1
+    }
+
+    /// 按路径查找（已规范化），新层优先
+    pub(super) fn get(&self, path: &str) -> Option<&Arc<IndexedFile>> {
+        if let Some(entry) = self.memtable.get(path) {
+            return entry.as_ref();
+        }
+        self.segments
+            .iter()
+            .rev()
+            .find_map(|segment| segment.get(path))
+            .and_then(Option::as_ref)
+    }
+
+    /// 合并各层后的现存文件（按路径排序）
+    fn live(&self) -> Live<'_> {
+        self.live_in("")
+    }
+
+    /// 合并各层后路径前缀下的现存文件（前缀须已规范化、不带末尾 `/`）
+    ///
+    /// 各层只取前缀对应的区间，逐个归并，不复制文件列表。
+    fn live_in(&self, prefix: &str) -> Live<'_> {
+        let layers = self
+            .segments
+            .iter()
+            .map(Arc::as_ref)
+            .chain([&self.memtable])
+            .map(|layer| layer.range::<str, _>((Bound::Included(prefix), Bound::Unbounded)).peekable())
+            .collect();
+        Live { layers, prefix: prefix.to_string() }
+    }
+
+    /// 所有现存文件的路径
+    pub(super) fn paths(&self) -> Vec<String> {
+        self.live().map(|(path, _)| path.to_string()).collect()
+    }
+
+    /// 写入（或替换）文件
+    pub(super) fn put(&mut self, file: IndexedFile) {
+        match self.get(&file.path) {
+            Some(old) => self.entities -= old.entities.len(),
+            None => self.len += 1,
+        }
+        self.entities += file.entities.len();
+        self.memtable.insert(file.path.clone(), Some(Arc::new(file)));
+        self.maybe_flush();
+    }
+
+    /// 删除文件，返回删除前的记录
+    pub(super) fn delete(&mut self, path: &str) -> Option<IndexedFile> {
+        let old = self.get(path)?.clone();
+        self.len -= 1;
+        self.entities -= old.entities.len();
+        if self.segments.iter().any(|segment| segment.contains_key(path)) {
+            // 旧层中仍有记录，需要删除标记遮住它
+            self.memtable.insert(path.to_string(), None);
+            self.maybe_flush();
+        } else {
+            self.memtable.remove(path);
+        }
+        Some(Arc::unwrap_or_clone(old))
+    }
+
+    /// 从文件中移除实体，返回移除的实体
+    pub(super) fn take_entity(&mut self, path: &str, id: &str) -> Option<IndexedEntity> {
+        let file = self.get_mut(path)?;
+        let pos = file.entities.iter().position(|entity| entity.id == id)?;
+        let entity = file.entities.remove(pos);
+        self.entities -= 1;
+        Some(entity)
+    }
+
+    /// 可修改的文件记录（写时复制：共享中的记录先复制到 memtable）
+    ///
+    /// 不得增删实体（实体数单独计数），移除实体用 `take_entity`。
+    pub(super) fn get_mut(&mut self, path: &str) -> Option<&mut IndexedFile> {
+        if !matches!(self.memtable.get(path), Some(Some(_))) {
+            let file = self.get(path)?.clone();
+            self.memtable.insert(path.to_string(), Some(file));
+        }
+        // 冻结推迟到下一次 put / delete，保证返回的引用指向 memtable
+        self.memtable.get_mut(path)?.as_mut().map(Arc::make_mut)
+    }
+
+    /// memtable 写满时冻结为 segment，并合并大小相近的相邻 segment
+    fn maybe_flush(&mut self) {
+        if self.memtable.len() < MEMTABLE_LIMIT {
+            return;
+        }
+        self.segments.push(Arc::new(std::mem::take(&mut self.memtable)));
+        while let [.., older, newer] = self.segments.as_slice() {
+            if newer.len() * 2 < older.len() {
+                break;
+            }
+            let newer = self.segments.pop().unwrap();
+            let older = self.segments.pop().unwrap();
+            let is_bottom = self.segments.is_empty();
+            let mut merged = Arc::unwrap_or_clone(older);
+            merged.extend(Arc::unwrap_or_clone(newer));
+            if is_bottom {
+                merged.retain(|_, file| file.is_some());
+            }
+            self.segments.push(Arc::new(merged));
+        }
+    }
+}
+
+/// 各层区间的 k 路归并：同一路径取最新一层的记录，删除标记遮住旧层
+struct Live<'a> {
+    /// 各层从前缀处开始的区间（旧在前、新在后）
+    layers: Vec<Peekable<btree_map::Range<'a, String, Option<Arc<IndexedFile>>>>>,
+    prefix: String,
+}
+
+impl<'a> Iterator for Live<'a> {
+    type Item = (&'a str, &'a Arc<IndexedFile>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let prefix = self.prefix.as_str();
+            let path = self
+                .layers
+                .iter_mut()
+                .filter_map(|layer| layer.peek().map(|&(path, _)| path.as_str()))
+                .filter(|path| path.starts_with(prefix))
+                .min()?;
+            let mut newest = None;
+            for layer in &mut self.layers {
+                if layer.peek().is_some_and(|&(other, _)| other == path) {
+                    newest = layer.next().map(|(_, file)| file);
+                }
+            }
+            // 前缀为目录时不含同名前缀的兄弟路径（`src/a` 不含 `src/ab`）
+            let in_scope = prefix.is_empty() || path.len() == prefix.len() || path.as_bytes()[prefix.len()] == b'/';
+            if let (true, Some(Some(file))) = (in_scope, newest) {
+                return Some((path, file));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::index::tests::{result, with_calls};
+    use crate::CodeIndex;
+
+    #[test]
+    fn test_snapshot_is_isolated_from_updates() {
+        let mut index = CodeIndex::new();
+        index.insert(result("a.ts", &[("a", "function a() {}")]));
+        let snapshot = index.snapshot();
+
+        index.insert(with_calls(result("b.ts", &[("b", "function b() { a(); }")]), &[("b", "a")]));
+        index.insert(result("a.ts", &[("renamed", "function renamed() {}")]));
+        index.remove("b.ts");
+
+        assert_eq!(snapshot.file_count(), 1);
+        let a = snapshot.entity("a.ts:1:function:a").unwrap();
+        assert_eq!(a.rank_hints.caller_count, 0);
+        assert!(snapshot.call_edges().is_empty());
+        assert!(index.entity("a.ts:1:function:a").is_none());
+
+        // 未修改的文件与快照共享同一份记录
+        let current = index.snapshot();
+        let shared = index.snapshot();
+        assert!(std::ptr::eq(current.file("a.ts").unwrap(), shared.file("a.ts").unwrap()));
+    }
+
+    #[test]
+    fn test_segments_flush_merge_and_delete() {
+        let mut index = CodeIndex::new();
+        index.insert_batch((0..1000).map(|i| result(&format!("f{i:04}.ts"), &[("f", "function f() {}")])));
+        let before = index.snapshot();
+        assert!(before.layer_count() > 1);
+
+        for i in (0..1000).step_by(2) {
+            index.remove(&format!("f{i:04}.ts"));
+        }
+        index.insert(result("f0001.ts", &[("g", "function g() {}")]));
+
+        index.insert(result("f0003.ts", &[("f", "function f() {}"), ("h", "function h() {}")]));
+        assert!(index.remove_entity("f0005.ts:1:function:f"));
+
+        let after = index.snapshot();
+        assert_eq!(after.file_count(), 500);
+        assert_eq!(after.files().count(), 500);
+        // 实体数单独计数，与逐个文件统计一致
+        assert_eq!(after.entity_count(), 500);
+        assert_eq!(after.entity_count(), after.entities().count());
+        assert_eq!(before.entity_count(), 1000);
+        assert!(after.file("f0000.ts").is_none());
+        assert_eq!(after.file("f0001.ts").unwrap().entities[0].name.as_deref(), Some("g"));
+        assert_eq!(before.file_count(), 1000);
+        assert_eq!(before.file("f0001.ts").unwrap().entities[0].name.as_deref(), Some("f"));
+    }
+
+    #[test]
+    fn test_entities_in_path_prefix() {
+        let mut index = CodeIndex::new();
+        for path in ["src/billing/a.ts", "src/billing/sub/b.ts", "src/billing2/c.ts", "src/x.ts"] {
+            index.insert(result(path, &[("f", "function f() {}")]));
+        }
+        let files = |prefix: &str| index.entities_in(prefix).map(|e| e.file_path.as_str()).collect::<Vec<_>>();
+
+        assert_eq!(files("src/billing"), vec!["src/billing/a.ts", "src/billing/sub/b.ts"]);
+        assert_eq!(files("./src/billing/"), vec!["src/billing/a.ts", "src/billing/sub/b.ts"]);
+        assert_eq!(files("src/billing/a.ts"), vec!["src/billing/a.ts"]);
+        assert_eq!(files("src/bill"), Vec::<&str>::new());
+        assert_eq!(files("").len(), 4);
+        assert_eq!(files(".").len(), 4);
+
+        // 删除标记遮住旧层中的文件
+        index.insert_batch((0..300).map(|i| result(&format!("lib/{i:03}.ts"), &[("g", "function g() {}")])));
+        index.remove("src/billing/a.ts");
+        assert_eq!(index.snapshot().files_in("src").count(), 3);
+    }
+
+    #[test]
+    fn test_snapshot_sees_consistent_rank_hints() {
+        let mut index = CodeIndex::new();
+        index.insert(result("a.ts", &[("a", "function a() {}")]));
+        index.insert(with_calls(result("b.ts", &[("b", "function b() { a(); }")]), &[("b", "a")]));
+        let before = index.snapshot();
+
+        index.remove("b.ts");
+        assert_eq!(before.entity("a.ts:1:function:a").unwrap().rank_hints.caller_count, 1);
+        assert_eq!(index.entity("a.ts:1:function:a").unwrap().rank_hints.caller_count, 0);
+    }
+}
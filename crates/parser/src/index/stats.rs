@@ -0,0 +1,143 @@
+//! 索引统计（容量规划用）
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::{CodeIndex, IndexedEntity, IndexedFile};
+
+/// 最大文件 / 实体列表的长度
+const LARGEST_LIMIT: usize = 10;
+
+/// 按大小排序的条目
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizedItem {
+    /// 文件路径或实体 ID
+    pub id: String,
+    pub bytes: u64,
+}
+
+/// 索引统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexStats {
+    pub files: usize,
+    pub entities: usize,
+    /// 节点类型（`file` 与各实体类型）-> 数量
+    pub nodes_by_kind: BTreeMap<String, usize>,
+    /// 语言 -> 节点数（文件与实体）
+    pub nodes_by_language: BTreeMap<String, usize>,
+    /// 边类型（`CONTAINS` / `CALLS`）-> 数量
+    pub edges_by_kind: BTreeMap<String, usize>,
+    /// 索引内容的估算字节数（字符串负载，不含容器开销）
+    pub storage_bytes: u64,
+    /// 实体代码片段总字节数最多的文件
+    pub largest_files: Vec<SizedItem>,
+    /// 代码片段最长的实体
+    pub largest_entities: Vec<SizedItem>,
+    /// 写入、导入累计耗时
+    pub build_ms: u64,
+    /// 尚未压缩的墓碑数（文件与实体）
+    pub tombstones: usize,
+    /// 墓碑占用的估算字节数（`compact` 可回收）
+    pub tombstone_bytes: u64,
+}
+
+impl CodeIndex {
+    /// 统计节点 / 边数量、存储大小、最大的文件与实体以及构建耗时
+    pub fn index_stats(&self) -> IndexStats {
+        let mut stats = IndexStats {
+            files: self.file_count(),
+            entities: self.entity_count(),
+            build_ms: self.build_time.as_millis() as u64,
+            tombstones: self.tombstones.len(),
+            tombstone_bytes: self.tombstones.bytes(),
+            ..Default::default()
+        };
+        let mut files = Vec::new();
+        let mut entities = Vec::new();
+
+        for file in self.files() {
+            *stats.nodes_by_kind.entry("file".to_string()).or_default() += 1;
+            *stats.nodes_by_language.entry(file.language.clone()).or_default() += 1;
+            stats.storage_bytes += file_record_bytes(file);
+
+            let mut file_bytes = 0;
+            for entity in &file.entities {
+                *stats.nodes_by_kind.entry(entity.kind.clone()).or_default() += 1;
+                *stats.nodes_by_language.entry(entity.language.clone()).or_default() += 1;
+                stats.storage_bytes += entity_bytes(entity);
+                file_bytes += entity.text.len() as u64;
+                entities.push(SizedItem {
+                    id: entity.id.clone(),
+                    bytes: entity.text.len() as u64,
+                });
+            }
+            files.push(SizedItem {
+                id: file.path.clone(),
+                bytes: file_bytes,
+            });
+        }
+
+        stats.edges_by_kind.insert("CONTAINS".to_string(), stats.entities);
+        stats.edges_by_kind.insert("CALLS".to_string(), self.call_edges().len());
+        stats.largest_files = largest(files);
+        stats.largest_entities = largest(entities);
+        stats
+    }
+}
+
+/// 文件记录自身（不含实体）的估算字节数
+pub(super) fn file_record_bytes(file: &IndexedFile) -> u64 {
+    (file.path.len() + file.language.len() + file.call_refs.keys().map(String::len).sum::<usize>()) as u64
+}
+
+/// 实体的估算字节数
+pub(super) fn entity_bytes(entity: &IndexedEntity) -> u64 {
+    (entity.id.len()
+        + entity.file_path.len()
+        + entity.language.len()
+        + entity.kind.len()
+        + entity.name.as_ref().map_or(0, String::len)
+        + entity.text.len()
+        + entity.tags.iter().map(String::len).sum::<usize>()) as u64
+}
+
+/// 按大小降序（相同大小按 ID）取前 `LARGEST_LIMIT` 个
+fn largest(mut items: Vec<SizedItem>) -> Vec<SizedItem> {
+    items.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.id.cmp(&b.id)));
+    items.truncate(LARGEST_LIMIT);
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::tests::{result, with_calls};
+
+    #[test]
+    fn test_index_stats() {
+        let mut index = CodeIndex::new();
+        let mut a = with_calls(
+            result("src/a.ts", &[("helper", "function helper() {}"), ("A", "class A { run() { helper(); } }")]),
+            &[("A", "helper")],
+        );
+        a.entity_info[1].kind = "class".to_string();
+        let mut b = with_calls(result("lib/b.py", &[("main", "def main(): helper()")]), &[("main", "helper")]);
+        b.language = "Python".to_string();
+        index.insert_batch(vec![a, b]);
+
+        let stats = index.index_stats();
+        assert_eq!(stats.files, 2);
+        assert_eq!(stats.entities, 3);
+        assert_eq!(stats.nodes_by_kind.get("file"), Some(&2));
+        assert_eq!(stats.nodes_by_kind.get("function"), Some(&2));
+        assert_eq!(stats.nodes_by_language.get("TypeScript"), Some(&3));
+        assert_eq!(stats.nodes_by_language.get("Python"), Some(&2));
+        assert_eq!(stats.edges_by_kind.get("CONTAINS"), Some(&3));
+        assert_eq!(stats.edges_by_kind.get("CALLS"), Some(&2));
+        assert_eq!(stats.largest_files[0], SizedItem { id: "src/a.ts".to_string(), bytes: 51 });
+        assert_eq!(stats.largest_entities[0].id, "src/a.ts:2:class:A");
+        assert!(stats.storage_bytes > 51 + 20);
+    }
+}
@@ -0,0 +1,193 @@
+//! 实体标签规则
+//!
+//! 规则文件为 JSON，按顺序匹配，命中的规则的标签全部加到实体上：
+//!
+//! ```json
+//! {
+//!   "rules": [
+//!     { "glob": "src/api/**", "kind": "function", "tags": ["api-layer"] },
+//!     { "regex": "@deprecated", "tags": ["legacy"] },
+//!     { "glob": "**/experimental/**", "name": "^unstable_", "tags": ["experimental"] }
+//!   ]
+//! }
+//! ```
+//!
+//! 同一条规则中的条件需全部满足，未给出的条件不参与匹配。
+
+use globset::{Glob, GlobMatcher};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use super::IndexedEntity;
+
+/// 单条标签规则
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TagRule {
+    /// 文件路径 glob（匹配索引中的规范化路径）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub glob: Option<String>,
+    /// 实体类型（function / class / method ...）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// 匹配实体名字的正则
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// 匹配实体代码片段的正则
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// 规则文件内容
+#[derive(Debug, Deserialize)]
+struct TagRulesFile {
+    rules: Vec<TagRule>,
+}
+
+/// 编译后的规则
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    glob: Option<GlobMatcher>,
+    kind: Option<String>,
+    name: Option<Regex>,
+    regex: Option<Regex>,
+    tags: Vec<String>,
+}
+
+impl CompiledRule {
+    fn matches(&self, entity: &IndexedEntity) -> bool {
+        self.glob.as_ref().is_none_or(|glob| glob.is_match(&entity.file_path))
+            && self.kind.as_ref().is_none_or(|kind| *kind == entity.kind)
+            && self
+                .name
+                .as_ref()
+                .is_none_or(|re| entity.name.as_deref().is_some_and(|name| re.is_match(name)))
+            && self.regex.as_ref().is_none_or(|re| re.is_match(&entity.text))
+    }
+}
+
+/// 一组标签规则（写入索引时对每个实体求值）
+#[derive(Debug, Clone, Default)]
+pub struct TagRules {
+    rules: Vec<CompiledRule>,
+}
+
+impl TagRules {
+    /// 编译规则，glob 或正则无效时返回错误
+    pub fn new(rules: Vec<TagRule>) -> Result<Self, String> {
+        let rules = rules
+            .into_iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let regex = |pattern: Option<String>| {
+                    pattern
+                        .map(|p| Regex::new(&p).map_err(|e| format!("Invalid regex in tag rule {}: {}", i, e)))
+                        .transpose()
+                };
+                Ok(CompiledRule {
+                    glob: rule
+                        .glob
+                        .map(|g| {
+                            Glob::new(&g)
+                                .map(|glob| glob.compile_matcher())
+                                .map_err(|e| format!("Invalid glob in tag rule {}: {}", i, e))
+                        })
+                        .transpose()?,
+                    kind: rule.kind,
+                    name: regex(rule.name)?,
+                    regex: regex(rule.regex)?,
+                    tags: rule.tags,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { rules })
+    }
+
+    /// 从 JSON 文本解析（`{"rules": [...]}`）
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let file: TagRulesFile =
+            serde_json::from_str(json).map_err(|e| format!("Invalid tag rules: {}", e))?;
+        Self::new(file.rules)
+    }
+
+    /// 从规则文件加载
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::from_json(&json)
+    }
+
+    /// 是否没有规则
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// 实体命中的标签（按规则顺序，去重）
+    pub fn tags_for(&self, entity: &IndexedEntity) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for rule in self.rules.iter().filter(|rule| rule.matches(entity)) {
+            for tag in &rule.tags {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+        }
+        tags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::RankHints;
+    use crate::types::Range;
+
+    fn entity(path: &str, kind: &str, name: &str, text: &str) -> IndexedEntity {
+        IndexedEntity {
+            id: format!("{}:1:{}:{}", path, kind, name),
+            file_path: path.to_string(),
+            language: "TypeScript".to_string(),
+            kind: kind.to_string(),
+            name: Some(name.to_string()),
+            range: Range { start: 1, end: 1 },
+            text: text.to_string(),
+            signature: None,
+            doc: None,
+            tags: Vec::new(),
+            calls: Default::default(),
+            rank_hints: RankHints::default(),
+        }
+    }
+
+    #[test]
+    fn test_tags_for() {
+        let rules = TagRules::from_json(
+            r#"{"rules": [
+                {"glob": "src/api/**", "kind": "function", "tags": ["api-layer"]},
+                {"regex": "@deprecated", "tags": ["legacy"]},
+                {"name": "^unstable_", "tags": ["experimental", "legacy"]}
+            ]}"#,
+        )
+        .unwrap();
+
+        let handler = entity("src/api/users.ts", "function", "getUser", "function getUser()");
+        assert_eq!(rules.tags_for(&handler), vec!["api-layer"]);
+        let class = entity("src/api/users.ts", "class", "UserApi", "/** @deprecated */ class UserApi");
+        assert_eq!(rules.tags_for(&class), vec!["legacy"]);
+        let unstable = entity("src/lib.ts", "function", "unstable_run", "/** @deprecated */ function unstable_run()");
+        assert_eq!(rules.tags_for(&unstable), vec!["legacy", "experimental"]);
+        assert!(rules.tags_for(&entity("src/lib.ts", "function", "run", "function run()")).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let error = TagRules::from_json(r#"{"rules": [{"regex": "(", "tags": ["x"]}]}"#).unwrap_err();
+        assert!(error.contains("tag rule 0"), "{}", error);
+        assert!(TagRules::from_json(r#"{"rules": [{"glob": "a/{b", "tags": []}]}"#).is_err());
+        assert!(TagRules::from_json("[]").is_err());
+    }
+}
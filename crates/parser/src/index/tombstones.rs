@@ -0,0 +1,193 @@
+//! 删除墓碑与压缩
+//!
+//! 删除文件、或重新写入文件后消失的实体不会立即丢弃，而是留下墓碑：
+//! 导出（NDJSON / Cypher）时带上删除记录，下游图数据库据此同步删除。
+//! 长期运行的 watch 模式索引需定期调用 `compact` 回收墓碑占用的空间。
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::stats::{entity_bytes, file_record_bytes};
+use super::{CodeIndex, IndexedEntity, IndexedFile};
+
+/// 已删除、尚未压缩的文件与实体
+#[derive(Debug, Default)]
+pub struct Tombstones {
+    files: BTreeMap<String, IndexedFile>,
+    entities: BTreeMap<String, IndexedEntity>,
+}
+
+impl Tombstones {
+    /// 墓碑数（文件与实体）
+    pub fn len(&self) -> usize {
+        self.files.len() + self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.entities.is_empty()
+    }
+
+    /// 已删除的文件路径
+    pub fn files(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(String::as_str)
+    }
+
+    /// 已删除的实体 ID（不含随文件一起删除的实体）
+    pub fn entities(&self) -> impl Iterator<Item = &str> {
+        self.entities.keys().map(String::as_str)
+    }
+
+    /// 墓碑占用的估算字节数
+    pub fn bytes(&self) -> u64 {
+        let files: u64 = self
+            .files
+            .values()
+            .map(|file| file_record_bytes(file) + file.entities.iter().map(entity_bytes).sum::<u64>())
+            .sum();
+        files + self.entities.values().map(entity_bytes).sum::<u64>()
+    }
+
+    /// 压缩统计（回收的文件数、实体数与字节数）
+    pub fn stats(&self) -> CompactStats {
+        CompactStats {
+            files: self.files.len(),
+            entities: self.entities.len() + self.files.values().map(|file| file.entities.len()).sum::<usize>(),
+            bytes: self.bytes(),
+        }
+    }
+}
+
+/// `compact` 回收的内容
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactStats {
+    pub files: usize,
+    /// 实体数（含随文件删除的实体）
+    pub entities: usize,
+    pub bytes: u64,
+}
+
+impl CodeIndex {
+    /// 当前的墓碑
+    pub fn tombstones(&self) -> &Tombstones {
+        &self.tombstones
+    }
+
+    /// 丢弃所有墓碑，返回回收的内容
+    pub fn compact(&mut self) -> CompactStats {
+        self.take_tombstones().stats()
+    }
+
+    /// 取出所有墓碑（索引中立即清空），便于在其他线程释放
+    pub fn take_tombstones(&mut self) -> Tombstones {
+        std::mem::take(&mut self.tombstones)
+    }
+
+    /// 删除单个实体（留下墓碑），返回是否存在
+    pub fn remove_entity(&mut self, id: &str) -> bool {
+        let Some(path) = self.entity(id).map(|entity| entity.file_path.clone()) else {
+            return false;
+        };
+        let Some(entity) = self.current.take_entity(&path, id) else {
+            return false;
+        };
+        let Some(file) = self.current.get_mut(&path) else {
+            return false;
+        };
+
+        let call_refs: HashMap<String, usize> = entity.calls.iter().map(|(name, &count)| (name.clone(), count)).collect();
+        for (name, count) in &call_refs {
+            if let Some(total) = file.call_refs.get_mut(name) {
+                *total = total.saturating_sub(*count);
+                if *total == 0 {
+                    file.call_refs.remove(name);
+                }
+            }
+        }
+        let is_generated = file.is_generated;
+        let still_defined = entity.name.is_some() && file.entities.iter().any(|other| other.name == entity.name);
+        self.forget_call_refs(&path, is_generated, &call_refs);
+        if let (Some(name), false) = (&entity.name, still_defined) {
+            self.forget_definition(name, &path);
+        }
+        self.tombstones.entities.insert(entity.id.clone(), entity);
+        self.refresh_rank_hints();
+        true
+    }
+
+    /// 文件被删除：整体移入墓碑
+    pub(super) fn bury_file(&mut self, file: IndexedFile) {
+        self.tombstones.files.insert(file.path.clone(), file);
+    }
+
+    /// 文件被重新写入：旧版本中不再存在的实体留下墓碑，重新出现的路径与实体撤销墓碑
+    pub(super) fn bury_replaced(&mut self, old: Option<IndexedFile>, current: &IndexedFile) {
+        self.tombstones.files.remove(&current.path);
+        for entity in &current.entities {
+            self.tombstones.entities.remove(&entity.id);
+        }
+        let Some(old) = old else {
+            return;
+        };
+        let current_ids: HashSet<&str> = current.entities.iter().map(|entity| entity.id.as_str()).collect();
+        for entity in old.entities {
+            if !current_ids.contains(entity.id.as_str()) {
+                self.tombstones.entities.insert(entity.id.clone(), entity);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::index::tests::{result, with_calls};
+    use crate::CodeIndex;
+
+    #[test]
+    fn test_remove_leaves_tombstone_until_compact() {
+        let mut index = CodeIndex::new();
+        index.insert(result("a.ts", &[("a", "function a() {}")]));
+        index.insert(with_calls(result("b.ts", &[("b", "function b() { a(); }")]), &[("b", "a")]));
+
+        assert!(index.remove("b.ts"));
+        assert_eq!(index.file_count(), 1);
+        assert_eq!(index.tombstones().files().collect::<Vec<_>>(), vec!["b.ts"]);
+        assert_eq!(index.entities().next().unwrap().rank_hints.caller_count, 0);
+
+        let bytes = index.tombstones().bytes();
+        let stats = index.compact();
+        assert_eq!((stats.files, stats.entities, stats.bytes), (1, 1, bytes));
+        assert!(index.tombstones().is_empty());
+        assert_eq!(index.compact().bytes, 0);
+    }
+
+    #[test]
+    fn test_reinsert_buries_vanished_entities() {
+        let mut index = CodeIndex::new();
+        index.insert(result("a.ts", &[("a", "function a() {}"), ("b", "function b() {}")]));
+        index.insert(result("a.ts", &[("a", "function a() {}")]));
+        assert_eq!(index.tombstones().entities().collect::<Vec<_>>(), vec!["a.ts:2:function:b"]);
+
+        // 实体重新出现时撤销墓碑
+        index.insert(result("a.ts", &[("a", "function a() {}"), ("b", "function b() {}")]));
+        assert!(index.tombstones().is_empty());
+
+        index.remove("a.ts");
+        index.insert(result("a.ts", &[("a", "function a() {}")]));
+        assert_eq!(index.tombstones().files().count(), 0);
+    }
+
+    #[test]
+    fn test_remove_entity() {
+        let mut index = CodeIndex::new();
+        index.insert(with_calls(result("a.ts", &[("a", "function a() {}"), ("b", "function b() { a(); }")]), &[("b", "a")]));
+        assert_eq!(index.entity("a.ts:1:function:a").unwrap().rank_hints.caller_count, 1);
+
+        assert!(index.remove_entity("a.ts:2:function:b"));
+        assert!(!index.remove_entity("a.ts:2:function:b"));
+        assert_eq!(index.entity_count(), 1);
+        assert_eq!(index.entity("a.ts:1:function:a").unwrap().rank_hints.caller_count, 0);
+        assert!(index.call_edges().is_empty());
+        assert_eq!(index.compact().entities, 1);
+    }
+}
@@ -0,0 +1,180 @@
+//! 解析结果的持久化存储（sled）
+//!
+//! 按文件路径保存 `ParseResult` 及其源码的内容哈希。重新扫描时内容未变的文件直接复用
+//! 存储的结果，只解析修改过的文件；删除的文件在扫描结束时清理。解析器版本或解析选项
+//! 变化后（见 `sync_fingerprint`）存储整体失效。
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::summary_cache::content_hash;
+use crate::types::{ParseOptions, ParseResult};
+
+const RESULTS_TREE: &str = "results";
+const FINGERPRINT_KEY: &[u8] = b"fingerprint";
+
+lazy_static! {
+    /// 进程内已打开的数据库（sled 对目录加独占锁，同一目录重复打开会失败）
+    static ref OPEN_STORES: Mutex<HashMap<PathBuf, sled::Db>> = Mutex::new(HashMap::new());
+}
+
+/// 一条存储记录
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredResult {
+    hash: String,
+    result: ParseResult,
+}
+
+/// 解析结果存储（可 clone，底层共享同一个数据库）
+#[derive(Clone)]
+pub struct IndexStore {
+    db: sled::Db,
+    results: sled::Tree,
+}
+
+impl fmt::Debug for IndexStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexStore").field("len", &self.len()).finish()
+    }
+}
+
+/// 解析器版本 + 解析选项的指纹，任一变化时存储的结果不再可用
+pub fn store_fingerprint(options: &ParseOptions) -> String {
+    let options = serde_json::to_string(options).unwrap_or_default();
+    content_hash(&format!("{}\n{}", env!("CARGO_PKG_VERSION"), options))
+}
+
+impl IndexStore {
+    /// 打开（或创建）存储目录；同一进程内多次打开同一目录共享一个数据库
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let open_error = |e: &dyn fmt::Display| format!("Failed to open index store {}: {}", path.display(), e);
+        fs::create_dir_all(path).map_err(|e| open_error(&e))?;
+        let key = fs::canonicalize(path).map_err(|e| open_error(&e))?;
+
+        let mut stores = OPEN_STORES.lock().unwrap_or_else(|e| e.into_inner());
+        let db = match stores.get(&key) {
+            Some(db) => db.clone(),
+            None => {
+                let db = sled::open(&key).map_err(|e| open_error(&e))?;
+                stores.insert(key, db.clone());
+                db
+            }
+        };
+        let results = db.open_tree(RESULTS_TREE).map_err(store_error)?;
+        Ok(Self { db, results })
+    }
+
+    /// 检查指纹，不一致时清空全部结果并记录新指纹，返回是否清空
+    pub fn sync_fingerprint(&self, fingerprint: &str) -> Result<bool, String> {
+        let stored = self.db.get(FINGERPRINT_KEY).map_err(store_error)?;
+        if stored.as_deref() == Some(fingerprint.as_bytes()) {
+            return Ok(false);
+        }
+        self.results.clear().map_err(store_error)?;
+        self.db.insert(FINGERPRINT_KEY, fingerprint.as_bytes()).map_err(store_error)?;
+        Ok(true)
+    }
+
+    /// 内容未变时返回存储的结果
+    pub fn get(&self, path: &str, content: &str) -> Result<Option<ParseResult>, String> {
+        let Some(bytes) = self.results.get(path.as_bytes()).map_err(store_error)? else {
+            return Ok(None);
+        };
+        // 无法解码的旧记录视为未命中，随后被覆盖
+        let Ok(stored) = serde_json::from_slice::<StoredResult>(&bytes) else {
+            return Ok(None);
+        };
+        Ok((stored.hash == content_hash(content)).then_some(stored.result))
+    }
+
+    /// 写入文件的解析结果
+    pub fn put(&self, path: &str, content: &str, result: &ParseResult) -> Result<(), String> {
+        self.put_hashed(path, content_hash(content), result)
+    }
+
+    /// 同 `put`，传入预先计算的内容哈希（`content_hash`）
+    pub fn put_hashed(&self, path: &str, hash: String, result: &ParseResult) -> Result<(), String> {
+        let stored = StoredResult {
+            hash,
+            result: result.clone(),
+        };
+        let bytes = serde_json::to_vec(&stored).map_err(|e| e.to_string())?;
+        self.results.insert(path.as_bytes(), bytes).map_err(store_error)?;
+        Ok(())
+    }
+
+    /// 移除文件，返回是否存在
+    pub fn remove(&self, path: &str) -> Result<bool, String> {
+        Ok(self.results.remove(path.as_bytes()).map_err(store_error)?.is_some())
+    }
+
+    /// 移除 `prefix` 下不在 `live` 中的文件（已删除或不再参与扫描），返回移除数量
+    pub fn prune(&self, prefix: &str, live: &HashSet<&str>) -> Result<usize, String> {
+        let mut removed = 0;
+        for entry in self.results.scan_prefix(prefix.as_bytes()) {
+            let (key, _) = entry.map_err(store_error)?;
+            let path = String::from_utf8_lossy(&key);
+            if !live.contains(path.as_ref()) {
+                self.results.remove(&key).map_err(store_error)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// 存储的文件数
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// 等待写入落盘
+    pub fn flush(&self) -> Result<(), String> {
+        self.db.flush().map(|_| ()).map_err(store_error)
+    }
+}
+
+fn store_error(e: sled::Error) -> String {
+    format!("Index store error: {}", e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use crate::LanguageManager;
+
+    #[test]
+    fn test_store_roundtrip() {
+        let dir = temp_dir("index-store", "roundtrip");
+        let code = "export function a() {}\n";
+        let result = LanguageManager::new().parse_file("/ws/a.ts", code).unwrap();
+        {
+            let store = IndexStore::open(&dir).unwrap();
+            assert!(store.sync_fingerprint("v1").unwrap());
+            store.put("/ws/a.ts", code, &result).unwrap();
+            store.put("/ws/gone.ts", code, &result).unwrap();
+            store.flush().unwrap();
+        }
+
+        let store = IndexStore::open(&dir).unwrap();
+        assert!(!store.sync_fingerprint("v1").unwrap());
+        assert_eq!(store.get("/ws/a.ts", code).unwrap().unwrap().entities, result.entities);
+        assert!(store.get("/ws/a.ts", "export function b() {}\n").unwrap().is_none());
+
+        assert_eq!(store.prune("/ws/", &HashSet::from(["/ws/a.ts"])).unwrap(), 1);
+        assert_eq!(store.len(), 1);
+        assert!(store.sync_fingerprint("v2").unwrap());
+        assert!(store.is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+use std::borrow::Cow;
+
+use tree_sitter::Node;
+
+/// 检测文件是否声明了 Flow（`// @flow` 或 `/* @flow */`，只检查文件头部注释）
+pub fn is_flow_file(source_code: &str) -> bool {
+    for line in source_code.lines().take(20) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if !(trimmed.starts_with("//") || trimmed.starts_with("/*") || trimmed.starts_with('*')) {
+            return false;
+        }
+        if trimmed.contains("@flow") {
+            return true;
+        }
+    }
+    false
+}
+
+/// 将 TSX 语法不认识的 Flow 关键字替换为等长空白
+///
+/// 保持字节偏移不变，这样解析树上的范围仍然可以直接回到原始源码取文本。
+/// 目前处理 `opaque type` 和 `declare export`。
+pub fn sanitize_flow(source_code: &str) -> Cow<'_, str> {
+    const PATTERNS: [(&str, usize); 2] = [
+        // (模式, 需要抹掉的前缀长度)
+        ("opaque type ", "opaque ".len()),
+        ("declare export ", "declare ".len()),
+    ];
+
+    if !PATTERNS.iter().any(|(p, _)| source_code.contains(p)) {
+        return Cow::Borrowed(source_code);
+    }
+
+    let mut bytes = source_code.as_bytes().to_vec();
+    for (pattern, blank_len) in PATTERNS {
+        let mut offset = 0;
+        while let Some(pos) = source_code[offset..].find(pattern) {
+            let start = offset + pos;
+            let at_line_start = source_code[..start]
+                .chars()
+                .next_back()
+                .is_none_or(|c| c.is_whitespace() || c == ';');
+            if at_line_start {
+                bytes[start..start + blank_len].fill(b' ');
+            }
+            offset = start + pattern.len();
+        }
+    }
+
+    // 只替换了 ASCII 字节，结果仍是合法 UTF-8
+    Cow::Owned(String::from_utf8(bytes).unwrap_or_else(|_| source_code.to_string()))
+}
+
+/// 节点是否位于 ERROR 节点内部（容错模式下这些捕获不可信）
+pub fn is_inside_error(node: Node) -> bool {
+    let mut current = Some(node);
+    while let Some(n) = current {
+        if n.is_error() {
+            return true;
+        }
+        current = n.parent();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_flow_file() {
+        assert!(is_flow_file("// @flow\nconst a = 1;"));
+        assert!(is_flow_file("/**\n * @flow strict\n */\nexport type A = ?string;"));
+        assert!(!is_flow_file("const a = 1;\n// @flow"));
+    }
+
+    #[test]
+    fn test_sanitize_flow_keeps_offsets() {
+        let code = "opaque type ID = string;\ndeclare export function foo(): void;";
+        let sanitized = sanitize_flow(code);
+        assert_eq!(sanitized.len(), code.len());
+        assert!(sanitized.starts_with("       type ID"));
+        assert!(sanitized.contains("        export function foo"));
+    }
+}
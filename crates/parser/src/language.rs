@@ -1,4 +1,5 @@
 use std::fmt;
+use std::str::FromStr;
 
 /// 支持的编程语言（15种，对标 repomix）
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
@@ -31,6 +32,28 @@ pub enum SupportedLanguage {
     Css,
     #[cfg(feature = "vue")]
     Vue,
+    #[cfg(feature = "sql")]
+    Sql,
+    #[cfg(feature = "graphql")]
+    GraphQL,
+    #[cfg(feature = "proto")]
+    Protobuf,
+    #[cfg(feature = "elixir")]
+    Elixir,
+    #[cfg(feature = "erlang")]
+    Erlang,
+    #[cfg(feature = "zig")]
+    Zig,
+    #[cfg(feature = "nim")]
+    Nim,
+    #[cfg(feature = "config-files")]
+    Json,
+    #[cfg(feature = "config-files")]
+    Yaml,
+    #[cfg(feature = "config-files")]
+    Toml,
+    #[cfg(feature = "markdown")]
+    Markdown,
 }
 
 impl fmt::Display for SupportedLanguage {
@@ -64,6 +87,28 @@ impl fmt::Display for SupportedLanguage {
             Self::Css => "CSS",
             #[cfg(feature = "vue")]
             Self::Vue => "Vue",
+            #[cfg(feature = "sql")]
+            Self::Sql => "SQL",
+            #[cfg(feature = "graphql")]
+            Self::GraphQL => "GraphQL",
+            #[cfg(feature = "proto")]
+            Self::Protobuf => "Protobuf",
+            #[cfg(feature = "elixir")]
+            Self::Elixir => "Elixir",
+            #[cfg(feature = "erlang")]
+            Self::Erlang => "Erlang",
+            #[cfg(feature = "zig")]
+            Self::Zig => "Zig",
+            #[cfg(feature = "nim")]
+            Self::Nim => "Nim",
+            #[cfg(feature = "config-files")]
+            Self::Json => "JSON",
+            #[cfg(feature = "config-files")]
+            Self::Yaml => "YAML",
+            #[cfg(feature = "config-files")]
+            Self::Toml => "TOML",
+            #[cfg(feature = "markdown")]
+            Self::Markdown => "Markdown",
         };
         write!(f, "{}", name)
     }
@@ -116,6 +161,64 @@ impl SupportedLanguage {
         #[cfg(feature = "vue")]
         langs.push(Self::Vue);
         
+        #[cfg(feature = "sql")]
+        langs.push(Self::Sql);
+        
+        #[cfg(feature = "graphql")]
+        langs.push(Self::GraphQL);
+        
+        #[cfg(feature = "proto")]
+        langs.push(Self::Protobuf);
+        
+        #[cfg(feature = "elixir")]
+        langs.push(Self::Elixir);
+        
+        #[cfg(feature = "erlang")]
+        langs.push(Self::Erlang);
+        
+        #[cfg(feature = "zig")]
+        langs.push(Self::Zig);
+        
+        #[cfg(feature = "nim")]
+        langs.push(Self::Nim);
+        
+        #[cfg(feature = "config-files")]
+        langs.extend([Self::Json, Self::Yaml, Self::Toml]);
+        
+        #[cfg(feature = "markdown")]
+        langs.push(Self::Markdown);
+        
         langs
     }
 }
+
+impl FromStr for SupportedLanguage {
+    type Err = String;
+
+    /// 按名字解析语言（不区分大小写，支持常见别名，如 `ts` / `py` / `c++`）
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let lower = name.trim().to_lowercase();
+        let alias = match lower.as_str() {
+            "ts" => "typescript",
+            "js" => "javascript",
+            "py" => "python",
+            "golang" => "go",
+            "rs" | "rust-lang" => "rust",
+            "cpp" | "cxx" => "c++",
+            "cs" | "csharp" => "c#",
+            "rb" => "ruby",
+            "sol" => "solidity",
+            "gql" => "graphql",
+            "proto" => "protobuf",
+            "ex" | "exs" => "elixir",
+            "erl" => "erlang",
+            "yml" => "yaml",
+            "md" => "markdown",
+            other => other,
+        };
+        Self::all()
+            .into_iter()
+            .find(|lang| lang.to_string().to_lowercase() == alias)
+            .ok_or_else(|| format!("Unknown or disabled language: {}", name))
+    }
+}
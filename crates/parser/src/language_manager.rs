@@ -1,68 +1,344 @@
 use tree_sitter::{Language, Parser, Query, QueryCursor};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, RwLock};
+use std::path::Path;
+use std::time::Instant;
 
 use crate::language::SupportedLanguage;
-use crate::strategies::{create_strategy, Capture, ParseStrategy};
+use crate::strategies::{
+    create_strategy, definition_node, function_definition, function_signature, get_node_text, CallSyntax, Capture, ParseStrategy,
+};
 use crate::queries::get_query;
-use crate::ext_to_lang::guess_language;
-use crate::types::ParseResult;
+use crate::ext_to_lang::{
+    classify_path, file_role, guess_language, guess_language_for_source, has_generated_marker,
+};
+use crate::js_dialect::{is_flow_file, is_inside_error, sanitize_flow};
+use crate::hooks::{apply_entity_hook, EntityHook};
+use crate::metrics::{apply_metrics, entity_complexity, FUNCTION_KINDS};
+use crate::encoding::{decode_source, DecodedSource};
+use crate::file_reader::read_source;
+use crate::directives::file_directives;
+use crate::signature::normalize_signatures;
+use crate::truncation::apply_entity_caps;
+use crate::ordering::apply_entity_order;
+use crate::summary_cache::content_hash;
+use crate::nesting::{enclosing_scopes, link_entities};
+use crate::frameworks::react;
+#[cfg(feature = "vue")]
+use crate::sfc::script_blocks;
+#[cfg(any(feature = "vue", feature = "markdown"))]
+use crate::sfc::mask_outside;
+#[cfg(feature = "markdown")]
+use crate::markdown::{code_blocks, document_links};
+#[cfg(feature = "parallel")]
+use crate::throttle::Throttle;
+#[cfg(feature = "plugins")]
+use crate::plugin::LanguagePack;
+#[cfg(feature = "plugins")]
+use crate::strategies::PluginStrategy;
+use crate::throttle::ThrottleOptions;
+#[cfg(feature = "parallel")]
+use crate::types::{BatchProgress, FileOutcome};
+use crate::types::{
+    BodyMode, CallReference, ElementUsage, EntityInfo, FileSummary, ParseError, ParseOptions, ParseResult, ParserConfig,
+    Range, SamplingOptions,
+};
 
-/// 语言资源（Parser + Query + Strategy）
+/// 语言资源（Parser 池 + Query + Strategy），在并发解析间共享
 struct LanguageResources {
-    #[allow(dead_code)]
     language: Language,
-    parser: Parser,
+    /// 空闲的 Parser（Parser 不是 Sync，每次解析借出一个，用完归还）
+    parsers: Mutex<Vec<Parser>>,
     query: Query,
     strategy: Box<dyn ParseStrategy>,
+    /// 只出现在自定义 query 中的捕获名（策略不认识，按定义节点原文提取）
+    extra_captures: HashSet<String>,
+}
+
+impl LanguageResources {
+    /// 借出一个 Parser：池为空时新建，guard 释放时归还
+    fn checkout(&self) -> Result<PooledParser<'_>, String> {
+        let idle = self.parsers.lock().unwrap_or_else(|e| e.into_inner()).pop();
+        let parser = match idle {
+            Some(parser) => parser,
+            None => {
+                let mut parser = Parser::new();
+                parser
+                    .set_language(&self.language)
+                    .map_err(|e| format!("Failed to set language: {}", e))?;
+                parser
+            }
+        };
+        Ok(PooledParser {
+            parser: Some(parser),
+            pool: &self.parsers,
+        })
+    }
+
+    fn idle_parsers(&self) -> usize {
+        self.parsers.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+/// 借出的 Parser，drop 时归还到所属语言的池中
+struct PooledParser<'a> {
+    parser: Option<Parser>,
+    pool: &'a Mutex<Vec<Parser>>,
+}
+
+impl Deref for PooledParser<'_> {
+    type Target = Parser;
+
+    fn deref(&self) -> &Parser {
+        self.parser.as_ref().expect("parser checked in")
+    }
+}
+
+impl DerefMut for PooledParser<'_> {
+    fn deref_mut(&mut self) -> &mut Parser {
+        self.parser.as_mut().expect("parser checked in")
+    }
+}
+
+impl Drop for PooledParser<'_> {
+    fn drop(&mut self) {
+        if let Some(mut parser) = self.parser.take() {
+            parser.reset();
+            self.pool.lock().unwrap_or_else(|e| e.into_inner()).push(parser);
+        }
+    }
+}
+
+/// 已注册的语言包及其语言资源（注册时即创建）
+#[cfg(feature = "plugins")]
+#[derive(Clone)]
+struct LoadedPack {
+    pack: LanguagePack,
+    resources: Arc<LanguageResources>,
+}
+
+/// 解析使用的语法：内置语言或已注册的语言包（`packs` 中的下标）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Grammar {
+    Builtin(SupportedLanguage),
+    #[cfg(feature = "plugins")]
+    Pack(usize),
+}
+
+impl Grammar {
+    fn builtin(self) -> Option<SupportedLanguage> {
+        match self {
+            Grammar::Builtin(lang) => Some(lang),
+            #[cfg(feature = "plugins")]
+            Grammar::Pack(_) => None,
+        }
+    }
 }
 
 /// 多语言管理器（核心）
+///
+/// 可在线程间共享：`parse_file_shared` 只需 `&self`，每种语言按需维护一个 Parser 池，
+/// 并发的解析请求各自借出一个 Parser，无需为每个线程复制整个管理器。
 pub struct LanguageManager {
-    resources: HashMap<SupportedLanguage, LanguageResources>,
+    resources: RwLock<HashMap<SupportedLanguage, Arc<LanguageResources>>>,
+    options: ParseOptions,
+    entity_hook: Mutex<Option<Box<dyn EntityHook + Send>>>,
+    /// 运行时注册的 query（覆盖内置 query）
+    custom_queries: HashMap<SupportedLanguage, String>,
+    /// 运行时注册的语言包（见 `load_language_pack`）
+    #[cfg(feature = "plugins")]
+    packs: Vec<LoadedPack>,
+}
+
+impl Default for LanguageManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl LanguageManager {
     /// 创建新的管理器
     pub fn new() -> Self {
+        Self::with_options(ParseOptions::default())
+    }
+    
+    /// 使用指定选项创建管理器
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self {
+            resources: RwLock::new(HashMap::new()),
+            options,
+            entity_hook: Mutex::new(None),
+            custom_queries: HashMap::new(),
+            #[cfg(feature = "plugins")]
+            packs: Vec::new(),
+        }
+    }
+    
+    /// 使用指定的提取配置创建管理器（其余选项为默认值）
+    pub fn with_config(config: ParserConfig) -> Self {
+        Self::with_options(ParseOptions {
+            config,
+            ..Default::default()
+        })
+    }
+    
+    /// 继承解析选项、自定义 query 与语言包的新管理器（实体钩子与已加载的内置语言资源不继承）
+    pub fn fork(&self) -> Self {
         Self {
-            resources: HashMap::new(),
+            custom_queries: self.custom_queries.clone(),
+            #[cfg(feature = "plugins")]
+            packs: self.packs.clone(),
+            ..Self::with_options(self.options.clone())
+        }
+    }
+    
+    /// 更新解析选项
+    pub fn set_options(&mut self, options: ParseOptions) {
+        self.options = options;
+    }
+    
+    /// 更新提取配置（其余解析选项不变）
+    pub fn set_config(&mut self, config: ParserConfig) {
+        self.options.config = config;
+    }
+    
+    /// 当前解析选项
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+    
+    /// 输出中使用的文件路径（按 `ParseOptions::path_mode` 改写）
+    pub fn display_path(&self, file_path: &str) -> String {
+        self.options.display_path(file_path)
+    }
+    
+    /// 设置实体后处理钩子（如生成摘要），每次解析后对所有实体调用
+    pub fn set_entity_hook(&mut self, hook: impl EntityHook + Send + 'static) {
+        *self.entity_hook.get_mut().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(hook));
+    }
+    
+    /// 移除实体钩子
+    pub fn clear_entity_hook(&mut self) {
+        *self.entity_hook.get_mut().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+    
+    /// 注册自定义 query，覆盖该语言的内置 query
+    ///
+    /// query 先按语法编译校验，失败时返回错误且保留原有 query。内置策略不认识的
+    /// `definition.*` 捕获按定义节点原文提取，实体类型取捕获名去掉 `definition.` 前缀。
+    /// 需要保留内置捕获时可在 `default_query` 的基础上追加。
+    pub fn set_query(&mut self, lang: SupportedLanguage, query: &str) -> Result<(), String> {
+        let language = load_tree_sitter_language(lang)?;
+        Query::new(&language, query).map_err(|e| format!("Invalid query for {}: {}", lang, e))?;
+        self.custom_queries.insert(lang, query.to_string());
+        self.resources_mut().remove(&lang);
+        Ok(())
+    }
+    
+    /// 移除自定义 query，恢复内置 query
+    pub fn reset_query(&mut self, lang: SupportedLanguage) {
+        if self.custom_queries.remove(&lang).is_some() {
+            self.resources_mut().remove(&lang);
         }
     }
     
-    /// 延迟加载语言资源
-    fn load_language(&mut self, lang: SupportedLanguage) -> Result<&mut LanguageResources, String> {
-        if !self.resources.contains_key(&lang) {
-            let resources = self.prepare_language(lang)?;
-            self.resources.insert(lang, resources);
+    /// 语言的内置 query
+    pub fn default_query(lang: SupportedLanguage) -> &'static str {
+        get_query(lang)
+    }
+    
+    /// 从动态库加载语言包并注册，返回语言名（ABI 见 `plugin` 模块与 LANGUAGE_SUPPORT.md）
+    #[cfg(feature = "plugins")]
+    pub fn load_language_pack(&mut self, path: impl AsRef<Path>) -> Result<String, String> {
+        let pack = LanguagePack::load(path)?;
+        let name = pack.name().to_string();
+        self.register_language_pack(pack)?;
+        Ok(name)
+    }
+    
+    /// 注册语言包：其扩展名的文件按语言包解析，优先于内置语言；同名的语言包被替换
+    #[cfg(feature = "plugins")]
+    pub fn register_language_pack(&mut self, pack: LanguagePack) -> Result<(), String> {
+        let query = Query::new(pack.language(), pack.query())
+            .map_err(|e| format!("Invalid query for {}: {}", pack.name(), e))?;
+        let resources = Arc::new(LanguageResources {
+            language: pack.language().clone(),
+            parsers: Mutex::new(Vec::new()),
+            query,
+            strategy: Box::new(PluginStrategy::new(pack.visibility())),
+            extra_captures: HashSet::new(),
+        });
+        let loaded = LoadedPack { pack, resources };
+        match self.packs.iter().position(|known| known.pack.name() == loaded.pack.name()) {
+            Some(index) => self.packs[index] = loaded,
+            None => self.packs.push(loaded),
+        }
+        Ok(())
+    }
+    
+    /// 已注册的语言包
+    #[cfg(feature = "plugins")]
+    pub fn language_packs(&self) -> impl Iterator<Item = &LanguagePack> {
+        self.packs.iter().map(|loaded| &loaded.pack)
+    }
+    
+    /// 处理该文件的语言包（后注册的优先）
+    #[cfg(feature = "plugins")]
+    fn pack_index(&self, file_path: &str) -> Option<usize> {
+        self.packs.iter().rposition(|loaded| loaded.pack.handles(file_path))
+    }
+    
+    fn resources_mut(&mut self) -> &mut HashMap<SupportedLanguage, Arc<LanguageResources>> {
+        self.resources.get_mut().unwrap_or_else(|e| e.into_inner())
+    }
+    
+    /// 延迟加载语言资源（并发首次加载时以先写入的为准）
+    fn load_language(&self, lang: SupportedLanguage) -> Result<Arc<LanguageResources>, String> {
+        if let Some(resources) = self.resources.read().unwrap_or_else(|e| e.into_inner()).get(&lang) {
+            return Ok(Arc::clone(resources));
         }
         
-        Ok(self.resources.get_mut(&lang).unwrap())
+        let resources = Arc::new(self.prepare_language(lang)?);
+        let mut loaded = self.resources.write().unwrap_or_else(|e| e.into_inner());
+        Ok(Arc::clone(loaded.entry(lang).or_insert(resources)))
     }
     
-    /// 准备语言资源
+    /// 语言的空闲 Parser 数（未加载的语言为 0）
+    pub fn idle_parsers(&self, lang: SupportedLanguage) -> usize {
+        self.resources
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&lang)
+            .map_or(0, |resources| resources.idle_parsers())
+    }
+    
+    /// 准备语言资源（Parser 在首次解析时创建）
     fn prepare_language(&self, lang: SupportedLanguage) -> Result<LanguageResources, String> {
         // 加载 tree-sitter 语言
         let language = load_tree_sitter_language(lang)?;
         
-        // 创建 parser
-        let mut parser = Parser::new();
-        parser
-            .set_language(&language)
-            .map_err(|e| format!("Failed to set language: {}", e))?;
-        
-        // 创建 query
-        let query_str = get_query(lang);
+        // 创建 query（自定义优先）
+        let builtin = get_query(lang);
+        let query_str = self.custom_queries.get(&lang).map_or(builtin, String::as_str);
         let query = Query::new(&language, query_str)
             .map_err(|e| format!("Failed to create query: {}", e))?;
+        let extra_captures = query
+            .capture_names()
+            .iter()
+            .filter(|name| name.starts_with("definition.") && !has_capture(builtin, name))
+            .map(|name| name.to_string())
+            .collect();
         
         // 创建策略
         let strategy = create_strategy(lang);
         
         Ok(LanguageResources {
             language,
-            parser,
+            parsers: Mutex::new(Vec::new()),
             query,
             strategy,
+            extra_captures,
         })
     }
     
@@ -71,12 +347,114 @@ impl LanguageManager {
         guess_language(file_path)
     }
     
+    /// 根据路径与内容判断语言（`.h` 在 C / C++ 之间选择，见 `ParseOptions::header_language`）
+    pub fn detect_language(&self, file_path: &str, source_code: &str) -> Option<SupportedLanguage> {
+        guess_language_for_source(file_path, source_code, self.options.header_language)
+    }
+    
     /// 解析单个文件
     pub fn parse_file(&mut self, file_path: &str, source_code: &str) -> Result<ParseResult, String> {
-        let lang = self.guess_language(file_path)
-            .ok_or_else(|| format!("Unsupported file type: {}", file_path))?;
+        self.parse_file_shared(file_path, source_code)
+    }
+    
+    /// 解析单个文件（共享管理器）：可从多个线程并发调用，实体钩子串行执行
+    pub fn parse_file_shared(&self, file_path: &str, source_code: &str) -> Result<ParseResult, String> {
+        #[cfg(feature = "plugins")]
+        if let Some(index) = self.pack_index(file_path) {
+            return self.parse_grammar(file_path, source_code, Grammar::Pack(index));
+        }
+        
+        let lang = self.detect_language(file_path, source_code)
+            .ok_or_else(|| format!("Unsupported file type: {}", self.display_path(file_path)))?;
         
-        self.parse_with_language(file_path, source_code, lang)
+        self.parse_with_language_shared(file_path, source_code, lang)
+    }
+    
+    /// 内容变化时才解析：内容哈希（`content_hash`，blake3）与 `previous_hash` 相同时返回 None，
+    /// 否则解析并在结果的 `content_hash` 中记录本次的哈希，供下次调用传入
+    ///
+    /// 增量索引只需为每个文件保存上次的哈希，未变化的文件不再解析。
+    pub fn parse_if_changed(
+        &self,
+        file_path: &str,
+        source_code: &str,
+        previous_hash: Option<&str>,
+    ) -> Result<Option<ParseResult>, String> {
+        let hash = content_hash(source_code);
+        if previous_hash == Some(hash.as_str()) {
+            return Ok(None);
+        }
+        
+        let mut result = self.parse_file_shared(file_path, source_code)?;
+        result.content_hash = Some(hash);
+        Ok(Some(result))
+    }
+    
+    /// 解析原始字节：非 UTF-8 内容按 `decode_source` 解码（Windows-1252 / UTF-16 / 混合编码），
+    /// 编码转换与字节替换作为 `ParseError` 记入结果，而不是使整个文件失败
+    pub fn parse_file_bytes(&mut self, file_path: &str, bytes: &[u8]) -> Result<ParseResult, String> {
+        self.parse_file_bytes_shared(file_path, bytes)
+    }
+    
+    /// 解析原始字节（共享管理器，见 `parse_file_bytes`）
+    pub fn parse_file_bytes_shared(&self, file_path: &str, bytes: &[u8]) -> Result<ParseResult, String> {
+        self.parse_decoded(file_path, decode_source(bytes))
+    }
+    
+    /// 按路径在 Rust 侧读取并解析，调用方不必先读取文件再传入内容
+    ///
+    /// 解码同 `parse_file_bytes`；二进制内容返回 `Binary file` 错误。启用 `mmap` 时，
+    /// 不小于 `ParseOptions::mmap_threshold` 的文件以内存映射读取。
+    pub fn parse_path(&self, file_path: &str) -> Result<ParseResult, String> {
+        let decoded = read_source(Path::new(file_path), self.options.mmap_threshold)?;
+        self.parse_decoded(file_path, decoded)
+    }
+    
+    /// 按路径批量解析（启用 `parallel` 时在线程池中读取与解析）
+    ///
+    /// 结果顺序与输入一致，读取或解析失败的文件被跳过。
+    pub fn parse_paths(&self, paths: Vec<String>) -> Result<Vec<ParseResult>, String> {
+        self.parse_paths_with(paths, &ThrottleOptions::default())
+    }
+    
+    /// 按路径批量解析，按节流选项限制线程数与 CPU / IO
+    #[cfg(feature = "parallel")]
+    pub fn parse_paths_with(&self, paths: Vec<String>, throttle: &ThrottleOptions) -> Result<Vec<ParseResult>, String> {
+        self.parse_sharded(&paths, throttle, |manager, path| {
+            let decoded = match read_source(Path::new(path), manager.options.mmap_threshold) {
+                Ok(decoded) => decoded,
+                Err(e) => {
+                    eprintln!("Failed to parse {}: {}", manager.display_path(path), e);
+                    return (0, None);
+                }
+            };
+            let bytes = decoded.text.len() as u64;
+            let result = manager
+                .parse_decoded(path, decoded)
+                .map_err(|e| eprintln!("Failed to parse {}: {}", manager.display_path(path), e))
+                .ok();
+            (bytes, result)
+        })
+    }
+    
+    /// 按路径批量解析（未启用 `parallel` 时逐个解析，不做节流）
+    #[cfg(not(feature = "parallel"))]
+    pub fn parse_paths_with(&self, paths: Vec<String>, _throttle: &ThrottleOptions) -> Result<Vec<ParseResult>, String> {
+        Ok(paths
+            .iter()
+            .filter_map(|path| {
+                self.parse_path(path)
+                    .map_err(|e| eprintln!("Failed to parse {}: {}", self.display_path(path), e))
+                    .ok()
+            })
+            .collect())
+    }
+    
+    /// 解析已解码的源码，解码说明附加到结果的 errors
+    fn parse_decoded(&self, file_path: &str, decoded: DecodedSource) -> Result<ParseResult, String> {
+        let mut result = self.parse_file_shared(file_path, &decoded.text)?;
+        result.errors.extend(decoded.note());
+        Ok(result)
     }
     
     /// 使用指定语言解析
@@ -86,80 +464,432 @@ impl LanguageManager {
         source_code: &str,
         lang: SupportedLanguage,
     ) -> Result<ParseResult, String> {
-        let resources = self.load_language(lang)?;
+        self.parse_with_language_shared(file_path, source_code, lang)
+    }
+    
+    /// 使用指定语言解析（共享管理器，见 `parse_file_shared`）
+    pub fn parse_with_language_shared(
+        &self,
+        file_path: &str,
+        source_code: &str,
+        lang: SupportedLanguage,
+    ) -> Result<ParseResult, String> {
+        self.parse_grammar(file_path, source_code, Grammar::Builtin(lang))
+    }
+    
+    fn parse_grammar(&self, file_path: &str, source_code: &str, grammar: Grammar) -> Result<ParseResult, String> {
+        if let Some(max_file_size) = self.options.config.max_file_size {
+            if source_code.len() > max_file_size {
+                return Err(format!(
+                    "File too large: {} ({} bytes, max_file_size {})",
+                    self.display_path(file_path),
+                    source_code.len(),
+                    max_file_size
+                ));
+            }
+        }
         
-        // 解析源代码
-        let tree = resources.parser
-            .parse(source_code, None)
+        // wasm32-unknown-unknown 没有时钟，`Instant::now` 会 panic，耗时记为 0
+        let started = (!cfg!(target_arch = "wasm32")).then(Instant::now);
+        let mut result = self.extract(file_path, source_code, grammar)?;
+        
+        #[cfg(feature = "vue")]
+        if grammar == Grammar::Builtin(SupportedLanguage::Vue) {
+            self.extract_vue_scripts(&mut result, file_path, source_code)?;
+        }
+        
+        #[cfg(feature = "markdown")]
+        if grammar == Grammar::Builtin(SupportedLanguage::Markdown) && self.options.parse_code_blocks {
+            self.extract_code_blocks(&mut result, file_path, source_code);
+        }
+        
+        normalize_signatures(&mut result);
+        
+        if let Some(metrics) = &self.options.metrics {
+            apply_metrics(&mut result, source_code, metrics);
+        }
+        
+        if let Some(hook) = self.entity_hook.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            apply_entity_hook(hook.as_mut(), &mut result);
+        }
+        
+        // 钩子与指标基于完整文本，最后再截断
+        if let Some(cap) = self.options.effective_entity_cap() {
+            apply_entity_caps(&mut result, &cap);
+        }
+        
+        apply_entity_order(&mut result, self.options.entity_order);
+        
+        result.summary = FileSummary {
+            kinds: result.entity_info.iter().fold(BTreeMap::new(), |mut kinds, info| {
+                *kinds.entry(info.kind.clone()).or_default() += 1;
+                kinds
+            }),
+            entities: result.entities.len(),
+            lines: source_code.lines().count(),
+            elapsed_us: started.map_or(0, |started| started.elapsed().as_micros() as u64),
+        };
+        
+        Ok(result)
+    }
+    
+    /// Vue 单文件组件：`<script>` 块的内容按 `lang` 属性以 TypeScript / JavaScript 解析，
+    /// 组件的导入、函数、props 等实体并入结果
+    ///
+    /// 块外的内容替换为等长空白后再解析，行号与字节偏移与原文件一致。
+    #[cfg(feature = "vue")]
+    fn extract_vue_scripts(&self, result: &mut ParseResult, file_path: &str, source_code: &str) -> Result<(), String> {
+        for block in script_blocks(source_code) {
+            let masked = mask_outside(source_code, block.content.clone());
+            let script = self.extract(file_path, &masked, Grammar::Builtin(block.language))?;
+            
+            let offset = result.entity_info.len();
+            result.entities.extend(script.entities);
+            result.entity_info.extend(script.entity_info.into_iter().map(|mut info| {
+                info.parent = info.parent.map(|parent| parent + offset);
+                info
+            }));
+            result.imports.extend(script.imports);
+            result.exports.extend(script.exports);
+            result.dynamic_imports.extend(script.dynamic_imports);
+            result.calls.extend(script.calls);
+            result.element_usages.extend(script.element_usages);
+            result.modifications.extend(script.modifications);
+            result.type_relations.extend(script.type_relations);
+            result.errors.extend(script.errors);
+            result.is_partial |= script.is_partial;
+        }
+        Ok(())
+    }
+    
+    /// Markdown 围栏代码块：语言标记对应已启用的语言时按该语言解析，块内实体并入结果，
+    /// 块内的顶层实体以所在的代码块实体为 parent
+    ///
+    /// 块外的内容替换为等长空白后再解析，行号与字节偏移与原文件一致。
+    /// 文档中的示例代码常常不完整，块内的语法错误不计入结果。
+    #[cfg(feature = "markdown")]
+    fn extract_code_blocks(&self, result: &mut ParseResult, file_path: &str, source_code: &str) {
+        for block in code_blocks(source_code) {
+            let Ok(lang) = block.language.parse::<SupportedLanguage>() else {
+                continue;
+            };
+            if lang == SupportedLanguage::Markdown {
+                continue;
+            }
+            let masked = mask_outside(source_code, block.content.clone());
+            let Ok(code) = self.extract(file_path, &masked, Grammar::Builtin(lang)) else {
+                continue;
+            };
+            
+            let owner = result
+                .entity_info
+                .iter()
+                .position(|info| info.kind == "code" && info.range.start == block.start_line);
+            let offset = result.entity_info.len();
+            result.entities.extend(code.entities);
+            result.entity_info.extend(code.entity_info.into_iter().map(|mut info| {
+                info.parent = info.parent.map(|parent| parent + offset).or(owner);
+                info
+            }));
+            result.calls.extend(code.calls);
+        }
+    }
+    
+    /// 用 query 提取实体、导入导出与调用关系（不含签名整理、指标与钩子等后处理）
+    fn extract(&self, file_path: &str, source_code: &str, grammar: Grammar) -> Result<ParseResult, String> {
+        let lang = grammar.builtin();
+        let is_script = matches!(lang, Some(SupportedLanguage::TypeScript | SupportedLanguage::JavaScript));
+        let flow = is_script
+            && (self.options.flow || (lang == Some(SupportedLanguage::JavaScript) && is_flow_file(source_code)));
+        // Flow / 旧版装饰器：容错模式，过滤 ERROR 节点内的捕获并合并语法错误
+        let tolerant = flow || (is_script && self.options.legacy_decorators);
+        let display_path = self.display_path(file_path);
+        let window = self.options.sampling.as_ref().and_then(|sampling| sample_window(source_code, sampling));
+        let path_info = classify_path(file_path);
+        
+        let (resources, language) = match grammar {
+            Grammar::Builtin(lang) => {
+                // `.tsx` 含 JSX：按 TSX 语法解析（与 JavaScript 共用语言资源与 query）
+                let grammar = if lang == SupportedLanguage::TypeScript && path_info.extension == "tsx" {
+                    SupportedLanguage::JavaScript
+                } else {
+                    lang
+                };
+                (self.load_language(grammar)?, lang.to_string())
+            }
+            #[cfg(feature = "plugins")]
+            Grammar::Pack(index) => {
+                let loaded = &self.packs[index];
+                (Arc::clone(&loaded.resources), loaded.pack.name().to_string())
+            }
+        };
+        
+        // 解析源代码（Flow 先做等长替换，字节偏移与原文一致）
+        let parse_source = if flow { sanitize_flow(source_code) } else { source_code.into() };
+        let tree = resources
+            .checkout()?
+            .parse(parse_source.as_ref(), None)
             .ok_or("Failed to parse source code")?;
         
         let root_node = tree.root_node();
         
-        // 使用 query 提取代码实体
-        let mut cursor = QueryCursor::new();
-        let matches = cursor.matches(&resources.query, root_node, source_code.as_bytes());
+        // 使用 query 提取代码实体；大文件只完整提取首尾窗口，中间部分只保留顶层签名（见 sample_window）
+        let passes = match window {
+            None => vec![(0..source_code.len(), None)],
+            Some((head_end, tail_start)) => vec![
+                (0..head_end, None),
+                (head_end..tail_start, Some(MAX_OUTLINE_DEPTH)),
+                (tail_start..source_code.len(), None),
+            ],
+        };
+        
+        // 调用关系只在完整提取的范围内收集
+        let call_ranges: Vec<_> = passes
+            .iter()
+            .filter(|(_, max_depth)| max_depth.is_none())
+            .map(|(range, _)| range.clone())
+            .collect();
         
+        // 实体按位置（定义的字节范围 + 捕获名）去重；策略内的文本去重只在按文本去重时跨捕获共享集合。
+        // 注释与代码片段分开记录，去重方式可分别配置；两者都只在本文件内生效
+        let config = &self.options.config;
+        let dedup_by_text = self.options.dedup_by_text();
+        let dedup_comments_by_text = self.options.dedup_comments_by_text();
         let mut processed_chunks = HashSet::new();
+        let mut processed_comments = HashSet::new();
+        let mut seen_locations = HashSet::new();
         let mut entities = Vec::new();
+        let mut entity_info = Vec::new();
+        // 每个实体定义节点的字节范围与外层作用域，用于关联嵌套实体
+        let mut def_ranges = Vec::new();
+        let mut scopes = Vec::new();
         
-        for match_ in matches {
-            for capture in match_.captures {
-                let capture_name = resources.query.capture_names()[capture.index as usize];
-                
-                let capture_data = Capture {
-                    node: capture.node,
-                    name: capture_name,
-                };
-                
-                if let Some(code) = resources.strategy.parse_capture(
-                    capture_data,
-                    source_code,
-                    &mut processed_chunks,
-                ) {
-                    entities.push(code);
+        for (range, max_depth) in passes {
+            let outline = max_depth.is_some();
+            let mut cursor = QueryCursor::new();
+            cursor.set_byte_range(range.clone()).set_max_start_depth(max_depth);
+            let matches = cursor.matches(&resources.query, root_node, source_code.as_bytes());
+            
+            for match_ in matches {
+                for capture in match_.captures {
+                    if tolerant && is_inside_error(capture.node) {
+                        continue;
+                    }
+                    
+                    let capture_name = resources.query.capture_names()[capture.index as usize];
+                    let def = definition_node(capture.node);
+                    if !seen_locations.insert((def.byte_range(), capture_name)) {
+                        continue;
+                    }
+                    let is_comment = capture_name.contains("comment");
+                    if !config.include_comments && is_comment {
+                        continue;
+                    }
+                    // 不按文本去重时，策略只看到本次捕获自己的空集合
+                    let mut unshared = HashSet::new();
+                    let processed = match (is_comment, dedup_comments_by_text, dedup_by_text) {
+                        (true, true, _) => &mut processed_comments,
+                        (false, _, true) => &mut processed_chunks,
+                        _ => &mut unshared,
+                    };
+                    
+                    if let Some((head_end, tail_start)) = window {
+                        let sampled = def.end_byte() <= head_end || def.start_byte() >= tail_start;
+                        if sampled == outline {
+                            continue;
+                        }
+                        if outline {
+                            if let Some((text, info)) = outline_entity(
+                                resources.strategy.as_ref(),
+                                capture.node,
+                                capture_name,
+                                source_code,
+                            ) {
+                                if processed.insert(text.clone()) {
+                                    entities.push(text);
+                                    entity_info.push(info);
+                                    def_ranges.push((def.start_byte(), def.end_byte()));
+                                    scopes.push(enclosing_scopes(def, source_code));
+                                }
+                            }
+                            continue;
+                        }
+                    }
+                    
+                    let capture_data = Capture {
+                        node: capture.node,
+                        name: capture_name,
+                    };
+                    
+                    let code = resources
+                        .strategy
+                        .parse_capture(capture_data, source_code, processed)
+                        .or_else(|| {
+                            resources
+                                .extra_captures
+                                .contains(capture_name)
+                                .then(|| custom_capture_text(capture.node, source_code, processed))
+                                .flatten()
+                        });
+                    if let Some(code) = code {
+                        let mut info = build_entity_info(
+                            resources.strategy.as_ref(),
+                            capture.node,
+                            capture_name,
+                            source_code,
+                        );
+                        if self.options.complexity {
+                            info.complexity = entity_complexity(def, &info.kind, source_code);
+                        }
+                        entities.push(function_text(code, def, &info.kind, source_code, config.bodies));
+                        entity_info.push(info);
+                        def_ranges.push((def.start_byte(), def.end_byte()));
+                        scopes.push(enclosing_scopes(def, source_code));
+                    }
                 }
             }
         }
         
+        // React 组件：与对应的函数 / 类实体并列，额外输出 `component` 实体
+        if is_script && self.options.react_components {
+            for component in react::components(root_node, source_code) {
+                let def = definition_node(component.name);
+                if !call_ranges.iter().any(|range| range.contains(&def.start_byte()))
+                    || (tolerant && is_inside_error(def))
+                {
+                    continue;
+                }
+                let mut info = build_entity_info(
+                    resources.strategy.as_ref(),
+                    component.name,
+                    "definition.component",
+                    source_code,
+                );
+                info.component = Some(component.info);
+                entities.push(component.text);
+                entity_info.push(info);
+                def_ranges.push((def.start_byte(), def.end_byte()));
+                scopes.push(enclosing_scopes(def, source_code));
+            }
+        }
+        
+        let errors = collect_syntax_errors(root_node, tolerant);
+        let module_refs = resources.strategy.extract_module_refs(root_node, source_code, &display_path);
+        let calls = resources
+            .strategy
+            .call_syntax()
+            .map(|syntax| extract_calls(root_node, source_code, syntax, &call_ranges, tolerant))
+            .unwrap_or_default();
+        let element_usages = resources
+            .strategy
+            .call_syntax()
+            .filter(|_| is_script)
+            .map(|syntax| extract_elements(root_node, source_code, syntax.callers, &call_ranges, tolerant))
+            .unwrap_or_default();
+        let type_relations = resources.strategy.type_relations(root_node, source_code);
+        let modifications = resources
+            .strategy
+            .modifications(root_node, source_code, &module_refs.imports);
+        
         // 构建结果
-        Ok(ParseResult {
-            file_path: file_path.to_string(),
-            language: format!("{}", lang),
+        let mut result = ParseResult {
+            file_path: display_path,
+            language,
             entities,
-            imports: Vec::new(), // TODO: 单独提取
-            exports: Vec::new(), // TODO: 单独提取
-            errors: Vec::new(),
-        })
+            entity_info,
+            imports: module_refs.imports,
+            exports: module_refs.exports,
+            dynamic_imports: module_refs.dynamic_imports,
+            calls,
+            element_usages,
+            modifications,
+            type_relations,
+            errors,
+            is_declaration_file: path_info.is_declaration,
+            is_partial: window.is_some(),
+            is_generated: path_info.is_generated || has_generated_marker(source_code),
+            is_test: path_info.is_test,
+            is_minified: path_info.is_minified,
+            is_story: path_info.is_story,
+            is_barrel: false,
+            links: Vec::new(),
+            directives: file_directives(source_code),
+            role: file_role(file_path, source_code),
+            content_hash: None,
+            summary: Default::default(),
+        };
+        
+        link_entities(&mut result, &def_ranges, &scopes, self.options.qualified_names);
+        result.is_barrel = is_script && is_barrel(&result);
+        #[cfg(feature = "markdown")]
+        if lang == Some(SupportedLanguage::Markdown) {
+            result.links = document_links(source_code);
+        }
+        // 本文件定义的构造函数上的原型赋值是 ES5 风格的类定义，不算补丁
+        let entity_info = &result.entity_info;
+        result.modifications.retain(|m| {
+            m.source.is_some() || !entity_info.iter().any(|info| info.name.as_deref() == Some(m.object.as_str()))
+        });
+        Ok(result)
+    }
+    
+    /// 统计 query 中每个捕获名在源码上的命中次数（未命中的捕获计为 0）
+    ///
+    /// 用于校验 fixture 是否覆盖了语言 query 的全部捕获
+    pub fn capture_counts(
+        &mut self,
+        source_code: &str,
+        lang: SupportedLanguage,
+    ) -> Result<BTreeMap<String, usize>, String> {
+        let resources = self.load_language(lang)?;
+        let tree = resources
+            .checkout()?
+            .parse(source_code, None)
+            .ok_or("Failed to parse source code")?;
+        
+        let mut counts: BTreeMap<String, usize> = resources.query
+            .capture_names()
+            .iter()
+            .map(|name| (name.to_string(), 0))
+            .collect();
+        
+        let mut cursor = QueryCursor::new();
+        for match_ in cursor.matches(&resources.query, tree.root_node(), source_code.as_bytes()) {
+            for capture in match_.captures {
+                let name = resources.query.capture_names()[capture.index as usize];
+                *counts.entry(name.to_string()).or_default() += 1;
+            }
+        }
+        
+        Ok(counts)
     }
     
     /// 批量解析文件
+    ///
+    /// 结果顺序与输入一致，无法识别语言或解析失败的文件被跳过。
     pub fn parse_files_batch(
         &mut self,
         files: Vec<(String, String)>, // (path, content)
+    ) -> Result<Vec<ParseResult>, String> {
+        self.parse_files_batch_shared(files)
+    }
+    
+    /// 批量解析文件（共享管理器，见 `parse_files_batch`）
+    pub fn parse_files_batch_shared(
+        &self,
+        files: Vec<(String, String)>, // (path, content)
     ) -> Result<Vec<ParseResult>, String> {
         let mut results = Vec::new();
         
-        // 按语言分组（优化）
-        let mut by_lang: HashMap<SupportedLanguage, Vec<(String, String)>> = HashMap::new();
-        
         for (path, content) in files {
-            if let Some(lang) = self.guess_language(&path) {
-                by_lang.entry(lang).or_default().push((path, content));
+            if !self.can_parse(&path, &content) {
+                continue;
             }
-        }
-        
-        // 处理每种语言的文件
-        for (lang, files) in by_lang {
-            // 预加载语言资源
-            self.load_language(lang)?;
-            
-            for (path, content) in files {
-                match self.parse_with_language(&path, &content, lang) {
-                    Ok(result) => results.push(result),
-                    Err(e) => {
-                        // 记录错误但继续处理
-                        eprintln!("Failed to parse {}: {}", path, e);
-                    }
+            match self.parse_file_shared(&path, &content) {
+                Ok(result) => results.push(result),
+                Err(e) => {
+                    // 记录错误但继续处理
+                    eprintln!("Failed to parse {}: {}", self.display_path(&path), e);
                 }
             }
         }
@@ -167,14 +897,512 @@ impl LanguageManager {
         Ok(results)
     }
     
+    /// 语言包或内置语言能否处理该文件
+    fn can_parse(&self, file_path: &str, source_code: &str) -> bool {
+        #[cfg(feature = "plugins")]
+        if self.pack_index(file_path).is_some() {
+            return true;
+        }
+        self.detect_language(file_path, source_code).is_some()
+    }
+    
+    /// 批量解析并按语言分组：语言名 -> 该语言的结果（按语言名排序，组内保持输入顺序）
+    pub fn parse_files_batch_by_language(
+        &mut self,
+        files: Vec<(String, String)>, // (path, content)
+    ) -> Result<BTreeMap<String, Vec<ParseResult>>, String> {
+        let mut groups: BTreeMap<String, Vec<ParseResult>> = BTreeMap::new();
+        for result in self.parse_files_batch(files)? {
+            groups.entry(result.language.clone()).or_default().push(result);
+        }
+        Ok(groups)
+    }
+    
+    /// 并行批量解析文件（rayon 线程池，使用全部 CPU）
+    ///
+    /// 结果顺序与输入一致，解析失败的文件被跳过。
+    #[cfg(feature = "parallel")]
+    pub fn parse_files_batch_parallel(
+        &self,
+        files: Vec<(String, String)>,
+    ) -> Result<Vec<ParseResult>, String> {
+        self.parse_files_batch_parallel_with(files, &ThrottleOptions::default())
+    }
+    
+    /// 并行批量解析文件，按节流选项限制线程数与 CPU / IO
+    ///
+    /// tree-sitter 的 Parser 不是 Sync：文件被切分为若干分片，每个分片由
+    /// 一个独立的 LanguageManager（继承当前解析选项）处理。实体钩子不会
+    /// 传递到工作线程，需要时对结果调用 `apply_entity_hook`。
+    #[cfg(feature = "parallel")]
+    pub fn parse_files_batch_parallel_with(
+        &self,
+        files: Vec<(String, String)>, // (path, content)
+        throttle: &ThrottleOptions,
+    ) -> Result<Vec<ParseResult>, String> {
+        self.parse_sharded(&files, throttle, |manager, (path, content)| {
+            let result = manager
+                .parse_file(path, content)
+                .map_err(|e| eprintln!("Failed to parse {}: {}", manager.display_path(path), e))
+                .ok();
+            (content.len() as u64, result)
+        })
+    }
+    
+    /// 把输入切分为若干分片，在线程池中用各自的 LanguageManager（见 `fork`）处理
+    ///
+    /// `parse` 返回该项的字节数（用于 IO 节流）与结果；结果顺序与输入一致，None 被跳过。
+    #[cfg(feature = "parallel")]
+    fn parse_sharded<T: Sync>(
+        &self,
+        items: &[T],
+        throttle: &ThrottleOptions,
+        parse: impl Fn(&mut LanguageManager, &T) -> (u64, Option<ParseResult>) + Sync,
+    ) -> Result<Vec<ParseResult>, String> {
+        use rayon::prelude::*;
+        
+        let threads = throttle.thread_count();
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_err(|e| format!("Failed to build thread pool: {}", e))?;
+        
+        // 每个线程约 4 个分片，兼顾负载均衡与语言资源的加载开销
+        let shard_size = items.len().div_ceil(threads * 4).max(1);
+        
+        let results = pool.install(|| {
+            items
+                .par_chunks(shard_size)
+                .flat_map_iter(|shard| {
+                    let mut manager = self.fork();
+                    let mut throttle = Throttle::new(throttle.clone());
+                    shard
+                        .iter()
+                        .filter_map(|item| {
+                            throttle.begin_unit();
+                            let (bytes, result) = parse(&mut manager, item);
+                            throttle.after_unit(bytes);
+                            result
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect()
+        });
+        
+        Ok(results)
+    }
+    
+    /// 并行批量解析，每个文件完成后立即交给 `on_result`，不在内存中累积结果
+    ///
+    /// `on_result` 串行调用，进度单调递增；回调顺序为完成顺序而非输入顺序。
+    /// 每个文件的源码在解析后即释放。返回最终进度。
+    #[cfg(feature = "parallel")]
+    pub fn parse_files_streaming(
+        &self,
+        files: Vec<(String, String)>, // (path, content)
+        throttle: &ThrottleOptions,
+        on_result: impl FnMut(FileOutcome) + Send,
+    ) -> Result<BatchProgress, String> {
+        use rayon::prelude::*;
+        use std::sync::Mutex;
+        
+        let threads = throttle.thread_count();
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_err(|e| format!("Failed to build thread pool: {}", e))?;
+        
+        let total = files.len();
+        let shard_size = total.div_ceil(threads * 4).max(1);
+        let mut files = files.into_iter();
+        let shards: Vec<Vec<(String, String)>> = std::iter::from_fn(|| {
+            let shard: Vec<_> = files.by_ref().take(shard_size).collect();
+            (!shard.is_empty()).then_some(shard)
+        })
+        .collect();
+        
+        let progress = BatchProgress { total, ..Default::default() };
+        let state = Mutex::new((progress, on_result));
+        pool.install(|| {
+            shards.into_par_iter().for_each(|shard| {
+                let mut manager = self.fork();
+                let mut throttle = Throttle::new(throttle.clone());
+                for (path, content) in shard {
+                    throttle.begin_unit();
+                    let result = manager.parse_file(&path, &content);
+                    throttle.after_unit(content.len() as u64);
+                    drop(content);
+                    
+                    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+                    let (progress, on_result) = &mut *state;
+                    progress.completed += 1;
+                    progress.failed += usize::from(result.is_err());
+                    let (result, error) = match result {
+                        Ok(result) => (Some(result), None),
+                        Err(e) => (None, Some(e)),
+                    };
+                    on_result(FileOutcome {
+                        file_path: manager.display_path(&path),
+                        result,
+                        error,
+                        progress: *progress,
+                    });
+                }
+            });
+        });
+        
+        Ok(state.into_inner().unwrap_or_else(|e| e.into_inner()).0)
+    }
+    
     /// 获取支持的语言列表
     pub fn supported_languages() -> Vec<SupportedLanguage> {
         SupportedLanguage::all()
     }
 }
 
+/// 采样模式下中间部分的最大嵌套深度（根节点为 0；`export` 等包装节点占一层）
+const MAX_OUTLINE_DEPTH: u32 = 2;
+
+/// 大文件的采样窗口：(头部结束, 尾部开始)，均落在行边界；未超过阈值时为 None
+fn sample_window(source_code: &str, sampling: &SamplingOptions) -> Option<(usize, usize)> {
+    if source_code.len() <= sampling.threshold {
+        return None;
+    }
+    let bytes = source_code.as_bytes();
+    let head_end = bytes
+        .iter()
+        .skip(sampling.sample_bytes)
+        .position(|&b| b == b'\n')
+        .map_or(bytes.len(), |i| sampling.sample_bytes + i + 1);
+    let tail_from = bytes.len().saturating_sub(sampling.sample_bytes);
+    let tail_start = bytes[..tail_from].iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+    (head_end < tail_start).then_some((head_end, tail_start))
+}
+
+/// 采样中间部分的顶层实体：只保留签名作为代码片段
+fn outline_entity(
+    strategy: &dyn ParseStrategy,
+    node: tree_sitter::Node,
+    capture_name: &str,
+    source_code: &str,
+) -> Option<(String, EntityInfo)> {
+    let def = definition_node(node);
+    let mut depth = 0;
+    let mut current = def;
+    while let Some(parent) = current.parent() {
+        depth += 1;
+        current = parent;
+    }
+    if depth > MAX_OUTLINE_DEPTH {
+        return None;
+    }
+    
+    let info = build_entity_info(strategy, node, capture_name, source_code);
+    let text = info.signature.clone()?;
+    Some((text, info))
+}
+
+/// query 文本中是否有名为 `name` 的捕获（`@name` 后不再接名字字符）
+fn has_capture(query: &str, name: &str) -> bool {
+    let pattern = format!("@{}", name);
+    query.match_indices(&pattern).any(|(i, _)| {
+        !query[i + pattern.len()..]
+            .starts_with(|c: char| c.is_alphanumeric() || matches!(c, '_' | '.' | '-'))
+    })
+}
+
+/// 自定义捕获的代码片段：定义节点原文（已提取过的跳过）
+fn custom_capture_text(
+    node: tree_sitter::Node,
+    source_code: &str,
+    processed_chunks: &mut HashSet<String>,
+) -> Option<String> {
+    let text = get_node_text(definition_node(node), source_code).trim().to_string();
+    processed_chunks.insert(text.clone()).then_some(text)
+}
+
+/// 按 `BodyMode` 改写函数类实体的片段：只留签名或换成完整定义，其余实体不变
+///
+/// 两种改写都从定义所在行的行首开始，`Full` 的片段总以 `Signatures` 的片段开头，与语言无关。
+fn function_text(code: String, def: tree_sitter::Node, kind: &str, source_code: &str, bodies: BodyMode) -> String {
+    if !FUNCTION_KINDS.contains(&kind) {
+        return code;
+    }
+    match bodies {
+        BodyMode::Language => code,
+        BodyMode::Signatures => function_signature(def, source_code).to_string(),
+        BodyMode::Full => function_definition(def, source_code).to_string(),
+    }
+}
+
+/// 构建单个代码片段的元数据
+fn build_entity_info(
+    strategy: &dyn ParseStrategy,
+    node: tree_sitter::Node,
+    capture_name: &str,
+    source_code: &str,
+) -> EntityInfo {
+    let def = definition_node(node);
+    let kind = capture_name
+        .strip_prefix("definition.")
+        .unwrap_or(capture_name)
+        .to_string();
+    let name = if kind == "comment" {
+        None
+    } else {
+        strategy.entity_name(node, source_code)
+    };
+    let (is_exported, visibility) = strategy.classify_visibility(node, source_code);
+    let signature = entity_signature(def, &kind, source_code);
+    let doc = if kind == "comment" {
+        None
+    } else {
+        strategy.doc_comment(node, source_code)
+    };
+    
+    EntityInfo {
+        kind,
+        name,
+        range: Range {
+            start: def.start_position().row + 1,
+            end: def.end_position().row + 1,
+        },
+        is_exported,
+        visibility,
+        signature,
+        doc,
+        summary: None,
+        context: None,
+        metrics: None,
+        complexity: None,
+        is_truncated: false,
+        raw_signature: None,
+        parent: None,
+        qualified_name: None,
+        constraints: strategy.constraints(node, source_code),
+        annotations: strategy.annotations(node, source_code),
+        component: None,
+    }
+}
+
+/// 声明签名：定义体（`body` 字段）之前的文本；没有定义体时取首行
+fn entity_signature(def: tree_sitter::Node, kind: &str, source_code: &str) -> Option<String> {
+    if matches!(kind, "comment" | "import" | "use" | "include" | "package") {
+        return None;
+    }
+    
+    let end = match def.child_by_field_name("body") {
+        Some(body) => body.start_byte(),
+        None => source_code[def.start_byte()..def.end_byte()]
+            .find('\n')
+            .map_or(def.end_byte(), |i| def.start_byte() + i),
+    };
+    // 原始切片，显示形式由 `normalize_signatures` 生成
+    let signature = source_code.get(def.start_byte()..end)?.trim();
+    
+    (!signature.is_empty()).then(|| signature.to_string())
+}
+
+/// 提取调用关系：每个调用表达式归属最近的具名调用方定义
+fn extract_calls(
+    root: tree_sitter::Node,
+    source_code: &str,
+    syntax: &CallSyntax,
+    ranges: &[std::ops::Range<usize>],
+    tolerant: bool,
+) -> Vec<CallReference> {
+    let mut calls = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+        
+        let Some(&(_, field)) = syntax.calls.iter().find(|(kind, _)| *kind == node.kind()) else {
+            continue;
+        };
+        if !ranges.iter().any(|range| range.contains(&node.start_byte())) || (tolerant && is_inside_error(node)) {
+            continue;
+        }
+        let Some(callee) = node.child_by_field_name(field).and_then(|callee| callee_name(callee, source_code)) else {
+            continue;
+        };
+        
+        let caller = enclosing_caller(node, source_code, syntax.callers);
+        calls.push(CallReference {
+            caller: caller.as_ref().map(|(name, _)| name.clone()),
+            caller_range: caller.map(|(_, range)| range),
+            callee,
+            range: Range {
+                start: node.start_position().row + 1,
+                end: node.end_position().row + 1,
+            },
+        });
+    }
+    calls
+}
+
+/// 提取 JSX 中使用的组件元素：首字母大写或带成员访问的元素名（`<UserCard />`、`<Icons.Star />`），
+/// 归属最近的具名函数 / 组件定义
+fn extract_elements(
+    root: tree_sitter::Node,
+    source_code: &str,
+    callers: &[&str],
+    ranges: &[std::ops::Range<usize>],
+    tolerant: bool,
+) -> Vec<ElementUsage> {
+    let mut usages = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+        
+        let name = match node.kind() {
+            "jsx_self_closing_element" => node.child_by_field_name("name"),
+            "jsx_element" => node
+                .child_by_field_name("open_tag")
+                .and_then(|tag| tag.child_by_field_name("name")),
+            _ => continue,
+        };
+        // 片段 `<>` 没有名字
+        let Some(name) = name else {
+            continue;
+        };
+        if !ranges.iter().any(|range| range.contains(&node.start_byte())) || (tolerant && is_inside_error(node)) {
+            continue;
+        }
+        let element = get_node_text(name, source_code);
+        let is_component = name.kind() == "member_expression" || element.starts_with(|c: char| c.is_uppercase());
+        if !is_component {
+            continue;
+        }
+        
+        let caller = enclosing_caller(node, source_code, callers);
+        usages.push(ElementUsage {
+            caller: caller.as_ref().map(|(name, _)| name.clone()),
+            caller_range: caller.map(|(_, range)| range),
+            element: element.to_string(),
+            range: Range {
+                start: node.start_position().row + 1,
+                end: node.end_position().row + 1,
+            },
+        });
+    }
+    usages
+}
+
+/// 桶文件：有导出，且除导入、导出语句与注释外没有其他实体（导出均为 re-export）
+fn is_barrel(result: &ParseResult) -> bool {
+    !result.exports.is_empty()
+        && result
+            .entity_info
+            .iter()
+            .all(|info| matches!(info.kind.as_str(), "import" | "export" | "comment"))
+}
+
+/// 被调用者的名字：成员访问 / 路径取最后一段，泛型取类型本身；无法确定时为 None
+fn callee_name(node: tree_sitter::Node, source_code: &str) -> Option<String> {
+    if node.named_child_count() == 0 {
+        let text = get_node_text(node, source_code);
+        let is_ident = text
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+            && text.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+        return is_ident.then(|| text.to_string());
+    }
+    for field in ["property", "field", "attribute", "name", "function", "type"] {
+        if let Some(child) = node.child_by_field_name(field) {
+            return callee_name(child, source_code);
+        }
+    }
+    match node.kind() {
+        // Java：`new Foo<T>()`、`new a.b.Foo()`
+        "generic_type" => callee_name(node.named_child(0)?, source_code),
+        "scoped_type_identifier" => callee_name(node.named_child(node.named_child_count() - 1)?, source_code),
+        _ => None,
+    }
+}
+
+/// 最近的具名调用方定义：(名字, 定义范围)
+///
+/// 匿名函数取所赋值的变量 / 属性名，范围取该声明；没有名字时继续向外查找
+fn enclosing_caller(node: tree_sitter::Node, source_code: &str, callers: &[&str]) -> Option<(String, Range)> {
+    let mut current = node.parent();
+    while let Some(def) = current {
+        current = def.parent();
+        if !callers.contains(&def.kind()) {
+            continue;
+        }
+        let named = match def.child_by_field_name("name") {
+            Some(name) => Some((name, def)),
+            None => def.parent().and_then(|parent| {
+                ["name", "key", "property", "left"]
+                    .iter()
+                    .find_map(|field| parent.child_by_field_name(field))
+                    .filter(|name| name.id() != def.id())
+                    .map(|name| (name, parent))
+            }),
+        };
+        if let Some((name, owner)) = named {
+            if let Some(name) = callee_name(name, source_code) {
+                let range = Range {
+                    start: owner.start_position().row + 1,
+                    end: owner.end_position().row + 1,
+                };
+                return Some((name, range));
+            }
+        }
+    }
+    None
+}
+
+/// 收集语法错误（ERROR / MISSING 节点）
+///
+/// 容错模式下不逐个上报，只合并为一条摘要，避免 Flow/装饰器语法淹没结果
+fn collect_syntax_errors(root: tree_sitter::Node, tolerant: bool) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+    if !root.has_error() {
+        return errors;
+    }
+    
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.is_error() || node.is_missing() {
+            errors.push(ParseError {
+                message: if node.is_missing() {
+                    format!("Missing {} at line {}", node.kind(), node.start_position().row + 1)
+                } else {
+                    format!("Syntax error at line {}", node.start_position().row + 1)
+                },
+                range: Some(Range {
+                    start: node.start_position().row + 1,
+                    end: node.end_position().row + 1,
+                }),
+            });
+            continue;
+        }
+        if node.has_error() {
+            let mut cursor = node.walk();
+            let children: Vec<_> = node.children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
+        }
+    }
+    
+    if tolerant && !errors.is_empty() {
+        let first = errors[0].range.clone();
+        return vec![ParseError {
+            message: format!("{} syntax error(s) tolerated (Flow / legacy decorators)", errors.len()),
+            range: first,
+        }];
+    }
+    
+    errors
+}
+
 /// 加载 tree-sitter 语言
-fn load_tree_sitter_language(lang: SupportedLanguage) -> Result<Language, String> {
+pub(crate) fn load_tree_sitter_language(lang: SupportedLanguage) -> Result<Language, String> {
     // 统一使用 0.23.x API：所有语言包都提供 LANGUAGE 常量（LanguageFn 类型）
     // LanguageFn 可以转换为 Language
     let language = match lang {
@@ -208,6 +1436,10 @@ fn load_tree_sitter_language(lang: SupportedLanguage) -> Result<Language, String
         SupportedLanguage::Cpp => {
             tree_sitter_cpp::LANGUAGE.into()
         }
+        #[cfg(feature = "php")]
+        SupportedLanguage::PHP => {
+            tree_sitter_php::LANGUAGE_PHP.into()
+        }
         #[cfg(feature = "swift")]
         SupportedLanguage::Swift => {
             tree_sitter_swift::LANGUAGE.into()
@@ -224,6 +1456,50 @@ fn load_tree_sitter_language(lang: SupportedLanguage) -> Result<Language, String
         SupportedLanguage::Vue => {
             tree_sitter_vue::LANGUAGE.into()
         }
+        #[cfg(feature = "sql")]
+        SupportedLanguage::Sql => {
+            tree_sitter_sequel::LANGUAGE.into()
+        }
+        #[cfg(feature = "graphql")]
+        SupportedLanguage::GraphQL => {
+            tree_sitter_graphql::LANGUAGE.into()
+        }
+        #[cfg(feature = "proto")]
+        SupportedLanguage::Protobuf => {
+            tree_sitter_proto::LANGUAGE.into()
+        }
+        #[cfg(feature = "elixir")]
+        SupportedLanguage::Elixir => {
+            tree_sitter_elixir::LANGUAGE.into()
+        }
+        #[cfg(feature = "erlang")]
+        SupportedLanguage::Erlang => {
+            tree_sitter_erlang::LANGUAGE.into()
+        }
+        #[cfg(feature = "zig")]
+        SupportedLanguage::Zig => {
+            tree_sitter_zig::LANGUAGE.into()
+        }
+        #[cfg(feature = "nim")]
+        SupportedLanguage::Nim => {
+            tree_sitter_nim::LANGUAGE.into()
+        }
+        #[cfg(feature = "config-files")]
+        SupportedLanguage::Json => {
+            tree_sitter_json::LANGUAGE.into()
+        }
+        #[cfg(feature = "config-files")]
+        SupportedLanguage::Yaml => {
+            tree_sitter_yaml::LANGUAGE.into()
+        }
+        #[cfg(feature = "config-files")]
+        SupportedLanguage::Toml => {
+            tree_sitter_toml_ng::LANGUAGE.into()
+        }
+        #[cfg(feature = "markdown")]
+        SupportedLanguage::Markdown => {
+            tree_sitter_md::LANGUAGE.into()
+        }
     };
     
     Ok(language)
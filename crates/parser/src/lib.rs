@@ -4,6 +4,50 @@ mod ext_to_lang;
 mod strategies;
 mod queries;
 mod language_manager;
+mod js_dialect;
+mod index;
+mod diagram;
+mod report;
+mod code_map;
+mod hooks;
+mod quality;
+mod streaming;
+mod summary_cache;
+mod throttle;
+mod paths;
+mod walker;
+mod file_kind;
+mod dir_context;
+mod doc_comment;
+mod graph_filter;
+mod symbol_graph;
+mod scanner;
+mod estimate;
+mod overlay;
+mod position_map;
+mod metrics;
+mod encoding;
+mod file_reader;
+mod signature;
+mod nesting;
+mod sfc;
+mod directives;
+mod truncation;
+mod ordering;
+mod markdown;
+mod frameworks;
+#[cfg(feature = "fixtures")]
+mod fixtures;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "index-store")]
+mod index_store;
+#[cfg(feature = "watch")]
+mod watcher;
+#[cfg(feature = "plugins")]
+mod plugin;
+#[cfg(feature = "wasm")]
+mod wasm;
 
 // 旧版实现（保留）
 mod parser;
@@ -12,6 +56,49 @@ mod extractor;
 pub use types::*;
 pub use language::SupportedLanguage;
 pub use language_manager::LanguageManager;
+pub use hooks::{apply_entity_hook, EntityContext, EntityHook};
+pub use diagram::{render_diagram, DiagramEdge, DiagramFormat, DiagramNode, EdgeKind, Subgraph};
+pub use index::{
+    ChunkOptions, CodeIndex, CompactStats, IndexSnapshot, IndexStats, IndexedEntity, IndexedFile, RankHints,
+    SharedIndex, SizedItem, TagRule, TagRules, Tombstones,
+};
+pub use report::render_html_report;
+pub use code_map::{render_code_map, CodeMapOptions};
+pub use quality::{quality_report, KindScore, LanguageQuality, QualityReport};
+pub use streaming::{CreditGate, StreamStats, StreamingIndexer};
+pub use summary_cache::{content_hash, CachedHook, CachedSummary, SummaryCache};
+pub use throttle::{Throttle, ThrottleOptions};
+pub use paths::{canonical_path, normalize_path, redact_path, PathMode};
+pub use walker::{walk_project, walk_project_filtered, WalkOptions, WalkResult, WalkStats, WalkedFile};
+pub use file_kind::{classify_file, AssetFile, FileKind};
+pub use ext_to_lang::{classify_path, PathInfo};
+pub use dir_context::DirectoryContext;
+pub use scanner::{ProjectScanner, ScanOptions, ScanResult, ScanStats};
+pub use estimate::{estimate_index, IndexEstimate, LanguageEstimate};
+pub use overlay::Overlay;
+pub use position_map::{LineHunk, PositionMap};
+pub use metrics::count_tokens;
+pub use encoding::{decode_source, DecodedSource};
+pub use signature::pretty_signature;
+pub use nesting::EntityNode;
+pub use directives::file_directives;
+#[cfg(feature = "index-store")]
+pub use index_store::{store_fingerprint, IndexStore};
+#[cfg(feature = "watch")]
+pub use watcher::{ProjectWatcher, WatchEvent, WatchOptions};
+#[cfg(feature = "plugins")]
+pub use plugin::{
+    LanguagePack, SynapseLanguagePack, VisibilityCallback, PLUGIN_ABI_VERSION, PLUGIN_ENTRY_SYMBOL, VISIBILITY_INTERNAL,
+    VISIBILITY_PRIVATE, VISIBILITY_PROTECTED, VISIBILITY_PUBLIC, VISIBILITY_UNKNOWN,
+};
+pub use graph_filter::GraphFilter;
+pub use symbol_graph::{
+    build_symbol_graph, build_symbol_graph_filtered, import_summary, import_summary_filtered, ComponentEdge, FileEdge,
+    ModifiesEdge, PackageUsage, SymbolEdge, SymbolGraph, UnresolvedImport,
+};
+
+#[cfg(feature = "fixtures")]
+pub use fixtures::{fixture_extension, fixture_snapshot};
 
 // 旧版 API（保留兼容性）
 pub use parser::ASTParser as LegacyASTParser;
@@ -40,4 +127,11 @@ mod tests {
         let langs = LanguageManager::supported_languages();
         assert!(langs.len() >= 2); // 至少 TS/JS
     }
+    
+    #[test]
+    fn test_language_from_str() {
+        assert_eq!("ts".parse::<SupportedLanguage>(), Ok(SupportedLanguage::TypeScript));
+        assert_eq!("JavaScript".parse::<SupportedLanguage>(), Ok(SupportedLanguage::JavaScript));
+        assert!("cobol".parse::<SupportedLanguage>().is_err());
+    }
 }
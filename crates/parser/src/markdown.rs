@@ -0,0 +1,177 @@
+//! Markdown 文档：围栏代码块与链接
+//!
+//! 标题层级与代码块实体由 tree-sitter-md 的块语法提取；这里按行扫描代码块的内容范围
+//! （供按语言标记递归解析）以及行内链接（块语法不展开行内内容）。
+
+#![cfg_attr(not(feature = "markdown"), allow(dead_code))]
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::ops::Range;
+
+use crate::types::{DocumentLink, Range as LineRange};
+
+lazy_static! {
+    /// 行内链接与图片：`[文字](目标 "标题")`、`![替代文本](图片)`
+    static ref INLINE_LINK: Regex =
+        Regex::new(r#"(!?)\[([^\]]*)\]\(\s*<?([^)\s>]+)>?(?:\s+["'(][^)]*)?\)"#).unwrap();
+    /// 自动链接：`<https://example.com>`
+    static ref AUTOLINK: Regex = Regex::new(r"<((?:(?:https?|ftp)://|mailto:)[^>\s]+)>").unwrap();
+    /// 链接引用定义：`[标签]: 目标`
+    static ref REFERENCE: Regex = Regex::new(r"^ {0,3}\[([^\]]+)\]:\s*<?([^\s>]+)>?").unwrap();
+    /// 行内代码（其中的方括号不是链接）
+    static ref CODE_SPAN: Regex = Regex::new(r"`+[^`]*`+").unwrap();
+}
+
+/// 一个围栏代码块
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CodeBlock {
+    /// 语言标记（信息串的第一个词，小写）；没有时为空
+    pub language: String,
+    /// 块内容（开闭围栏之间）的字节范围
+    pub content: Range<usize>,
+    /// 开始围栏所在行（1 起）
+    pub start_line: usize,
+    /// 结束围栏所在行；未闭合时为最后一行
+    pub end_line: usize,
+}
+
+/// 开始围栏：最多 3 个空格缩进，后接至少 3 个 `` ` `` 或 `~`，返回 (围栏字符, 长度, 信息串)
+fn opening_fence(line: &str) -> Option<(char, usize, &str)> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = &line[indent..];
+    let fence = rest.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let len = rest.len() - rest.trim_start_matches(fence).len();
+    (len >= 3).then(|| (fence, len, rest[len..].trim()))
+}
+
+/// 按行切分，附带每行的起始字节偏移（行文本不含换行符）
+fn lines_with_offsets(source_code: &str) -> Vec<(usize, &str)> {
+    let mut offset = 0;
+    source_code
+        .split_inclusive('\n')
+        .map(|line| {
+            let start = offset;
+            offset += line.len();
+            (start, line.trim_end_matches(['\n', '\r']))
+        })
+        .collect()
+}
+
+/// 找出全部围栏代码块（未闭合的块延续到文件末尾）
+pub(crate) fn code_blocks(source_code: &str) -> Vec<CodeBlock> {
+    let lines = lines_with_offsets(source_code);
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let Some((fence, len, info)) = opening_fence(lines[i].1) else {
+            i += 1;
+            continue;
+        };
+        let start_line = i + 1;
+        let content_start = lines.get(i + 1).map_or(source_code.len(), |(offset, _)| *offset);
+        let mut content_end = source_code.len();
+        i += 1;
+        while i < lines.len() {
+            let (offset, line) = lines[i];
+            i += 1;
+            let trimmed = line.trim();
+            if trimmed.len() >= len && trimmed.chars().all(|c| c == fence) {
+                content_end = offset;
+                break;
+            }
+        }
+        blocks.push(CodeBlock {
+            language: info.split_whitespace().next().unwrap_or_default().to_lowercase(),
+            content: content_start..content_end.max(content_start),
+            start_line,
+            end_line: i.max(start_line),
+        });
+    }
+    blocks
+}
+
+/// 提取文档中的链接（行内链接、图片、自动链接与链接引用定义），跳过代码块与行内代码
+pub(crate) fn document_links(source_code: &str) -> Vec<DocumentLink> {
+    let blocks = code_blocks(source_code);
+    let in_block = |line: usize| blocks.iter().any(|block| (block.start_line..=block.end_line).contains(&line));
+
+    let mut links = Vec::new();
+    for (index, line) in source_code.lines().enumerate() {
+        let line_number = index + 1;
+        if in_block(line_number) {
+            continue;
+        }
+        let range = LineRange {
+            start: line_number,
+            end: line_number,
+        };
+        if let Some(captures) = REFERENCE.captures(line) {
+            links.push(DocumentLink {
+                text: captures[1].to_string(),
+                target: captures[2].to_string(),
+                is_image: false,
+                range,
+            });
+            continue;
+        }
+        let line = CODE_SPAN.replace_all(line, |c: &regex::Captures| " ".repeat(c[0].len()));
+        for captures in INLINE_LINK.captures_iter(&line) {
+            links.push(DocumentLink {
+                text: captures[2].to_string(),
+                target: captures[3].to_string(),
+                is_image: &captures[1] == "!",
+                range: range.clone(),
+            });
+        }
+        for captures in AUTOLINK.captures_iter(&line) {
+            links.push(DocumentLink {
+                text: captures[1].to_string(),
+                target: captures[1].to_string(),
+                is_image: false,
+                range: range.clone(),
+            });
+        }
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "# Guide\n\nSee [install](docs/install.md#linux) and ![logo](img/logo.png \"Logo\").\n\n```ts\nconst a = [1](2);\n```\n\nUse `[x](y)` literally, or visit <https://example.com>.\n\n[spec]: https://spec.commonmark.org\n\n~~~~\nunterminated";
+
+    #[test]
+    fn test_code_blocks() {
+        let blocks = code_blocks(DOC);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].language, "ts");
+        assert_eq!(blocks[0].start_line, 5);
+        assert_eq!(&DOC[blocks[0].content.clone()], "const a = [1](2);\n");
+        assert_eq!(blocks[0].end_line, 7);
+        assert_eq!(blocks[1].language, "");
+        assert_eq!(&DOC[blocks[1].content.clone()], "unterminated");
+    }
+
+    #[test]
+    fn test_document_links() {
+        let links = document_links(DOC);
+        let links: Vec<(&str, &str, bool, usize)> = links
+            .iter()
+            .map(|link| (link.text.as_str(), link.target.as_str(), link.is_image, link.range.start))
+            .collect();
+        assert_eq!(
+            links,
+            vec![
+                ("install", "docs/install.md#linux", false, 3),
+                ("logo", "img/logo.png", true, 3),
+                ("https://example.com", "https://example.com", false, 9),
+                ("spec", "https://spec.commonmark.org", false, 11),
+            ]
+        );
+    }
+}
@@ -0,0 +1,383 @@
+//! 实体指标
+//!
+//! - 规模：行数、字节数与近似 token 数（供调用方按上下文窗口预算打包）
+//! - 复杂度：函数体的圈复杂度、最大嵌套深度与语句数（按各语法共用的节点类型名统计）
+
+use tree_sitter::Node;
+
+use crate::strategies::get_node_text;
+use crate::types::{ComplexityMetrics, EntityMetrics, MetricsOptions, ParseResult, Tokenizer};
+
+/// 函数类实体（计算复杂度；`ParserConfig::bodies` 作用的范围）
+pub(crate) const FUNCTION_KINDS: &[&str] = &["function", "method", "constructor", "destructor", "operator", "modifier"];
+
+/// 决策点：分支、循环、异常捕获、三元表达式、推导式中的 for / if
+const DECISION_KINDS: &[&str] = &[
+    "if_statement",
+    "if_expression",
+    "elif_clause",
+    "guard_statement",
+    "for_statement",
+    "for_in_statement",
+    "for_expression",
+    "enhanced_for_statement",
+    "foreach_statement",
+    "while_statement",
+    "while_expression",
+    "do_statement",
+    "repeat_while_statement",
+    "catch_clause",
+    "except_clause",
+    "conditional_expression",
+    "ternary_expression",
+    "for_in_clause",
+    "if_clause",
+];
+
+/// switch / match 的分支（`default` 分支不计）
+const CASE_KINDS: &[&str] = &[
+    "switch_case",
+    "switch_label",
+    "switch_entry",
+    "case_statement",
+    "case_clause",
+    "expression_case",
+    "type_case",
+    "communication_case",
+    "match_arm",
+];
+
+/// 短路逻辑运算（Python 为 `boolean_operator`，Swift 为 conjunction / disjunction）
+const LOGICAL_KINDS: &[&str] = &["boolean_operator", "conjunction_expression", "disjunction_expression"];
+const LOGICAL_OPERATORS: &[&str] = &["&&", "||", "and", "or"];
+
+/// 加深嵌套的控制结构
+const NESTING_KINDS: &[&str] = &[
+    "if_statement",
+    "if_expression",
+    "for_statement",
+    "for_in_statement",
+    "for_expression",
+    "enhanced_for_statement",
+    "foreach_statement",
+    "while_statement",
+    "while_expression",
+    "do_statement",
+    "repeat_while_statement",
+    "loop_expression",
+    "switch_statement",
+    "switch_expression",
+    "expression_switch_statement",
+    "type_switch_statement",
+    "select_statement",
+    "match_expression",
+    "match_statement",
+    "try_statement",
+    "with_statement",
+];
+
+/// 语句列表节点，其具名子节点均视为语句
+const BLOCK_KINDS: &[&str] = &[
+    "block",
+    "statement_block",
+    "compound_statement",
+    "statement_list",
+    "statements",
+];
+
+/// 函数 / 方法实体的复杂度；其他类型的实体为 None
+pub(crate) fn entity_complexity(def: Node, kind: &str, source_code: &str) -> Option<ComplexityMetrics> {
+    if !FUNCTION_KINDS.contains(&kind) {
+        return None;
+    }
+    // 没有函数体的声明（接口方法、原型）不计算
+    let body = def.child_by_field_name("body")?;
+    let mut metrics = ComplexityMetrics {
+        cyclomatic: 1,
+        ..Default::default()
+    };
+
+    let mut stack = vec![(body, 0)];
+    while let Some((node, depth)) = stack.pop() {
+        let kind = node.kind();
+        if is_decision(node, source_code) {
+            metrics.cyclomatic += 1;
+        }
+        if is_statement(node) {
+            metrics.statements += 1;
+        }
+        let depth = if NESTING_KINDS.contains(&kind) && !is_else_if(node) {
+            depth + 1
+        } else {
+            depth
+        };
+        metrics.max_nesting = metrics.max_nesting.max(depth);
+
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor).map(|child| (child, depth)));
+    }
+    Some(metrics)
+}
+
+fn is_decision(node: Node, source_code: &str) -> bool {
+    let kind = node.kind();
+    if DECISION_KINDS.contains(&kind) || LOGICAL_KINDS.contains(&kind) {
+        return true;
+    }
+    if CASE_KINDS.contains(&kind) {
+        return !get_node_text(node, source_code).trim_start().starts_with("default");
+    }
+    kind == "binary_expression"
+        && node
+            .child_by_field_name("operator")
+            .is_some_and(|op| LOGICAL_OPERATORS.contains(&get_node_text(op, source_code)))
+}
+
+/// 语句：`*_statement` 节点或语句列表中的直接成员（块与注释除外）
+fn is_statement(node: Node) -> bool {
+    let kind = node.kind();
+    if BLOCK_KINDS.contains(&kind) || kind.contains("comment") {
+        return false;
+    }
+    kind.ends_with("_statement") || node.parent().is_some_and(|parent| BLOCK_KINDS.contains(&parent.kind()))
+}
+
+/// `else if`：位于 else 子句中，或作为外层 if 的 `alternative`
+fn is_else_if(node: Node) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    parent.kind() == "else_clause"
+        || parent
+            .child_by_field_name("alternative")
+            .is_some_and(|alternative| alternative.id() == node.id())
+}
+
+/// BPE 近似中一个 ASCII 子词平均覆盖的字符数
+const BPE_CHARS_PER_SUBWORD: usize = 6;
+
+/// 按 `options.tokenizer` 估算文本的 token 数
+pub fn count_tokens(text: &str, options: &MetricsOptions) -> usize {
+    match options.tokenizer {
+        Tokenizer::Bpe => bpe_estimate(text),
+        Tokenizer::Chars => text.chars().count().div_ceil(options.chars_per_token.max(1)),
+        Tokenizer::Words => text.split_whitespace().count(),
+    }
+}
+
+/// 为结果中的每个实体填写 `metrics`（按实体范围内的完整源码行计算）
+pub(crate) fn apply_metrics(result: &mut ParseResult, source_code: &str, options: &MetricsOptions) {
+    // 每行的起始字节
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(source_code.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let line_end = |line: usize| {
+        line_starts
+            .get(line)
+            .map_or(source_code.len(), |&next| next - 1)
+    };
+
+    for info in &mut result.entity_info {
+        let (start, end) = (info.range.start.max(1), info.range.end.max(info.range.start.max(1)));
+        let Some(&from) = line_starts.get(start - 1) else {
+            continue;
+        };
+        let text = &source_code[from..line_end(end).max(from)];
+        info.metrics = Some(EntityMetrics {
+            lines: end - start + 1,
+            bytes: text.len(),
+            tokens: count_tokens(text, options),
+        });
+    }
+}
+
+/// 近似 tiktoken（cl100k）的计数：字母串按驼峰拆成子词，数字每 3 位一个 token，
+/// 连续标点约每 2 个字符一个 token，空白串计 1 个（其后内容前的单个空格并入其后的 token）
+fn bpe_estimate(text: &str) -> usize {
+    let chars: Vec<char> = text.chars().collect();
+    let run_end = |start: usize, pred: &dyn Fn(char) -> bool| {
+        chars[start..].iter().position(|&c| !pred(c)).map_or(chars.len(), |n| start + n)
+    };
+
+    let mut tokens = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let end = if c.is_alphabetic() {
+            let end = run_end(i, &|c| c.is_alphabetic());
+            tokens += word_tokens(&chars[i..end]);
+            end
+        } else if c.is_numeric() {
+            let end = run_end(i, &|c| c.is_numeric());
+            tokens += (end - i).div_ceil(3);
+            end
+        } else if c.is_whitespace() {
+            let end = run_end(i, &|c| c.is_whitespace());
+            let joins_next = c == ' ' && end == i + 1 && end < chars.len();
+            if !joins_next {
+                tokens += 1;
+            }
+            end
+        } else {
+            let end = run_end(i, &|c| !c.is_alphanumeric() && !c.is_whitespace());
+            tokens += (end - i).div_ceil(2);
+            end
+        };
+        i = end;
+    }
+    tokens
+}
+
+/// 字母串的 token 数：ASCII 子词（驼峰边界切分）按长度估算，非 ASCII 字母每字 1 个
+fn word_tokens(word: &[char]) -> usize {
+    let mut tokens = 0;
+    let mut len: usize = 0;
+    for (i, &c) in word.iter().enumerate() {
+        let boundary = !c.is_ascii() || (i > 0 && c.is_uppercase() && word[i - 1].is_lowercase());
+        if boundary {
+            tokens += len.div_ceil(BPE_CHARS_PER_SUBWORD);
+            len = 0;
+        }
+        if c.is_ascii() {
+            len += 1;
+        } else {
+            tokens += 1;
+        }
+    }
+    tokens + len.div_ceil(BPE_CHARS_PER_SUBWORD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ParseOptions;
+    use crate::LanguageManager;
+
+    #[test]
+    fn test_count_tokens() {
+        let bpe = MetricsOptions::default();
+        assert_eq!(count_tokens("", &bpe), 0);
+        // return / a / + / b / ;
+        assert_eq!(count_tokens("return a + b;", &bpe), 5);
+        // get / User / Name / ( / ) + 空白中的换行
+        assert_eq!(count_tokens("getUserName()\n", &bpe), 5);
+        assert_eq!(count_tokens("1234567", &bpe), 3);
+        assert_eq!(count_tokens("你好", &bpe), 2);
+
+        let chars = MetricsOptions {
+            tokenizer: Tokenizer::Chars,
+            chars_per_token: 3,
+        };
+        assert_eq!(count_tokens("abcdefg", &chars), 3);
+        let words = MetricsOptions {
+            tokenizer: Tokenizer::Words,
+            ..Default::default()
+        };
+        assert_eq!(count_tokens("fn main() {\n}\n", &words), 4);
+    }
+
+    #[test]
+    fn test_entity_metrics() {
+        let code = "// note\nexport function add(a: number, b: number) {\n  return a + b;\n}\n";
+        let mut manager = LanguageManager::new();
+        let plain = manager.parse_file("src/math.ts", code).unwrap();
+        assert!(plain.entity_info.iter().all(|info| info.metrics.is_none()));
+
+        manager.set_options(ParseOptions {
+            metrics: Some(MetricsOptions::default()),
+            ..Default::default()
+        });
+        let result = manager.parse_file("src/math.ts", code).unwrap();
+        let add = result
+            .entity_info
+            .iter()
+            .find(|info| info.name.as_deref() == Some("add"))
+            .unwrap();
+        let metrics = add.metrics.unwrap();
+        // 按完整定义计算，而不是只含签名的实体文本
+        assert_eq!(metrics.lines, 3);
+        assert_eq!(metrics.bytes, code.len() - "// note\n".len() - 1);
+        assert!(metrics.tokens > 10 && metrics.tokens < metrics.bytes);
+    }
+
+    #[test]
+    fn test_function_complexity() {
+        let code = r#"
+export function classify(items: number[], strict: boolean) {
+  let total = 0;
+  for (const item of items) {
+    if (item > 10 && strict) {
+      total += item;
+    } else if (item < 0) {
+      try {
+        total -= 1;
+      } catch (e) {
+        return -1;
+      }
+    }
+  }
+  switch (total) {
+    case 0:
+      return 0;
+    default:
+      return total > 100 ? 100 : total;
+  }
+}
+
+export function plain() {
+  return 1;
+}
+"#;
+        let mut manager = LanguageManager::new();
+        let off = manager.parse_file("src/classify.ts", code).unwrap();
+        assert!(off.entity_info.iter().all(|info| info.complexity.is_none()));
+
+        manager.set_options(ParseOptions {
+            complexity: true,
+            ..Default::default()
+        });
+        let result = manager.parse_file("src/classify.ts", code).unwrap();
+        let complexity = |name: &str| {
+            result
+                .entity_info
+                .iter()
+                .find(|info| info.name.as_deref() == Some(name))
+                .and_then(|info| info.complexity)
+                .unwrap()
+        };
+
+        let classify = complexity("classify");
+        // for / if / && / else if / catch / case 0 / 三元
+        assert_eq!(classify.cyclomatic, 8);
+        // for → if → else if（不加深）→ try
+        assert_eq!(classify.max_nesting, 3);
+        assert_eq!(classify.statements, 11);
+
+        let plain = complexity("plain");
+        assert_eq!(plain.cyclomatic, 1);
+        assert_eq!(plain.max_nesting, 0);
+        assert_eq!(plain.statements, 1);
+    }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_python_complexity() {
+        let code = "def pick(xs):\n    if not xs or xs[0] is None:\n        return None\n    elif len(xs) > 3:\n        return [x for x in xs if x]\n    return xs\n";
+        let mut manager = LanguageManager::new();
+        manager.set_options(ParseOptions {
+            complexity: true,
+            ..Default::default()
+        });
+        let result = manager.parse_file("pick.py", code).unwrap();
+        let pick = result
+            .entity_info
+            .iter()
+            .find(|info| info.name.as_deref() == Some("pick"))
+            .and_then(|info| info.complexity)
+            .unwrap();
+        // if / or / elif / 推导式的 for 与 if
+        assert_eq!(pick.cyclomatic, 6);
+        assert_eq!(pick.max_nesting, 1);
+        assert_eq!(pick.statements, 4);
+    }
+}
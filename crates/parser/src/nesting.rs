@@ -0,0 +1,248 @@
+//! 嵌套的类型与命名空间：内部类、嵌套命名空间中的成员
+//!
+//! 两种表示供调用方按需选用：
+//! - 树：`EntityInfo::parent` 指向外层类型 / 命名空间实体，`ParseResult::entity_tree` 据此组装
+//! - 扁平表：`ParseOptions::qualified_names` 开启时填写 `EntityInfo::qualified_name`
+//!   （`Outer.Inner.method`，C / C++ / Rust 为 `ns::Outer::method`）
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tree_sitter::Node;
+
+use crate::strategies::get_node_text;
+#[cfg(feature = "elixir")]
+use crate::strategies::ElixirStrategy;
+#[cfg(feature = "zig")]
+use crate::strategies::ZigStrategy;
+use crate::types::{ParseResult, StructuredEntity};
+
+/// 构成名字作用域的节点类型
+const SCOPE_KINDS: &[&str] = &[
+    // Java / C# / TypeScript / PHP / Swift（Swift 的类、结构体、枚举与扩展均为 class_declaration）
+    "class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "record_declaration",
+    "annotation_type_declaration",
+    "struct_declaration",
+    "trait_declaration",
+    "namespace_declaration",
+    "internal_module",
+    "protocol_declaration",
+    // C / C++
+    "class_specifier",
+    "struct_specifier",
+    "union_specifier",
+    "enum_specifier",
+    "namespace_definition",
+    // Python / Rust
+    "class_definition",
+    "mod_item",
+    // GraphQL（根类型的字段归属 Query / Mutation / Subscription）
+    "object_type_definition",
+    // Protobuf（嵌套消息、服务中的 rpc）
+    "message",
+    "service",
+    // 配置文件（JSON 的键、YAML 的映射项、TOML 的表）
+    "pair",
+    "block_mapping_pair",
+    "table",
+    // Markdown（标题层级）
+    "section",
+];
+
+/// 外层作用域：定义节点的字节范围与名字（匿名命名空间等没有名字）
+#[derive(Debug, Clone)]
+pub(crate) struct Scope {
+    range: (usize, usize),
+    name: Option<String>,
+}
+
+/// 树形表示中的一个实体及其直接成员
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityNode {
+    #[serde(flatten)]
+    pub entity: StructuredEntity,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub children: Vec<EntityNode>,
+}
+
+/// 定义节点的外层作用域，由内向外
+pub(crate) fn enclosing_scopes(def: Node, source_code: &str) -> Vec<Scope> {
+    let mut scopes = Vec::new();
+    let mut current = def.parent();
+    while let Some(node) = current {
+        if SCOPE_KINDS.contains(&node.kind()) {
+            scopes.push(Scope {
+                range: (node.start_byte(), node.end_byte()),
+                name: node
+                    .child_by_field_name("name")
+                    .map(|name| get_node_text(name, source_code).to_string()),
+            });
+        }
+        // Elixir 的模块定义是 `defmodule` 等宏调用，按调用目标识别
+        #[cfg(feature = "elixir")]
+        if let Some(name) = ElixirStrategy::module_name(node, source_code) {
+            scopes.push(Scope {
+                range: (node.start_byte(), node.end_byte()),
+                name: Some(name),
+            });
+        }
+        // Zig 的 struct 等是匿名的容器表达式，名字来自所赋值的常量
+        #[cfg(feature = "zig")]
+        if let Some(name) = ZigStrategy::container_name(node, source_code) {
+            scopes.push(Scope {
+                range: (node.start_byte(), node.end_byte()),
+                name: Some(name),
+            });
+        }
+        current = node.parent();
+    }
+    scopes
+}
+
+/// 可被扩展（Swift `extension`）的类型实体
+const EXTENDABLE_KINDS: &[&str] = &["class", "struct", "enum", "protocol"];
+
+/// 限定名的分隔符
+fn separator(language: &str) -> &'static str {
+    match language {
+        "C" | "C++" | "Rust" => "::",
+        "PHP" => "\\",
+        _ => ".",
+    }
+}
+
+/// 按外层作用域填写 `parent`，`qualify` 时同时填写 `qualified_name`
+///
+/// `defs[i]` 为第 i 个实体定义节点的字节范围，`scopes[i]` 为其外层作用域。
+/// 扩展中的成员归属被扩展的类型（同一文件中声明了该类型时）。
+pub(crate) fn link_entities(
+    result: &mut ParseResult,
+    defs: &[(usize, usize)],
+    scopes: &[Vec<Scope>],
+    qualify: bool,
+) {
+    let by_range: HashMap<(usize, usize), usize> = defs
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, range)| (*range, i))
+        .collect();
+    let separator = separator(&result.language);
+    let extended: Vec<Option<usize>> = result
+        .entity_info
+        .iter()
+        .map(|info| {
+            if info.kind != "extension" {
+                return None;
+            }
+            result.entity_info.iter().position(|target| {
+                EXTENDABLE_KINDS.contains(&target.kind.as_str()) && target.name.is_some() && target.name == info.name
+            })
+        })
+        .collect();
+
+    for (i, info) in result.entity_info.iter_mut().enumerate() {
+        let Some(scopes) = scopes.get(i) else {
+            continue;
+        };
+        info.parent = scopes
+            .iter()
+            .find_map(|scope| by_range.get(&scope.range).copied())
+            .map(|parent| extended[parent].unwrap_or(parent))
+            .filter(|&parent| parent != i);
+
+        if qualify && !scopes.is_empty() {
+            if let Some(name) = &info.name {
+                let mut parts: Vec<&str> = scopes.iter().rev().filter_map(|scope| scope.name.as_deref()).collect();
+                // 名字已带所属类型（C++ 构造 / 析构函数 `Foo::Foo`）时不再重复
+                if parts.last().is_some_and(|owner| name.starts_with(&format!("{}{}", owner, separator))) {
+                    parts.pop();
+                }
+                parts.push(name);
+                info.qualified_name = Some(parts.join(separator));
+            }
+        }
+    }
+}
+
+impl ParseResult {
+    /// 按 `parent` 组装的实体树（顶层实体为根，成员按源码顺序）
+    pub fn entity_tree(&self) -> Vec<EntityNode> {
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); self.entity_info.len()];
+        let mut roots = Vec::new();
+        for (i, info) in self.entity_info.iter().enumerate() {
+            match info.parent.filter(|&parent| parent < self.entity_info.len() && parent != i) {
+                Some(parent) => children[parent].push(i),
+                None => roots.push(i),
+            }
+        }
+
+        let entities = self.structured_entities();
+        fn build(i: usize, entities: &[StructuredEntity], children: &[Vec<usize>]) -> EntityNode {
+            EntityNode {
+                entity: entities[i].clone(),
+                children: children[i].iter().map(|&child| build(child, entities, children)).collect(),
+            }
+        }
+        roots.into_iter().map(|i| build(i, &entities, &children)).collect()
+    }
+}
+
+#[cfg(all(test, any(feature = "java", feature = "cpp")))]
+mod tests {
+    use crate::types::ParseOptions;
+    use crate::LanguageManager;
+
+    #[cfg(feature = "java")]
+    #[test]
+    fn test_java_inner_classes() {
+        let code = "package app;\n\npublic class Outer {\n  static class Inner {\n    void run() {}\n  }\n  void stop() {}\n}\n";
+        let mut manager = LanguageManager::new();
+        let plain = manager.parse_file("src/Outer.java", code).unwrap();
+        assert!(plain.entity_info.iter().all(|info| info.qualified_name.is_none()));
+
+        let tree = plain.entity_tree();
+        let outer = tree.iter().find(|node| node.entity.name.as_deref() == Some("Outer")).unwrap();
+        let inner = outer.children.iter().find(|node| node.entity.name.as_deref() == Some("Inner")).unwrap();
+        assert_eq!(inner.children[0].entity.name.as_deref(), Some("run"));
+        assert!(outer.children.iter().any(|node| node.entity.name.as_deref() == Some("stop")));
+
+        manager.set_options(ParseOptions {
+            qualified_names: true,
+            ..Default::default()
+        });
+        let flat = manager.parse_file("src/Outer.java", code).unwrap();
+        let qualified: Vec<&str> = flat.entity_info.iter().filter_map(|info| info.qualified_name.as_deref()).collect();
+        assert!(qualified.contains(&"Outer.Inner"));
+        assert!(qualified.contains(&"Outer.Inner.run"));
+        assert!(qualified.contains(&"Outer.stop"));
+    }
+
+    #[cfg(feature = "cpp")]
+    #[test]
+    fn test_cpp_nested_namespaces() {
+        let code = "namespace app {\nnamespace net {\nclass Socket {\n public:\n  void close() {}\n};\n}\n}\n";
+        let mut manager = LanguageManager::new();
+        manager.set_options(ParseOptions {
+            qualified_names: true,
+            ..Default::default()
+        });
+        let result = manager.parse_file("src/socket.cpp", code).unwrap();
+        let socket = result
+            .entity_info
+            .iter()
+            .position(|info| info.name.as_deref() == Some("Socket"))
+            .unwrap();
+        assert_eq!(result.entity_info[socket].qualified_name.as_deref(), Some("app::net::Socket"));
+        let close = result
+            .entity_info
+            .iter()
+            .find(|info| info.name.as_deref() == Some("close"))
+            .unwrap();
+        assert_eq!(close.qualified_name.as_deref(), Some("app::net::Socket::close"));
+        assert_eq!(close.parent, Some(socket));
+    }
+}
@@ -0,0 +1,53 @@
+//! 实体的输出顺序（见 `ParseOptions::entity_order`）
+//!
+//! 默认按 query 的捕获顺序输出，导入、注释与函数交错出现；按位置排序或按类型分组后，
+//! 调用方无需再对每个文件重新排序。`entities` 与 `entity_info` 同步重排，`parent` 下标随之改写。
+
+use std::collections::HashMap;
+
+use crate::types::{EntityOrder, ParseResult};
+
+/// 按 `order` 重排结果中的实体
+pub(crate) fn apply_entity_order(result: &mut ParseResult, order: EntityOrder) {
+    let Some(permutation) = permutation(result, order) else {
+        return;
+    };
+
+    // 旧下标 → 新下标
+    let mut new_index = vec![0; permutation.len()];
+    for (new, &old) in permutation.iter().enumerate() {
+        new_index[old] = new;
+    }
+
+    let mut entities: Vec<Option<String>> = std::mem::take(&mut result.entities).into_iter().map(Some).collect();
+    let mut entity_info: Vec<_> = std::mem::take(&mut result.entity_info).into_iter().map(Some).collect();
+    for &old in &permutation {
+        if let (Some(text), Some(mut info)) = (entities[old].take(), entity_info[old].take()) {
+            info.parent = info.parent.and_then(|parent| new_index.get(parent).copied());
+            result.entities.push(text);
+            result.entity_info.push(info);
+        }
+    }
+}
+
+/// 新顺序中每个位置对应的旧下标（捕获顺序或实体数与元数据数不一致时为 None）
+fn permutation(result: &ParseResult, order: EntityOrder) -> Option<Vec<usize>> {
+    if order == EntityOrder::Capture || result.entities.len() != result.entity_info.len() {
+        return None;
+    }
+    let info = &result.entity_info;
+    let mut indices: Vec<usize> = (0..info.len()).collect();
+    // 位置：起始行，其次结束行靠后的在前（外层定义先于其成员）；同位置保持捕获顺序
+    indices.sort_by_key(|&i| (info[i].range.start, std::cmp::Reverse(info[i].range.end)));
+
+    if order == EntityOrder::Kind {
+        // 类型分组按该类型首次出现的位置排列，组内按位置
+        let mut group: HashMap<&str, usize> = HashMap::new();
+        for &i in &indices {
+            let next = group.len();
+            group.entry(info[i].kind.as_str()).or_insert(next);
+        }
+        indices.sort_by_key(|&i| group[info[i].kind.as_str()]);
+    }
+    Some(indices)
+}
@@ -0,0 +1,123 @@
+//! 未保存的编辑器缓冲区
+//!
+//! 覆盖层中的内容优先于磁盘：扫描项目、读取文件时先查覆盖层，尚未保存到磁盘的新文件
+//! 同样参与扫描。路径按 `normalize_path` 比较，调用方应与扫描根目录使用同一种形式
+//! （通常都用绝对路径）。
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::encoding::decode_source;
+use crate::paths::normalize_path;
+use crate::position_map::PositionMap;
+
+/// 文件路径 -> 未保存的内容
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Overlay {
+    files: BTreeMap<String, String>,
+}
+
+impl Overlay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置（或替换）文件的未保存内容
+    pub fn set(&mut self, path: &str, content: impl Into<String>) {
+        self.files.insert(normalize_path(path), content.into());
+    }
+
+    /// 移除文件的未保存内容（回到磁盘内容），返回是否存在
+    pub fn clear(&mut self, path: &str) -> bool {
+        self.files.remove(&normalize_path(path)).is_some()
+    }
+
+    /// 移除全部未保存内容
+    pub fn clear_all(&mut self) {
+        self.files.clear();
+    }
+
+    /// 文件的未保存内容
+    pub fn get(&self, path: &str) -> Option<&str> {
+        self.files.get(&normalize_path(path)).map(String::as_str)
+    }
+
+    /// 有未保存内容的文件路径（已规范化，按路径排序）
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// 读取文件：有未保存内容时返回它，否则读取磁盘（非 UTF-8 内容按 `decode_source` 解码）
+    pub fn read(&self, path: impl AsRef<Path>) -> io::Result<String> {
+        let path = path.as_ref();
+        match self.get(&path.to_string_lossy()) {
+            Some(content) => Ok(content.to_string()),
+            None => read_source(path),
+        }
+    }
+
+    /// 文件磁盘版本与未保存版本之间的行号映射；没有未保存内容时返回 None，
+    /// 磁盘上尚不存在的文件视为空文件
+    pub fn position_map(&self, path: impl AsRef<Path>) -> io::Result<Option<PositionMap>> {
+        let path = path.as_ref();
+        let Some(overlay) = self.get(&path.to_string_lossy()) else {
+            return Ok(None);
+        };
+        let disk = match read_source(path) {
+            Ok(disk) => disk,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Some(PositionMap::between(&disk, overlay)))
+    }
+
+    /// 根目录下的覆盖文件：(相对路径, 规范化路径)，按路径排序
+    pub fn files_under<'a>(&'a self, root: &str) -> impl Iterator<Item = (&'a str, &'a str)> + 'a {
+        let root = normalize_path(root);
+        let prefix = if root == "." { String::new() } else { format!("{}/", root.trim_end_matches('/')) };
+        self.files.keys().filter_map(move |path| {
+            let relative = path.strip_prefix(prefix.as_str())?;
+            Some((relative, path.as_str()))
+        })
+    }
+}
+
+fn read_source(path: &Path) -> io::Result<String> {
+    fs::read(path).map(|bytes| decode_source(&bytes).text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlay_paths() {
+        let mut overlay = Overlay::new();
+        overlay.set("/ws/src/./a.ts", "export const a = 1;");
+        overlay.set("/ws/src/new.ts", "export const b = 2;");
+        overlay.set("/other/c.ts", "");
+
+        assert_eq!(overlay.get("/ws/src/a.ts"), Some("export const a = 1;"));
+        assert_eq!(overlay.read("/ws/src/a.ts").unwrap(), "export const a = 1;");
+        assert_eq!(
+            overlay.files_under("/ws/").collect::<Vec<_>>(),
+            vec![("src/a.ts", "/ws/src/a.ts"), ("src/new.ts", "/ws/src/new.ts")]
+        );
+
+        assert!(overlay.clear("/ws/src/a.ts"));
+        assert!(!overlay.clear("/ws/src/a.ts"));
+        assert!(overlay.read("/ws/src/a.ts").is_err());
+        overlay.clear_all();
+        assert!(overlay.is_empty());
+    }
+}
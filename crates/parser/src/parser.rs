@@ -2,6 +2,8 @@ use tree_sitter::{Node, Parser};
 // tree-sitter 0.23.x 使用 LANGUAGE 常量
 
 use crate::extractor::CodeEntityExtractor;
+use crate::language::SupportedLanguage;
+use crate::language_manager::load_tree_sitter_language;
 use crate::types::*;
 
 /// AST 解析器
@@ -22,6 +24,16 @@ impl ASTParser {
         Ok(Self { parser })
     }
 
+    /// 使用指定语言的语法创建解析器（类、方法与字段的提取覆盖 TypeScript / Java / Python）
+    pub fn for_language(lang: SupportedLanguage) -> Result<Self, String> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&load_tree_sitter_language(lang)?)
+            .map_err(|e| format!("Failed to set language: {}", e))?;
+
+        Ok(Self { parser })
+    }
+
     /// 解析文件内容
     pub fn parse_file(&mut self, file_path: &str, source_code: &str) -> Result<LegacyParseResult, String> {
         let tree = self.parser
@@ -42,14 +54,14 @@ impl ASTParser {
 
         // 检查语法错误
         if root_node.has_error() {
-            self.collect_errors(root_node, source_code, &mut result);
+            Self::collect_errors(root_node, &mut result);
         }
         
         Ok(result)
     }
 
     /// 收集语法错误
-    fn collect_errors(&self, node: Node, source_code: &str, result: &mut LegacyParseResult) {
+    fn collect_errors(node: Node, result: &mut LegacyParseResult) {
         if node.is_error() {
             result.errors.push(ParseError {
                 message: format!("Syntax error at {:?}", node.range()),
@@ -62,7 +74,7 @@ impl ASTParser {
 
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
-            self.collect_errors(child, source_code, result);
+            Self::collect_errors(child, result);
         }
     }
 }
@@ -129,4 +141,19 @@ function broken(
         
         assert!(!result.errors.is_empty());
     }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_parse_python_class_with_language() {
+        let mut parser = ASTParser::for_language(SupportedLanguage::Python).unwrap();
+        let code = "class Point:\n    x: int\n    y: int\n";
+
+        let result = parser.parse_file("point.py", code).unwrap();
+
+        let Some(CodeEntity::Class(class)) = result.entities.first() else {
+            panic!("expected a class entity");
+        };
+        assert_eq!(class.name, "Point");
+        assert_eq!(class.properties.len(), 2);
+    }
 }
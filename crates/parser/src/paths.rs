@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+
+/// 输出中文件路径的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PathMode {
+    /// 不改写为相对路径，仅做规范化（默认）
+    #[default]
+    Preserve,
+    /// 相对项目根目录，分隔符统一为 `/`
+    Relative,
+    /// 相对路径的哈希 + 原扩展名（如 `3f2a…9c.test.ts`），同一路径结果稳定
+    Anonymized,
+}
+
+/// 按模式改写路径，避免开发机的绝对路径写入共享索引
+///
+/// 路径总会先经过 `normalize_path`；未提供 `project_root` 时以原路径为准，
+/// 根目录之外的文件用 `../` 表示。
+pub fn redact_path(path: &str, project_root: Option<&str>, mode: PathMode) -> String {
+    let path = normalize_path(path);
+    if mode == PathMode::Preserve {
+        return path;
+    }
+
+    let relative = match project_root {
+        Some(root) => relative_to(&path, &normalize_path(root)),
+        None => path,
+    };
+    match mode {
+        PathMode::Anonymized => anonymize(&relative),
+        _ => relative,
+    }
+}
+
+/// 规范化路径，使同一仓库在不同平台上得到相同的记录
+///
+/// 分隔符统一为 `/`，去掉 `.` 与多余的分隔符，按字面折叠 `..`，
+/// 盘符统一为大写并去掉 Windows 的 `\\?\` 前缀。不访问文件系统。
+pub fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let path = path.strip_prefix("//?/").unwrap_or(&path);
+
+    let (mut prefix, rest) = match path.as_bytes() {
+        [letter, b':', ..] if letter.is_ascii_alphabetic() => {
+            (format!("{}:", letter.to_ascii_uppercase() as char), &path[2..])
+        }
+        _ => (String::new(), path),
+    };
+    // UNC 路径（`//server/share`）保留双斜杠
+    if rest.starts_with("//") {
+        prefix.push('/');
+    }
+    let rooted = rest.starts_with('/');
+    if rooted {
+        prefix.push('/');
+    }
+
+    let mut parts: Vec<&str> = Vec::new();
+    for part in rest.split('/') {
+        match part {
+            "" | "." => {}
+            ".." if parts.last().is_some_and(|p| *p != "..") => {
+                parts.pop();
+            }
+            // 根目录之上没有父目录
+            ".." if rooted => {}
+            _ => parts.push(part),
+        }
+    }
+
+    let joined = format!("{}{}", prefix, parts.join("/"));
+    if joined.is_empty() {
+        ".".to_string()
+    } else {
+        joined
+    }
+}
+
+/// 解析符号链接得到真实路径（文件不存在时原样返回）
+///
+/// Windows 上同时得到磁盘上的真实大小写。
+pub fn canonical_path(path: &str) -> String {
+    std::fs::canonicalize(path)
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| path.to_string())
+}
+
+/// 按路径组件计算相对路径（参数均已规范化，不访问文件系统）
+fn relative_to(path: &str, root: &str) -> String {
+    // 相对路径无法与绝对根目录比较，原样保留
+    if is_absolute(path) != is_absolute(root) {
+        return path.to_string();
+    }
+
+    let path: Vec<&str> = path.split('/').filter(|p| !p.is_empty() && *p != ".").collect();
+    let root: Vec<&str> = root.split('/').filter(|p| !p.is_empty() && *p != ".").collect();
+    let common = path
+        .iter()
+        .zip(&root)
+        .take_while(|(a, b)| same_component(a, b))
+        .count();
+
+    let mut parts: Vec<&str> = vec![".."; root.len() - common];
+    parts.extend(&path[common..]);
+    if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    }
+}
+
+fn is_absolute(path: &str) -> bool {
+    path.starts_with('/') || path.as_bytes().get(1) == Some(&b':')
+}
+
+/// Windows 文件系统不区分大小写
+fn same_component(a: &str, b: &str) -> bool {
+    if cfg!(windows) {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        a == b
+    }
+}
+
+fn anonymize(relative: &str) -> String {
+    let name = relative.rsplit('/').next().unwrap_or(relative);
+    // 保留复合扩展名（`.d.ts`、`.test.ts`），测试 / 声明文件的识别不受影响
+    let suffix = name
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| *c == '.')
+        .map(|(i, _)| &name[i..])
+        .unwrap_or("");
+    let hash = blake3::hash(relative.as_bytes()).to_hex();
+    format!("{}{}", &hash[..16], suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_paths() {
+        let root = Some("/home/dev/project");
+        assert_eq!(redact_path("/home/dev/project/src/a.ts", root, PathMode::Relative), "src/a.ts");
+        assert_eq!(redact_path("/home/dev/other/b.ts", root, PathMode::Relative), "../other/b.ts");
+        assert_eq!(redact_path("src/c.ts", root, PathMode::Relative), "src/c.ts");
+        assert_eq!(redact_path("/home/dev/project/src/a.ts", root, PathMode::Preserve), "/home/dev/project/src/a.ts");
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("./src//a/../b.ts"), "src/b.ts");
+        assert_eq!(normalize_path("c:\\Users\\dev\\repo\\x.ts"), "C:/Users/dev/repo/x.ts");
+        assert_eq!(normalize_path("\\\\?\\D:\\repo"), "D:/repo");
+        assert_eq!(normalize_path("/../etc/"), "/etc");
+        assert_eq!(normalize_path("../a/./b"), "../a/b");
+        assert_eq!(normalize_path("a/.."), ".");
+    }
+
+    #[test]
+    fn test_windows_paths_match_posix() {
+        let posix = redact_path("/work/repo/src/a.ts", Some("/work/repo"), PathMode::Relative);
+        let windows = redact_path("C:\\work\\repo\\src\\a.ts", Some("c:\\work\\repo\\"), PathMode::Relative);
+        assert_eq!(posix, "src/a.ts");
+        assert_eq!(windows, posix);
+    }
+
+    #[test]
+    fn test_anonymized_paths() {
+        let root = Some("/home/dev/project");
+        let a = redact_path("/home/dev/project/src/a.test.ts", root, PathMode::Anonymized);
+        assert!(a.ends_with(".test.ts"));
+        assert!(!a.contains("src"));
+        // 与根目录无关，只取决于相对路径
+        assert_eq!(a, redact_path("/tmp/ci/src/a.test.ts", Some("/tmp/ci"), PathMode::Anonymized));
+        assert_eq!(redact_path("/p/.eslintrc", Some("/p"), PathMode::Anonymized).len(), 16);
+    }
+}
@@ -0,0 +1,282 @@
+//! 语言包插件（`plugins` feature）
+//!
+//! 第三方语言（Gleam、Haskell 等）编译成独立的动态库（cdylib），不随本 crate 发版，
+//! 由 `LanguageManager::load_language_pack` 在运行时加载。动态库导出注册函数
+//! `synapse_language_pack`，返回描述语言包的 `SynapseLanguagePack`：
+//!
+//! - `language`：tree-sitter 语法（即语法仓库生成的 `tree_sitter_<lang>()`）
+//! - `query`：`definition.<kind>` 捕获为实体（类型取 `<kind>`），名字含 `comment` 的捕获为注释
+//! - `visibility`：可选的策略回调，判断实体的导出状态与可见性
+//!
+//! ABI 的完整说明见 LANGUAGE_SUPPORT.md 的 "Language Packs" 一节。结构体只在末尾追加字段，
+//! 不兼容的修改提升 `PLUGIN_ABI_VERSION`。
+
+use std::ffi::{c_char, CStr};
+use std::path::Path;
+use tree_sitter::{Language, Query};
+use tree_sitter_language::LanguageFn;
+
+/// 当前的插件 ABI 版本
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// 动态库导出的注册函数名（`extern "C" fn() -> *const SynapseLanguagePack`）
+pub const PLUGIN_ENTRY_SYMBOL: &str = "synapse_language_pack";
+
+/// `visibility` 回调的返回值：无法判断（不导出、无可见性信息）
+pub const VISIBILITY_UNKNOWN: i32 = 0;
+/// 公开（视为导出）
+pub const VISIBILITY_PUBLIC: i32 = 1;
+pub const VISIBILITY_PRIVATE: i32 = 2;
+pub const VISIBILITY_PROTECTED: i32 = 3;
+/// 包 / 模块内可见
+pub const VISIBILITY_INTERNAL: i32 = 4;
+
+/// 可见性回调：参数为定义节点的类型名（NUL 结尾）与定义的源码（UTF-8，不以 NUL 结尾），
+/// 返回 `VISIBILITY_*` 之一；指针只在调用期间有效
+pub type VisibilityCallback = unsafe extern "C" fn(node_kind: *const c_char, text: *const u8, text_len: usize) -> i32;
+
+/// 注册函数返回的语言包描述（C ABI）
+///
+/// 字符串均为 NUL 结尾的 UTF-8，与函数指针一样须在进程生命周期内有效（通常是静态数据）。
+#[repr(C)]
+pub struct SynapseLanguagePack {
+    /// 编译时的 `PLUGIN_ABI_VERSION`
+    pub abi_version: u32,
+    /// 语言名（结果的 `language` 字段）
+    pub name: *const c_char,
+    /// 逗号分隔的扩展名，不含点（如 `"ex,exs"`）
+    pub extensions: *const c_char,
+    /// 返回 tree-sitter 语法（`TSLanguage *`）
+    pub language: unsafe extern "C" fn() -> *const (),
+    /// 提取实体的 query
+    pub query: *const c_char,
+    /// 可选的可见性回调（为空时实体均不导出、无可见性信息）
+    pub visibility: Option<VisibilityCallback>,
+}
+
+// SAFETY: 描述中只有指向静态数据的指针与函数指针，按 ABI 约定不会被修改
+unsafe impl Sync for SynapseLanguagePack {}
+
+/// 加载后的语言包
+#[derive(Clone)]
+pub struct LanguagePack {
+    name: String,
+    extensions: Vec<String>,
+    language: Language,
+    query: String,
+    visibility: Option<VisibilityCallback>,
+}
+
+impl std::fmt::Debug for LanguagePack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LanguagePack")
+            .field("name", &self.name)
+            .field("extensions", &self.extensions)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LanguagePack {
+    /// 从动态库加载语言包
+    ///
+    /// 动态库加载后不再卸载：语法表与回调在进程生命周期内都可能被使用。
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let load_error = |e: String| format!("Failed to load language pack {}: {}", path.display(), e);
+
+        // SAFETY: 加载动态库会运行其初始化代码，调用方须信任该插件
+        let library = unsafe { libloading::Library::new(path) }.map_err(|e| load_error(e.to_string()))?;
+        let pack = {
+            // SAFETY: 注册函数的签名由 ABI 约定
+            let entry = unsafe {
+                library.get::<unsafe extern "C" fn() -> *const SynapseLanguagePack>(PLUGIN_ENTRY_SYMBOL.as_bytes())
+            }
+            .map_err(|e| load_error(e.to_string()))?;
+            // SAFETY: 同上；返回的描述指向插件内的静态数据
+            unsafe { Self::from_raw(entry()) }.map_err(load_error)?
+        };
+        std::mem::forget(library);
+        Ok(pack)
+    }
+
+    /// 从语言包描述创建（插件已静态链接或由调用方自行加载时使用）
+    ///
+    /// # Safety
+    ///
+    /// `pack` 须为空指针或指向符合 ABI 约定的描述，其中的指针在进程生命周期内有效。
+    pub unsafe fn from_raw(pack: *const SynapseLanguagePack) -> Result<Self, String> {
+        let pack = pack.as_ref().ok_or("registration function returned null")?;
+        if pack.abi_version != PLUGIN_ABI_VERSION {
+            return Err(format!(
+                "ABI version {} is not supported (expected {})",
+                pack.abi_version, PLUGIN_ABI_VERSION
+            ));
+        }
+        let name = c_string(pack.name, "name")?;
+        let extensions = c_string(pack.extensions, "extensions")?
+            .split(',')
+            .map(|ext| ext.trim().trim_start_matches('.').to_ascii_lowercase())
+            .filter(|ext| !ext.is_empty())
+            .collect::<Vec<_>>();
+        if name.is_empty() || extensions.is_empty() {
+            return Err("name and extensions must not be empty".to_string());
+        }
+        let query = c_string(pack.query, "query")?;
+
+        if (pack.language)().is_null() {
+            return Err("language function returned null".to_string());
+        }
+        let language = Language::new(LanguageFn::from_raw(pack.language));
+        // 语法版本不兼容或 query 有误时在加载时报错，而不是在首次解析时
+        Query::new(&language, &query).map_err(|e| format!("Invalid query for {}: {}", name, e))?;
+
+        Ok(Self {
+            name,
+            extensions,
+            language,
+            query,
+            visibility: pack.visibility,
+        })
+    }
+
+    /// 语言名
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 处理的扩展名（小写，不含点）
+    pub fn extensions(&self) -> &[String] {
+        &self.extensions
+    }
+
+    /// 是否处理该文件（按扩展名，不区分大小写）
+    pub fn handles(&self, file_path: &str) -> bool {
+        Path::new(file_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.extensions.iter().any(|known| known.eq_ignore_ascii_case(ext)))
+    }
+
+    pub(crate) fn language(&self) -> &Language {
+        &self.language
+    }
+
+    pub(crate) fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub(crate) fn visibility(&self) -> Option<VisibilityCallback> {
+        self.visibility
+    }
+}
+
+/// 读取描述中的字符串字段
+///
+/// # Safety
+///
+/// `ptr` 须为空指针或指向 NUL 结尾的字符串。
+unsafe fn c_string(ptr: *const c_char, field: &str) -> Result<String, String> {
+    if ptr.is_null() {
+        return Err(format!("{} is null", field));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map(str::to_string)
+        .map_err(|_| format!("{} is not valid UTF-8", field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language_manager::LanguageManager;
+    use crate::types::Visibility;
+
+    /// 以 `_` 开头的函数为私有，其余公开
+    unsafe extern "C" fn visibility(node_kind: *const c_char, text: *const u8, text_len: usize) -> i32 {
+        if CStr::from_ptr(node_kind).to_bytes() != b"function_declaration" {
+            return VISIBILITY_UNKNOWN;
+        }
+        if std::slice::from_raw_parts(text, text_len).starts_with(b"function _") {
+            VISIBILITY_PRIVATE
+        } else {
+            VISIBILITY_PUBLIC
+        }
+    }
+
+    static PACK: SynapseLanguagePack = SynapseLanguagePack {
+        abi_version: PLUGIN_ABI_VERSION,
+        name: c"TsPack".as_ptr(),
+        extensions: c"tsp, .TSPX".as_ptr(),
+        language: tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into_raw(),
+        query: c"(function_declaration) @definition.function (comment) @comment".as_ptr(),
+        visibility: Some(visibility),
+    };
+
+    #[test]
+    fn test_language_pack() {
+        let pack = unsafe { LanguagePack::from_raw(&PACK) }.unwrap();
+        assert_eq!(pack.name(), "TsPack");
+        assert_eq!(pack.extensions(), ["tsp", "tspx"]);
+        assert!(pack.handles("src/a.TSP") && !pack.handles("src/a.ts"));
+
+        let mut manager = LanguageManager::new();
+        manager.register_language_pack(pack).unwrap();
+        let result = manager
+            .parse_file("src/a.tsp", "// helpers\nfunction run() {}\nfunction _hidden() {}\n")
+            .unwrap();
+        assert_eq!(result.language, "TsPack");
+        let functions: Vec<_> = result.entity_info.iter().filter(|info| info.kind == "function").collect();
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[0].name.as_deref(), Some("run"));
+        assert!(functions[0].is_exported && matches!(functions[0].visibility, Some(Visibility::Public)));
+        assert_eq!(functions[1].name.as_deref(), Some("_hidden"));
+        assert!(!functions[1].is_exported && matches!(functions[1].visibility, Some(Visibility::Private)));
+        assert!(result.entity_info.iter().any(|info| info.kind == "comment"));
+
+        // 分叉的管理器继承语言包
+        assert_eq!(manager.fork().parse_file("b.tspx", "function b() {}").unwrap().language, "TsPack");
+        assert_eq!(manager.parse_file("c.ts", "function c() {}").unwrap().language, "TypeScript");
+    }
+
+    #[test]
+    fn test_language_pack_on_shared_manager() {
+        // NAPI 的异步任务在线程池中使用调用方的管理器（Arc 共享）
+        let mut manager = LanguageManager::new();
+        manager.register_language_pack(unsafe { LanguagePack::from_raw(&PACK) }.unwrap()).unwrap();
+        let manager = std::sync::Arc::new(manager);
+
+        let shared = std::sync::Arc::clone(&manager);
+        let (single, batch) = std::thread::spawn(move || {
+            let single = shared.parse_file_shared("a.tsp", "function a() {}").unwrap();
+            let batch = shared
+                .parse_files_batch_shared(vec![
+                    ("b.tspx".to_string(), "function b() {}".to_string()),
+                    ("c.ts".to_string(), "function c() {}".to_string()),
+                    ("d.unknown".to_string(), String::new()),
+                ])
+                .unwrap();
+            (single, batch)
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(single.language, "TsPack");
+        let languages: Vec<_> = batch.iter().map(|result| result.language.as_str()).collect();
+        assert_eq!(languages, ["TsPack", "TypeScript"]);
+    }
+
+    #[test]
+    fn test_invalid_language_pack() {
+        let outdated = SynapseLanguagePack {
+            abi_version: PLUGIN_ABI_VERSION + 1,
+            ..PACK
+        };
+        assert!(unsafe { LanguagePack::from_raw(&outdated) }.unwrap_err().contains("ABI version"));
+        let bad_query = SynapseLanguagePack {
+            query: c"(no_such_node) @definition.function".as_ptr(),
+            ..PACK
+        };
+        assert!(unsafe { LanguagePack::from_raw(&bad_query) }.unwrap_err().starts_with("Invalid query"));
+        assert!(LanguagePack::load("/nonexistent/libpack.so").unwrap_err().starts_with("Failed to load"));
+    }
+}
@@ -0,0 +1,240 @@
+//! 磁盘版本与覆盖（编辑中）版本之间的行号映射
+//!
+//! 索引中的范围按解析时的内容计算。文件在编辑器中有未保存的修改时，用行级 diff
+//! 把磁盘版本的行号换算到编辑器缓冲区（或反向），使检索到的引用能定位到正确的行。
+//! 行号从 1 开始，与 `Range` 一致；未修改的行上列号不变。
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::Range;
+
+/// diff 中间段（去掉相同的首尾行后）允许的最大 LCS 表大小，超过时整段视为替换
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// 一处修改：磁盘版本从 `disk_start` 起的 `disk_len` 行替换为覆盖版本从 `overlay_start` 起的
+/// `overlay_len` 行（下标从 0 开始）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineHunk {
+    pub disk_start: usize,
+    pub disk_len: usize,
+    pub overlay_start: usize,
+    pub overlay_len: usize,
+}
+
+impl LineHunk {
+    /// 修改在一侧的 (起始下标, 行数)
+    fn span(&self, overlay: bool) -> (usize, usize) {
+        if overlay {
+            (self.overlay_start, self.overlay_len)
+        } else {
+            (self.disk_start, self.disk_len)
+        }
+    }
+}
+
+/// 两个版本之间的行号映射
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionMap {
+    hunks: Vec<LineHunk>,
+    disk_lines: usize,
+    overlay_lines: usize,
+}
+
+/// 行在一侧的位置：未修改（映射到另一侧的下标），或落在某处修改内
+enum Located {
+    Line(usize),
+    Changed(LineHunk),
+}
+
+impl PositionMap {
+    /// 比较磁盘内容与覆盖内容
+    pub fn between(disk: &str, overlay: &str) -> Self {
+        let old: Vec<&str> = disk.lines().collect();
+        let new: Vec<&str> = overlay.lines().collect();
+        Self {
+            hunks: diff_lines(&old, &new),
+            disk_lines: old.len(),
+            overlay_lines: new.len(),
+        }
+    }
+
+    /// 两个版本的行完全一致
+    pub fn is_identity(&self) -> bool {
+        self.hunks.is_empty() && self.disk_lines == self.overlay_lines
+    }
+
+    /// 修改列表（按位置排序）
+    pub fn hunks(&self) -> &[LineHunk] {
+        &self.hunks
+    }
+
+    /// 磁盘行号 -> 覆盖行号；该行被修改或删除时返回 None
+    pub fn to_overlay(&self, line: usize) -> Option<usize> {
+        match self.locate(line.checked_sub(1)?, false)? {
+            Located::Line(index) => Some(index + 1),
+            Located::Changed(_) => None,
+        }
+    }
+
+    /// 覆盖行号 -> 磁盘行号；该行是新增或修改的行时返回 None
+    pub fn to_disk(&self, line: usize) -> Option<usize> {
+        match self.locate(line.checked_sub(1)?, true)? {
+            Located::Line(index) => Some(index + 1),
+            Located::Changed(_) => None,
+        }
+    }
+
+    /// 把磁盘版本中的范围换算到覆盖版本；端点落在修改处时收缩 / 扩展到修改的边界，
+    /// 整个范围都被删除时返回 None
+    pub fn range_to_overlay(&self, range: &Range) -> Option<Range> {
+        self.map_range(range, false)
+    }
+
+    /// 把覆盖版本中的范围换算回磁盘版本（规则同 `range_to_overlay`）
+    pub fn range_to_disk(&self, range: &Range) -> Option<Range> {
+        self.map_range(range, true)
+    }
+
+    fn map_range(&self, range: &Range, to_disk: bool) -> Option<Range> {
+        // 落在修改处的端点取修改在目标一侧的首行 / 末行
+        let start = match self.locate(range.start.checked_sub(1)?, to_disk)? {
+            Located::Line(index) => index + 1,
+            Located::Changed(hunk) => hunk.span(!to_disk).0 + 1,
+        };
+        let end = match self.locate(range.end.checked_sub(1)?, to_disk)? {
+            Located::Line(index) => index + 1,
+            Located::Changed(hunk) => {
+                let (start, len) = hunk.span(!to_disk);
+                start + len
+            }
+        };
+        (end >= start).then_some(Range { start, end })
+    }
+
+    /// 定位一侧的行（下标从 0 开始），超出该版本行数时返回 None
+    fn locate(&self, index: usize, from_overlay: bool) -> Option<Located> {
+        let lines = if from_overlay { self.overlay_lines } else { self.disk_lines };
+        if index >= lines {
+            return None;
+        }
+
+        let mut delta = 0isize;
+        for hunk in &self.hunks {
+            let (start, len) = hunk.span(from_overlay);
+            if index < start {
+                break;
+            }
+            if index < start + len {
+                return Some(Located::Changed(*hunk));
+            }
+            let (target_start, target_len) = hunk.span(!from_overlay);
+            delta = (target_start + target_len) as isize - (start + len) as isize;
+        }
+        Some(Located::Line((index as isize + delta) as usize))
+    }
+}
+
+/// 行级 diff：去掉相同的首尾行后对中间段求 LCS
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<LineHunk> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+    if old_mid.is_empty() && new_mid.is_empty() {
+        return Vec::new();
+    }
+
+    let (n, m) = (old_mid.len(), new_mid.len());
+    let matches = if n.saturating_mul(m) <= MAX_DIFF_CELLS {
+        lcs_matches(old_mid, new_mid)
+    } else {
+        Vec::new()
+    };
+
+    // 相邻的匹配行之间即为一处修改
+    let mut hunks = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for (a, b) in matches.into_iter().chain(std::iter::once((n, m))) {
+        if a > i || b > j {
+            hunks.push(LineHunk {
+                disk_start: prefix + i,
+                disk_len: a - i,
+                overlay_start: prefix + j,
+                overlay_len: b - j,
+            });
+        }
+        (i, j) = (a + 1, b + 1);
+    }
+    hunks
+}
+
+/// 最长公共子序列中匹配的行对（按顺序）
+fn lcs_matches(old: &[&str], new: &[&str]) -> Vec<(usize, usize)> {
+    let (n, m) = (old.len(), new.len());
+    // table[i][j] = old[i..] 与 new[j..] 的 LCS 长度
+    let mut table = vec![0u32; (n + 1) * (m + 1)];
+    let at = |i: usize, j: usize| i * (m + 1) + j;
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[at(i, j)] = if old[i] == new[j] {
+                table[at(i + 1, j + 1)] + 1
+            } else {
+                table[at(i + 1, j)].max(table[at(i, j + 1)])
+            };
+        }
+    }
+
+    let mut matches = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            matches.push((i, j));
+            i += 1;
+            j += 1;
+        } else if table[at(i + 1, j)] >= table[at(i, j + 1)] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_lines_through_edits() {
+        let disk = "import a\n\nfunction f() {\n  old()\n}\n\nfunction g() {}\n";
+        let overlay = "import a\nimport b\n\nfunction f() {\n  new()\n  more()\n}\n\nfunction g() {}\n";
+        let map = PositionMap::between(disk, overlay);
+        assert!(!map.is_identity());
+
+        assert_eq!(map.to_overlay(1), Some(1));
+        assert_eq!(map.to_overlay(3), Some(4));
+        assert_eq!(map.to_overlay(4), None);
+        assert_eq!(map.to_overlay(7), Some(9));
+        assert_eq!(map.to_overlay(8), None);
+        assert_eq!(map.to_disk(2), None);
+        assert_eq!(map.to_disk(9), Some(7));
+
+        // 范围端点落在修改处时扩展到修改的边界
+        assert_eq!(map.range_to_overlay(&Range { start: 3, end: 5 }), Some(Range { start: 4, end: 7 }));
+        assert_eq!(map.range_to_overlay(&Range { start: 4, end: 4 }), Some(Range { start: 5, end: 6 }));
+        assert_eq!(map.range_to_disk(&Range { start: 5, end: 6 }), Some(Range { start: 4, end: 4 }));
+        // 整段被删除
+        let map = PositionMap::between("a\nb\nc\n", "a\nc\n");
+        assert_eq!(map.range_to_overlay(&Range { start: 2, end: 2 }), None);
+        assert_eq!(map.range_to_overlay(&Range { start: 1, end: 3 }), Some(Range { start: 1, end: 2 }));
+
+        assert!(PositionMap::between("a\nb\n", "a\nb").is_identity());
+    }
+}
@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::ext_to_lang::guess_language;
+use crate::language_manager::LanguageManager;
+
+/// 标注文件后缀：`foo.ts` 的期望实体写在同目录的 `foo.expected.json`
+const EXPECTED_SUFFIX: &str = ".expected.json";
+
+/// 单个实体类型的得分
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KindScore {
+    /// 标注中的实体数
+    pub expected: usize,
+    /// 实际提取的实体数
+    pub extracted: usize,
+    /// 命中（kind 与 name 都一致）的实体数
+    pub matched: usize,
+    pub precision: f64,
+    pub recall: f64,
+}
+
+/// 单个语言的得分
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageQuality {
+    pub files: usize,
+    pub kinds: BTreeMap<String, KindScore>,
+    /// 所有实体类型合计
+    pub total: KindScore,
+}
+
+/// 语料库质量报告
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityReport {
+    /// 按语言名（如 "TypeScript"）分组
+    pub languages: BTreeMap<String, LanguageQuality>,
+    /// 无法解析或标注无效的文件
+    pub errors: Vec<String>,
+}
+
+/// kind -> (期望数, 提取数, 命中数)
+type KindCounts = BTreeMap<String, (usize, usize, usize)>;
+
+#[derive(Deserialize)]
+struct Annotation {
+    entities: Vec<ExpectedEntity>,
+}
+
+#[derive(Deserialize)]
+struct ExpectedEntity {
+    kind: String,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+/// 解析带标注的语料库，按语言、实体类型统计召回率与精确率
+///
+/// 每个源文件旁放一个 `<文件名去扩展名>.expected.json`，格式与
+/// `cargo xtask gen-fixtures` 生成的快照一致（只读取 `entities[].kind/name`）。
+/// 没有标注的源文件会被忽略。
+pub fn quality_report(corpus_dir: impl AsRef<Path>) -> Result<QualityReport, String> {
+    let corpus_dir = corpus_dir.as_ref();
+    let mut files = Vec::new();
+    collect_files(corpus_dir, &mut files)
+        .map_err(|e| format!("Failed to read corpus {}: {}", corpus_dir.display(), e))?;
+    files.sort();
+
+    let mut manager = LanguageManager::new();
+    let mut report = QualityReport::default();
+
+    for path in files {
+        let path_str = path.to_string_lossy();
+        if path_str.ends_with(EXPECTED_SUFFIX) || guess_language(&path_str).is_none() {
+            continue;
+        }
+        let Some(expected_path) = expected_path(&path) else {
+            continue;
+        };
+        if !expected_path.exists() {
+            continue;
+        }
+
+        match score_file(&mut manager, &path, &expected_path) {
+            Ok((language, counts)) => {
+                let quality = report.languages.entry(language).or_default();
+                quality.files += 1;
+                for (kind, (expected, extracted, matched)) in counts {
+                    let score = quality.kinds.entry(kind).or_default();
+                    score.expected += expected;
+                    score.extracted += extracted;
+                    score.matched += matched;
+                }
+            }
+            Err(e) => report.errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    for quality in report.languages.values_mut() {
+        let mut total = KindScore::default();
+        for score in quality.kinds.values_mut() {
+            finish_score(score);
+            total.expected += score.expected;
+            total.extracted += score.extracted;
+            total.matched += score.matched;
+        }
+        finish_score(&mut total);
+        quality.total = total;
+    }
+
+    Ok(report)
+}
+
+/// 按 kind 统计单个文件，返回语言名
+fn score_file(
+    manager: &mut LanguageManager,
+    path: &Path,
+    expected_path: &Path,
+) -> Result<(String, KindCounts), String> {
+    let source = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let annotation: Annotation = serde_json::from_str(
+        &fs::read_to_string(expected_path).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| format!("invalid annotation: {}", e))?;
+
+    let result = manager.parse_file(&path.to_string_lossy(), &source)?;
+
+    // 未命中的期望实体，按 (kind, name) 计数，支持同名重复
+    let mut pending: BTreeMap<(String, Option<String>), usize> = BTreeMap::new();
+    let mut counts = KindCounts::new();
+    for entity in annotation.entities {
+        counts.entry(entity.kind.clone()).or_default().0 += 1;
+        *pending.entry((entity.kind, entity.name)).or_default() += 1;
+    }
+
+    for info in &result.entity_info {
+        let entry = counts.entry(info.kind.clone()).or_default();
+        entry.1 += 1;
+        if let Some(remaining) = pending.get_mut(&(info.kind.clone(), info.name.clone())) {
+            if *remaining > 0 {
+                *remaining -= 1;
+                entry.2 += 1;
+            }
+        }
+    }
+
+    Ok((result.language, counts))
+}
+
+fn finish_score(score: &mut KindScore) {
+    score.precision = ratio(score.matched, score.extracted);
+    score.recall = ratio(score.matched, score.expected);
+}
+
+/// 分母为 0 时视为满分（没有提取 / 没有期望即没有错误）
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        1.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+fn expected_path(path: &Path) -> Option<PathBuf> {
+    let stem = path.file_stem()?.to_string_lossy();
+    Some(path.with_file_name(format!("{}{}", stem, EXPECTED_SUFFIX)))
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_quality_report_on_fixtures() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        let report = quality_report(&fixtures).unwrap();
+
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        let ts = &report.languages["TypeScript"];
+        assert_eq!(ts.files, 1);
+        assert_eq!(ts.total.precision, 1.0);
+        assert_eq!(ts.total.recall, 1.0);
+    }
+
+    #[test]
+    fn test_quality_report_scores_misses() {
+        let dir = temp_dir("quality", "misses");
+        fs::write(dir.join("a.ts"), "function foo() {}\n").unwrap();
+        fs::write(
+            dir.join("a.expected.json"),
+            r#"{"entities": [
+                {"kind": "function", "name": "foo"},
+                {"kind": "function", "name": "bar"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let report = quality_report(&dir).unwrap();
+
+        let function = &report.languages["TypeScript"].kinds["function"];
+        assert_eq!((function.expected, function.extracted, function.matched), (2, 1, 1));
+        assert_eq!(function.precision, 1.0);
+        assert_eq!(function.recall, 0.5);
+    }
+}
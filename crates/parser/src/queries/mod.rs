@@ -23,9 +23,21 @@ pub const TYPESCRIPT_QUERY: &str = r#"
 (function_declaration
   name: (identifier) @definition.function)
 
+(function_signature
+  name: (identifier) @definition.function)
+
+(ambient_declaration
+  (module
+    name: (_) @definition.module))
+
 (method_definition
   name: (property_identifier) @definition.method)
 
+(lexical_declaration
+  (variable_declarator
+    name: (identifier) @definition.function
+    value: [(arrow_function) (function_expression) (generator_function)]))
+
 (lexical_declaration
   (variable_declarator
     name: (identifier) @definition.variable))
@@ -155,6 +167,8 @@ pub const RUBY_QUERY: &str = r#"
 /// PHP query
 #[cfg(feature = "php")]
 pub const PHP_QUERY: &str = r#"
+(comment) @comment
+
 (namespace_definition) @definition.namespace
 
 (namespace_use_declaration) @definition.use
@@ -162,6 +176,15 @@ pub const PHP_QUERY: &str = r#"
 (class_declaration
   name: (name) @definition.class)
 
+(interface_declaration
+  name: (name) @definition.interface)
+
+(trait_declaration
+  name: (name) @definition.trait)
+
+(enum_declaration
+  name: (name) @definition.enum)
+
 (function_definition
   name: (name) @definition.function)
 
@@ -206,12 +229,47 @@ pub const CPP_QUERY: &str = r#"
     declarator: (qualified_identifier
       name: (identifier) @definition.function)))
 
+(function_definition
+  declarator: (function_declarator
+    declarator: (field_identifier) @definition.method))
+
+(function_definition
+  !type
+  declarator: (function_declarator
+    declarator: [(identifier) (qualified_identifier name: (identifier))])) @definition.constructor
+
+(function_definition
+  declarator: (function_declarator
+    declarator: [(destructor_name) (qualified_identifier name: (destructor_name))])) @definition.destructor
+
+(function_definition
+  declarator: [
+    (function_declarator declarator: [(operator_name) (qualified_identifier name: (operator_name))])
+    (reference_declarator (function_declarator declarator: [(operator_name) (qualified_identifier name: (operator_name))]))
+    (pointer_declarator declarator: (function_declarator declarator: [(operator_name) (qualified_identifier name: (operator_name))]))
+    (operator_cast)
+    (qualified_identifier name: (operator_cast))
+  ]) @definition.operator
+
 (class_specifier
   name: (type_identifier) @definition.class)
 
 (struct_specifier
   name: (type_identifier) @definition.struct)
 
+(class_specifier
+  name: (template_type) @definition.specialization)
+
+(struct_specifier
+  name: (template_type) @definition.specialization)
+
+(function_definition
+  declarator: (function_declarator
+    declarator: (template_function) @definition.specialization))
+
+(concept_definition
+  name: (identifier) @definition.concept)
+
 (enum_specifier
   name: (type_identifier) @definition.enum)
 
@@ -227,24 +285,34 @@ pub const CPP_QUERY: &str = r#"
 pub const SWIFT_QUERY: &str = r#"
 (comment) @comment
 
+(multiline_comment) @comment
+
 (import_declaration) @definition.import
 
 (class_declaration
+  declaration_kind: ["class" "actor"]
   name: (type_identifier) @definition.class)
 
-(struct_declaration
+(class_declaration
+  declaration_kind: "struct"
   name: (type_identifier) @definition.struct)
 
 (protocol_declaration
   name: (type_identifier) @definition.protocol)
 
-(enum_declaration
+(class_declaration
+  declaration_kind: "enum"
   name: (type_identifier) @definition.enum)
 
 (function_declaration
   name: (simple_identifier) @definition.function)
 
-(extension_declaration) @definition.extension
+(class_body
+  (property_declaration
+    name: (pattern) @definition.property))
+
+(class_declaration
+  declaration_kind: "extension") @definition.extension
 "#;
 
 /// Solidity query
@@ -309,6 +377,289 @@ pub const VUE_QUERY: &str = r#"
 (template_element) @definition.template
 "#;
 
+/// SQL query（迁移 / DDL 脚本，捕获整条语句）
+#[cfg(feature = "sql")]
+pub const SQL_QUERY: &str = r#"
+(comment) @comment
+
+(create_schema) @definition.schema
+
+(create_table) @definition.table
+
+(create_view) @definition.view
+
+(create_function) @definition.function
+"#;
+
+/// GraphQL query（schema 类型定义、根类型的字段、具名操作与片段）
+///
+/// Query / Mutation / Subscription 的字段单独捕获，便于与同名的 resolver 函数关联。
+#[cfg(feature = "graphql")]
+pub const GRAPHQL_QUERY: &str = r#"
+(comment) @comment
+
+(schema_definition) @definition.schema
+
+(object_type_definition) @definition.type
+
+(interface_type_definition) @definition.interface
+
+(enum_type_definition) @definition.enum
+
+(input_object_type_definition) @definition.input
+
+(union_type_definition) @definition.union
+
+(scalar_type_definition) @definition.scalar
+
+(object_type_definition
+  (name) @_root
+  (fields_definition
+    (field_definition) @definition.field)
+  (#match? @_root "^(Query|Mutation|Subscription)$"))
+
+(operation_definition
+  (operation_type) @_operation
+  (name)
+  (#eq? @_operation "query")) @definition.query
+
+(operation_definition
+  (operation_type) @_operation
+  (name)
+  (#eq? @_operation "mutation")) @definition.mutation
+
+(operation_definition
+  (operation_type) @_operation
+  (name)
+  (#eq? @_operation "subscription")) @definition.subscription
+
+(fragment_definition) @definition.fragment
+"#;
+
+/// Protobuf query（消息、枚举、服务与 rpc，以及 package 与 import 声明）
+#[cfg(feature = "proto")]
+pub const PROTOBUF_QUERY: &str = r#"
+(comment) @comment
+
+(package) @definition.package
+
+(import) @definition.import
+
+(message) @definition.message
+
+(enum) @definition.enum
+
+(service) @definition.service
+
+(rpc) @definition.rpc
+"#;
+
+/// Elixir query（模块、协议与实现、函数与宏、behaviour 与回调）
+///
+/// 定义都是宏调用，按调用目标区分种类；`defp` / `defmacrop` 等私有定义同样捕获，可见性由策略判断。
+#[cfg(feature = "elixir")]
+pub const ELIXIR_QUERY: &str = r#"
+(comment) @comment
+
+(call
+  target: (identifier) @_macro
+  (#eq? @_macro "defmodule")) @definition.module
+
+(call
+  target: (identifier) @_macro
+  (#eq? @_macro "defprotocol")) @definition.protocol
+
+(call
+  target: (identifier) @_macro
+  (#eq? @_macro "defimpl")) @definition.implementation
+
+(call
+  target: (identifier) @_macro
+  (#match? @_macro "^(def|defp|defdelegate)$")) @definition.function
+
+(call
+  target: (identifier) @_macro
+  (#match? @_macro "^(defmacro|defmacrop|defguard|defguardp)$")) @definition.macro
+
+(unary_operator
+  operator: "@"
+  operand: (call
+    target: (identifier) @_attribute
+    (#eq? @_attribute "behaviour"))) @definition.behaviour
+
+(unary_operator
+  operator: "@"
+  operand: (call
+    target: (identifier) @_attribute
+    (#match? @_attribute "^(callback|macrocallback)$"))) @definition.callback
+"#;
+
+/// Erlang query（模块、behaviour、函数、宏、记录、类型与回调）
+#[cfg(feature = "erlang")]
+pub const ERLANG_QUERY: &str = r#"
+(comment) @comment
+
+(module_attribute) @definition.module
+
+(behaviour_attribute) @definition.behaviour
+
+(fun_decl) @definition.function
+
+(pp_define) @definition.macro
+
+(record_decl) @definition.record
+
+(type_alias) @definition.type
+
+(callback) @definition.callback
+"#;
+
+/// Zig query（函数、赋给常量的 struct / enum / union / error set 与 `@import`）
+#[cfg(feature = "zig")]
+pub const ZIG_QUERY: &str = r#"
+(comment) @comment
+
+(function_declaration
+  name: (identifier) @definition.function)
+
+(variable_declaration
+  (struct_declaration)) @definition.struct
+
+(variable_declaration
+  (enum_declaration)) @definition.enum
+
+(variable_declaration
+  (union_declaration)) @definition.union
+
+(variable_declaration
+  (error_set_declaration)) @definition.type
+
+(variable_declaration
+  (builtin_function
+    (builtin_identifier) @_builtin
+    (#eq? @_builtin "@import"))) @definition.import
+"#;
+
+/// Nim query（proc / func / method / iterator / converter、模板与宏、类型声明与导入）
+#[cfg(feature = "nim")]
+pub const NIM_QUERY: &str = r#"
+[
+  (comment)
+  (block_comment)
+  (documentation_comment)
+  (block_documentation_comment)
+] @comment
+
+(proc_declaration
+  name: (_) @definition.function)
+
+(func_declaration
+  name: (_) @definition.function)
+
+(converter_declaration
+  name: (_) @definition.function)
+
+(method_declaration
+  name: (_) @definition.method)
+
+(iterator_declaration
+  name: (_) @definition.iterator)
+
+(template_declaration
+  name: (_) @definition.template)
+
+(macro_declaration
+  name: (_) @definition.macro)
+
+(type_declaration) @definition.type
+
+(import_statement) @definition.import
+(import_from_statement) @definition.import
+(include_statement) @definition.import
+"#;
+
+/// JSON query（顶层键；`scripts` 与各类依赖对象中的条目单独捕获，如 package.json、composer.json）
+#[cfg(feature = "config-files")]
+pub const JSON_QUERY: &str = r#"
+(document
+  (object
+    (pair) @definition.key))
+
+(document
+  (object
+    (pair
+      key: (string (string_content) @_section)
+      value: (object (pair) @definition.script))
+    (#eq? @_section "scripts")))
+
+(document
+  (object
+    (pair
+      key: (string (string_content) @_section)
+      value: (object (pair) @definition.dependency))
+    (#match? @_section "^(dependencies|devDependencies|peerDependencies|optionalDependencies|require|require-dev)$")))
+"#;
+
+/// YAML query（顶层键；docker-compose 的 `services` 与 GitHub Actions 的 `jobs` 中的条目单独捕获）
+#[cfg(feature = "config-files")]
+pub const YAML_QUERY: &str = r#"
+(comment) @comment
+
+(stream
+  (document
+    (block_node
+      (block_mapping
+        (block_mapping_pair) @definition.key))))
+
+(stream
+  (document
+    (block_node
+      (block_mapping
+        (block_mapping_pair
+          key: (flow_node) @_section
+          value: (block_node
+            (block_mapping
+              (block_mapping_pair) @definition.service)))
+        (#eq? @_section "services")))))
+
+(stream
+  (document
+    (block_node
+      (block_mapping
+        (block_mapping_pair
+          key: (flow_node) @_section
+          value: (block_node
+            (block_mapping
+              (block_mapping_pair) @definition.job)))
+        (#eq? @_section "jobs")))))
+"#;
+
+/// TOML query（顶层键与表；Cargo.toml 等依赖表中的条目单独捕获）
+#[cfg(feature = "config-files")]
+pub const TOML_QUERY: &str = r#"
+(comment) @comment
+
+(document
+  (pair) @definition.key)
+
+(table) @definition.table
+
+(table
+  [(bare_key) (dotted_key)] @_section
+  (pair) @definition.dependency
+  (#match? @_section "dependencies$"))
+"#;
+
+/// Markdown query（带标题的章节与围栏代码块）
+///
+/// tree-sitter-md 按标题级别嵌套 `section`，章节的层级即实体的 parent 链。
+#[cfg(feature = "markdown")]
+pub const MARKDOWN_QUERY: &str = r#"
+(section) @definition.section
+
+(fenced_code_block) @definition.code
+"#;
+
 /// 获取语言对应的 query
 pub fn get_query(lang: SupportedLanguage) -> &'static str {
     match lang {
@@ -339,6 +690,28 @@ pub fn get_query(lang: SupportedLanguage) -> &'static str {
         SupportedLanguage::Css => CSS_QUERY,
         #[cfg(feature = "vue")]
         SupportedLanguage::Vue => VUE_QUERY,
+        #[cfg(feature = "sql")]
+        SupportedLanguage::Sql => SQL_QUERY,
+        #[cfg(feature = "graphql")]
+        SupportedLanguage::GraphQL => GRAPHQL_QUERY,
+        #[cfg(feature = "proto")]
+        SupportedLanguage::Protobuf => PROTOBUF_QUERY,
+        #[cfg(feature = "elixir")]
+        SupportedLanguage::Elixir => ELIXIR_QUERY,
+        #[cfg(feature = "erlang")]
+        SupportedLanguage::Erlang => ERLANG_QUERY,
+        #[cfg(feature = "zig")]
+        SupportedLanguage::Zig => ZIG_QUERY,
+        #[cfg(feature = "nim")]
+        SupportedLanguage::Nim => NIM_QUERY,
+        #[cfg(feature = "config-files")]
+        SupportedLanguage::Json => JSON_QUERY,
+        #[cfg(feature = "config-files")]
+        SupportedLanguage::Yaml => YAML_QUERY,
+        #[cfg(feature = "config-files")]
+        SupportedLanguage::Toml => TOML_QUERY,
+        #[cfg(feature = "markdown")]
+        SupportedLanguage::Markdown => MARKDOWN_QUERY,
         #[allow(unreachable_patterns)]
         _ => TYPESCRIPT_QUERY, // Fallback
     }
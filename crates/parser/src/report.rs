@@ -0,0 +1,311 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::diagram::{render_diagram, DiagramFormat, EdgeKind, Subgraph};
+use crate::index::{CodeIndex, IndexedEntity};
+
+/// 报告中每张排行表的行数
+const TOP_N: usize = 20;
+
+/// 嵌入报告、供搜索脚本使用的符号
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SymbolRow<'a> {
+    name: &'a str,
+    kind: &'a str,
+    file: &'a str,
+    line: usize,
+    exported: bool,
+}
+
+/// 从索引生成静态 HTML 报告（无需服务端，可直接分享）
+///
+/// 包含：按语言的统计表、文件树、符号搜索、文件间调用依赖（表格 + Mermaid 源码），
+/// 以及调用最多 / 最大的实体排行。
+pub fn render_html_report(index: &CodeIndex, title: &str) -> String {
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n",
+        title = escape(title),
+    );
+
+    html.push_str(&metrics_section(index));
+    html.push_str(&tree_section(index));
+    html.push_str(&search_section(index));
+    html.push_str(&dependency_section(index));
+    html.push_str(&ranking_section(index));
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+impl CodeIndex {
+    /// 导出 HTML 报告
+    pub fn export_html_report(&self, path: impl AsRef<Path>, title: &str) -> Result<(), String> {
+        let path = path.as_ref();
+        fs::write(path, render_html_report(self, title))
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+fn metrics_section(index: &CodeIndex) -> String {
+    // 语言 -> (文件数, 实体数, 导出实体数, 总行数)
+    let mut by_language: BTreeMap<&str, (usize, usize, usize, usize)> = BTreeMap::new();
+    for file in index.files() {
+        let row = by_language.entry(&file.language).or_default();
+        row.0 += 1;
+        for entity in &file.entities {
+            row.1 += 1;
+            row.2 += usize::from(entity.rank_hints.is_exported);
+            row.3 += entity.rank_hints.loc;
+        }
+    }
+
+    let mut out = String::from(
+        "<section>\n<h2>Metrics</h2>\n<table>\n<tr><th>Language</th><th>Files</th><th>Entities</th><th>Exported</th><th>Entity lines</th></tr>\n",
+    );
+    for (language, (files, entities, exported, loc)) in &by_language {
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(language),
+            files,
+            entities,
+            exported,
+            loc
+        );
+    }
+    let _ = writeln!(
+        out,
+        "<tr class=\"total\"><td>Total</td><td>{}</td><td>{}</td><td></td><td></td></tr>",
+        index.file_count(),
+        index.entity_count()
+    );
+    out.push_str("</table>\n</section>\n");
+    out
+}
+
+/// 目录节点：子目录 + 文件（路径, 实体数）
+#[derive(Default)]
+struct TreeNode<'a> {
+    dirs: BTreeMap<&'a str, TreeNode<'a>>,
+    files: Vec<(&'a str, &'a str, usize)>,
+}
+
+fn tree_section(index: &CodeIndex) -> String {
+    let mut root = TreeNode::default();
+    for file in index.files() {
+        let mut parts: Vec<&str> = file.path.split(['/', '\\']).filter(|p| !p.is_empty()).collect();
+        let Some(name) = parts.pop() else {
+            continue;
+        };
+        let mut node = &mut root;
+        for dir in parts {
+            node = node.dirs.entry(dir).or_default();
+        }
+        node.files.push((name, &file.path, file.entities.len()));
+    }
+
+    let mut out = String::from("<section>\n<h2>Files</h2>\n<div class=\"tree\">\n");
+    write_tree(&root, &mut out);
+    out.push_str("</div>\n</section>\n");
+    out
+}
+
+fn write_tree(node: &TreeNode, out: &mut String) {
+    out.push_str("<ul>\n");
+    for (dir, child) in &node.dirs {
+        let _ = writeln!(out, "<li><details open><summary>{}/</summary>", escape(dir));
+        write_tree(child, out);
+        out.push_str("</details></li>\n");
+    }
+    for (name, path, entities) in &node.files {
+        let _ = writeln!(
+            out,
+            "<li title=\"{}\">{} <span class=\"muted\">({} entities)</span></li>",
+            escape(path),
+            escape(name),
+            entities
+        );
+    }
+    out.push_str("</ul>\n");
+}
+
+fn search_section(index: &CodeIndex) -> String {
+    let symbols: Vec<SymbolRow> = index
+        .entities()
+        .filter_map(|e| {
+            Some(SymbolRow {
+                name: e.name.as_deref()?,
+                kind: &e.kind,
+                file: &e.file_path,
+                line: e.range.start,
+                exported: e.rank_hints.is_exported,
+            })
+        })
+        .collect();
+    // `</script>` 不能出现在内联脚本中
+    let data = serde_json::to_string(&symbols)
+        .unwrap_or_else(|_| "[]".to_string())
+        .replace("</", "<\\/");
+
+    format!(
+        "<section>\n<h2>Symbols</h2>\n<input id=\"search\" type=\"search\" placeholder=\"Search symbols...\">\n\
+         <table id=\"symbols\"><thead><tr><th>Name</th><th>Kind</th><th>Location</th><th>Exported</th></tr></thead><tbody></tbody></table>\n\
+         <script>const SYMBOLS = {data};\n{SEARCH_SCRIPT}</script>\n</section>\n"
+    )
+}
+
+fn dependency_section(index: &CodeIndex) -> String {
+    let owners: HashMap<&str, &str> = index
+        .entities()
+        .map(|e| (e.id.as_str(), e.file_path.as_str()))
+        .collect();
+
+    // 文件 -> 被调用文件 -> 次数（忽略文件内部调用）
+    let mut deps: BTreeMap<&str, BTreeMap<&str, usize>> = BTreeMap::new();
+    for (from, to, count) in index.call_edges() {
+        if let Some(&target) = owners.get(to) {
+            if target != from {
+                *deps.entry(from).or_default().entry(target).or_default() += count;
+            }
+        }
+    }
+
+    let mut out = String::from("<section>\n<h2>Dependencies</h2>\n");
+    if deps.is_empty() {
+        out.push_str("<p class=\"muted\">No cross-file calls found.</p>\n</section>\n");
+        return out;
+    }
+
+    out.push_str("<table>\n<tr><th>File</th><th>Calls into</th></tr>\n");
+    let mut graph = Subgraph::new();
+    for (from, targets) in &deps {
+        let list: Vec<String> = targets
+            .iter()
+            .map(|(to, count)| format!("{} ({})", escape(to), count))
+            .collect();
+        let _ = writeln!(out, "<tr><td>{}</td><td>{}</td></tr>", escape(from), list.join("<br>"));
+
+        graph.add_node(*from, *from, "file");
+        for to in targets.keys() {
+            graph.add_node(*to, *to, "file");
+            graph.add_edge(*from, *to, EdgeKind::Calls);
+        }
+    }
+    out.push_str("</table>\n");
+    let _ = write!(
+        out,
+        "<details><summary>Mermaid source</summary>\n<pre>{}</pre>\n</details>\n</section>\n",
+        escape(&render_diagram(&graph, DiagramFormat::Mermaid))
+    );
+    out
+}
+
+fn ranking_section(index: &CodeIndex) -> String {
+    let mut named: Vec<_> = index.entities().filter(|e| e.name.is_some()).collect();
+    let mut out = String::from("<section>\n<h2>Most called</h2>\n");
+
+    named.sort_by(|a, b| b.rank_hints.caller_count.cmp(&a.rank_hints.caller_count).then(a.id.cmp(&b.id)));
+    write_ranking(&mut out, named.iter().filter(|e| e.rank_hints.caller_count > 0), "Callers", |e| {
+        e.rank_hints.caller_count
+    });
+
+    out.push_str("<h2>Largest</h2>\n");
+    named.sort_by(|a, b| b.rank_hints.loc.cmp(&a.rank_hints.loc).then(a.id.cmp(&b.id)));
+    write_ranking(&mut out, named.iter(), "Lines", |e| e.rank_hints.loc);
+
+    out.push_str("</section>\n");
+    out
+}
+
+fn write_ranking<'a>(
+    out: &mut String,
+    entities: impl Iterator<Item = &'a &'a IndexedEntity>,
+    metric: &str,
+    value: impl Fn(&IndexedEntity) -> usize,
+) {
+    let _ = writeln!(out, "<table>\n<tr><th>Entity</th><th>Location</th><th>{}</th></tr>", metric);
+    for entity in entities.take(TOP_N) {
+        let _ = writeln!(
+            out,
+            "<tr><td>{} <span class=\"muted\">{}</span></td><td>{}:{}</td><td>{}</td></tr>",
+            escape(entity.name.as_deref().unwrap_or("")),
+            escape(&entity.kind),
+            escape(&entity.file_path),
+            entity.range.start,
+            value(entity)
+        );
+    }
+    out.push_str("</table>\n");
+}
+
+/// HTML 转义
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#222}\
+table{border-collapse:collapse;margin:.5rem 0 1.5rem}\
+th,td{border:1px solid #ddd;padding:.25rem .6rem;text-align:left;vertical-align:top}\
+th{background:#f5f5f5}tr.total td{font-weight:bold}\
+.muted{color:#888;font-size:.9em}.tree ul{list-style:none;padding-left:1.2rem}\
+#search{padding:.4rem;width:20rem}pre{background:#f8f8f8;padding:.75rem;overflow:auto}";
+
+const SEARCH_SCRIPT: &str = r#"(function () {
+  const input = document.getElementById('search');
+  const body = document.querySelector('#symbols tbody');
+  function render(query) {
+    const q = query.trim().toLowerCase();
+    const rows = SYMBOLS.filter((s) => !q || s.name.toLowerCase().includes(q)).slice(0, 200);
+    body.replaceChildren(...rows.map((s) => {
+      const tr = document.createElement('tr');
+      for (const text of [s.name, s.kind, s.file + ':' + s.line, s.exported ? 'yes' : '']) {
+        const td = document.createElement('td');
+        td.textContent = text;
+        tr.appendChild(td);
+      }
+      return tr;
+    }));
+  }
+  input.addEventListener('input', () => render(input.value));
+  render('');
+})();
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LanguageManager;
+
+    #[test]
+    fn test_render_html_report() {
+        let mut manager = LanguageManager::new();
+        let mut index = CodeIndex::new();
+        index.insert(manager.parse_file("src/util/math.ts", "export function add() {}\n").unwrap());
+        index.insert(manager.parse_file("src/main.ts", "class Main</script> {}\n").unwrap());
+
+        let html = render_html_report(&index, "Repo <analysis>");
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Repo &lt;analysis&gt;</title>"));
+        assert!(html.contains("<summary>util/</summary>"));
+        assert!(html.contains("\"name\":\"add\""));
+        // 内联脚本中的 `</` 已被转义
+        assert_eq!(html.matches("</script>").count(), 1);
+    }
+}
@@ -0,0 +1,725 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::dir_context::{context_file_kind, DirectoryContext, DirectoryContexts};
+use crate::encoding::{decode_source, looks_binary};
+use crate::ext_to_lang::guess_language;
+use crate::file_kind::{classify_file, AssetFile, FileKind};
+#[cfg(feature = "index-store")]
+use crate::index_store::{store_fingerprint, IndexStore};
+use crate::language_manager::LanguageManager;
+use crate::overlay::Overlay;
+use crate::paths::normalize_path;
+#[cfg(feature = "index-store")]
+use crate::summary_cache::content_hash;
+use crate::throttle::ThrottleOptions;
+use crate::types::{ParseOptions, ParseResult};
+use crate::walker::{walk_project_filtered, WalkOptions, WalkResult, WalkStats, WalkedFile};
+
+/// 项目扫描选项
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScanOptions {
+    /// 目录遍历选项（深度、符号链接）
+    #[serde(flatten)]
+    pub walk: WalkOptions,
+    /// 只解析匹配这些 glob 的文件（相对根目录，为空时不限制）
+    pub include: Vec<String>,
+    /// 排除匹配这些 glob 的文件和目录
+    pub exclude: Vec<String>,
+    /// 遵循各级目录中的 `.gitignore`
+    pub respect_gitignore: bool,
+    /// 超过该大小（字节）的文件不解析
+    pub max_file_size: Option<u64>,
+    /// 为未解析的文件（图片、二进制、文档等）生成轻量节点
+    pub include_assets: bool,
+    /// 把各目录 README / OWNERS 的摘要附加到该目录（及子目录）中的实体
+    pub directory_context: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            walk: WalkOptions::default(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            respect_gitignore: true,
+            max_file_size: Some(2 * 1024 * 1024),
+            include_assets: true,
+            directory_context: true,
+        }
+    }
+}
+
+/// 扫描统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanStats {
+    /// 目录遍历统计（`filtered` 为被 `.gitignore` / exclude 排除的条目）
+    pub walk: WalkStats,
+    /// 未被 include 匹配的文件
+    pub not_included: usize,
+    /// 无法识别语言的文件
+    pub unsupported: usize,
+    /// 超过 `max_file_size` 的文件
+    pub too_large: usize,
+    /// 成功解析的文件数
+    pub parsed: usize,
+    /// 其中从索引存储复用（内容未变、未重新解析）的文件数
+    pub cached: usize,
+    /// 读取或解析失败的文件
+    pub failed: Vec<String>,
+    /// 语言 -> 解析成功的文件数
+    pub languages: BTreeMap<String, usize>,
+    pub entities: usize,
+    /// 解析的源码总字节数
+    pub bytes: u64,
+    pub elapsed_ms: u64,
+}
+
+/// 扫描结果（按相对路径排序）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanResult {
+    pub results: Vec<ParseResult>,
+    /// 未解析的文件（`include_assets` 关闭时为空）
+    pub assets: Vec<AssetFile>,
+    /// 带有 README / OWNERS 的目录（`directory_context` 关闭时为空）
+    pub contexts: Vec<DirectoryContext>,
+    pub stats: ScanStats,
+}
+
+/// 项目扫描器：遍历目录、过滤、识别语言并批量解析
+///
+/// 文件在 Rust 侧读取，调用方只需传入根目录，不必在 JS 中枚举和读取文件。
+#[derive(Debug, Clone, Default)]
+pub struct ProjectScanner {
+    options: ScanOptions,
+    parse_options: ParseOptions,
+    throttle: ThrottleOptions,
+    overlay: Overlay,
+    #[cfg(feature = "index-store")]
+    store: Option<IndexStore>,
+}
+
+impl ProjectScanner {
+    /// 创建扫描器
+    pub fn new(options: ScanOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    /// 设置解析选项（未指定 `project_root` 时使用扫描根目录）
+    pub fn with_parse_options(mut self, options: ParseOptions) -> Self {
+        self.parse_options = options;
+        self
+    }
+
+    /// 设置 CPU / IO 节流
+    pub fn with_throttle(mut self, throttle: ThrottleOptions) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// 设置未保存的编辑器内容：覆盖同名磁盘文件，根目录下尚未保存的新文件也参与扫描
+    pub fn with_overlay(mut self, overlay: Overlay) -> Self {
+        self.overlay = overlay;
+        self
+    }
+
+    /// 设置持久化存储：内容未变的文件复用上次的解析结果，已删除的文件从存储中清理
+    #[cfg(feature = "index-store")]
+    pub fn with_store(mut self, store: IndexStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+    
+    /// 扫描选项
+    pub fn options(&self) -> &ScanOptions {
+        &self.options
+    }
+
+    /// 遍历根目录，跳过 `.git`、exclude 与 `.gitignore` 排除的条目
+    pub(crate) fn walk(
+        &self,
+        root: &Path,
+        exclude: Option<&GlobSet>,
+        gitignores: &mut GitignoreStack,
+    ) -> Result<WalkResult, String> {
+        walk_project_filtered(root, &self.options.walk, |relative, is_dir| {
+            if is_dir && relative.rsplit('/').next() == Some(".git") {
+                return false;
+            }
+            if exclude.is_some_and(|set| set.is_match(relative)) {
+                return false;
+            }
+            !(self.options.respect_gitignore && gitignores.is_ignored(relative, is_dir))
+        })
+    }
+    
+    /// 节流选项
+    #[cfg(feature = "parallel")]
+    pub(crate) fn throttle(&self) -> &ThrottleOptions {
+        &self.throttle
+    }
+    
+    /// 扫描并解析根目录下的全部源码文件
+    pub fn scan(&self, root: impl AsRef<Path>) -> Result<ScanResult, String> {
+        let started = Instant::now();
+        let root = root.as_ref();
+        let include = build_globs(&self.options.include)?;
+        let exclude = build_globs(&self.options.exclude)?;
+        let mut gitignores = GitignoreStack::new(root);
+        let walked = self.walk(root, exclude.as_ref(), &mut gitignores)?;
+
+        let mut parse_options = self.parse_options.clone();
+        if parse_options.project_root.is_none() {
+            parse_options.project_root = Some(root.to_string_lossy().into_owned());
+        }
+        #[cfg(feature = "index-store")]
+        let fingerprint = store_fingerprint(&parse_options);
+        let manager = LanguageManager::with_options(parse_options);
+
+        let mut stats = ScanStats {
+            walk: walked.stats,
+            ..Default::default()
+        };
+        let mut files = Vec::new();
+        let mut relative_paths = Vec::new();
+        let mut contexts = DirectoryContexts::default();
+        let mut assets = Vec::new();
+        let mut walked_paths = HashSet::new();
+        let mut encoding_notes = HashMap::new();
+        let mut add_asset = |file: &WalkedFile, kind: Option<FileKind>| {
+            if self.options.include_assets {
+                assets.push(AssetFile {
+                    path: manager.display_path(&file.path.to_string_lossy()),
+                    size: file.size,
+                    kind: kind.unwrap_or_else(|| classify_file(&file.path)),
+                });
+            }
+        };
+        for file in walked.files {
+            if self.options.directory_context {
+                let name = file.relative_path.rsplit('/').next().unwrap_or(&file.relative_path);
+                if let Some(kind) = context_file_kind(name) {
+                    contexts.add_file(&file.relative_path, &file.path, kind);
+                }
+            }
+            if include.as_ref().is_some_and(|set| !set.is_match(&file.relative_path)) {
+                stats.not_included += 1;
+                continue;
+            }
+            if guess_language(&file.relative_path).is_none() {
+                stats.unsupported += 1;
+                add_asset(&file, None);
+                continue;
+            }
+            let path = file.path.to_string_lossy().into_owned();
+            let overlaid = self.overlay.get(&path);
+            walked_paths.insert(normalize_path(&path));
+            let size = overlaid.map_or(file.size, |content| content.len() as u64);
+            if self.options.max_file_size.is_some_and(|max| size > max) {
+                stats.too_large += 1;
+                add_asset(&file, Some(FileKind::Source));
+                continue;
+            }
+            let content = match overlaid {
+                Some(content) => Ok(Some((content.to_string(), None))),
+                // 扩展名像源码、内容却是二进制（如编译产物）时为 None
+                None => fs::read(&file.path).map(|bytes| {
+                    (!looks_binary(&bytes)).then(|| {
+                        let decoded = decode_source(&bytes);
+                        let note = decoded.note();
+                        (decoded.text, note)
+                    })
+                }),
+            };
+            match content {
+                // 按内容才能确定语言的文件（如 Objective-C 的 `.h`）
+                Ok(Some((content, _))) if manager.detect_language(&path, &content).is_none() => {
+                    stats.unsupported += 1;
+                    add_asset(&file, None);
+                }
+                Ok(Some((content, note))) => {
+                    // 非 UTF-8 文件的解码说明，解析后附加到结果
+                    if let Some(note) = note {
+                        encoding_notes.insert(manager.display_path(&path), note);
+                    }
+                    files.push((file.path.to_string_lossy().into_owned(), content));
+                    relative_paths.push(file.relative_path);
+                }
+                Ok(None) => {
+                    stats.unsupported += 1;
+                    add_asset(&file, Some(FileKind::Binary));
+                }
+                Err(e) => stats.failed.push(format!("{}: {}", file.relative_path, e)),
+            }
+        }
+        
+        // 只存在于覆盖层的新文件（编辑器中尚未保存），同样经过过滤；
+        // 遍历时目录级的排除会跳过整个目录，这里逐级检查上层目录
+        for (relative, path) in self.overlay.files_under(&root.to_string_lossy()) {
+            let dirs = relative.match_indices('/').map(|(i, _)| &relative[..i]);
+            let mut excluded = |path: &str, is_dir: bool| {
+                (is_dir && path.rsplit('/').next() == Some(".git"))
+                    || exclude.as_ref().is_some_and(|set| set.is_match(path))
+                    || (self.options.respect_gitignore && gitignores.is_ignored(path, is_dir))
+            };
+            if walked_paths.contains(path)
+                || dirs.clone().any(|dir| excluded(dir, true))
+                || excluded(relative, false)
+                || include.as_ref().is_some_and(|set| !set.is_match(relative))
+                || guess_language(relative).is_none()
+            {
+                continue;
+            }
+            let content = self.overlay.get(path).unwrap_or_default();
+            if manager.detect_language(path, content).is_none() {
+                continue;
+            }
+            if self.options.max_file_size.is_some_and(|max| content.len() as u64 > max) {
+                stats.too_large += 1;
+                continue;
+            }
+            files.push((root.join(relative).to_string_lossy().into_owned(), content.to_string()));
+            relative_paths.push(relative.to_string());
+        }
+
+        let expected: Vec<String> = files.iter().map(|(path, _)| manager.display_path(path)).collect();
+        stats.bytes = files.iter().map(|(_, content)| content.len() as u64).sum();
+        #[cfg(feature = "index-store")]
+        let mut results = match &self.store {
+            Some(store) => {
+                store.sync_fingerprint(&fingerprint)?;
+                parse_incremental(store, manager, files, &expected, root, &self.throttle, &mut stats)?
+            }
+            None => parse_all(manager, files, &self.throttle)?,
+        };
+        #[cfg(not(feature = "index-store"))]
+        let mut results = parse_all(manager, files, &self.throttle)?;
+
+        // 批量解析跳过失败的文件，按输出路径找回
+        let parsed: HashSet<&str> = results.iter().map(|r| r.file_path.as_str()).collect();
+        stats
+            .failed
+            .extend(expected.iter().filter(|path| !parsed.contains(path.as_str())).cloned());
+        for result in &results {
+            *stats.languages.entry(result.language.clone()).or_default() += 1;
+            stats.entities += result.entities.len();
+        }
+        stats.parsed = results.len();
+
+        // 输出路径可能被改写（相对 / 匿名化），按解析前的顺序找回相对路径
+        let relative: HashMap<&str, &str> = expected
+            .iter()
+            .map(String::as_str)
+            .zip(relative_paths.iter().map(String::as_str))
+            .collect();
+        for result in &mut results {
+            if let Some(note) = encoding_notes.remove(&result.file_path) {
+                result.errors.push(note);
+            }
+            let context = relative
+                .get(result.file_path.as_str())
+                .and_then(|path| contexts.context_for(path));
+            if let Some(context) = context {
+                for info in &mut result.entity_info {
+                    info.context = Some(context.clone());
+                }
+            }
+        }
+        stats.elapsed_ms = started.elapsed().as_millis() as u64;
+
+        Ok(ScanResult {
+            results,
+            assets,
+            contexts: contexts.into_vec(),
+            stats,
+        })
+    }
+}
+
+/// 只解析内容有变化的文件，其余复用存储中的结果（保持输入顺序）
+#[cfg(feature = "index-store")]
+fn parse_incremental(
+    store: &IndexStore,
+    manager: LanguageManager,
+    files: Vec<(String, String)>,
+    expected: &[String],
+    root: &Path,
+    throttle: &ThrottleOptions,
+    stats: &mut ScanStats,
+) -> Result<Vec<ParseResult>, String> {
+    // 根目录下不再参与扫描的文件（已删除、被排除）
+    let live: HashSet<&str> = files.iter().map(|(path, _)| path.as_str()).collect();
+    store.prune(&root.join("").to_string_lossy(), &live)?;
+
+    let mut cached = Vec::with_capacity(files.len());
+    let mut pending = Vec::new();
+    let mut pending_keys = Vec::new();
+    for ((path, content), display) in files.into_iter().zip(expected) {
+        let result = store.get(&path, &content)?;
+        if result.is_none() {
+            pending_keys.push((display.as_str(), path.clone(), content_hash(&content)));
+            pending.push((path, content));
+        }
+        cached.push(result);
+    }
+    stats.cached = cached.iter().flatten().count();
+
+    let mut parsed: HashMap<String, ParseResult> = parse_all(manager, pending, throttle)?
+        .into_iter()
+        .map(|result| (result.file_path.clone(), result))
+        .collect();
+    // 解析失败的文件不写入，下次扫描重试
+    for (display, path, hash) in pending_keys {
+        if let Some(result) = parsed.get(display) {
+            store.put_hashed(&path, hash, result)?;
+        }
+    }
+    store.flush()?;
+
+    Ok(cached
+        .into_iter()
+        .zip(expected)
+        .filter_map(|(result, display)| result.or_else(|| parsed.remove(display)))
+        .collect())
+}
+
+#[cfg(feature = "parallel")]
+fn parse_all(
+    manager: LanguageManager,
+    files: Vec<(String, String)>,
+    throttle: &ThrottleOptions,
+) -> Result<Vec<ParseResult>, String> {
+    manager.parse_files_batch_parallel_with(files, throttle)
+}
+
+#[cfg(not(feature = "parallel"))]
+fn parse_all(
+    mut manager: LanguageManager,
+    files: Vec<(String, String)>,
+    _throttle: &ThrottleOptions,
+) -> Result<Vec<ParseResult>, String> {
+    manager.parse_files_batch(files)
+}
+
+pub(crate) fn build_globs(patterns: &[String]) -> Result<Option<GlobSet>, String> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).map_err(|e| format!("Invalid glob {}: {}", pattern, e))?);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| format!("Failed to build globs: {}", e))
+}
+
+/// 按目录懒加载的 `.gitignore`，子目录的规则优先于父目录
+pub(crate) struct GitignoreStack<'a> {
+    root: &'a Path,
+    /// 相对目录 -> 该目录下的 .gitignore（不存在时为 None）
+    cache: HashMap<String, Option<Gitignore>>,
+}
+
+impl<'a> GitignoreStack<'a> {
+    pub(crate) fn new(root: &'a Path) -> Self {
+        Self {
+            root,
+            cache: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn is_ignored(&mut self, relative: &str, is_dir: bool) -> bool {
+        let path = self.root.join(relative);
+        let mut dir = relative;
+        loop {
+            dir = dir.rfind('/').map_or("", |i| &dir[..i]);
+            let matched = self.load(dir).map(|gitignore| gitignore.matched(&path, is_dir));
+            match matched {
+                Some(m) if m.is_ignore() => return true,
+                Some(m) if m.is_whitelist() => return false,
+                _ if dir.is_empty() => return false,
+                _ => {}
+            }
+        }
+    }
+
+    fn load(&mut self, dir: &str) -> Option<&Gitignore> {
+        let root = self.root;
+        self.cache
+            .entry(dir.to_string())
+            .or_insert_with(|| {
+                let dir = root.join(dir);
+                let file = dir.join(".gitignore");
+                if !file.is_file() {
+                    return None;
+                }
+                let mut builder = GitignoreBuilder::new(&dir);
+                // 部分无效的行被忽略，其余规则照常生效
+                let _ = builder.add(file);
+                builder.build().ok()
+            })
+            .as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PathMode;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_scan_respects_gitignore_and_globs() {
+        let root = temp_dir("scanner", "filters");
+        fs::create_dir_all(root.join("src/gen")).unwrap();
+        fs::create_dir_all(root.join("node_modules/dep")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(root.join(".gitignore"), "node_modules/\n*.log\n").unwrap();
+        fs::write(root.join("src/.gitignore"), "gen/\n").unwrap();
+        fs::write(root.join("src/main.ts"), "export function main() {}\n").unwrap();
+        fs::write(root.join("src/util.ts"), "class Util {}\n").unwrap();
+        fs::write(root.join("src/gen/out.ts"), "function gen() {}\n").unwrap();
+        fs::write(root.join("node_modules/dep/index.js"), "function dep() {}\n").unwrap();
+        fs::write(root.join("docs/readme.txt"), "docs\n").unwrap();
+        fs::write(root.join("docs/example.ts"), "function example() {}\n").unwrap();
+        fs::write(root.join("debug.log"), "").unwrap();
+
+        let options = ScanOptions {
+            exclude: vec!["docs/*.ts".to_string()],
+            ..Default::default()
+        };
+        let scanner = ProjectScanner::new(options).with_parse_options(ParseOptions {
+            path_mode: PathMode::Relative,
+            ..Default::default()
+        });
+        let result = scanner.scan(&root).unwrap();
+
+        let paths: Vec<&str> = result.results.iter().map(|r| r.file_path.as_str()).collect();
+        assert_eq!(paths, vec!["src/main.ts", "src/util.ts"]);
+        assert_eq!(result.stats.parsed, 2);
+        assert_eq!(result.stats.unsupported, 3); // 两个 .gitignore 与 readme.txt
+        assert_eq!(result.stats.walk.filtered, 4); // node_modules、debug.log、src/gen、docs/example.ts
+        assert_eq!(result.stats.languages.get("TypeScript"), Some(&2));
+        assert!(result.stats.entities >= 2);
+
+        // include 只保留匹配的文件
+        let options = ScanOptions {
+            include: vec!["**/main.ts".to_string()],
+            ..Default::default()
+        };
+        let result = ProjectScanner::new(options).scan(&root).unwrap();
+        assert_eq!(result.stats.parsed, 1);
+        assert!(result.results[0].file_path.ends_with("src/main.ts"));
+    }
+
+    #[test]
+    fn test_scan_uses_overlay() {
+        let root = temp_dir("scanner", "overlay");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("node_modules/dep")).unwrap();
+        fs::write(root.join(".gitignore"), "node_modules/\n").unwrap();
+        fs::write(root.join("src/main.ts"), "export function main() {}\n").unwrap();
+
+        let path = |relative: &str| root.join(relative).to_string_lossy().into_owned();
+        let mut overlay = Overlay::new();
+        overlay.set(&path("src/main.ts"), "export function main() {}\nexport function unsaved() {}\n");
+        overlay.set(&path("src/new.ts"), "export class Draft {}\n");
+        overlay.set(&path("node_modules/dep/index.ts"), "export function dep() {}\n");
+        overlay.set(&path("README.txt"), "readme\n");
+
+        let scanner = ProjectScanner::new(ScanOptions::default())
+            .with_parse_options(ParseOptions {
+                path_mode: PathMode::Relative,
+                ..Default::default()
+            })
+            .with_overlay(overlay);
+        let result = scanner.scan(&root).unwrap();
+
+        let paths: Vec<&str> = result.results.iter().map(|r| r.file_path.as_str()).collect();
+        assert_eq!(paths, vec!["src/main.ts", "src/new.ts"]);
+        let names: Vec<_> = result.results[0].entity_info.iter().filter_map(|e| e.name.as_deref()).collect();
+        assert_eq!(names, vec!["main", "unsaved"]);
+        assert_eq!(result.results[1].entity_info[0].name.as_deref(), Some("Draft"));
+    }
+
+    #[test]
+    fn test_scan_limits_file_size() {
+        let root = temp_dir("scanner", "size");
+        fs::write(root.join("small.py"), "def f():\n    pass\n").unwrap();
+        fs::write(root.join("big.ts"), "const x = 1;\n".repeat(100)).unwrap();
+
+        let options = ScanOptions {
+            max_file_size: Some(64),
+            respect_gitignore: false,
+            ..Default::default()
+        };
+        let result = ProjectScanner::new(options).scan(&root).unwrap();
+        assert_eq!(result.stats.too_large, 1);
+        assert_eq!(result.stats.parsed, usize::from(cfg!(feature = "python")));
+    }
+
+    #[test]
+    fn test_scan_decodes_non_utf8_sources() {
+        let root = temp_dir("scanner", "encoding");
+        // Windows-1252 编码的注释
+        fs::write(root.join("legacy.ts"), b"// caf\xe9\nexport function brew() {}\n").unwrap();
+
+        let options = ScanOptions {
+            respect_gitignore: false,
+            ..Default::default()
+        };
+        let result = ProjectScanner::new(options).scan(&root).unwrap();
+        assert_eq!(result.stats.parsed, 1);
+        let parsed = &result.results[0];
+        assert!(parsed.entities.iter().any(|e| e.contains("café")));
+        assert_eq!(parsed.errors.len(), 1);
+        assert!(parsed.errors[0].message.contains("windows-1252"));
+    }
+
+    #[test]
+    fn test_scan_classifies_assets() {
+        let root = temp_dir("scanner", "assets");
+        fs::create_dir_all(root.join("img")).unwrap();
+        fs::write(root.join("img/logo.png"), [0x89, b'P', b'N', b'G']).unwrap();
+        fs::write(root.join("tool"), b"\x7fELF\x02\x01\x00\x00").unwrap();
+        fs::write(root.join("NOTES"), "plain text\n").unwrap();
+        fs::write(root.join("bundle.js"), [0x00, 0x61, 0xff, 0xfe]).unwrap();
+        fs::write(root.join("app.ts"), "export const a = 1;\n").unwrap();
+
+        let options = ScanOptions {
+            respect_gitignore: false,
+            ..Default::default()
+        };
+        let scanner = ProjectScanner::new(options).with_parse_options(ParseOptions {
+            path_mode: PathMode::Relative,
+            ..Default::default()
+        });
+        let result = scanner.scan(&root).unwrap();
+        assert_eq!(result.stats.parsed, 1);
+
+        let assets: Vec<(&str, FileKind, u64)> =
+            result.assets.iter().map(|a| (a.path.as_str(), a.kind, a.size)).collect();
+        assert_eq!(
+            assets,
+            vec![
+                ("NOTES", FileKind::Text, 11),
+                ("bundle.js", FileKind::Binary, 4),
+                ("img/logo.png", FileKind::Image, 4),
+                ("tool", FileKind::Binary, 8),
+            ]
+        );
+
+        let options = ScanOptions {
+            include_assets: false,
+            ..Default::default()
+        };
+        assert!(ProjectScanner::new(options).scan(&root).unwrap().assets.is_empty());
+    }
+
+    #[test]
+    fn test_scan_attaches_directory_context() {
+        let root = temp_dir("scanner", "context");
+        fs::create_dir_all(root.join("src/billing/tax")).unwrap();
+        fs::write(root.join("README.md"), "# Shop\n\nAn online shop.\n").unwrap();
+        fs::write(root.join("src/billing/README.md"), "# Billing\n\nInvoices and refunds.\n").unwrap();
+        fs::write(root.join("src/billing/OWNERS"), "alice@example.com\n").unwrap();
+        fs::write(root.join("src/billing/tax/vat.ts"), "export function vat() {}\n").unwrap();
+        fs::write(root.join("src/main.ts"), "export function main() {}\n").unwrap();
+
+        let options = ScanOptions {
+            respect_gitignore: false,
+            include: vec!["src/**/*.ts".to_string()],
+            ..Default::default()
+        };
+        let scanner = ProjectScanner::new(options.clone()).with_parse_options(ParseOptions {
+            path_mode: PathMode::Relative,
+            ..Default::default()
+        });
+        let result = scanner.scan(&root).unwrap();
+        let context = |path: &str| {
+            let file = result.results.iter().find(|r| r.file_path == path).unwrap();
+            file.entity_info[0].context.clone()
+        };
+        assert_eq!(
+            context("src/billing/tax/vat.ts").as_deref(),
+            Some("Billing: Invoices and refunds.\nOwners: alice@example.com")
+        );
+        assert_eq!(context("src/main.ts").as_deref(), Some("Shop: An online shop."));
+        let dirs: Vec<&str> = result.contexts.iter().map(|c| c.dir.as_str()).collect();
+        assert_eq!(dirs, vec!["", "src/billing"]);
+
+        let options = ScanOptions {
+            directory_context: false,
+            ..options
+        };
+        let result = ProjectScanner::new(options).scan(&root).unwrap();
+        assert!(result.contexts.is_empty());
+        assert!(result.results.iter().all(|r| r.entity_info.iter().all(|e| e.context.is_none())));
+    }
+
+    #[cfg(feature = "index-store")]
+    #[test]
+    fn test_scan_reuses_store() {
+        let root = temp_dir("scanner", "store");
+        let store_dir = temp_dir("scanner", "store-db");
+        fs::write(root.join("a.ts"), "export function a() {}\n").unwrap();
+        fs::write(root.join("b.ts"), "export function b() {}\n").unwrap();
+        fs::write(root.join("c.ts"), "export function c() {}\n").unwrap();
+        let scan = || {
+            let store = IndexStore::open(&store_dir).unwrap();
+            let result = ProjectScanner::new(ScanOptions::default())
+                .with_parse_options(ParseOptions {
+                    path_mode: PathMode::Relative,
+                    ..Default::default()
+                })
+                .with_store(store.clone())
+                .scan(&root)
+                .unwrap();
+            (result, store.len())
+        };
+        let names = |result: &ScanResult| -> Vec<String> {
+            result
+                .results
+                .iter()
+                .flat_map(|r| r.entity_info.iter().filter_map(|info| info.name.clone()))
+                .collect()
+        };
+
+        let (first, stored) = scan();
+        assert_eq!((first.stats.parsed, first.stats.cached, stored), (3, 0, 3));
+        let (second, _) = scan();
+        assert_eq!((second.stats.parsed, second.stats.cached), (3, 3));
+        let json = |result: &ScanResult| serde_json::to_value(&result.results).unwrap();
+        assert_eq!(json(&second), json(&first));
+
+        // 只重新解析修改过的文件，已删除的文件从存储中清理
+        fs::write(root.join("b.ts"), "export function renamed() {}\n").unwrap();
+        fs::remove_file(root.join("c.ts")).unwrap();
+        let (third, stored) = scan();
+        assert_eq!((third.stats.parsed, third.stats.cached, stored), (2, 1, 2));
+        assert_eq!(names(&third), vec!["a", "renamed"]);
+    }
+
+    #[test]
+    fn test_invalid_glob() {
+        let options = ScanOptions {
+            include: vec!["src/[".to_string()],
+            ..Default::default()
+        };
+        let err = ProjectScanner::new(options).scan(std::env::temp_dir()).unwrap_err();
+        assert!(err.contains("Invalid glob"));
+    }
+}
@@ -0,0 +1,141 @@
+//! Vue 单文件组件（SFC）的 `<script>` 块
+//!
+//! 按文本扫描顶层的 `<script>` / `<script setup>` 标签，取出块内容的字节范围与 `lang` 属性，
+//! 供语言管理器以 TypeScript / JavaScript 解析组件逻辑。
+
+#![cfg_attr(not(feature = "vue"), allow(dead_code))]
+
+use std::ops::Range;
+
+use crate::language::SupportedLanguage;
+
+/// 一个 `<script>` 块
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ScriptBlock {
+    /// 块内容（开始标签与结束标签之间）的字节范围
+    pub content: Range<usize>,
+    /// 解析块内容使用的语言
+    pub language: SupportedLanguage,
+}
+
+/// 找出全部 `<script>` 块（跳过 HTML 注释与 `src` 引用外部文件的空块）
+pub(crate) fn script_blocks(source_code: &str) -> Vec<ScriptBlock> {
+    let lower = source_code.to_ascii_lowercase();
+    let mut blocks = Vec::new();
+    let mut offset = 0;
+    while let Some(pos) = lower[offset..].find('<') {
+        let start = offset + pos;
+        let rest = &lower[start..];
+        if rest.starts_with("<!--") {
+            offset = rest.find("-->").map_or(lower.len(), |end| start + end + 3);
+            continue;
+        }
+        let is_script = rest
+            .strip_prefix("<script")
+            .is_some_and(|after| after.starts_with(|c: char| c.is_ascii_whitespace() || c == '>'));
+        if !is_script {
+            offset = start + 1;
+            continue;
+        }
+
+        let Some(tag_end) = open_tag_end(&source_code[start..]).map(|n| start + n) else {
+            break;
+        };
+        let attributes = &source_code[start + "<script".len()..tag_end - 1];
+        let content_end = lower[tag_end..].find("</script").map_or(lower.len(), |n| tag_end + n);
+        if !source_code[tag_end..content_end].trim().is_empty() {
+            blocks.push(ScriptBlock {
+                content: tag_end..content_end,
+                language: script_language(attribute(attributes, "lang")),
+            });
+        }
+        offset = content_end;
+    }
+    blocks
+}
+
+/// 开始标签 `>` 之后的字节偏移（属性值中的 `>` 不算）
+fn open_tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '>' => return Some(i + 1),
+            None => {}
+        }
+    }
+    None
+}
+
+/// 开始标签中属性的值（`lang="ts"`、`lang='ts'`、`lang=ts`）
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+    while let Some(pos) = rest.find(name) {
+        let before = rest[..pos].chars().next_back();
+        let after = rest[pos + name.len()..].trim_start();
+        rest = &rest[pos + name.len()..];
+        if before.is_some_and(|c| !c.is_ascii_whitespace()) {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=').map(str::trim_start) else {
+            continue;
+        };
+        return Some(match value.chars().next() {
+            Some(q @ ('"' | '\'')) => value[1..].split(q).next().unwrap_or(""),
+            _ => value.split(|c: char| c.is_ascii_whitespace()).next().unwrap_or(""),
+        });
+    }
+    None
+}
+
+/// `lang="ts"` 按 TypeScript 解析；`tsx` / `jsx` / `js` 与未指定时按 JavaScript（TSX 语法，兼容 JSX 与类型注解）
+fn script_language(lang: Option<&str>) -> SupportedLanguage {
+    match lang.map(str::to_ascii_lowercase).as_deref() {
+        Some("ts" | "typescript") => SupportedLanguage::TypeScript,
+        _ => SupportedLanguage::JavaScript,
+    }
+}
+
+/// 把 `keep` 之外的内容替换为等长空白（保留换行），行号与字节偏移不变
+pub(crate) fn mask_outside(source_code: &str, keep: Range<usize>) -> String {
+    let blank = |text: &str| -> String {
+        text.bytes()
+            .map(|b| if matches!(b, b'\n' | b'\r') { b as char } else { ' ' })
+            .collect()
+    };
+    format!(
+        "{}{}{}",
+        blank(&source_code[..keep.start]),
+        &source_code[keep.clone()],
+        blank(&source_code[keep.end..])
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_blocks() {
+        let sfc = "<template>\n  <!-- <script>ignored</script> -->\n  <div>{{ msg }}</div>\n</template>\n\n<script lang=\"ts\">\nexport default {}\n</script>\n\n<script setup lang='TSX'>\nimport Foo from './Foo.vue'\n</script>\n<script src=\"./external.js\"></script>\n";
+        let blocks = script_blocks(sfc);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(&sfc[blocks[0].content.clone()], "\nexport default {}\n");
+        assert_eq!(blocks[0].language, SupportedLanguage::TypeScript);
+        assert_eq!(&sfc[blocks[1].content.clone()], "\nimport Foo from './Foo.vue'\n");
+        assert_eq!(blocks[1].language, SupportedLanguage::JavaScript);
+    }
+
+    #[test]
+    fn test_mask_outside() {
+        let sfc = "<p>é</p>\n<script>\nlet a = 1;\n</script>\n";
+        let block = &script_blocks(sfc)[0];
+        let masked = mask_outside(sfc, block.content.clone());
+        assert_eq!(masked.len(), sfc.len());
+        assert_eq!(masked.lines().count(), sfc.lines().count());
+        assert_eq!(masked.trim(), "let a = 1;");
+        assert_eq!(masked.find("let"), sfc.find("let"));
+    }
+}
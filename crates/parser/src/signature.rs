@@ -0,0 +1,190 @@
+//! 签名的单行显示形式
+//!
+//! 从源码切出的声明头保留了原有的换行、缩进、注释与注解，不便直接展示。
+//! `EntityInfo::signature` 存放整理后的单行形式，原始切片另存于 `raw_signature`。
+
+use crate::types::ParseResult;
+
+/// 把声明头渲染为单行：去掉注释与前置注解 / 装饰器 / 属性，空白折叠为单个空格，
+/// 括号内侧与逗号前不留空格，去掉末尾多余的逗号以及定义体起始符（`{`、Python 的 `:`、`;`）
+pub fn pretty_signature(language: &str, raw: &str) -> String {
+    let uncommented = strip_comments(language, raw);
+    let collapsed = uncommented.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut out = tighten(strip_leading_attributes(&collapsed));
+
+    loop {
+        let stripped = match language {
+            "Python" => out.strip_suffix(':'),
+            "Ruby" => None,
+            _ => out.strip_suffix('{').or_else(|| out.strip_suffix(';')),
+        };
+        match stripped {
+            Some(rest) => out = rest.trim_end().to_string(),
+            None => return out,
+        }
+    }
+}
+
+/// 为结果中的签名生成显示形式，原始切片与显示形式不同时存入 `raw_signature`
+pub(crate) fn normalize_signatures(result: &mut ParseResult) {
+    for info in &mut result.entity_info {
+        let Some(raw) = info.signature.take() else {
+            continue;
+        };
+        let pretty = pretty_signature(&result.language, &raw);
+        if pretty.is_empty() {
+            continue;
+        }
+        info.raw_signature = (pretty != raw).then_some(raw);
+        info.signature = Some(pretty);
+    }
+}
+
+/// 去掉注释（不进入字符串字面量）
+fn strip_comments(language: &str, raw: &str) -> String {
+    let hash_comments = matches!(language, "Python" | "Ruby");
+    // Rust 的 `'a` 是生命周期而不是字符字面量
+    let quotes: &[char] = if language == "Rust" { &['"'] } else { &['"', '\'', '`'] };
+
+    let chars: Vec<char> = raw.chars().collect();
+    let mut out = String::with_capacity(raw.len());
+    let mut quote = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match quote {
+            Some(q) => {
+                out.push(c);
+                if c == '\\' {
+                    out.extend(next);
+                    i += 1;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None if quotes.contains(&c) => {
+                quote = Some(c);
+                out.push(c);
+            }
+            None if (c == '/' && next == Some('/') && !hash_comments) || (c == '#' && hash_comments) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                out.push(' ');
+                continue;
+            }
+            None if c == '/' && next == Some('*') && !hash_comments => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+                out.push(' ');
+                continue;
+            }
+            None => out.push(c),
+        }
+        i += 1;
+    }
+    out
+}
+
+/// 去掉开头的注解 / 装饰器（`@Override`、`@app.route("/")`）与 Rust 属性（`#[inline]`）
+fn strip_leading_attributes(text: &str) -> &str {
+    let mut rest = text;
+    while let Some(end) = leading_attribute_len(rest) {
+        let next = rest[end..].trim_start();
+        // 只有注解时保持原样
+        if next.is_empty() {
+            break;
+        }
+        rest = next;
+    }
+    rest
+}
+
+/// 开头的一个注解 / 属性的字节长度
+fn leading_attribute_len(text: &str) -> Option<usize> {
+    if let Some(after) = text.strip_prefix("#[") {
+        return balanced_end(after, '[', ']').map(|n| 2 + n);
+    }
+    let after = text.strip_prefix('@')?;
+    // 注解名（可带点）后可跟一组括号参数
+    let name_len = after
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        .unwrap_or(after.len());
+    // Java 的 `@interface` 是注解类型的声明
+    if name_len == 0 || &after[..name_len] == "interface" {
+        return None;
+    }
+    match after[name_len..].strip_prefix('(') {
+        Some(args) => balanced_end(args, '(', ')').map(|n| 1 + name_len + 1 + n),
+        None => Some(1 + name_len),
+    }
+}
+
+/// `text` 以开括号之后的内容开头，返回匹配的闭括号之后的字节偏移
+fn balanced_end(text: &str, open: char, close: char) -> Option<usize> {
+    let mut depth = 1;
+    for (i, c) in text.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i + c.len_utf8());
+            }
+        }
+    }
+    None
+}
+
+/// 括号内侧与逗号前不留空格，去掉闭括号前多余的逗号
+fn tighten(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let chars: Vec<char> = text.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        let next = chars.get(i + 1).copied();
+        if c == ' ' && (matches!(out.chars().last(), Some('(' | '[')) || matches!(next, Some(')' | ']' | ','))) {
+            continue;
+        }
+        if c == ',' {
+            let rest = chars[i + 1..].iter().find(|c| **c != ' ');
+            if matches!(rest, Some(')' | ']')) {
+                continue;
+            }
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pretty_signature() {
+        assert_eq!(
+            pretty_signature("TypeScript", "function add(\n    a: number, // first\n    b: number,\n): number {"),
+            "function add(a: number, b: number): number"
+        );
+        assert_eq!(
+            pretty_signature("Java", "@Override\n  @SuppressWarnings(\"unchecked\")\n  public String toString()"),
+            "public String toString()"
+        );
+        assert_eq!(pretty_signature("Python", "def handler(request,  *args):"), "def handler(request, *args)");
+        assert_eq!(
+            pretty_signature("Rust", "#[inline]\npub fn get<'a>(&'a self, key: &str /* utf-8 */) -> Option<&'a str>"),
+            "pub fn get<'a>(&'a self, key: &str) -> Option<&'a str>"
+        );
+        // 字符串中的注释符号与括号不受影响
+        assert_eq!(
+            pretty_signature("TypeScript", "function open(url = \"http://x/*\", mode = '(')"),
+            "function open(url = \"http://x/*\", mode = '(')"
+        );
+        assert_eq!(pretty_signature("C", "int  main ( void );"), "int main (void)");
+        assert_eq!(pretty_signature("Java", "@interface Marker"), "@interface Marker");
+    }
+}
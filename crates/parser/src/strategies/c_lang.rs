@@ -1,7 +1,11 @@
 use tree_sitter::Node;
 use std::collections::HashSet;
 
-use super::{Capture, ParseStrategy, get_node_text, get_lines_text};
+use super::{
+    Capture, ModuleRefs, ParseStrategy, dedup_chunk, definition_node, function_signature, get_node_text, include_refs,
+    lines_chunk, node_chunk,
+};
+use crate::types::Visibility;
 
 /// C 解析策略
 pub struct CStrategy;
@@ -47,43 +51,9 @@ impl CStrategy {
         source_code: &str,
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
-        // 获取函数签名（不包括函数体）
-        // node 是函数名，需要获取 function_definition 父节点
-        let mut current = node;
-        while let Some(parent) = current.parent() {
-            if parent.kind() == "function_definition" {
-                let start_row = parent.start_position().row;
-                let end_row = parent.end_position().row;
-                
-                // 查找函数签名结束位置（{ 之前）
-                let signature_end = self.find_signature_end(source_code, start_row, end_row);
-                let signature = get_lines_text(source_code, start_row, signature_end);
-                let cleaned = signature.trim().to_string();
-                
-                if processed_chunks.contains(&cleaned) {
-                    return None;
-                }
-                
-                processed_chunks.insert(cleaned.clone());
-                return Some(cleaned);
-            }
-            current = parent;
-        }
-        
-        None
-    }
-    
-    fn find_signature_end(&self, source_code: &str, start: usize, end: usize) -> usize {
-        let lines: Vec<&str> = source_code.lines().collect();
-        
-        for i in start..=end.min(lines.len() - 1) {
-            let line = lines[i].trim();
-            if line.ends_with('{') || line.ends_with(';') {
-                return i;
-            }
-        }
-        
-        start
+        // 函数签名（不包括函数体），按语法树定位函数体
+        let def = std::iter::successors(Some(node), |n| n.parent()).find(|n| n.kind() == "function_definition")?;
+        dedup_chunk(function_signature(def, source_code), processed_chunks)
     }
     
     fn parse_struct_or_enum(
@@ -94,18 +64,7 @@ impl CStrategy {
     ) -> Option<String> {
         // node 是结构体/枚举名称，需要获取完整的定义
         if let Some(parent) = node.parent() {
-            let start_row = parent.start_position().row;
-            let end_row = parent.end_position().row;
-            
-            let full_text = get_lines_text(source_code, start_row, end_row);
-            let cleaned = full_text.trim().to_string();
-            
-            if processed_chunks.contains(&cleaned) {
-                return None;
-            }
-            
-            processed_chunks.insert(cleaned.clone());
-            return Some(cleaned);
+            return lines_chunk(parent, source_code, processed_chunks);
         }
         
         None
@@ -138,16 +97,33 @@ impl ParseStrategy for CStrategy {
         if capture_types.iter().any(|t| {
             matches!(t, CaptureType::Typedef | CaptureType::Include | CaptureType::Comment)
         }) {
-            let text = get_node_text(node, source_code).trim().to_string();
-            
-            if processed_chunks.contains(&text) {
-                return None;
-            }
-            
-            processed_chunks.insert(text.clone());
-            return Some(text);
+            return node_chunk(node, source_code, processed_chunks);
         }
         
         None
     }
+    
+    fn extract_module_refs(&self, root: Node, source_code: &str, file_path: &str) -> ModuleRefs {
+        include_refs(root, source_code, file_path)
+    }
+
+    fn classify_visibility(&self, node: Node, source_code: &str) -> (bool, Option<Visibility>) {
+        let def = definition_node(node);
+        match def.kind() {
+            "function_definition" => {
+                // static 函数只在当前编译单元可见
+                let mut cursor = def.walk();
+                let is_static = def.children(&mut cursor).any(|child| {
+                    child.kind() == "storage_class_specifier" && get_node_text(child, source_code) == "static"
+                });
+                if is_static {
+                    (false, Some(Visibility::Private))
+                } else {
+                    (true, Some(Visibility::Public))
+                }
+            }
+            "struct_specifier" | "enum_specifier" | "type_definition" => (true, Some(Visibility::Public)),
+            _ => (false, None),
+        }
+    }
 }
@@ -0,0 +1,42 @@
+use tree_sitter::Node;
+use std::collections::HashSet;
+
+use super::{Capture, ParseStrategy, dedup_chunk, get_node_text};
+
+/// 配置文件（JSON / YAML / TOML）解析策略：顶层键、TOML 表与常见条目（脚本、依赖、服务）
+pub struct ConfigStrategy;
+
+impl ConfigStrategy {
+    /// 键节点：JSON / YAML 的 `key` 字段，TOML 的键值对与表取首个命名子节点（`bare_key` / `dotted_key` 等）
+    fn key_node(node: Node) -> Option<Node> {
+        node.child_by_field_name("key").or_else(|| node.named_child(0))
+    }
+}
+
+impl ParseStrategy for ConfigStrategy {
+    fn parse_capture(
+        &self,
+        capture: Capture,
+        source_code: &str,
+        processed_chunks: &mut HashSet<String>,
+    ) -> Option<String> {
+        // `_` 开头的捕获只用于谓词
+        if capture.name.starts_with('_') {
+            return None;
+        }
+        let text = get_node_text(capture.node, source_code).trim();
+        // 顶层键与表的值中的条目另有实体，只保留键所在的首行
+        let text = match capture.name {
+            "definition.key" | "definition.table" => text.lines().next().unwrap_or_default().trim_end(),
+            _ => text,
+        };
+        let text = text.to_string();
+
+        dedup_chunk(text, processed_chunks)
+    }
+
+    fn entity_name(&self, node: Node, source_code: &str) -> Option<String> {
+        let key = get_node_text(Self::key_node(node)?, source_code).trim();
+        Some(key.trim_matches(|c| matches!(c, '"' | '\'')).to_string())
+    }
+}
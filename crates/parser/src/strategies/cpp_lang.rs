@@ -1,7 +1,11 @@
 use tree_sitter::Node;
 use std::collections::HashSet;
 
-use super::{Capture, ParseStrategy, get_node_text, get_lines_text};
+use super::{
+    Capture, HeaderExtractor, ModuleRefs, ParseStrategy, dedup_chunk, definition_name, definition_node, find_ancestor,
+    function_signature, get_node_text, include_refs, lines_chunk, node_chunk,
+};
+use crate::types::Visibility;
 
 /// C++ 解析策略
 pub struct CppStrategy;
@@ -10,7 +14,12 @@ enum CaptureType {
     Comment,
     Include,
     Function,
+    Constructor,
+    Destructor,
+    Operator,
     Class,
+    Specialization,
+    Concept,
     Struct,
     Enum,
     Namespace,
@@ -28,12 +37,27 @@ impl CppStrategy {
         if name.contains("definition.include") {
             types.push(CaptureType::Include);
         }
-        if name.contains("definition.function") {
+        if name.contains("definition.function") || name.contains("definition.method") {
             types.push(CaptureType::Function);
         }
+        if name.contains("definition.constructor") {
+            types.push(CaptureType::Constructor);
+        }
+        if name.contains("definition.destructor") {
+            types.push(CaptureType::Destructor);
+        }
+        if name.contains("definition.operator") {
+            types.push(CaptureType::Operator);
+        }
         if name.contains("definition.class") {
             types.push(CaptureType::Class);
         }
+        if name.contains("definition.specialization") {
+            types.push(CaptureType::Specialization);
+        }
+        if name.contains("definition.concept") {
+            types.push(CaptureType::Concept);
+        }
         if name.contains("definition.struct") {
             types.push(CaptureType::Struct);
         }
@@ -59,42 +83,9 @@ impl CppStrategy {
         source_code: &str,
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
-        // 获取函数签名（不包括函数体）
-        let mut current = node;
-        while let Some(parent) = current.parent() {
-            if parent.kind() == "function_definition" {
-                let start_row = parent.start_position().row;
-                let end_row = parent.end_position().row;
-                
-                // 查找函数签名结束位置（{ 之前）
-                let signature_end = self.find_signature_end(source_code, start_row, end_row);
-                let signature = get_lines_text(source_code, start_row, signature_end);
-                let cleaned = signature.trim().to_string();
-                
-                if processed_chunks.contains(&cleaned) {
-                    return None;
-                }
-                
-                processed_chunks.insert(cleaned.clone());
-                return Some(cleaned);
-            }
-            current = parent;
-        }
-        
-        None
-    }
-    
-    fn find_signature_end(&self, source_code: &str, start: usize, end: usize) -> usize {
-        let lines: Vec<&str> = source_code.lines().collect();
-        
-        for i in start..=end.min(lines.len() - 1) {
-            let line = lines[i].trim();
-            if line.ends_with('{') || line.ends_with(';') {
-                return i;
-            }
-        }
-        
-        start
+        // 函数签名（不包括函数体），按语法树定位函数体；构造 / 析构 / 运算符捕获的是 function_definition 本身
+        let def = std::iter::successors(Some(node), |n| n.parent()).find(|n| n.kind() == "function_definition")?;
+        dedup_chunk(function_signature(def, source_code), processed_chunks)
     }
     
     fn parse_class(
@@ -103,34 +94,8 @@ impl CppStrategy {
         source_code: &str,
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
-        // node 是类名，需要获取 class_specifier 父节点
-        if let Some(parent) = node.parent() {
-            let start_row = parent.start_position().row;
-            
-            // 只提取类声明行（包括继承关系）
-            let mut lines: Vec<String> = Vec::new();
-            lines.push(source_code.lines().nth(start_row)?.to_string());
-            
-            // 检查下一行是否有继承声明 : public Base
-            if let Some(next_line) = source_code.lines().nth(start_row + 1) {
-                let trimmed = next_line.trim();
-                if trimmed.starts_with(':') || trimmed.contains("public") || trimmed.contains("private") || trimmed.contains("protected") {
-                    lines.push(next_line.to_string());
-                }
-            }
-            
-            let definition = lines.join("\n");
-            let cleaned = definition.split('{').next()?.trim().to_string();
-            
-            if processed_chunks.contains(&cleaned) {
-                return None;
-            }
-            
-            processed_chunks.insert(cleaned.clone());
-            return Some(cleaned);
-        }
-        
-        None
+        // 只提取类声明头（含跨行的继承列表与模板参数）
+        HeaderExtractor::chunk(node, source_code, processed_chunks)
     }
     
     fn parse_struct_or_enum(
@@ -141,18 +106,7 @@ impl CppStrategy {
     ) -> Option<String> {
         // node 是名称，需要获取完整的定义
         if let Some(parent) = node.parent() {
-            let start_row = parent.start_position().row;
-            let end_row = parent.end_position().row;
-            
-            let full_text = get_lines_text(source_code, start_row, end_row);
-            let cleaned = full_text.trim().to_string();
-            
-            if processed_chunks.contains(&cleaned) {
-                return None;
-            }
-            
-            processed_chunks.insert(cleaned.clone());
-            return Some(cleaned);
+            return lines_chunk(parent, source_code, processed_chunks);
         }
         
         None
@@ -164,21 +118,29 @@ impl CppStrategy {
         source_code: &str,
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
-        // 只提取 namespace 声明行（不包括内容）
-        if let Some(parent) = node.parent() {
-            let start_row = parent.start_position().row;
-            let line = source_code.lines().nth(start_row)?;
-            let declaration = line.split('{').next()?.trim().to_string();
-            
-            if processed_chunks.contains(&declaration) {
-                return None;
+        // 只提取 namespace 声明头（不包括内容）；捕获的就是 namespace_definition 本身
+        HeaderExtractor::chunk(node, source_code, processed_chunks)
+    }
+    
+    fn member_access(&self, member: Node, list: Node, source_code: &str) -> Visibility {
+        let mut sibling = member.prev_sibling();
+        while let Some(node) = sibling {
+            if node.kind() == "access_specifier" {
+                return match get_node_text(node, source_code).trim() {
+                    "public" => Visibility::Public,
+                    "protected" => Visibility::Protected,
+                    _ => Visibility::Private,
+                };
             }
-            
-            processed_chunks.insert(declaration.clone());
-            return Some(declaration);
+            sibling = node.prev_sibling();
         }
         
-        None
+        let is_struct = list.parent().is_some_and(|p| p.kind() == "struct_specifier");
+        if is_struct {
+            Visibility::Public
+        } else {
+            Visibility::Private
+        }
     }
 }
 
@@ -194,8 +156,18 @@ impl ParseStrategy for CppStrategy {
         
         let capture_types = self.get_capture_type(name);
         
-        // 函数
-        if capture_types.iter().any(|t| matches!(t, CaptureType::Function)) {
+        // 构造函数同时匹配普通函数的模式，只按构造函数提取
+        if capture_types.iter().any(|t| matches!(t, CaptureType::Function | CaptureType::Constructor)) {
+            let is_constructor = is_constructor(definition_node(node), source_code);
+            let wants_constructor = capture_types.iter().any(|t| matches!(t, CaptureType::Constructor));
+            if is_constructor != wants_constructor {
+                return None;
+            }
+            return self.parse_function(node, source_code, processed_chunks);
+        }
+        
+        // 析构函数与运算符重载
+        if capture_types.iter().any(|t| matches!(t, CaptureType::Destructor | CaptureType::Operator)) {
             return self.parse_function(node, source_code, processed_chunks);
         }
         
@@ -204,6 +176,20 @@ impl ParseStrategy for CppStrategy {
             return self.parse_class(node, source_code, processed_chunks);
         }
         
+        // 模板特化：类只取声明头，函数取签名
+        if capture_types.iter().any(|t| matches!(t, CaptureType::Specialization)) {
+            return match definition_node(node).kind() {
+                "function_definition" => self.parse_function(node, source_code, processed_chunks),
+                _ => self.parse_class(node, source_code, processed_chunks),
+            };
+        }
+        
+        // concept：不含 template 头（整个 template 声明另作为 template 实体提取）
+        if capture_types.iter().any(|t| matches!(t, CaptureType::Concept)) {
+            let text = get_node_text(definition_node(node), source_code).trim().to_string();
+            return dedup_chunk(text, processed_chunks);
+        }
+        
         // 结构体和枚举
         if capture_types.iter().any(|t| matches!(t, CaptureType::Struct | CaptureType::Enum)) {
             return self.parse_struct_or_enum(node, source_code, processed_chunks);
@@ -218,16 +204,180 @@ impl ParseStrategy for CppStrategy {
         if capture_types.iter().any(|t| {
             matches!(t, CaptureType::Template | CaptureType::Using | CaptureType::Include | CaptureType::Comment)
         }) {
-            let text = get_node_text(node, source_code).trim().to_string();
-            
-            if processed_chunks.contains(&text) {
-                return None;
-            }
-            
-            processed_chunks.insert(text.clone());
-            return Some(text);
+            return node_chunk(node, source_code, processed_chunks);
         }
         
         None
     }
+    
+    fn entity_name(&self, node: Node, source_code: &str) -> Option<String> {
+        let def = definition_node(node);
+        if def.kind() == "function_definition" {
+            if let Some(name) = special_member_name(def, source_code) {
+                return Some(name);
+            }
+        }
+        definition_name(node, source_code)
+    }
+    
+    fn constraints(&self, node: Node, source_code: &str) -> Vec<String> {
+        let def = definition_node(node);
+        let one_line = |node: Node| get_node_text(node, source_code).split_whitespace().collect::<Vec<_>>().join(" ");
+        
+        // concept 的约束表达式（`=` 之后的部分）
+        if def.kind() == "concept_definition" {
+            let name = def.child_by_field_name("name").map(|name| name.id());
+            let mut cursor = def.walk();
+            let expression = def.named_children(&mut cursor).filter(|child| Some(child.id()) != name).last();
+            return expression.map(one_line).into_iter().collect();
+        }
+        
+        // template 头上的 requires 子句，以及函数声明符之后的尾置 requires 子句
+        let mut clauses = Vec::new();
+        if let Some(template) = def.parent().filter(|p| p.kind() == "template_declaration") {
+            let mut cursor = template.walk();
+            clauses.extend(template.named_children(&mut cursor).filter(|child| child.kind() == "requires_clause"));
+        }
+        if let Some(declarator) = def.child_by_field_name("declarator").filter(|d| d.kind() == "function_declarator") {
+            let mut cursor = declarator.walk();
+            clauses.extend(declarator.named_children(&mut cursor).filter(|child| child.kind() == "requires_clause"));
+        }
+        clauses
+            .into_iter()
+            .filter_map(|clause| clause.child_by_field_name("constraint"))
+            .map(one_line)
+            .collect()
+    }
+    
+    fn extract_module_refs(&self, root: Node, source_code: &str, file_path: &str) -> ModuleRefs {
+        include_refs(root, source_code, file_path)
+    }
+
+    fn classify_visibility(&self, node: Node, source_code: &str) -> (bool, Option<Visibility>) {
+        let def = definition_node(node);
+        if !matches!(
+            def.kind(),
+            "function_definition" | "class_specifier" | "struct_specifier" | "enum_specifier" | "concept_definition"
+        ) {
+            return (false, None);
+        }
+        
+        // 类成员：看前面最近的 access_specifier，没有则 class 默认 private、struct 默认 public
+        if let Some(list) = def.parent().filter(|p| p.kind() == "field_declaration_list") {
+            let visibility = self.member_access(def, list, source_code);
+            let exported = matches!(visibility, Visibility::Public);
+            return (exported, Some(visibility));
+        }
+        
+        // 匿名命名空间中的定义只在当前编译单元可见
+        let mut current = def.parent();
+        while let Some(parent) = current {
+            if parent.kind() == "namespace_definition" && parent.child_by_field_name("name").is_none() {
+                return (false, Some(Visibility::Private));
+            }
+            current = parent.parent();
+        }
+        
+        let mut cursor = def.walk();
+        let is_static = def.children(&mut cursor).any(|child| {
+            child.kind() == "storage_class_specifier" && get_node_text(child, source_code) == "static"
+        });
+        if is_static {
+            (false, Some(Visibility::Private))
+        } else {
+            (true, Some(Visibility::Public))
+        }
+    }
+}
+
+/// 函数定义的名字节点，以及限定名中的作用域（`Foo::bar` 的 `Foo`）
+///
+/// 跳过返回引用 / 指针的声明符（`Foo& operator=(...)`）。
+fn function_name_parts(def: Node) -> Option<(Node, Option<Node>)> {
+    let mut declarator = def.child_by_field_name("declarator")?;
+    while matches!(declarator.kind(), "reference_declarator" | "pointer_declarator") {
+        declarator = declarator
+            .child_by_field_name("declarator")
+            .or_else(|| declarator.named_child(0))?;
+    }
+    if declarator.kind() == "function_declarator" {
+        declarator = declarator.child_by_field_name("declarator")?;
+    }
+    let mut scope = None;
+    while declarator.kind() == "qualified_identifier" {
+        scope = declarator.child_by_field_name("scope");
+        declarator = declarator.child_by_field_name("name")?;
+    }
+    Some((declarator, scope))
+}
+
+/// 作用域的类名（模板 `Bar<T>` 取 `Bar`）
+fn scope_class_name<'a>(scope: Node, source_code: &'a str) -> &'a str {
+    let name = match scope.kind() {
+        "template_type" => scope.child_by_field_name("name").unwrap_or(scope),
+        _ => scope,
+    };
+    get_node_text(name, source_code).trim()
+}
+
+/// 定义所属的类：限定名中的作用域，或类体内定义时外层的类 / 结构体
+fn owner_class_name(def: Node, scope: Option<Node>, source_code: &str) -> Option<String> {
+    if let Some(scope) = scope {
+        return Some(scope_class_name(scope, source_code).to_string());
+    }
+    let class = find_ancestor(def, &["class_specifier", "struct_specifier"])?;
+    Some(get_node_text(class.child_by_field_name("name")?, source_code).to_string())
+}
+
+/// 构造函数：没有返回类型，名字与所属的类相同（类体内或 `Foo::Foo`）
+fn is_constructor(def: Node, source_code: &str) -> bool {
+    if def.kind() != "function_definition" || def.child_by_field_name("type").is_some() {
+        return false;
+    }
+    let Some((name, scope)) = function_name_parts(def) else {
+        return false;
+    };
+    if name.kind() != "identifier" {
+        return false;
+    }
+    let name = get_node_text(name, source_code);
+    match scope {
+        Some(scope) => scope_class_name(scope, source_code) == name,
+        None => {
+            let parent = def.parent().filter(|p| p.kind() == "template_declaration").and_then(|p| p.parent());
+            parent.or(def.parent()).is_some_and(|p| p.kind() == "field_declaration_list")
+        }
+    }
+}
+
+/// 构造 / 析构函数与运算符重载的名字：`Foo::Foo`、`Foo::~Foo`、`operator==`、`operator bool`
+fn special_member_name(def: Node, source_code: &str) -> Option<String> {
+    let (name, scope) = function_name_parts(def)?;
+    match name.kind() {
+        "operator_name" => Some(operator_name(get_node_text(name, source_code))),
+        "operator_cast" => {
+            let target = get_node_text(name.child_by_field_name("type")?, source_code);
+            Some(format!("operator {}", target.split_whitespace().collect::<Vec<_>>().join(" ")))
+        }
+        "destructor_name" => {
+            let class = owner_class_name(def, scope, source_code)?;
+            let name: String = get_node_text(name, source_code).split_whitespace().collect();
+            Some(format!("{}::{}", class, name))
+        }
+        "identifier" if is_constructor(def, source_code) => {
+            let class = owner_class_name(def, scope, source_code)?;
+            Some(format!("{}::{}", class, get_node_text(name, source_code)))
+        }
+        _ => None,
+    }
+}
+
+/// 规范化运算符名：符号紧跟 `operator`（`operator==`），关键字之间保留一个空格（`operator new[]`）
+fn operator_name(text: &str) -> String {
+    let symbol: String = text.trim_start_matches("operator").split_whitespace().collect();
+    if symbol.starts_with(char::is_alphabetic) {
+        format!("operator {}", symbol)
+    } else {
+        format!("operator{}", symbol)
+    }
 }
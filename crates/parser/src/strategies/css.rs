@@ -1,7 +1,6 @@
-use tree_sitter::Node;
 use std::collections::HashSet;
 
-use super::{Capture, ParseStrategy, get_node_text};
+use super::{Capture, ParseStrategy, node_chunk};
 
 /// CSS 解析策略
 pub struct CssStrategy;
@@ -53,24 +52,10 @@ impl ParseStrategy for CssStrategy {
         // 对于 CSS，我们主要提取选择器和 @ 规则
         // 选择器
         if capture_types.iter().any(|t| matches!(t, CaptureType::Selector)) {
-            let text = get_node_text(node, source_code).trim().to_string();
-            
-            if processed_chunks.contains(&text) {
-                return None;
-            }
-            
-            processed_chunks.insert(text.clone());
-            return Some(text);
+            return node_chunk(node, source_code, processed_chunks);
         }
         
         // @ 规则（media, keyframes, import）和注释 - 直接提取
-        let text = get_node_text(node, source_code).trim().to_string();
-        
-        if processed_chunks.contains(&text) {
-            return None;
-        }
-        
-        processed_chunks.insert(text.clone());
-        Some(text)
+        node_chunk(node, source_code, processed_chunks)
     }
 }
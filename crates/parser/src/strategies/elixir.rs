@@ -0,0 +1,269 @@
+use tree_sitter::Node;
+use std::collections::HashSet;
+
+use super::{Capture, ModuleRefs, ParseStrategy, collect_all_nodes, dedup_chunk, get_node_text, node_chunk};
+use crate::types::{ImportDeclaration, Range, RelationKind, TypeRelation, Visibility};
+
+/// 定义模块的宏
+const MODULE_MACROS: &[&str] = &["defmodule", "defprotocol", "defimpl"];
+
+/// 公开的函数 / 宏定义（对应的 `p` 后缀版本为私有）
+const PUBLIC_DEFINITIONS: &[&str] = &["def", "defdelegate", "defmacro", "defguard"];
+const PRIVATE_DEFINITIONS: &[&str] = &["defp", "defmacrop", "defguardp"];
+
+/// 引用其他模块的指令
+const MODULE_DIRECTIVES: &[&str] = &["alias", "import", "require", "use"];
+
+/// Elixir 解析策略（模块、协议与实现、带元数的函数与宏、behaviour 与回调）
+///
+/// Elixir 的定义都是宏调用（`def run(a), do: a` 是对 `def` 的调用），语法树中统一为 `call` 节点，
+/// 按调用目标区分种类。函数名带元数（`run/1`），与 Elixir 自身引用函数的方式一致。
+pub struct ElixirStrategy;
+
+impl ElixirStrategy {
+    /// 调用目标的名字（`def`、`defmodule`、`alias` 等）
+    fn call_target<'a>(node: Node, source_code: &'a str) -> Option<&'a str> {
+        if node.kind() != "call" {
+            return None;
+        }
+        let target = node.child_by_field_name("target").filter(|target| target.kind() == "identifier")?;
+        Some(get_node_text(target, source_code))
+    }
+
+    /// 调用的参数列表
+    fn arguments(node: Node) -> Option<Node> {
+        let mut cursor = node.walk();
+        let arguments = node.named_children(&mut cursor).find(|child| child.kind() == "arguments");
+        arguments
+    }
+
+    /// 关键字参数（`alias A.B, as: C` 中的 `as: C`）
+    fn keyword_pair<'a>(arguments: Node<'a>, key: &str, source_code: &str) -> Option<Node<'a>> {
+        let mut cursor = arguments.walk();
+        let keywords: Vec<Node> = arguments
+            .named_children(&mut cursor)
+            .filter(|child| child.kind() == "keywords")
+            .collect();
+        keywords.into_iter().find_map(|keywords| {
+            let mut cursor = keywords.walk();
+            let pairs: Vec<Node> = keywords.named_children(&mut cursor).collect();
+            pairs.into_iter().find(|pair| {
+                pair.child_by_field_name("key")
+                    .is_some_and(|keyword| get_node_text(keyword, source_code).trim().trim_end_matches(':') == key)
+            })
+        })
+    }
+
+    /// 关键字参数的值
+    fn keyword_value<'a>(arguments: Node<'a>, key: &str, source_code: &str) -> Option<Node<'a>> {
+        Self::keyword_pair(arguments, key, source_code)?.child_by_field_name("value")
+    }
+
+    /// 模块定义的名字：`defmodule MyApp.Repo` 为 `MyApp.Repo`，`defimpl String.Chars, for: User`
+    /// 为 `String.Chars.User`（与编译后的模块名一致）
+    pub(crate) fn module_name(node: Node, source_code: &str) -> Option<String> {
+        let target = Self::call_target(node, source_code)?;
+        if !MODULE_MACROS.contains(&target) {
+            return None;
+        }
+        let arguments = Self::arguments(node)?;
+        let alias = arguments.named_child(0).filter(|child| child.kind() == "alias")?;
+        let name = get_node_text(alias, source_code);
+        let implemented_for = (target == "defimpl")
+            .then(|| Self::keyword_value(arguments, "for", source_code))
+            .flatten()
+            .filter(|value| value.kind() == "alias");
+        Some(match implemented_for {
+            Some(module) => format!("{}.{}", name, get_node_text(module, source_code)),
+            None => name.to_string(),
+        })
+    }
+
+    /// 函数头的名字与元数：`run(a, b)` 为 `run/2`，不带括号的 `run` 为 `run/0`
+    ///
+    /// 带 guard（`when`）或类型说明（`::`）时取运算符左侧的函数头。
+    fn function_name(head: Node, source_code: &str) -> Option<String> {
+        let head = match head.kind() {
+            "binary_operator" => head.child_by_field_name("left")?,
+            _ => head,
+        };
+        match head.kind() {
+            "identifier" => Some(format!("{}/0", get_node_text(head, source_code))),
+            "call" => {
+                let name = head.child_by_field_name("target")?;
+                let arity = Self::arguments(head).map_or(0, |arguments| arguments.named_child_count());
+                Some(format!("{}/{}", get_node_text(name, source_code), arity))
+            }
+            _ => None,
+        }
+    }
+
+    /// 定义的头部：`do ... end` 块或 `do:` 关键字之前的部分
+    fn header<'a>(node: Node, source_code: &'a str) -> &'a str {
+        let mut cursor = node.walk();
+        let do_block = node.named_children(&mut cursor).find(|child| child.kind() == "do_block");
+        let do_keyword = Self::arguments(node).and_then(|arguments| Self::keyword_pair(arguments, "do", source_code));
+        let end = do_block.or(do_keyword).map_or(node.end_byte(), |body| body.start_byte());
+        source_code[node.start_byte()..end].trim_end().trim_end_matches(',')
+    }
+
+    /// `@behaviour` / `@callback` 等模块属性的名字与参数
+    fn attribute<'a>(node: Node<'a>, source_code: &str) -> Option<(&'static str, Node<'a>)> {
+        if node.kind() != "unary_operator" {
+            return None;
+        }
+        let operand = node.child_by_field_name("operand")?;
+        let name = match Self::call_target(operand, source_code)? {
+            "behaviour" => "behaviour",
+            "callback" | "macrocallback" => "callback",
+            _ => return None,
+        };
+        Some((name, Self::arguments(operand)?.named_child(0)?))
+    }
+}
+
+impl ParseStrategy for ElixirStrategy {
+    fn parse_capture(
+        &self,
+        capture: Capture,
+        source_code: &str,
+        processed_chunks: &mut HashSet<String>,
+    ) -> Option<String> {
+        // `_` 开头的捕获只用于谓词
+        if capture.name.starts_with('_') {
+            return None;
+        }
+        // 模块与函数只取定义头，模块成员由各自的捕获处理
+        if capture.node.kind() == "call" {
+            return dedup_chunk(Self::header(capture.node, source_code), processed_chunks);
+        }
+        node_chunk(capture.node, source_code, processed_chunks)
+    }
+
+    fn entity_name(&self, node: Node, source_code: &str) -> Option<String> {
+        if let Some((attribute, argument)) = Self::attribute(node, source_code) {
+            return match attribute {
+                "behaviour" => Some(get_node_text(argument, source_code).to_string()),
+                _ => Self::function_name(argument, source_code),
+            };
+        }
+        Self::module_name(node, source_code)
+            .or_else(|| Self::function_name(Self::arguments(node)?.named_child(0)?, source_code))
+    }
+
+    fn classify_visibility(&self, node: Node, source_code: &str) -> (bool, Option<Visibility>) {
+        match Self::call_target(node, source_code) {
+            Some(target) if MODULE_MACROS.contains(&target) || PUBLIC_DEFINITIONS.contains(&target) => {
+                (true, Some(Visibility::Public))
+            }
+            Some(target) if PRIVATE_DEFINITIONS.contains(&target) => (false, Some(Visibility::Private)),
+            _ => (false, None),
+        }
+    }
+
+    /// `alias` / `import` / `require` / `use` 指令
+    ///
+    /// `alias` 绑定的名字（`as:` 或别名的最后一段）记为命名空间导入；不带 `only:` 的 `import`
+    /// 记为通配导入，`only: [from: 2]` 记为具名导入 `from/2`。
+    fn extract_module_refs(&self, root: Node, source_code: &str, file_path: &str) -> ModuleRefs {
+        let mut refs = ModuleRefs::default();
+        for call in collect_all_nodes(root, &["call"]) {
+            let Some(directive) = Self::call_target(call, source_code).filter(|t| MODULE_DIRECTIVES.contains(t)) else {
+                continue;
+            };
+            let Some(arguments) = Self::arguments(call) else {
+                continue;
+            };
+            let Some(module) = arguments.named_child(0).filter(|child| child.kind() == "alias") else {
+                continue;
+            };
+            let source = get_node_text(module, source_code).to_string();
+            let mut import = ImportDeclaration {
+                file_path: file_path.to_string(),
+                ..Default::default()
+            };
+            match directive {
+                "alias" => {
+                    import.namespace_import = Self::keyword_value(arguments, "as", source_code)
+                        .map(|alias| get_node_text(alias, source_code).to_string())
+                        .or_else(|| source.rsplit('.').next().map(str::to_string));
+                }
+                "import" => match Self::keyword_value(arguments, "only", source_code) {
+                    Some(only) => {
+                        let mut cursor = only.walk();
+                        for keywords in only.named_children(&mut cursor).filter(|child| child.kind() == "keywords") {
+                            let mut pair_cursor = keywords.walk();
+                            for pair in keywords.named_children(&mut pair_cursor) {
+                                let (Some(key), Some(value)) =
+                                    (pair.child_by_field_name("key"), pair.child_by_field_name("value"))
+                                else {
+                                    continue;
+                                };
+                                let name = get_node_text(key, source_code).trim().trim_end_matches(':');
+                                import.specifiers.push(format!("{}/{}", name, get_node_text(value, source_code)));
+                            }
+                        }
+                    }
+                    None => import.is_wildcard = true,
+                },
+                _ => {}
+            }
+            import.source = source;
+            refs.imports.push(import);
+        }
+        refs
+    }
+
+    /// 模块实现的 behaviour（`@behaviour GenServer`）与协议实现（`defimpl Proto, for: Type`），记为遵循关系
+    fn type_relations(&self, root: Node, source_code: &str) -> Vec<TypeRelation> {
+        let mut relations = Vec::new();
+        for call in collect_all_nodes(root, &["call"]) {
+            let Some(target) = Self::call_target(call, source_code).filter(|t| MODULE_MACROS.contains(t)) else {
+                continue;
+            };
+            let Some(arguments) = Self::arguments(call) else {
+                continue;
+            };
+            if target == "defimpl" {
+                let protocol = arguments.named_child(0).filter(|child| child.kind() == "alias");
+                let implemented_for = Self::keyword_value(arguments, "for", source_code);
+                if let (Some(protocol), Some(implemented_for)) = (protocol, implemented_for) {
+                    relations.push(TypeRelation {
+                        type_name: get_node_text(implemented_for, source_code).to_string(),
+                        target: get_node_text(protocol, source_code).to_string(),
+                        kind: RelationKind::Conforms,
+                        range: range(call),
+                    });
+                }
+                continue;
+            }
+            let Some(module) = Self::module_name(call, source_code) else {
+                continue;
+            };
+            // 只看本模块体的直接成员，嵌套模块的 behaviour 归嵌套模块
+            let mut cursor = call.walk();
+            let Some(body) = call.named_children(&mut cursor).find(|child| child.kind() == "do_block") else {
+                continue;
+            };
+            let mut body_cursor = body.walk();
+            for member in body.named_children(&mut body_cursor) {
+                if let Some(("behaviour", behaviour)) = Self::attribute(member, source_code) {
+                    relations.push(TypeRelation {
+                        type_name: module.clone(),
+                        target: get_node_text(behaviour, source_code).to_string(),
+                        kind: RelationKind::Conforms,
+                        range: range(member),
+                    });
+                }
+            }
+        }
+        relations
+    }
+}
+
+fn range(node: Node) -> Range {
+    Range {
+        start: node.start_position().row + 1,
+        end: node.end_position().row + 1,
+    }
+}
@@ -0,0 +1,182 @@
+use tree_sitter::Node;
+use std::collections::HashSet;
+
+use super::{Capture, ModuleRefs, ParseStrategy, dedup_chunk, get_node_text, node_chunk, root_node, string_literal_value};
+use crate::types::{ImportDeclaration, Range, RelationKind, TypeRelation, Visibility};
+
+/// Erlang 解析策略（模块、behaviour、带元数的函数、宏、记录、类型与回调）
+///
+/// 函数名带元数（`add/2`），与 `-export` 列表的写法一致；多子句函数取第一个子句的函数头。
+pub struct ErlangStrategy;
+
+impl ErlangStrategy {
+    /// 函数定义的名字与元数：`add(A, B) -> ...` 为 `add/2`
+    fn function_name(node: Node, source_code: &str) -> Option<String> {
+        let clause = node.child_by_field_name("clause")?;
+        let name = clause.child_by_field_name("name")?;
+        let arity = clause.child_by_field_name("args").map_or(0, |args| args.named_child_count());
+        Some(format!("{}/{}", get_node_text(name, source_code), arity))
+    }
+
+    /// 回调的名字与元数：`-callback init(Args) -> ...` 为 `init/1`
+    fn callback_name(node: Node, source_code: &str) -> Option<String> {
+        let name = node.child_by_field_name("fun")?;
+        let arity = node
+            .child_by_field_name("sigs")
+            .and_then(|sig| sig.child_by_field_name("args"))
+            .map_or(0, |args| args.named_child_count());
+        Some(format!("{}/{}", get_node_text(name, source_code), arity))
+    }
+
+    /// 第一个子句的函数头（`->` 之前，含 guard）
+    fn function_header<'a>(node: Node, source_code: &'a str) -> Option<&'a str> {
+        let clause = node.child_by_field_name("clause")?;
+        let end = clause.child_by_field_name("body").map_or(clause.end_byte(), |body| body.start_byte());
+        Some(source_code[clause.start_byte()..end].trim_end().trim_end_matches("->").trim_end())
+    }
+
+    /// 文件中 `-export([...])` 导出的函数（`name/arity`，去掉空白）；`-compile(export_all)` 时为 None（全部导出）
+    fn exports(root: Node, source_code: &str) -> Option<HashSet<String>> {
+        let mut exports = HashSet::new();
+        let mut cursor = root.walk();
+        for form in root.named_children(&mut cursor) {
+            let text = get_node_text(form, source_code);
+            if text.starts_with("-compile") && text.contains("export_all") {
+                return None;
+            }
+            if form.kind() != "export_attribute" {
+                continue;
+            }
+            let mut fa_cursor = form.walk();
+            for fa in form.named_children(&mut fa_cursor).filter(|child| child.kind() == "fa") {
+                exports.insert(get_node_text(fa, source_code).split_whitespace().collect());
+            }
+        }
+        Some(exports)
+    }
+
+    /// 模块声明中的名字（`-module(shop_orders).`）
+    fn module_name(root: Node, source_code: &str) -> Option<String> {
+        let mut cursor = root.walk();
+        let module = root.named_children(&mut cursor).find(|form| form.kind() == "module_attribute");
+        Some(get_node_text(module?.child_by_field_name("name")?, source_code).to_string())
+    }
+}
+
+impl ParseStrategy for ErlangStrategy {
+    fn parse_capture(
+        &self,
+        capture: Capture,
+        source_code: &str,
+        processed_chunks: &mut HashSet<String>,
+    ) -> Option<String> {
+        // 函数只取第一个子句的函数头，其余属性取完整声明
+        if capture.node.kind() == "fun_decl" {
+            return dedup_chunk(Self::function_header(capture.node, source_code)?, processed_chunks);
+        }
+        node_chunk(capture.node, source_code, processed_chunks)
+    }
+
+    fn entity_name(&self, node: Node, source_code: &str) -> Option<String> {
+        match node.kind() {
+            "fun_decl" => Self::function_name(node, source_code),
+            "callback" => Self::callback_name(node, source_code),
+            // 宏名在 `lhs` 中（`-define(MAX(A, B), ...)` 为 `MAX`）
+            "pp_define" => {
+                let name = node.child_by_field_name("lhs")?.child_by_field_name("name")?;
+                Some(get_node_text(name, source_code).to_string())
+            }
+            _ => {
+                // 类型名带参数列表（`-type tree(T) :: ...`），只取名字
+                let name = node.child_by_field_name("name")?;
+                let name = name.child_by_field_name("name").unwrap_or(name);
+                Some(get_node_text(name, source_code).to_string())
+            }
+        }
+    }
+
+    fn classify_visibility(&self, node: Node, source_code: &str) -> (bool, Option<Visibility>) {
+        match node.kind() {
+            "module_attribute" => (true, Some(Visibility::Public)),
+            // 只有 `-export` 列出的函数可以从模块外调用
+            "fun_decl" => {
+                let exported = match (Self::exports(root_node(node), source_code), Self::function_name(node, source_code)) {
+                    (None, _) => true,
+                    (Some(exports), Some(name)) => exports.contains(&name),
+                    (Some(_), None) => false,
+                };
+                if exported {
+                    (true, Some(Visibility::Public))
+                } else {
+                    (false, Some(Visibility::Private))
+                }
+            }
+            _ => (false, None),
+        }
+    }
+
+    /// `-import(lists, [map/2]).` 与 `-include("x.hrl")` / `-include_lib("app/include/x.hrl")`
+    fn extract_module_refs(&self, root: Node, source_code: &str, file_path: &str) -> ModuleRefs {
+        let mut refs = ModuleRefs::default();
+        let mut cursor = root.walk();
+        for form in root.named_children(&mut cursor) {
+            let import = match form.kind() {
+                "import_attribute" => {
+                    let Some(module) = form.child_by_field_name("module") else {
+                        continue;
+                    };
+                    let mut fa_cursor = form.walk();
+                    ImportDeclaration {
+                        source: get_node_text(module, source_code).to_string(),
+                        specifiers: form
+                            .named_children(&mut fa_cursor)
+                            .filter(|child| child.kind() == "fa")
+                            .map(|fa| get_node_text(fa, source_code).split_whitespace().collect())
+                            .collect(),
+                        file_path: file_path.to_string(),
+                        ..Default::default()
+                    }
+                }
+                "pp_include" | "pp_include_lib" => {
+                    let Some(file) = form.child_by_field_name("file") else {
+                        continue;
+                    };
+                    ImportDeclaration {
+                        source: string_literal_value(file, source_code),
+                        file_path: file_path.to_string(),
+                        ..Default::default()
+                    }
+                }
+                _ => continue,
+            };
+            refs.imports.push(import);
+        }
+        refs
+    }
+
+    /// 模块实现的 behaviour（`-behaviour(gen_server).`），记为遵循关系
+    fn type_relations(&self, root: Node, source_code: &str) -> Vec<TypeRelation> {
+        let Some(module) = Self::module_name(root, source_code) else {
+            return Vec::new();
+        };
+        let mut cursor = root.walk();
+        let behaviours: Vec<Node> = root
+            .named_children(&mut cursor)
+            .filter(|form| form.kind() == "behaviour_attribute")
+            .collect();
+        behaviours
+            .into_iter()
+            .filter_map(|form| {
+                Some(TypeRelation {
+                    type_name: module.clone(),
+                    target: get_node_text(form.child_by_field_name("name")?, source_code).to_string(),
+                    kind: RelationKind::Conforms,
+                    range: Range {
+                        start: form.start_position().row + 1,
+                        end: form.end_position().row + 1,
+                    },
+                })
+            })
+            .collect()
+    }
+}
@@ -1,7 +1,17 @@
 use tree_sitter::Node;
 use std::collections::HashSet;
 
-use super::{Capture, ParseStrategy, get_node_text, get_lines_text};
+use super::{
+    CallSyntax, Capture, ModuleRefs, ParseStrategy, SignatureExtractor, collect_nodes, definition_node, get_node_text,
+    node_chunk, string_literal_value,
+};
+use crate::types::{ExportDeclaration, ImportDeclaration, Range, RelationKind, TypeRelation, Visibility};
+
+/// 调用关系（函数字面量中的调用归属外层函数）
+static CALL_SYNTAX: CallSyntax = CallSyntax {
+    calls: &[("call_expression", "function")],
+    callers: &["function_declaration", "method_declaration"],
+};
 
 /// Go 解析策略（基于 repomix 的实现）
 pub struct GoStrategy;
@@ -55,34 +65,19 @@ impl GoStrategy {
         source_code: &str,
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
-        let start_row = node.start_position().row;
-        let end_row = node.end_position().row;
-        
-        // 查找签名结束位置（{ 之前）
-        let signature_end = self.find_brace_start(source_code, start_row, end_row);
-        let signature = get_lines_text(source_code, start_row, signature_end);
-        
-        // 移除 { 及之后的内容
-        let cleaned = signature.split('{').next()?.trim().to_string();
-        
-        if processed_chunks.contains(&cleaned) {
-            return None;
-        }
-        
-        processed_chunks.insert(cleaned.clone());
-        Some(cleaned)
+        // 函数签名（不包括函数体），按语法树定位函数体
+        SignatureExtractor::chunk(node, source_code, processed_chunks)
     }
     
-    fn find_brace_start(&self, source_code: &str, start: usize, end: usize) -> usize {
-        let lines: Vec<&str> = source_code.lines().collect();
-        
-        for i in start..=end.min(lines.len() - 1) {
-            if lines[i].contains('{') {
-                return i;
-            }
+    /// 被嵌入的类型名：结构体中没有字段名的字段、接口中只有单个类型的元素（不含 `~T` 与联合）
+    fn embedded_type<'a>(member: Node<'a>) -> Option<Node<'a>> {
+        match member.kind() {
+            "field_declaration" if member.child_by_field_name("name").is_none() => member.child_by_field_name("type"),
+            "type_elem" if member.named_child_count() == 1 => member
+                .named_child(0)
+                .filter(|ty| matches!(ty.kind(), "type_identifier" | "qualified_type" | "generic_type")),
+            _ => None,
         }
-        
-        start
     }
 }
 
@@ -104,13 +99,158 @@ impl ParseStrategy for GoStrategy {
         }
         
         // 其他类型（直接提取）
-        let text = get_node_text(node, source_code).trim().to_string();
+        node_chunk(node, source_code, processed_chunks)
+    }
+    
+    fn extract_module_refs(&self, root: Node, source_code: &str, file_path: &str) -> ModuleRefs {
+        let mut refs = ModuleRefs::default();
+        for spec in collect_nodes(root, &["import_spec"]) {
+            let Some(path) = spec.child_by_field_name("path") else {
+                continue;
+            };
+            let mut import = ImportDeclaration {
+                source: string_literal_value(path, source_code),
+                file_path: file_path.to_string(),
+                ..Default::default()
+            };
+            // `. "pkg"` 把包成员导入当前作用域；`_ "pkg"` 只为副作用导入
+            match spec.child_by_field_name("name") {
+                Some(name) if name.kind() == "dot" => import.is_wildcard = true,
+                Some(name) if name.kind() == "package_identifier" => {
+                    import.namespace_import = Some(get_node_text(name, source_code).to_string());
+                }
+                Some(_) => {}
+                None => {
+                    import.namespace_import = import.source.rsplit('/').next().map(str::to_string);
+                }
+            }
+            refs.imports.push(import);
+        }
         
-        if processed_chunks.contains(&text) {
-            return None;
+        // 包级的函数、类型、常量与变量以首字母大写导出（方法随接收者类型导出）
+        let mut names = Vec::new();
+        let mut cursor = root.walk();
+        for declaration in root.children(&mut cursor) {
+            let name_nodes = match declaration.kind() {
+                "function_declaration" => declaration.child_by_field_name("name").into_iter().collect(),
+                "type_declaration" => collect_nodes(declaration, &["type_spec", "type_alias"])
+                    .into_iter()
+                    .filter_map(|spec| spec.child_by_field_name("name"))
+                    .collect(),
+                "const_declaration" | "var_declaration" => collect_nodes(declaration, &["const_spec", "var_spec"])
+                    .into_iter()
+                    .flat_map(|spec| {
+                        let mut cursor = spec.walk();
+                        spec.children_by_field_name("name", &mut cursor).collect::<Vec<_>>()
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            names.extend(
+                name_nodes
+                    .into_iter()
+                    .map(|name| get_node_text(name, source_code))
+                    .filter(|name| name.starts_with(char::is_uppercase))
+                    .map(str::to_string),
+            );
         }
+        if !names.is_empty() {
+            refs.exports.push(ExportDeclaration {
+                specifiers: names,
+                file_path: file_path.to_string(),
+                ..Default::default()
+            });
+        }
+        refs
+    }
+
+    fn classify_visibility(&self, node: Node, source_code: &str) -> (bool, Option<Visibility>) {
+        let def = definition_node(node);
+        let name_node = match def.kind() {
+            "function_declaration" | "method_declaration" => def.child_by_field_name("name"),
+            // type 声明取第一个 type_spec 的名字
+            "type_declaration" => def
+                .named_child(0)
+                .and_then(|spec| spec.child_by_field_name("name")),
+            _ => None,
+        };
+        let Some(name_node) = name_node else {
+            return (false, None);
+        };
         
-        processed_chunks.insert(text.clone());
-        Some(text)
+        // Go 以首字母大小写决定导出
+        let exported = get_node_text(name_node, source_code)
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_uppercase());
+        if exported {
+            (true, Some(Visibility::Public))
+        } else {
+            (false, Some(Visibility::Internal))
+        }
+    }
+    
+    fn constraints(&self, node: Node, source_code: &str) -> Vec<String> {
+        // 泛型函数与泛型类型的类型参数声明（`K comparable`、`T, U any`）
+        let def = definition_node(node);
+        let lists = match def.kind() {
+            "function_declaration" => def.child_by_field_name("type_parameters").into_iter().collect(),
+            "type_declaration" => collect_nodes(def, &["type_spec"])
+                .into_iter()
+                .filter_map(|spec| spec.child_by_field_name("type_parameters"))
+                .collect(),
+            _ => Vec::new(),
+        };
+        lists
+            .into_iter()
+            .flat_map(|list| collect_nodes(list, &["type_parameter_declaration"]))
+            .map(|param| {
+                get_node_text(param, source_code)
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect()
+    }
+    
+    fn call_syntax(&self) -> Option<&'static CallSyntax> {
+        Some(&CALL_SYNTAX)
+    }
+    
+    fn type_relations(&self, root: Node, source_code: &str) -> Vec<TypeRelation> {
+        let mut relations = Vec::new();
+        for spec in collect_nodes(root, &["type_spec"]) {
+            let (Some(name), Some(body)) = (spec.child_by_field_name("name"), spec.child_by_field_name("type")) else {
+                continue;
+            };
+            let members = match body.kind() {
+                "struct_type" => collect_nodes(body, &["field_declaration"]),
+                "interface_type" => {
+                    let mut cursor = body.walk();
+                    body.named_children(&mut cursor).collect()
+                }
+                _ => continue,
+            };
+            for member in members {
+                let Some(embedded) = Self::embedded_type(member) else {
+                    continue;
+                };
+                // 泛型实例 `List[T]` 只保留类型名
+                let target = match embedded.kind() {
+                    "generic_type" => embedded.child_by_field_name("type").unwrap_or(embedded),
+                    _ => embedded,
+                };
+                relations.push(TypeRelation {
+                    type_name: get_node_text(name, source_code).to_string(),
+                    target: get_node_text(target, source_code).to_string(),
+                    kind: RelationKind::Embeds,
+                    range: Range {
+                        start: member.start_position().row + 1,
+                        end: member.end_position().row + 1,
+                    },
+                });
+            }
+        }
+        relations
     }
 }
@@ -0,0 +1,53 @@
+use tree_sitter::Node;
+use std::collections::HashSet;
+
+use super::{Capture, ParseStrategy, dedup_chunk, get_node_text};
+use crate::types::Visibility;
+
+/// GraphQL 解析策略（schema 类型、根类型字段、具名操作与片段）
+pub struct GraphqlStrategy;
+
+impl GraphqlStrategy {
+    /// 定义的名字节点：语法没有 `name` 字段，取首个 `name` 子节点（片段名在 `fragment_name` 中）
+    fn name_node(node: Node) -> Option<Node> {
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        children.into_iter().find_map(|child| match child.kind() {
+            "name" => Some(child),
+            "fragment_name" => Self::name_node(child),
+            _ => None,
+        })
+    }
+}
+
+impl ParseStrategy for GraphqlStrategy {
+    fn parse_capture(
+        &self,
+        capture: Capture,
+        source_code: &str,
+        processed_chunks: &mut HashSet<String>,
+    ) -> Option<String> {
+        // `_` 开头的捕获只用于谓词
+        if capture.name.starts_with('_') {
+            return None;
+        }
+        // 完整定义（类型包含字段列表，操作包含选择集）
+        let text = get_node_text(capture.node, source_code).trim().to_string();
+        
+        dedup_chunk(text, processed_chunks)
+    }
+    
+    fn entity_name(&self, node: Node, source_code: &str) -> Option<String> {
+        Some(get_node_text(Self::name_node(node)?, source_code).to_string())
+    }
+    
+    fn classify_visibility(&self, node: Node, _source_code: &str) -> (bool, Option<Visibility>) {
+        match node.kind() {
+            // schema 中的类型与字段对所有客户端可见
+            "schema_definition" | "object_type_definition" | "interface_type_definition" | "enum_type_definition"
+            | "input_object_type_definition" | "union_type_definition" | "scalar_type_definition"
+            | "field_definition" => (true, Some(Visibility::Public)),
+            _ => (false, None),
+        }
+    }
+}
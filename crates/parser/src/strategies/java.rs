@@ -1,7 +1,17 @@
 use tree_sitter::Node;
 use std::collections::HashSet;
 
-use super::{Capture, ParseStrategy, get_node_text};
+use super::{
+    CallSyntax, Capture, HeaderExtractor, ModuleRefs, ParseStrategy, definition_node, get_node_text, node_chunk,
+    visibility_from_keywords,
+};
+use crate::types::{ExportDeclaration, ImportDeclaration, Visibility};
+
+/// 调用关系：方法调用与 `new`（lambda 中的调用归属外层方法）
+static CALL_SYNTAX: CallSyntax = CallSyntax {
+    calls: &[("method_invocation", "name"), ("object_creation_expression", "type")],
+    callers: &["method_declaration", "constructor_declaration"],
+};
 
 /// Java 解析策略
 pub struct JavaStrategy;
@@ -53,14 +63,7 @@ impl JavaStrategy {
     ) -> Option<String> {
         // 返回完整的方法内容（包括方法体），以便 TypeScript 侧提取函数调用
         // 修改理由：之前只返回签名，导致 indexingService.extractFunctionCalls 无法提取调用关系
-        let text = get_node_text(node, source_code).trim().to_string();
-
-        if processed_chunks.contains(&text) {
-            return None;
-        }
-
-        processed_chunks.insert(text.clone());
-        Some(text)
+        node_chunk(node, source_code, processed_chunks)
     }
     
     fn parse_class(
@@ -69,29 +72,36 @@ impl JavaStrategy {
         source_code: &str,
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
-        let start_row = node.start_position().row;
-        
-        // 只提取类声明行
-        let mut lines: Vec<String> = Vec::new();
-        lines.push(source_code.lines().nth(start_row)?.to_string());
+        // 只提取类声明头（含跨行的 extends / implements）
+        HeaderExtractor::chunk(node, source_code, processed_chunks)
+    }
+    
+    fn is_declaration(kind: &str) -> bool {
+        matches!(
+            kind,
+            "class_declaration" | "interface_declaration" | "enum_declaration" | "record_declaration"
+                | "method_declaration" | "constructor_declaration" | "field_declaration"
+        )
+    }
+    
+    /// 声明上的访问修饰符；接口成员默认 public，其余默认包级可见
+    fn declared_visibility(&self, def: Node, source_code: &str) -> Visibility {
+        let mut cursor = def.walk();
+        let modifiers = def
+            .children(&mut cursor)
+            .find(|child| child.kind() == "modifiers")
+            .map(|m| get_node_text(m, source_code));
         
-        // 检查下一行是否有 extends 或 implements
-        if let Some(next_line) = source_code.lines().nth(start_row + 1) {
-            let trimmed = next_line.trim();
-            if trimmed.starts_with("extends") || trimmed.starts_with("implements") {
-                lines.push(next_line.to_string());
-            }
+        if let Some(visibility) = modifiers.and_then(visibility_from_keywords) {
+            return visibility;
         }
         
-        let definition = lines.join("\n");
-        let cleaned = definition.split('{').next()?.trim().to_string();
-        
-        if processed_chunks.contains(&cleaned) {
-            return None;
+        let in_interface = def.parent().is_some_and(|p| p.kind() == "interface_body");
+        if in_interface {
+            Visibility::Public
+        } else {
+            Visibility::Internal
         }
-        
-        processed_chunks.insert(cleaned.clone());
-        Some(cleaned)
     }
 }
 
@@ -118,13 +128,89 @@ impl ParseStrategy for JavaStrategy {
         }
         
         // 其他类型（直接提取）
-        let text = get_node_text(node, source_code).trim().to_string();
+        node_chunk(node, source_code, processed_chunks)
+    }
+    
+    fn extract_module_refs(&self, root: Node, source_code: &str, file_path: &str) -> ModuleRefs {
+        let mut refs = ModuleRefs::default();
+        let mut cursor = root.walk();
+        for declaration in root.children(&mut cursor) {
+            if declaration.kind() != "import_declaration" {
+                continue;
+            }
+            let mut inner = declaration.walk();
+            let children: Vec<Node> = declaration.named_children(&mut inner).collect();
+            let Some(path) = children.iter().find(|c| matches!(c.kind(), "scoped_identifier" | "identifier")) else {
+                continue;
+            };
+            let mut import = ImportDeclaration {
+                file_path: file_path.to_string(),
+                ..Default::default()
+            };
+            // `import a.b.*;` 导入整个包（或静态导入类的全部成员）
+            if children.iter().any(|c| c.kind() == "asterisk") {
+                import.source = get_node_text(*path, source_code).to_string();
+                import.is_wildcard = true;
+            } else {
+                let (Some(scope), Some(name)) = (path.child_by_field_name("scope"), path.child_by_field_name("name")) else {
+                    continue;
+                };
+                import.source = get_node_text(scope, source_code).to_string();
+                import.specifiers.push(get_node_text(name, source_code).to_string());
+            }
+            refs.imports.push(import);
+        }
         
-        if processed_chunks.contains(&text) {
-            return None;
+        // 对外可见的是 public 顶层类型（其成员随类型一起导出）
+        let mut cursor = root.walk();
+        let public_types: Vec<String> = root
+            .children(&mut cursor)
+            .filter(|declaration| {
+                matches!(
+                    declaration.kind(),
+                    "class_declaration" | "interface_declaration" | "enum_declaration" | "record_declaration"
+                        | "annotation_type_declaration"
+                ) && matches!(self.declared_visibility(*declaration, source_code), Visibility::Public)
+            })
+            .filter_map(|declaration| declaration.child_by_field_name("name"))
+            .map(|name| get_node_text(name, source_code).to_string())
+            .collect();
+        if !public_types.is_empty() {
+            refs.exports.push(ExportDeclaration {
+                specifiers: public_types,
+                file_path: file_path.to_string(),
+                ..Default::default()
+            });
+        }
+        refs
+    }
+
+    fn classify_visibility(&self, node: Node, source_code: &str) -> (bool, Option<Visibility>) {
+        let def = definition_node(node);
+        if !Self::is_declaration(def.kind()) {
+            return (false, None);
         }
         
-        processed_chunks.insert(text.clone());
-        Some(text)
+        let visibility = self.declared_visibility(def, source_code);
+        if !matches!(visibility, Visibility::Public) {
+            return (false, Some(visibility));
+        }
+        
+        // 只有所有外层类型都是 public 才属于对外 API
+        let mut current = def.parent();
+        while let Some(parent) = current {
+            if Self::is_declaration(parent.kind())
+                && !matches!(self.declared_visibility(parent, source_code), Visibility::Public)
+            {
+                return (false, Some(visibility));
+            }
+            current = parent.parent();
+        }
+        
+        (true, Some(visibility))
+    }
+    
+    fn call_syntax(&self) -> Option<&'static CallSyntax> {
+        Some(&CALL_SYNTAX)
     }
 }
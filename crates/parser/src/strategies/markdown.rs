@@ -0,0 +1,63 @@
+use tree_sitter::Node;
+use std::collections::HashSet;
+
+use super::{Capture, ParseStrategy, dedup_chunk, get_node_text};
+
+/// Markdown 解析策略（带标题的章节、围栏代码块）
+pub struct MarkdownStrategy;
+
+impl MarkdownStrategy {
+    /// 章节的标题节点（ATX `#` 或 Setext 下划线标题）；标题之前的前言没有
+    fn heading(section: Node) -> Option<Node> {
+        section
+            .named_child(0)
+            .filter(|child| matches!(child.kind(), "atx_heading" | "setext_heading"))
+    }
+
+    /// 章节自身的内容：从标题到第一个子章节之前（子章节另有实体）
+    fn own_text<'a>(section: Node, source_code: &'a str) -> &'a str {
+        let mut cursor = section.walk();
+        let end = section
+            .named_children(&mut cursor)
+            .find(|child| child.kind() == "section")
+            .map_or(section.end_byte(), |child| child.start_byte());
+        &source_code[section.start_byte()..end]
+    }
+}
+
+impl ParseStrategy for MarkdownStrategy {
+    fn parse_capture(
+        &self,
+        capture: Capture,
+        source_code: &str,
+        processed_chunks: &mut HashSet<String>,
+    ) -> Option<String> {
+        let node = capture.node;
+        let text = match node.kind() {
+            "section" => {
+                Self::heading(node)?;
+                Self::own_text(node, source_code).trim().to_string()
+            }
+            _ => get_node_text(node, source_code).trim().to_string(),
+        };
+
+        dedup_chunk(text, processed_chunks)
+    }
+
+    fn entity_name(&self, node: Node, source_code: &str) -> Option<String> {
+        match node.kind() {
+            "section" => {
+                let content = Self::heading(node)?.child_by_field_name("heading_content")?;
+                Some(get_node_text(content, source_code).trim().to_string())
+            }
+            // 代码块以语言标记为名
+            "fenced_code_block" => {
+                let mut cursor = node.walk();
+                let info = node.named_children(&mut cursor).find(|child| child.kind() == "info_string")?;
+                let language = get_node_text(info, source_code).split_whitespace().next()?;
+                Some(language.to_string())
+            }
+            _ => None,
+        }
+    }
+}
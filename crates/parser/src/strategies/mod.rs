@@ -10,6 +10,8 @@ mod go_lang;
 mod rust_lang;
 #[cfg(feature = "java")]
 mod java;
+#[cfg(feature = "php")]
+mod php;
 #[cfg(feature = "c-lang")]
 mod c_lang;
 #[cfg(feature = "cpp")]
@@ -22,6 +24,27 @@ mod solidity;
 mod css;
 #[cfg(feature = "vue")]
 mod vue;
+#[cfg(feature = "sql")]
+mod sql;
+#[cfg(feature = "graphql")]
+mod graphql;
+#[cfg(feature = "proto")]
+mod protobuf;
+#[cfg(feature = "elixir")]
+mod elixir;
+#[cfg(feature = "erlang")]
+mod erlang;
+#[cfg(feature = "zig")]
+mod zig;
+#[cfg(feature = "nim")]
+mod nim;
+#[cfg(feature = "config-files")]
+mod config;
+#[cfg(feature = "markdown")]
+mod markdown;
+#[cfg(feature = "plugins")]
+mod plugin;
+mod strategy_utils;
 
 pub use typescript::TypeScriptStrategy;
 #[cfg(feature = "python")]
@@ -32,6 +55,8 @@ pub use go_lang::GoStrategy;
 pub use rust_lang::RustStrategy;
 #[cfg(feature = "java")]
 pub use java::JavaStrategy;
+#[cfg(feature = "php")]
+pub use php::PhpStrategy;
 #[cfg(feature = "c-lang")]
 pub use c_lang::CStrategy;
 #[cfg(feature = "cpp")]
@@ -44,8 +69,38 @@ pub use solidity::SolidityStrategy;
 pub use css::CssStrategy;
 #[cfg(feature = "vue")]
 pub use vue::VueStrategy;
+#[cfg(feature = "sql")]
+pub use sql::SqlStrategy;
+#[cfg(feature = "graphql")]
+pub use graphql::GraphqlStrategy;
+#[cfg(feature = "proto")]
+pub use protobuf::ProtobufStrategy;
+#[cfg(feature = "elixir")]
+pub use elixir::ElixirStrategy;
+#[cfg(feature = "erlang")]
+pub use erlang::ErlangStrategy;
+#[cfg(feature = "zig")]
+pub use zig::ZigStrategy;
+#[cfg(feature = "nim")]
+pub use nim::NimStrategy;
+#[cfg(feature = "config-files")]
+pub use config::ConfigStrategy;
+#[cfg(feature = "markdown")]
+pub use markdown::MarkdownStrategy;
+#[cfg(feature = "plugins")]
+pub use plugin::PluginStrategy;
+pub use strategy_utils::{
+    HeaderExtractor, SignatureExtractor, dedup_chunk, function_definition, function_signature, lines_chunk, node_chunk,
+};
+#[cfg(any(feature = "php", feature = "solidity"))]
+pub use strategy_utils::declaration_header;
 
+use crate::doc_comment::leading_comment;
 use crate::language::SupportedLanguage;
+use crate::types::{
+    Annotation, DynamicImport, DynamicImportKind, ExportDeclaration, ImportDeclaration, Modification, ModificationKind,
+    Range, TypeRelation, Visibility,
+};
 
 /// 解析捕获的节点
 pub struct Capture<'a> {
@@ -53,6 +108,22 @@ pub struct Capture<'a> {
     pub name: &'a str,
 }
 
+/// 文件级的 import / export 声明
+#[derive(Debug, Default)]
+pub struct ModuleRefs {
+    pub imports: Vec<ImportDeclaration>,
+    pub exports: Vec<ExportDeclaration>,
+    pub dynamic_imports: Vec<DynamicImport>,
+}
+
+/// 调用关系的语法描述（用于提取调用图）
+pub struct CallSyntax {
+    /// 调用表达式的节点类型，及其中被调用者所在的字段
+    pub calls: &'static [(&'static str, &'static str)],
+    /// 可作为调用方的定义节点类型（匿名函数的名字取自所赋值的变量 / 属性）
+    pub callers: &'static [&'static str],
+}
+
 /// 解析策略 trait（继承自 repomix 的设计）
 pub trait ParseStrategy: Send + Sync {
     /// 解析捕获的节点，返回提取的代码片段
@@ -63,6 +134,69 @@ pub trait ParseStrategy: Send + Sync {
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String>;
 
+    /// 判断捕获节点对应实体的导出状态与可见性，返回 (是否导出, 可见性)
+    ///
+    /// 默认不导出、无可见性信息（注释、导入等）
+    fn classify_visibility(&self, _node: Node, _source_code: &str) -> (bool, Option<Visibility>) {
+        (false, None)
+    }
+
+    /// 捕获节点对应实体的名字
+    ///
+    /// 默认取定义节点的 `name` 字段（或捕获的名字节点本身）
+    fn entity_name(&self, node: Node, source_code: &str) -> Option<String> {
+        definition_name(node, source_code)
+    }
+
+    /// 捕获节点对应实体的约束（C++20 `requires` 子句、concept 的约束表达式等）
+    ///
+    /// 默认没有约束
+    fn constraints(&self, _node: Node, _source_code: &str) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// 捕获节点对应实体的注解（Swift 属性包装器 `@State`、`@Published` 等）
+    ///
+    /// 默认没有注解
+    fn annotations(&self, _node: Node, _source_code: &str) -> Vec<Annotation> {
+        Vec::new()
+    }
+
+    /// 捕获节点对应实体的文档注释
+    ///
+    /// 默认取紧贴在定义之前的注释
+    fn doc_comment(&self, node: Node, source_code: &str) -> Option<String> {
+        leading_comment(definition_node(node), source_code)
+    }
+
+    /// 从语法树提取 import / export 声明
+    ///
+    /// 默认不提取（未实现的语言返回空列表）
+    fn extract_module_refs(&self, _root: Node, _source_code: &str, _file_path: &str) -> ModuleRefs {
+        ModuleRefs::default()
+    }
+
+    /// 调用关系的语法描述
+    ///
+    /// 默认不提取调用关系（返回 None）
+    fn call_syntax(&self) -> Option<&'static CallSyntax> {
+        None
+    }
+
+    /// 从语法树提取类型关系（继承、协议遵循）
+    ///
+    /// 默认不提取
+    fn type_relations(&self, _root: Node, _source_code: &str) -> Vec<TypeRelation> {
+        Vec::new()
+    }
+
+    /// 从语法树提取模块顶层的运行时修改（导入名字的重新赋值、成员补丁、原型补丁）
+    ///
+    /// 默认不提取
+    fn modifications(&self, _root: Node, _source_code: &str, _imports: &[ImportDeclaration]) -> Vec<Modification> {
+        Vec::new()
+    }
+
     /// 是否应该跳过此节点（预留接口，未来可能使用）
     #[allow(dead_code)]
     fn should_skip(&self, _node: &Node) -> bool {
@@ -84,6 +218,8 @@ pub fn create_strategy(lang: SupportedLanguage) -> Box<dyn ParseStrategy> {
         SupportedLanguage::Rust => Box::new(RustStrategy),
         #[cfg(feature = "java")]
         SupportedLanguage::Java => Box::new(JavaStrategy),
+        #[cfg(feature = "php")]
+        SupportedLanguage::PHP => Box::new(PhpStrategy),
         #[cfg(feature = "c-lang")]
         SupportedLanguage::C => Box::new(CStrategy),
         #[cfg(feature = "cpp")]
@@ -96,6 +232,24 @@ pub fn create_strategy(lang: SupportedLanguage) -> Box<dyn ParseStrategy> {
         SupportedLanguage::Css => Box::new(CssStrategy),
         #[cfg(feature = "vue")]
         SupportedLanguage::Vue => Box::new(VueStrategy),
+        #[cfg(feature = "sql")]
+        SupportedLanguage::Sql => Box::new(SqlStrategy),
+        #[cfg(feature = "graphql")]
+        SupportedLanguage::GraphQL => Box::new(GraphqlStrategy),
+        #[cfg(feature = "proto")]
+        SupportedLanguage::Protobuf => Box::new(ProtobufStrategy),
+        #[cfg(feature = "elixir")]
+        SupportedLanguage::Elixir => Box::new(ElixirStrategy),
+        #[cfg(feature = "erlang")]
+        SupportedLanguage::Erlang => Box::new(ErlangStrategy),
+        #[cfg(feature = "zig")]
+        SupportedLanguage::Zig => Box::new(ZigStrategy),
+        #[cfg(feature = "nim")]
+        SupportedLanguage::Nim => Box::new(NimStrategy),
+        #[cfg(feature = "config-files")]
+        SupportedLanguage::Json | SupportedLanguage::Yaml | SupportedLanguage::Toml => Box::new(ConfigStrategy),
+        #[cfg(feature = "markdown")]
+        SupportedLanguage::Markdown => Box::new(MarkdownStrategy),
     }
 }
 
@@ -104,12 +258,197 @@ pub fn get_node_text<'a>(node: Node, source_code: &'a str) -> &'a str {
     &source_code[node.byte_range()]
 }
 
-/// 辅助函数：获取指定行范围的文本
-pub fn get_lines_text(source_code: &str, start_row: usize, end_row: usize) -> String {
-    source_code
-        .lines()
-        .skip(start_row)
-        .take(end_row - start_row + 1)
-        .collect::<Vec<_>>()
-        .join("\n")
+/// 辅助函数：字符串字面量的内容（去掉引号或 `<>`）
+pub fn string_literal_value(node: Node, source_code: &str) -> String {
+    get_node_text(node, source_code)
+        .trim_matches(|c| matches!(c, '"' | '\'' | '`' | '<' | '>'))
+        .to_string()
+}
+
+/// 辅助函数：按先序收集指定类型的节点（命中的节点不再向下查找）
+pub fn collect_nodes<'a>(root: Node<'a>, kinds: &[&str]) -> Vec<Node<'a>> {
+    let mut found = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if kinds.contains(&node.kind()) {
+            found.push(node);
+            continue;
+        }
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    found
+}
+
+/// 辅助函数：按先序收集指定类型的全部节点（命中节点内部的同类节点也收集）
+pub fn collect_all_nodes<'a>(root: Node<'a>, kinds: &[&str]) -> Vec<Node<'a>> {
+    let mut found = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if kinds.contains(&node.kind()) {
+            found.push(node);
+        }
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    found
+}
+
+/// 辅助函数：调用表达式构成的动态导入，首个参数为不含插值的字符串时记录字面量目标
+pub fn dynamic_import(call: Node, kind: DynamicImportKind, source_code: &str) -> Option<DynamicImport> {
+    let argument = call.child_by_field_name("arguments")?.named_child(0)?;
+    // Python 关键字参数 `import_module(name='pkg')`
+    let argument = match argument.kind() {
+        "keyword_argument" => argument.child_by_field_name("value")?,
+        _ => argument,
+    };
+    let mut cursor = argument.walk();
+    let is_literal = matches!(argument.kind(), "string" | "template_string")
+        && !argument
+            .children(&mut cursor)
+            .any(|child| matches!(child.kind(), "template_substitution" | "interpolation"));
+    Some(DynamicImport {
+        source: is_literal.then(|| string_literal_value(argument, source_code)),
+        expression: get_node_text(argument, source_code).to_string(),
+        kind,
+        range: Range {
+            start: call.start_position().row + 1,
+            end: call.end_position().row + 1,
+        },
+    })
+}
+
+/// 辅助函数：赋值目标（`a.b.c` 形式的点分路径）构成的运行时修改
+///
+/// 路径含 `prototype` 的为原型补丁；单个名字只在是导入的绑定时算重新赋值；
+/// 其余路径的根名字须是导入的绑定（本地对象的成员赋值不算）。
+pub fn modification(path: &str, statement: Node, imports: &[ImportDeclaration]) -> Option<Modification> {
+    let segments: Vec<&str> = path.split('.').map(str::trim).collect();
+    let is_identifier = |s: &&str| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if !segments.iter().all(is_identifier) {
+        return None;
+    }
+    let object = segments[0];
+    let source = imports
+        .iter()
+        .find(|import| {
+            import.namespace_import.as_deref() == Some(object)
+                || import.default_import.as_deref() == Some(object)
+                || import.specifiers.iter().any(|s| s == object)
+        })
+        .map(|import| import.source.clone());
+    let kind = if segments[1..].contains(&"prototype") {
+        ModificationKind::Prototype
+    } else if source.is_none() {
+        return None;
+    } else if segments.len() == 1 {
+        ModificationKind::Reassign
+    } else {
+        ModificationKind::Patch
+    };
+    Some(Modification {
+        target: segments.join("."),
+        object: object.to_string(),
+        source,
+        kind,
+        range: Range {
+            start: statement.start_position().row + 1,
+            end: statement.end_position().row + 1,
+        },
+    })
+}
+
+/// 辅助函数：C / C++ 的 `#include`（条件编译块中的也算）
+#[cfg(any(feature = "c-lang", feature = "cpp"))]
+pub fn include_refs(root: Node, source_code: &str, file_path: &str) -> ModuleRefs {
+    let imports = collect_nodes(root, &["preproc_include"])
+        .into_iter()
+        .filter_map(|node| node.child_by_field_name("path"))
+        .map(|path| ImportDeclaration {
+            source: string_literal_value(path, source_code),
+            file_path: file_path.to_string(),
+            ..Default::default()
+        })
+        .collect();
+    ModuleRefs {
+        imports,
+        ..Default::default()
+    }
+}
+
+/// 辅助函数：获取捕获节点对应的定义节点
+///
+/// 多数 query 捕获的是名字节点（`name:` / `declarator:` 字段），需要向上找到声明本身
+pub fn definition_node(node: Node) -> Node {
+    let mut current = node;
+    while let Some(parent) = current.parent() {
+        let is_name_of_parent = ["name", "declarator"].iter().any(|field| {
+            parent
+                .child_by_field_name(field)
+                .is_some_and(|child| child.id() == current.id())
+        });
+        if !is_name_of_parent {
+            break;
+        }
+        current = parent;
+    }
+    current
+}
+
+/// 辅助函数：获取捕获节点对应实体的名字
+pub fn definition_name(node: Node, source_code: &str) -> Option<String> {
+    let def = definition_node(node);
+    let name_node = if def.id() != node.id() {
+        node
+    } else {
+        def.child_by_field_name("name")?
+    };
+    let name = get_node_text(name_node, source_code)
+        .trim_matches(|c| c == '"' || c == '\'')
+        .to_string();
+    Some(name)
+}
+
+/// 辅助函数：查找最近的指定类型祖先节点
+pub fn find_ancestor<'a>(node: Node<'a>, kinds: &[&str]) -> Option<Node<'a>> {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if kinds.contains(&parent.kind()) {
+            return Some(parent);
+        }
+        current = parent.parent();
+    }
+    None
+}
+
+/// 辅助函数：获取语法树根节点
+pub fn root_node(node: Node) -> Node {
+    let mut current = node;
+    while let Some(parent) = current.parent() {
+        current = parent;
+    }
+    current
+}
+
+/// 辅助函数：按关键字判断可见性（Java / Swift / Solidity / C# 等修饰符风格的语言）
+#[cfg(any(feature = "java", feature = "swift", feature = "php", feature = "solidity"))]
+pub fn visibility_from_keywords(header: &str) -> Option<Visibility> {
+    let words: HashSet<&str> = header
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    if words.contains("private") || words.contains("fileprivate") {
+        Some(Visibility::Private)
+    } else if words.contains("protected") {
+        Some(Visibility::Protected)
+    } else if words.contains("public") || words.contains("open") || words.contains("external") {
+        Some(Visibility::Public)
+    } else if words.contains("internal") {
+        Some(Visibility::Internal)
+    } else {
+        None
+    }
 }
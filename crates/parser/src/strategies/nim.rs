@@ -0,0 +1,176 @@
+use tree_sitter::Node;
+use std::collections::HashSet;
+
+use super::{
+    Capture, ModuleRefs, ParseStrategy, SignatureExtractor, dedup_chunk, definition_name, definition_node,
+    get_node_text, node_chunk,
+};
+use crate::doc_comment::{clean_comment, leading_comment};
+use crate::types::{ImportDeclaration, Visibility};
+
+/// 文档注释节点（`##` 与 `##[ ... ]##`）
+const DOC_COMMENT_KINDS: &[&str] = &["documentation_comment", "block_documentation_comment"];
+
+/// Nim 解析策略（proc / func / method / iterator / template / macro、类型与 import）
+///
+/// 名字后带 `*` 的符号（`proc greet*(name: string)`）对其他模块导出，实体名不含 `*`。
+/// 文档注释 `##` 写在定义体的开头，与 Python docstring 的位置相同。
+pub struct NimStrategy;
+
+impl NimStrategy {
+    /// 定义的名字节点：例程为 `name` 字段，类型声明为 `type_symbol_declaration` 的 `name` 字段
+    fn name_node(def: Node) -> Option<Node> {
+        if def.kind() != "type_declaration" {
+            return def.child_by_field_name("name");
+        }
+        let mut cursor = def.walk();
+        let symbol = def
+            .named_children(&mut cursor)
+            .find(|child| child.kind() == "type_symbol_declaration")?;
+        symbol.child_by_field_name("name")
+    }
+
+    /// 定义体开头的文档注释
+    fn body_doc(def: Node, source_code: &str) -> Option<String> {
+        let body = def.child_by_field_name("body")?;
+        let mut cursor = body.walk();
+        let comments: Vec<&str> = body
+            .named_children(&mut cursor)
+            .take_while(|child| DOC_COMMENT_KINDS.contains(&child.kind()))
+            .map(|child| {
+                let text = get_node_text(child, source_code).trim();
+                text.strip_prefix("##[")
+                    .and_then(|text| text.strip_suffix("]##"))
+                    .unwrap_or(text)
+            })
+            .collect();
+        let text = clean_comment(&comments.join("\n"));
+        (!text.is_empty()).then_some(text)
+    }
+
+    /// import 列表中的一项：`std/[os, strutils]` 展开为 `std/os`、`std/strutils`，`json as j` 带别名
+    fn module_items(text: &str) -> Vec<(String, Option<String>)> {
+        let (path, alias) = match text.split_once(" as ") {
+            Some((path, alias)) => (path.trim(), Some(alias.trim().to_string())),
+            None => (text.trim(), None),
+        };
+        let path = path.trim_matches('"');
+        match path.split_once("/[") {
+            Some((prefix, rest)) => rest
+                .trim_end_matches(']')
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| (format!("{}/{}", prefix.trim(), name), None))
+                .collect(),
+            None => vec![(path.to_string(), alias)],
+        }
+    }
+
+    /// 语句中列出的各项（`expression_list` 的成员，或直接的子节点）
+    fn list_items<'t>(node: Node<'t>) -> Vec<Node<'t>> {
+        let mut cursor = node.walk();
+        node.named_children(&mut cursor)
+            .flat_map(|child| {
+                if child.kind() == "expression_list" {
+                    let mut cursor = child.walk();
+                    child.named_children(&mut cursor).collect()
+                } else {
+                    vec![child]
+                }
+            })
+            .filter(|child| !child.kind().contains("comment"))
+            .collect()
+    }
+}
+
+impl ParseStrategy for NimStrategy {
+    fn parse_capture(
+        &self,
+        capture: Capture,
+        source_code: &str,
+        processed_chunks: &mut HashSet<String>,
+    ) -> Option<String> {
+        let node = capture.node;
+        if capture.name.contains("comment") {
+            return Some(get_node_text(node, source_code).trim().to_string());
+        }
+        if capture.name.contains("definition.type") || capture.name.contains("definition.import") {
+            return node_chunk(node, source_code, processed_chunks);
+        }
+        // 例程只取签名（去掉函数体前的 `=`）
+        let signature = SignatureExtractor::extract(node, source_code);
+        dedup_chunk(signature.strip_suffix('=').unwrap_or(signature).trim_end(), processed_chunks)
+    }
+
+    fn entity_name(&self, node: Node, source_code: &str) -> Option<String> {
+        let def = definition_node(node);
+        let name = match Self::name_node(def) {
+            Some(name) => get_node_text(name, source_code).to_string(),
+            None => definition_name(node, source_code)?,
+        };
+        Some(name.trim_end_matches('*').trim().trim_matches('`').to_string())
+    }
+
+    fn classify_visibility(&self, node: Node, _source_code: &str) -> (bool, Option<Visibility>) {
+        let def = definition_node(node);
+        match Self::name_node(def) {
+            Some(name) if name.kind() == "exported_symbol" => (true, Some(Visibility::Public)),
+            Some(_) => (false, Some(Visibility::Private)),
+            None => (false, None),
+        }
+    }
+
+    fn doc_comment(&self, node: Node, source_code: &str) -> Option<String> {
+        let def = definition_node(node);
+        Self::body_doc(def, source_code).or_else(|| leading_comment(def, source_code))
+    }
+
+    /// `import` / `include` 的每一项为一个来源；`from m import a, b` 记录具名导入
+    fn extract_module_refs(&self, root: Node, source_code: &str, file_path: &str) -> ModuleRefs {
+        let mut refs = ModuleRefs::default();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            match node.kind() {
+                "import_statement" | "include_statement" => {
+                    for item in Self::list_items(node) {
+                        let text = get_node_text(item, source_code);
+                        // `import os except getEnv` 只取模块名
+                        let text = text.split(" except ").next().unwrap_or(text);
+                        for (source, namespace_import) in Self::module_items(text) {
+                            refs.imports.push(ImportDeclaration {
+                                source,
+                                namespace_import,
+                                file_path: file_path.to_string(),
+                                ..Default::default()
+                            });
+                        }
+                    }
+                }
+                "import_from_statement" => {
+                    let items = Self::list_items(node);
+                    let Some((module, names)) = items.split_first() else {
+                        continue;
+                    };
+                    let specifiers: Vec<String> = names
+                        .iter()
+                        .map(|name| get_node_text(*name, source_code).trim().to_string())
+                        .collect();
+                    refs.imports.push(ImportDeclaration {
+                        source: get_node_text(*module, source_code).trim_matches('"').to_string(),
+                        is_wildcard: specifiers.iter().any(|name| name == "*"),
+                        specifiers: specifiers.into_iter().filter(|name| name != "*").collect(),
+                        file_path: file_path.to_string(),
+                        ..Default::default()
+                    });
+                }
+                _ => {
+                    let mut cursor = node.walk();
+                    let children: Vec<_> = node.named_children(&mut cursor).collect();
+                    stack.extend(children.into_iter().rev());
+                }
+            }
+        }
+        refs
+    }
+}
@@ -0,0 +1,157 @@
+use tree_sitter::Node;
+use std::collections::HashSet;
+
+use super::{
+    CallSyntax, Capture, ModuleRefs, ParseStrategy, collect_nodes, declaration_header, dedup_chunk, definition_node,
+    get_node_text, visibility_from_keywords,
+};
+use crate::types::{ImportDeclaration, Visibility};
+
+/// 调用关系：函数、实例方法、静态方法调用（闭包中的调用归属外层函数）
+static CALL_SYNTAX: CallSyntax = CallSyntax {
+    calls: &[
+        ("function_call_expression", "function"),
+        ("member_call_expression", "name"),
+        ("nullsafe_member_call_expression", "name"),
+        ("scoped_call_expression", "name"),
+    ],
+    callers: &["function_definition", "method_declaration"],
+};
+
+/// PHP 解析策略
+pub struct PhpStrategy;
+
+enum CaptureType {
+    Comment,
+    Namespace,
+    Use,
+    Type,
+    Function,
+}
+
+/// 类型声明（类、接口、trait、枚举）
+const TYPE_KINDS: &[&str] = &["class_declaration", "interface_declaration", "trait_declaration", "enum_declaration"];
+
+impl PhpStrategy {
+    fn get_capture_type(&self, name: &str) -> Option<CaptureType> {
+        if name.contains("comment") {
+            Some(CaptureType::Comment)
+        } else if name.contains("definition.namespace") {
+            Some(CaptureType::Namespace)
+        } else if name.contains("definition.use") {
+            Some(CaptureType::Use)
+        } else if ["class", "interface", "trait", "enum"]
+            .iter()
+            .any(|kind| name.contains(&format!("definition.{}", kind)))
+        {
+            Some(CaptureType::Type)
+        } else if name.contains("definition.function") || name.contains("definition.method") {
+            Some(CaptureType::Function)
+        } else {
+            None
+        }
+    }
+
+    /// `use` 子句中的完整名字（去掉前导 `\`）
+    fn clause_name(clause: Node, source_code: &str) -> Option<String> {
+        let mut cursor = clause.walk();
+        let name = clause
+            .named_children(&mut cursor)
+            .find(|child| matches!(child.kind(), "name" | "qualified_name"))?;
+        Some(get_node_text(name, source_code).trim_start_matches('\\').to_string())
+    }
+}
+
+impl ParseStrategy for PhpStrategy {
+    fn parse_capture(
+        &self,
+        capture: Capture,
+        source_code: &str,
+        processed_chunks: &mut HashSet<String>,
+    ) -> Option<String> {
+        let def = definition_node(capture.node);
+        let text = match self.get_capture_type(capture.name)? {
+            // 类型与带定义体的命名空间只保留声明头，成员各自作为实体提取
+            CaptureType::Type | CaptureType::Namespace => declaration_header(def, source_code).trim().to_string(),
+            // 函数与方法保留完整内容（含函数体），供调用关系提取
+            CaptureType::Comment | CaptureType::Use | CaptureType::Function => {
+                get_node_text(def, source_code).trim().to_string()
+            }
+        };
+
+        dedup_chunk(text, processed_chunks)
+    }
+
+    fn extract_module_refs(&self, root: Node, source_code: &str, file_path: &str) -> ModuleRefs {
+        let mut refs = ModuleRefs::default();
+        for declaration in collect_nodes(root, &["namespace_use_declaration"]) {
+            // 分组导入 `use App\Models\{User, Post};` 的公共前缀
+            let mut cursor = declaration.walk();
+            let group_prefix = declaration
+                .named_children(&mut cursor)
+                .find(|child| child.kind() == "namespace_name")
+                .map(|prefix| get_node_text(prefix, source_code).trim_start_matches('\\').to_string());
+            let clauses = match declaration.child_by_field_name("body") {
+                Some(group) => collect_nodes(group, &["namespace_use_clause"]),
+                None => collect_nodes(declaration, &["namespace_use_clause"]),
+            };
+
+            // 按命名空间合并：同一来源的多个名字记为一条导入
+            let mut imports: Vec<ImportDeclaration> = Vec::new();
+            for clause in clauses {
+                let Some(name) = Self::clause_name(clause, source_code) else {
+                    continue;
+                };
+                let full = match &group_prefix {
+                    Some(prefix) => format!("{}\\{}", prefix, name),
+                    None => name,
+                };
+                let (source, member) = match full.rsplit_once('\\') {
+                    Some((namespace, member)) => (namespace.to_string(), Some(member.to_string())),
+                    None => (full.clone(), None),
+                };
+                let index = match imports.iter().position(|i| i.source == source) {
+                    Some(index) => index,
+                    None => {
+                        imports.push(ImportDeclaration {
+                            source: source.clone(),
+                            file_path: file_path.to_string(),
+                            ..Default::default()
+                        });
+                        imports.len() - 1
+                    }
+                };
+                match member {
+                    Some(member) => imports[index].specifiers.push(member),
+                    // `use Foo;` 导入全局命名空间中的名字
+                    None => imports[index].namespace_import = Some(source),
+                }
+            }
+            refs.imports.extend(imports);
+        }
+        refs
+    }
+
+    fn classify_visibility(&self, node: Node, source_code: &str) -> (bool, Option<Visibility>) {
+        let def = definition_node(node);
+        match def.kind() {
+            // 类型与函数在命名空间中全局可见
+            kind if TYPE_KINDS.contains(&kind) || kind == "function_definition" => (true, Some(Visibility::Public)),
+            // 方法默认 public
+            "method_declaration" => {
+                let mut cursor = def.walk();
+                let visibility = def
+                    .children(&mut cursor)
+                    .find(|child| child.kind() == "visibility_modifier")
+                    .and_then(|modifier| visibility_from_keywords(get_node_text(modifier, source_code)))
+                    .unwrap_or(Visibility::Public);
+                (matches!(visibility, Visibility::Public), Some(visibility))
+            }
+            _ => (false, None),
+        }
+    }
+
+    fn call_syntax(&self) -> Option<&'static CallSyntax> {
+        Some(&CALL_SYNTAX)
+    }
+}
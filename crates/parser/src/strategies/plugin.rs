@@ -0,0 +1,54 @@
+use tree_sitter::Node;
+use std::collections::HashSet;
+use std::ffi::CString;
+
+use super::{Capture, ParseStrategy, definition_node, get_node_text, node_chunk};
+use crate::plugin::{
+    VisibilityCallback, VISIBILITY_INTERNAL, VISIBILITY_PRIVATE, VISIBILITY_PROTECTED, VISIBILITY_PUBLIC,
+};
+use crate::types::Visibility;
+
+/// 语言包插件的解析策略：`definition.*` 与注释捕获按定义节点原文提取，可见性交给插件回调
+pub struct PluginStrategy {
+    visibility: Option<VisibilityCallback>,
+}
+
+impl PluginStrategy {
+    pub fn new(visibility: Option<VisibilityCallback>) -> Self {
+        Self { visibility }
+    }
+}
+
+impl ParseStrategy for PluginStrategy {
+    fn parse_capture(
+        &self,
+        capture: Capture,
+        source_code: &str,
+        processed_chunks: &mut HashSet<String>,
+    ) -> Option<String> {
+        // 其余捕获（名字节点、谓词用的辅助捕获）不产生实体
+        if !capture.name.starts_with("definition.") && !capture.name.contains("comment") {
+            return None;
+        }
+        node_chunk(definition_node(capture.node), source_code, processed_chunks)
+    }
+
+    fn classify_visibility(&self, node: Node, source_code: &str) -> (bool, Option<Visibility>) {
+        let Some(callback) = self.visibility else {
+            return (false, None);
+        };
+        let def = definition_node(node);
+        let Ok(kind) = CString::new(def.kind()) else {
+            return (false, None);
+        };
+        let text = get_node_text(def, source_code);
+        // SAFETY: 参数在调用期间有效，回调按 ABI 约定不保留指针
+        match unsafe { callback(kind.as_ptr(), text.as_ptr(), text.len()) } {
+            VISIBILITY_PUBLIC => (true, Some(Visibility::Public)),
+            VISIBILITY_PRIVATE => (false, Some(Visibility::Private)),
+            VISIBILITY_PROTECTED => (false, Some(Visibility::Protected)),
+            VISIBILITY_INTERNAL => (false, Some(Visibility::Internal)),
+            _ => (false, None),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use tree_sitter::Node;
+use std::collections::HashSet;
+
+use super::{Capture, ModuleRefs, ParseStrategy, dedup_chunk, get_node_text, string_literal_value};
+use crate::types::{ExportDeclaration, ImportDeclaration, Visibility};
+
+/// Protobuf 解析策略（消息、枚举、服务与 rpc）
+pub struct ProtobufStrategy;
+
+impl ProtobufStrategy {
+    /// 定义的名字节点：`message_name` / `enum_name` / `service_name` / `rpc_name`，package 取完整的点分名
+    fn name_node(node: Node) -> Option<Node> {
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        children
+            .into_iter()
+            .find(|child| child.kind().ends_with("_name") || child.kind() == "full_ident")
+    }
+}
+
+impl ParseStrategy for ProtobufStrategy {
+    fn parse_capture(
+        &self,
+        capture: Capture,
+        source_code: &str,
+        processed_chunks: &mut HashSet<String>,
+    ) -> Option<String> {
+        // 完整定义（消息包含字段，服务包含 rpc 列表）
+        let text = get_node_text(capture.node, source_code).trim().to_string();
+
+        dedup_chunk(text, processed_chunks)
+    }
+
+    fn entity_name(&self, node: Node, source_code: &str) -> Option<String> {
+        Some(get_node_text(Self::name_node(node)?, source_code).to_string())
+    }
+
+    fn classify_visibility(&self, node: Node, _source_code: &str) -> (bool, Option<Visibility>) {
+        match node.kind() {
+            // 消息与服务对导入该文件的 .proto 和生成代码都可见
+            "message" | "enum" | "service" | "rpc" => (true, Some(Visibility::Public)),
+            _ => (false, None),
+        }
+    }
+
+    fn extract_module_refs(&self, root: Node, source_code: &str, file_path: &str) -> ModuleRefs {
+        let mut refs = ModuleRefs::default();
+        let mut cursor = root.walk();
+        for statement in root.children(&mut cursor).filter(|n| n.kind() == "import") {
+            let mut import_cursor = statement.walk();
+            let children: Vec<Node> = statement.children(&mut import_cursor).collect();
+            let Some(path) = children.iter().find(|child| child.kind() == "string") else {
+                continue;
+            };
+            let source = string_literal_value(*path, source_code);
+            // `import public` 把被导入文件的定义转发给本文件的导入方
+            if children.iter().any(|child| child.kind() == "public") {
+                refs.exports.push(ExportDeclaration {
+                    source: Some(source.clone()),
+                    file_path: file_path.to_string(),
+                    is_wildcard: true,
+                    ..Default::default()
+                });
+            }
+            refs.imports.push(ImportDeclaration {
+                source,
+                file_path: file_path.to_string(),
+                ..Default::default()
+            });
+        }
+        refs
+    }
+}
@@ -1,7 +1,22 @@
 use tree_sitter::Node;
 use std::collections::HashSet;
 
-use super::{Capture, ParseStrategy, get_node_text, get_lines_text};
+use super::{
+    CallSyntax, Capture, ModuleRefs, ParseStrategy, SignatureExtractor, collect_all_nodes, collect_nodes,
+    dedup_chunk, definition_name, definition_node, dynamic_import, find_ancestor, get_node_text, lines_chunk,
+    modification, node_chunk, root_node, string_literal_value,
+};
+use crate::doc_comment::{leading_comment, trim_lines};
+use crate::types::{DynamicImportKind, ExportDeclaration, ImportDeclaration, Modification, Visibility};
+
+/// 动态导入的调用（`from importlib import import_module` 后可直接调用）
+const IMPORTLIB_CALLS: &[&str] = &["importlib.import_module", "import_module", "__import__"];
+
+/// 调用关系（lambda 中的调用归属外层函数）
+static CALL_SYNTAX: CallSyntax = CallSyntax {
+    calls: &[("call", "function")],
+    callers: &["function_definition"],
+};
 
 /// Python 解析策略（基于 repomix 的实现）
 pub struct PythonStrategy;
@@ -39,37 +54,106 @@ impl PythonStrategy {
         source_code: &str,
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
-        let start_row = node.start_position().row;
-        let end_row = node.end_position().row;
-        
-        // 查找签名结束位置（冒号之前）
-        let signature_end = self.find_signature_end(source_code, start_row, end_row);
-        
-        let lines: Vec<&str> = source_code.lines().collect();
-        let signature: String = lines[start_row..=signature_end]
-            .iter()
-            .map(|l| l.trim())
-            .collect::<Vec<_>>()
-            .join("\n");
-        
-        if processed_chunks.contains(&signature) {
+        // 函数签名（不包括函数体），按语法树定位函数体
+        SignatureExtractor::chunk(node, source_code, processed_chunks)
+    }
+    
+    /// 解析模块级 `__all__ = [...]`，不存在时返回 None
+    /// `import a.b as c, d`：每个模块一条声明，绑定名记为命名空间导入
+    fn import_statement(&self, node: Node, source_code: &str, file_path: &str) -> Vec<ImportDeclaration> {
+        let mut imports = Vec::new();
+        let mut cursor = node.walk();
+        for name in node.children_by_field_name("name", &mut cursor) {
+            let (module, alias) = match name.kind() {
+                "aliased_import" => (name.child_by_field_name("name"), name.child_by_field_name("alias")),
+                _ => (Some(name), None),
+            };
+            let Some(module) = module else {
+                continue;
+            };
+            let source = get_node_text(module, source_code).to_string();
+            // `import a.b` 绑定的是顶层包 `a`
+            let binding = match alias {
+                Some(alias) => get_node_text(alias, source_code).to_string(),
+                None => source.split('.').next().unwrap_or_default().to_string(),
+            };
+            imports.push(ImportDeclaration {
+                source,
+                file_path: file_path.to_string(),
+                namespace_import: Some(binding),
+                ..Default::default()
+            });
+        }
+        imports
+    }
+
+    /// `setattr(obj, 'name', value)` 修改的目标（属性名须为字面量）
+    fn setattr_target(&self, call: Node, source_code: &str) -> Option<String> {
+        let function = call.child_by_field_name("function")?;
+        if get_node_text(function, source_code) != "setattr" {
             return None;
         }
-        
-        processed_chunks.insert(signature.clone());
-        Some(signature)
+        let arguments = call.child_by_field_name("arguments")?;
+        let (object, name) = (arguments.named_child(0)?, arguments.named_child(1)?);
+        if name.kind() != "string" {
+            return None;
+        }
+        Some(format!("{}.{}", get_node_text(object, source_code), string_literal_value(name, source_code)))
     }
-    
-    fn find_signature_end(&self, source_code: &str, start: usize, end: usize) -> usize {
-        let lines: Vec<&str> = source_code.lines().collect();
-        
-        for i in start..=end.min(lines.len() - 1) {
-            if lines[i].trim_end().ends_with(':') {
-                return i;
+
+    /// `from .mod import a, b as c` / `from mod import *`
+    fn import_from_statement(&self, node: Node, source_code: &str, file_path: &str) -> Option<ImportDeclaration> {
+        let module = node.child_by_field_name("module_name")?;
+        let mut import = ImportDeclaration {
+            source: get_node_text(module, source_code).to_string(),
+            file_path: file_path.to_string(),
+            ..Default::default()
+        };
+
+        let mut cursor = node.walk();
+        for name in node.children_by_field_name("name", &mut cursor) {
+            let name = match name.kind() {
+                "aliased_import" => name.child_by_field_name("name"),
+                _ => Some(name),
+            };
+            if let Some(name) = name {
+                import.specifiers.push(get_node_text(name, source_code).to_string());
             }
         }
-        
-        start
+        import.is_wildcard = !collect_nodes(node, &["wildcard_import"]).is_empty();
+
+        Some(import)
+    }
+
+    fn dunder_all(&self, root: Node, source_code: &str) -> Option<Vec<String>> {
+        let mut cursor = root.walk();
+        for statement in root.children(&mut cursor) {
+            if statement.kind() != "expression_statement" {
+                continue;
+            }
+            let Some(assignment) = statement.named_child(0).filter(|n| n.kind() == "assignment") else {
+                continue;
+            };
+            let is_all = assignment
+                .child_by_field_name("left")
+                .is_some_and(|left| get_node_text(left, source_code) == "__all__");
+            if !is_all {
+                continue;
+            }
+            
+            let mut names = Vec::new();
+            if let Some(right) = assignment.child_by_field_name("right") {
+                let mut inner = right.walk();
+                for item in right.named_children(&mut inner) {
+                    if item.kind() == "string" {
+                        let text = get_node_text(item, source_code);
+                        names.push(text.trim_matches(|c| c == '"' || c == '\'').to_string());
+                    }
+                }
+            }
+            return Some(names);
+        }
+        None
     }
 }
 
@@ -93,37 +177,128 @@ impl ParseStrategy for PythonStrategy {
         // 类 - 提取完整定义（包括方法）
         if capture_types.iter().any(|t| matches!(t, CaptureType::Class)) {
             if let Some(parent) = node.parent() {
-                let start_row = parent.start_position().row;
-                let end_row = parent.end_position().row;
-                let full_text = get_lines_text(source_code, start_row, end_row);
-                let cleaned = full_text.trim().to_string();
-                
-                if processed_chunks.contains(&cleaned) {
-                    return None;
-                }
-                
-                processed_chunks.insert(cleaned.clone());
-                return Some(cleaned);
+                return lines_chunk(parent, source_code, processed_chunks);
             }
         }
         
         // 导入（直接提取）
         if capture_types.iter().any(|t| matches!(t, CaptureType::Import)) {
-            let text = get_node_text(node, source_code).trim().to_string();
-            
-            if processed_chunks.contains(&text) {
-                return None;
-            }
-            
-            processed_chunks.insert(text.clone());
-            return Some(text);
+            return node_chunk(node, source_code, processed_chunks);
         }
         
         // 注释
         if capture_types.iter().any(|t| matches!(t, CaptureType::Comment)) {
-            return Some(get_node_text(node, source_code).trim().to_string());
+            return dedup_chunk(get_node_text(node, source_code).trim(), processed_chunks);
         }
         
         None
     }
+    
+    fn extract_module_refs(&self, root: Node, source_code: &str, file_path: &str) -> ModuleRefs {
+        let mut refs = ModuleRefs::default();
+        // 函数内部的延迟导入同样是依赖
+        for node in collect_nodes(root, &["import_statement", "import_from_statement"]) {
+            match node.kind() {
+                "import_statement" => refs.imports.extend(self.import_statement(node, source_code, file_path)),
+                "import_from_statement" => refs.imports.extend(self.import_from_statement(node, source_code, file_path)),
+                _ => {}
+            }
+        }
+        // 模块的导出列表只能由 `__all__` 显式声明
+        if let Some(all) = self.dunder_all(root, source_code) {
+            refs.exports.push(ExportDeclaration {
+                specifiers: all,
+                file_path: file_path.to_string(),
+                ..Default::default()
+            });
+        }
+        for call in collect_all_nodes(root, &["call"]) {
+            let is_importlib = call
+                .child_by_field_name("function")
+                .is_some_and(|function| IMPORTLIB_CALLS.contains(&get_node_text(function, source_code)));
+            if is_importlib {
+                refs.dynamic_imports
+                    .extend(dynamic_import(call, DynamicImportKind::Importlib, source_code));
+            }
+        }
+        refs
+    }
+
+    fn modifications(&self, root: Node, source_code: &str, imports: &[ImportDeclaration]) -> Vec<Modification> {
+        let mut modifications = Vec::new();
+        let mut cursor = root.walk();
+        for statement in root.children(&mut cursor).filter(|n| n.kind() == "expression_statement") {
+            let Some(expression) = statement.named_child(0) else {
+                continue;
+            };
+            let path = match expression.kind() {
+                "assignment" => expression
+                    .child_by_field_name("left")
+                    .filter(|left| matches!(left.kind(), "identifier" | "attribute"))
+                    .map(|left| get_node_text(left, source_code).to_string()),
+                "call" => self.setattr_target(expression, source_code),
+                _ => None,
+            };
+            modifications.extend(path.and_then(|path| modification(&path, statement, imports)));
+        }
+        modifications
+    }
+
+    fn doc_comment(&self, node: Node, source_code: &str) -> Option<String> {
+        // docstring 优先，没有时退回定义前的 `#` 注释
+        let def = definition_node(node);
+        docstring(def, source_code).or_else(|| leading_comment(def, source_code))
+    }
+    
+    fn classify_visibility(&self, node: Node, source_code: &str) -> (bool, Option<Visibility>) {
+        let def = definition_node(node);
+        if !matches!(def.kind(), "function_definition" | "class_definition") {
+            return (false, None);
+        }
+        let Some(name) = definition_name(node, source_code) else {
+            return (false, None);
+        };
+        
+        // _name / __name 为私有约定，__dunder__ 仍是公开的
+        let is_dunder = name.starts_with("__") && name.ends_with("__");
+        let visibility = if name.starts_with('_') && !is_dunder {
+            Visibility::Private
+        } else {
+            Visibility::Public
+        };
+        
+        // 嵌套在函数内部的定义不属于模块 API
+        if find_ancestor(def, &["function_definition"]).is_some() {
+            return (false, Some(visibility));
+        }
+        
+        // 方法：所在类导出且自身公开
+        if let Some(class) = find_ancestor(def, &["class_definition"]) {
+            let (class_exported, _) = self.classify_visibility(class, source_code);
+            return (class_exported && matches!(visibility, Visibility::Public), Some(visibility));
+        }
+        
+        // 模块级：有 __all__ 时以其为准
+        let exported = match self.dunder_all(root_node(def), source_code) {
+            Some(all) => all.contains(&name),
+            None => matches!(visibility, Visibility::Public),
+        };
+        (exported, Some(visibility))
+    }
+    
+    fn call_syntax(&self) -> Option<&'static CallSyntax> {
+        Some(&CALL_SYNTAX)
+    }
+}
+
+/// 定义体第一条语句为字符串时即为 docstring（去掉引号与字符串前缀）
+fn docstring(def: Node, source_code: &str) -> Option<String> {
+    let body = def.child_by_field_name("body")?;
+    let statement = body.named_child(0).filter(|n| n.kind() == "expression_statement")?;
+    let string = statement.named_child(0).filter(|n| n.kind() == "string")?;
+    let text = get_node_text(string, source_code).trim_start_matches(|c: char| "rRuUbBfF".contains(c));
+    let quote = ["\"\"\"", "'''", "\"", "'"].into_iter().find(|q| text.starts_with(q))?;
+    let content = text.strip_prefix(quote)?.strip_suffix(quote)?;
+    let doc = trim_lines(content.lines());
+    (!doc.is_empty()).then_some(doc)
 }
@@ -1,7 +1,30 @@
 use tree_sitter::Node;
 use std::collections::HashSet;
 
-use super::{Capture, ParseStrategy, get_node_text, get_lines_text};
+use super::{
+    CallSyntax, Capture, ModuleRefs, ParseStrategy, SignatureExtractor, collect_nodes, definition_node, get_node_text,
+    lines_chunk, node_chunk,
+};
+use crate::types::{ExportDeclaration, ImportDeclaration, Visibility};
+
+/// 调用关系：函数、关联函数与方法调用（闭包中的调用归属外层函数，宏不计）
+static CALL_SYNTAX: CallSyntax = CallSyntax {
+    calls: &[("call_expression", "function")],
+    callers: &["function_item"],
+};
+
+/// 可以用 `pub` 导出的顶层条目
+const PUBLIC_ITEM_KINDS: &[&str] = &[
+    "function_item",
+    "struct_item",
+    "enum_item",
+    "union_item",
+    "trait_item",
+    "mod_item",
+    "type_item",
+    "const_item",
+    "static_item",
+];
 
 /// Rust 解析策略
 pub struct RustStrategy;
@@ -17,6 +40,58 @@ enum CaptureType {
     Use,
 }
 
+/// use 树展开后的一项：(路径前缀, 名字, 别名)，名字为 None 表示 `prefix::*`
+type UseItem = (String, Option<String>, Option<String>);
+
+fn join_path(prefix: &str, path: &str) -> String {
+    if prefix.is_empty() {
+        path.to_string()
+    } else {
+        format!("{}::{}", prefix, path)
+    }
+}
+
+/// 展开 use 树（`a::{b, c::d as e, f::*}`）
+fn flatten_use_tree(node: Node, prefix: &str, source_code: &str, items: &mut Vec<UseItem>) {
+    match node.kind() {
+        "scoped_identifier" => {
+            let path = node.child_by_field_name("path").map(|p| get_node_text(p, source_code));
+            let name = node.child_by_field_name("name").map(|n| get_node_text(n, source_code).to_string());
+            items.push((join_path(prefix, path.unwrap_or_default()), name, None));
+        }
+        "use_as_clause" => {
+            let (Some(path), Some(alias)) = (node.child_by_field_name("path"), node.child_by_field_name("alias")) else {
+                return;
+            };
+            let start = items.len();
+            flatten_use_tree(path, prefix, source_code, items);
+            if let Some(item) = items.get_mut(start) {
+                item.2 = Some(get_node_text(alias, source_code).to_string());
+            }
+        }
+        "use_wildcard" => {
+            let path = node.named_child(0).map(|p| get_node_text(p, source_code)).unwrap_or_default();
+            items.push((join_path(prefix, path), None, None));
+        }
+        "scoped_use_list" => {
+            let prefix = match node.child_by_field_name("path") {
+                Some(path) => join_path(prefix, get_node_text(path, source_code)),
+                None => prefix.to_string(),
+            };
+            if let Some(list) = node.child_by_field_name("list") {
+                flatten_use_tree(list, &prefix, source_code, items);
+            }
+        }
+        "use_list" => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                flatten_use_tree(child, prefix, source_code, items);
+            }
+        }
+        _ => items.push((prefix.to_string(), Some(get_node_text(node, source_code).to_string()), None)),
+    }
+}
+
 impl RustStrategy {
     fn get_capture_type(&self, name: &str) -> Vec<CaptureType> {
         let mut types = Vec::new();
@@ -55,34 +130,8 @@ impl RustStrategy {
         source_code: &str,
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
-        let start_row = node.start_position().row;
-        let end_row = node.end_position().row;
-        
-        // 查找签名结束位置（{ 之前）
-        let signature_end = self.find_brace_start(source_code, start_row, end_row);
-        let signature = get_lines_text(source_code, start_row, signature_end);
-        
-        // 移除 { 及之后的内容
-        let cleaned = signature.split('{').next()?.trim().to_string();
-        
-        if processed_chunks.contains(&cleaned) {
-            return None;
-        }
-        
-        processed_chunks.insert(cleaned.clone());
-        Some(cleaned)
-    }
-    
-    fn find_brace_start(&self, source_code: &str, start: usize, end: usize) -> usize {
-        let lines: Vec<&str> = source_code.lines().collect();
-        
-        for i in start..=end.min(lines.len() - 1) {
-            if lines[i].contains('{') {
-                return i;
-            }
-        }
-        
-        start
+        // 函数签名（不包括函数体），按语法树定位函数体
+        SignatureExtractor::chunk(node, source_code, processed_chunks)
     }
 }
 
@@ -108,28 +157,137 @@ impl ParseStrategy for RustStrategy {
             matches!(t, CaptureType::Struct | CaptureType::Enum | CaptureType::Trait | CaptureType::Impl)
         }) {
             if let Some(parent) = node.parent() {
-                let start_row = parent.start_position().row;
-                let end_row = parent.end_position().row;
-                let full_text = get_lines_text(source_code, start_row, end_row);
-                let cleaned = full_text.trim().to_string();
-                
-                if processed_chunks.contains(&cleaned) {
-                    return None;
-                }
-                
-                processed_chunks.insert(cleaned.clone());
-                return Some(cleaned);
+                return lines_chunk(parent, source_code, processed_chunks);
             }
         }
         
         // Mod, Use - 直接提取
-        let text = get_node_text(node, source_code).trim().to_string();
+        node_chunk(node, source_code, processed_chunks)
+    }
+    
+    fn extract_module_refs(&self, root: Node, source_code: &str, file_path: &str) -> ModuleRefs {
+        let mut refs = ModuleRefs::default();
+        for node in collect_nodes(root, &["use_declaration", "extern_crate_declaration"]) {
+            if node.kind() == "extern_crate_declaration" {
+                let Some(name) = node.child_by_field_name("name") else {
+                    continue;
+                };
+                let name = get_node_text(name, source_code).to_string();
+                let alias = node.child_by_field_name("alias").map(|a| get_node_text(a, source_code).to_string());
+                refs.imports.push(ImportDeclaration {
+                    namespace_import: Some(alias.unwrap_or_else(|| name.clone())),
+                    source: name,
+                    file_path: file_path.to_string(),
+                    ..Default::default()
+                });
+                continue;
+            }
+
+            let Some(argument) = node.child_by_field_name("argument") else {
+                continue;
+            };
+            let mut items = Vec::new();
+            flatten_use_tree(argument, "", source_code, &mut items);
+
+            // 按路径前缀合并：`use a::{b, c}` 记为一条来源为 `a` 的导入
+            let mut imports: Vec<ImportDeclaration> = Vec::new();
+            for (prefix, name, _) in &items {
+                // (来源, 具名成员)；成员为 None 时表示导入模块本身（`use serde;` / `a::{self}`）
+                let (source, member) = match name.as_deref() {
+                    Some(name) if prefix.is_empty() => (name.to_string(), None),
+                    Some("self") => (prefix.clone(), None),
+                    Some(name) => (prefix.clone(), Some(name)),
+                    None => (prefix.clone(), None),
+                };
+                let index = match imports.iter().position(|i| i.source == source) {
+                    Some(index) => index,
+                    None => {
+                        imports.push(ImportDeclaration {
+                            source: source.clone(),
+                            file_path: file_path.to_string(),
+                            ..Default::default()
+                        });
+                        imports.len() - 1
+                    }
+                };
+                let import = &mut imports[index];
+                match (member, name) {
+                    (Some(member), _) => import.specifiers.push(member.to_string()),
+                    (None, None) => import.is_wildcard = true,
+                    (None, Some(_)) => import.namespace_import = source.rsplit("::").next().map(str::to_string),
+                }
+            }
+            refs.imports.extend(imports);
+
+            // 顶层的 `pub use` 是重新导出
+            let is_pub = node
+                .named_child(0)
+                .is_some_and(|c| c.kind() == "visibility_modifier" && get_node_text(c, source_code) == "pub");
+            if is_pub && node.parent().is_some_and(|p| p.kind() == "source_file") {
+                for (prefix, name, alias) in items {
+                    let (source, exported) = match name {
+                        Some(name) if prefix.is_empty() => (name.clone(), Some(alias.unwrap_or(name))),
+                        Some(name) => (prefix, Some(alias.unwrap_or(name))),
+                        None => (prefix, None),
+                    };
+                    refs.exports.push(ExportDeclaration {
+                        is_wildcard: exported.is_none(),
+                        specifiers: exported.into_iter().collect(),
+                        file_path: file_path.to_string(),
+                        source: Some(source),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
         
-        if processed_chunks.contains(&text) {
-            return None;
+        // 顶层的 `pub` 条目（`pub(crate)` 等受限可见性不算）
+        let mut cursor = root.walk();
+        let public_items: Vec<String> = root
+            .children(&mut cursor)
+            .filter(|item| PUBLIC_ITEM_KINDS.contains(&item.kind()))
+            .filter(|item| {
+                let mut cursor = item.walk();
+                let modifier = item.children(&mut cursor).find(|child| child.kind() == "visibility_modifier");
+                modifier.is_some_and(|m| get_node_text(m, source_code) == "pub")
+            })
+            .filter_map(|item| item.child_by_field_name("name"))
+            .map(|name| get_node_text(name, source_code).to_string())
+            .collect();
+        if !public_items.is_empty() {
+            refs.exports.push(ExportDeclaration {
+                specifiers: public_items,
+                file_path: file_path.to_string(),
+                ..Default::default()
+            });
         }
+        refs
+    }
+
+    fn classify_visibility(&self, node: Node, source_code: &str) -> (bool, Option<Visibility>) {
+        let def = definition_node(node);
+        if !matches!(
+            def.kind(),
+            "function_item" | "struct_item" | "enum_item" | "trait_item" | "mod_item" | "type_item" | "const_item" | "static_item"
+        ) {
+            return (false, None);
+        }
+        
+        let mut cursor = def.walk();
+        let modifier = def
+            .children(&mut cursor)
+            .find(|child| child.kind() == "visibility_modifier")
+            .map(|child| get_node_text(child, source_code).replace(' ', ""));
         
-        processed_chunks.insert(text.clone());
-        Some(text)
+        match modifier.as_deref() {
+            Some("pub") => (true, Some(Visibility::Public)),
+            // pub(crate) / pub(super) / pub(in path)
+            Some(_) => (false, Some(Visibility::Internal)),
+            None => (false, Some(Visibility::Private)),
+        }
+    }
+    
+    fn call_syntax(&self) -> Option<&'static CallSyntax> {
+        Some(&CALL_SYNTAX)
     }
 }
@@ -1,7 +1,11 @@
 use tree_sitter::Node;
 use std::collections::HashSet;
 
-use super::{Capture, ParseStrategy, get_node_text, get_lines_text};
+use super::{
+    Capture, HeaderExtractor, ParseStrategy, SignatureExtractor, declaration_header, definition_node, node_chunk,
+    visibility_from_keywords,
+};
+use crate::types::Visibility;
 
 /// Solidity 解析策略
 pub struct SolidityStrategy;
@@ -67,34 +71,8 @@ impl SolidityStrategy {
         source_code: &str,
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
-        // node 是合约名称，需要获取完整声明
-        if let Some(parent) = node.parent() {
-            let start_row = parent.start_position().row;
-            
-            // 只提取声明行（不包括方法体）
-            let mut lines: Vec<String> = Vec::new();
-            lines.push(source_code.lines().nth(start_row)?.to_string());
-            
-            // 检查下一行是否有继承
-            if let Some(next_line) = source_code.lines().nth(start_row + 1) {
-                let trimmed = next_line.trim();
-                if trimmed.starts_with("is") {
-                    lines.push(next_line.to_string());
-                }
-            }
-            
-            let definition = lines.join("\n");
-            let cleaned = definition.split('{').next()?.trim().to_string();
-            
-            if processed_chunks.contains(&cleaned) {
-                return None;
-            }
-            
-            processed_chunks.insert(cleaned.clone());
-            return Some(cleaned);
-        }
-        
-        None
+        // 只提取声明头（含跨行的 is 继承列表），不包括成员
+        HeaderExtractor::chunk(node, source_code, processed_chunks)
     }
     
     fn parse_function(
@@ -103,38 +81,8 @@ impl SolidityStrategy {
         source_code: &str,
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
-        // 获取函数签名（不包括函数体）
-        if let Some(parent) = node.parent() {
-            let start_row = parent.start_position().row;
-            let end_row = parent.end_position().row;
-            
-            // 查找函数签名结束位置（{ 或 ; 之前）
-            let signature_end = self.find_signature_end(source_code, start_row, end_row);
-            let signature = get_lines_text(source_code, start_row, signature_end);
-            let cleaned = signature.trim().to_string();
-            
-            if processed_chunks.contains(&cleaned) {
-                return None;
-            }
-            
-            processed_chunks.insert(cleaned.clone());
-            return Some(cleaned);
-        }
-        
-        None
-    }
-    
-    fn find_signature_end(&self, source_code: &str, start: usize, end: usize) -> usize {
-        let lines: Vec<&str> = source_code.lines().collect();
-        
-        for i in start..=end.min(lines.len() - 1) {
-            let line = lines[i].trim();
-            if line.ends_with('{') || line.ends_with(';') {
-                return i;
-            }
-        }
-        
-        start
+        // 函数签名（不包括函数体），按语法树定位函数体
+        SignatureExtractor::chunk(node, source_code, processed_chunks)
     }
 }
 
@@ -163,13 +111,22 @@ impl ParseStrategy for SolidityStrategy {
         }
         
         // Pragma、导入、事件、结构体、枚举、注释 - 直接提取
-        let text = get_node_text(node, source_code).trim().to_string();
-        
-        if processed_chunks.contains(&text) {
-            return None;
+        node_chunk(node, source_code, processed_chunks)
+    }
+    
+    fn classify_visibility(&self, node: Node, source_code: &str) -> (bool, Option<Visibility>) {
+        let def = definition_node(node);
+        match def.kind() {
+            // 合约、接口、库本身总是可被外部引用
+            "contract_declaration" | "interface_declaration" | "library_declaration" => (true, Some(Visibility::Public)),
+            "function_definition" | "modifier_definition" => {
+                // public / external 对外可见；internal / private 不属于合约 ABI
+                let visibility = visibility_from_keywords(declaration_header(def, source_code))
+                    .unwrap_or(Visibility::Public);
+                (matches!(visibility, Visibility::Public), Some(visibility))
+            }
+            "event_definition" | "struct_declaration" | "enum_declaration" => (true, Some(Visibility::Public)),
+            _ => (false, None),
         }
-        
-        processed_chunks.insert(text.clone());
-        Some(text)
     }
 }
@@ -0,0 +1,52 @@
+use tree_sitter::Node;
+use std::collections::HashSet;
+
+use super::{Capture, ParseStrategy, dedup_chunk, get_node_text};
+use crate::types::Visibility;
+
+/// SQL 解析策略（DDL：schema、表、视图、函数）
+pub struct SqlStrategy;
+
+impl SqlStrategy {
+    /// 语句中被创建对象的名字节点：`object_reference`（可带 schema 前缀），schema 语句取其标识符
+    fn name_node(node: Node) -> Option<Node> {
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        children
+            .iter()
+            .find(|child| child.kind() == "object_reference")
+            .or_else(|| children.iter().find(|child| child.kind() == "identifier"))
+            .copied()
+    }
+}
+
+impl ParseStrategy for SqlStrategy {
+    fn parse_capture(
+        &self,
+        capture: Capture,
+        source_code: &str,
+        processed_chunks: &mut HashSet<String>,
+    ) -> Option<String> {
+        // 整条语句（建表语句包含列定义）
+        let text = get_node_text(capture.node, source_code).trim().to_string();
+        
+        dedup_chunk(text, processed_chunks)
+    }
+    
+    fn entity_name(&self, node: Node, source_code: &str) -> Option<String> {
+        // 去掉标识符引号：`"billing"."invoices"`、`[dbo].[users]`、反引号
+        let name: String = get_node_text(Self::name_node(node)?, source_code)
+            .chars()
+            .filter(|c| !matches!(c, '"' | '`' | '[' | ']'))
+            .collect();
+        Some(name)
+    }
+    
+    fn classify_visibility(&self, node: Node, _source_code: &str) -> (bool, Option<Visibility>) {
+        match node.kind() {
+            // 数据库对象对所有连接可见
+            "create_schema" | "create_table" | "create_view" | "create_function" => (true, Some(Visibility::Public)),
+            _ => (false, None),
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! 策略共用的片段提取原语
+//!
+//! 各语言策略的 `parse_capture` 只决定取定义的哪一部分（签名、声明头、整行范围或节点全文），
+//! 截取与去重统一在这里完成，不同语言对同一种定义的截取行为保持一致。
+
+use tree_sitter::Node;
+use std::collections::HashSet;
+
+use super::{definition_node, get_node_text};
+
+/// 去重：空片段与已出现过的片段返回 None，否则记录后返回
+pub fn dedup_chunk(text: impl Into<String>, processed_chunks: &mut HashSet<String>) -> Option<String> {
+    let text = text.into();
+    if text.is_empty() || processed_chunks.contains(&text) {
+        return None;
+    }
+
+    processed_chunks.insert(text.clone());
+    Some(text)
+}
+
+/// 节点全文（去掉首尾空白）作为片段
+pub fn node_chunk(node: Node, source_code: &str, processed_chunks: &mut HashSet<String>) -> Option<String> {
+    dedup_chunk(get_node_text(node, source_code).trim(), processed_chunks)
+}
+
+/// 节点覆盖的整行（含首行缩进之前与末行结束之后的同行内容）作为片段
+pub fn lines_chunk(node: Node, source_code: &str, processed_chunks: &mut HashSet<String>) -> Option<String> {
+    let text = get_lines_text(source_code, node.start_position().row, node.end_position().row);
+    dedup_chunk(text.trim(), processed_chunks)
+}
+
+/// 获取指定行范围的文本
+pub fn get_lines_text(source_code: &str, start_row: usize, end_row: usize) -> String {
+    source_code
+        .lines()
+        .skip(start_row)
+        .take(end_row - start_row + 1)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 定义节点的声明头部文本（函数体之前的部分），用于查找修饰符关键字
+pub fn declaration_header<'a>(def: Node, source_code: &'a str) -> &'a str {
+    let end = def
+        .child_by_field_name("body")
+        .map(|body| body.start_byte())
+        .unwrap_or_else(|| def.end_byte());
+    &source_code[def.start_byte()..end]
+}
+
+/// 类型声明头，从声明所在行的行首到定义体之前
+///
+/// 按语法树定位定义体，继承、实现列表与泛型约束跨多少行都完整保留；同一行上
+/// 声明之前的修饰符（如 `export`）一并保留。没有定义体时取到首个 `{` 或声明结束。
+pub fn type_header<'a>(def: Node, source_code: &'a str) -> &'a str {
+    let start = line_start(source_code, def.start_byte());
+    let header = declaration_header(def, source_code);
+    let end = def.start_byte() + header.find('{').unwrap_or(header.len());
+    source_code[start..end].trim()
+}
+
+/// 函数签名，从定义所在行的行首到函数体之前
+///
+/// 函数体按语法树定位（`body` 字段；C++ 构造函数的成员初始化列表也算在内），跨行的泛型与参数列表、
+/// 解构参数或默认值中的 `{` 都不影响截取。赋给变量的函数（`const run = () => {}`）取变量声明到值的
+/// 函数体之前。没有函数体的声明（重载签名、抽象方法、原型）取整个声明，去掉末尾的 `;`。
+pub fn function_signature<'a>(def: Node, source_code: &'a str) -> &'a str {
+    let start = line_start(source_code, def.start_byte());
+    let mut cursor = def.walk();
+    let end = def
+        .children(&mut cursor)
+        .find(|child| child.kind() == "field_initializer_list")
+        .or_else(|| def.child_by_field_name("body"))
+        .or_else(|| {
+            let value = def.child_by_field_name("value").filter(|_| def.kind() == "variable_declarator")?;
+            value.child_by_field_name("body")
+        })
+        .map_or(def.end_byte(), |node| node.start_byte());
+    let signature = source_code[start..end].trim_end();
+    signature.strip_suffix(';').unwrap_or(signature).trim()
+}
+
+/// 完整函数定义（签名 + 函数体），与 `function_signature` 一样从定义所在行的行首开始
+///
+/// 同一行上定义之前的修饰符（如 TypeScript 的 `export`）一并保留，带函数体的片段总以签名开头。
+pub fn function_definition<'a>(def: Node, source_code: &'a str) -> &'a str {
+    let start = line_start(source_code, def.start_byte());
+    source_code[start..def.end_byte()].trim()
+}
+
+/// 字节偏移所在行的行首
+fn line_start(source_code: &str, byte: usize) -> usize {
+    source_code[..byte].rfind('\n').map_or(0, |i| i + 1)
+}
+
+/// 函数 / 方法片段：只取签名，函数体不进入片段
+pub struct SignatureExtractor;
+
+impl SignatureExtractor {
+    /// 捕获节点（名字节点或定义本身）对应定义的签名
+    pub fn extract<'a>(node: Node, source_code: &'a str) -> &'a str {
+        function_signature(definition_node(node), source_code)
+    }
+
+    pub fn chunk(node: Node, source_code: &str, processed_chunks: &mut HashSet<String>) -> Option<String> {
+        dedup_chunk(Self::extract(node, source_code), processed_chunks)
+    }
+}
+
+/// 类型 / 模块片段：只取声明头，成员由各自的捕获处理
+pub struct HeaderExtractor;
+
+impl HeaderExtractor {
+    /// 捕获节点对应定义的类型声明头（见 `type_header`）
+    pub fn extract<'a>(node: Node, source_code: &'a str) -> &'a str {
+        type_header(definition_node(node), source_code)
+    }
+
+    /// 节点起始行在首个 `{` 之前的部分（`declare module 'x' {`、`<script setup>` 这类单行声明）
+    pub fn first_line<'a>(node: Node, source_code: &'a str) -> Option<&'a str> {
+        let line = source_code.lines().nth(node.start_position().row)?;
+        line.split('{').next().map(str::trim)
+    }
+
+    pub fn chunk(node: Node, source_code: &str, processed_chunks: &mut HashSet<String>) -> Option<String> {
+        dedup_chunk(Self::extract(node, source_code), processed_chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tree_sitter::{Parser, Tree};
+
+    fn parse(source_code: &str) -> Tree {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into())
+            .unwrap();
+        parser.parse(source_code, None).unwrap()
+    }
+
+    fn find<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+        if node.kind() == kind {
+            return Some(node);
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node<'a>> = node.children(&mut cursor).collect();
+        children.into_iter().find_map(|child| find(child, kind))
+    }
+
+    #[test]
+    fn test_dedup_chunk() {
+        let mut processed = HashSet::new();
+        assert_eq!(dedup_chunk("a", &mut processed), Some("a".to_string()));
+        assert_eq!(dedup_chunk("a", &mut processed), None);
+        assert_eq!(dedup_chunk("", &mut processed), None);
+    }
+
+    #[test]
+    fn test_signature_extractor() {
+        let code = "export function load(\n  path: string,\n  { retries = 3 }: Options,\n): Promise<void> {\n  return fetch(path);\n}\ndeclare function stub(): void;";
+        let tree = parse(code);
+        let root = tree.root_node();
+        let function = find(root, "function_declaration").unwrap();
+        let name = function.child_by_field_name("name").unwrap();
+        // 名字节点与定义本身得到同一签名；解构默认值中的 `{` 不截断
+        assert_eq!(
+            SignatureExtractor::extract(name, code),
+            "export function load(\n  path: string,\n  { retries = 3 }: Options,\n): Promise<void>"
+        );
+        assert_eq!(SignatureExtractor::extract(function, code), SignatureExtractor::extract(name, code));
+        // 没有函数体的声明去掉末尾的 `;`
+        let stub = find(root, "function_signature").unwrap();
+        assert_eq!(SignatureExtractor::extract(stub, code), "declare function stub(): void");
+        // 带函数体的定义同样从行首开始，以签名开头
+        let full = function_definition(function, code);
+        assert!(full.starts_with(SignatureExtractor::extract(function, code)));
+        assert!(full.ends_with("return fetch(path);\n}"));
+
+        let mut processed = HashSet::new();
+        assert!(SignatureExtractor::chunk(name, code, &mut processed).is_some());
+        assert!(SignatureExtractor::chunk(function, code, &mut processed).is_none());
+    }
+
+    #[test]
+    fn test_header_extractor() {
+        let code = "export class Repo<T>\n  extends Base\n  implements Store<T> {\n  get(): T {}\n}\ndeclare module 'pkg' {\n  export const a: number;\n}";
+        let tree = parse(code);
+        let root = tree.root_node();
+        let class = find(root, "class_declaration").unwrap();
+        assert_eq!(
+            HeaderExtractor::extract(class, code),
+            "export class Repo<T>\n  extends Base\n  implements Store<T>"
+        );
+        let module = find(root, "module").unwrap();
+        assert_eq!(HeaderExtractor::first_line(module, code), Some("declare module 'pkg'"));
+    }
+
+    #[test]
+    fn test_lines_chunk() {
+        let code = "const x = 1; interface A {\n  a: string;\n}";
+        let tree = parse(code);
+        let interface = find(tree.root_node(), "interface_declaration").unwrap();
+        let mut processed = HashSet::new();
+        // 整行截取，同一行上声明之前的内容一并保留
+        assert_eq!(
+            lines_chunk(interface, code, &mut processed),
+            Some("const x = 1; interface A {\n  a: string;\n}".to_string())
+        );
+        assert_eq!(get_lines_text(code, 1, 2), "  a: string;\n}");
+    }
+}
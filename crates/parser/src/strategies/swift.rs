@@ -1,7 +1,17 @@
 use tree_sitter::Node;
 use std::collections::HashSet;
 
-use super::{Capture, ParseStrategy, get_node_text, get_lines_text};
+use super::{
+    Capture, HeaderExtractor, ParseStrategy, SignatureExtractor, dedup_chunk, definition_node, get_node_text,
+    node_chunk, visibility_from_keywords,
+};
+use crate::types::{Annotation, Range, RelationKind, TypeRelation, Visibility};
+
+/// 可作为枚举原始值类型的标准库类型（出现在继承子句中但不是协议）
+const RAW_VALUE_TYPES: &[&str] = &[
+    "String", "Character", "Int", "Int8", "Int16", "Int32", "Int64", "UInt", "UInt8", "UInt16", "UInt32", "UInt64",
+    "Float", "Double",
+];
 
 /// Swift 解析策略
 pub struct SwiftStrategy;
@@ -14,6 +24,7 @@ enum CaptureType {
     Protocol,
     Enum,
     Function,
+    Property,
     Extension,
 }
 
@@ -42,6 +53,9 @@ impl SwiftStrategy {
         if name.contains("definition.function") {
             types.push(CaptureType::Function);
         }
+        if name.contains("definition.property") {
+            types.push(CaptureType::Property);
+        }
         if name.contains("definition.extension") {
             types.push(CaptureType::Extension);
         }
@@ -55,34 +69,8 @@ impl SwiftStrategy {
         source_code: &str,
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
-        // node 是名称，需要获取完整声明
-        if let Some(parent) = node.parent() {
-            let start_row = parent.start_position().row;
-            
-            // 只提取声明行（不包括方法体）
-            let mut lines: Vec<String> = Vec::new();
-            lines.push(source_code.lines().nth(start_row)?.to_string());
-            
-            // 检查下一行是否有继承或协议
-            if let Some(next_line) = source_code.lines().nth(start_row + 1) {
-                let trimmed = next_line.trim();
-                if trimmed.starts_with(':') || trimmed.contains("where") {
-                    lines.push(next_line.to_string());
-                }
-            }
-            
-            let definition = lines.join("\n");
-            let cleaned = definition.split('{').next()?.trim().to_string();
-            
-            if processed_chunks.contains(&cleaned) {
-                return None;
-            }
-            
-            processed_chunks.insert(cleaned.clone());
-            return Some(cleaned);
-        }
-        
-        None
+        // 只提取声明头（含跨行的继承、协议与 where 约束），不包括成员
+        HeaderExtractor::chunk(node, source_code, processed_chunks)
     }
     
     fn parse_function(
@@ -91,38 +79,51 @@ impl SwiftStrategy {
         source_code: &str,
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
-        // 获取函数签名（不包括函数体）
-        if let Some(parent) = node.parent() {
-            let start_row = parent.start_position().row;
-            let end_row = parent.end_position().row;
-            
-            // 查找函数签名结束位置（{ 之前）
-            let signature_end = self.find_signature_end(source_code, start_row, end_row);
-            let signature = get_lines_text(source_code, start_row, signature_end);
-            let cleaned = signature.trim().to_string();
-            
-            if processed_chunks.contains(&cleaned) {
-                return None;
-            }
-            
-            processed_chunks.insert(cleaned.clone());
-            return Some(cleaned);
-        }
-        
-        None
+        // 函数签名（不包括函数体），按语法树定位函数体
+        SignatureExtractor::chunk(node, source_code, processed_chunks)
     }
     
-    fn find_signature_end(&self, source_code: &str, start: usize, end: usize) -> usize {
-        let lines: Vec<&str> = source_code.lines().collect();
+    fn parse_property(
+        &self,
+        node: Node,
+        source_code: &str,
+        processed_chunks: &mut HashSet<String>,
+    ) -> Option<String> {
+        // 属性声明（含属性包装器），不包括计算属性的实现与 willSet / didSet
+        let def = definition_node(node);
+        let start = source_code[..def.start_byte()].rfind('\n').map_or(0, |i| i + 1);
+        let mut cursor = def.walk();
+        let end = def
+            .children(&mut cursor)
+            .find(|child| matches!(child.kind(), "computed_property" | "willset_didset_block"))
+            .map_or(def.end_byte(), |block| block.start_byte());
+        let cleaned = source_code[start..end].trim().to_string();
         
-        for i in start..=end.min(lines.len() - 1) {
-            let line = lines[i].trim();
-            if line.ends_with('{') {
-                return i;
+        dedup_chunk(cleaned, processed_chunks)
+    }
+    
+    /// 声明上的属性节点（`@State`、`@MainActor` 等），位于 `modifiers` 中或直接挂在声明上
+    fn attribute_nodes(def: Node) -> Vec<Node> {
+        let mut attributes = Vec::new();
+        let mut cursor = def.walk();
+        for child in def.children(&mut cursor) {
+            match child.kind() {
+                "attribute" => attributes.push(child),
+                "modifiers" => {
+                    let mut inner = child.walk();
+                    attributes.extend(child.children(&mut inner).filter(|c| c.kind() == "attribute"));
+                }
+                _ => {}
             }
         }
-        
-        start
+        attributes
+    }
+    
+    /// 继承子句中的类型名（去掉泛型参数）
+    fn inherited_name(specifier: Node, source_code: &str) -> Option<String> {
+        let target = specifier.child_by_field_name("inherits_from")?;
+        let text = get_node_text(target, source_code);
+        Some(text.split('<').next().unwrap_or(text).trim().to_string())
     }
 }
 
@@ -143,27 +144,121 @@ impl ParseStrategy for SwiftStrategy {
             return self.parse_function(node, source_code, processed_chunks);
         }
         
-        // 类、结构体、协议
+        // 属性
+        if capture_types.iter().any(|t| matches!(t, CaptureType::Property)) {
+            return self.parse_property(node, source_code, processed_chunks);
+        }
+        
+        // 类、结构体、协议、扩展：只保留声明头，成员各自作为实体提取
         if capture_types.iter().any(|t| {
-            matches!(t, CaptureType::Class | CaptureType::Struct | CaptureType::Protocol | CaptureType::Enum)
+            matches!(
+                t,
+                CaptureType::Class | CaptureType::Struct | CaptureType::Protocol | CaptureType::Enum | CaptureType::Extension
+            )
         }) {
             return self.parse_class_struct_protocol(node, source_code, processed_chunks);
         }
         
-        // 导入、扩展、注释 - 直接提取
-        if capture_types.iter().any(|t| {
-            matches!(t, CaptureType::Import | CaptureType::Extension | CaptureType::Comment)
-        }) {
-            let text = get_node_text(node, source_code).trim().to_string();
-            
-            if processed_chunks.contains(&text) {
-                return None;
-            }
-            
-            processed_chunks.insert(text.clone());
-            return Some(text);
+        // 导入、注释 - 直接提取
+        if capture_types.iter().any(|t| matches!(t, CaptureType::Import | CaptureType::Comment)) {
+            return node_chunk(node, source_code, processed_chunks);
         }
         
         None
     }
+    
+    fn classify_visibility(&self, node: Node, source_code: &str) -> (bool, Option<Visibility>) {
+        let def = definition_node(node);
+        if !matches!(
+            def.kind(),
+            "class_declaration" | "protocol_declaration" | "function_declaration" | "property_declaration"
+        ) {
+            return (false, None);
+        }
+        
+        // 修饰符位于名字之前；Swift 默认 internal
+        let header = &source_code[def.start_byte()..node.start_byte().max(def.start_byte())];
+        let visibility = visibility_from_keywords(header).unwrap_or(Visibility::Internal);
+        (matches!(visibility, Visibility::Public), Some(visibility))
+    }
+    
+    fn annotations(&self, node: Node, source_code: &str) -> Vec<Annotation> {
+        Self::attribute_nodes(definition_node(node))
+            .into_iter()
+            .filter_map(|attribute| {
+                let mut cursor = attribute.walk();
+                let name_node = attribute.named_children(&mut cursor).find(|c| c.kind() == "user_type")?;
+                // `@Environment(\.dismiss)` 的参数为括号内的部分
+                let arguments = source_code[name_node.end_byte()..attribute.end_byte()]
+                    .trim()
+                    .strip_prefix('(')
+                    .and_then(|rest| rest.strip_suffix(')'))
+                    .map(|args| args.trim().to_string());
+                Some(Annotation {
+                    name: get_node_text(name_node, source_code).to_string(),
+                    arguments,
+                })
+            })
+            .collect()
+    }
+    
+    fn type_relations(&self, root: Node, source_code: &str) -> Vec<TypeRelation> {
+        let mut declarations = Vec::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if matches!(node.kind(), "class_declaration" | "protocol_declaration") {
+                declarations.push(node);
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+        }
+        declarations.sort_by_key(|node| node.start_byte());
+        
+        let protocols: HashSet<&str> = declarations
+            .iter()
+            .filter(|node| node.kind() == "protocol_declaration")
+            .filter_map(|node| node.child_by_field_name("name"))
+            .map(|name| get_node_text(name, source_code))
+            .collect();
+        
+        let mut relations = Vec::new();
+        for declaration in declarations {
+            let Some(type_name) = declaration.child_by_field_name("name") else {
+                continue;
+            };
+            let declaration_kind = declaration
+                .child_by_field_name("declaration_kind")
+                .map_or("", |kind| get_node_text(kind, source_code));
+            let mut cursor = declaration.walk();
+            let specifiers: Vec<Node> = declaration
+                .children(&mut cursor)
+                .filter(|child| child.kind() == "inheritance_specifier")
+                .collect();
+            
+            for (i, specifier) in specifiers.into_iter().enumerate() {
+                let Some(target) = Self::inherited_name(specifier, source_code) else {
+                    continue;
+                };
+                let kind = match declaration_kind {
+                    // 协议继承其他协议
+                    "protocol" => RelationKind::Extends,
+                    // 父类必须写在继承子句首位；同一文件中声明为协议的除外
+                    "class" | "actor" if i == 0 && !protocols.contains(target.as_str()) => RelationKind::Extends,
+                    // 枚举的原始值类型不是协议
+                    "enum" if i == 0 && RAW_VALUE_TYPES.contains(&target.as_str()) => continue,
+                    _ => RelationKind::Conforms,
+                };
+                relations.push(TypeRelation {
+                    type_name: get_node_text(type_name, source_code).to_string(),
+                    target,
+                    kind,
+                    range: Range {
+                        start: specifier.start_position().row + 1,
+                        end: specifier.end_position().row + 1,
+                    },
+                });
+            }
+        }
+        relations
+    }
 }
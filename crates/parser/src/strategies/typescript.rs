@@ -1,7 +1,33 @@
 use tree_sitter::Node;
 use std::collections::HashSet;
 
-use super::{Capture, ParseStrategy, get_node_text, get_lines_text};
+use super::{
+    CallSyntax, Capture, HeaderExtractor, ModuleRefs, ParseStrategy, SignatureExtractor, collect_all_nodes,
+    collect_nodes, dedup_chunk, definition_name, definition_node, dynamic_import, get_node_text, lines_chunk,
+    modification, node_chunk, root_node, string_literal_value,
+};
+use crate::types::{DynamicImportKind, ExportDeclaration, ImportDeclaration, Modification, Visibility};
+
+/// 修改目标对象的调用：`Object.assign(Foo.prototype, {...})`、`Object.defineProperty(fs, 'readFile', {...})`
+const PATCH_CALLS: &[&str] = &[
+    "Object.assign",
+    "Object.defineProperty",
+    "Object.defineProperties",
+    "Reflect.defineProperty",
+];
+
+/// 调用关系：普通调用与 `new`
+static CALL_SYNTAX: CallSyntax = CallSyntax {
+    calls: &[("call_expression", "function"), ("new_expression", "constructor")],
+    callers: &[
+        "function_declaration",
+        "generator_function_declaration",
+        "method_definition",
+        "function_expression",
+        "generator_function",
+        "arrow_function",
+    ],
+};
 
 /// TypeScript/JavaScript 解析策略（基于 repomix 的实现）
 pub struct TypeScriptStrategy;
@@ -16,6 +42,7 @@ enum CaptureType {
     Function,
     Method,
     Property,
+    Module,
 }
 
 impl TypeScriptStrategy {
@@ -49,6 +76,9 @@ impl TypeScriptStrategy {
         if name.contains("definition.property") {
             types.push(CaptureType::Property);
         }
+        if name.contains("definition.module") {
+            types.push(CaptureType::Module);
+        }
         
         types
     }
@@ -60,18 +90,7 @@ impl TypeScriptStrategy {
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
         // 提取完整的接口或类型定义（直到找到结束的 }）
-        let start_row = node.start_position().row;
-        let end_row = node.end_position().row;
-        
-        let full_text = get_lines_text(source_code, start_row, end_row);
-        let cleaned = full_text.trim().to_string();
-        
-        if processed_chunks.contains(&cleaned) {
-            return None;
-        }
-        
-        processed_chunks.insert(cleaned.clone());
-        Some(cleaned)
+        lines_chunk(node, source_code, processed_chunks)
     }
     
     fn parse_function(
@@ -80,77 +99,225 @@ impl TypeScriptStrategy {
         source_code: &str,
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
-        let start_row = node.start_position().row;
-        let end_row = node.end_position().row;
-        
-        // 查找函数签名的结束位置（不包括函数体）
-        let signature_end = self.find_signature_end(source_code, start_row, end_row);
-        let signature = get_lines_text(source_code, start_row, signature_end);
-        let cleaned = self.clean_function_signature(&signature);
-        
-        if processed_chunks.contains(&cleaned) {
-            return None;
-        }
-        
-        processed_chunks.insert(cleaned.clone());
-        Some(cleaned)
+        // 函数签名（不包括函数体），按语法树定位函数体
+        SignatureExtractor::chunk(node, source_code, processed_chunks)
     }
     
-    fn find_signature_end(&self, source_code: &str, start: usize, end: usize) -> usize {
-        let lines: Vec<&str> = source_code.lines().collect();
-        
-        for i in start..=end.min(lines.len() - 1) {
-            let line = lines[i].trim();
-            if line.contains(')') && (line.ends_with('{') || line.ends_with("=>") || line.ends_with(';')) {
-                return i;
+    fn parse_class(
+        &self,
+        node: Node,
+        source_code: &str,
+        processed_chunks: &mut HashSet<String>,
+    ) -> Option<String> {
+        // 只提取类声明头（含跨行的 extends / implements），不包括方法
+        HeaderExtractor::chunk(node, source_code, processed_chunks)
+    }
+    
+    fn member_visibility(&self, member: Node, source_code: &str) -> Visibility {
+        let mut cursor = member.walk();
+        for child in member.children(&mut cursor) {
+            if child.kind() == "accessibility_modifier" {
+                return match get_node_text(child, source_code) {
+                    "private" => Visibility::Private,
+                    "protected" => Visibility::Protected,
+                    _ => Visibility::Public,
+                };
             }
         }
         
-        start
+        let is_hash_private = member
+            .child_by_field_name("name")
+            .is_some_and(|n| n.kind() == "private_property_identifier");
+        if is_hash_private {
+            Visibility::Private
+        } else {
+            Visibility::Public
+        }
     }
     
-    fn clean_function_signature(&self, signature: &str) -> String {
-        let mut result = signature.to_string();
-        
-        // 移除函数体的开始 { 或 =>
-        if let Some(pos) = result.rfind('{') {
-            result = result[..pos].trim().to_string();
-        } else if let Some(pos) = result.rfind("=>") {
-            result = result[..pos].trim().to_string();
+    /// 收集 `export { a, b as c }` 中导出的本地名字
+    fn export_clause_names(&self, root: Node, source_code: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut cursor = root.walk();
+        for statement in root.children(&mut cursor) {
+            // 带 source 的是 re-export，不对应本文件的声明
+            if statement.kind() != "export_statement" || statement.child_by_field_name("source").is_some() {
+                continue;
+            }
+            let mut inner = statement.walk();
+            for clause in statement.children(&mut inner) {
+                if clause.kind() != "export_clause" {
+                    continue;
+                }
+                let mut spec_cursor = clause.walk();
+                for spec in clause.children(&mut spec_cursor) {
+                    if let Some(name) = spec.child_by_field_name("name") {
+                        names.push(get_node_text(name, source_code).to_string());
+                    }
+                }
+            }
         }
-        
-        result
+        names
     }
     
-    fn parse_class(
+    /// 解析 `import` 语句（ES 模块与 `import x = require('y')`）
+    pub(crate) fn import_declaration(&self, node: Node, source_code: &str, file_path: &str) -> Option<ImportDeclaration> {
+        let mut import = ImportDeclaration {
+            file_path: file_path.to_string(),
+            ..Default::default()
+        };
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "type" => import.is_type_only = true,
+                "import_require_clause" => {
+                    import.source = string_literal_value(child.child_by_field_name("source")?, source_code);
+                    import.namespace_import = child
+                        .named_child(0)
+                        .filter(|n| n.kind() == "identifier")
+                        .map(|n| get_node_text(n, source_code).to_string());
+                }
+                "import_clause" => {
+                    let mut clause_cursor = child.walk();
+                    for binding in child.named_children(&mut clause_cursor) {
+                        match binding.kind() {
+                            "identifier" => {
+                                import.default_import = Some(get_node_text(binding, source_code).to_string());
+                            }
+                            "namespace_import" => {
+                                import.namespace_import = binding
+                                    .named_child(0)
+                                    .map(|n| get_node_text(n, source_code).to_string());
+                            }
+                            "named_imports" => {
+                                let mut spec_cursor = binding.walk();
+                                for spec in binding.named_children(&mut spec_cursor) {
+                                    let Some(name) = spec.child_by_field_name("name") else {
+                                        continue;
+                                    };
+                                    let name = string_literal_value(name, source_code);
+                                    if spec.child(0).is_some_and(|c| c.kind() == "type") {
+                                        import.type_specifiers.push(name.clone());
+                                    }
+                                    import.specifiers.push(name);
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(source) = node.child_by_field_name("source") {
+            import.source = string_literal_value(source, source_code);
+        }
+        // `import { type A, type B }` 与 `import type { A, B }` 等价
+        if !import.type_specifiers.is_empty()
+            && import.type_specifiers.len() == import.specifiers.len()
+            && import.default_import.is_none()
+            && import.namespace_import.is_none()
+        {
+            import.is_type_only = true;
+        }
+
+        (!import.source.is_empty()).then_some(import)
+    }
+
+    /// `PATCH_CALLS` 修改的目标：第一个参数，`defineProperty` 的字面量属性名接在其后
+    fn patch_call_target(&self, call: Node, source_code: &str) -> Option<String> {
+        let function = call.child_by_field_name("function")?;
+        let function = get_node_text(function, source_code);
+        if !PATCH_CALLS.contains(&function) {
+            return None;
+        }
+        let arguments = call.child_by_field_name("arguments")?;
+        let object = get_node_text(arguments.named_child(0)?, source_code);
+        match arguments.named_child(1) {
+            Some(property) if function.ends_with("defineProperty") && property.kind() == "string" => {
+                Some(format!("{}.{}", object, string_literal_value(property, source_code)))
+            }
+            _ => Some(object.to_string()),
+        }
+    }
+
+    /// 解析 `export` 语句（声明导出、具名导出、默认导出与 re-export）
+    fn export_declaration(&self, node: Node, source_code: &str, file_path: &str) -> Option<ExportDeclaration> {
+        let mut export = ExportDeclaration {
+            file_path: file_path.to_string(),
+            source: node
+                .child_by_field_name("source")
+                .map(|n| string_literal_value(n, source_code)),
+            ..Default::default()
+        };
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "type" => export.is_type_only = true,
+                "*" => export.is_wildcard = true,
+                // `export default ...` / `export = x`
+                "default" | "=" => export.specifiers.push("default".to_string()),
+                "namespace_export" => {
+                    if let Some(name) = child.named_child(0) {
+                        export.specifiers.push(string_literal_value(name, source_code));
+                    }
+                }
+                "export_clause" => {
+                    let mut spec_cursor = child.walk();
+                    for spec in child.named_children(&mut spec_cursor) {
+                        let Some(name) = spec.child_by_field_name("alias").or_else(|| spec.child_by_field_name("name")) else {
+                            continue;
+                        };
+                        export.specifiers.push(string_literal_value(name, source_code));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(declaration) = node.child_by_field_name("declaration") {
+            if matches!(declaration.kind(), "interface_declaration" | "type_alias_declaration") {
+                export.is_type_only = true;
+            }
+            if !export.specifiers.iter().any(|s| s == "default") {
+                export.specifiers.extend(self.declared_names(declaration, source_code));
+            }
+        }
+
+        (export.is_wildcard || !export.specifiers.is_empty()).then_some(export)
+    }
+
+    /// 声明引入的名字（变量声明可能通过解构引入多个）
+    fn declared_names(&self, declaration: Node, source_code: &str) -> Vec<String> {
+        if let Some(name) = declaration.child_by_field_name("name") {
+            return vec![get_node_text(name, source_code).to_string()];
+        }
+        let mut names = Vec::new();
+        let mut cursor = declaration.walk();
+        for declarator in declaration.named_children(&mut cursor) {
+            let Some(pattern) = declarator.child_by_field_name("name") else {
+                continue;
+            };
+            names.extend(
+                collect_nodes(pattern, &["identifier", "shorthand_property_identifier_pattern"])
+                    .into_iter()
+                    .map(|n| get_node_text(n, source_code).to_string()),
+            );
+        }
+        names
+    }
+
+    fn parse_module(
         &self,
         node: Node,
         source_code: &str,
         processed_chunks: &mut HashSet<String>,
     ) -> Option<String> {
-        let start_row = node.start_position().row;
-        
-        // 只提取类声明行（不包括方法）
-        let mut lines: Vec<String> = Vec::new();
-        lines.push(source_code.lines().nth(start_row)?.to_string());
-        
-        // 检查下一行是否有 extends 或 implements
-        if let Some(next_line) = source_code.lines().nth(start_row + 1) {
-            let trimmed = next_line.trim();
-            if trimmed.starts_with("extends") || trimmed.starts_with("implements") {
-                lines.push(next_line.to_string());
-            }
-        }
-        
-        let definition = lines.join("\n");
-        let cleaned = definition.split('{').next()?.trim().to_string();
-        
-        if processed_chunks.contains(&cleaned) {
-            return None;
-        }
-        
-        processed_chunks.insert(cleaned.clone());
-        Some(cleaned)
+        // 只提取 `declare module 'x'` 声明行，模块体中的成员由各自的捕获处理
+        dedup_chunk(HeaderExtractor::first_line(node, source_code)?, processed_chunks)
     }
 }
 
@@ -176,6 +343,11 @@ impl ParseStrategy for TypeScriptStrategy {
             return self.parse_class(node, source_code, processed_chunks);
         }
         
+        // declare module
+        if capture_types.iter().any(|t| matches!(t, CaptureType::Module)) {
+            return self.parse_module(node, source_code, processed_chunks);
+        }
+        
         // 接口、类型、枚举 - 提取父节点（完整定义）
         if capture_types.iter().any(|t| {
             matches!(t, CaptureType::Interface | CaptureType::Type | CaptureType::Enum)
@@ -189,21 +361,100 @@ impl ParseStrategy for TypeScriptStrategy {
         
         // 导入（直接提取节点文本）
         if capture_types.iter().any(|t| matches!(t, CaptureType::Import)) {
-            let text = get_node_text(node, source_code).trim().to_string();
-            
-            if processed_chunks.contains(&text) {
-                return None;
-            }
-            
-            processed_chunks.insert(text.clone());
-            return Some(text);
+            return node_chunk(node, source_code, processed_chunks);
         }
         
         // 注释
         if capture_types.iter().any(|t| matches!(t, CaptureType::Comment)) {
-            return Some(get_node_text(node, source_code).trim().to_string());
+            return dedup_chunk(get_node_text(node, source_code).trim(), processed_chunks);
         }
         
         None
     }
+    
+    fn extract_module_refs(&self, root: Node, source_code: &str, file_path: &str) -> ModuleRefs {
+        let mut refs = ModuleRefs::default();
+        // `declare module 'x' { ... }` 内部的语句属于环境声明，不计入本文件
+        let mut cursor = root.walk();
+        for statement in root.children(&mut cursor) {
+            match statement.kind() {
+                "import_statement" => refs.imports.extend(self.import_declaration(statement, source_code, file_path)),
+                "export_statement" => refs.exports.extend(self.export_declaration(statement, source_code, file_path)),
+                _ => {}
+            }
+        }
+        // `import('./a')` 与 CommonJS `require('./a')`（可出现在任意表达式中）
+        for call in collect_all_nodes(root, &["call_expression"]) {
+            let kind = match call.child_by_field_name("function") {
+                Some(function) if function.kind() == "import" => DynamicImportKind::Import,
+                Some(function) if get_node_text(function, source_code) == "require" => DynamicImportKind::Require,
+                _ => continue,
+            };
+            refs.dynamic_imports.extend(dynamic_import(call, kind, source_code));
+        }
+        refs
+    }
+
+    fn modifications(&self, root: Node, source_code: &str, imports: &[ImportDeclaration]) -> Vec<Modification> {
+        let mut modifications = Vec::new();
+        let mut cursor = root.walk();
+        for statement in root.children(&mut cursor).filter(|n| n.kind() == "expression_statement") {
+            let Some(expression) = statement.named_child(0) else {
+                continue;
+            };
+            let path = match expression.kind() {
+                "assignment_expression" => expression
+                    .child_by_field_name("left")
+                    .map(|left| get_node_text(left, source_code).to_string()),
+                "call_expression" => self.patch_call_target(expression, source_code),
+                _ => None,
+            };
+            modifications.extend(path.and_then(|path| modification(&path, statement, imports)));
+        }
+        modifications
+    }
+
+    fn classify_visibility(&self, node: Node, source_code: &str) -> (bool, Option<Visibility>) {
+        let def = definition_node(node);
+        
+        // 类成员：accessibility_modifier / #private
+        let member_visibility = match def.kind() {
+            "method_definition" | "method_signature" | "abstract_method_signature" | "public_field_definition" => {
+                Some(self.member_visibility(def, source_code))
+            }
+            _ => None,
+        };
+        if matches!(member_visibility, Some(Visibility::Private | Visibility::Protected)) {
+            return (false, member_visibility);
+        }
+        
+        // 向上查找 export 语句（到函数体/文件为止，嵌套在函数内部的声明不算）
+        let mut current = def.parent();
+        while let Some(parent) = current {
+            match parent.kind() {
+                "export_statement" => return (true, Some(Visibility::Public)),
+                "statement_block" | "program" => break,
+                _ => current = parent.parent(),
+            }
+        }
+        
+        // `export { foo }` 形式的具名导出（变量在 `const` / `let` 声明之内）
+        let declaration = match def.kind() {
+            "variable_declarator" => def.parent().unwrap_or(def),
+            _ => def,
+        };
+        if declaration.parent().is_some_and(|p| p.kind() == "program") {
+            if let Some(name) = definition_name(node, source_code) {
+                if self.export_clause_names(root_node(def), source_code).contains(&name) {
+                    return (true, Some(Visibility::Public));
+                }
+            }
+        }
+        
+        (false, member_visibility)
+    }
+    
+    fn call_syntax(&self) -> Option<&'static CallSyntax> {
+        Some(&CALL_SYNTAX)
+    }
 }
@@ -1,7 +1,6 @@
-use tree_sitter::Node;
 use std::collections::HashSet;
 
-use super::{Capture, ParseStrategy, get_node_text};
+use super::{Capture, ParseStrategy, dedup_chunk, node_chunk};
 
 /// Vue 解析策略（处理 .vue 单文件组件）
 pub struct VueStrategy;
@@ -58,25 +57,13 @@ impl ParseStrategy for VueStrategy {
             if let Some(line_text) = line {
                 let cleaned = line_text.trim().to_string();
                 
-                if processed_chunks.contains(&cleaned) {
-                    return None;
-                }
-                
-                processed_chunks.insert(cleaned.clone());
-                return Some(cleaned);
+                return dedup_chunk(cleaned, processed_chunks);
             }
         }
         
         // 注释 - 直接提取
         if capture_types.iter().any(|t| matches!(t, CaptureType::Comment)) {
-            let text = get_node_text(node, source_code).trim().to_string();
-            
-            if processed_chunks.contains(&text) {
-                return None;
-            }
-            
-            processed_chunks.insert(text.clone());
-            return Some(text);
+            return node_chunk(node, source_code, processed_chunks);
         }
         
         None
@@ -0,0 +1,132 @@
+use tree_sitter::Node;
+use std::collections::HashSet;
+
+use super::{
+    Capture, HeaderExtractor, ModuleRefs, ParseStrategy, SignatureExtractor, collect_all_nodes, definition_name,
+    definition_node, get_node_text, node_chunk, string_literal_value,
+};
+use crate::types::{ImportDeclaration, Visibility};
+
+/// 赋给常量即成为具名类型的容器表达式
+const CONTAINER_KINDS: &[&str] = &[
+    "struct_declaration",
+    "enum_declaration",
+    "union_declaration",
+    "opaque_declaration",
+    "error_set_declaration",
+];
+
+/// Zig 解析策略（函数、struct / enum / union / error set 与 `@import`）
+///
+/// Zig 的类型是匿名的容器表达式（`const Point = struct { ... };`），以所赋值的常量声明为实体，
+/// 名字取常量名。`pub` / `export` 的声明对其他文件可见，其余为文件私有。
+pub struct ZigStrategy;
+
+impl ZigStrategy {
+    /// 变量声明的名字（`const Point = ...` 为 `Point`）
+    fn variable_name<'a>(node: Node, source_code: &'a str) -> Option<&'a str> {
+        let mut cursor = node.walk();
+        let name = node.named_children(&mut cursor).find(|child| child.kind() == "identifier");
+        Some(get_node_text(name?, source_code))
+    }
+
+    /// 变量声明的值（`=` 之后的表达式）
+    fn value(node: Node) -> Option<Node> {
+        let mut cursor = node.walk();
+        let value = node
+            .named_children(&mut cursor)
+            .find(|child| CONTAINER_KINDS.contains(&child.kind()) || child.kind() == "builtin_function");
+        value
+    }
+
+    /// 赋给常量的容器类型的名字，用于确定嵌套成员的外层作用域
+    pub(crate) fn container_name(node: Node, source_code: &str) -> Option<String> {
+        if node.kind() != "variable_declaration" {
+            return None;
+        }
+        Self::value(node).filter(|value| CONTAINER_KINDS.contains(&value.kind()))?;
+        Self::variable_name(node, source_code).map(str::to_string)
+    }
+
+    /// `@import("std")` 调用的模块路径
+    fn import_source(call: Node, source_code: &str) -> Option<String> {
+        let builtin = call.named_child(0).filter(|child| child.kind() == "builtin_identifier")?;
+        if get_node_text(builtin, source_code) != "@import" {
+            return None;
+        }
+        let mut cursor = call.walk();
+        let arguments = call.named_children(&mut cursor).find(|child| child.kind() == "arguments")?;
+        Some(string_literal_value(arguments.named_child(0)?, source_code))
+    }
+}
+
+impl ParseStrategy for ZigStrategy {
+    fn parse_capture(
+        &self,
+        capture: Capture,
+        source_code: &str,
+        processed_chunks: &mut HashSet<String>,
+    ) -> Option<String> {
+        // `_` 开头的捕获只用于谓词
+        if capture.name.starts_with('_') {
+            return None;
+        }
+        let node = capture.node;
+        if capture.name.contains("comment") {
+            return Some(get_node_text(node, source_code).trim().to_string());
+        }
+        if capture.name.contains("definition.function") {
+            return SignatureExtractor::chunk(node, source_code, processed_chunks);
+        }
+        // 容器类型只取 `pub const Point = struct` 声明头，成员由各自的捕获处理
+        if Self::container_name(node, source_code).is_some() {
+            return HeaderExtractor::chunk(node, source_code, processed_chunks);
+        }
+        node_chunk(node, source_code, processed_chunks)
+    }
+
+    fn entity_name(&self, node: Node, source_code: &str) -> Option<String> {
+        match node.kind() {
+            "variable_declaration" => Self::variable_name(node, source_code).map(str::to_string),
+            _ => definition_name(node, source_code),
+        }
+    }
+
+    fn classify_visibility(&self, node: Node, _source_code: &str) -> (bool, Option<Visibility>) {
+        let def = definition_node(node);
+        if !matches!(def.kind(), "function_declaration" | "variable_declaration") {
+            return (false, None);
+        }
+        let mut cursor = def.walk();
+        let is_public = def.children(&mut cursor).any(|child| matches!(child.kind(), "pub" | "export"));
+        if is_public {
+            (true, Some(Visibility::Public))
+        } else {
+            (false, Some(Visibility::Private))
+        }
+    }
+
+    /// `const std = @import("std");`：模块路径为来源，常量名记为命名空间导入
+    ///
+    /// `@import("std").debug.print` 这类直接取成员的导入只记录来源。
+    fn extract_module_refs(&self, root: Node, source_code: &str, file_path: &str) -> ModuleRefs {
+        let mut refs = ModuleRefs::default();
+        for call in collect_all_nodes(root, &["builtin_function"]) {
+            let Some(source) = Self::import_source(call, source_code) else {
+                continue;
+            };
+            let namespace_import = call
+                .parent()
+                .filter(|parent| parent.kind() == "variable_declaration")
+                .and_then(|declaration| Self::variable_name(declaration, source_code))
+                .map(str::to_string);
+            refs.imports.push(ImportDeclaration {
+                source,
+                namespace_import,
+                file_path: file_path.to_string(),
+                ..Default::default()
+            });
+        }
+        refs
+    }
+}
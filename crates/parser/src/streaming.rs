@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Condvar, Mutex};
+
+use crate::language_manager::LanguageManager;
+use crate::throttle::{Throttle, ThrottleOptions};
+use crate::types::ParseResult;
+
+/// 基于信用（credit）的流控闸门
+///
+/// 生产方每发出一个批次消耗一份信用，消费方处理完批次后调用 `ack` 归还。
+/// 信用耗尽时生产方阻塞，因此未确认的批次数不会超过初始信用；多余的 `ack` 不会让信用超过初始值。
+#[derive(Debug, Clone)]
+pub struct CreditGate {
+    inner: Arc<(Mutex<GateState>, Condvar)>,
+}
+
+#[derive(Debug)]
+struct GateState {
+    credits: usize,
+    /// 初始信用（信用上限）
+    capacity: usize,
+    closed: bool,
+}
+
+impl CreditGate {
+    /// 创建闸门，`credits` 为允许同时在途的批次数（至少为 1）
+    pub fn new(credits: usize) -> Self {
+        Self {
+            inner: Arc::new((
+                Mutex::new(GateState { credits: credits.max(1), capacity: credits.max(1), closed: false }),
+                Condvar::new(),
+            )),
+        }
+    }
+
+    /// 获取一份信用，信用不足时阻塞；闸门关闭后返回 false
+    pub fn acquire(&self) -> bool {
+        let (lock, cvar) = &*self.inner;
+        let mut state = lock.lock().unwrap_or_else(|e| e.into_inner());
+        while state.credits == 0 && !state.closed {
+            state = cvar.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+        if state.closed {
+            return false;
+        }
+        state.credits -= 1;
+        true
+    }
+
+    /// 归还信用（消费方确认已处理 `batches` 个批次）
+    pub fn ack(&self, batches: usize) {
+        let (lock, cvar) = &*self.inner;
+        let mut state = lock.lock().unwrap_or_else(|e| e.into_inner());
+        state.credits = (state.credits + batches).min(state.capacity);
+        cvar.notify_all();
+    }
+
+    /// 关闭闸门，唤醒并终止等待中的生产方
+    pub fn close(&self) {
+        let (lock, cvar) = &*self.inner;
+        let mut state = lock.lock().unwrap_or_else(|e| e.into_inner());
+        state.closed = true;
+        cvar.notify_all();
+    }
+
+    /// 当前可用信用
+    pub fn available(&self) -> usize {
+        let (lock, _) = &*self.inner;
+        lock.lock().unwrap_or_else(|e| e.into_inner()).credits
+    }
+
+    /// 是否已关闭
+    pub fn is_closed(&self) -> bool {
+        let (lock, _) = &*self.inner;
+        lock.lock().unwrap_or_else(|e| e.into_inner()).closed
+    }
+}
+
+/// 流式索引统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamStats {
+    /// 已发出的批次数
+    pub batches: usize,
+    /// 成功解析的文件数
+    pub parsed: usize,
+    /// 解析失败（跳过）的文件
+    pub failed: Vec<String>,
+    /// 是否被提前取消
+    pub cancelled: bool,
+}
+
+/// 流式索引器：分批解析文件并交给 sink，受 CreditGate 流控
+///
+/// 每个批次在解析前先获取信用，所以慢速消费方不会导致 Rust 侧无限缓存结果。
+#[derive(Debug, Clone)]
+pub struct StreamingIndexer {
+    batch_size: usize,
+    gate: CreditGate,
+    throttle: ThrottleOptions,
+}
+
+impl StreamingIndexer {
+    /// 创建索引器
+    ///
+    /// * `batch_size` - 每批文件数
+    /// * `max_in_flight` - 允许同时未确认的批次数
+    pub fn new(batch_size: usize, max_in_flight: usize) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            gate: CreditGate::new(max_in_flight),
+            throttle: ThrottleOptions::default(),
+        }
+    }
+
+    /// 设置 CPU / IO 节流（逐文件生效）
+    pub fn with_throttle(mut self, throttle: ThrottleOptions) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// 流控闸门（交给消费方用于 ack / close）
+    pub fn gate(&self) -> CreditGate {
+        self.gate.clone()
+    }
+
+    /// 解析全部文件，逐批调用 sink
+    pub fn run<I, F>(&self, manager: &mut LanguageManager, files: I, mut sink: F) -> StreamStats
+    where
+        I: IntoIterator<Item = (String, String)>,
+        F: FnMut(Vec<ParseResult>),
+    {
+        let mut stats = StreamStats::default();
+        let mut throttle = Throttle::new(self.throttle.clone());
+        let mut files = files.into_iter().peekable();
+
+        while files.peek().is_some() {
+            if !self.gate.acquire() {
+                stats.cancelled = true;
+                break;
+            }
+
+            let mut batch = Vec::with_capacity(self.batch_size);
+            for (path, content) in files.by_ref().take(self.batch_size) {
+                throttle.begin_unit();
+                match manager.parse_file(&path, &content) {
+                    Ok(result) => batch.push(result),
+                    Err(_) => stats.failed.push(manager.display_path(&path)),
+                }
+                throttle.after_unit(content.len() as u64);
+            }
+
+            stats.parsed += batch.len();
+            stats.batches += 1;
+            sink(batch);
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc;
+    use std::thread;
+
+    fn files(n: usize) -> Vec<(String, String)> {
+        (0..n)
+            .map(|i| (format!("f{}.ts", i), format!("function f{}() {{}}", i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_in_flight_bounded_by_credits() {
+        let indexer = StreamingIndexer::new(2, 2);
+        let gate = indexer.gate();
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let (tx, rx) = mpsc::channel::<Vec<ParseResult>>();
+
+        let producer_in_flight = in_flight.clone();
+        let producer = thread::spawn(move || {
+            let mut manager = LanguageManager::new();
+            indexer.run(&mut manager, files(9), |batch| {
+                let now = producer_in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                assert!(now <= 2, "{} batches in flight", now);
+                tx.send(batch).unwrap();
+            })
+        });
+
+        let mut received = 0;
+        for batch in rx {
+            received += batch.len();
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            gate.ack(1);
+        }
+
+        let stats = producer.join().unwrap();
+        assert_eq!(received, 9);
+        assert_eq!(stats.batches, 5);
+        assert_eq!(stats.parsed, 9);
+        assert!(!stats.cancelled);
+    }
+
+    #[test]
+    fn test_ack_does_not_exceed_capacity() {
+        let gate = CreditGate::new(2);
+        assert!(gate.acquire());
+        gate.ack(5);
+        assert_eq!(gate.available(), 2);
+    }
+
+    #[test]
+    fn test_close_cancels_stream() {
+        let indexer = StreamingIndexer::new(1, 1);
+        let gate = indexer.gate();
+        let mut manager = LanguageManager::new();
+
+        let stats = indexer.run(&mut manager, files(3), |_| gate.close());
+        assert_eq!(stats.batches, 1);
+        assert!(stats.cancelled);
+    }
+}
@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::hooks::{EntityContext, EntityHook};
+
+/// 实体内容哈希（blake3，十六进制）
+pub fn content_hash(text: &str) -> String {
+    blake3::hash(text.as_bytes()).to_hex().to_string()
+}
+
+/// 缓存的摘要 / 向量
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedSummary {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// 磁盘上的一行记录（JSON Lines）
+#[derive(Serialize, Deserialize)]
+struct CacheLine {
+    hash: String,
+    #[serde(flatten)]
+    entry: CachedSummary,
+}
+
+/// 按实体内容哈希缓存调用方生成的摘要和向量
+///
+/// 内容不变的实体在多次重建索引之间复用结果，不再重复调用 LLM / 向量模型。
+/// 持久化格式为 JSON Lines，`save` 通过临时文件 + rename 原子写入。
+#[derive(Debug, Default)]
+pub struct SummaryCache {
+    path: Option<PathBuf>,
+    entries: HashMap<String, CachedSummary>,
+    dirty: bool,
+}
+
+impl SummaryCache {
+    /// 创建内存缓存（不持久化）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 打开持久化缓存，文件不存在时为空缓存
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        let mut entries = HashMap::new();
+
+        if path.exists() {
+            let file = fs::File::open(&path)
+                .map_err(|e| format!("Failed to open summary cache {}: {}", path.display(), e))?;
+            for (i, line) in BufReader::new(file).lines().enumerate() {
+                let line = line.map_err(|e| format!("Failed to read summary cache: {}", e))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record: CacheLine = serde_json::from_str(&line)
+                    .map_err(|e| format!("Invalid summary cache entry at line {}: {}", i + 1, e))?;
+                entries.insert(record.hash, record.entry);
+            }
+        }
+
+        Ok(Self {
+            path: Some(path),
+            entries,
+            dirty: false,
+        })
+    }
+
+    /// 条目数量
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 按实体文本查找
+    pub fn get(&self, text: &str) -> Option<&CachedSummary> {
+        self.entries.get(&content_hash(text))
+    }
+
+    /// 按内容哈希查找
+    pub fn get_by_hash(&self, hash: &str) -> Option<&CachedSummary> {
+        self.entries.get(hash)
+    }
+
+    /// 写入摘要
+    pub fn put_summary(&mut self, text: &str, summary: String) {
+        self.entries.entry(content_hash(text)).or_default().summary = Some(summary);
+        self.dirty = true;
+    }
+
+    /// 写入向量
+    pub fn put_embedding(&mut self, text: &str, embedding: Vec<f32>) {
+        self.entries.entry(content_hash(text)).or_default().embedding = Some(embedding);
+        self.dirty = true;
+    }
+
+    /// 只保留给定哈希集合中的条目（清理已删除的实体），返回移除数量
+    pub fn retain_hashes<'a>(&mut self, hashes: impl IntoIterator<Item = &'a str>) -> usize {
+        let keep: std::collections::HashSet<&str> = hashes.into_iter().collect();
+        let before = self.entries.len();
+        self.entries.retain(|hash, _| keep.contains(hash.as_str()));
+        let removed = before - self.entries.len();
+        self.dirty |= removed > 0;
+        removed
+    }
+
+    /// 是否有未保存的修改
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// 保存到打开时的路径（内存缓存直接返回）
+    pub fn save(&mut self) -> Result<(), String> {
+        let Some(path) = self.path.clone() else {
+            return Ok(());
+        };
+        if !self.dirty && path.exists() {
+            return Ok(());
+        }
+        self.save_to(&path)?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// 保存到指定路径（按哈希排序，便于 diff）
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+
+        let tmp = path.with_extension("tmp");
+        let write = || -> std::io::Result<()> {
+            let mut writer = BufWriter::new(fs::File::create(&tmp)?);
+            let mut hashes: Vec<_> = self.entries.keys().collect();
+            hashes.sort();
+            for hash in hashes {
+                let line = CacheLine {
+                    hash: hash.clone(),
+                    entry: self.entries[hash].clone(),
+                };
+                serde_json::to_writer(&mut writer, &line)?;
+                writer.write_all(b"\n")?;
+            }
+            writer.flush()?;
+            fs::rename(&tmp, path)
+        };
+        write().map_err(|e| format!("Failed to save summary cache {}: {}", path.display(), e))
+    }
+}
+
+/// 带缓存的实体钩子：命中缓存时直接返回摘要，未命中才调用内部钩子并写回
+pub struct CachedHook<H> {
+    cache: Arc<Mutex<SummaryCache>>,
+    inner: H,
+}
+
+impl<H: EntityHook> CachedHook<H> {
+    pub fn new(cache: Arc<Mutex<SummaryCache>>, inner: H) -> Self {
+        Self { cache, inner }
+    }
+}
+
+impl<H: EntityHook> EntityHook for CachedHook<H> {
+    fn process(&mut self, entity: &EntityContext) -> Option<String> {
+        let hash = content_hash(entity.text);
+        {
+            let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(summary) = cache.get_by_hash(&hash).and_then(|c| c.summary.clone()) {
+                return Some(summary);
+            }
+        }
+
+        let summary = self.inner.process(entity)?;
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache.entries.entry(hash).or_default().summary = Some(summary.clone());
+        cache.dirty = true;
+        Some(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LanguageManager;
+
+    #[test]
+    fn test_roundtrip() {
+        let path = std::env::temp_dir().join(format!("synapse-summary-{}.jsonl", std::process::id()));
+        let mut cache = SummaryCache::open(&path).unwrap();
+        cache.put_summary("function a() {}", "does a".to_string());
+        cache.put_embedding("function a() {}", vec![0.5, 1.0]);
+        cache.save().unwrap();
+
+        let reopened = SummaryCache::open(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let entry = reopened.get("function a() {}").unwrap();
+        assert_eq!(entry.summary.as_deref(), Some("does a"));
+        assert_eq!(entry.embedding, Some(vec![0.5, 1.0]));
+    }
+
+    #[test]
+    fn test_cached_hook_skips_unchanged_entities() {
+        let cache = Arc::new(Mutex::new(SummaryCache::new()));
+        let calls = Arc::new(Mutex::new(0));
+
+        let counter = calls.clone();
+        let mut manager = LanguageManager::new();
+        manager.set_entity_hook(CachedHook::new(cache.clone(), move |entity: &EntityContext| {
+            *counter.lock().unwrap() += 1;
+            entity.name.map(|name| format!("summary of {}", name))
+        }));
+
+        let code = "function a() {}\nfunction b() {}\n";
+        manager.parse_file("a.ts", code).unwrap();
+        manager.parse_file("a.ts", code).unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), 2);
+        assert_eq!(cache.lock().unwrap().len(), 2);
+    }
+}
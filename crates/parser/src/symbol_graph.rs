@@ -0,0 +1,1136 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::graph_filter::GraphFilter;
+use crate::index::entity_id;
+use crate::paths::normalize_path;
+use crate::types::{EntityInfo, ImportDeclaration, Modification, ModificationKind, ParseResult, Range};
+
+/// re-export 链的最大追踪深度
+const MAX_REEXPORT_DEPTH: usize = 8;
+
+/// TS / JS 相对导入尝试的扩展名（按优先级）
+const SCRIPT_EXTENSIONS: &[&str] = &[".ts", ".tsx", ".d.ts", ".mts", ".cts", ".js", ".jsx", ".mjs", ".cjs"];
+
+/// 文件之间的导入边
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileEdge {
+    pub from: String,
+    pub to: String,
+    /// 导入语句中的模块路径
+    pub source: String,
+    /// 两个文件之间只有仅类型的导入（`import type`、全部带 `type` 修饰的具名导入），编译后不构成运行时依赖
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub is_type_only: bool,
+}
+
+/// 导入的符号到定义的边
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolEdge {
+    /// 导入方文件
+    pub file: String,
+    /// 导入的名字（定义处的原名）
+    pub name: String,
+    /// 定义所在文件（经 re-export 追踪后的最终文件）
+    pub target_file: String,
+    /// 定义实体的 ID（与 CodeIndex 的实体 ID 一致）
+    pub definition_id: String,
+    pub kind: String,
+    pub range: Range,
+    /// 以 `import type` 或 `type` 修饰导入，只在类型位置使用
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub is_type_only: bool,
+}
+
+/// JSX 组件使用到组件定义的边
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentEdge {
+    /// 使用方文件
+    pub file: String,
+    /// 使用方组件；顶层 JSX 为 None
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub caller: Option<String>,
+    /// 元素名（`UserCard`、`Icons.Star`）
+    pub element: String,
+    /// 组件定义所在文件
+    pub target_file: String,
+    /// 组件定义实体的 ID（与 CodeIndex 的实体 ID 一致）
+    pub definition_id: String,
+    /// 元素在使用方文件中的范围
+    pub range: Range,
+}
+
+/// 运行时修改（monkey-patching）到被修改模块的边
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModifiesEdge {
+    /// 进行修改的文件
+    pub file: String,
+    /// 被修改的目标（`utils.format`、`Model.prototype.save`）
+    pub target: String,
+    pub kind: ModificationKind,
+    /// 被修改的导入所在的文件
+    pub target_file: String,
+    /// 被修改的定义实体的 ID；找不到定义时为 None
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub definition_id: Option<String>,
+    /// 修改语句在修改方文件中的范围
+    pub range: Range,
+}
+
+/// 无法在本批文件中解析的导入（第三方包、标准库等）
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnresolvedImport {
+    pub file: String,
+    pub source: String,
+}
+
+/// 跨文件引用图
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolGraph {
+    pub file_edges: Vec<FileEdge>,
+    pub symbol_edges: Vec<SymbolEdge>,
+    pub component_edges: Vec<ComponentEdge>,
+    /// 动态导入（`import()`、`require()`、`importlib`）的字面量目标构成的文件边
+    pub dynamic_edges: Vec<FileEdge>,
+    /// 模块顶层对导入名字的重新赋值与原型补丁
+    pub modifies_edges: Vec<ModifiesEdge>,
+    pub unresolved: Vec<UnresolvedImport>,
+}
+
+/// 外部包的使用情况（见 `import_summary`）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageUsage {
+    /// 包名（npm 包、Python 顶层包、crate、Go 模块、Java 包或 C/C++ 头文件）
+    pub package: String,
+    /// 导入该包的文件的语言
+    pub languages: Vec<String>,
+    /// 导入语句数（含 re-export）
+    pub import_count: usize,
+    /// 导入该包的文件
+    pub files: Vec<String>,
+    /// 具名导入的名字
+    pub specifiers: Vec<String>,
+}
+
+/// 解析一批文件之间的导入，生成 文件→文件 与 符号→定义 的边
+///
+/// 只在本批文件内解析：路径统一经 `normalize_path`，因此 Windows 与 POSIX 路径可以混用。
+/// 无法定位到文件的导入记入 `unresolved`；能定位文件但找不到定义的符号只产生文件边。
+/// JSX 元素按本文件的定义或导入解析到组件定义，生成 `component_edges`。
+/// 对导入的名字重新赋值、对其成员或原型打补丁的语句生成 `modifies_edges`（全局对象的补丁不在本批文件中，不产生边）。
+/// `filter.exclude_barrels` 时经由桶文件的导入直接连到定义所在的文件（整体导入连到桶文件展开后的全部来源）。
+pub fn build_symbol_graph(results: &[ParseResult]) -> SymbolGraph {
+    build_symbol_graph_filtered(results, &GraphFilter::default())
+}
+
+/// 同 `build_symbol_graph`，但 `filter` 排除的文件（测试、生成代码）不产生也不接收边
+///
+/// 被排除的文件仍参与导入解析，指向它们的导入不会被误记为 `unresolved`。
+pub fn build_symbol_graph_filtered(results: &[ParseResult], filter: &GraphFilter) -> SymbolGraph {
+    let files = FileTable::new(results);
+    // (导入方, 被导入方) -> (模块路径, 是否仅类型)
+    let mut file_edges: BTreeMap<(String, String), (String, bool)> = BTreeMap::new();
+    let mut symbol_edges = BTreeSet::new();
+    let mut component_edges = BTreeSet::new();
+    let mut dynamic_edges = BTreeMap::new();
+    let mut modifies_edges = BTreeSet::new();
+    let mut unresolved = BTreeSet::new();
+
+    let skipped_barrel = |path: &str| filter.exclude_barrels && files.files.get(path).is_some_and(|r| r.is_barrel);
+    let excluded = |path: &str| {
+        skipped_barrel(path) || files.files.get(path).is_some_and(|r| filter.excludes_file(path, r.is_generated))
+    };
+    for (path, result) in files.files.iter().filter(|(path, _)| !excluded(path)) {
+        // re-export 同样构成依赖
+        let reexports = result.exports.iter().filter_map(|export| {
+            Some(ImportDeclaration {
+                source: export.source.clone()?,
+                specifiers: export.specifiers.clone(),
+                is_type_only: export.is_type_only,
+                is_wildcard: export.is_wildcard,
+                ..Default::default()
+            })
+        });
+        for import in result.imports.iter().cloned().chain(reexports) {
+            let mut targets = files.resolve(&import, path, &result.language);
+            // 跳过桶文件后直接依赖的文件
+            let mut flattened = Vec::new();
+
+            for name in &import.specifiers {
+                let mut found = files.find_in(&targets, name);
+                // 具名导入也可能是子模块（Python `from pkg import mod`、Rust `use a::{b}`）
+                if found.is_none() {
+                    if let Some(source) = submodule_source(&result.language, &import.source, name) {
+                        let submodule = ImportDeclaration {
+                            source,
+                            ..Default::default()
+                        };
+                        targets.extend(files.resolve(&submodule, path, &result.language));
+                        found = files.find_in(&targets, name);
+                    }
+                }
+                if let Some((target_file, definition_id, kind, range)) = found.filter(|(file, ..)| !excluded(file)) {
+                    if targets.iter().any(|target| skipped_barrel(target)) {
+                        flattened.push(target_file.clone());
+                    }
+                    symbol_edges.insert(SymbolEdge {
+                        file: path.clone(),
+                        name: name.clone(),
+                        target_file,
+                        definition_id,
+                        kind,
+                        range,
+                        is_type_only: import.is_type_only || import.type_specifiers.contains(name),
+                    });
+                }
+            }
+
+            if targets.is_empty() {
+                unresolved.insert(UnresolvedImport {
+                    file: path.clone(),
+                    source: import.source.clone(),
+                });
+            }
+            if import.specifiers.is_empty() {
+                for barrel in targets.iter().filter(|target| skipped_barrel(target)) {
+                    flattened.extend(files.barrel_sources(barrel, 0));
+                }
+            }
+            for target in targets.into_iter().chain(flattened) {
+                if target != *path && !excluded(&target) {
+                    // 任一导入在运行时使用，文件边就不是仅类型的
+                    file_edges
+                        .entry((path.clone(), target))
+                        .and_modify(|(_, type_only)| *type_only &= import.is_type_only)
+                        .or_insert_with(|| (import.source.clone(), import.is_type_only));
+                }
+            }
+        }
+
+        for import in dynamic_sources(result) {
+            let targets = files.resolve(&import, path, &result.language);
+            if targets.is_empty() {
+                unresolved.insert(UnresolvedImport {
+                    file: path.clone(),
+                    source: import.source.clone(),
+                });
+            }
+            for target in targets {
+                if target != *path && !excluded(&target) {
+                    dynamic_edges
+                        .entry((path.clone(), target))
+                        .or_insert_with(|| import.source.clone());
+                }
+            }
+        }
+
+        for usage in &result.element_usages {
+            let Some((target_file, definition_id, ..)) = files.resolve_element(path, result, &usage.element) else {
+                continue;
+            };
+            if excluded(&target_file) {
+                continue;
+            }
+            component_edges.insert(ComponentEdge {
+                file: path.clone(),
+                caller: usage.caller.clone(),
+                element: usage.element.clone(),
+                target_file,
+                definition_id,
+                range: usage.range.clone(),
+            });
+        }
+
+        for modification in &result.modifications {
+            let Some((target_file, definition_id)) = files.resolve_modification(path, result, modification) else {
+                continue;
+            };
+            if target_file == *path || excluded(&target_file) {
+                continue;
+            }
+            modifies_edges.insert(ModifiesEdge {
+                file: path.clone(),
+                target: modification.target.clone(),
+                kind: modification.kind,
+                target_file,
+                definition_id,
+                range: modification.range.clone(),
+            });
+        }
+    }
+
+    SymbolGraph {
+        file_edges: file_edges
+            .into_iter()
+            .map(|((from, to), (source, is_type_only))| FileEdge {
+                from,
+                to,
+                source,
+                is_type_only,
+            })
+            .collect(),
+        symbol_edges: symbol_edges.into_iter().collect(),
+        component_edges: component_edges.into_iter().collect(),
+        dynamic_edges: dynamic_edges
+            .into_iter()
+            .map(|((from, to), source)| FileEdge {
+                from,
+                to,
+                source,
+                is_type_only: false,
+            })
+            .collect(),
+        modifies_edges: modifies_edges.into_iter().collect(),
+        unresolved: unresolved.into_iter().collect(),
+    }
+}
+
+/// 汇总一批文件对外部包的导入：按包名去重，附带导入次数、导入文件与导入的名字
+///
+/// 只统计支持导入解析的语言（TS / JS、Python、Go、Rust、Java、C / C++、Protobuf）；能在本批文件中解析的
+/// 导入与相对路径导入不计入。动态导入的字面量目标（`require('lodash')`）同样计入。结果按导入次数从多到少、再按包名排序。
+pub fn import_summary(results: &[ParseResult]) -> Vec<PackageUsage> {
+    import_summary_filtered(results, &GraphFilter::default())
+}
+
+/// 同 `import_summary`，但不统计 `filter` 排除的文件（如只看生产代码的依赖）
+pub fn import_summary_filtered(results: &[ParseResult], filter: &GraphFilter) -> Vec<PackageUsage> {
+    let files = FileTable::new(results);
+    let mut packages: BTreeMap<String, PackageUsage> = BTreeMap::new();
+
+    for (path, result) in &files.files {
+        if filter.excludes_file(path, result.is_generated) {
+            continue;
+        }
+        let reexports = result.exports.iter().filter_map(|export| {
+            Some(ImportDeclaration {
+                source: export.source.clone()?,
+                specifiers: export.specifiers.clone(),
+                ..Default::default()
+            })
+        });
+        for import in result.imports.iter().cloned().chain(reexports).chain(dynamic_sources(result)) {
+            let Some(package) = package_name(&result.language, &import.source) else {
+                continue;
+            };
+            if !files.resolve(&import, path, &result.language).is_empty() {
+                continue;
+            }
+            let usage = packages.entry(package.clone()).or_insert_with(|| PackageUsage {
+                package,
+                ..Default::default()
+            });
+            usage.languages.push(result.language.clone());
+            usage.import_count += 1;
+            usage.files.push(path.clone());
+            usage.specifiers.extend(import.specifiers);
+        }
+    }
+
+    let mut summary: Vec<PackageUsage> = packages.into_values().collect();
+    for usage in &mut summary {
+        for list in [&mut usage.languages, &mut usage.files, &mut usage.specifiers] {
+            list.sort();
+            list.dedup();
+        }
+    }
+    summary.sort_by(|a, b| b.import_count.cmp(&a.import_count).then_with(|| a.package.cmp(&b.package)));
+    summary
+}
+
+/// 动态导入中的字面量目标（运行时计算的目标无法解析，跳过）
+fn dynamic_sources(result: &ParseResult) -> impl Iterator<Item = ImportDeclaration> + '_ {
+    result.dynamic_imports.iter().filter_map(|import| {
+        Some(ImportDeclaration {
+            source: import.source.clone()?,
+            file_path: result.file_path.clone(),
+            ..Default::default()
+        })
+    })
+}
+
+/// 导入路径所属的外部包；相对导入、本 crate 路径与不支持的语言返回 None
+fn package_name(language: &str, source: &str) -> Option<String> {
+    let source = source.trim();
+    if source.is_empty() {
+        return None;
+    }
+    match language {
+        "TypeScript" | "JavaScript" => {
+            if source.starts_with('.') || source.starts_with('/') {
+                return None;
+            }
+            // `@scope/pkg/sub` 取 `@scope/pkg`，`pkg/sub` 取 `pkg`
+            let take = if source.starts_with('@') { 2 } else { 1 };
+            Some(source.split('/').take(take).collect::<Vec<_>>().join("/"))
+        }
+        "Python" => (!source.starts_with('.')).then(|| source.split('.').next().unwrap_or(source).to_string()),
+        "Rust" => {
+            let root = source.trim_start_matches("::").split("::").next().unwrap_or(source);
+            (!matches!(root, "crate" | "self" | "super")).then(|| root.to_string())
+        }
+        "Go" => {
+            // 带域名的取模块路径（`github.com/owner/repo`），标准库取完整包路径
+            let segments: Vec<&str> = source.split('/').collect();
+            let take = if segments[0].contains('.') { 3 } else { segments.len() };
+            Some(segments[..take.min(segments.len())].join("/"))
+        }
+        "Java" | "C" | "C++" => Some(source.to_string()),
+        "Protobuf" => Some(source.to_string()),
+        _ => None,
+    }
+}
+
+/// 按规范化路径索引的文件
+struct FileTable<'a> {
+    files: BTreeMap<String, &'a ParseResult>,
+}
+
+impl<'a> FileTable<'a> {
+    fn new(results: &'a [ParseResult]) -> Self {
+        Self {
+            files: results.iter().map(|r| (normalize_path(&r.file_path), r)).collect(),
+        }
+    }
+
+    fn exact(&self, path: &str) -> Option<String> {
+        let path = normalize_path(path);
+        self.files.contains_key(&path).then_some(path)
+    }
+
+    /// 以 `suffix` 结尾的文件（按路径组件匹配），多个候选时取与 `near` 共同前缀最长的
+    fn by_suffix(&self, suffix: &str, near: &str) -> Option<String> {
+        self.files
+            .keys()
+            .filter(|path| ends_with_path(path, suffix))
+            .max_by_key(|path| (common_prefix(path, near), std::cmp::Reverse(path.len())))
+            .cloned()
+    }
+
+    /// 目录以 `dir_suffix` 结尾的全部文件（同一目录，取离 `near` 最近的目录）
+    fn dir_by_suffix(&self, dir_suffix: &str, near: &str, language: &str) -> Vec<String> {
+        let dir = self
+            .files
+            .iter()
+            .filter(|(path, r)| r.language == language && ends_with_path(parent_dir(path), dir_suffix))
+            .map(|(path, _)| parent_dir(path))
+            .max_by_key(|dir| (common_prefix(dir, near), std::cmp::Reverse(dir.len())));
+        let Some(dir) = dir else {
+            return Vec::new();
+        };
+        self.files
+            .iter()
+            .filter(|(path, r)| r.language == language && parent_dir(path) == dir)
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    /// 解析导入对应的文件
+    fn resolve(&self, import: &ImportDeclaration, importer: &str, language: &str) -> Vec<String> {
+        let source = import.source.as_str();
+        match language {
+            "TypeScript" | "JavaScript" => self.resolve_script(source, importer).into_iter().collect(),
+            "Python" => self.resolve_python(source, importer).into_iter().collect(),
+            "Go" => self.resolve_go(source, importer),
+            "Rust" => self.resolve_rust(source, importer).into_iter().collect(),
+            "Java" => self.resolve_java(import, importer),
+            "C" | "C++" => self
+                .exact(&join(parent_dir(importer), source))
+                .or_else(|| self.by_suffix(source, importer))
+                .into_iter()
+                .collect(),
+            // 导入路径相对于 `--proto_path` 根目录
+            "Protobuf" => self.by_suffix(source, importer).into_iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn resolve_script(&self, source: &str, importer: &str) -> Option<String> {
+        // 只解析相对导入，包名导入交给调用方
+        if !(source.starts_with("./") || source.starts_with("../") || source == "." || source == "..") {
+            return None;
+        }
+        let base = normalize_path(&join(parent_dir(importer), source));
+        // ESM 风格的 `./a.js` 实际指向 `a.ts`
+        let stem = [".js", ".jsx", ".mjs", ".cjs"]
+            .iter()
+            .find_map(|ext| base.strip_suffix(ext))
+            .unwrap_or(&base);
+
+        std::iter::once(base.clone())
+            .chain(SCRIPT_EXTENSIONS.iter().map(|ext| format!("{}{}", stem, ext)))
+            .chain(SCRIPT_EXTENSIONS.iter().map(|ext| format!("{}/index{}", base, ext)))
+            .find_map(|candidate| self.exact(&candidate))
+    }
+
+    fn resolve_python(&self, source: &str, importer: &str) -> Option<String> {
+        let dots = source.len() - source.trim_start_matches('.').len();
+        let module = source[dots..].replace('.', "/");
+        if dots > 0 {
+            // 相对导入：一个点为当前包，每多一个点上移一层
+            let mut dir = parent_dir(importer).to_string();
+            for _ in 1..dots {
+                dir = join(&dir, "..");
+            }
+            let base = normalize_path(&join(&dir, &module));
+            return self
+                .exact(&format!("{}.py", base))
+                .or_else(|| self.exact(&format!("{}/__init__.py", base)));
+        }
+        self.by_suffix(&format!("{}.py", module), importer)
+            .or_else(|| self.by_suffix(&format!("{}/__init__.py", module), importer))
+    }
+
+    fn resolve_go(&self, source: &str, importer: &str) -> Vec<String> {
+        let segments: Vec<&str> = source.split('/').collect();
+        // 标准库（首段不含 `.`）只接受完整路径匹配，避免与同名本地目录混淆
+        let min = if segments[0].contains('.') { 1 } else { segments.len() };
+        (min..=segments.len())
+            .rev()
+            .map(|k| self.dir_by_suffix(&segments[segments.len() - k..].join("/"), importer, "Go"))
+            .find(|files| !files.is_empty())
+            .unwrap_or_default()
+    }
+
+    fn resolve_rust(&self, source: &str, importer: &str) -> Option<String> {
+        let mut segments: Vec<&str> = source.split("::").collect();
+        let base = match segments.first().copied() {
+            Some("crate") => {
+                segments.remove(0);
+                self.rust_crate_root(importer)?
+            }
+            Some("self") | Some("super") => {
+                let mut dir = rust_module_dir(importer);
+                while segments.first() == Some(&"self") {
+                    segments.remove(0);
+                }
+                while segments.first() == Some(&"super") {
+                    segments.remove(0);
+                    dir = parent_dir(&dir).to_string();
+                }
+                dir
+            }
+            // 外部 crate / 2018 版本的本模块相对路径
+            _ => rust_module_dir(importer),
+        };
+
+        // 最长的可解析前缀即模块文件，其余为模块内的条目
+        (1..=segments.len()).rev().find_map(|i| {
+            let path = join(&base, &segments[..i].join("/"));
+            self.exact(&format!("{}.rs", path))
+                .or_else(|| self.exact(&format!("{}/mod.rs", path)))
+        })
+    }
+
+    /// 包含 lib.rs / main.rs 的最近祖先目录
+    fn rust_crate_root(&self, importer: &str) -> Option<String> {
+        let mut dir = parent_dir(importer);
+        loop {
+            if ["lib.rs", "main.rs"].iter().any(|root| self.files.contains_key(&join(dir, root))) {
+                return Some(dir.to_string());
+            }
+            if dir.is_empty() || dir == "." || dir == "/" {
+                return None;
+            }
+            dir = parent_dir(dir);
+        }
+    }
+
+    fn resolve_java(&self, import: &ImportDeclaration, importer: &str) -> Vec<String> {
+        let package = import.source.replace('.', "/");
+        if import.is_wildcard {
+            return self.dir_by_suffix(&package, importer, "Java");
+        }
+        let mut targets = Vec::new();
+        for name in &import.specifiers {
+            // 类导入 `a.b.C` 或静态导入 `a.b.C.member`
+            let found = self
+                .by_suffix(&format!("{}/{}.java", package, name), importer)
+                .or_else(|| self.by_suffix(&format!("{}.java", package), importer));
+            targets.extend(found);
+        }
+        targets.dedup();
+        targets
+    }
+
+    /// JSX 元素对应的组件定义：本文件中的定义优先（有 `component` 实体时取之），其次按导入（具名、默认、`* as ns` 的成员）解析
+    fn resolve_element(&self, path: &str, result: &ParseResult, element: &str) -> Option<(String, String, String, Range)> {
+        let (head, member) = match element.split_once('.') {
+            Some((head, member)) => (head, Some(member.rsplit('.').next().unwrap_or(member))),
+            None => (element, None),
+        };
+        if member.is_none() {
+            let local = result
+                .entity_info
+                .iter()
+                .filter(|info| info.name.as_deref() == Some(head) && !matches!(info.kind.as_str(), "import" | "comment"))
+                .min_by_key(|info| info.kind != "component");
+            if let Some(info) = local {
+                let id = entity_id(path, &info.range, &info.kind, Some(head));
+                return Some((path.to_string(), id, info.kind.clone(), info.range.clone()));
+            }
+        }
+
+        result.imports.iter().find_map(|import| {
+            let name = if import.namespace_import.as_deref() == Some(head) {
+                member?
+            } else if import.default_import.as_deref() == Some(head) || import.specifiers.iter().any(|s| s == head) {
+                head
+            } else {
+                return None;
+            };
+            let targets = self.resolve(import, path, &result.language);
+            self.find_in(&targets, name)
+        })
+    }
+
+    /// 运行时修改的目标文件与被修改的定义：命名空间导入（`import * as ns`、Python `import mod`）取被修改的成员，
+    /// 其余导入取导入的名字本身；能定位文件但找不到定义时只返回文件
+    fn resolve_modification(
+        &self,
+        path: &str,
+        result: &ParseResult,
+        modification: &Modification,
+    ) -> Option<(String, Option<String>)> {
+        let source = modification.source.as_deref()?;
+        let object = modification.object.as_str();
+        let import = result.imports.iter().find(|import| {
+            import.source == source
+                && (import.namespace_import.as_deref() == Some(object)
+                    || import.default_import.as_deref() == Some(object)
+                    || import.specifiers.iter().any(|s| s == object))
+        })?;
+        let name = match modification.target.split('.').nth(1) {
+            Some(member) if import.namespace_import.as_deref() == Some(object) && member != "prototype" => member,
+            _ => object,
+        };
+        let targets = self.resolve(import, path, &result.language);
+        match self.find_in(&targets, name) {
+            Some((target_file, definition_id, ..)) => Some((target_file, Some(definition_id))),
+            None => Some((targets.into_iter().next()?, None)),
+        }
+    }
+
+    /// 桶文件 re-export 的来源文件（沿嵌套的桶文件展开）
+    fn barrel_sources(&self, path: &str, depth: usize) -> Vec<String> {
+        let Some(result) = self.files.get(path) else {
+            return Vec::new();
+        };
+        let mut sources = Vec::new();
+        for source in result.exports.iter().filter_map(|export| export.source.as_deref()) {
+            let import = ImportDeclaration {
+                source: source.to_string(),
+                ..Default::default()
+            };
+            for target in self.resolve(&import, path, &result.language) {
+                match self.files.get(&target) {
+                    Some(r) if r.is_barrel && depth < MAX_REEXPORT_DEPTH => {
+                        sources.extend(self.barrel_sources(&target, depth + 1))
+                    }
+                    _ => sources.push(target),
+                }
+            }
+        }
+        sources
+    }
+
+    /// 在目标文件中查找名字的定义
+    fn find_in(&self, targets: &[String], name: &str) -> Option<(String, String, String, Range)> {
+        targets.iter().find_map(|target| self.find_definition(target, name, 0))
+    }
+
+    /// 查找文件中名字的定义，优先导出的实体（同名的 React 组件实体优先于对应的函数 / 类），并沿 re-export 追踪
+    fn find_definition(&self, path: &str, name: &str, depth: usize) -> Option<(String, String, String, Range)> {
+        let result = self.files.get(path)?;
+        let candidates = || result.entity_info.iter().filter(|info| info.name.as_deref() == Some(name));
+        let preferred = |info: &&EntityInfo| info.kind != "component";
+        if let Some(info) = candidates()
+            .filter(|info| info.is_exported)
+            .min_by_key(preferred)
+            .or_else(|| candidates().min_by_key(preferred))
+        {
+            let id = entity_id(path, &info.range, &info.kind, Some(name));
+            return Some((path.to_string(), id, info.kind.clone(), info.range.clone()));
+        }
+
+        if depth >= MAX_REEXPORT_DEPTH {
+            return None;
+        }
+        result
+            .exports
+            .iter()
+            .filter(|export| export.is_wildcard || export.specifiers.iter().any(|s| s == name))
+            .filter_map(|export| export.source.as_deref())
+            .flat_map(|source| {
+                let import = ImportDeclaration {
+                    source: source.to_string(),
+                    ..Default::default()
+                };
+                self.resolve(&import, path, &result.language)
+            })
+            .find_map(|target| self.find_definition(&target, name, depth + 1))
+    }
+}
+
+/// 具名导入作为子模块时的模块路径
+fn submodule_source(language: &str, source: &str, name: &str) -> Option<String> {
+    match language {
+        "Python" if source.ends_with('.') => Some(format!("{}{}", source, name)),
+        "Python" => Some(format!("{}.{}", source, name)),
+        "Rust" => Some(format!("{}::{}", source, name)),
+        _ => None,
+    }
+}
+
+/// Rust 文件对应模块的子模块目录：mod.rs / lib.rs / main.rs 为所在目录，其余为同名目录
+fn rust_module_dir(path: &str) -> String {
+    let dir = parent_dir(path);
+    let file = path.rsplit('/').next().unwrap_or(path);
+    match file.strip_suffix(".rs") {
+        Some("mod" | "lib" | "main") | None => dir.to_string(),
+        Some(stem) => join(dir, stem),
+    }
+}
+
+fn parent_dir(path: &str) -> &str {
+    path.rfind('/').map_or("", |i| if i == 0 { "/" } else { &path[..i] })
+}
+
+fn join(dir: &str, path: &str) -> String {
+    match dir {
+        "" => path.to_string(),
+        "/" => format!("/{}", path),
+        _ => format!("{}/{}", dir, path),
+    }
+}
+
+fn ends_with_path(path: &str, suffix: &str) -> bool {
+    path == suffix || path.strip_suffix(suffix).is_some_and(|rest| rest.ends_with('/'))
+}
+
+fn common_prefix(a: &str, b: &str) -> usize {
+    a.split('/').zip(b.split('/')).take_while(|(x, y)| x == y).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DynamicImportKind;
+    use crate::LanguageManager;
+
+    fn parse(files: &[(&str, &str)]) -> Vec<ParseResult> {
+        let mut manager = LanguageManager::new();
+        files.iter().map(|(path, code)| manager.parse_file(path, code).unwrap()).collect()
+    }
+
+    fn file_edges(graph: &SymbolGraph) -> Vec<(&str, &str)> {
+        graph.file_edges.iter().map(|e| (e.from.as_str(), e.to.as_str())).collect()
+    }
+
+    #[test]
+    fn test_typescript_imports_and_reexports() {
+        let results = parse(&[
+            ("src/app.ts", "import { add, Widget } from './lib';\nimport { readFile } from 'fs';\n"),
+            ("src/lib/index.ts", "export * from './math';\nexport { Widget } from './widget.js';\n"),
+            ("src/lib/math.ts", "export function add(a: number, b: number) { return a + b; }\n"),
+            ("src/lib/widget.ts", "export class Widget {}\n"),
+        ]);
+        let graph = build_symbol_graph(&results);
+
+        assert_eq!(
+            file_edges(&graph),
+            vec![
+                ("src/app.ts", "src/lib/index.ts"),
+                ("src/lib/index.ts", "src/lib/math.ts"),
+                ("src/lib/index.ts", "src/lib/widget.ts"),
+            ]
+        );
+        let symbols: Vec<(&str, &str, &str)> = graph
+            .symbol_edges
+            .iter()
+            .map(|e| (e.name.as_str(), e.target_file.as_str(), e.kind.as_str()))
+            .collect();
+        assert_eq!(
+            symbols,
+            vec![
+                ("Widget", "src/lib/widget.ts", "class"),
+                ("add", "src/lib/math.ts", "function"),
+                // re-export 本身也指向定义
+                ("Widget", "src/lib/widget.ts", "class"),
+            ]
+        );
+        assert_eq!(graph.symbol_edges[1].definition_id, "src/lib/math.ts:1:function:add");
+        assert_eq!(graph.unresolved.len(), 1);
+        assert_eq!(graph.unresolved[0].source, "fs");
+    }
+
+    #[test]
+    fn test_type_only_imports() {
+        let results = parse(&[
+            ("src/app.ts", "import type { Config } from './config';\nimport { type Props, render } from './view';\n"),
+            ("src/config.ts", "export interface Config {}\n"),
+            ("src/view.ts", "export interface Props {}\nexport function render() {}\n"),
+        ]);
+        let graph = build_symbol_graph(&results);
+
+        let files: Vec<(&str, bool)> = graph.file_edges.iter().map(|e| (e.to.as_str(), e.is_type_only)).collect();
+        assert_eq!(files, vec![("src/config.ts", true), ("src/view.ts", false)]);
+        let symbols: Vec<(&str, bool)> = graph
+            .symbol_edges
+            .iter()
+            .map(|e| (e.name.as_str(), e.is_type_only))
+            .collect();
+        assert_eq!(symbols, vec![("Config", true), ("Props", true), ("render", false)]);
+    }
+
+    #[test]
+    fn test_dynamic_imports() {
+        let results = parse(&[
+            (
+                "src/app.js",
+                "const fs = require('fs');\nconst util = require(\"./util\");\nasync function load(name) {\n  await import('./pages/home');\n  return import(`./pages/${name}`);\n}\n",
+            ),
+            ("src/util.js", "module.exports = {};\n"),
+            ("src/pages/home.ts", "export default function Home() {}\n"),
+        ]);
+        let app = &results[0];
+        let dynamic: Vec<(Option<&str>, DynamicImportKind)> = app
+            .dynamic_imports
+            .iter()
+            .map(|import| (import.source.as_deref(), import.kind))
+            .collect();
+        assert_eq!(
+            dynamic,
+            vec![
+                (Some("fs"), DynamicImportKind::Require),
+                (Some("./util"), DynamicImportKind::Require),
+                (Some("./pages/home"), DynamicImportKind::Import),
+                (None, DynamicImportKind::Import),
+            ]
+        );
+        assert_eq!(app.dynamic_imports[3].expression, "`./pages/${name}`");
+
+        let graph = build_symbol_graph(&results);
+        assert!(graph.file_edges.is_empty());
+        let edges: Vec<(&str, &str)> = graph.dynamic_edges.iter().map(|e| (e.from.as_str(), e.to.as_str())).collect();
+        assert_eq!(edges, vec![("src/app.js", "src/pages/home.ts"), ("src/app.js", "src/util.js")]);
+        assert_eq!(graph.unresolved[0].source, "fs");
+        assert_eq!(import_summary(&results)[0].package, "fs");
+    }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_python_importlib() {
+        let results = parse(&[
+            (
+                "app/main.py",
+                "import importlib\n\ndef load(name):\n    plugins = importlib.import_module('app.plugins')\n    return __import__(name)\n",
+            ),
+            ("app/plugins.py", "def run():\n    pass\n"),
+        ]);
+        let sources: Vec<Option<&str>> = results[0].dynamic_imports.iter().map(|i| i.source.as_deref()).collect();
+        assert_eq!(sources, vec![Some("app.plugins"), None]);
+        assert!(results[0].dynamic_imports.iter().all(|i| i.kind == DynamicImportKind::Importlib));
+
+        let graph = build_symbol_graph(&results);
+        assert_eq!(graph.dynamic_edges[0].to, "app/plugins.py");
+    }
+
+    #[test]
+    fn test_modifies_edges() {
+        let results = parse(&[
+            (
+                "src/patch.ts",
+                "import * as utils from './utils';\nimport { Model } from './model';\n\nutils.format = (s) => s.trim();\nModel.prototype.save = function () {};\nObject.defineProperty(utils, 'parse', { value: JSON.parse });\nArray.prototype.last = function () { return this[this.length - 1]; };\n\nfunction Local() {}\nLocal.prototype.run = function () {};\nconst config = {};\nconfig.debug = true;\n",
+            ),
+            ("src/utils.ts", "export function format(s: string) { return s; }\nexport function parse(s: string) { return s; }\n"),
+            ("src/model.ts", "export class Model {}\n"),
+        ]);
+        let modifications: Vec<(&str, ModificationKind)> = results[0]
+            .modifications
+            .iter()
+            .map(|m| (m.target.as_str(), m.kind))
+            .collect();
+        assert_eq!(
+            modifications,
+            vec![
+                ("utils.format", ModificationKind::Patch),
+                ("Model.prototype.save", ModificationKind::Prototype),
+                ("utils.parse", ModificationKind::Patch),
+                ("Array.prototype.last", ModificationKind::Prototype),
+            ]
+        );
+        assert_eq!(results[0].modifications[3].source, None);
+
+        let graph = build_symbol_graph(&results);
+        let edges: Vec<(&str, &str)> = graph
+            .modifies_edges
+            .iter()
+            .map(|e| (e.target.as_str(), e.target_file.as_str()))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                ("Model.prototype.save", "src/model.ts"),
+                ("utils.format", "src/utils.ts"),
+                ("utils.parse", "src/utils.ts"),
+            ]
+        );
+        assert!(graph.modifies_edges.iter().all(|e| e.definition_id.is_some()));
+    }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_python_monkey_patching() {
+        let results = parse(&[
+            (
+                "app/patches.py",
+                "import os\nimport app.settings as settings\nfrom app.models import User, save\n\nos.getcwd = lambda: '/tmp'\nUser.full_name = property(lambda self: self.name)\nsave = wrapped(save)\nsetattr(settings, 'DEBUG', True)\n\ndef configure():\n    os.sep = '/'\n",
+            ),
+            ("app/settings.py", "DEBUG = False\n"),
+            ("app/models.py", "class User:\n    pass\n\ndef save(user):\n    pass\n"),
+        ]);
+        let modifications: Vec<(&str, ModificationKind)> = results[0]
+            .modifications
+            .iter()
+            .map(|m| (m.target.as_str(), m.kind))
+            .collect();
+        assert_eq!(
+            modifications,
+            vec![
+                ("os.getcwd", ModificationKind::Patch),
+                ("User.full_name", ModificationKind::Patch),
+                ("save", ModificationKind::Reassign),
+                ("settings.DEBUG", ModificationKind::Patch),
+            ]
+        );
+
+        let graph = build_symbol_graph(&results);
+        let edges: Vec<(&str, &str, bool)> = graph
+            .modifies_edges
+            .iter()
+            .map(|e| (e.target.as_str(), e.target_file.as_str(), e.definition_id.is_some()))
+            .collect();
+        assert_eq!(edges[0], ("User.full_name", "app/models.py", true));
+        assert_eq!(edges[1], ("save", "app/models.py", true));
+        assert_eq!(edges[2].1, "app/settings.py");
+    }
+
+    #[test]
+    fn test_barrel_files_flattened() {
+        let results = parse(&[
+            ("src/app.ts", "import { add, Widget } from './lib';\n"),
+            ("src/view.ts", "import * as lib from './lib';\n"),
+            ("src/lib/index.ts", "export * from './shapes';\nexport { Widget } from './widget';\n"),
+            ("src/lib/shapes/index.ts", "export * from '../math';\n"),
+            ("src/lib/math.ts", "export function add(a: number, b: number) { return a + b; }\n"),
+            ("src/lib/widget.ts", "export class Widget {}\n"),
+        ]);
+        let barrels: Vec<&str> = results.iter().filter(|r| r.is_barrel).map(|r| r.file_path.as_str()).collect();
+        assert_eq!(barrels, vec!["src/lib/index.ts", "src/lib/shapes/index.ts"]);
+
+        let filter = GraphFilter {
+            exclude_barrels: true,
+            ..Default::default()
+        };
+        let graph = build_symbol_graph_filtered(&results, &filter);
+        assert_eq!(
+            file_edges(&graph),
+            vec![
+                ("src/app.ts", "src/lib/math.ts"),
+                ("src/app.ts", "src/lib/widget.ts"),
+                ("src/view.ts", "src/lib/math.ts"),
+                ("src/view.ts", "src/lib/widget.ts"),
+            ]
+        );
+        let symbols: Vec<(&str, &str)> = graph
+            .symbol_edges
+            .iter()
+            .map(|e| (e.name.as_str(), e.target_file.as_str()))
+            .collect();
+        assert_eq!(symbols, vec![("Widget", "src/lib/widget.ts"), ("add", "src/lib/math.ts")]);
+        assert!(graph.unresolved.is_empty());
+    }
+
+    #[test]
+    fn test_filtered_graph_skips_tests_and_generated() {
+        let results = parse(&[
+            ("src/app.ts", "import { User } from './__generated__/schema';
+import { add } from './math';
+"),
+            ("src/math.ts", "export function add(a: number, b: number) { return a + b; }
+"),
+            ("src/math.test.ts", "import { add } from './math';
+"),
+            ("src/__generated__/schema.ts", "export class User {}
+"),
+        ]);
+        assert_eq!(build_symbol_graph(&results).file_edges.len(), 3);
+
+        let graph = build_symbol_graph_filtered(&results, &GraphFilter::all());
+        assert_eq!(file_edges(&graph), vec![("src/app.ts", "src/math.ts")]);
+        let names: Vec<&str> = graph.symbol_edges.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["add"]);
+        // 指向生成代码的导入能解析，只是不连边
+        assert!(graph.unresolved.is_empty());
+    }
+
+    #[test]
+    fn test_import_summary() {
+        let results = parse(&[
+            (
+                "src/app.ts",
+                "import React, { useState } from 'react';\nimport { add } from './math';\nimport { z } from 'zod';\n",
+            ),
+            ("src/view.tsx", "import { useEffect } from 'react';\nimport { Button } from '@acme/ui/button';\n"),
+            ("src/math.ts", "export * from 'lodash/fp';\nexport function add(a: number, b: number) { return a + b; }\n"),
+            ("src/app.test.ts", "import { describe } from 'vitest';\nimport { z } from 'zod';\n"),
+            ("src/missing.ts", "import { gone } from './gone';\n"),
+        ]);
+
+        let summary = import_summary(&results);
+        let packages: Vec<(&str, usize)> = summary.iter().map(|p| (p.package.as_str(), p.import_count)).collect();
+        assert_eq!(
+            packages,
+            vec![("react", 2), ("zod", 2), ("@acme/ui", 1), ("lodash", 1), ("vitest", 1)]
+        );
+        assert_eq!(summary[0].files, vec!["src/app.ts", "src/view.tsx"]);
+        assert_eq!(summary[0].specifiers, vec!["useEffect", "useState"]);
+        assert_eq!(summary[0].languages, vec!["TypeScript"]);
+
+        let production = import_summary_filtered(&results, &GraphFilter::all());
+        assert!(production.iter().all(|p| p.package != "vitest"));
+        assert_eq!(production.iter().find(|p| p.package == "zod").unwrap().files, vec!["src/app.ts"]);
+    }
+
+    #[test]
+    fn test_package_name() {
+        assert_eq!(package_name("Python", "numpy.linalg").as_deref(), Some("numpy"));
+        assert_eq!(package_name("Python", ".models"), None);
+        assert_eq!(package_name("Rust", "serde::de::Deserialize").as_deref(), Some("serde"));
+        assert_eq!(package_name("Rust", "crate::index"), None);
+        assert_eq!(package_name("Go", "github.com/spf13/cobra/doc").as_deref(), Some("github.com/spf13/cobra"));
+        assert_eq!(package_name("Go", "net/http").as_deref(), Some("net/http"));
+        assert_eq!(package_name("Ruby", "json"), None);
+    }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_python_modules() {
+        let results = parse(&[
+            ("pkg/main.py", "from pkg.util import helper\nfrom . import models\nfrom .models import User\n"),
+            ("pkg/util.py", "def helper():\n    pass\n"),
+            ("pkg/models/__init__.py", "class User:\n    pass\n"),
+        ]);
+        let graph = build_symbol_graph(&results);
+
+        assert_eq!(
+            file_edges(&graph),
+            vec![("pkg/main.py", "pkg/models/__init__.py"), ("pkg/main.py", "pkg/util.py")]
+        );
+        let names: Vec<&str> = graph.symbol_edges.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["User", "helper"]);
+    }
+
+    #[cfg(feature = "rust-lang")]
+    #[test]
+    fn test_rust_module_paths() {
+        let results = parse(&[
+            ("src/lib.rs", "mod net;\nuse crate::net::client::Client;\n"),
+            ("src/net/mod.rs", "pub mod client;\nuse self::client::connect;\n"),
+            ("src/net/client.rs", "pub struct Client;\npub fn connect() {}\nuse super::super::lib_helper;\n"),
+        ]);
+        let graph = build_symbol_graph(&results);
+
+        assert!(file_edges(&graph).contains(&("src/lib.rs", "src/net/client.rs")));
+        assert!(file_edges(&graph).contains(&("src/net/mod.rs", "src/net/client.rs")));
+        let names: Vec<&str> = graph.symbol_edges.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["Client", "connect"]);
+    }
+
+    #[cfg(all(feature = "go", feature = "java"))]
+    #[test]
+    fn test_go_packages_and_java_classes() {
+        let results = parse(&[
+            ("cmd/main.go", "package main\n\nimport (\n\t\"fmt\"\n\t\"github.com/acme/app/pkg/util\"\n)\n"),
+            ("pkg/util/a.go", "package util\n\nfunc A() {}\n"),
+            ("pkg/util/b.go", "package util\n\nfunc B() {}\n"),
+            ("src/com/acme/App.java", "import com.acme.model.User;\n\npublic class App {}\n"),
+            ("src/com/acme/model/User.java", "public class User {}\n"),
+        ]);
+        let graph = build_symbol_graph(&results);
+
+        assert_eq!(
+            file_edges(&graph),
+            vec![
+                ("cmd/main.go", "pkg/util/a.go"),
+                ("cmd/main.go", "pkg/util/b.go"),
+                ("src/com/acme/App.java", "src/com/acme/model/User.java"),
+            ]
+        );
+        assert_eq!(graph.unresolved[0].source, "fmt");
+        assert_eq!(graph.symbol_edges[0].name, "User");
+    }
+
+    #[test]
+    fn test_jsx_component_edges() {
+        let files = [
+            (
+                "src/pages/Profile.tsx",
+                "import UserCard from '../components/UserCard';\nimport * as Icons from '../components/icons';\n\nfunction Badge() {\n  return <span>badge</span>;\n}\n\nexport function Profile() {\n  return (\n    <div>\n      <UserCard name=\"a\" />\n      <Icons.Star />\n      <Badge />\n    </div>\n  );\n}\n",
+            ),
+            (
+                "src/components/UserCard.tsx",
+                "export default function UserCard(props: { name: string }) {\n  return <p>{props.name}</p>;\n}\n",
+            ),
+            ("src/components/icons.jsx", "export const Star = () => <svg />;\n"),
+        ];
+        let results = parse(&files);
+        let usages: Vec<&str> = results[0].element_usages.iter().map(|u| u.element.as_str()).collect();
+        assert_eq!(usages, vec!["UserCard", "Icons.Star", "Badge"]);
+        assert!(results[0].errors.is_empty());
+
+        let graph = build_symbol_graph(&results);
+        let edges: Vec<(Option<&str>, &str, &str)> = graph
+            .component_edges
+            .iter()
+            .map(|e| (e.caller.as_deref(), e.element.as_str(), e.target_file.as_str()))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                (Some("Profile"), "Badge", "src/pages/Profile.tsx"),
+                (Some("Profile"), "Icons.Star", "src/components/icons.jsx"),
+                (Some("Profile"), "UserCard", "src/components/UserCard.tsx"),
+            ]
+        );
+
+        // 识别 React 组件时，边指向组件实体而不是同名的函数实体
+        let mut manager = LanguageManager::new();
+        manager.set_options(crate::types::ParseOptions {
+            react_components: true,
+            ..Default::default()
+        });
+        let results: Vec<_> = files.iter().map(|(path, code)| manager.parse_file(path, code).unwrap()).collect();
+        let graph = build_symbol_graph(&results);
+        assert_eq!(graph.component_edges.len(), 3);
+        assert!(graph.component_edges.iter().all(|e| e.definition_id.contains(":component:")));
+    }
+
+    #[test]
+    fn test_windows_paths() {
+        let results = parse(&[
+            ("C:\\repo\\src\\a.ts", "import { b } from './b';\n"),
+            ("C:\\repo\\src\\b.ts", "export const b = () => 1;\n"),
+        ]);
+        let graph = build_symbol_graph(&results);
+        assert_eq!(file_edges(&graph), vec![("C:/repo/src/a.ts", "C:/repo/src/b.ts")]);
+    }
+}
@@ -0,0 +1,38 @@
+//! 单元测试共用的辅助函数
+
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// 测试用的临时目录，离开作用域（包括断言失败）时删除
+pub(crate) struct TempDir(PathBuf);
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// 创建空的临时目录 `synapse-<prefix>-<name>-<pid>`（规范化后的路径）
+///
+/// 同一模块的测试用不同的 `name`，以免并行运行时互相干扰。
+pub(crate) fn temp_dir(prefix: &str, name: &str) -> TempDir {
+    let dir = std::env::temp_dir().join(format!("synapse-{}-{}-{}", prefix, name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    TempDir(fs::canonicalize(dir).unwrap())
+}
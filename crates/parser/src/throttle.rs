@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 后台索引的资源限制（避免抢占交互式 MCP 请求）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ThrottleOptions {
+    /// 最大工作线程数（None 表示使用全部 CPU）
+    pub max_threads: Option<usize>,
+    /// 类似 nice 的让步等级：0 不限制，每升一级少占 5% CPU，最高 19（约 5%）
+    pub nice: u8,
+    /// 读取速率上限（字节/秒，None 表示不限制）
+    pub io_bytes_per_sec: Option<u64>,
+}
+
+impl ThrottleOptions {
+    /// 实际使用的线程数（不超过可用并行度，至少为 1）
+    pub fn thread_count(&self) -> usize {
+        let available = thread::available_parallelism().map_or(1, |n| n.get());
+        self.max_threads.map_or(available, |max| max.clamp(1, available))
+    }
+
+    /// 是否需要节流
+    pub fn is_unlimited(&self) -> bool {
+        self.nice == 0 && self.io_bytes_per_sec.is_none()
+    }
+}
+
+/// 单个工作线程的节流器
+///
+/// 每处理完一个单元（通常是一个文件）调用 `after_unit`：按 nice 等级让出
+/// 与工作时长成比例的 CPU 时间，并按累计读取字节数限制 IO 速率。
+#[derive(Debug)]
+pub struct Throttle {
+    options: ThrottleOptions,
+    started: Instant,
+    unit_started: Instant,
+    bytes: u64,
+}
+
+impl Throttle {
+    /// 创建节流器
+    pub fn new(options: ThrottleOptions) -> Self {
+        let now = Instant::now();
+        Self {
+            options,
+            started: now,
+            unit_started: now,
+            bytes: 0,
+        }
+    }
+
+    /// 节流配置
+    pub fn options(&self) -> &ThrottleOptions {
+        &self.options
+    }
+
+    /// 标记一个工作单元开始
+    pub fn begin_unit(&mut self) {
+        self.unit_started = Instant::now();
+    }
+
+    /// 工作单元结束，`bytes_read` 为本单元读取的字节数
+    pub fn after_unit(&mut self, bytes_read: u64) {
+        if self.options.is_unlimited() {
+            return;
+        }
+
+        let now = Instant::now();
+        self.bytes += bytes_read;
+
+        let cpu_delay = nice_delay(now - self.unit_started, self.options.nice);
+        let io_delay = self
+            .options
+            .io_bytes_per_sec
+            .map_or(Duration::ZERO, |rate| io_delay(self.bytes, now - self.started, rate));
+
+        let delay = cpu_delay.max(io_delay);
+        if delay.is_zero() {
+            if self.options.nice > 0 {
+                thread::yield_now();
+            }
+        } else {
+            thread::sleep(delay);
+        }
+    }
+}
+
+/// nice 等级对应的让步时长：占用比例为 `(20 - nice) / 20`
+fn nice_delay(work: Duration, nice: u8) -> Duration {
+    let nice = u32::from(nice.min(19));
+    if nice == 0 {
+        return Duration::ZERO;
+    }
+    work * nice / (20 - nice)
+}
+
+/// 达到速率上限所需的等待时长
+fn io_delay(total_bytes: u64, elapsed: Duration, bytes_per_sec: u64) -> Duration {
+    if bytes_per_sec == 0 {
+        return Duration::ZERO;
+    }
+    let expected = Duration::from_secs_f64(total_bytes as f64 / bytes_per_sec as f64);
+    expected.saturating_sub(elapsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nice_delay() {
+        let work = Duration::from_millis(10);
+        assert_eq!(nice_delay(work, 0), Duration::ZERO);
+        // nice 10：约 50% CPU，睡眠与工作等长
+        assert_eq!(nice_delay(work, 10), work);
+        // 超过 19 按 19 处理
+        assert_eq!(nice_delay(work, 50), nice_delay(work, 19));
+    }
+
+    #[test]
+    fn test_io_delay() {
+        // 1 秒内读了 2MB，上限 1MB/s，还需等待 1 秒
+        assert_eq!(io_delay(2_000_000, Duration::from_secs(1), 1_000_000), Duration::from_secs(1));
+        assert_eq!(io_delay(500_000, Duration::from_secs(1), 1_000_000), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_thread_count() {
+        let options = ThrottleOptions { max_threads: Some(0), ..Default::default() };
+        assert_eq!(options.thread_count(), 1);
+        assert!(ThrottleOptions::default().thread_count() >= 1);
+    }
+}
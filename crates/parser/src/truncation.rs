@@ -0,0 +1,94 @@
+//! 代码片段的长度上限（见 `ParseOptions::entity_cap`）
+//!
+//! 超长的定义（巨型 Java 方法、生成的查找表）保留签名与首尾若干行，中间替换为一行截断标记，
+//! 这样检索结果仍能看出定义的形状，又不会让单个实体占满上下文。
+
+use crate::types::{EntityCapOptions, ParseResult};
+
+/// 截断标记（单独占一行）
+fn marker(omitted_lines: usize) -> String {
+    format!("... [{} lines truncated] ...", omitted_lines)
+}
+
+/// 按实体类型的上限截断结果中的代码片段，并标记 `is_truncated`
+pub(crate) fn apply_entity_caps(result: &mut ParseResult, options: &EntityCapOptions) {
+    for (text, info) in result.entities.iter_mut().zip(result.entity_info.iter_mut()) {
+        let Some(cap) = options.cap_for(&info.kind) else {
+            continue;
+        };
+        if text.chars().count() <= cap {
+            continue;
+        }
+        // 签名跨多行时整体保留
+        let signature_lines = info
+            .raw_signature
+            .as_deref()
+            .or(info.signature.as_deref())
+            .map_or(1, |signature| signature.lines().count());
+        let head = options.head_lines.max(signature_lines);
+        *text = truncate_text(text, cap, head, options.tail_lines);
+        info.is_truncated = true;
+    }
+}
+
+/// 保留开头 `head` 行与结尾 `tail` 行，仍超出上限时先减结尾、再减开头的行数，
+/// 最后对过长的行按字符截断
+fn truncate_text(text: &str, cap: usize, head: usize, tail: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let (mut head, mut tail) = (head.min(lines.len()), tail);
+    if head + tail >= lines.len() {
+        tail = lines.len() - head;
+    }
+
+    loop {
+        let omitted = lines.len() - head - tail;
+        let mut kept: Vec<String> = lines[..head].iter().map(|line| line.to_string()).collect();
+        if omitted > 0 {
+            kept.push(marker(omitted));
+        }
+        kept.extend(lines[lines.len() - tail..].iter().map(|line| line.to_string()));
+        let joined = kept.join("\n");
+        if joined.chars().count() <= cap || (head <= 1 && tail == 0) {
+            return cut_chars(&joined, cap);
+        }
+        if tail > 0 {
+            tail -= 1;
+        } else {
+            head -= 1;
+        }
+    }
+}
+
+/// 按字符截断（不切开多字节字符），被截断时末尾追加标记
+fn cut_chars(text: &str, cap: usize) -> String {
+    if text.chars().count() <= cap {
+        return text.to_string();
+    }
+    let end = text.char_indices().nth(cap).map_or(text.len(), |(i, _)| i);
+    format!("{}\n... [truncated] ...", &text[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_keeps_head_and_tail() {
+        let text: Vec<String> = (1..=100).map(|i| format!("line {}", i)).collect();
+        let truncated = truncate_text(&text.join("\n"), 200, 3, 2);
+        assert_eq!(truncated, "line 1\nline 2\nline 3\n... [95 lines truncated] ...\nline 99\nline 100");
+    }
+
+    #[test]
+    fn test_truncate_shrinks_to_cap() {
+        let text: Vec<String> = (1..=100).map(|i| format!("line {}", i)).collect();
+        let truncated = truncate_text(&text.join("\n"), 40, 5, 5);
+        assert!(truncated.chars().count() <= 40);
+        assert!(truncated.starts_with("line 1\n"));
+        assert!(truncated.contains("lines truncated"));
+
+        // 单行超长的文本按字符截断
+        let long = "x".repeat(50);
+        assert_eq!(truncate_text(&long, 10, 3, 2), format!("{}\n... [truncated] ...", "x".repeat(10)));
+    }
+}
@@ -1,4 +1,9 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::paths::{canonical_path, redact_path, PathMode};
 
 /// 代码实体的统一枚举类型
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,6 +13,7 @@ pub enum CodeEntity {
     Class(ClassEntity),
     Interface(InterfaceEntity),
     Variable(VariableEntity),
+    Module(ModuleEntity),
 }
 
 /// 函数实体
@@ -22,6 +28,9 @@ pub struct FunctionEntity {
     pub return_type: Option<String>,
     pub calls: Vec<String>,
     pub is_exported: bool,
+    /// 是否为环境声明（`declare` / `.d.ts`），没有实现体
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_ambient: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comments: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
@@ -41,6 +50,9 @@ pub struct ClassEntity {
     pub methods: Vec<FunctionEntity>,
     pub properties: Vec<PropertyEntity>,
     pub is_exported: bool,
+    /// 是否为环境声明（`declare` / `.d.ts`），没有实现体
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_ambient: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comments: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
@@ -57,6 +69,8 @@ pub struct InterfaceEntity {
     pub extends: Vec<String>,
     pub methods: Vec<MethodSignature>,
     pub is_exported: bool,
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_ambient: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comments: Option<String>,
 }
@@ -70,6 +84,20 @@ pub struct VariableEntity {
     pub var_type: Option<String>,
     pub is_exported: bool,
     pub is_const: bool,
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_ambient: bool,
+}
+
+/// 模块实体（TypeScript `declare module` / `namespace` 块）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleEntity {
+    pub name: String,
+    pub file_path: String,
+    pub range: Range,
+    pub is_exported: bool,
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_ambient: bool,
 }
 
 /// 属性实体
@@ -81,6 +109,9 @@ pub struct PropertyEntity {
     pub prop_type: Option<String>,
     pub is_static: bool,
     pub visibility: Visibility,
+    /// 只读（TS `readonly`、Java `final`、Python `Final[...]`）
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_readonly: bool,
 }
 
 /// 方法签名
@@ -99,8 +130,8 @@ pub struct Parameter {
     pub is_optional: bool,
 }
 
-/// 注解信息（用于 Java/TypeScript 装饰器等）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 注解信息（用于 Java/TypeScript 装饰器、Swift 属性包装器等）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Annotation {
     pub name: String,
@@ -109,7 +140,7 @@ pub struct Annotation {
 }
 
 /// 源码范围（行号）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Range {
     pub start: usize,
     pub end: usize,
@@ -122,23 +153,198 @@ pub enum Visibility {
     Public,
     Private,
     Protected,
+    /// 包/crate 内可见（Go 小写、Rust `pub(crate)`、Java 包级、Swift `internal`）
+    Internal,
 }
 
 /// Import 声明
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// `source` 为模块路径（TS 的模块说明符、Python 的点分模块、Rust 的路径前缀等）。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ImportDeclaration {
     pub source: String,
+    /// 具名导入的原始名字（`import { a as b }` 记为 `a`）
     pub specifiers: Vec<String>,
     pub file_path: String,
+    /// `import type` / 全部具名导入均带 `type` 修饰
     pub is_type_only: bool,
+    /// 带 `type` 修饰的具名导入（`import { type A, b }` 记为 `A`），只在类型位置使用
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub type_specifiers: Vec<String>,
+    /// 默认导入的本地名（`import React from 'react'`）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub default_import: Option<String>,
+    /// 以命名空间方式绑定的本地名（`* as ns`、Python `import os.path as p`、Go 包别名）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub namespace_import: Option<String>,
+    /// 通配导入（`from x import *`、`java.io.*`、`use a::*`、Go 点导入）
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_wildcard: bool,
 }
 
 /// Export 声明
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ExportDeclaration {
+    /// 对外可见的名字（别名优先，默认导出记为 `default`）
     pub specifiers: Vec<String>,
     pub file_path: String,
+    /// 重新导出的来源模块（`export { a } from './a'`、Rust `pub use`）
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_type_only: bool,
+    /// `export * from './a'`
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_wildcard: bool,
+}
+
+/// 动态导入的形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DynamicImportKind {
+    /// `import('./a')`
+    Import,
+    /// CommonJS `require('./a')`
+    Require,
+    /// Python `importlib.import_module('pkg.mod')` / `__import__('pkg')`
+    Importlib,
+}
+
+/// 动态导入：运行时加载的模块（import 语句之外的依赖）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DynamicImport {
+    /// 字面量目标（不含插值的字符串）；运行时计算的为 None
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub source: Option<String>,
+    /// 模块参数的原文
+    pub expression: String,
+    pub kind: DynamicImportKind,
+    pub range: Range,
+}
+
+/// 运行时修改的形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModificationKind {
+    /// 模块顶层对导入的名字重新赋值（Python `from m import f` 后 `f = wrap(f)`）
+    Reassign,
+    /// 对导入的模块 / 对象的成员赋值（`fs.readFile = ...`、`setattr(os, 'getcwd', ...)`）
+    Patch,
+    /// 原型补丁（`Array.prototype.last = ...`、`Object.assign(Foo.prototype, ...)`）
+    Prototype,
+}
+
+/// 模块顶层的运行时修改（monkey-patching）：修改了导入的名字或全局对象的原型
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Modification {
+    /// 被修改的目标（`fs.readFile`、`Array.prototype.last`）
+    pub target: String,
+    /// 目标的根名字（导入的绑定或全局对象：`fs`、`Array`）
+    pub object: String,
+    /// 根名字所属导入的模块路径；全局对象为 None
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     pub source: Option<String>,
+    pub kind: ModificationKind,
+    pub range: Range,
+}
+
+/// Markdown 文档中的链接（行内链接、图片、自动链接与链接引用定义）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentLink {
+    /// 链接文字（图片为替代文本，引用定义为标签）
+    pub text: String,
+    /// 链接目标（URL、相对路径或锚点）
+    pub target: String,
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_image: bool,
+    pub range: Range,
+}
+
+/// 调用关系：调用方实体 → 被调用名
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallReference {
+    /// 调用方函数 / 方法名；顶层代码中的调用为 None
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub caller: Option<String>,
+    /// 调用方定义的范围（与 entity_info 中对应实体的范围一致）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub caller_range: Option<Range>,
+    /// 被调用的名字，成员调用只保留最后一段（`a.b.c()` 记为 `c`）
+    pub callee: String,
+    /// 调用表达式的范围
+    pub range: Range,
+}
+
+/// JSX 元素的使用：组件 → 其渲染的组件元素（`<UserCard />`）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElementUsage {
+    /// 使用方函数 / 组件名；顶层 JSX 为 None
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub caller: Option<String>,
+    /// 使用方定义的范围（与 entity_info 中对应实体的范围一致）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub caller_range: Option<Range>,
+    /// 元素名（`UserCard`、`Icons.Star`）
+    pub element: String,
+    /// 元素的范围（含子元素）
+    pub range: Range,
+}
+
+/// 类型关系的种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelationKind {
+    /// 继承父类（协议继承其他协议也记为 extends）
+    Extends,
+    /// 遵循协议
+    Conforms,
+    /// 嵌入（Go 结构体的匿名字段、接口中嵌入的接口）
+    Embeds,
+}
+
+/// 类型关系：类型 → 父类 / 所遵循的协议 / 嵌入的类型
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeRelation {
+    /// 声明关系的类型名（扩展为被扩展的类型名）
+    pub type_name: String,
+    /// 父类、协议或被嵌入的类型名（去掉泛型参数）
+    pub target: String,
+    pub kind: RelationKind,
+    /// 继承子句所在的范围
+    pub range: Range,
+}
+
+/// React 组件的种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentKind {
+    /// 函数组件（含 `memo` / `forwardRef` 包装的）
+    Function,
+    /// 继承 `React.Component` / `PureComponent` 的类组件
+    Class,
+}
+
+/// React 组件的详情（`component` 实体，见 `ParseOptions::react_components`）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentInfo {
+    pub kind: ComponentKind,
+    /// props 的类型名（`ButtonProps`）；未标注类型时为 None
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub props_type: Option<String>,
+    /// 使用的 hook（`useState`、`useQuery`），按首次出现的顺序
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub hooks: Vec<String>,
+    /// 渲染的 JSX 元素名（含 `div` 等内置标签），按首次出现的顺序
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub children: Vec<String>,
 }
 
 /// 解析错误
@@ -148,16 +354,585 @@ pub struct ParseError {
     pub range: Option<Range>,
 }
 
-/// 解析结果（新版本 - 支持多语言）
+/// 实体元数据（新版 ParseResult，与 `entities` 按下标一一对应）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityInfo {
+    /// 捕获类型（function / class / import / comment ...）
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub range: Range,
+    /// 是否属于文件的公开 API（export / pub / public / 首字母大写 / `__all__`）
+    pub is_exported: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+    /// 声明签名的单行显示形式（定义体之前的部分，见 `pretty_signature`）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub signature: Option<String>,
+    /// 源码中签名的原始切片（保留换行、注释与注解；与 `signature` 相同时省略）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub raw_signature: Option<String>,
+    /// 紧贴在定义之前的文档注释（Python 为 docstring），已去掉注释符号
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub doc: Option<String>,
+    /// 实体钩子生成的摘要（见 `LanguageManager::set_entity_hook`）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub summary: Option<String>,
+    /// 所在目录的 README / OWNERS 摘要（项目扫描时附加）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub context: Option<String>,
+    /// 规模指标（`ParseOptions::metrics` 开启时计算）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub metrics: Option<EntityMetrics>,
+    /// 函数 / 方法的复杂度（`ParseOptions::complexity` 开启时计算）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub complexity: Option<ComplexityMetrics>,
+    /// 代码片段超过 `ParseOptions::entity_cap` 的上限，中间部分已截去
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_truncated: bool,
+    /// 外层类型 / 命名空间实体在 `entity_info` 中的下标（见 `ParseResult::entity_tree`）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub parent: Option<usize>,
+    /// 带外层类型与命名空间的限定名（`ParseOptions::qualified_names` 开启时填写）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub qualified_name: Option<String>,
+    /// 模板约束（C++20 `requires` 子句、concept 的约束表达式、Go 的类型参数声明），单行形式
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub constraints: Vec<String>,
+    /// 注解与属性（Swift 的 `@State`、`@Published` 等属性包装器）
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub annotations: Vec<Annotation>,
+    /// React 组件的详情（只有 `component` 实体填写）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub component: Option<ComponentInfo>,
+}
+
+/// 实体的规模指标，按定义所占的完整源码行计算（不受签名截取影响）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityMetrics {
+    pub lines: usize,
+    pub bytes: usize,
+    /// 按 `MetricsOptions::tokenizer` 估算的 token 数
+    pub tokens: usize,
+}
+
+/// 函数体的复杂度指标，按语法树计算（与语言无关的节点类型集合，见 `metrics` 模块）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComplexityMetrics {
+    /// 圈复杂度：1 + 分支、循环、case、catch、三元与短路逻辑运算的个数
+    pub cyclomatic: usize,
+    /// 控制结构（if / 循环 / switch / try）的最大嵌套层数，`else if` 不加深
+    pub max_nesting: usize,
+    /// 语句数（含声明语句，不含块本身与注释）
+    pub statements: usize,
+}
+
+/// 结构化实体（EntityInfo + 代码片段 + 语言），下游无需再解析字符串
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct StructuredEntity {
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qualified_name: Option<String>,
+    pub range: Range,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_signature: Option<String>,
+    pub language: String,
+    pub is_exported: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<EntityMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub complexity: Option<ComplexityMetrics>,
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_truncated: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub constraints: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub annotations: Vec<Annotation>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub component: Option<ComponentInfo>,
+    /// 提取的代码片段
+    pub text: String,
+}
+
+/// 解析选项（LanguageManager 使用）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ParseOptions {
+    /// 允许 Flow 类型注解（文件头带 `@flow` 时自动开启）
+    pub flow: bool,
+    /// 允许旧版装饰器语法（如对象字面量成员上的装饰器）
+    pub legacy_decorators: bool,
+    /// 输出路径的处理方式（结果、导入导出、错误信息中的文件路径）
+    pub path_mode: PathMode,
+    /// 项目根目录，`Relative` / `Anonymized` 据此计算相对路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_root: Option<String>,
+    /// 解析符号链接后再输出路径（同一文件经不同链接访问时记录一致）
+    pub resolve_symlinks: bool,
+    /// 大文件采样（None 表示总是完整提取）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<SamplingOptions>,
+    /// `.h` 头文件按哪种语言解析（默认按内容判断）
+    pub header_language: HeaderLanguage,
+    /// 为每个实体计算行数、字节数与 token 数（None 表示不计算）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<MetricsOptions>,
+    /// 为函数 / 方法计算圈复杂度、最大嵌套深度与语句数
+    pub complexity: bool,
+    /// 为嵌套在类型 / 命名空间中的实体填写限定名（扁平表使用）
+    pub qualified_names: bool,
+    /// 额外按代码片段文本去重（文本相同的实体只保留第一个，如完全相同的重载或辅助函数）
+    ///
+    /// 默认只按位置（定义的字节范围 + 捕获名）去重。
+    pub dedup_text: bool,
+    /// Markdown 围栏代码块按语言标记解析，块内实体并入结果（parent 指向所在的代码块）
+    pub parse_code_blocks: bool,
+    /// 识别 React 组件（TS / JS），每个组件额外输出一个 `component` 实体（见 `frameworks::react`）
+    pub react_components: bool,
+    /// 代码片段的长度上限，超出时保留签名与首尾若干行（None 表示不截断）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity_cap: Option<EntityCapOptions>,
+    /// 提取行为：注释、函数体、长度与文件大小上限、去重方式（见 `LanguageManager::with_config`）
+    pub config: ParserConfig,
+    /// 结果中实体的顺序（默认为 query 的捕获顺序）
+    pub entity_order: EntityOrder,
+    /// 按路径解析（`LanguageManager::parse_path`）时，不小于该大小（字节）的文件以内存映射读取
+    /// （需启用 `mmap` feature；None 表示总是普通读取）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mmap_threshold: Option<u64>,
+}
+
+/// 结果中实体的顺序（`entities` 与 `entity_info` 同步重排，`parent` 下标随之改写）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntityOrder {
+    /// query 的捕获顺序（导入、注释、函数可能交错）
+    #[default]
+    Capture,
+    /// 按源码位置：起始行，同一行时外层定义在前
+    Position,
+    /// 按类型分组（组按该类型首次出现的位置排列），组内按源码位置
+    Kind,
+}
+
+/// 提取行为的配置，对所有语言一致生效
+///
+/// 未改动的项沿用各语言策略的默认做法（例如 TypeScript 的函数片段只有签名，Java 的方法片段含方法体）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ParserConfig {
+    /// 提取注释实体
+    pub include_comments: bool,
+    /// 函数 / 方法片段包含函数体还是只有签名
+    pub bodies: BodyMode,
+    /// 片段长度上限（字符数），按 `EntityCapOptions` 的默认方式截断；`entity_cap` 已设置时以其为准
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_entity_length: Option<usize>,
+    /// 超过该大小（字节）的文件不解析，返回 `File too large` 错误
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_file_size: Option<usize>,
+    /// 实体去重方式
+    pub dedup: DedupMode,
+    /// 注释实体的去重方式（None 表示同 `dedup`）
+    ///
+    /// 去重只在单个文件内进行：不同文件中相同的注释（如各自的 `// TODO: fix race`）总会保留。
+    /// 设为 `Location` 时，即使代码片段按文本去重，同一文件内重复的注释也都保留。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment_dedup: Option<DedupMode>,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self {
+            include_comments: true,
+            bodies: BodyMode::default(),
+            max_entity_length: None,
+            max_file_size: None,
+            dedup: DedupMode::default(),
+            comment_dedup: None,
+        }
+    }
+}
+
+/// 函数 / 方法（含构造函数、运算符等）片段的内容
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyMode {
+    /// 由各语言策略决定
+    #[default]
+    Language,
+    /// 只保留签名（函数体之前的部分）
+    Signatures,
+    /// 完整定义（含函数体）
+    Full,
+}
+
+/// 实体去重方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DedupMode {
+    /// 按位置（定义的字节范围 + 捕获名）去重，文本相同的不同实体都保留
+    #[default]
+    Location,
+    /// 额外按片段文本去重（同 `ParseOptions::dedup_text`）
+    Text,
+}
+
+/// `.h` 头文件的语言（C / C++ / Objective-C 共用该扩展名）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeaderLanguage {
+    /// 按内容判断：Objective-C 指令、C++ 专有语法（class / template / namespace 等），否则为 C
+    #[default]
+    Auto,
+    C,
+    Cpp,
+    /// Objective-C（没有对应的语法，不解析）
+    Objc,
+}
+
+/// 大文件采样：只完整提取首尾各 `sample_bytes`，中间部分只保留顶层声明的签名
+///
+/// 用于大型生成文件，使其贡献大纲信息而不拖慢整体索引。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SamplingOptions {
+    /// 超过该大小（字节）的文件启用采样
+    pub threshold: usize,
+    /// 首尾各完整提取的字节数（按行边界取整）
+    pub sample_bytes: usize,
+}
+
+impl Default for SamplingOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 1024 * 1024,
+            sample_bytes: 64 * 1024,
+        }
+    }
+}
+
+/// 实体规模指标选项
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MetricsOptions {
+    pub tokenizer: Tokenizer,
+    /// `Chars` 分词器每个 token 对应的字符数
+    pub chars_per_token: usize,
+}
+
+impl Default for MetricsOptions {
+    fn default() -> Self {
+        Self {
+            tokenizer: Tokenizer::default(),
+            chars_per_token: 4,
+        }
+    }
+}
+
+/// 代码片段长度上限：超出时保留签名与首尾若干行，中间替换为截断标记
+///
+/// 元数据（范围、签名、指标）仍按完整定义计算，只有 `entities` 中的文本被截断。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EntityCapOptions {
+    /// 默认上限（字符数）
+    pub max_chars: usize,
+    /// 按实体类型覆盖上限（如 `method` → 2000）；0 表示该类型不截断
+    pub per_kind: BTreeMap<String, usize>,
+    /// 截断时保留的开头行数（签名更长时保留完整签名）
+    pub head_lines: usize,
+    /// 截断时保留的结尾行数
+    pub tail_lines: usize,
+}
+
+impl Default for EntityCapOptions {
+    fn default() -> Self {
+        Self {
+            max_chars: 8000,
+            per_kind: BTreeMap::new(),
+            head_lines: 20,
+            tail_lines: 5,
+        }
+    }
+}
+
+impl EntityCapOptions {
+    /// 实体类型适用的上限；不截断时为 None
+    pub fn cap_for(&self, kind: &str) -> Option<usize> {
+        let cap = self.per_kind.get(kind).copied().unwrap_or(self.max_chars);
+        (cap > 0).then_some(cap)
+    }
+}
+
+/// token 数的估算方式（见 `count_tokens`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tokenizer {
+    /// 近似 tiktoken（cl100k）的 BPE 计数：按其预分词规则切分后逐段估算
+    #[default]
+    Bpe,
+    /// 字符数除以 `chars_per_token`
+    Chars,
+    /// 空白分隔的单词数
+    Words,
+}
+
+impl ParseOptions {
+    /// 是否按片段文本去重（`dedup_text` 或 `config.dedup` 为 `Text`）
+    pub fn dedup_by_text(&self) -> bool {
+        self.dedup_text || self.config.dedup == DedupMode::Text
+    }
+
+    /// 注释实体是否按文本去重（`config.comment_dedup`，未设置时同 `dedup_by_text`）
+    pub fn dedup_comments_by_text(&self) -> bool {
+        self.config
+            .comment_dedup
+            .map_or_else(|| self.dedup_by_text(), |mode| mode == DedupMode::Text)
+    }
+
+    /// 生效的片段长度上限：`entity_cap`，未设置时由 `config.max_entity_length` 生成
+    pub fn effective_entity_cap(&self) -> Option<EntityCapOptions> {
+        self.entity_cap.clone().or_else(|| {
+            self.config.max_entity_length.map(|max_chars| EntityCapOptions {
+                max_chars,
+                ..Default::default()
+            })
+        })
+    }
+
+    /// 按 `path_mode` 改写后的输出路径
+    pub fn display_path(&self, path: &str) -> String {
+        if self.resolve_symlinks {
+            let root = self.project_root.as_deref().map(canonical_path);
+            return redact_path(&canonical_path(path), root.as_deref(), self.path_mode);
+        }
+        redact_path(path, self.project_root.as_deref(), self.path_mode)
+    }
+}
+
+/// 解析结果（新版本 - 支持多语言）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ParseResult {
     pub file_path: String,
     pub language: String,
     pub entities: Vec<String>, // 提取的代码片段
+    /// 每个代码片段的元数据（与 entities 一一对应）
+    #[serde(default)]
+    pub entity_info: Vec<EntityInfo>,
     pub imports: Vec<ImportDeclaration>,
     pub exports: Vec<ExportDeclaration>,
+    /// 动态导入（`import()`、`require()`、Python `importlib`）
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub dynamic_imports: Vec<DynamicImport>,
+    /// 调用关系（按出现顺序；采样模式下只含首尾窗口中的调用）
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub calls: Vec<CallReference>,
+    /// JSX 中使用的组件元素（TS / JS；HTML 内置标签不计）
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub element_usages: Vec<ElementUsage>,
+    /// 模块顶层对导入名字的重新赋值与原型补丁（TS / JS、Python）
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub modifications: Vec<Modification>,
+    /// Markdown 文档中的链接
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub links: Vec<DocumentLink>,
+    /// 类型关系：继承与协议遵循（Swift）、嵌入（Go）
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub type_relations: Vec<TypeRelation>,
     pub errors: Vec<ParseError>,
+    /// 是否为 TypeScript 声明文件（`.d.ts`），其中的实体均为环境声明
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_declaration_file: bool,
+    /// 大文件采样结果：中间部分只有顶层声明的签名
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_partial: bool,
+    /// 生成代码（路径约定或文件头的 `@generated`、`DO NOT EDIT` 等标记）
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_generated: bool,
+    /// 测试文件（`.test.ts`、`__tests__/`、`_test.go` 等路径约定）
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_test: bool,
+    /// 压缩产物（`.min.js` / `.min.css`）
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_minified: bool,
+    /// Storybook 故事文件（`.stories.tsx` / `.story.js`）
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_story: bool,
+    /// 桶文件：只有导入与 re-export、没有自身定义的 TS / JS 模块（如 `index.ts` 中的 `export * from './x'`）
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub is_barrel: bool,
+    /// 文件开头的指令（`"use client"`、`// @flow`、`//go:build` 等）
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub directives: Vec<Directive>,
+    /// 文件角色（见 `FileRole`）
+    #[serde(default)]
+    pub role: FileRole,
+    /// 源码内容的哈希（`content_hash`，blake3 十六进制；由 `LanguageManager::parse_if_changed` 填写）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub content_hash: Option<String>,
+    /// 文件级统计：各类实体的数量、总行数与解析耗时
+    #[serde(default)]
+    pub summary: FileSummary,
+}
+
+/// 文件级统计，调用方无需遍历实体列表即可展示
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileSummary {
+    /// 实体类型 -> 数量（与 `entity_info` 的 `kind` 一致）
+    pub kinds: BTreeMap<String, usize>,
+    /// 实体总数
+    pub entities: usize,
+    /// 源码总行数
+    pub lines: usize,
+    /// 解析耗时（微秒，含签名整理、指标、钩子等后处理；WebAssembly 中为 0）
+    pub elapsed_us: u64,
+}
+
+/// 文件级指令的形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DirectiveKind {
+    /// 指令序言（`"use client"`、`"use server"`、`"use strict"`）
+    Prologue,
+    /// 编译器 / 工具 pragma（`// @flow`、`//go:build`、`#pragma once`、`#![no_std]`、`from __future__ import`）
+    Pragma,
+}
+
+/// 文件开头的指令（影响路由、打包与编译语义）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Directive {
+    /// 指令名（`use client`、`@jsx`、`go:build`、`no_std`、`__future__`）
+    pub name: String,
+    /// 指令参数（`@jsx h` 的 `h`、`go:build` 的约束表达式）；没有时为 None
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub value: Option<String>,
+    pub kind: DirectiveKind,
+    /// 所在行（1 起）
+    pub line: usize,
+}
+
+/// 文件在项目中的角色（按路径约定与文件内容推断），用于按角色检索（如只看生产代码）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileRole {
+    /// 生产代码
+    #[default]
+    Source,
+    Test,
+    /// 工具与框架配置（`vite.config.ts`、`.eslintrc.js`、`setup.py` 等）
+    Config,
+    /// 构建 / 运维脚本（`scripts/` 目录、shebang、`gulpfile.js` 等）
+    Script,
+    /// 文档与示例（`docs/`、`examples/` 目录）
+    Docs,
+}
+
+impl FileRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Source => "source",
+            Self::Test => "test",
+            Self::Config => "config",
+            Self::Script => "script",
+            Self::Docs => "docs",
+        }
+    }
+}
+
+impl fmt::Display for FileRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FileRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "source" | "src" => Ok(Self::Source),
+            "test" | "tests" => Ok(Self::Test),
+            "config" => Ok(Self::Config),
+            "script" | "scripts" => Ok(Self::Script),
+            "docs" | "doc" => Ok(Self::Docs),
+            _ => Err(format!("Unknown file role: {}", s)),
+        }
+    }
+}
+
+/// 流式批量解析的进度
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchProgress {
+    /// 已完成（含失败）的文件数
+    pub completed: usize,
+    pub failed: usize,
+    pub total: usize,
+}
+
+/// 流式批量解析中单个文件的结果（`LanguageManager::parse_files_streaming`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileOutcome {
+    pub file_path: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub result: Option<ParseResult>,
+    /// 解析失败的原因（不支持的语言、读取失败等）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<String>,
+    /// 回调该文件时的累计进度
+    pub progress: BatchProgress,
+}
+
+impl ParseResult {
+    /// 按实体组合元数据与代码片段
+    pub fn structured_entities(&self) -> Vec<StructuredEntity> {
+        self.entities
+            .iter()
+            .zip(&self.entity_info)
+            .map(|(text, info)| StructuredEntity {
+                kind: info.kind.clone(),
+                name: info.name.clone(),
+                qualified_name: info.qualified_name.clone(),
+                range: info.range.clone(),
+                signature: info.signature.clone(),
+                raw_signature: info.raw_signature.clone(),
+                language: self.language.clone(),
+                is_exported: info.is_exported,
+                visibility: info.visibility.clone(),
+                doc: info.doc.clone(),
+                summary: info.summary.clone(),
+                context: info.context.clone(),
+                metrics: info.metrics,
+                complexity: info.complexity,
+                is_truncated: info.is_truncated,
+                constraints: info.constraints.clone(),
+                annotations: info.annotations.clone(),
+                component: info.component.clone(),
+                text: text.clone(),
+            })
+            .collect()
+    }
 }
 
 /// 旧版解析结果（保留兼容性）
@@ -168,3 +943,8 @@ pub struct LegacyParseResult {
     pub exports: Vec<ExportDeclaration>,
     pub errors: Vec<ParseError>,
 }
+
+/// serde 辅助函数：false 时跳过序列化
+fn is_false(value: &bool) -> bool {
+    !*value
+}
@@ -0,0 +1,250 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, Metadata};
+use std::path::{Path, PathBuf};
+
+/// 目录遍历选项
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WalkOptions {
+    /// 最大目录深度（根目录下的文件深度为 1，None 表示不限制）
+    pub max_depth: Option<usize>,
+    /// 是否跟随符号链接（跟随时检测环路并按设备号 / inode 去重）
+    pub follow_symlinks: bool,
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: Some(64),
+            follow_symlinks: true,
+        }
+    }
+}
+
+/// 遍历得到的文件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkedFile {
+    /// 可直接读取的路径（根目录 + 相对路径）
+    pub path: PathBuf,
+    /// 相对根目录的路径，分隔符为 `/`
+    pub relative_path: String,
+    pub size: u64,
+}
+
+/// 遍历统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkStats {
+    pub files: usize,
+    pub directories: usize,
+    /// 指向祖先目录的符号链接（环路）
+    pub symlink_cycles: usize,
+    /// 经不同路径重复到达的文件 / 目录（同一设备号 + inode）
+    pub duplicates: usize,
+    /// 超过 max_depth 未展开的目录
+    pub depth_limited: usize,
+    /// 被过滤器排除的文件 / 目录（目录不再展开）
+    pub filtered: usize,
+    /// 无法读取的条目（权限不足、悬空链接等）
+    pub errors: Vec<String>,
+}
+
+/// 遍历结果（文件按相对路径排序）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkResult {
+    pub files: Vec<WalkedFile>,
+    pub stats: WalkStats,
+}
+
+/// 文件身份：Unix 上为 (设备号, inode)，其他平台退化为真实路径
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum FileId {
+    #[cfg_attr(not(unix), allow(dead_code))]
+    Inode(u64, u64),
+    #[cfg_attr(unix, allow(dead_code))]
+    Path(PathBuf),
+}
+
+#[cfg(unix)]
+fn file_id(_path: &Path, metadata: &Metadata) -> Option<FileId> {
+    use std::os::unix::fs::MetadataExt;
+    Some(FileId::Inode(metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(path: &Path, _metadata: &Metadata) -> Option<FileId> {
+    fs::canonicalize(path).ok().map(FileId::Path)
+}
+
+/// 待展开的目录
+struct PendingDir {
+    path: PathBuf,
+    relative: String,
+    depth: usize,
+    /// 从根目录到当前目录的身份链，用于识别环路
+    ancestors: Vec<FileId>,
+}
+
+/// 遍历项目目录
+///
+/// 符号链接按 `follow_symlinks` 处理：指向祖先目录的链接计为环路并跳过，
+/// 同一文件 / 目录经多条路径到达时只保留第一次（按名字排序的遍历顺序，结果稳定）。
+pub fn walk_project(root: impl AsRef<Path>, options: &WalkOptions) -> Result<WalkResult, String> {
+    walk_project_filtered(root, options, |_, _| true)
+}
+
+/// 遍历项目目录，`keep(相对路径, 是否目录)` 返回 false 的条目被跳过
+pub fn walk_project_filtered(
+    root: impl AsRef<Path>,
+    options: &WalkOptions,
+    mut keep: impl FnMut(&str, bool) -> bool,
+) -> Result<WalkResult, String> {
+    let root = root.as_ref();
+    let metadata = fs::metadata(root).map_err(|e| format!("Failed to read {}: {}", root.display(), e))?;
+    if !metadata.is_dir() {
+        return Err(format!("Not a directory: {}", root.display()));
+    }
+
+    let mut result = WalkResult::default();
+    let mut seen = HashSet::new();
+    let root_id = file_id(root, &metadata);
+    if let Some(id) = &root_id {
+        seen.insert(id.clone());
+    }
+
+    let mut stack = vec![PendingDir {
+        path: root.to_path_buf(),
+        relative: String::new(),
+        depth: 0,
+        ancestors: root_id.into_iter().collect(),
+    }];
+
+    while let Some(dir) = stack.pop() {
+        result.stats.directories += 1;
+        let entries = match fs::read_dir(&dir.path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                result.stats.errors.push(format!("{}: {}", dir.path.display(), e));
+                continue;
+            }
+        };
+        let mut entries: Vec<_> = entries.filter_map(Result::ok).collect();
+        entries.sort_by_key(|entry| entry.file_name());
+
+        let mut subdirs = Vec::new();
+        for entry in entries {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let relative = if dir.relative.is_empty() {
+                name
+            } else {
+                format!("{}/{}", dir.relative, name)
+            };
+
+            let is_symlink = entry.file_type().is_ok_and(|t| t.is_symlink());
+            if is_symlink && !options.follow_symlinks {
+                continue;
+            }
+            // metadata 跟随符号链接
+            let metadata = match fs::metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    result.stats.errors.push(format!("{}: {}", relative, e));
+                    continue;
+                }
+            };
+            if !keep(&relative, metadata.is_dir()) {
+                result.stats.filtered += 1;
+                continue;
+            }
+            let id = file_id(&path, &metadata);
+
+            if metadata.is_dir() {
+                if let Some(id) = &id {
+                    if dir.ancestors.contains(id) {
+                        result.stats.symlink_cycles += 1;
+                        continue;
+                    }
+                    if !seen.insert(id.clone()) {
+                        result.stats.duplicates += 1;
+                        continue;
+                    }
+                }
+                if options.max_depth.is_some_and(|max| dir.depth + 1 >= max) {
+                    result.stats.depth_limited += 1;
+                    continue;
+                }
+                let mut ancestors = dir.ancestors.clone();
+                ancestors.extend(id);
+                subdirs.push(PendingDir { path, relative, depth: dir.depth + 1, ancestors });
+            } else if metadata.is_file() {
+                if id.is_some_and(|id| !seen.insert(id)) {
+                    result.stats.duplicates += 1;
+                    continue;
+                }
+                result.files.push(WalkedFile { path, relative_path: relative, size: metadata.len() });
+            }
+        }
+
+        // 逆序入栈，按名字顺序展开
+        stack.extend(subdirs.into_iter().rev());
+    }
+
+    result.files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    result.stats.files = result.files.len();
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_walk_sorted_with_depth_limit() {
+        let root = temp_dir("walker", "depth");
+        fs::create_dir_all(root.join("a/b/c")).unwrap();
+        fs::write(root.join("z.ts"), "").unwrap();
+        fs::write(root.join("a/x.ts"), "x").unwrap();
+        fs::write(root.join("a/b/c/deep.ts"), "").unwrap();
+
+        let result = walk_project(&root, &WalkOptions { max_depth: Some(2), ..Default::default() }).unwrap();
+        let paths: Vec<&str> = result.files.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["a/x.ts", "z.ts"]);
+        assert_eq!(result.files[0].size, 1);
+        assert_eq!(result.stats.depth_limited, 1);
+
+        let result = walk_project_filtered(&root, &WalkOptions::default(), |path, is_dir| !is_dir || path != "a/b").unwrap();
+        assert_eq!(result.files.len(), 2);
+        assert_eq!(result.stats.filtered, 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_cycles_and_duplicates() {
+        use std::os::unix::fs::symlink;
+
+        let root = temp_dir("walker", "links");
+        fs::create_dir_all(root.join("pkg/lib")).unwrap();
+        fs::write(root.join("pkg/lib/index.js"), "").unwrap();
+        // 环路：pkg/lib/self -> pkg
+        symlink(root.join("pkg"), root.join("pkg/lib/self")).unwrap();
+        // 重复：node_modules/pkg -> pkg
+        fs::create_dir(root.join("node_modules")).unwrap();
+        symlink(root.join("pkg"), root.join("node_modules/pkg")).unwrap();
+
+        let result = walk_project(&root, &WalkOptions::default()).unwrap();
+        let paths: Vec<&str> = result.files.iter().map(|f| f.relative_path.as_str()).collect();
+        // 同一层级先登记的真实目录优先
+        assert_eq!(paths, vec!["pkg/lib/index.js"]);
+        assert_eq!(result.stats.duplicates, 1);
+        assert_eq!(result.stats.symlink_cycles, 1);
+
+        let result = walk_project(&root, &WalkOptions { follow_symlinks: false, ..Default::default() }).unwrap();
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.files[0].relative_path, "pkg/lib/index.js");
+    }
+}
@@ -0,0 +1,99 @@
+//! wasm-bindgen 绑定（`wasm` feature）
+//!
+//! 供浏览器 / 边缘运行时（如 VS Code Web 扩展）在没有 NAPI 原生模块时做轻量解析。
+//! 与 NAPI 绑定一致，选项与结果均以 JSON 字符串传递。
+//!
+//! ```js
+//! import init, { LanguageManager } from 'synapse-parser-wasm';
+//! await init();
+//! const manager = new LanguageManager(JSON.stringify({ pathMode: 'relative', projectRoot: '/ws' }));
+//! const result = JSON.parse(manager.parseFile('/ws/src/app.ts', source));
+//! ```
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::graph_filter::GraphFilter;
+use crate::language_manager::LanguageManager as RustLanguageManager;
+use crate::types::{ParseOptions, ParseResult};
+
+fn to_json(value: &impl Serialize) -> Result<String, JsError> {
+    serde_json::to_string(value).map_err(|e| JsError::new(&format!("Serialization error: {}", e)))
+}
+
+fn parse_options(options: Option<String>) -> Result<ParseOptions, JsError> {
+    match options {
+        Some(json) => serde_json::from_str(&json).map_err(|e| JsError::new(&format!("Invalid parse options: {}", e))),
+        None => Ok(ParseOptions::default()),
+    }
+}
+
+/// 多语言解析器（LanguageManager 的 wasm 包装）
+#[wasm_bindgen(js_name = LanguageManager)]
+pub struct WasmLanguageManager {
+    inner: RustLanguageManager,
+}
+
+#[wasm_bindgen(js_class = LanguageManager)]
+impl WasmLanguageManager {
+    /// 创建解析器，`options` 为 ParseOptions 的 JSON
+    #[wasm_bindgen(constructor)]
+    pub fn new(options: Option<String>) -> Result<WasmLanguageManager, JsError> {
+        Ok(Self {
+            inner: RustLanguageManager::with_options(parse_options(options)?),
+        })
+    }
+
+    /// 更新解析选项（ParseOptions 的 JSON）
+    #[wasm_bindgen(js_name = setOptions)]
+    pub fn set_options(&mut self, options: String) -> Result<(), JsError> {
+        self.inner.set_options(parse_options(Some(options))?);
+        Ok(())
+    }
+
+    /// 根据文件路径检测语言并解析，返回 ParseResult 的 JSON
+    #[wasm_bindgen(js_name = parseFile)]
+    pub fn parse_file(&mut self, file_path: String, source_code: String) -> Result<String, JsError> {
+        let result = self.inner.parse_file(&file_path, &source_code).map_err(|e| JsError::new(&e))?;
+        to_json(&result)
+    }
+
+    /// 解析并返回结构化实体的 JSON 数组
+    #[wasm_bindgen(js_name = parseFileStructured)]
+    pub fn parse_file_structured(&mut self, file_path: String, source_code: String) -> Result<String, JsError> {
+        let result = self.inner.parse_file(&file_path, &source_code).map_err(|e| JsError::new(&e))?;
+        to_json(&result.structured_entities())
+    }
+
+    /// 根据文件路径猜测语言
+    #[wasm_bindgen(js_name = guessLanguage)]
+    pub fn guess_language(&self, file_path: String) -> Option<String> {
+        self.inner.guess_language(&file_path).map(|lang| lang.to_string())
+    }
+}
+
+/// 当前构建支持的语言
+#[wasm_bindgen(js_name = getSupportedLanguages)]
+pub fn get_supported_languages() -> Vec<String> {
+    RustLanguageManager::supported_languages()
+        .iter()
+        .map(|lang| lang.to_string())
+        .collect()
+}
+
+/// 解析一批文件之间的导入，`results` 为 ParseResult 的 JSON 数组，返回 SymbolGraph 的 JSON
+///
+/// `filter` 为可选的 GraphFilter JSON，排除测试与生成代码。
+#[wasm_bindgen(js_name = buildSymbolGraph)]
+pub fn build_symbol_graph(results: Vec<String>, filter: Option<String>) -> Result<String, JsError> {
+    let parsed = results
+        .iter()
+        .map(|json| serde_json::from_str::<ParseResult>(json))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| JsError::new(&format!("Invalid parse result: {}", e)))?;
+    let filter: GraphFilter = match filter {
+        Some(json) => serde_json::from_str(&json).map_err(|e| JsError::new(&format!("Invalid graph filter: {}", e)))?,
+        None => GraphFilter::default(),
+    };
+    to_json(&crate::symbol_graph::build_symbol_graph_filtered(&parsed, &filter))
+}
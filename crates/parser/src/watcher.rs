@@ -0,0 +1,300 @@
+//! 文件系统监听：持续增量索引
+//!
+//! 监听项目根目录（notify），变化的路径按静默间隔合并成一批，在 Rust 侧读取并重新解析，
+//! 通过回调交出更新的解析结果与删除的文件。过滤规则与 `ProjectScanner` 一致；
+//! 内容哈希未变的文件（只改了元数据、保存了相同内容）不重复解析。
+
+use globset::GlobSet;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use crate::encoding::{decode_source, looks_binary};
+use crate::ext_to_lang::guess_language;
+use crate::language_manager::LanguageManager;
+use crate::scanner::{build_globs, GitignoreStack};
+use crate::types::{ParseOptions, ParseResult};
+
+/// 监听选项（过滤规则同 `ScanOptions`）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WatchOptions {
+    /// 只解析匹配这些 glob 的文件（相对根目录，为空时不限制）
+    pub include: Vec<String>,
+    /// 排除匹配这些 glob 的文件和目录
+    pub exclude: Vec<String>,
+    /// 遵循各级目录中的 `.gitignore`
+    pub respect_gitignore: bool,
+    /// 超过该大小（字节）的文件不解析
+    pub max_file_size: Option<u64>,
+    /// 静默间隔（毫秒）：最后一次变化之后这么久没有新的变化，才处理这一批
+    pub debounce_ms: u64,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            respect_gitignore: true,
+            max_file_size: Some(2 * 1024 * 1024),
+            debounce_ms: 200,
+        }
+    }
+}
+
+/// 一批变化的处理结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchEvent {
+    /// 新增或内容有变化的文件的解析结果（`content_hash` 已填写）
+    pub changed: Vec<ParseResult>,
+    /// 已删除或不再参与索引的文件（输出路径，同 `ParseResult::file_path`）
+    pub removed: Vec<String>,
+    /// 读取或解析失败的文件（`相对路径: 原因`）
+    pub failed: Vec<String>,
+}
+
+impl WatchEvent {
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty() && self.failed.is_empty()
+    }
+}
+
+/// 项目监听器：后台线程处理变化，`stop` 或释放时停止
+#[derive(Default)]
+pub struct ProjectWatcher {
+    options: WatchOptions,
+    parse_options: ParseOptions,
+    watcher: Option<RecommendedWatcher>,
+}
+
+impl ProjectWatcher {
+    /// 创建监听器
+    pub fn new(options: WatchOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    /// 设置解析选项（未指定 `project_root` 时使用监听的根目录）
+    pub fn with_parse_options(mut self, options: ParseOptions) -> Self {
+        self.parse_options = options;
+        self
+    }
+
+    /// 是否正在监听
+    pub fn is_running(&self) -> bool {
+        self.watcher.is_some()
+    }
+
+    /// 开始监听根目录，每批变化处理完后调用一次 `on_change`（没有实际变化的批次不回调）
+    ///
+    /// 只报告启动之后发生的变化；需要完整索引时先用 `ProjectScanner` 扫描一次。
+    pub fn start<F>(&mut self, root: impl AsRef<Path>, mut on_change: F) -> Result<(), String>
+    where
+        F: FnMut(WatchEvent) + Send + 'static,
+    {
+        if self.watcher.is_some() {
+            return Err("Watcher is already running".to_string());
+        }
+        let root = fs::canonicalize(root.as_ref())
+            .map_err(|e| format!("Failed to watch {}: {}", root.as_ref().display(), e))?;
+        let mut state = WatchState::new(&root, &self.options, self.parse_options.clone())?;
+
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            // 单个事件出错（如系统事件队列溢出）时忽略，后续变化照常处理
+            if let Ok(event) = event {
+                let _ = sender.send(event.paths);
+            }
+        })
+        .map_err(|e| format!("Failed to watch {}: {}", root.display(), e))?;
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .map_err(|e| format!("Failed to watch {}: {}", root.display(), e))?;
+
+        let debounce = Duration::from_millis(self.options.debounce_ms);
+        std::thread::spawn(move || {
+            // 监听器释放后通道关闭，线程随之结束
+            while let Ok(paths) = receiver.recv() {
+                let mut pending: BTreeSet<PathBuf> = paths.into_iter().collect();
+                loop {
+                    match receiver.recv_timeout(debounce) {
+                        Ok(paths) => pending.extend(paths),
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+                let event = state.process(pending);
+                if !event.is_empty() {
+                    on_change(event);
+                }
+            }
+        });
+
+        self.watcher = Some(watcher);
+        Ok(())
+    }
+
+    /// 停止监听（正在处理的一批完成后不再回调）
+    pub fn stop(&mut self) {
+        self.watcher = None;
+    }
+}
+
+/// 监听线程的状态：过滤规则、解析器与已索引文件的内容哈希
+struct WatchState {
+    root: PathBuf,
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+    respect_gitignore: bool,
+    max_file_size: Option<u64>,
+    manager: LanguageManager,
+    hashes: HashMap<PathBuf, String>,
+}
+
+impl WatchState {
+    fn new(root: &Path, options: &WatchOptions, mut parse_options: ParseOptions) -> Result<Self, String> {
+        if parse_options.project_root.is_none() {
+            parse_options.project_root = Some(root.to_string_lossy().into_owned());
+        }
+        Ok(Self {
+            root: root.to_path_buf(),
+            include: build_globs(&options.include)?,
+            exclude: build_globs(&options.exclude)?,
+            respect_gitignore: options.respect_gitignore,
+            max_file_size: options.max_file_size,
+            manager: LanguageManager::with_options(parse_options),
+            hashes: HashMap::new(),
+        })
+    }
+
+    /// 处理一批变化的路径
+    fn process(&mut self, paths: impl IntoIterator<Item = PathBuf>) -> WatchEvent {
+        let mut event = WatchEvent::default();
+        // 每批重新读取 .gitignore，规则的修改随即生效
+        let root = self.root.clone();
+        let mut gitignores = GitignoreStack::new(&root);
+        for path in paths {
+            if path.is_dir() {
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(&self.root) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if !path.is_file() || !self.accepts(&relative, &mut gitignores) {
+                // 删除（含整个目录）或不再参与索引：清理该路径及其下已索引的文件
+                self.forget(&path, &mut event);
+                continue;
+            }
+            match self.parse(&path) {
+                Ok(Some(result)) => event.changed.push(result),
+                Ok(None) => {}
+                Err(e) => event.failed.push(format!("{}: {}", relative, e)),
+            }
+        }
+        event
+    }
+
+    /// 过滤规则：排除的目录、glob、`.gitignore`、可识别的语言
+    fn accepts(&self, relative: &str, gitignores: &mut GitignoreStack) -> bool {
+        let mut excluded = |path: &str, is_dir: bool| {
+            (is_dir && path.rsplit('/').next() == Some(".git"))
+                || self.exclude.as_ref().is_some_and(|set| set.is_match(path))
+                || (self.respect_gitignore && gitignores.is_ignored(path, is_dir))
+        };
+        let mut dirs = relative.match_indices('/').map(|(i, _)| &relative[..i]);
+        !(dirs.any(|dir| excluded(dir, true))
+            || excluded(relative, false)
+            || self.include.as_ref().is_some_and(|set| !set.is_match(relative))
+            || guess_language(relative).is_none())
+    }
+
+    /// 读取并解析；内容未变化、二进制或超出大小上限时返回 None（后两者同时清理已索引的结果）
+    fn parse(&mut self, path: &Path) -> Result<Option<ParseResult>, String> {
+        let size = fs::metadata(path).map_err(|e| e.to_string())?.len();
+        if self.max_file_size.is_some_and(|max| size > max) {
+            self.hashes.remove(path);
+            return Ok(None);
+        }
+        let bytes = fs::read(path).map_err(|e| e.to_string())?;
+        if looks_binary(&bytes) {
+            self.hashes.remove(path);
+            return Ok(None);
+        }
+        let decoded = decode_source(&bytes);
+        let file_path = path.to_string_lossy();
+        if self.manager.detect_language(&file_path, &decoded.text).is_none() {
+            self.hashes.remove(path);
+            return Ok(None);
+        }
+
+        let previous = self.hashes.get(path).map(String::as_str);
+        let Some(mut result) = self.manager.parse_if_changed(&file_path, &decoded.text, previous)? else {
+            return Ok(None);
+        };
+        result.errors.extend(decoded.note());
+        if let Some(hash) = &result.content_hash {
+            self.hashes.insert(path.to_path_buf(), hash.clone());
+        }
+        Ok(Some(result))
+    }
+
+    /// 从已索引的文件中移除该路径（目录时为其下的全部文件），记入 `removed`
+    fn forget(&mut self, path: &Path, event: &mut WatchEvent) {
+        let gone: Vec<PathBuf> = self.hashes.keys().filter(|known| known.starts_with(path)).cloned().collect();
+        for known in gone {
+            self.hashes.remove(&known);
+            event.removed.push(self.manager.display_path(&known.to_string_lossy()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_process_changes() {
+        let root = temp_dir("watcher", "process");
+        fs::write(root.join(".gitignore"), "dist/\n").unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("dist")).unwrap();
+        let app = root.join("src/app.ts");
+        let bundle = root.join("dist/bundle.js");
+        fs::write(&app, "export function run() {}\n").unwrap();
+        fs::write(&bundle, "function bundled() {}\n").unwrap();
+
+        let options = WatchOptions {
+            exclude: vec!["**/*.spec.ts".to_string()],
+            ..Default::default()
+        };
+        let mut state = WatchState::new(&root, &options, ParseOptions::default()).unwrap();
+        let event = state.process([app.clone(), bundle.clone(), root.join("src")]);
+        // 被 .gitignore 排除的文件与目录本身不产生结果
+        assert_eq!(event.changed.len(), 1);
+        assert!(event.changed[0].file_path.ends_with("src/app.ts"));
+        assert!(event.changed[0].content_hash.is_some());
+
+        // 内容未变化时不再解析
+        assert!(state.process([app.clone()]).is_empty());
+        fs::write(&app, "export function run() {}\nexport function stop() {}\n").unwrap();
+        let event = state.process([app.clone()]);
+        assert!(event.changed[0].entity_info.iter().any(|info| info.name.as_deref() == Some("stop")));
+
+        // 删除整个目录时，其下已索引的文件都记入 removed
+        fs::remove_dir_all(root.join("src")).unwrap();
+        let event = state.process([root.join("src")]);
+        assert_eq!(event.removed.len(), 1);
+        assert!(event.removed[0].ends_with("src/app.ts"));
+        assert!(state.hashes.is_empty());
+    }
+}
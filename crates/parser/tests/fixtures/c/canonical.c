@@ -0,0 +1,18 @@
+// Canonical C fixture: exercises every capture in C_QUERY.
+#include <stdio.h>
+
+struct Point {
+    int x;
+    int y;
+};
+
+enum Color {
+    RED,
+    GREEN
+};
+
+typedef unsigned int uint;
+
+int add(int a, int b) {
+    return a + b;
+}
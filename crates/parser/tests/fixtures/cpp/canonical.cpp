@@ -0,0 +1,50 @@
+// Canonical C++ fixture: exercises every capture in CPP_QUERY.
+#include <string>
+
+using std::string;
+
+namespace geometry {
+
+struct Point {
+    int x;
+    int y;
+};
+
+enum Color {
+    Red,
+    Green
+};
+
+class Shape {
+public:
+    Shape() {}
+    virtual ~Shape() {}
+    virtual double area() const = 0;
+    bool empty() const { return area() == 0; }
+    bool operator==(const Shape& other) const { return area() == other.area(); }
+};
+
+template <typename T>
+T max_of(T a, T b) {
+    return a > b ? a : b;
+}
+
+template <typename T>
+concept Measurable = requires(T shape) {
+    shape.area();
+};
+
+template <>
+struct Box<Shape> {
+    Shape value;
+};
+
+}
+
+double Circle::area() const {
+    return 3.14 * radius * radius;
+}
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,20 @@
+// Canonical Java fixture: exercises every capture in JAVA_QUERY.
+package com.example.service;
+
+import java.util.List;
+
+public interface Repository {
+    List<String> findAll();
+}
+
+public enum Status {
+    ACTIVE,
+    DISABLED
+}
+
+public class UserService extends BaseService implements Repository {
+    public List<String> findAll() {
+        Helper helper = new Helper();
+        return helper.load();
+    }
+}
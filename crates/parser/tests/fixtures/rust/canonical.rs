@@ -0,0 +1,26 @@
+// Canonical Rust fixture: exercises every capture in RUST_QUERY.
+use std::collections::HashMap;
+
+/// A simple key-value store.
+pub struct Store {
+    items: HashMap<String, String>,
+}
+
+pub enum Mode {
+    ReadOnly,
+    ReadWrite,
+}
+
+pub trait Backend {
+    fn load(&self, key: &str) -> Option<String>;
+}
+
+impl Store {
+    pub fn new() -> Self {
+        Self { items: HashMap::new() }
+    }
+}
+
+mod internal {
+    pub fn helper() {}
+}
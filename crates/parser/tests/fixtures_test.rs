@@ -0,0 +1,47 @@
+//! 规范样例快照回放
+//!
+//! 样例由 `cargo xtask gen-fixtures <lang>` 生成；解析输出变化时重新生成并检查差异。
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+use synapse_parser::{fixture_snapshot, LanguageManager};
+
+#[test]
+fn test_canonical_fixtures() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut manager = LanguageManager::new();
+    let mut checked = 0;
+
+    for dir in fs::read_dir(&fixtures).expect("fixtures directory") {
+        let dir = dir.unwrap().path();
+        let expected_path = dir.join("canonical.expected.json");
+        let Some(source_path) = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .find(|path| path.file_stem().is_some_and(|stem| stem == "canonical") && path != &expected_path)
+        else {
+            continue;
+        };
+
+        let file_name = source_path.file_name().unwrap().to_string_lossy().to_string();
+        let source = fs::read_to_string(&source_path).unwrap();
+        let result = match manager.parse_file(&file_name, &source) {
+            Ok(result) => result,
+            // 当前 feature 未启用该语言
+            Err(_) => continue,
+        };
+
+        let expected: Value = serde_json::from_str(&fs::read_to_string(&expected_path).unwrap()).unwrap();
+        assert_eq!(
+            fixture_snapshot(&result),
+            expected,
+            "fixture {} is out of date; run `cargo xtask gen-fixtures`",
+            dir.display()
+        );
+        checked += 1;
+    }
+
+    assert!(checked > 0, "no fixtures were checked");
+}
@@ -2,18 +2,21 @@ use synapse_parser::{LanguageManager, SupportedLanguage};
 
 #[test]
 fn test_supported_languages_count() {
-    let langs = SupportedLanguage::all();
-    
+    // 配置文件与 Markdown 不属于 all-languages，按各自的 feature 另计
+    let documents = usize::from(cfg!(feature = "config-files")) * 3 + usize::from(cfg!(feature = "markdown"));
+    let langs = SupportedLanguage::all().len() - documents;
+
     // 默认 feature 支持 8 种语言: TS, JS, Python, Go, Rust, Java, C, C++
     #[cfg(not(feature = "all-languages"))]
-    assert_eq!(langs.len(), 8, "Default features should support 8 languages");
-    
-    // all-languages feature 支持 15 种语言
+    assert_eq!(langs, 8, "Default features should support 8 languages");
+
+    // all-languages feature 支持 22 种语言
     #[cfg(feature = "all-languages")]
-    assert_eq!(langs.len(), 15, "All-languages feature should support 15 languages");
+    assert_eq!(langs, 22, "All-languages feature should support 22 languages");
 }
 
 #[test]
+#[allow(clippy::len_zero)]
 fn test_typescript_parsing() {
     let mut manager = LanguageManager::new();
     
@@ -128,6 +131,74 @@ public class Greeter {
     assert!(result.is_ok(), "Java parsing should succeed");
 }
 
+#[cfg(feature = "php")]
+#[test]
+fn test_php_parsing() {
+    let mut manager = LanguageManager::new();
+    
+    let code = r#"<?php
+namespace App\Services;
+
+use App\Models\{User, Post};
+use Psr\Log\LoggerInterface as Logger;
+
+trait Loggable {
+    public function log(string $message): void {}
+}
+
+/** Sends greetings. */
+class Greeter extends Base implements Contract {
+    use Loggable;
+
+    public function hello(User $user): string {
+        $this->log("hello");
+        return format_name($user);
+    }
+
+    private static function secret() {}
+}
+
+function format_name(User $user): string {
+    return strtoupper($user->name);
+}
+"#;
+    
+    let result = manager.parse_file("src/Services/Greeter.php", code).unwrap();
+    assert_eq!(result.language, "PHP");
+    let find = |name: &str| {
+        result
+            .entity_info
+            .iter()
+            .position(|info| info.name.as_deref() == Some(name))
+            .unwrap_or_else(|| panic!("missing {}", name))
+    };
+    
+    let namespace = &result.entity_info[find("App\\Services")];
+    assert_eq!(namespace.kind, "namespace");
+    assert_eq!(result.entity_info[find("Loggable")].kind, "trait");
+    let class = find("Greeter");
+    assert_eq!(result.entity_info[class].kind, "class");
+    assert_eq!(result.entities[class], "class Greeter extends Base implements Contract");
+    assert_eq!(result.entity_info[class].doc.as_deref(), Some("Sends greetings."));
+    assert_eq!(result.entity_info[find("format_name")].kind, "function");
+    
+    let hello = &result.entity_info[find("hello")];
+    assert_eq!(hello.kind, "method");
+    assert!(hello.is_exported);
+    assert!(!result.entity_info[find("secret")].is_exported);
+    
+    assert_eq!(result.imports.len(), 2);
+    assert_eq!(result.imports[0].source, "App\\Models");
+    assert_eq!(result.imports[0].specifiers, vec!["User", "Post"]);
+    assert_eq!(result.imports[1].source, "Psr\\Log");
+    assert_eq!(result.imports[1].specifiers, vec!["LoggerInterface"]);
+    
+    let callees: Vec<_> = result.calls.iter().map(|call| (call.caller.as_deref(), call.callee.as_str())).collect();
+    assert!(callees.contains(&(Some("hello"), "log")));
+    assert!(callees.contains(&(Some("hello"), "format_name")));
+    assert!(callees.contains(&(Some("format_name"), "strtoupper")));
+}
+
 #[cfg(feature = "c-lang")]
 #[test]
 fn test_c_parsing() {
@@ -175,6 +246,122 @@ namespace math {
     assert!(result.is_ok(), "C++ parsing should succeed");
 }
 
+#[cfg(feature = "cpp")]
+#[test]
+fn test_cpp_templates_and_concepts() {
+    let code = r#"
+template <typename T>
+concept Hashable = requires(T a) {
+    { std::hash<T>{}(a) } -> std::convertible_to<std::size_t>;
+};
+
+template <typename T> requires Hashable<T> && std::copyable<T>
+void store(T value) {}
+
+template <typename T>
+T twice(T v) requires std::integral<T> { return v * 2; }
+
+template <>
+class Box<int> {};
+
+template <>
+void store<int>(int value) {}
+"#;
+    let result = LanguageManager::new().parse_file("src/store.cpp", code).unwrap();
+    let find = |kind: &str, name: &str| {
+        result
+            .entity_info
+            .iter()
+            .find(|info| info.kind == kind && info.name.as_deref() == Some(name))
+            .unwrap_or_else(|| panic!("missing {} {}", kind, name))
+    };
+
+    let concept = find("concept", "Hashable");
+    assert_eq!(concept.constraints.len(), 1);
+    assert!(concept.constraints[0].starts_with("requires(T a) { { std::hash<T>{}(a) }"));
+    assert_eq!(find("function", "store").constraints, vec!["Hashable<T> && std::copyable<T>"]);
+    assert_eq!(find("function", "twice").constraints, vec!["std::integral<T>"]);
+    assert!(find("specialization", "Box<int>").constraints.is_empty());
+    assert_eq!(find("specialization", "store<int>").signature.as_deref(), Some("void store<int>(int value)"));
+}
+
+#[cfg(feature = "cpp")]
+#[test]
+fn test_cpp_special_members() {
+    let code = r#"
+class Vec2 {
+public:
+    explicit Vec2(double x) : x_(x) {}
+    ~Vec2() {}
+    bool operator==(const Vec2& other) const { return x_ == other.x_; }
+    Vec2& operator = (const Vec2& other) { x_ = other.x_; return *this; }
+    operator bool() const { return x_ != 0; }
+    double length() const { return x_; }
+private:
+    double x_;
+};
+
+Vec2::Vec2() : x_(0) {}
+Vec2::~Vec2() {}
+std::ostream& operator<<(std::ostream& os, const Vec2& v) { return os; }
+"#;
+    let result = LanguageManager::new().parse_file("src/vec2.cpp", code).unwrap();
+    let named: Vec<(&str, &str)> = result
+        .entity_info
+        .iter()
+        .filter_map(|info| Some((info.kind.as_str(), info.name.as_deref()?)))
+        .collect();
+
+    for expected in [
+        ("constructor", "Vec2::Vec2"),
+        ("destructor", "Vec2::~Vec2"),
+        ("operator", "operator=="),
+        ("operator", "operator="),
+        ("operator", "operator bool"),
+        ("operator", "operator<<"),
+        ("method", "length"),
+    ] {
+        assert!(named.contains(&expected), "missing {:?} in {:?}", expected, named);
+    }
+    // 类体内与类外的构造、析构函数各一个
+    assert_eq!(named.iter().filter(|(kind, _)| *kind == "constructor").count(), 2);
+    assert_eq!(named.iter().filter(|(kind, _)| *kind == "destructor").count(), 2);
+    assert!(!named.iter().any(|(kind, _)| *kind == "function"));
+    // 构造函数不带返回类型，签名保留初始化列表之前的部分
+    let ctor = result
+        .entity_info
+        .iter()
+        .find(|info| info.kind == "constructor")
+        .unwrap();
+    assert!(ctor.signature.as_deref().unwrap().starts_with("explicit Vec2(double x)"));
+    assert!(ctor.is_exported);
+}
+
+#[cfg(all(feature = "c-lang", feature = "cpp"))]
+#[test]
+fn test_header_language_detection() {
+    use synapse_parser::{HeaderLanguage, ParseOptions};
+
+    let c_header = "#ifndef UTIL_H\n#define UTIL_H\nstruct point { int x; int y; };\nint distance(struct point a, struct point b);\n#endif\n";
+    let cpp_header = "#pragma once\nnamespace geo {\nclass Shape {\npublic:\n    virtual double area() const = 0;\n};\n}\n";
+    let objc_header = "#import <Foundation/Foundation.h>\n@interface Shape : NSObject\n- (double)area;\n@end\n";
+
+    let mut manager = LanguageManager::new();
+    assert_eq!(manager.parse_file("include/util.h", c_header).unwrap().language, "C");
+    let cpp = manager.parse_file("include/shape.h", cpp_header).unwrap();
+    assert_eq!(cpp.language, "C++");
+    assert!(cpp.entity_info.iter().any(|info| info.name.as_deref() == Some("Shape")));
+    assert!(manager.parse_file("include/Shape.h", objc_header).is_err());
+
+    // 项目级覆盖：全部按 C++ 解析
+    let mut manager = LanguageManager::with_options(ParseOptions {
+        header_language: HeaderLanguage::Cpp,
+        ..Default::default()
+    });
+    assert_eq!(manager.parse_file("include/util.h", c_header).unwrap().language, "C++");
+    assert_eq!(manager.detect_language("src/util.c", cpp_header), Some(SupportedLanguage::C));
+}
+
 #[cfg(feature = "swift")]
 #[test]
 fn test_swift_parsing() {
@@ -199,6 +386,77 @@ struct Point {
     assert!(result.is_ok(), "Swift parsing should succeed");
 }
 
+#[cfg(feature = "swift")]
+#[test]
+fn test_swift_extensions_and_property_wrappers() {
+    use synapse_parser::{ParseOptions, RelationKind};
+
+    let mut manager = LanguageManager::with_options(ParseOptions {
+        qualified_names: true,
+        ..Default::default()
+    });
+    let code = r#"protocol Named: AnyObject {
+    var name: String { get }
+}
+
+class Store: NSObject, Named {
+    @Published private(set) var name: String = ""
+    @Environment(\.dismiss) var dismiss
+}
+
+enum Color: String, CaseIterable {
+    case red
+}
+
+extension Store: Identifiable {
+    var id: String { name }
+    func rename(to name: String) {}
+}
+"#;
+    let result = manager.parse_file("Store.swift", code).unwrap();
+    let find = |name: &str, kind: &str| {
+        result
+            .entity_info
+            .iter()
+            .position(|info| info.name.as_deref() == Some(name) && info.kind == kind)
+            .unwrap_or_else(|| panic!("missing {} {}", kind, name))
+    };
+
+    // 扩展中的成员归属被扩展的类型
+    let store = find("Store", "class");
+    let rename = &result.entity_info[find("rename", "function")];
+    assert_eq!(rename.parent, Some(store));
+    assert_eq!(rename.qualified_name.as_deref(), Some("Store.rename"));
+    assert_eq!(result.entity_info[find("id", "property")].parent, Some(store));
+    assert_eq!(result.entities[find("id", "property")], "var id: String");
+    assert_eq!(result.entities[find("Store", "extension")], "extension Store: Identifiable");
+
+    // 属性包装器
+    let name = &result.entity_info[find("name", "property")];
+    assert_eq!(name.annotations.len(), 1);
+    assert_eq!(name.annotations[0].name, "Published");
+    let dismiss = &result.entity_info[find("dismiss", "property")];
+    assert_eq!(dismiss.annotations[0].name, "Environment");
+    assert_eq!(dismiss.annotations[0].arguments.as_deref(), Some("\\.dismiss"));
+
+    // 继承与协议遵循
+    let relations: Vec<(&str, &str, RelationKind)> = result
+        .type_relations
+        .iter()
+        .map(|r| (r.type_name.as_str(), r.target.as_str(), r.kind))
+        .collect();
+    assert_eq!(
+        relations,
+        vec![
+            ("Named", "AnyObject", RelationKind::Extends),
+            ("Store", "NSObject", RelationKind::Extends),
+            ("Store", "Named", RelationKind::Conforms),
+            ("Color", "CaseIterable", RelationKind::Conforms),
+            ("Store", "Identifiable", RelationKind::Conforms),
+        ]
+    );
+}
+
 #[cfg(feature = "solidity")]
 #[test]
 fn test_solidity_parsing() {
@@ -242,6 +500,572 @@ fn test_css_parsing() {
     assert!(result.is_ok(), "CSS parsing should succeed");
 }
 
+#[cfg(feature = "sql")]
+#[test]
+fn test_sql_parsing() {
+    let mut manager = LanguageManager::new();
+    
+    let code = r#"
+-- 账单表
+CREATE SCHEMA billing;
+
+CREATE TABLE billing.invoices (
+    id BIGINT PRIMARY KEY,
+    amount NUMERIC(10, 2) NOT NULL
+);
+
+CREATE VIEW "billing"."open_invoices" AS
+SELECT id, amount FROM billing.invoices WHERE amount > 0;
+
+CREATE FUNCTION total_due() RETURNS NUMERIC AS $$
+    SELECT sum(amount) FROM billing.invoices;
+$$ LANGUAGE sql;
+"#;
+    
+    let result = manager.parse_file("migrations/001_init.sql", code).expect("SQL parsing should succeed");
+    let entities: Vec<(&str, Option<&str>)> = result
+        .entity_info
+        .iter()
+        .filter(|info| info.kind != "comment")
+        .map(|info| (info.kind.as_str(), info.name.as_deref()))
+        .collect();
+    assert_eq!(
+        entities,
+        vec![
+            ("schema", Some("billing")),
+            ("table", Some("billing.invoices")),
+            ("view", Some("billing.open_invoices")),
+            ("function", Some("total_due")),
+        ]
+    );
+    
+    let table = result.entities.iter().find(|text| text.starts_with("CREATE TABLE")).unwrap();
+    assert!(table.contains("amount NUMERIC(10, 2) NOT NULL"));
+}
+
+#[cfg(feature = "graphql")]
+#[test]
+fn test_graphql_parsing() {
+    let mut manager = LanguageManager::new();
+    
+    let code = r#"
+# 用户与订单
+type User implements Node {
+  id: ID!
+  orders(first: Int): [Order!]!
+}
+
+enum OrderStatus { OPEN CLOSED }
+
+input NewOrder {
+  userId: ID!
+}
+
+type Query {
+  user(id: ID!): User
+}
+
+type Mutation {
+  placeOrder(input: NewOrder!): Order
+}
+
+query GetUser($id: ID!) {
+  user(id: $id) { ...UserFields }
+}
+
+mutation PlaceOrder($input: NewOrder!) {
+  placeOrder(input: $input) { id }
+}
+
+fragment UserFields on User { id }
+"#;
+    
+    let result = manager.parse_file("schema/api.graphql", code).expect("GraphQL parsing should succeed");
+    let names = |kind: &str| -> Vec<&str> {
+        result
+            .entity_info
+            .iter()
+            .filter(|info| info.kind == kind)
+            .filter_map(|info| info.name.as_deref())
+            .collect()
+    };
+    assert_eq!(names("type"), vec!["User", "Query", "Mutation"]);
+    assert_eq!(names("enum"), vec!["OrderStatus"]);
+    assert_eq!(names("input"), vec!["NewOrder"]);
+    // 根类型的字段与同名 resolver 对应
+    assert_eq!(names("field"), vec!["user", "placeOrder"]);
+    assert_eq!(names("query"), vec!["GetUser"]);
+    assert_eq!(names("mutation"), vec!["PlaceOrder"]);
+    assert_eq!(names("fragment"), vec!["UserFields"]);
+    
+    let query_type = result.entity_info.iter().position(|info| info.name.as_deref() == Some("Query")).unwrap();
+    let user_field = result.entity_info.iter().find(|info| info.kind == "field" && info.name.as_deref() == Some("user")).unwrap();
+    assert_eq!(user_field.parent, Some(query_type));
+}
+
+#[cfg(feature = "proto")]
+#[test]
+fn test_protobuf_parsing() {
+    let mut manager = LanguageManager::new();
+    
+    let code = r#"
+syntax = "proto3";
+
+package shop.orders.v1;
+
+import "google/protobuf/timestamp.proto";
+import public "shop/common.proto";
+
+// 订单
+message Order {
+  string id = 1;
+  google.protobuf.Timestamp created_at = 2;
+
+  message Item {
+    string sku = 1;
+  }
+}
+
+enum OrderStatus {
+  ORDER_STATUS_UNSPECIFIED = 0;
+  ORDER_STATUS_OPEN = 1;
+}
+
+service OrderService {
+  rpc GetOrder(GetOrderRequest) returns (Order);
+  rpc WatchOrders(WatchRequest) returns (stream Order);
+}
+"#;
+    
+    let result = manager.parse_file("proto/shop/orders.proto", code).expect("Protobuf parsing should succeed");
+    let names = |kind: &str| -> Vec<&str> {
+        result
+            .entity_info
+            .iter()
+            .filter(|info| info.kind == kind)
+            .filter_map(|info| info.name.as_deref())
+            .collect()
+    };
+    assert_eq!(names("package"), vec!["shop.orders.v1"]);
+    assert_eq!(names("message"), vec!["Order", "Item"]);
+    assert_eq!(names("enum"), vec!["OrderStatus"]);
+    assert_eq!(names("service"), vec!["OrderService"]);
+    assert_eq!(names("rpc"), vec!["GetOrder", "WatchOrders"]);
+    
+    let service = result.entity_info.iter().position(|info| info.name.as_deref() == Some("OrderService")).unwrap();
+    let rpc = result.entity_info.iter().find(|info| info.name.as_deref() == Some("GetOrder")).unwrap();
+    assert_eq!(rpc.parent, Some(service));
+    
+    let sources: Vec<&str> = result.imports.iter().map(|i| i.source.as_str()).collect();
+    assert_eq!(sources, vec!["google/protobuf/timestamp.proto", "shop/common.proto"]);
+    assert_eq!(result.exports[0].source.as_deref(), Some("shop/common.proto"));
+}
+
+#[cfg(feature = "elixir")]
+#[test]
+fn test_elixir_parsing() {
+    let mut manager = LanguageManager::new();
+    
+    let code = r#"
+defmodule Shop.Orders do
+  @moduledoc "Order lifecycle"
+  use GenServer
+  @behaviour Shop.Notifier
+  alias Shop.Repo
+  import Ecto.Query, only: [from: 2]
+
+  @callback notify(order :: term) :: :ok
+
+  # 创建订单
+  def create(attrs, opts \\ []) do
+    Repo.insert(attrs, opts)
+  end
+
+  def count, do: 0
+
+  defp validate(attrs) when is_map(attrs), do: attrs
+
+  defmacro audited(do: block), do: block
+end
+
+defimpl String.Chars, for: Shop.Orders do
+  def to_string(_order), do: "order"
+end
+"#;
+    
+    let result = manager.parse_file("lib/shop/orders.ex", code).expect("Elixir parsing should succeed");
+    assert_eq!(result.language, "Elixir");
+    let names = |kind: &str| -> Vec<&str> {
+        result
+            .entity_info
+            .iter()
+            .filter(|info| info.kind == kind)
+            .filter_map(|info| info.name.as_deref())
+            .collect()
+    };
+    assert_eq!(names("module"), vec!["Shop.Orders"]);
+    assert_eq!(names("implementation"), vec!["String.Chars.Shop.Orders"]);
+    // 函数名带元数，默认参数计入元数
+    assert_eq!(names("function"), vec!["create/2", "count/0", "validate/1", "to_string/1"]);
+    assert_eq!(names("macro"), vec!["audited/1"]);
+    assert_eq!(names("behaviour"), vec!["Shop.Notifier"]);
+    assert_eq!(names("callback"), vec!["notify/1"]);
+    
+    let function = |name: &str| result.entity_info.iter().find(|info| info.name.as_deref() == Some(name)).unwrap();
+    assert!(function("create/2").is_exported);
+    assert!(!function("validate/1").is_exported);
+    assert_eq!(function("create/2").doc.as_deref(), Some("创建订单"));
+    let module = result.entity_info.iter().position(|info| info.kind == "module").unwrap();
+    assert_eq!(function("create/2").parent, Some(module));
+    assert!(result.entities.contains(&"def create(attrs, opts \\\\ [])".to_string()));
+    assert!(result.entities.contains(&"defp validate(attrs) when is_map(attrs)".to_string()));
+    
+    let imports: Vec<(&str, Option<&str>, bool)> = result
+        .imports
+        .iter()
+        .map(|import| (import.source.as_str(), import.namespace_import.as_deref(), import.is_wildcard))
+        .collect();
+    assert_eq!(
+        imports,
+        vec![("GenServer", None, false), ("Shop.Repo", Some("Repo"), false), ("Ecto.Query", None, false)]
+    );
+    assert_eq!(result.imports[2].specifiers, vec!["from/2"]);
+    
+    let relations: Vec<(&str, &str)> = result
+        .type_relations
+        .iter()
+        .map(|relation| (relation.type_name.as_str(), relation.target.as_str()))
+        .collect();
+    assert_eq!(relations, vec![("Shop.Orders", "Shop.Notifier"), ("Shop.Orders", "String.Chars")]);
+}
+
+#[cfg(feature = "erlang")]
+#[test]
+fn test_erlang_parsing() {
+    let mut manager = LanguageManager::new();
+    
+    let code = r#"
+-module(shop_orders).
+-behaviour(gen_server).
+-export([start_link/1, init/1]).
+-import(lists, [map/2]).
+-include("shop.hrl").
+-define(TIMEOUT, 5000).
+-record(order, {id, total = 0}).
+-type order_id() :: integer().
+-callback notify(Order :: term()) -> ok.
+
+%% 启动服务
+start_link(Args) ->
+    gen_server:start_link(?MODULE, Args, []).
+
+init([]) -> {ok, #{}};
+init(Args) -> {ok, Args}.
+
+total(#order{total = T}) when T > 0 -> T.
+"#;
+    
+    let result = manager.parse_file("src/shop_orders.erl", code).expect("Erlang parsing should succeed");
+    assert_eq!(result.language, "Erlang");
+    let names = |kind: &str| -> Vec<&str> {
+        result
+            .entity_info
+            .iter()
+            .filter(|info| info.kind == kind)
+            .filter_map(|info| info.name.as_deref())
+            .collect()
+    };
+    assert_eq!(names("module"), vec!["shop_orders"]);
+    assert_eq!(names("behaviour"), vec!["gen_server"]);
+    assert_eq!(names("function"), vec!["start_link/1", "init/1", "total/1"]);
+    assert_eq!(names("macro"), vec!["TIMEOUT"]);
+    assert_eq!(names("record"), vec!["order"]);
+    assert_eq!(names("type"), vec!["order_id"]);
+    assert_eq!(names("callback"), vec!["notify/1"]);
+    
+    // 只有 -export 列出的函数是导出的
+    let exported: Vec<&str> = result
+        .entity_info
+        .iter()
+        .filter(|info| info.kind == "function" && info.is_exported)
+        .filter_map(|info| info.name.as_deref())
+        .collect();
+    assert_eq!(exported, vec!["start_link/1", "init/1"]);
+    let start_link = result.entity_info.iter().find(|info| info.name.as_deref() == Some("start_link/1")).unwrap();
+    assert_eq!(start_link.doc.as_deref(), Some("启动服务"));
+    assert!(result.entities.contains(&"total(#order{total = T}) when T > 0".to_string()));
+    
+    assert_eq!(result.imports[0].source, "lists");
+    assert_eq!(result.imports[0].specifiers, vec!["map/2"]);
+    assert_eq!(result.imports[1].source, "shop.hrl");
+    assert_eq!(result.type_relations.len(), 1);
+    assert_eq!(result.type_relations[0].type_name, "shop_orders");
+    assert_eq!(result.type_relations[0].target, "gen_server");
+}
+
+#[cfg(feature = "zig")]
+#[test]
+fn test_zig_parsing() {
+    let mut manager = LanguageManager::new();
+    
+    let code = r#"
+const std = @import("std");
+const mem = @import("mem.zig");
+
+pub const Point = struct {
+    x: i32,
+    y: i32,
+
+    pub fn init(x: i32, y: i32) Point {
+        return .{ .x = x, .y = y };
+    }
+};
+
+const Color = enum { red, green };
+pub const ParseError = error{ InvalidChar, Overflow };
+
+// 两数相加
+pub fn add(a: i32, b: i32) i32 {
+    return a + b;
+}
+
+fn helper() void {}
+"#;
+    
+    let result = manager.parse_file("src/point.zig", code).expect("Zig parsing should succeed");
+    assert_eq!(result.language, "Zig");
+    let names = |kind: &str| -> Vec<&str> {
+        result
+            .entity_info
+            .iter()
+            .filter(|info| info.kind == kind)
+            .filter_map(|info| info.name.as_deref())
+            .collect()
+    };
+    assert_eq!(names("function"), vec!["init", "add", "helper"]);
+    assert_eq!(names("struct"), vec!["Point"]);
+    assert_eq!(names("enum"), vec!["Color"]);
+    assert_eq!(names("type"), vec!["ParseError"]);
+    
+    let entity = |name: &str| result.entity_info.iter().find(|info| info.name.as_deref() == Some(name)).unwrap();
+    assert!(entity("add").is_exported);
+    assert!(!entity("helper").is_exported);
+    assert!(entity("Point").is_exported);
+    assert!(!entity("Color").is_exported);
+    assert_eq!(entity("add").doc.as_deref(), Some("两数相加"));
+    let point = result.entity_info.iter().position(|info| info.name.as_deref() == Some("Point")).unwrap();
+    assert_eq!(entity("init").parent, Some(point));
+    assert!(result.entities.contains(&"pub fn add(a: i32, b: i32) i32".to_string()));
+    assert!(result.entities.contains(&"pub const Point = struct".to_string()));
+    
+    let imports: Vec<(&str, Option<&str>)> = result
+        .imports
+        .iter()
+        .map(|import| (import.source.as_str(), import.namespace_import.as_deref()))
+        .collect();
+    assert_eq!(imports, vec![("std", Some("std")), ("mem.zig", Some("mem"))]);
+}
+
+#[cfg(feature = "nim")]
+#[test]
+fn test_nim_parsing() {
+    let mut manager = LanguageManager::new();
+    
+    let code = r#"
+import std/[os, strutils], json as j
+from sequtils import map, filter
+include helpers
+
+type
+  Shape* = object
+    width*: int
+  Kind = enum
+    kCircle, kSquare
+
+proc greet*(name: string): string =
+  ## 生成问候语
+  result = "Hello, " & name
+
+func area(s: Shape): int = s.width * s.width
+
+method draw*(s: Shape) {.base.} =
+  discard
+
+template twice(body: untyped) =
+  body
+  body
+"#;
+    
+    let result = manager.parse_file("src/shapes.nim", code).expect("Nim parsing should succeed");
+    assert_eq!(result.language, "Nim");
+    let names = |kind: &str| -> Vec<&str> {
+        result
+            .entity_info
+            .iter()
+            .filter(|info| info.kind == kind)
+            .filter_map(|info| info.name.as_deref())
+            .collect()
+    };
+    assert_eq!(names("function"), vec!["greet", "area"]);
+    assert_eq!(names("method"), vec!["draw"]);
+    assert_eq!(names("template"), vec!["twice"]);
+    assert_eq!(names("type"), vec!["Shape", "Kind"]);
+    
+    // 名字后带 `*` 的符号是导出的
+    let entity = |name: &str| result.entity_info.iter().find(|info| info.name.as_deref() == Some(name)).unwrap();
+    assert!(entity("greet").is_exported);
+    assert!(entity("Shape").is_exported);
+    assert!(!entity("area").is_exported);
+    assert!(!entity("Kind").is_exported);
+    assert_eq!(entity("greet").doc.as_deref(), Some("生成问候语"));
+    assert!(result.entities.contains(&"proc greet*(name: string): string".to_string()));
+    
+    let imports: Vec<(&str, Option<&str>)> = result
+        .imports
+        .iter()
+        .map(|import| (import.source.as_str(), import.namespace_import.as_deref()))
+        .collect();
+    assert_eq!(
+        imports,
+        vec![
+            ("std/os", None),
+            ("std/strutils", None),
+            ("json", Some("j")),
+            ("sequtils", None),
+            ("helpers", None),
+        ]
+    );
+    assert_eq!(result.imports[3].specifiers, vec!["map", "filter"]);
+}
+
+#[cfg(feature = "config-files")]
+#[test]
+fn test_config_file_parsing() {
+    let mut manager = LanguageManager::new();
+    let names = |result: &synapse_parser::ParseResult, kind: &str| -> Vec<String> {
+        result
+            .entity_info
+            .iter()
+            .filter(|info| info.kind == kind)
+            .filter_map(|info| info.name.clone())
+            .collect()
+    };
+    
+    let package_json = r#"{
+  "name": "web",
+  "scripts": {
+    "build": "vite build",
+    "test": "vitest"
+  },
+  "dependencies": {
+    "react": "^18.2.0"
+  }
+}
+"#;
+    let result = manager.parse_file("package.json", package_json).expect("JSON parsing should succeed");
+    assert_eq!(names(&result, "key"), vec!["name", "scripts", "dependencies"]);
+    assert_eq!(names(&result, "script"), vec!["build", "test"]);
+    assert_eq!(names(&result, "dependency"), vec!["react"]);
+    let scripts = result.entity_info.iter().position(|info| info.name.as_deref() == Some("scripts")).unwrap();
+    assert_eq!(result.entities[scripts], r#""scripts": {"#);
+    let build = result.entity_info.iter().find(|info| info.name.as_deref() == Some("build")).unwrap();
+    assert_eq!(build.parent, Some(scripts));
+    
+    let cargo_toml = r#"[package]
+name = "api"
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+tokio = "1"
+
+[dev-dependencies]
+insta = "1"
+"#;
+    let result = manager.parse_file("Cargo.toml", cargo_toml).expect("TOML parsing should succeed");
+    assert_eq!(names(&result, "table"), vec!["package", "dependencies", "dev-dependencies"]);
+    assert_eq!(names(&result, "dependency"), vec!["serde", "tokio", "insta"]);
+    
+    let compose = r#"version: "3.9"
+services:
+  web:
+    image: nginx
+    ports:
+      - "80:80"
+  db:
+    image: postgres
+"#;
+    let result = manager.parse_file("docker-compose.yml", compose).expect("YAML parsing should succeed");
+    assert_eq!(names(&result, "key"), vec!["version", "services"]);
+    assert_eq!(names(&result, "service"), vec!["web", "db"]);
+    let web = result.entity_info.iter().position(|info| info.name.as_deref() == Some("web")).unwrap();
+    assert!(result.entities[web].contains("image: nginx"));
+}
+
+#[cfg(feature = "markdown")]
+#[test]
+fn test_markdown_parsing() {
+    use synapse_parser::ParseOptions;
+    
+    let code = r#"# Synapse
+
+Intro with a [guide](docs/guide.md).
+
+## Install
+
+```bash
+npm install synapse
+```
+
+## Usage
+
+### TypeScript
+
+```ts
+export function connect(url: string) {
+  return url;
+}
+```
+"#;
+    
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("README.md", code).expect("Markdown parsing should succeed");
+    let sections: Vec<&str> = result
+        .entity_info
+        .iter()
+        .filter(|info| info.kind == "section")
+        .filter_map(|info| info.name.as_deref())
+        .collect();
+    assert_eq!(sections, vec!["Synapse", "Install", "Usage", "TypeScript"]);
+    
+    let position = |name: &str| result.entity_info.iter().position(|info| info.name.as_deref() == Some(name)).unwrap();
+    assert_eq!(result.entity_info[position("Usage")].parent, Some(position("Synapse")));
+    assert_eq!(result.entity_info[position("TypeScript")].parent, Some(position("Usage")));
+    // 章节文本只含自身内容
+    assert_eq!(result.entities[position("Synapse")], "# Synapse\n\nIntro with a [guide](docs/guide.md).");
+    
+    let languages: Vec<&str> = result
+        .entity_info
+        .iter()
+        .filter(|info| info.kind == "code")
+        .filter_map(|info| info.name.as_deref())
+        .collect();
+    assert_eq!(languages, vec!["bash", "ts"]);
+    assert_eq!(result.links[0].target, "docs/guide.md");
+    assert!(!result.entity_info.iter().any(|info| info.kind == "function"));
+    
+    // 按语言标记递归解析代码块
+    let mut manager = LanguageManager::with_options(ParseOptions {
+        parse_code_blocks: true,
+        ..Default::default()
+    });
+    let result = manager.parse_file("README.md", code).unwrap();
+    let function = result.entity_info.iter().find(|info| info.kind == "function").expect("function in code block");
+    assert_eq!(function.name.as_deref(), Some("connect"));
+    assert_eq!(function.range.start, 16);
+    let block = function.parent.expect("parented to the code block");
+    assert_eq!(result.entity_info[block].kind, "code");
+}
+
 #[cfg(feature = "vue")]
 #[test]
 fn test_vue_parsing() {
@@ -274,6 +1098,42 @@ h1 {
     assert!(result.is_ok(), "Vue parsing should succeed");
 }
 
+#[cfg(feature = "vue")]
+#[test]
+fn test_vue_script_block_entities() {
+    let code = r#"<template>
+  <button @click="increment">{{ count }}</button>
+</template>
+
+<script setup lang="ts">
+import { ref } from 'vue'
+import Counter from './Counter.vue'
+
+const props = defineProps<{ step: number }>()
+const count = ref(0)
+
+function increment(): void {
+  count.value += props.step
+}
+</script>
+"#;
+    let result = LanguageManager::new().parse_file("src/App.vue", code).unwrap();
+    assert_eq!(result.language, "Vue");
+    let sources: Vec<&str> = result.imports.iter().map(|import| import.source.as_str()).collect();
+    assert_eq!(sources, vec!["vue", "./Counter.vue"]);
+
+    // 行号对应原 .vue 文件
+    let increment = result
+        .entity_info
+        .iter()
+        .find(|info| info.name.as_deref() == Some("increment"))
+        .unwrap();
+    assert_eq!((increment.range.start, increment.range.end), (12, 14));
+    assert_eq!(increment.signature.as_deref(), Some("function increment(): void"));
+    // 原有的 <script> 标签实体仍保留
+    assert!(result.entity_info.iter().any(|info| info.kind == "script"));
+}
+
 #[test]
 fn test_file_extension_detection() {
     let manager = LanguageManager::new();
@@ -298,6 +1158,9 @@ fn test_file_extension_detection() {
     #[cfg(feature = "java")]
     assert_eq!(manager.guess_language("file.java"), Some(SupportedLanguage::Java));
     
+    #[cfg(feature = "php")]
+    assert_eq!(manager.guess_language("src/Controller.PHP"), Some(SupportedLanguage::PHP));
+    
     #[cfg(feature = "c-lang")]
     {
         assert_eq!(manager.guess_language("file.c"), Some(SupportedLanguage::C));
@@ -325,6 +1188,1287 @@ fn test_file_extension_detection() {
     #[cfg(feature = "vue")]
     assert_eq!(manager.guess_language("file.vue"), Some(SupportedLanguage::Vue));
     
+    #[cfg(feature = "sql")]
+    assert_eq!(manager.guess_language("migrations/001_init.sql"), Some(SupportedLanguage::Sql));
+    
+    #[cfg(feature = "graphql")]
+    {
+        assert_eq!(manager.guess_language("schema.graphql"), Some(SupportedLanguage::GraphQL));
+        assert_eq!(manager.guess_language("queries/user.gql"), Some(SupportedLanguage::GraphQL));
+    }
+    
+    #[cfg(feature = "proto")]
+    assert_eq!(manager.guess_language("proto/shop/orders.proto"), Some(SupportedLanguage::Protobuf));
+    
+    #[cfg(feature = "elixir")]
+    {
+        assert_eq!(manager.guess_language("lib/shop/orders.ex"), Some(SupportedLanguage::Elixir));
+        assert_eq!(manager.guess_language("test/orders_test.exs"), Some(SupportedLanguage::Elixir));
+    }
+    
+    #[cfg(feature = "erlang")]
+    {
+        assert_eq!(manager.guess_language("src/shop_orders.erl"), Some(SupportedLanguage::Erlang));
+        assert_eq!(manager.guess_language("include/shop.hrl"), Some(SupportedLanguage::Erlang));
+    }
+    
+    #[cfg(feature = "zig")]
+    assert_eq!(manager.guess_language("src/point.zig"), Some(SupportedLanguage::Zig));
+    
+    #[cfg(feature = "nim")]
+    assert_eq!(manager.guess_language("src/shapes.nim"), Some(SupportedLanguage::Nim));
+    
+    #[cfg(feature = "config-files")]
+    {
+        assert_eq!(manager.guess_language("package.json"), Some(SupportedLanguage::Json));
+        assert_eq!(manager.guess_language(".github/workflows/ci.yml"), Some(SupportedLanguage::Yaml));
+        assert_eq!(manager.guess_language("Cargo.toml"), Some(SupportedLanguage::Toml));
+    }
+    
+    #[cfg(feature = "markdown")]
+    assert_eq!(manager.guess_language("docs/README.md"), Some(SupportedLanguage::Markdown));
+    
     // Unknown
     assert_eq!(manager.guess_language("file.unknown"), None);
 }
+
+#[test]
+fn test_declaration_file_mode() {
+    let mut manager = LanguageManager::new();
+
+    let code = r#"
+declare module 'express-extra' {
+    export function middleware(options?: Options): Handler;
+}
+
+declare function greet(name: string): void;
+    "#;
+
+    let result = manager.parse_file("types/express-extra.d.ts", code).unwrap();
+    assert!(result.is_declaration_file);
+    assert!(result.entities.iter().any(|e| e == "declare module 'express-extra'"));
+    assert!(result.entities.iter().any(|e| e.starts_with("declare function greet")));
+
+    let result = manager.parse_file("src/index.ts", "export const x = 1;").unwrap();
+    assert!(!result.is_declaration_file);
+}
+
+#[test]
+fn test_flow_and_legacy_decorators_tolerance() {
+    use synapse_parser::ParseOptions;
+
+    let code = r#"// @flow
+opaque type ID = string;
+declare export function lookup(id: ID): void;
+const store = { @observable items: [] };
+function load(id: ID): ?string { return null; }
+"#;
+
+    // 默认选项：Flow 文件自动识别，错误被合并为一条
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("store.js", code).unwrap();
+    assert!(result.errors.len() <= 1);
+    assert!(result.entities.iter().any(|e| e.starts_with("function load")));
+
+    // 非 Flow 文件的旧版装饰器需要显式开启
+    let code = "const store = { @observable items: [] };\nfunction save() {}\n";
+    let strict = manager.parse_file("store.ts", code).unwrap();
+    assert!(!strict.errors.is_empty());
+
+    let mut tolerant = LanguageManager::with_options(ParseOptions {
+        legacy_decorators: true,
+        ..Default::default()
+    });
+    let result = tolerant.parse_file("store.ts", code).unwrap();
+    assert_eq!(result.errors.len(), 1);
+    assert!(result.errors[0].message.contains("tolerated"));
+}
+
+fn exported_names(result: &synapse_parser::ParseResult) -> Vec<String> {
+    assert_eq!(result.entities.len(), result.entity_info.len());
+    result
+        .entity_info
+        .iter()
+        .filter(|info| info.is_exported)
+        .filter_map(|info| info.name.clone())
+        .collect()
+}
+
+#[test]
+fn test_typescript_export_visibility() {
+    let mut manager = LanguageManager::new();
+    let code = r#"
+export function publicApi(): void {}
+function helper(): void {}
+export class Service {
+    private secret(): void {}
+    run(): void {}
+}
+function later() {}
+export { later };
+"#;
+    let result = manager.parse_file("api.ts", code).unwrap();
+    let exported = exported_names(&result);
+    assert!(exported.contains(&"publicApi".to_string()));
+    assert!(exported.contains(&"Service".to_string()));
+    assert!(exported.contains(&"run".to_string()));
+    assert!(exported.contains(&"later".to_string()));
+    assert!(!exported.contains(&"helper".to_string()));
+    assert!(!exported.contains(&"secret".to_string()));
+}
+
+#[test]
+fn test_arrow_function_constants() {
+    let mut manager = LanguageManager::new();
+    let code = r#"
+export const handler = async (req: Request): Promise<Response> => {
+  return respond(req);
+};
+const double = (n: number) => n * 2;
+const legacy = function (value) { return value; };
+const limit = 10;
+export { double };
+"#;
+    let result = manager.parse_file("api.ts", code).unwrap();
+    let functions: Vec<_> = result
+        .entity_info
+        .iter()
+        .enumerate()
+        .filter(|(_, info)| info.kind == "function")
+        .collect();
+    let names: Vec<_> = functions.iter().map(|(_, info)| info.name.as_deref().unwrap()).collect();
+    assert_eq!(names, ["handler", "double", "legacy"]);
+    // 签名截到函数体之前
+    assert_eq!(
+        result.entities[functions[0].0],
+        "export const handler = async (req: Request): Promise<Response> =>"
+    );
+    assert_eq!(result.entities[functions[1].0], "const double = (n: number) =>");
+
+    let exported = exported_names(&result);
+    assert!(exported.contains(&"handler".to_string()));
+    assert!(exported.contains(&"double".to_string()));
+    assert!(!exported.contains(&"legacy".to_string()));
+    // 普通常量不算函数
+    assert!(!names.contains(&"limit"));
+}
+
+#[test]
+fn test_react_components() {
+    use synapse_parser::{ComponentKind, ParseOptions};
+
+    let code = r#"
+import { useState } from 'react';
+
+export function Counter({ start }: CounterProps) {
+  const [count, setCount] = useState(start);
+  return <Button onClick={() => setCount(count + 1)}>{count}</Button>;
+}
+
+const Button = (props: ButtonProps) => <button {...props} />;
+
+export class Legacy extends React.PureComponent<LegacyProps> {
+  render() {
+    return <Counter start={0} />;
+  }
+}
+"#;
+    // 默认不识别组件
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("counter.tsx", code).unwrap();
+    assert!(result.entity_info.iter().all(|info| info.kind != "component"));
+
+    manager.set_options(ParseOptions {
+        react_components: true,
+        ..Default::default()
+    });
+    let result = manager.parse_file("counter.tsx", code).unwrap();
+    assert_eq!(result.entities.len(), result.entity_info.len());
+    let components: Vec<_> = result
+        .entity_info
+        .iter()
+        .zip(&result.entities)
+        .filter(|(info, _)| info.kind == "component")
+        .collect();
+    let names: Vec<_> = components.iter().map(|(info, _)| info.name.as_deref().unwrap()).collect();
+    assert_eq!(names, ["Counter", "Button", "Legacy"]);
+
+    let (counter, text) = components[0];
+    assert!(counter.is_exported);
+    assert_eq!(text.as_str(), "export function Counter({ start }: CounterProps)");
+    let details = counter.component.as_ref().unwrap();
+    assert_eq!(details.kind, ComponentKind::Function);
+    assert_eq!(details.props_type.as_deref(), Some("CounterProps"));
+    assert_eq!(details.hooks, ["useState"]);
+    assert_eq!(details.children, ["Button"]);
+    assert!(!components[1].0.is_exported);
+
+    let legacy = components[2].0.component.as_ref().unwrap();
+    assert_eq!(legacy.kind, ComponentKind::Class);
+    assert_eq!(legacy.props_type.as_deref(), Some("LegacyProps"));
+    // 函数与类实体仍然保留
+    assert!(result.entity_info.iter().any(|info| info.kind == "function" && info.name.as_deref() == Some("Counter")));
+    assert!(result.entity_info.iter().any(|info| info.kind == "class" && info.name.as_deref() == Some("Legacy")));
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn test_python_export_visibility() {
+    let mut manager = LanguageManager::new();
+    let code = r#"
+__all__ = ["listed"]
+
+def listed():
+    pass
+
+def not_listed():
+    pass
+"#;
+    let result = manager.parse_file("mod.py", code).unwrap();
+    assert_eq!(exported_names(&result), vec!["listed".to_string()]);
+
+    let code = "def public():\n    pass\n\ndef _private():\n    pass\n";
+    let result = manager.parse_file("mod.py", code).unwrap();
+    assert_eq!(exported_names(&result), vec!["public".to_string()]);
+}
+
+#[cfg(feature = "go")]
+#[test]
+fn test_go_export_visibility() {
+    let mut manager = LanguageManager::new();
+    let code = "package p\n\nfunc Exported() {}\n\nfunc unexported() {}\n";
+    let result = manager.parse_file("p.go", code).unwrap();
+    assert_eq!(exported_names(&result), vec!["Exported".to_string()]);
+}
+
+#[cfg(feature = "rust-lang")]
+#[test]
+fn test_rust_export_visibility() {
+    let mut manager = LanguageManager::new();
+    let code = "pub fn api() {}\npub(crate) fn internal() {}\nfn private() {}\n";
+    let result = manager.parse_file("lib.rs", code).unwrap();
+    assert_eq!(exported_names(&result), vec!["api".to_string()]);
+}
+
+#[cfg(feature = "java")]
+#[test]
+fn test_java_export_visibility() {
+    let mut manager = LanguageManager::new();
+    let code = r#"
+public class Api {
+    public void open() {}
+    private void hidden() {}
+}
+class PackagePrivate {
+    public void notReachable() {}
+}
+"#;
+    let result = manager.parse_file("Api.java", code).unwrap();
+    let exported = exported_names(&result);
+    assert!(exported.contains(&"Api".to_string()));
+    assert!(exported.contains(&"open".to_string()));
+    assert!(!exported.contains(&"hidden".to_string()));
+    assert!(!exported.contains(&"notReachable".to_string()));
+}
+
+#[test]
+fn test_entity_hook_summaries() {
+    let mut manager = LanguageManager::new();
+    manager.set_entity_hook(|entity: &synapse_parser::EntityContext| {
+        (entity.kind == "function").then(|| format!("{} ({})", entity.name.unwrap_or("?"), entity.language))
+    });
+
+    let code = "// Adds two numbers\nfunction add(a: number, b: number) { return a + b; }\n";
+    let result = manager.parse_file("math.ts", code).unwrap();
+
+    let summaries: Vec<_> = result.entity_info.iter().filter_map(|info| info.summary.as_deref()).collect();
+    assert_eq!(summaries, vec!["add (TypeScript)"]);
+
+    manager.clear_entity_hook();
+    let result = manager.parse_file("math.ts", code).unwrap();
+    assert!(result.entity_info.iter().all(|info| info.summary.is_none()));
+}
+
+#[test]
+fn test_structured_entities() {
+    let mut manager = LanguageManager::new();
+    let code = "export class Greeter {\n  greet(name: string): string {\n    return name;\n  }\n}\n";
+    let result = manager.parse_file("greeter.ts", code).unwrap();
+
+    let entities = result.structured_entities();
+    let class = entities.iter().find(|e| e.kind == "class").expect("class entity");
+    assert_eq!(class.name.as_deref(), Some("Greeter"));
+    assert_eq!(class.language, "TypeScript");
+    assert_eq!(class.signature.as_deref(), Some("class Greeter"));
+
+    let method = entities.iter().find(|e| e.kind == "method").expect("method entity");
+    assert_eq!(method.signature.as_deref(), Some("greet(name: string): string"));
+    assert_eq!((method.range.start, method.range.end), (2, 4));
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn test_batch_order_and_language_groups() {
+    let files: Vec<(String, String)> = vec![
+        ("b.py".to_string(), "def b():\n    pass\n".to_string()),
+        ("a.ts".to_string(), "export function a() {}\n".to_string()),
+        ("c.py".to_string(), "def c():\n    pass\n".to_string()),
+        ("notes.unknown".to_string(), String::new()),
+        ("d.ts".to_string(), "export function d() {}\n".to_string()),
+    ];
+
+    // 结果顺序与输入一致，不随语言分组变化
+    let results = LanguageManager::new().parse_files_batch(files.clone()).unwrap();
+    let paths: Vec<&str> = results.iter().map(|r| r.file_path.as_str()).collect();
+    assert_eq!(paths, ["b.py", "a.ts", "c.py", "d.ts"]);
+
+    // 按语言名排序分组，组内保持输入顺序
+    let groups = LanguageManager::new().parse_files_batch_by_language(files).unwrap();
+    let grouped: Vec<(&str, Vec<&str>)> = groups
+        .iter()
+        .map(|(lang, results)| (lang.as_str(), results.iter().map(|r| r.file_path.as_str()).collect()))
+        .collect();
+    assert_eq!(grouped, [("Python", vec!["b.py", "c.py"]), ("TypeScript", vec!["a.ts", "d.ts"])]);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_batch_matches_sequential() {
+    let files: Vec<(String, String)> = (0..40)
+        .map(|i| (format!("src/f{}.ts", i), format!("export function f{}() {{ return {}; }}\n", i, i)))
+        .chain(std::iter::once(("README.unknown".to_string(), String::new())))
+        .collect();
+
+    let manager = LanguageManager::new();
+    let parallel = manager.parse_files_batch_parallel(files.clone()).unwrap();
+    assert_eq!(parallel.len(), 40);
+    // 保持输入顺序
+    assert!(parallel.iter().enumerate().all(|(i, r)| r.file_path == format!("src/f{}.ts", i)));
+
+    let sequential = LanguageManager::new().parse_files_batch(files).unwrap();
+    assert_eq!(sequential.len(), parallel.len());
+    for (a, b) in parallel.iter().zip(&sequential) {
+        assert_eq!(a.file_path, b.file_path);
+        assert_eq!(a.entities, b.entities);
+    }
+}
+
+#[test]
+fn test_multiline_class_headers() {
+    let mut manager = LanguageManager::new();
+    let ts = "export class Repository<\n  T extends Entity,\n  K = string,\n>\n  extends Base<T>\n  implements Reader<T>,\n    Writer<T> {\n  find() {}\n}\n";
+    let result = manager.parse_file("src/repo.ts", ts).unwrap();
+    assert_eq!(
+        result.entities[0],
+        "export class Repository<\n  T extends Entity,\n  K = string,\n>\n  extends Base<T>\n  implements Reader<T>,\n    Writer<T>"
+    );
+
+    #[cfg(feature = "java")]
+    {
+        let java = "public class OrderService\n    extends BaseService\n    implements Auditable,\n        Closeable {\n}\n";
+        let result = manager.parse_file("OrderService.java", java).unwrap();
+        assert_eq!(
+            result.entities[0],
+            "public class OrderService\n    extends BaseService\n    implements Auditable,\n        Closeable"
+        );
+    }
+
+    #[cfg(feature = "cpp")]
+    {
+        let cpp = "class Widget\n    : public Base,\n      private Noncopyable\n{\n  int x;\n};\n";
+        let result = manager.parse_file("widget.cpp", cpp).unwrap();
+        assert_eq!(result.entities[0], "class Widget\n    : public Base,\n      private Noncopyable");
+    }
+}
+
+#[test]
+fn test_identical_entities_dedup_by_location() {
+    use synapse_parser::ParseOptions;
+
+    let code = "class A {\n  reset() {\n    this.items = [];\n  }\n}\n\nclass B {\n  reset() {\n    this.items = [];\n  }\n}\n";
+    let count = |result: &synapse_parser::ParseResult| {
+        result.entity_info.iter().filter(|info| info.name.as_deref() == Some("reset")).count()
+    };
+
+    // 文本相同、位置不同的实体都保留
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("src/stores.ts", code).unwrap();
+    assert_eq!(count(&result), 2);
+    assert_eq!(result.entities.iter().filter(|text| text.starts_with("class ")).count(), 2);
+
+    let mut manager = LanguageManager::with_options(ParseOptions {
+        dedup_text: true,
+        ..Default::default()
+    });
+    let result = manager.parse_file("src/stores.ts", code).unwrap();
+    assert_eq!(count(&result), 1);
+}
+
+#[cfg(feature = "java")]
+#[test]
+fn test_entity_cap_per_kind() {
+    use std::collections::BTreeMap;
+    use synapse_parser::{EntityCapOptions, ParseOptions};
+
+    let body: String = (0..200).map(|i| format!("        total += {};\n", i)).collect();
+    let code = format!(
+        "public class Report {{\n    public int sum(int base) {{\n        int total = base;\n{}        return total;\n    }}\n}}\n",
+        body
+    );
+    let parse = |method_cap: usize| {
+        let mut manager = LanguageManager::with_options(ParseOptions {
+            entity_cap: Some(EntityCapOptions {
+                max_chars: 100,
+                per_kind: BTreeMap::from([("method".to_string(), method_cap)]),
+                head_lines: 3,
+                tail_lines: 2,
+            }),
+            ..Default::default()
+        });
+        manager.parse_file("src/Report.java", &code).unwrap()
+    };
+
+    let result = parse(600);
+    let method = result.entity_info.iter().position(|info| info.kind == "method").unwrap();
+    let text = &result.entities[method];
+    assert!(result.entity_info[method].is_truncated);
+    assert!(text.chars().count() <= 600);
+    assert!(text.starts_with("public int sum(int base) {"));
+    assert!(text.contains("lines truncated"));
+    assert!(text.ends_with("        return total;\n    }"));
+    // 元数据仍按完整定义计算
+    assert_eq!((result.entity_info[method].range.start, result.entity_info[method].range.end), (2, 205));
+
+    // 上限为 0 的类型不截断
+    let result = parse(0);
+    let method = result.entity_info.iter().position(|info| info.kind == "method").unwrap();
+    assert!(!result.entity_info[method].is_truncated);
+    assert!(result.entities[method].contains("total += 199;"));
+}
+
+#[test]
+fn test_parser_config() {
+    use synapse_parser::{BodyMode, DedupMode, ParserConfig};
+
+    let java = "public class Report {\n    public int sum(int base) {\n        return base + 1;\n    }\n}\n";
+    let ts = "// TODO\nexport function load(path: string) {\n  return read(path);\n}\n// TODO\n";
+    let parse = |config: ParserConfig, path: &str, code: &str| LanguageManager::with_config(config).parse_file(path, code);
+    let text_of = |result: &synapse_parser::ParseResult, kind: &str| {
+        let i = result.entity_info.iter().position(|info| info.kind == kind).unwrap();
+        result.entities[i].clone()
+    };
+
+    // 默认沿用各语言的做法：Java 方法含方法体，TypeScript 函数只有签名
+    let java_default = parse(ParserConfig::default(), "Report.java", java).unwrap();
+    assert!(text_of(&java_default, "method").contains("return base + 1;"));
+    let ts_default = parse(ParserConfig::default(), "load.ts", ts).unwrap();
+    assert_eq!(text_of(&ts_default, "function"), "export function load(path: string)");
+    assert_eq!(ts_default.entity_info.iter().filter(|info| info.kind == "comment").count(), 2);
+
+    let signatures = ParserConfig {
+        bodies: BodyMode::Signatures,
+        ..Default::default()
+    };
+    assert_eq!(text_of(&parse(signatures, "Report.java", java).unwrap(), "method"), "public int sum(int base)");
+    let full = ParserConfig {
+        bodies: BodyMode::Full,
+        ..Default::default()
+    };
+    assert!(text_of(&parse(full, "load.ts", ts).unwrap(), "function").ends_with("return read(path);\n}"));
+
+    let no_comments = ParserConfig {
+        include_comments: false,
+        ..Default::default()
+    };
+    let result = parse(no_comments, "load.ts", ts).unwrap();
+    assert!(!result.entity_info.iter().any(|info| info.kind == "comment"));
+
+    let by_text = ParserConfig {
+        dedup: DedupMode::Text,
+        ..Default::default()
+    };
+    let comments = |result: &synapse_parser::ParseResult| result.entity_info.iter().filter(|info| info.kind == "comment").count();
+    let result = parse(by_text.clone(), "load.ts", ts).unwrap();
+    assert_eq!(comments(&result), 1);
+    // 去重只在单个文件内进行：同一个 manager 解析的另一个文件中相同的注释仍然保留
+    let mut manager = LanguageManager::with_config(by_text.clone());
+    assert_eq!(comments(&manager.parse_file("a.ts", ts).unwrap()), 1);
+    assert_eq!(comments(&manager.parse_file("b.ts", ts).unwrap()), 1);
+    // 注释单独按位置去重：代码按文本去重时，文件内重复的注释也都保留
+    let keep_comments = ParserConfig {
+        comment_dedup: Some(DedupMode::Location),
+        ..by_text
+    };
+    assert_eq!(comments(&parse(keep_comments, "load.ts", ts).unwrap()), 2);
+
+    let capped = ParserConfig {
+        max_entity_length: Some(20),
+        bodies: BodyMode::Full,
+        ..Default::default()
+    };
+    let result = parse(capped, "load.ts", ts).unwrap();
+    let function = result.entity_info.iter().position(|info| info.kind == "function").unwrap();
+    assert!(result.entity_info[function].is_truncated);
+
+    let small = ParserConfig {
+        max_file_size: Some(16),
+        ..Default::default()
+    };
+    assert!(parse(small, "load.ts", ts).unwrap_err().starts_with("File too large: load.ts"));
+}
+
+#[test]
+fn test_file_summary() {
+    let code = "import { a } from './a';\n// helper\nexport function f() {}\nexport function g() {}\n";
+    let result = LanguageManager::new().parse_file("summary.ts", code).unwrap();
+    let summary = &result.summary;
+    assert_eq!(summary.lines, 4);
+    assert_eq!(summary.entities, result.entities.len());
+    assert_eq!(summary.kinds.get("function"), Some(&2));
+    assert_eq!(summary.kinds.get("import"), Some(&1));
+    assert_eq!(summary.kinds.values().sum::<usize>(), summary.entities);
+}
+
+#[test]
+fn test_parse_if_changed() {
+    let manager = LanguageManager::new();
+    let code = "export function load() {}\n";
+
+    let first = manager.parse_if_changed("load.ts", code, None).unwrap().unwrap();
+    let hash = first.content_hash.clone().unwrap();
+    assert_eq!(hash, synapse_parser::content_hash(code));
+    assert_eq!(first.entities, manager.parse_file_shared("load.ts", code).unwrap().entities);
+
+    // 内容未变化时不解析
+    assert!(manager.parse_if_changed("load.ts", code, Some(&hash)).unwrap().is_none());
+    let changed = manager
+        .parse_if_changed("load.ts", "export function save() {}\n", Some(&hash))
+        .unwrap()
+        .unwrap();
+    assert_ne!(changed.content_hash.as_deref(), Some(hash.as_str()));
+}
+
+#[test]
+fn test_entity_order() {
+    use synapse_parser::{EntityOrder, ParseOptions};
+
+    let code = "import { a } from './a';\n// helper\nfunction f() {}\nimport { b } from './b';\nclass C {\n  m() {}\n}\n";
+    let parse = |entity_order: EntityOrder| {
+        LanguageManager::with_options(ParseOptions {
+            entity_order,
+            ..Default::default()
+        })
+        .parse_file("order.ts", code)
+        .unwrap()
+    };
+    let kinds = |result: &synapse_parser::ParseResult| -> Vec<String> {
+        result.entity_info.iter().map(|info| info.kind.clone()).collect()
+    };
+
+    let by_position = parse(EntityOrder::Position);
+    let starts: Vec<usize> = by_position.entity_info.iter().map(|info| info.range.start).collect();
+    assert!(starts.windows(2).all(|pair| pair[0] <= pair[1]), "{:?}", starts);
+    assert_eq!(kinds(&by_position), ["import", "comment", "function", "import", "class", "method"]);
+
+    // 按类型分组：组按首次出现的位置排列，组内按位置；片段与元数据同步重排
+    let by_kind = parse(EntityOrder::Kind);
+    assert_eq!(kinds(&by_kind), ["import", "import", "comment", "function", "class", "method"]);
+    assert_eq!(by_kind.entities[1], "import { b } from './b';");
+    let method = by_kind.entity_info.iter().position(|info| info.kind == "method").unwrap();
+    let parent = by_kind.entity_info[method].parent.unwrap();
+    assert_eq!(by_kind.entity_info[parent].name.as_deref(), Some("C"));
+
+    // 默认的捕获顺序包含同样的实体
+    let mut capture = kinds(&parse(EntityOrder::Capture));
+    capture.sort();
+    let mut sorted = kinds(&by_kind);
+    sorted.sort();
+    assert_eq!(capture, sorted);
+}
+
+#[test]
+#[cfg(all(feature = "go", feature = "rust-lang"))]
+fn test_full_bodies_across_languages() {
+    use synapse_parser::{BodyMode, ParserConfig};
+
+    let sources = [
+        ("load.ts", "export function load(path: string) {\n  return read(path);\n}\n", "export function load(path: string)", "return read(path);"),
+        ("load.go", "package main\n\nfunc (s *Store) Load(path string) error {\n\treturn read(path)\n}\n", "func (s *Store) Load(path string) error", "return read(path)"),
+        ("load.rs", "pub fn load(path: &str) -> Result<()> {\n    read(path)\n}\n", "pub fn load(path: &str) -> Result<()>", "read(path)"),
+    ];
+    for (path, code, signature, body) in sources {
+        let text_for = |bodies: BodyMode| {
+            let result = LanguageManager::with_config(ParserConfig { bodies, ..Default::default() })
+                .parse_file(path, code)
+                .unwrap();
+            let i = result
+                .entity_info
+                .iter()
+                .position(|info| matches!(info.kind.as_str(), "function" | "method"))
+                .unwrap();
+            result.entities[i].clone()
+        };
+        // 签名与带函数体的片段在各语言中形式一致：后者以前者开头
+        assert_eq!(text_for(BodyMode::Signatures), signature, "{}", path);
+        let full = text_for(BodyMode::Full);
+        assert!(full.starts_with(signature) && full.contains(body), "{}: {}", path, full);
+    }
+}
+
+#[test]
+fn test_function_signatures_from_ast() {
+    let mut manager = LanguageManager::new();
+    let ts = "export function merge<\n  T extends object,\n  U = {},\n>(\n  { target, source = {} }: Args<T, U>,\n  opts = { deep: true },\n): T & U {\n  return { ...target, ...source };\n}\nfunction id(x: number) { return x; }\n";
+    let result = manager.parse_file("src/merge.ts", ts).unwrap();
+    assert_eq!(
+        result.entities[0],
+        "export function merge<\n  T extends object,\n  U = {},\n>(\n  { target, source = {} }: Args<T, U>,\n  opts = { deep: true },\n): T & U"
+    );
+    // 单行函数不带函数体
+    assert_eq!(result.entities[1], "function id(x: number)");
+
+    #[cfg(feature = "rust-lang")]
+    {
+        let rust = "pub fn build<T>(\n    config: Config,\n) -> Result<T>\nwhere\n    T: Default,\n{\n    todo!()\n}\n";
+        let result = manager.parse_file("src/build.rs", rust).unwrap();
+        assert_eq!(result.entities[0], "pub fn build<T>(\n    config: Config,\n) -> Result<T>\nwhere\n    T: Default,");
+    }
+
+    #[cfg(feature = "python")]
+    {
+        let python = "def load(path,\n         opts={'mode': 'r'}):\n    return open(path)\n";
+        let result = manager.parse_file("load.py", python).unwrap();
+        assert_eq!(result.entities[0], "def load(path,\n         opts={'mode': 'r'}):");
+    }
+}
+
+#[test]
+fn test_pretty_signatures_keep_raw_span() {
+    let code = "export function connect(\n  host: string, // hostname\n  port: number,\n): Socket {\n  return open(host, port);\n}\nfunction ping() {}\n";
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("src/net.ts", code).unwrap();
+
+    let connect = &result.entity_info[0];
+    assert_eq!(connect.signature.as_deref(), Some("function connect(host: string, port: number): Socket"));
+    assert_eq!(
+        connect.raw_signature.as_deref(),
+        Some("function connect(\n  host: string, // hostname\n  port: number,\n): Socket")
+    );
+    // 原样即为单行时不重复保存
+    let ping = result.entity_info.iter().find(|info| info.name.as_deref() == Some("ping")).unwrap();
+    assert_eq!((ping.signature.as_deref(), ping.raw_signature.as_deref()), (Some("function ping()"), None));
+}
+
+#[test]
+fn test_parse_file_bytes_non_utf8() {
+    let mut manager = LanguageManager::new();
+    // Windows-1252 的字符串字面量：整个文件照常解析，errors 中说明编码
+    let result = manager
+        .parse_file_bytes("src/greet.ts", b"export function greet() {\n  return 'ol\xe1';\n}\n")
+        .unwrap();
+    assert_eq!(result.entity_info[0].name.as_deref(), Some("greet"));
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].range.as_ref().map(|r| r.start), Some(2));
+
+    let clean = manager.parse_file_bytes("src/a.ts", b"export const a = 1;\n").unwrap();
+    assert!(clean.errors.is_empty());
+}
+
+#[test]
+fn test_shared_manager_pools_parsers() {
+    let manager = LanguageManager::new();
+    assert_eq!(manager.idle_parsers(SupportedLanguage::TypeScript), 0);
+
+    // 多个线程共用一个管理器，每个请求借出一个 Parser
+    let results: Vec<_> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let manager = &manager;
+                scope.spawn(move || {
+                    let path = format!("src/f{}.ts", i);
+                    let code = format!("export function f{}() {{ return {}; }}\n", i, i);
+                    manager.parse_file_shared(&path, &code).unwrap()
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    for (i, result) in results.iter().enumerate() {
+        assert_eq!(result.entity_info[0].name.as_deref(), Some(format!("f{}", i).as_str()));
+    }
+    // Parser 全部归还，数量不超过并发数
+    let idle = manager.idle_parsers(SupportedLanguage::TypeScript);
+    assert!((1..=8).contains(&idle));
+    manager.parse_file_shared("src/again.ts", "export const x = 1;\n").unwrap();
+    assert_eq!(manager.idle_parsers(SupportedLanguage::TypeScript), idle);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_streaming_batch_reports_each_file() {
+    let files: Vec<(String, String)> = (0..40)
+        .map(|i| (format!("src/f{}.ts", i), format!("export function f{}() {{ return {}; }}\n", i, i)))
+        .chain(std::iter::once(("README.unknown".to_string(), String::new())))
+        .collect();
+
+    let mut outcomes = Vec::new();
+    let throttle = synapse_parser::ThrottleOptions { max_threads: Some(3), ..Default::default() };
+    let progress = LanguageManager::new()
+        .parse_files_streaming(files, &throttle, |outcome| outcomes.push(outcome))
+        .unwrap();
+
+    assert_eq!((progress.completed, progress.failed, progress.total), (41, 1, 41));
+    assert_eq!(outcomes.len(), 41);
+    // 进度单调递增
+    assert!(outcomes.iter().enumerate().all(|(i, o)| o.progress.completed == i + 1 && o.progress.total == 41));
+    let failed: Vec<_> = outcomes.iter().filter(|o| o.error.is_some()).collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].file_path, "README.unknown");
+    assert!(failed[0].result.is_none());
+    assert!(outcomes
+        .iter()
+        .filter_map(|o| o.result.as_ref())
+        .all(|r| r.entities.len() == 1 && r.file_path.starts_with("src/f")));
+}
+
+#[test]
+fn test_typescript_imports_and_exports() {
+    let mut manager = LanguageManager::new();
+    let code = r#"
+import React, { useState, b as c, type Props } from 'react';
+import * as path from "path";
+import type { Config } from './config';
+import { type A, type B } from './types';
+import './polyfill';
+import fs = require('fs');
+
+export { useState as useLocalState };
+export * from './helpers';
+export * as utils from './utils';
+export type { Config } from './config';
+export default function App() {}
+export const one = 1, { two } = values;
+export interface Options {}
+"#;
+    let result = manager.parse_file("app.ts", code).unwrap();
+
+    let react = &result.imports[0];
+    assert_eq!(react.source, "react");
+    assert_eq!(react.default_import.as_deref(), Some("React"));
+    assert_eq!(react.specifiers, vec!["useState", "b", "Props"]);
+    assert!(!react.is_type_only);
+    assert_eq!(react.type_specifiers, vec!["Props"]);
+
+    assert_eq!(result.imports[1].namespace_import.as_deref(), Some("path"));
+    assert!(result.imports[2].is_type_only);
+    assert!(result.imports[3].is_type_only, "all specifiers are type-only");
+    assert!(result.imports[4].specifiers.is_empty());
+    assert_eq!(result.imports[5].source, "fs");
+    assert_eq!(result.imports.len(), 6);
+
+    let exports: Vec<(Vec<String>, Option<&str>, bool, bool)> = result
+        .exports
+        .iter()
+        .map(|e| (e.specifiers.clone(), e.source.as_deref(), e.is_wildcard, e.is_type_only))
+        .collect();
+    let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        exports,
+        vec![
+            (names(&["useLocalState"]), None, false, false),
+            (names(&[]), Some("./helpers"), true, false),
+            (names(&["utils"]), Some("./utils"), false, false),
+            (names(&["Config"]), Some("./config"), false, true),
+            (names(&["default"]), None, false, false),
+            (names(&["one", "two"]), None, false, false),
+            (names(&["Options"]), None, false, true),
+        ]
+    );
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn test_python_imports_and_exports() {
+    let mut manager = LanguageManager::new();
+    let code = "import os.path as p, sys\nfrom ..pkg import a, b as c\nfrom x import *\n\n__all__ = ['run']\n\ndef run():\n    import json\n";
+    let result = manager.parse_file("mod.py", code).unwrap();
+
+    let summary: Vec<(&str, Option<&str>, Vec<String>, bool)> = result
+        .imports
+        .iter()
+        .map(|i| (i.source.as_str(), i.namespace_import.as_deref(), i.specifiers.clone(), i.is_wildcard))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("os.path", Some("p"), vec![], false),
+            ("sys", Some("sys"), vec![], false),
+            ("..pkg", None, vec!["a".to_string(), "b".to_string()], false),
+            ("x", None, vec![], true),
+            ("json", Some("json"), vec![], false),
+        ]
+    );
+    assert_eq!(result.exports[0].specifiers, vec!["run"]);
+}
+
+#[cfg(feature = "go")]
+#[test]
+fn test_go_imports() {
+    let mut manager = LanguageManager::new();
+    let code = "package main\n\nimport (\n\t\"net/http\"\n\tf \"fmt\"\n\t. \"strings\"\n\t_ \"embed\"\n)\n";
+    let result = manager.parse_file("main.go", code).unwrap();
+
+    let summary: Vec<(&str, Option<&str>, bool)> = result
+        .imports
+        .iter()
+        .map(|i| (i.source.as_str(), i.namespace_import.as_deref(), i.is_wildcard))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("net/http", Some("http"), false),
+            ("fmt", Some("f"), false),
+            ("strings", None, true),
+            ("embed", None, false),
+        ]
+    );
+}
+
+#[cfg(feature = "rust-lang")]
+#[test]
+fn test_rust_imports_and_reexports() {
+    let mut manager = LanguageManager::new();
+    let code = "use std::collections::{HashMap, hash_map::Entry as E};\nuse crate::a::*;\nuse serde;\npub use self::b::{self as bee, C};\nextern crate log;\n";
+    let result = manager.parse_file("lib.rs", code).unwrap();
+
+    let summary: Vec<(&str, Vec<String>, Option<&str>, bool)> = result
+        .imports
+        .iter()
+        .map(|i| (i.source.as_str(), i.specifiers.clone(), i.namespace_import.as_deref(), i.is_wildcard))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("std::collections", vec!["HashMap".to_string()], None, false),
+            ("std::collections::hash_map", vec!["Entry".to_string()], None, false),
+            ("crate::a", vec![], None, true),
+            ("serde", vec![], Some("serde"), false),
+            ("self::b", vec!["C".to_string()], Some("b"), false),
+            ("log", vec![], Some("log"), false),
+        ]
+    );
+
+    let exported: Vec<&str> = result.exports.iter().flat_map(|e| &e.specifiers).map(String::as_str).collect();
+    assert_eq!(exported, vec!["bee", "C"]);
+}
+
+#[cfg(feature = "java")]
+#[test]
+fn test_java_imports() {
+    let mut manager = LanguageManager::new();
+    let code = "import java.util.List;\nimport java.io.*;\nimport static org.junit.Assert.assertEquals;\n\npublic class A {}\n";
+    let result = manager.parse_file("A.java", code).unwrap();
+
+    let summary: Vec<(&str, Vec<String>, bool)> = result
+        .imports
+        .iter()
+        .map(|i| (i.source.as_str(), i.specifiers.clone(), i.is_wildcard))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("java.util", vec!["List".to_string()], false),
+            ("java.io", vec![], true),
+            ("org.junit.Assert", vec!["assertEquals".to_string()], false),
+        ]
+    );
+}
+
+#[cfg(feature = "java")]
+#[test]
+fn test_java_public_type_exports() {
+    let code = "package app;
+
+public class Service {
+  public static class Config {}
+}
+
+class Helper {}
+
+public interface Api {}
+";
+    let result = LanguageManager::new().parse_file("Service.java", code).unwrap();
+    let exported: Vec<&str> = result.exports.iter().flat_map(|e| &e.specifiers).map(String::as_str).collect();
+    // 嵌套类型随外层类型导出，包级可见的类不导出
+    assert_eq!(exported, vec!["Service", "Api"]);
+}
+
+#[cfg(feature = "rust-lang")]
+#[test]
+fn test_rust_pub_item_exports() {
+    let code = "pub fn run() {}
+fn helper() {}
+pub(crate) struct Internal;
+pub struct Config { pub name: String }
+pub mod api {
+    pub fn nested() {}
+}
+pub const LIMIT: usize = 3;
+impl Config { pub fn new() -> Self { todo!() } }
+";
+    let result = LanguageManager::new().parse_file("src/lib.rs", code).unwrap();
+    let exported: Vec<&str> = result.exports.iter().flat_map(|e| &e.specifiers).map(String::as_str).collect();
+    assert_eq!(exported, vec!["run", "Config", "api", "LIMIT"]);
+    assert!(result.exports.iter().all(|e| e.source.is_none()));
+}
+
+#[cfg(feature = "go")]
+#[test]
+fn test_go_generics_and_embedding() {
+    use synapse_parser::RelationKind;
+
+    let code = r#"package store
+
+type Store[K comparable, V any] struct {
+	sync.Mutex
+	*Base
+	items map[K]V
+}
+
+type ReadCloser interface {
+	io.Reader
+	~int | ~string
+	Close() error
+}
+
+func Map[T, U any](xs []T, f func(T) U) []U {
+	return nil
+}
+"#;
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("store.go", code).unwrap();
+
+    let map = result.entity_info.iter().find(|info| info.name.as_deref() == Some("Map")).unwrap();
+    assert_eq!(map.constraints, vec!["T, U any"]);
+    let store = result.entity_info.iter().find(|info| info.kind == "type").unwrap();
+    assert_eq!(store.constraints, vec!["K comparable", "V any"]);
+
+    let relations: Vec<(&str, &str)> = result
+        .type_relations
+        .iter()
+        .filter(|r| r.kind == RelationKind::Embeds)
+        .map(|r| (r.type_name.as_str(), r.target.as_str()))
+        .collect();
+    assert_eq!(
+        relations,
+        vec![("Store", "sync.Mutex"), ("Store", "Base"), ("ReadCloser", "io.Reader")]
+    );
+}
+
+#[cfg(feature = "go")]
+#[test]
+fn test_go_capitalized_exports() {
+    let code = "package store
+
+type Store struct{}
+type cache map[string]int
+
+const (
+	MaxSize = 10
+	minSize = 1
+)
+
+var Default, fallback = New(), New()
+
+func New() *Store { return &Store{} }
+func (s *Store) Get() {}
+func helper() {}
+";
+    let result = LanguageManager::new().parse_file("store.go", code).unwrap();
+    let exported: Vec<&str> = result.exports.iter().flat_map(|e| &e.specifiers).map(String::as_str).collect();
+    assert_eq!(exported, vec!["Store", "MaxSize", "Default", "New"]);
+}
+
+#[cfg(feature = "c-lang")]
+#[test]
+fn test_c_includes() {
+    let mut manager = LanguageManager::new();
+    let code = "#include <stdio.h>\n#ifdef DEBUG\n#include \"debug.h\"\n#endif\n";
+    let result = manager.parse_file("main.c", code).unwrap();
+
+    let sources: Vec<&str> = result.imports.iter().map(|i| i.source.as_str()).collect();
+    assert_eq!(sources, vec!["stdio.h", "debug.h"]);
+}
+
+#[test]
+fn test_relative_path_mode() {
+    use synapse_parser::{ParseOptions, PathMode};
+
+    let mut manager = LanguageManager::with_options(ParseOptions {
+        path_mode: PathMode::Relative,
+        project_root: Some("/home/dev/project".to_string()),
+        ..Default::default()
+    });
+
+    let result = manager
+        .parse_file("/home/dev/project/src/app.ts", "import { a } from './a';\nexport const b = a;\n")
+        .unwrap();
+    assert_eq!(result.file_path, "src/app.ts");
+    assert_eq!(result.imports[0].file_path, "src/app.ts");
+    assert_eq!(result.exports[0].file_path, "src/app.ts");
+
+    let error = manager.parse_file("/home/dev/project/notes.txt", "").unwrap_err();
+    assert!(!error.contains("/home/dev"), "{}", error);
+}
+
+#[test]
+fn test_large_file_sampling() {
+    use synapse_parser::{ParseOptions, SamplingOptions};
+
+    let mut code = String::from("export function head() {\n  return 1;\n}\n");
+    for i in 0..50 {
+        code.push_str(&format!("export class Middle{i} extends Base {{\n  method{i}() {{\n    return {i};\n  }}\n}}\n"));
+    }
+    code.push_str("class Tail {\n  tailMethod() {}\n}\n");
+
+    let mut manager = LanguageManager::with_options(ParseOptions {
+        sampling: Some(SamplingOptions { threshold: 1024, sample_bytes: 80 }),
+        ..Default::default()
+    });
+    let result = manager.parse_file("gen.ts", &code).unwrap();
+    assert!(result.is_partial);
+
+    let names: Vec<&str> = result.entity_info.iter().filter_map(|e| e.name.as_deref()).collect();
+    assert!(names.contains(&"head"));
+    assert!(names.contains(&"tailMethod"));
+    assert!(names.contains(&"Middle25"));
+    // 中间部分的嵌套成员不提取，顶层声明只保留签名
+    assert!(!names.contains(&"method25"));
+    let text = |name: &str| {
+        let i = result.entity_info.iter().position(|e| e.name.as_deref() == Some(name)).unwrap();
+        result.entities[i].as_str()
+    };
+    assert_eq!(text("Middle25"), "class Middle25 extends Base");
+
+    // 未超过阈值时完整提取
+    let small = manager.parse_file("small.ts", "class A {\n  m() {}\n}\n").unwrap();
+    assert!(!small.is_partial);
+}
+
+/// 调用关系展开为 (调用方, 被调用名, 调用行)
+fn call_edges(result: &synapse_parser::ParseResult) -> Vec<(Option<&str>, &str, usize)> {
+    result
+        .calls
+        .iter()
+        .map(|call| (call.caller.as_deref(), call.callee.as_str(), call.range.start))
+        .collect()
+}
+
+#[test]
+fn test_typescript_call_graph() {
+    let code = r#"setup();
+export function main() {
+  const svc = new Service<number>();
+  svc.client.fetch(load(1));
+}
+const handler = async () => {
+  [1].forEach(function () { main(); });
+};
+"#;
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("app.ts", code).unwrap();
+    assert_eq!(
+        call_edges(&result),
+        vec![
+            (None, "setup", 1),
+            (Some("main"), "Service", 3),
+            (Some("main"), "fetch", 4),
+            (Some("main"), "load", 4),
+            (Some("handler"), "forEach", 7),
+            (Some("handler"), "main", 7),
+        ]
+    );
+
+    // 调用方范围与实体元数据一致，可据此关联到实体
+    let main = result.entity_info.iter().find(|e| e.name.as_deref() == Some("main")).unwrap();
+    assert_eq!(result.calls[1].caller_range.as_ref(), Some(&main.range));
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn test_python_call_graph() {
+    let code = "class Repo:\n    def save(self, item):\n        self.validate(item)\n        return db.insert(item)\n\ndef run():\n    Repo().save(map(lambda x: str(x), []))\n";
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("repo.py", code).unwrap();
+    assert_eq!(
+        call_edges(&result),
+        vec![
+            (Some("save"), "validate", 3),
+            (Some("save"), "insert", 4),
+            (Some("run"), "save", 7),
+            (Some("run"), "Repo", 7),
+            (Some("run"), "map", 7),
+            (Some("run"), "str", 7),
+        ]
+    );
+}
+
+#[cfg(feature = "go")]
+#[test]
+fn test_go_call_graph() {
+    let code = "package main\n\nfunc (s *Server) Start() {\n\tgo func() { s.listen() }()\n\tfmt.Println(helper())\n}\n";
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("server.go", code).unwrap();
+    let edges = call_edges(&result);
+    assert!(edges.contains(&(Some("Start"), "listen", 4)));
+    assert!(edges.contains(&(Some("Start"), "Println", 5)));
+    assert!(edges.contains(&(Some("Start"), "helper", 5)));
+}
+
+#[cfg(feature = "rust-lang")]
+#[test]
+fn test_rust_call_graph() {
+    let code = "fn build() -> Config {\n    let items = Vec::<u8>::new();\n    items.iter().map(|x| parse::<u8>(x));\n    println!(\"done\");\n    Config::default()\n}\n";
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("config.rs", code).unwrap();
+    assert_eq!(
+        call_edges(&result),
+        vec![
+            (Some("build"), "new", 2),
+            (Some("build"), "map", 3),
+            (Some("build"), "iter", 3),
+            (Some("build"), "parse", 3),
+            (Some("build"), "default", 5),
+        ]
+    );
+}
+
+#[cfg(feature = "java")]
+#[test]
+fn test_java_call_graph() {
+    let code = "public class App {\n    public App() { init(); }\n    void run() {\n        List<String> xs = new java.util.ArrayList<String>();\n        xs.forEach(x -> log.info(x));\n    }\n}\n";
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("App.java", code).unwrap();
+    assert_eq!(
+        call_edges(&result),
+        vec![
+            (Some("App"), "init", 2),
+            (Some("run"), "ArrayList", 4),
+            (Some("run"), "forEach", 5),
+            (Some("run"), "info", 5),
+        ]
+    );
+}
+
+#[test]
+fn test_custom_query() {
+    let code = "@Controller('users')\nclass UsersController {\n  findAll() {}\n}\n\nclass Plain {}\n";
+    let query = format!(
+        "{}\n(class_declaration\n  (decorator (call_expression function: (identifier) @_decorator (#eq? @_decorator \"Controller\")))\n  name: (type_identifier) @definition.controller)\n",
+        LanguageManager::default_query(SupportedLanguage::TypeScript)
+    );
+
+    let mut manager = LanguageManager::new();
+    manager.set_query(SupportedLanguage::TypeScript, &query).unwrap();
+    let result = manager.parse_file("users.controller.ts", code).unwrap();
+    let controllers: Vec<_> = result
+        .entity_info
+        .iter()
+        .zip(&result.entities)
+        .filter(|(info, _)| info.kind == "controller")
+        .collect();
+    assert_eq!(controllers.len(), 1);
+    assert_eq!(controllers[0].0.name.as_deref(), Some("UsersController"));
+    assert!(controllers[0].1.starts_with("@Controller('users')\nclass UsersController {"));
+    // 内置捕获仍然生效
+    assert!(result.entity_info.iter().any(|e| e.kind == "class" && e.name.as_deref() == Some("Plain")));
+
+    // 无效 query 被拒绝，原有 query 保持不变
+    let error = manager.set_query(SupportedLanguage::TypeScript, "(no_such_node) @x").unwrap_err();
+    assert!(error.contains("Invalid query"), "{}", error);
+    assert!(manager.parse_file("a.ts", code).unwrap().entity_info.iter().any(|e| e.kind == "controller"));
+
+    manager.reset_query(SupportedLanguage::TypeScript);
+    assert!(!manager.parse_file("a.ts", code).unwrap().entity_info.iter().any(|e| e.kind == "controller"));
+}
+
+fn entity_doc<'a>(result: &'a synapse_parser::ParseResult, name: &str) -> Option<&'a str> {
+    result
+        .entity_info
+        .iter()
+        .find(|e| e.kind != "comment" && e.name.as_deref() == Some(name))
+        .and_then(|e| e.doc.as_deref())
+}
+
+#[test]
+fn test_typescript_doc_comments() {
+    let code = "/**\n * Adds two numbers.\n * @param a first\n */\nexport function add(a: number, b: number) { return a + b; }\n\n// unrelated note\n\nfunction noDoc() {}\n\nclass Service {\n  /** Starts the service. */\n  start() {}\n}\n";
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("math.ts", code).unwrap();
+    assert_eq!(entity_doc(&result, "add"), Some("Adds two numbers.\n@param a first"));
+    assert_eq!(entity_doc(&result, "start"), Some("Starts the service."));
+    // 空行隔开的注释不属于定义
+    assert_eq!(entity_doc(&result, "noDoc"), None);
+    assert_eq!(entity_doc(&result, "Service"), None);
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn test_python_docstrings() {
+    let code = "def load(path):\n    \"\"\"Load a config file.\n\n    Returns a dict.\n    \"\"\"\n    return {}\n\n# Helper for tests\n@cache\ndef helper():\n    pass\n\nclass Store:\n    '''In-memory store.'''\n";
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("config.py", code).unwrap();
+    assert_eq!(entity_doc(&result, "load"), Some("Load a config file.\n\nReturns a dict."));
+    assert_eq!(entity_doc(&result, "helper"), Some("Helper for tests"));
+    assert_eq!(entity_doc(&result, "Store"), Some("In-memory store."));
+}
+
+#[cfg(feature = "rust-lang")]
+#[test]
+fn test_rust_doc_comments() {
+    let code = "/// Parsed configuration.\n///\n/// Loaded once at startup.\n#[derive(Debug)]\npub struct Config {}\n\n// plain comment\nfn helper() {}\n";
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("config.rs", code).unwrap();
+    assert_eq!(entity_doc(&result, "Config"), Some("Parsed configuration.\n\nLoaded once at startup."));
+    assert_eq!(entity_doc(&result, "helper"), Some("plain comment"));
+}
+
+#[cfg(feature = "java")]
+#[test]
+fn test_java_doc_comments() {
+    let code = "/** Application entry. */\npublic class App {\n    /**\n     * Runs the app.\n     */\n    @Override\n    public void run() {}\n}\n";
+    let mut manager = LanguageManager::new();
+    let result = manager.parse_file("App.java", code).unwrap();
+    assert_eq!(entity_doc(&result, "App"), Some("Application entry."));
+    assert_eq!(entity_doc(&result, "run"), Some("Runs the app."));
+}
+
+#[test]
+fn test_generated_code_markers() {
+    let mut manager = LanguageManager::new();
+    let generated = manager
+        .parse_file("src/api/client.ts", "/* eslint-disable */\n// @generated by openapi-typescript\nexport class ApiClient {}\n")
+        .unwrap();
+    assert!(generated.is_generated);
+    let handwritten = manager
+        .parse_file("src/api/wrapper.ts", "// Wraps the generated client.\nexport function call() { return new ApiClient(); }\n")
+        .unwrap();
+    assert!(!handwritten.is_generated);
+    // 路径约定同样生效
+    assert!(manager.parse_file("src/__generated__/types.ts", "export type Id = string;\n").unwrap().is_generated);
+
+    let mut index = synapse_parser::CodeIndex::new();
+    index.insert_batch(vec![generated, handwritten]);
+    assert!(index.file("src/api/client.ts").unwrap().is_generated);
+    let client = index.entities().find(|e| e.name.as_deref() == Some("ApiClient")).unwrap();
+    assert!(client.rank_hints.is_generated);
+}
+
+#[cfg(feature = "go")]
+#[test]
+fn test_go_generated_header() {
+    let code = "// Code generated by protoc-gen-go. DO NOT EDIT.\n// source: user.proto\n\npackage api\n\ntype User struct{}\n";
+    let mut manager = LanguageManager::new();
+    assert!(manager.parse_file("api/user.go", code).unwrap().is_generated);
+}
@@ -0,0 +1,94 @@
+/**
+ * 端到端示例（NAPI）：索引 → 检索 → 上下文打包
+ *
+ * 与 Rust 示例（examples/src/lib.rs）走同一条流程：
+ * 1. ProjectScanner 在 Rust 侧遍历并解析项目，结果放入 CodeIndex
+ * 2. 按名字检索实体：完全匹配优先，其次按被调用次数
+ * 3. 把命中的实体按 token 预算打包成 Markdown 上下文
+ *
+ * 运行（先执行 `pnpm build:rust`）：
+ *   node examples/node/pipeline.mjs examples/sample-repo invoice --budget 800
+ */
+
+import path from 'node:path';
+import { createRequire } from 'node:module';
+import { fileURLToPath } from 'node:url';
+
+const currentDir = path.dirname(fileURLToPath(import.meta.url));
+const require = createRequire(import.meta.url);
+const native = require(
+  process.env.SYNAPSE_NATIVE_MODULE ??
+    path.resolve(currentDir, '../../target/release/synapse_parser_napi.node'),
+);
+
+/** 扫描并索引项目根目录 */
+export async function indexProject(root) {
+  const scanner = new native.ProjectScanner();
+  const scan = JSON.parse(await scanner.scan(root));
+  const index = new native.CodeIndex();
+  index.insertResults(scan.results.map((result) => JSON.stringify(result)));
+  return { index, stats: scan.stats };
+}
+
+/** 按名字检索实体（不区分大小写的子串匹配），最多返回 limit 个 */
+export function search(index, query, limit = 10) {
+  const needle = query.toLowerCase();
+  return JSON.parse(index.entities())
+    .filter((entity) => entity.name?.toLowerCase().includes(needle))
+    .map((entity) => ({ entity, exact: entity.name.toLowerCase() === needle }))
+    .sort(
+      (a, b) =>
+        Number(b.exact) - Number(a.exact) ||
+        b.entity.rankHints.callerCount - a.entity.rankHints.callerCount ||
+        a.entity.id.localeCompare(b.entity.id),
+    )
+    .slice(0, limit)
+    .map(({ entity }) => entity);
+}
+
+/** 按检索顺序把实体放入上下文，直到用完 budget 个 token */
+export function contextPack(index, hits, budget) {
+  const hovers = index.hoverInfos(hits.map((entity) => entity.id));
+  const pack = { markdown: '', tokens: 0, included: [], omitted: [] };
+  hits.forEach((entity, i) => {
+    if (!hovers[i]) return;
+    let section = `## ${entity.name ?? entity.kind}\n\n${hovers[i]}`;
+    if (entity.signature !== entity.text && entity.text.split('\n').length > 1) {
+      section += `\n\`\`\`${entity.language.toLowerCase()}\n${entity.text}\n\`\`\`\n`;
+    }
+    section += '\n';
+
+    const tokens = native.countTokens(section);
+    if (pack.tokens + tokens > budget) {
+      pack.omitted.push(entity.id);
+      return;
+    }
+    pack.markdown += section;
+    pack.tokens += tokens;
+    pack.included.push(entity.id);
+  });
+  return pack;
+}
+
+async function main(args) {
+  const budgetIndex = args.indexOf('--budget');
+  const budget = budgetIndex >= 0 ? Number(args[budgetIndex + 1]) : 800;
+  const [root, query] = args.filter((arg, i) => !arg.startsWith('--') && args[i - 1] !== '--budget');
+  if (!root || !query || Number.isNaN(budget)) {
+    console.error('usage: node examples/node/pipeline.mjs <project_dir> <query> [--budget <tokens>]');
+    process.exit(1);
+  }
+
+  const { index, stats } = await indexProject(root);
+  console.error(`indexed ${stats.parsed} files in ${stats.elapsedMs} ms`);
+  const hits = search(index, query);
+  const pack = contextPack(index, hits, budget);
+  console.log(pack.markdown);
+  console.error(
+    `packed ${pack.included.length} entities (${pack.tokens} tokens), ${pack.omitted.length} over budget`,
+  );
+}
+
+if (process.argv[1] === fileURLToPath(import.meta.url)) {
+  await main(process.argv.slice(2));
+}
@@ -0,0 +1,23 @@
+"""Monthly revenue report for the sample shop."""
+
+from collections import defaultdict
+
+
+class RevenueReport:
+    """Aggregates invoice totals per customer."""
+
+    def __init__(self):
+        self.totals = defaultdict(float)
+
+    def add_invoice(self, customer_id, total):
+        """Records one invoice total."""
+        self.totals[customer_id] += total
+
+    def top_customers(self, limit=3):
+        """Customers with the highest revenue."""
+        return sorted(self.totals.items(), key=lambda item: item[1], reverse=True)[:limit]
+
+
+def render_report(report):
+    """Formats the report as plain text."""
+    return "\n".join(f"{customer}: {total:.2f}" for customer, total in report.top_customers())
@@ -0,0 +1,8 @@
+/** A percentage or fixed-amount discount */
+export type Discount = { kind: 'percent'; value: number } | { kind: 'fixed'; value: number };
+
+/** Applies a discount, never going below zero */
+export function applyDiscount(amount: number, discount: Discount): number {
+  const reduced = discount.kind === 'percent' ? amount * (1 - discount.value / 100) : amount - discount.value;
+  return Math.max(0, reduced);
+}
@@ -0,0 +1,31 @@
+import { applyDiscount, Discount } from './discount';
+
+/** A line on an invoice */
+export interface LineItem {
+  sku: string;
+  quantity: number;
+  unitPrice: number;
+}
+
+/** An invoice for one customer */
+export class Invoice {
+  constructor(
+    readonly customerId: string,
+    private readonly items: LineItem[],
+  ) {}
+
+  /** Sum of all lines before discounts */
+  subtotal(): number {
+    return this.items.reduce((sum, item) => sum + item.quantity * item.unitPrice, 0);
+  }
+
+  /** Total after applying the customer's discount */
+  total(discount?: Discount): number {
+    return discount ? applyDiscount(this.subtotal(), discount) : this.subtotal();
+  }
+}
+
+/** Builds an invoice from raw cart rows */
+export function createInvoice(customerId: string, rows: LineItem[]): Invoice {
+  return new Invoice(customerId, rows.filter((row) => row.quantity > 0));
+}
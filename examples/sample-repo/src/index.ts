@@ -0,0 +1,2 @@
+export { createInvoice, Invoice } from './billing/invoice';
+export { applyDiscount } from './billing/discount';
@@ -0,0 +1,78 @@
+//! 端到端示例：索引 → 检索 → 上下文打包
+//!
+//! 与 NAPI 示例（`node/pipeline.mjs`）走同一条流程，供集成方作为可执行的参考：
+//! 1. `ProjectScanner` 在 Rust 侧遍历并解析整个项目，结果放入 `CodeIndex`
+//! 2. 按名字检索实体：完全匹配优先，其次按被调用次数
+//! 3. 把命中的实体按 token 预算打包成 Markdown 上下文（悬停信息 + 代码片段），放不下的记入 `omitted`
+
+use std::path::Path;
+
+use synapse_parser::{count_tokens, CodeIndex, IndexedEntity, MetricsOptions, ProjectScanner, ScanOptions, ScanStats};
+
+/// 扫描并索引项目根目录
+pub fn index_project(root: impl AsRef<Path>) -> Result<(CodeIndex, ScanStats), String> {
+    let scan = ProjectScanner::new(ScanOptions::default()).scan(root)?;
+    let mut index = CodeIndex::new();
+    index.insert_batch(scan.results);
+    Ok((index, scan.stats))
+}
+
+/// 按名字检索实体（不区分大小写的子串匹配），最多返回 `limit` 个
+pub fn search<'a>(index: &'a CodeIndex, query: &str, limit: usize) -> Vec<&'a IndexedEntity> {
+    let query = query.to_lowercase();
+    let mut matches: Vec<(bool, &IndexedEntity)> = index
+        .entities()
+        .filter_map(|entity| {
+            let name = entity.name.as_deref()?.to_lowercase();
+            name.contains(&query).then_some((name == query, entity))
+        })
+        .collect();
+    matches.sort_by(|(a_exact, a), (b_exact, b)| {
+        b_exact
+            .cmp(a_exact)
+            .then(b.rank_hints.caller_count.cmp(&a.rank_hints.caller_count))
+            .then(a.id.cmp(&b.id))
+    });
+    matches.into_iter().take(limit).map(|(_, entity)| entity).collect()
+}
+
+/// 打包好的上下文
+#[derive(Debug, Clone, Default)]
+pub struct ContextPack {
+    pub markdown: String,
+    /// `markdown` 的 token 估算（见 `count_tokens`）
+    pub tokens: usize,
+    /// 放入上下文的实体 ID（按检索顺序）
+    pub included: Vec<String>,
+    /// 超出预算而未放入的实体 ID
+    pub omitted: Vec<String>,
+}
+
+/// 按检索顺序把实体放入上下文，直到用完 `budget` 个 token
+///
+/// 每个实体一节：悬停信息（签名、文档、位置），片段比签名多出内容时（类型定义、Python 类）附上片段。
+/// 放不下的实体跳过，后面更短的实体仍可能放入。
+pub fn context_pack(index: &CodeIndex, hits: &[&IndexedEntity], budget: usize) -> ContextPack {
+    let options = MetricsOptions::default();
+    let mut pack = ContextPack::default();
+    for entity in hits {
+        let Some(hover) = index.hover_info(&entity.id) else {
+            continue;
+        };
+        let mut section = format!("## {}\n\n{}", entity.name.as_deref().unwrap_or(&entity.kind), hover);
+        if entity.signature.as_deref() != Some(entity.text.as_str()) && entity.text.lines().count() > 1 {
+            section.push_str(&format!("\n```{}\n{}\n```\n", entity.language.to_lowercase(), entity.text));
+        }
+        section.push('\n');
+
+        let tokens = count_tokens(&section, &options);
+        if pack.tokens + tokens > budget {
+            pack.omitted.push(entity.id.clone());
+            continue;
+        }
+        pack.markdown.push_str(&section);
+        pack.tokens += tokens;
+        pack.included.push(entity.id.clone());
+    }
+    pack
+}
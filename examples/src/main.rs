@@ -0,0 +1,75 @@
+//! 端到端示例：索引项目、检索符号并打包上下文
+//!
+//! 用法：
+//!   cargo run -p synapse-examples -- <project_dir> <query> [--budget <tokens>] [--limit <n>]
+//!
+//! 例如 `cargo run -p synapse-examples -- examples/sample-repo invoice`。
+
+use std::process::ExitCode;
+
+use synapse_examples::{context_pack, index_project, search};
+
+const DEFAULT_BUDGET: usize = 800;
+const DEFAULT_LIMIT: usize = 10;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn usage() -> String {
+    "usage: synapse-pipeline <project_dir> <query> [--budget <tokens>] [--limit <n>]".to_string()
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let positional: Vec<&String> = args
+        .iter()
+        .enumerate()
+        .filter(|(i, arg)| !arg.starts_with("--") && (*i == 0 || !args[i - 1].starts_with("--")))
+        .map(|(_, arg)| arg)
+        .collect();
+    let [root, query] = positional[..] else {
+        return Err(usage());
+    };
+    let budget = flag(args, "--budget")?.unwrap_or(DEFAULT_BUDGET);
+    let limit = flag(args, "--limit")?.unwrap_or(DEFAULT_LIMIT);
+
+    let (index, stats) = index_project(root)?;
+    eprintln!(
+        "indexed {} files ({} entities) in {} ms",
+        stats.parsed,
+        index.entity_count(),
+        stats.elapsed_ms
+    );
+
+    let hits = search(&index, query, limit);
+    eprintln!("{} matches for `{}`", hits.len(), query);
+
+    let pack = context_pack(&index, &hits, budget);
+    println!("{}", pack.markdown);
+    eprintln!(
+        "packed {} entities ({} tokens), {} over budget",
+        pack.included.len(),
+        pack.tokens,
+        pack.omitted.len()
+    );
+    Ok(())
+}
+
+/// 数值参数 `--name <n>`
+fn flag(args: &[String], name: &str) -> Result<Option<usize>, String> {
+    let Some(i) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+    let value = args.get(i + 1).ok_or_else(usage)?;
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| format!("Invalid value for {}: {}", name, value))
+}
@@ -0,0 +1,54 @@
+//! 对示例仓库跑一遍完整流程：索引 → 检索 → 上下文打包
+
+use std::path::PathBuf;
+
+use synapse_examples::{context_pack, index_project, search};
+
+fn sample_repo() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("sample-repo")
+}
+
+#[test]
+fn test_index_search_pack() {
+    let (index, stats) = index_project(sample_repo()).expect("sample repo should scan");
+    assert_eq!(stats.parsed, 4);
+    assert!(stats.failed.is_empty());
+
+    // 完全匹配排在前面，其余按被调用次数
+    let hits = search(&index, "invoice", 10);
+    let names: Vec<&str> = hits.iter().filter_map(|entity| entity.name.as_deref()).collect();
+    assert_eq!(names.first(), Some(&"Invoice"));
+    assert!(names.contains(&"createInvoice"));
+
+    let hits = search(&index, "discount", 10);
+    assert_eq!(hits[0].name.as_deref(), Some("Discount"));
+    assert_eq!(hits[1].name.as_deref(), Some("applyDiscount"));
+    assert_eq!(hits[1].rank_hints.caller_count, 1);
+    // 唯一的调用在 Invoice.total 的方法体里，签名片段中看不到
+    let callers: Vec<&str> = index
+        .call_edges()
+        .into_iter()
+        .filter(|(_, to, _)| *to == hits[1].id)
+        .map(|(from, _, _)| from)
+        .collect();
+    assert_eq!(callers.len(), 1);
+    assert!(callers[0].ends_with("billing/invoice.ts"));
+
+    // Python 实体与 TypeScript 实体在同一个索引中
+    let hits = search(&index, "revenue", 10);
+    assert_eq!(hits[0].language, "Python");
+    assert_eq!(hits[0].doc.as_deref(), Some("Aggregates invoice totals per customer."));
+
+    let hits = search(&index, "invoice", 10);
+    let pack = context_pack(&index, &hits, 10_000);
+    assert_eq!(pack.included.len(), hits.len());
+    assert!(pack.omitted.is_empty());
+    assert!(pack.markdown.contains("## createInvoice"));
+    assert!(pack.markdown.contains("Builds an invoice from raw cart rows"));
+    assert!(pack.tokens > 0);
+
+    // 预算不足时跳过放不下的实体
+    let pack = context_pack(&index, &hits, 1);
+    assert!(pack.included.is_empty());
+    assert_eq!(pack.omitted.len(), hits.len());
+}
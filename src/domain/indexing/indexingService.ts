@@ -9,8 +9,14 @@ import { NervusDB } from '@nervusdb/core';
 import type { IndexMetadata, GitFingerprint } from '../types/indexMetadata.js';
 import { computeGitFingerprint, formatFingerprint, projectHash } from '../shared/gitFingerprint.js';
 import { createChildLogger } from '../../utils/logger.js';
-import { createMultiLanguageParser, MultiLanguageParser } from '../parsing/multiLanguageParser.js';
-import { makeNodeId } from '../types/codeGraph.js';
+import {
+  buildSymbolGraph,
+  createMultiLanguageParser,
+  MultiLanguageParser,
+  type GraphFilter,
+  type ParseResult,
+} from '../parsing/multiLanguageParser.js';
+import { makeNodeId, type GraphNodeType } from '../types/codeGraph.js';
 
 const CURRENT_DIR = path.dirname(fileURLToPath(import.meta.url));
 const ROOT_DIR = path.resolve(CURRENT_DIR, '../../..');
@@ -19,6 +25,8 @@ const logger = createChildLogger({ service: 'IndexingService' });
 export interface IndexingServiceOptions {
   dbRoot?: string;
   tempDir?: string;
+  /** 不写入 import 关系的文件（测试、生成代码），它们仍然被索引 */
+  graphFilter?: GraphFilter;
 }
 
 interface IndexResult {
@@ -41,14 +49,24 @@ const DEFAULT_DB_ROOT =
     return path.join(home, '.nervusdb');
   })();
 
+/**
+ * 图谱中使用的文件路径：相对项目根目录、分隔符统一为 `/`，
+ * 保证同一仓库在 Windows / macOS / Linux 上生成相同的节点 ID
+ */
+function toIndexPath(projectPath: string, filePath: string): string {
+  return path.relative(projectPath, filePath).split(path.sep).join('/');
+}
+
 export class IndexingService {
   private readonly dbRoot: string;
   private readonly tempRoot: string;
   private readonly deps: IndexingDependencies;
+  private readonly graphFilter?: GraphFilter;
 
   constructor(options: IndexingServiceOptions = {}, deps?: Partial<IndexingDependencies>) {
     this.dbRoot = options.dbRoot ?? DEFAULT_DB_ROOT;
     this.tempRoot = options.tempDir ?? path.join(this.dbRoot, 'tmp');
+    this.graphFilter = options.graphFilter;
 
     this.deps = {
       pack,
@@ -238,7 +256,7 @@ export class IndexingService {
     repomixResult: PackResult,
   ): Promise<void> {
     for (const file of repomixResult.processedFiles) {
-      const relativePath = path.relative(projectPath, file.path);
+      const relativePath = toIndexPath(projectPath, file.path);
       const fileNode = `file:${relativePath}`;
 
       db.addFact(
@@ -266,9 +284,11 @@ export class IndexingService {
     let parsedFiles = 0;
     let extractedEntities = 0;
     let skippedFiles = 0;
+    // 以项目相对路径保存解析结果，全部解析完后统一解析跨文件导入
+    const parsedResults: ParseResult[] = [];
 
     for (const file of repomixResult.processedFiles) {
-      const relativePath = path.relative(projectPath, file.path);
+      const relativePath = toIndexPath(projectPath, file.path);
       const fileNode = `file:${relativePath}`;
 
       // 添加项目包含文件关系
@@ -367,24 +387,7 @@ export class IndexingService {
           }
         }
 
-        // 提取 import 关系
-        if (parseResult.imports && Array.isArray(parseResult.imports)) {
-          for (const imp of parseResult.imports) {
-            if (typeof imp === 'string') {
-              // 简单的字符串形式：import xxx from 'module'
-              const importedModule = this.extractImportModule(imp);
-              if (importedModule) {
-                const importedFileNode = this.resolveImportPath(importedModule, relativePath);
-                if (importedFileNode) {
-                  db.addFact(
-                    { subject: fileNode, predicate: 'IMPORTS', object: importedFileNode },
-                    {},
-                  );
-                }
-              }
-            }
-          }
-        }
+        parsedResults.push({ ...parseResult, filePath: relativePath });
       } catch (error) {
         logger.warn(
           { filePath: file.path, error: error instanceof Error ? error.message : String(error) },
@@ -394,6 +397,8 @@ export class IndexingService {
       }
     }
 
+    this.addImportFacts(db, parsedResults);
+
     logger.info(
       {
         parsedFiles,
@@ -527,45 +532,69 @@ export class IndexingService {
   }
 
   /**
-   * 提取 import 的模块路径
+   * 根据符号图写入 import 关系：文件 → 文件、文件 → 导入符号的定义、文件 → 第三方包
    */
-  private extractImportModule(importStatement: string): string | null {
-    // import xxx from 'module'
-    let match = importStatement.match(/from\s+['"]([^'"]+)['"]/);
-    if (match) return match[1];
-
-    // import 'module'
-    match = importStatement.match(/import\s+['"]([^'"]+)['"]/);
-    if (match) return match[1];
+  private addImportFacts(db: NervusDB, results: ParseResult[]): void {
+    if (results.length === 0) return;
 
-    return null;
-  }
+    let graph;
+    try {
+      graph = buildSymbolGraph(results, this.graphFilter);
+    } catch (error) {
+      logger.warn(
+        { error: error instanceof Error ? error.message : String(error) },
+        'Failed to resolve imports, skipping import relations',
+      );
+      return;
+    }
 
-  /**
-   * 解析 import 路径为文件节点 ID
-   */
-  private resolveImportPath(importPath: string, currentFile: string): string | null {
-    // 只处理相对路径导入
-    if (importPath.startsWith('./') || importPath.startsWith('../')) {
-      const currentDir = path.dirname(currentFile);
-      const resolvedPath = path.normalize(path.join(currentDir, importPath));
-
-      // 尝试常见的文件扩展名
-      const extensions = ['.ts', '.tsx', '.js', '.jsx', '.py', '.go', '.rs', '.java'];
-      for (const ext of extensions) {
-        const withExt = resolvedPath.endsWith(ext) ? resolvedPath : `${resolvedPath}${ext}`;
-        return `file:${withExt}`;
-      }
+    for (const edge of graph.fileEdges) {
+      db.addFact(
+        { subject: `file:${edge.from}`, predicate: 'IMPORTS', object: `file:${edge.to}` },
+        {},
+      );
+    }
 
-      return `file:${resolvedPath}`;
+    for (const edge of graph.symbolEdges) {
+      const definitionNode = makeNodeId({
+        type: this.nodeTypeForKind(edge.kind),
+        name: edge.name,
+        filePath: edge.targetFile,
+      });
+      db.addFact(
+        { subject: `file:${edge.file}`, predicate: 'REFERENCES', object: definitionNode },
+        { objectProperties: { definitionId: edge.definitionId, kind: edge.kind } },
+      );
     }
 
-    // 第三方包导入，创建包节点
-    if (!importPath.startsWith('.') && !importPath.startsWith('/')) {
-      return `package:${importPath}`;
+    // 未能在项目内解析的非相对导入视为第三方包
+    for (const imp of graph.unresolved) {
+      if (!imp.source.startsWith('.') && !imp.source.startsWith('/')) {
+        db.addFact(
+          { subject: `file:${imp.file}`, predicate: 'IMPORTS', object: `package:${imp.source}` },
+          {},
+        );
+      }
     }
+  }
 
-    return null;
+  /**
+   * 解析器实体类型映射到图谱节点类型
+   */
+  private nodeTypeForKind(kind: string): GraphNodeType {
+    switch (kind) {
+      case 'function':
+      case 'method':
+      case 'interface':
+        return kind;
+      case 'class':
+      case 'struct':
+      case 'enum':
+      case 'trait':
+        return 'class';
+      default:
+        return 'variable';
+    }
   }
 
   private async getPackageVersion(): Promise<string> {
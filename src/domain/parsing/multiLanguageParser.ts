@@ -38,10 +38,31 @@ function loadNativeModule() {
   throw new Error(`Native module not found. Tried paths:\n${errors.join('\n')}`);
 }
 
-interface ImportExportItem {
-  name: string;
-  path?: string;
-  [key: string]: unknown;
+export interface ImportDeclaration {
+  /** 模块路径（TS 模块说明符、Python 点分模块、Rust 路径前缀等） */
+  source: string;
+  /** 具名导入的原始名字（`import { a as b }` 记为 `a`） */
+  specifiers: string[];
+  filePath: string;
+  /** `import type`，或全部具名导入都带 `type` 修饰 */
+  isTypeOnly: boolean;
+  /** 带 `type` 修饰的具名导入（`import { type A, b }` 为 `['A']`） */
+  typeSpecifiers?: string[];
+  defaultImport?: string;
+  /** `* as ns`、Python `import a as b`、Go 包别名 */
+  namespaceImport?: string;
+  /** `from x import *`、`java.io.*`、`use a::*`、Go 点导入 */
+  isWildcard?: boolean;
+}
+
+export interface ExportDeclaration {
+  /** 对外可见的名字（默认导出为 `default`） */
+  specifiers: string[];
+  filePath: string;
+  /** re-export 的来源模块 */
+  source?: string | null;
+  isTypeOnly?: boolean;
+  isWildcard?: boolean;
 }
 
 interface ParseError {
@@ -50,13 +71,749 @@ interface ParseError {
   [key: string]: unknown;
 }
 
+export interface EntityInfo {
+  kind: string;
+  name?: string;
+  range: { start: number; end: number };
+  isExported: boolean;
+  visibility?: 'public' | 'private' | 'protected' | 'internal';
+  /** 声明签名的单行显示形式（去掉注释与注解，空白与括号内侧空格已规范化） */
+  signature?: string;
+  /** 源码中签名的原始切片（与 signature 相同时省略） */
+  rawSignature?: string;
+  /** 紧贴定义的文档注释（Python 为 docstring），已去掉注释符号 */
+  doc?: string;
+  /** 实体钩子生成的摘要 */
+  summary?: string;
+  /** 所在目录的 README / OWNERS 摘要（项目扫描时附加） */
+  context?: string;
+  /** 规模指标（ParseOptions.metrics 开启时计算） */
+  metrics?: EntityMetrics;
+  /** 函数 / 方法的复杂度（ParseOptions.complexity 开启时计算） */
+  complexity?: ComplexityMetrics;
+  /** 代码片段超过 ParseOptions.entityCap 的上限，中间部分已截去 */
+  isTruncated?: boolean;
+  /** 外层类型 / 命名空间实体在 entityInfo 中的下标 */
+  parent?: number;
+  /** 带外层类型与命名空间的限定名（ParseOptions.qualifiedNames 开启时填写） */
+  qualifiedName?: string;
+  /** 模板约束（C++20 requires 子句、concept 的约束表达式、Go 的类型参数声明） */
+  constraints?: string[];
+  /** 注解与属性（Swift 的 `@State`、`@Published` 等属性包装器） */
+  annotations?: Annotation[];
+  /** React 组件的详情（只有 `component` 实体填写，ParseOptions.reactComponents 开启时提取） */
+  component?: ComponentInfo;
+}
+
+/** React 组件的详情 */
+export interface ComponentInfo {
+  kind: 'function' | 'class';
+  /** props 的类型名；未标注类型时省略 */
+  propsType?: string;
+  /** 使用的 hook，按首次出现的顺序 */
+  hooks?: string[];
+  /** 渲染的 JSX 元素名（含内置标签），按首次出现的顺序 */
+  children?: string[];
+}
+
+export interface Annotation {
+  name: string;
+  /** 括号内的参数原文 */
+  arguments?: string;
+}
+
+/** 实体的规模指标，按定义所占的完整源码行计算 */
+export interface EntityMetrics {
+  lines: number;
+  bytes: number;
+  /** 按 MetricsOptions.tokenizer 估算的 token 数 */
+  tokens: number;
+}
+
+/** 函数体的复杂度指标 */
+export interface ComplexityMetrics {
+  /** 圈复杂度：1 + 分支、循环、case、catch、三元与短路逻辑运算的个数 */
+  cyclomatic: number;
+  /** 控制结构的最大嵌套层数（else if 不加深） */
+  maxNesting: number;
+  statements: number;
+}
+
+/** 结构化实体（元数据 + 代码片段） */
+export interface StructuredEntity extends EntityInfo {
+  language: string;
+  text: string;
+}
+
+/** 实体树的节点：结构化实体及其直接成员（内部类、命名空间中的声明等） */
+export interface EntityNode extends StructuredEntity {
+  children?: EntityNode[];
+}
+
+/** 传给摘要钩子的实体上下文 */
+export interface EntityContext {
+  language: string;
+  filePath: string;
+  kind: string;
+  name?: string;
+  /** 紧贴定义的注释或 Python docstring */
+  doc?: string;
+  text: string;
+}
+
+/** 动态导入：运行时加载的模块 */
+export interface DynamicImport {
+  /** 字面量目标（不含插值的字符串），运行时计算的没有 */
+  source?: string;
+  /** 模块参数的原文 */
+  expression: string;
+  /** import(): import；CommonJS：require；Python importlib / __import__：importlib */
+  kind: 'import' | 'require' | 'importlib';
+  range: { start: number; end: number };
+}
+
+/** 调用关系：调用方实体 → 被调用名 */
+export interface CallReference {
+  /** 调用方函数 / 方法名，顶层代码中的调用没有 */
+  caller?: string;
+  /** 调用方定义的范围，与 entityInfo 中对应实体一致 */
+  callerRange?: { start: number; end: number };
+  /** 成员调用只保留最后一段（`a.b.c()` 记为 `c`） */
+  callee: string;
+  range: { start: number; end: number };
+}
+
+/** JSX 元素的使用：组件 → 其渲染的组件元素（`<UserCard />`） */
+export interface ElementUsage {
+  /** 使用方函数 / 组件名，顶层 JSX 没有 */
+  caller?: string;
+  callerRange?: { start: number; end: number };
+  /** 元素名（`UserCard`、`Icons.Star`） */
+  element: string;
+  range: { start: number; end: number };
+}
+
+/** 模块顶层的运行时修改（monkey-patching） */
+export interface Modification {
+  /** 被修改的目标（`fs.readFile`、`Array.prototype.last`） */
+  target: string;
+  /** 目标的根名字（导入的绑定或全局对象） */
+  object: string;
+  /** 根名字所属导入的模块路径，全局对象没有 */
+  source?: string;
+  /** 重新赋值导入的名字：reassign；成员赋值 / setattr：patch；原型补丁：prototype */
+  kind: 'reassign' | 'patch' | 'prototype';
+  range: { start: number; end: number };
+}
+
+/** Markdown 文档中的链接（行内链接、图片、自动链接与链接引用定义） */
+export interface DocumentLink {
+  /** 链接文字（图片为替代文本，引用定义为标签） */
+  text: string;
+  /** 链接目标（URL、相对路径或锚点） */
+  target: string;
+  isImage?: boolean;
+  range: { start: number; end: number };
+}
+
+/** 类型关系：类型 → 父类 / 所遵循的协议 / 嵌入的类型 */
+export interface TypeRelation {
+  /** 声明关系的类型名（扩展为被扩展的类型名） */
+  typeName: string;
+  /** 父类、协议或被嵌入的类型名（去掉泛型参数） */
+  target: string;
+  kind: 'extends' | 'conforms' | 'embeds';
+  range: { start: number; end: number };
+}
+
 export interface ParseResult {
   filePath: string;
   language: string;
   entities: string[];
-  imports: ImportExportItem[];
-  exports: ImportExportItem[];
+  /** 与 entities 按下标一一对应的元数据 */
+  entityInfo?: EntityInfo[];
+  imports: ImportDeclaration[];
+  exports: ExportDeclaration[];
+  /** 动态导入（import()、require()、Python importlib） */
+  dynamicImports?: DynamicImport[];
+  /** 调用关系（TS/JS、Python、Go、Rust、Java） */
+  calls?: CallReference[];
+  /** JSX 中使用的组件元素（TS/JS，HTML 内置标签不计） */
+  elementUsages?: ElementUsage[];
+  /** 模块顶层对导入名字的重新赋值与原型补丁（TS/JS、Python） */
+  modifications?: Modification[];
+  /** Markdown 文档中的链接 */
+  links?: DocumentLink[];
+  /** 类型关系：继承与协议遵循（Swift）、嵌入（Go） */
+  typeRelations?: TypeRelation[];
   errors: ParseError[];
+  /** TypeScript 声明文件（.d.ts），实体均为环境声明 */
+  isDeclarationFile?: boolean;
+  /** 大文件采样结果，中间部分只有顶层声明的签名 */
+  isPartial?: boolean;
+  /** 生成代码（路径约定或文件头的 `@generated`、`DO NOT EDIT` 等标记） */
+  isGenerated?: boolean;
+  /** 测试文件（`.test.ts`、`__tests__/`、`_test.go` 等路径约定） */
+  isTest?: boolean;
+  /** 压缩产物（`.min.js` / `.min.css`） */
+  isMinified?: boolean;
+  /** Storybook 故事文件（`.stories.tsx` / `.story.js`） */
+  isStory?: boolean;
+  /** 桶文件：只有导入与 re-export、没有自身定义（如 `export * from './x'` 的 index.ts） */
+  isBarrel?: boolean;
+  /** 文件开头的指令（`"use client"`、`// @flow`、`//go:build` 等） */
+  directives?: Directive[];
+  /** 文件角色（按路径约定与内容推断） */
+  role: FileRole;
+  /** 源码内容的哈希（blake3 十六进制，由 parseIfChanged 填写） */
+  contentHash?: string;
+  /** 文件级统计：各类实体的数量、总行数与解析耗时 */
+  summary: FileSummary;
+}
+
+/** 文件级统计 */
+export interface FileSummary {
+  /** 实体类型 -> 数量（与 entityInfo 的 kind 一致） */
+  kinds: Record<string, number>;
+  /** 实体总数 */
+  entities: number;
+  /** 源码总行数 */
+  lines: number;
+  /** 解析耗时（微秒，含后处理；WebAssembly 中为 0） */
+  elapsedUs: number;
+}
+
+/** 文件开头的指令 */
+export interface Directive {
+  /** 指令名（`use client`、`@jsx`、`go:build`、`no_std`、`__future__`） */
+  name: string;
+  /** 指令参数（`@jsx h` 的 `h`） */
+  value?: string;
+  /** 指令序言（`"use ..."`）：prologue；编译器 / 工具 pragma：pragma */
+  kind: 'prologue' | 'pragma';
+  line: number;
+}
+
+/** 文件在项目中的角色 */
+export type FileRole = 'source' | 'test' | 'config' | 'script' | 'docs';
+
+/** 按文件名得到的语言与分类标记 */
+export interface PathInfo {
+  /** 无法识别语言时为 null */
+  language: string | null;
+  /** 小写的基础扩展名（`ts`） */
+  extension: string;
+  /** 小写的复合扩展名（`d.ts`、`test.tsx`、`min.js`），没有限定段时同 extension */
+  compoundExtension: string;
+  isDeclaration: boolean;
+  isTest: boolean;
+  isMinified: boolean;
+  isStory: boolean;
+  /** 仅按路径约定判断 */
+  isGenerated: boolean;
+}
+
+/**
+ * 原生模块抛出的结构化错误（`code` 可用于选择性重试）
+ */
+export interface NativeParserError extends Error {
+  code:
+    | 'ERR_UNSUPPORTED_LANGUAGE'
+    | 'ERR_LANGUAGE_LOAD'
+    | 'ERR_PARSE'
+    | 'ERR_INVALID_INPUT'
+    | 'ERR_SERIALIZATION';
+  kind: 'unsupportedLanguage' | 'languageLoad' | 'parse' | 'invalidInput' | 'serialization';
+  retryable: boolean;
+  file?: string;
+  line?: number;
+  language?: string;
+}
+
+export function isNativeParserError(error: unknown): error is NativeParserError {
+  return (
+    error instanceof Error &&
+    typeof (error as Partial<NativeParserError>).kind === 'string' &&
+    typeof (error as Partial<NativeParserError>).retryable === 'boolean'
+  );
+}
+
+export interface StreamStats {
+  batches: number;
+  parsed: number;
+  /** 解析失败（已跳过）的文件路径 */
+  failed: string[];
+  cancelled: boolean;
+}
+
+/** 流式批量解析的累计进度 */
+export interface BatchProgress {
+  /** 已完成（含失败）的文件数 */
+  completed: number;
+  failed: number;
+  total: number;
+}
+
+/** 流式批量解析中单个文件的结果 */
+export interface FileOutcome {
+  filePath: string;
+  result?: ParseResult;
+  /** 解析失败的原因 */
+  error?: string;
+  progress: BatchProgress;
+}
+
+export interface StreamOptions {
+  /** 每批文件数（默认 64） */
+  batchSize?: number;
+  /** 允许同时未确认的批次数（默认 4） */
+  maxInFlight?: number;
+  /** 后台索引节流，避免抢占交互式请求 */
+  throttle?: ThrottleOptions;
+}
+
+export interface ParseOptions {
+  /** 允许 Flow 类型注解（文件头带 `@flow` 时自动开启） */
+  flow?: boolean;
+  /** 允许旧版装饰器语法 */
+  legacyDecorators?: boolean;
+  /**
+   * 输出路径处理方式（结果、导入导出、错误中的文件路径）
+   * - preserve: 原样输出（默认）
+   * - relative: 相对 projectRoot
+   * - anonymized: 相对路径的哈希 + 原扩展名
+   */
+  pathMode?: 'preserve' | 'relative' | 'anonymized';
+  projectRoot?: string;
+  /** 解析符号链接后再输出路径 */
+  resolveSymlinks?: boolean;
+  /** 大文件采样：只完整提取首尾部分，中间只保留顶层声明签名 */
+  sampling?: SamplingOptions;
+  /**
+   * `.h` 头文件的语言
+   * - auto: 按内容判断（Objective-C 指令 / class、template、namespace 等 C++ 语法，否则为 C；默认）
+   * - c / cpp: 固定按 C / C++ 解析
+   * - objc: Objective-C，不解析
+   */
+  headerLanguage?: 'auto' | 'c' | 'cpp' | 'objc';
+  /** 为每个实体计算行数、字节数与 token 数 */
+  metrics?: MetricsOptions;
+  /** 为函数 / 方法计算圈复杂度、最大嵌套深度与语句数 */
+  complexity?: boolean;
+  /** 为嵌套在类型 / 命名空间中的实体填写限定名（Outer.Inner.method、ns::Type） */
+  qualifiedNames?: boolean;
+  /** 额外按代码片段文本去重（默认只按位置去重，文本相同的重载、辅助函数都保留） */
+  dedupText?: boolean;
+  /** Markdown 围栏代码块按语言标记解析，块内实体并入结果（parent 指向所在的代码块） */
+  parseCodeBlocks?: boolean;
+  /** 识别 React 组件（TS / JS），每个组件额外输出一个 component 实体（props 类型、hook 与渲染的元素） */
+  reactComponents?: boolean;
+  /** 代码片段长度上限：超出时保留签名与首尾若干行，中间替换为截断标记 */
+  entityCap?: EntityCapOptions;
+  /** 提取行为：注释、函数体、长度与文件大小上限、去重方式 */
+  config?: ParserConfig;
+  /** 实体顺序：捕获顺序（默认）、按源码位置，或按类型分组（组内按位置）；entities 与 entityInfo 同步重排 */
+  entityOrder?: 'capture' | 'position' | 'kind';
+  /** 按路径解析（parsePath / parsePaths）时，不小于该大小（字节）的文件以内存映射读取；不提供时总是普通读取 */
+  mmapThreshold?: number;
+}
+
+/** 提取行为配置（未提供的字段沿用各语言的默认做法） */
+export interface ParserConfig {
+  /** 提取注释实体（默认 true） */
+  includeComments?: boolean;
+  /** 函数 / 方法片段：由各语言决定（默认）、只有签名或含函数体 */
+  bodies?: 'language' | 'signatures' | 'full';
+  /** 片段长度上限（字符数）；entityCap 已提供时以其为准 */
+  maxEntityLength?: number;
+  /** 超过该大小（字节）的文件不解析，抛出 ERR_INVALID_INPUT */
+  maxFileSize?: number;
+  /** 去重方式：按位置（默认）或额外按片段文本 */
+  dedup?: 'location' | 'text';
+  /** 注释的去重方式（默认同 dedup）；只在单个文件内去重，不同文件中相同的注释都保留 */
+  commentDedup?: 'location' | 'text';
+}
+
+export interface EntityCapOptions {
+  /** 默认上限（字符数，默认 8000） */
+  maxChars?: number;
+  /** 按实体类型覆盖上限（如 `{ method: 2000 }`），0 表示该类型不截断 */
+  perKind?: Record<string, number>;
+  /** 截断时保留的开头行数（默认 20，签名更长时保留完整签名） */
+  headLines?: number;
+  /** 截断时保留的结尾行数（默认 5） */
+  tailLines?: number;
+}
+
+export interface MetricsOptions {
+  /**
+   * token 估算方式
+   * - bpe: 近似 tiktoken（cl100k）的 BPE 计数（默认）
+   * - chars: 字符数除以 charsPerToken
+   * - words: 空白分隔的单词数
+   */
+  tokenizer?: 'bpe' | 'chars' | 'words';
+  /** chars 方式每个 token 对应的字符数（默认 4） */
+  charsPerToken?: number;
+}
+
+export interface SamplingOptions {
+  /** 超过该大小（字节）的文件启用采样（默认 1 MiB） */
+  threshold?: number;
+  /** 首尾各完整提取的字节数（默认 64 KiB） */
+  sampleBytes?: number;
+}
+
+export interface ThrottleOptions {
+  /** 最大工作线程数 */
+  maxThreads?: number;
+  /** 类似 nice 的让步等级（0-19，越大占用 CPU 越少） */
+  nice?: number;
+  /** 读取速率上限（字节/秒） */
+  ioBytesPerSec?: number;
+}
+
+export interface ScanOptions {
+  /** 最大目录深度（默认 64，0 表示不限制） */
+  maxDepth?: number;
+  /** 是否跟随符号链接（默认 true，环路与重复目录会被跳过） */
+  followSymlinks?: boolean;
+  /** 只解析匹配这些 glob 的文件（相对根目录） */
+  include?: string[];
+  /** 排除匹配这些 glob 的文件和目录 */
+  exclude?: string[];
+  /** 遵循 .gitignore（默认 true） */
+  respectGitignore?: boolean;
+  /** 超过该大小（字节）的文件不解析（默认 2 MiB，0 表示不限制） */
+  maxFileSize?: number;
+  /** 为图片、二进制、文档等未解析文件生成轻量节点（默认 true） */
+  includeAssets?: boolean;
+  /** 把目录 README / OWNERS 摘要附加到实体的 context（默认 true） */
+  directoryContext?: boolean;
+  throttle?: ThrottleOptions;
+  /** 编辑器中未保存的内容（路径 -> 内容），优先于磁盘；路径与 root 使用同一种形式 */
+  overlay?: Record<string, string>;
+  /** 持久化存储目录：再次扫描时内容未变的文件复用上次的解析结果 */
+  storePath?: string;
+}
+
+export interface WatchOptions {
+  /** 只解析匹配这些 glob 的文件（相对根目录） */
+  include?: string[];
+  /** 排除匹配这些 glob 的文件和目录 */
+  exclude?: string[];
+  /** 遵循 .gitignore（默认 true） */
+  respectGitignore?: boolean;
+  /** 超过该大小（字节）的文件不解析（默认 2 MiB，0 表示不限制） */
+  maxFileSize?: number;
+  /** 静默间隔（毫秒，默认 200）：最后一次变化后这么久没有新的变化才处理这一批 */
+  debounceMs?: number;
+}
+
+/** 一批文件变化的处理结果 */
+export interface WatchEvent {
+  /** 新增或内容有变化的文件的解析结果（contentHash 已填写） */
+  changed: ParseResult[];
+  /** 已删除或不再参与索引的文件（同 ParseResult.filePath） */
+  removed: string[];
+  /** 读取或解析失败的文件（`相对路径: 原因`） */
+  failed: string[];
+}
+
+/** 正在运行的项目监听，stop() 后不再回调 */
+export interface ProjectWatch {
+  stop(): void;
+  readonly isRunning: boolean;
+}
+
+export interface WalkStats {
+  files: number;
+  directories: number;
+  symlinkCycles: number;
+  duplicates: number;
+  depthLimited: number;
+  /** 被 .gitignore / exclude 排除的条目 */
+  filtered: number;
+  errors: string[];
+}
+
+export interface ScanStats {
+  walk: WalkStats;
+  notIncluded: number;
+  unsupported: number;
+  tooLarge: number;
+  parsed: number;
+  /** 其中从持久化存储复用（未重新解析）的文件数 */
+  cached: number;
+  /** 读取或解析失败的文件 */
+  failed: string[];
+  /** 语言 -> 文件数 */
+  languages: Record<string, number>;
+  entities: number;
+  bytes: number;
+  elapsedMs: number;
+}
+
+export type FileKind =
+  | 'source'
+  | 'text'
+  | 'notebook'
+  | 'image'
+  | 'media'
+  | 'font'
+  | 'archive'
+  | 'document'
+  | 'binary';
+
+/** 未解析的文件（完整文件清单的一部分） */
+export interface AssetFile {
+  path: string;
+  size: number;
+  /** source 表示可识别语言但未解析（如超过大小限制） */
+  kind: FileKind;
+}
+
+/** 带有 README / OWNERS 的目录 */
+export interface DirectoryContext {
+  /** 相对根目录的路径，根目录为空字符串 */
+  dir: string;
+  /** README 的标题与首段 */
+  readme?: string;
+  owners?: string[];
+}
+
+export interface ScanResult {
+  results: ParseResult[];
+  assets: AssetFile[];
+  contexts: DirectoryContext[];
+  stats: ScanStats;
+}
+
+/** 单个语言的索引开销估算 */
+export interface LanguageEstimate {
+  files: number;
+  bytes: number;
+  /** 单线程解析耗时（毫秒） */
+  estimatedMs: number;
+  /** 索引中的大小（字节） */
+  estimatedIndexBytes: number;
+}
+
+/** 索引开销估算（按各语言吞吐量的经验值推算，只反映量级） */
+export interface IndexEstimate {
+  /** 语言 -> 估算（只含会被解析的文件） */
+  languages: Record<string, LanguageEstimate>;
+  files: number;
+  bytes: number;
+  /** 超过 maxFileSize、不会解析的文件 */
+  tooLarge: number;
+  threads: number;
+  /** 预计总耗时（毫秒，已按线程数并行） */
+  estimatedMs: number;
+  /** 预计索引大小（字节） */
+  estimatedIndexBytes: number;
+  /** 本次估算的耗时（毫秒） */
+  elapsedMs: number;
+}
+
+/** 文件之间的导入边 */
+export interface FileEdge {
+  from: string;
+  to: string;
+  /** 导入语句中的模块路径 */
+  source: string;
+  /** 只有仅类型的导入，不构成运行时依赖 */
+  isTypeOnly?: boolean;
+}
+
+/** 导入的符号到定义的边 */
+export interface SymbolEdge {
+  file: string;
+  name: string;
+  /** 定义所在文件（已沿 re-export 追踪） */
+  targetFile: string;
+  definitionId: string;
+  kind: string;
+  range: { start: number; end: number };
+  /** 以 `import type` 或 `type` 修饰导入 */
+  isTypeOnly?: boolean;
+}
+
+/** JSX 组件使用到组件定义的边 */
+export interface ComponentEdge {
+  file: string;
+  /** 使用方组件，顶层 JSX 没有 */
+  caller?: string;
+  element: string;
+  targetFile: string;
+  definitionId: string;
+  range: { start: number; end: number };
+}
+
+/** 运行时修改到被修改模块的边 */
+export interface ModifiesEdge {
+  file: string;
+  target: string;
+  kind: 'reassign' | 'patch' | 'prototype';
+  targetFile: string;
+  /** 被修改的定义，找不到时没有 */
+  definitionId?: string;
+  range: { start: number; end: number };
+}
+
+export interface SymbolGraph {
+  fileEdges: FileEdge[];
+  symbolEdges: SymbolEdge[];
+  componentEdges: ComponentEdge[];
+  /** 动态导入的字面量目标构成的文件边 */
+  dynamicEdges: FileEdge[];
+  /** 对导入名字的重新赋值与原型补丁构成的边 */
+  modifiesEdges: ModifiesEdge[];
+  /** 本批文件中找不到的导入（第三方包、标准库等） */
+  unresolved: Array<{ file: string; source: string }>;
+}
+
+/** 调用 / 导入图中排除的文件（仍然参与检索） */
+export interface GraphFilter {
+  /** 测试文件与测试目录（`_test.go`、`*.spec.ts`、`tests/` 等） */
+  excludeTests?: boolean;
+  /** 生成代码（protobuf 输出、`*.g.dart`、`__generated__/` 等） */
+  excludeGenerated?: boolean;
+  /** 桶文件（只有 re-export 的 index.ts 等），经由它的导入直接连到定义所在的文件 */
+  excludeBarrels?: boolean;
+}
+
+/**
+ * 解析一批文件之间的导入（文件 → 文件、符号 → 定义）
+ */
+export function buildSymbolGraph(results: ParseResult[], filter?: GraphFilter): SymbolGraph {
+  const nativeModule = loadNativeModule();
+  const json = nativeModule.buildSymbolGraph(
+    results.map((r) => JSON.stringify(r)),
+    filter ? JSON.stringify(filter) : undefined,
+  ) as string;
+  return JSON.parse(json) as SymbolGraph;
+}
+
+/**
+ * 估算文本的 token 数（与 ParseOptions.metrics 的计算方式一致）
+ */
+export function countTokens(text: string, options?: MetricsOptions): number {
+  const nativeModule = loadNativeModule();
+  return nativeModule.countTokens(text, options) as number;
+}
+
+/** 外部包的使用情况 */
+export interface PackageUsage {
+  /** 包名（npm 包、Python 顶层包、crate、Go 模块、Java 包或 C/C++ 头文件） */
+  package: string;
+  languages: string[];
+  /** 导入语句数（含 re-export） */
+  importCount: number;
+  /** 导入该包的文件 */
+  files: string[];
+  /** 具名导入的名字 */
+  specifiers: string[];
+}
+
+/**
+ * 汇总一批文件对外部包的导入（按导入次数降序），回答“代码里实际用到了哪些第三方依赖”
+ */
+export function importSummary(results: ParseResult[], filter?: GraphFilter): PackageUsage[] {
+  const nativeModule = loadNativeModule();
+  const json = nativeModule.importSummary(
+    results.map((r) => JSON.stringify(r)),
+    filter ? JSON.stringify(filter) : undefined,
+  ) as string;
+  return JSON.parse(json) as PackageUsage[];
+}
+
+export interface CodeMapOptions {
+  /** 一级标题（默认 "Code Map"） */
+  title?: string;
+  /** 只列出导出的实体 */
+  exportedOnly?: boolean;
+  /** 在签名上方附加文档注释首行（默认 true） */
+  includeDocs?: boolean;
+}
+
+/**
+ * 把一批解析结果渲染为 Markdown 代码地图（文件树 + 签名），用于 LLM 上下文打包
+ */
+export function renderCodeMap(results: ParseResult[], options?: CodeMapOptions): string {
+  const nativeModule = loadNativeModule();
+  return nativeModule.renderCodeMap(
+    results.map((r) => JSON.stringify(r)),
+    options ? JSON.stringify(options) : undefined,
+  ) as string;
+}
+
+/**
+ * 在磁盘版本与编辑器中未保存的版本之间换算行号（从 1 开始），
+ * 使按磁盘内容索引的引用能定位到缓冲区中的正确位置；修改、新增或删除的行为 null
+ */
+export function mapOverlayLines(
+  disk: string,
+  overlay: string,
+  lines: number[],
+  toDisk = false,
+): Array<number | null> {
+  const nativeModule = loadNativeModule();
+  return nativeModule.mapOverlayLines(disk, overlay, lines, toDisk) as Array<number | null>;
+}
+
+/**
+ * 解析文件名的复合扩展名（不区分大小写）并给出测试 / 压缩 / Storybook 等分类标记，
+ * 与原生解析器的判断保持一致
+ */
+export function classifyPath(filePath: string): PathInfo {
+  const nativeModule = loadNativeModule();
+  return JSON.parse(nativeModule.classifyPath(filePath) as string) as PathInfo;
+}
+
+interface NativeProjectScanner {
+  setOptions(options: ParseOptions): void;
+  setThrottle(throttle: ThrottleOptions): void;
+  setOverlay(path: string, content: string): void;
+  setStore(path: string): void;
+  clearOverlay(path?: string): boolean;
+  scan(root: string): Promise<string>;
+  estimate(root: string): string;
+}
+
+/** 按实体内容哈希缓存的摘要 / 向量（原生 SummaryCache） */
+export interface SummaryCache {
+  getSummary(text: string): string | null;
+  getEmbedding(text: string): number[] | null;
+  putSummary(text: string, summary: string): void;
+  putEmbedding(text: string, embedding: number[]): void;
+  save(): void;
+  readonly size: number;
+}
+
+/**
+ * 打开摘要缓存（不传路径为内存缓存）
+ */
+export function openSummaryCache(cachePath?: string): SummaryCache {
+  const nativeModule = loadNativeModule();
+  return new nativeModule.SummaryCache(cachePath) as SummaryCache;
+}
+
+interface NativeProjectWatcher extends ProjectWatch {
+  setOptions(options: ParseOptions): void;
+  start(root: string, onChange: (err: Error | null, event: string) => void): void;
+}
+
+interface NativeStreamingIndexer {
+  start(
+    files: Array<[string, string]>,
+    onBatch: (err: Error | null, results: string[]) => void,
+    onDone: (err: Error | null, stats: string) => void,
+  ): void;
+  ack(batches?: number): void;
+  cancel(): void;
+  setOptions(options: ParseOptions): void;
+  setThrottle(throttle: ThrottleOptions): void;
 }
 
 export interface ParseStats {
@@ -83,12 +840,38 @@ export type SupportedLanguage =
  */
 interface NativeLanguageManager {
   parseFile(filePath: string, content: string): string;
+  parseFileBytes(filePath: string, content: Buffer): string;
+  parseIfChanged(filePath: string, content: string, previousHash?: string | null): string | null;
+  parsePath(filePath: string): string;
+  parsePaths(paths: string[], throttle?: ThrottleOptions): string[];
   parseFilesBatch(files: Array<[string, string]>): string[];
+  parseFilesBatchByLanguage(files: Array<[string, string]>): string;
+  parseFileAsync(filePath: string, content: string): Promise<string>;
+  parseFilesBatchAsync(files: Array<[string, string]>): Promise<string[]>;
+  parseFilesBatchParallel(files: Array<[string, string]>, throttle?: ThrottleOptions): string[];
+  parseFilesStreaming(
+    files: Array<[string, string]>,
+    onResult: (err: Error | null, outcome: string) => void,
+    onDone: (err: Error | null, progress: string) => void,
+    throttle?: ThrottleOptions,
+  ): void;
+  parseFileStructured(filePath: string, content: string): string;
+  parseFileTree(filePath: string, content: string): string;
+  parseFileWithHook(
+    filePath: string,
+    content: string,
+    hook: (entity: EntityContext) => string | null | undefined,
+  ): string;
   guessLanguage(filePath: string): string | null;
+  setOptions(options: ParseOptions): void;
+  setQuery(language: string, query: string): void;
+  resetQuery(language: string): void;
+  loadLanguagePack(path: string): string;
 }
 
 export class MultiLanguageParser {
   private manager: NativeLanguageManager; // NAPI LanguageManager
+  private options: ParseOptions = {};
 
   constructor() {
     try {
@@ -105,6 +888,48 @@ export class MultiLanguageParser {
     }
   }
 
+  /**
+   * 设置解析选项（同时作用于 streamFiles）
+   */
+  setOptions(options: ParseOptions): void {
+    this.manager.setOptions(options);
+    this.options = options;
+  }
+
+  /**
+   * 注册自定义 tree-sitter query，覆盖该语言的内置 query（编译失败时抛出，原 query 不变）
+   *
+   * 内置策略不认识的 `@definition.xxx` 捕获按定义原文提取，实体类型为 `xxx`。
+   * 需要保留内置捕获时可在 getDefaultQuery 的结果后追加。
+   */
+  setQuery(language: SupportedLanguage, query: string): void {
+    this.manager.setQuery(language, query);
+  }
+
+  /**
+   * 恢复语言的内置 query
+   */
+  resetQuery(language: SupportedLanguage): void {
+    this.manager.resetQuery(language);
+  }
+
+  /**
+   * 从动态库加载第三方语言包（Gleam、Haskell 等），返回语言名
+   *
+   * 语言包处理的扩展名优先于内置语言。ABI 见 LANGUAGE_SUPPORT.md 的 "Language Packs"。
+   */
+  loadLanguagePack(path: string): string {
+    return this.manager.loadLanguagePack(path);
+  }
+
+  /**
+   * 语言的内置 query
+   */
+  getDefaultQuery(language: SupportedLanguage): string {
+    const nativeModule = loadNativeModule();
+    return nativeModule.LanguageManager.getDefaultQuery(language) as string;
+  }
+
   /**
    * 根据文件路径自动检测语言并解析
    *
@@ -114,13 +939,168 @@ export class MultiLanguageParser {
    */
   async parseFile(filePath: string, content: string): Promise<ParseResult> {
     try {
-      const jsonResult = this.manager.parseFile(filePath, content);
+      // 在原生线程池中解析，不阻塞事件循环
+      const jsonResult = await this.manager.parseFileAsync(filePath, content);
       return JSON.parse(jsonResult) as ParseResult;
     } catch (error) {
+      // 结构化错误原样抛出，保留 code / file / line 等属性
+      if (isNativeParserError(error)) throw error;
+      throw new Error(`Failed to parse ${filePath}: ${error}`);
+    }
+  }
+
+  /**
+   * 解析原始文件内容（如 fs.readFile 不带编码的结果）
+   *
+   * 非 UTF-8 文件（Windows-1252、UTF-16、混合编码）先解码再解析，
+   * 编码转换与非法字节替换记入 errors，而不是使整个文件解析失败。
+   *
+   * @param filePath - 文件路径
+   * @param content - 文件的原始字节
+   * @returns 解析结果
+   */
+  parseFileBytes(filePath: string, content: Buffer): ParseResult {
+    try {
+      return JSON.parse(this.manager.parseFileBytes(filePath, content)) as ParseResult;
+    } catch (error) {
+      if (isNativeParserError(error)) throw error;
+      throw new Error(`Failed to parse ${filePath}: ${error}`);
+    }
+  }
+
+  /**
+   * 内容变化时才解析
+   *
+   * 内容哈希与 previousHash 相同时返回 null（文件未变化，无需重新索引）；
+   * 否则返回解析结果，其 contentHash 供下次调用传入。
+   *
+   * @param filePath - 文件路径
+   * @param content - 文件内容
+   * @param previousHash - 上次解析结果的 contentHash
+   * @returns 解析结果，未变化时为 null
+   */
+  parseIfChanged(filePath: string, content: string, previousHash?: string): ParseResult | null {
+    try {
+      const jsonResult = this.manager.parseIfChanged(filePath, content, previousHash ?? null);
+      return jsonResult === null ? null : (JSON.parse(jsonResult) as ParseResult);
+    } catch (error) {
+      if (isNativeParserError(error)) throw error;
+      throw new Error(`Failed to parse ${filePath}: ${error}`);
+    }
+  }
+
+  /**
+   * 按路径解析：文件在 Rust 侧读取（大文件可按 mmapThreshold 内存映射），内容不经过 JS
+   *
+   * @param filePath - 文件路径
+   * @returns 解析结果
+   */
+  parsePath(filePath: string): ParseResult {
+    try {
+      return JSON.parse(this.manager.parsePath(filePath)) as ParseResult;
+    } catch (error) {
+      if (isNativeParserError(error)) throw error;
+      throw new Error(`Failed to parse ${filePath}: ${error}`);
+    }
+  }
+
+  /**
+   * 按路径批量解析（结果顺序与输入一致，读取或解析失败的文件被跳过）
+   *
+   * @param paths - 文件路径列表
+   * @param throttle - 可选的线程数 / CPU / IO 限制
+   * @returns 解析结果数组
+   */
+  parsePaths(paths: string[], throttle?: ThrottleOptions): ParseResult[] {
+    try {
+      return this.manager.parsePaths(paths, throttle).map((json: string) => JSON.parse(json) as ParseResult);
+    } catch (error) {
+      if (isNativeParserError(error)) throw error;
+      throw new Error(`Batch parsing failed: ${error}`);
+    }
+  }
+
+  /**
+   * 解析文件并返回结构化实体
+   *
+   * @param filePath - 文件路径
+   * @param content - 文件内容
+   * @returns 结构化实体数组
+   */
+  async parseFileStructured(filePath: string, content: string): Promise<StructuredEntity[]> {
+    try {
+      const jsonResult = this.manager.parseFileStructured(filePath, content);
+      return JSON.parse(jsonResult) as StructuredEntity[];
+    } catch (error) {
+      if (isNativeParserError(error)) throw error;
+      throw new Error(`Failed to parse ${filePath}: ${error}`);
+    }
+  }
+
+  /**
+   * 解析文件并按嵌套关系返回实体树（顶层实体为根，成员在 children 中）
+   *
+   * @param filePath - 文件路径
+   * @param content - 文件内容
+   * @returns 实体树
+   */
+  async parseFileTree(filePath: string, content: string): Promise<EntityNode[]> {
+    try {
+      const jsonResult = this.manager.parseFileTree(filePath, content);
+      return JSON.parse(jsonResult) as EntityNode[];
+    } catch (error) {
+      if (isNativeParserError(error)) throw error;
       throw new Error(`Failed to parse ${filePath}: ${error}`);
     }
   }
 
+  /**
+   * 解析文件并为每个实体生成摘要（如调用 LLM），结果写入 entityInfo[i].summary
+   *
+   * @param filePath - 文件路径
+   * @param content - 文件内容
+   * @param summarize - 摘要函数，返回 undefined 表示跳过该实体
+   * @param cache - 摘要缓存，内容未变的实体直接复用，不再调用 summarize
+   * @returns 带摘要的解析结果
+   */
+  async parseFileWithSummaries(
+    filePath: string,
+    content: string,
+    summarize: (entity: EntityContext) => Promise<string | undefined>,
+    cache?: SummaryCache,
+  ): Promise<ParseResult> {
+    // 原生钩子是同步的：先收集上下文，再并发等待摘要
+    const contexts: EntityContext[] = [];
+    let result: ParseResult;
+    try {
+      const jsonResult = this.manager.parseFileWithHook(filePath, content, (entity) => {
+        contexts.push({ ...entity });
+        return null;
+      });
+      result = JSON.parse(jsonResult) as ParseResult;
+    } catch (error) {
+      if (isNativeParserError(error)) throw error;
+      throw new Error(`Failed to parse ${filePath}: ${error}`);
+    }
+
+    const summaries = await Promise.all(
+      contexts.map(async (entity) => {
+        const cached = cache?.getSummary(entity.text);
+        if (cached != null) return cached;
+        const summary = await summarize(entity);
+        if (summary !== undefined) cache?.putSummary(entity.text, summary);
+        return summary;
+      }),
+    );
+    summaries.forEach((summary, i) => {
+      const info = result.entityInfo?.[i];
+      if (info && summary !== undefined) {
+        info.summary = summary;
+      }
+    });
+    return result;
+  }
+
   /**
    * 批量解析文件（性能优化版本）
    *
@@ -133,13 +1113,226 @@ export class MultiLanguageParser {
     try {
       // 转换为 NAPI 期望的格式
       const filesArray = files.map(([path, content]) => [path, content] as [string, string]);
-      const jsonResults = this.manager.parseFilesBatch(filesArray);
+      const jsonResults = await this.manager.parseFilesBatchAsync(filesArray);
       return jsonResults.map((json: string) => JSON.parse(json) as ParseResult);
     } catch (error) {
+      if (isNativeParserError(error)) throw error;
       throw new Error(`Batch parsing failed: ${error}`);
     }
   }
 
+  /**
+   * 批量解析并按语言分组
+   *
+   * 分组按语言名排序，组内保持输入顺序，多次运行结果一致。
+   *
+   * @param files - 文件列表 [filePath, content][]
+   * @returns 语言名 -> 解析结果
+   */
+  parseFilesByLanguage(files: Array<[string, string]>): Record<string, ParseResult[]> {
+    try {
+      return JSON.parse(this.manager.parseFilesBatchByLanguage(files)) as Record<string, ParseResult[]>;
+    } catch (error) {
+      if (isNativeParserError(error)) throw error;
+      throw new Error(`Batch parsing failed: ${error}`);
+    }
+  }
+
+  /**
+   * 多线程批量解析（结果顺序与输入一致）
+   *
+   * @param files - 文件列表 [filePath, content][]
+   * @param throttle - 可选的线程数 / CPU / IO 限制
+   * @returns 解析结果数组
+   */
+  async parseFilesInParallel(
+    files: Array<[string, string]>,
+    throttle?: ThrottleOptions,
+  ): Promise<ParseResult[]> {
+    try {
+      const jsonResults = this.manager.parseFilesBatchParallel(files, throttle);
+      return jsonResults.map((json: string) => JSON.parse(json) as ParseResult);
+    } catch (error) {
+      if (isNativeParserError(error)) throw error;
+      throw new Error(`Parallel batch parsing failed: ${error}`);
+    }
+  }
+
+  /**
+   * 多线程批量解析，每个文件完成即回调（完成顺序，不累积全部结果）
+   *
+   * 适合超大仓库：回调队列有界，JS 侧处理不过来时 Rust 侧暂停解析。
+   *
+   * @param files - 文件列表 [filePath, content][]
+   * @param onResult - 每个文件的结果与累计进度
+   * @param throttle - 可选的线程数 / CPU / IO 限制
+   * @returns 最终进度
+   */
+  parseFilesStreaming(
+    files: Array<[string, string]>,
+    onResult: (outcome: FileOutcome) => void,
+    throttle?: ThrottleOptions,
+  ): Promise<BatchProgress> {
+    return new Promise((resolve, reject) => {
+      let failure: unknown = null;
+      this.manager.parseFilesStreaming(
+        files,
+        (err, json) => {
+          if (failure) return;
+          try {
+            if (err) throw err;
+            onResult(JSON.parse(json) as FileOutcome);
+          } catch (error) {
+            failure = error;
+          }
+        },
+        (err, progress) => {
+          if (failure || err) {
+            reject(failure ?? err);
+          } else {
+            resolve(JSON.parse(progress) as BatchProgress);
+          }
+        },
+        throttle,
+      );
+    });
+  }
+
+  /**
+   * 流式解析（带背压）
+   *
+   * 每个批次交给 onBatch，待其 Promise 完成后才归还信用，
+   * 慢速写入端会让 Rust 侧暂停解析而不是无限缓存结果。
+   *
+   * @param files - 文件列表 [filePath, content][]
+   * @param onBatch - 批次处理函数（如写入数据库）
+   * @param options - 批大小与在途批次数
+   * @returns 流式统计
+   */
+  streamFiles(
+    files: Array<[string, string]>,
+    onBatch: (results: ParseResult[]) => Promise<void>,
+    options: StreamOptions = {},
+  ): Promise<StreamStats> {
+    const nativeModule = loadNativeModule();
+    const indexer = new nativeModule.StreamingIndexer(
+      options.batchSize,
+      options.maxInFlight,
+    ) as NativeStreamingIndexer;
+    indexer.setOptions(this.options);
+    if (options.throttle) {
+      indexer.setThrottle(options.throttle);
+    }
+
+    return new Promise((resolve, reject) => {
+      let failure: unknown = null;
+      let pending = Promise.resolve();
+
+      indexer.start(
+        files,
+        (err, jsonResults) => {
+          if (err) {
+            failure ??= err;
+            indexer.cancel();
+            return;
+          }
+          // 按顺序处理批次，处理完成后才归还信用
+          pending = pending.then(async () => {
+            if (failure) return;
+            try {
+              await onBatch(jsonResults.map((json) => JSON.parse(json) as ParseResult));
+              indexer.ack(1);
+            } catch (error) {
+              failure = error;
+              indexer.cancel();
+            }
+          });
+        },
+        (err, stats) => {
+          void pending.then(() => {
+            if (failure || err) {
+              reject(failure ?? err);
+            } else {
+              resolve(JSON.parse(stats) as StreamStats);
+            }
+          });
+        },
+      );
+    });
+  }
+
+  /**
+   * 扫描并解析整个项目目录（遍历、过滤与读取都在 Rust 侧完成）
+   *
+   * @param root - 项目根目录
+   * @param options - glob / .gitignore / 深度 / 大小限制
+   * @returns 解析结果与汇总统计
+   */
+  async scanProject(root: string, options: ScanOptions = {}): Promise<ScanResult> {
+    const nativeModule = loadNativeModule();
+    const { throttle, overlay, storePath, ...scanOptions } = options;
+    const scanner = new nativeModule.ProjectScanner(scanOptions) as NativeProjectScanner;
+    scanner.setOptions(this.options);
+    if (throttle) {
+      scanner.setThrottle(throttle);
+    }
+    if (storePath) {
+      scanner.setStore(storePath);
+    }
+    for (const [path, content] of Object.entries(overlay ?? {})) {
+      scanner.setOverlay(path, content);
+    }
+    return JSON.parse(await scanner.scan(root)) as ScanResult;
+  }
+
+  /**
+   * 估算扫描项目的耗时与索引大小：过滤规则与 scanProject 一致，只遍历目录、不读取文件，
+   * 可在开始长时间索引之前提示用户
+   *
+   * @param root - 项目根目录
+   * @param options - glob / .gitignore / 深度 / 大小限制与线程数
+   * @returns 各语言的文件数与大小、预计耗时与索引大小
+   */
+  estimateProject(root: string, options: ScanOptions = {}): IndexEstimate {
+    const nativeModule = loadNativeModule();
+    const { throttle, overlay: _overlay, storePath: _storePath, ...scanOptions } = options;
+    const scanner = new nativeModule.ProjectScanner(scanOptions) as NativeProjectScanner;
+    if (throttle) {
+      scanner.setThrottle(throttle);
+    }
+    return JSON.parse(scanner.estimate(root)) as IndexEstimate;
+  }
+
+  /**
+   * 持续监听项目目录：变化的文件去抖后在 Rust 侧读取并重新解析，每批变化回调一次
+   *
+   * 只报告启动之后的变化，完整索引先用 scanProject 建立；内容未变的文件不重复解析。
+   *
+   * @param root - 项目根目录
+   * @param onChange - 每批变化的处理函数
+   * @param options - glob / .gitignore / 大小限制 / 去抖间隔
+   * @param onError - 回调数据无法解码时的处理函数
+   * @returns 监听句柄
+   */
+  watchProject(
+    root: string,
+    onChange: (event: WatchEvent) => void,
+    options: WatchOptions = {},
+    onError?: (error: Error) => void,
+  ): ProjectWatch {
+    const nativeModule = loadNativeModule();
+    const watcher = new nativeModule.ProjectWatcher(options) as NativeProjectWatcher;
+    watcher.setOptions(this.options);
+    watcher.start(root, (err, json) => {
+      if (err) {
+        onError?.(err);
+        return;
+      }
+      onChange(JSON.parse(json) as WatchEvent);
+    });
+    return watcher;
+  }
+
   /**
    * 检测文件语言
    *
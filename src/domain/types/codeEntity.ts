@@ -76,7 +76,9 @@ export interface PropertyEntity {
   range: Range;
   propType?: string;
   isStatic: boolean;
-  visibility: 'public' | 'private' | 'protected';
+  visibility: 'public' | 'private' | 'protected' | 'internal';
+  /** 只读（TS readonly、Java final、Python Final[...]） */
+  isReadonly?: boolean;
 }
 
 /**
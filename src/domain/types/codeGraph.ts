@@ -30,6 +30,7 @@ export type GraphPredicate =
   | 'EXPORTS' // file → symbol
   | 'IMPORTS' // file → file (module imports)
   | 'IMPORTS_FROM' // file → package
+  | 'REFERENCES' // file → 导入符号的定义（跨文件解析）
 
   // 类型关系
   | 'IMPLEMENTS' // class → interface
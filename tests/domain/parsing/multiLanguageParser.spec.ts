@@ -307,6 +307,31 @@ describe('MultiLanguageParser E2E', () => {
       });
     });
 
+    describe('Custom queries', () => {
+      const code = "@Controller('users')\nclass UsersController {\n  findAll() {}\n}\n\nclass Plain {}\n";
+      const controllers = (result: ParseResult) =>
+        (result.entityInfo ?? []).filter((info) => info.kind === 'controller').map((info) => info.name);
+
+      it('should apply setQuery and resetQuery to async parsing', async () => {
+        const parser = new MultiLanguageParser();
+        parser.setQuery(
+          'TypeScript',
+          `${parser.getDefaultQuery('TypeScript')}
+(class_declaration
+  (decorator (call_expression function: (identifier) @_decorator (#eq? @_decorator "Controller")))
+  name: (type_identifier) @definition.controller)
+`,
+        );
+
+        expect(controllers(await parser.parseFile('users.controller.ts', code))).toEqual(['UsersController']);
+        const [batched] = await parser.parseFilesInBatch([['users.controller.ts', code]]);
+        expect(controllers(batched)).toEqual(['UsersController']);
+
+        parser.resetQuery('TypeScript');
+        expect(controllers(await parser.parseFile('users.controller.ts', code))).toEqual([]);
+      });
+    });
+
     describe('Performance benchmark', () => {
       it('should benchmark TypeScript parsing', async () => {
         const code = readFileSync(join(FIXTURES_DIR, 'sample.ts'), 'utf-8');
@@ -0,0 +1,33 @@
+/**
+ * 端到端示例（examples/node/pipeline.mjs）的集成测试
+ *
+ * 需要先执行 `pnpm build:rust`；native module 不存在时跳过。
+ */
+
+import { existsSync } from 'node:fs';
+import path from 'node:path';
+import { fileURLToPath } from 'node:url';
+import { describe, it, expect } from 'vitest';
+
+const currentDir = path.dirname(fileURLToPath(import.meta.url));
+const nativePath = path.resolve(currentDir, '../../target/release/synapse_parser_napi.node');
+const sampleRepo = path.resolve(currentDir, '../../examples/sample-repo');
+
+describe.skipIf(!existsSync(nativePath))('examples/node/pipeline', () => {
+  it('indexes, searches and packs context for the sample repo', async () => {
+    const { indexProject, search, contextPack } = await import('../../examples/node/pipeline.mjs');
+
+    const { index, stats } = await indexProject(sampleRepo);
+    expect(stats.parsed).toBe(4);
+
+    const hits = search(index, 'discount');
+    expect(hits[0].name).toBe('Discount');
+    expect(hits[1].name).toBe('applyDiscount');
+
+    const pack = contextPack(index, search(index, 'invoice'), 10_000);
+    expect(pack.omitted).toEqual([]);
+    expect(pack.markdown).toContain('## createInvoice');
+
+    expect(contextPack(index, hits, 1).included).toEqual([]);
+  });
+});
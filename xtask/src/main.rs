@@ -0,0 +1,202 @@
+//! 工作区开发任务
+//!
+//! 用法：
+//!   cargo xtask gen-fixtures <lang> [--check]
+//!   cargo xtask quality <corpus_dir> [--json]
+//!
+//! 为指定语言生成规范测试样例（`crates/parser/tests/fixtures/<lang>/`）：
+//! 写入模板源码和期望的实体快照，并报告查询中每个捕获的命中次数。
+//! 有捕获未命中时失败，提示补充模板。`--check` 只比较不写入。
+//! 语言还没有模板（`xtask/templates/<lang>.<ext>`）时先生成骨架模板（注释中列出需要覆盖的捕获）
+//! 和对应的样例，再由贡献者补全模板后重新生成。
+//!
+//! `quality` 对带标注的语料库输出各语言、各实体类型的精确率与召回率。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use synapse_parser::{fixture_extension, fixture_snapshot, quality_report, LanguageManager, SupportedLanguage};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("gen-fixtures") => gen_fixtures(&args[1..]),
+        Some("quality") => quality(&args[1..]),
+        _ => Err(usage()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn usage() -> String {
+    "usage:\n  cargo xtask gen-fixtures <lang> [--check]\n  cargo xtask quality <corpus_dir> [--json]".to_string()
+}
+
+fn gen_fixtures(args: &[String]) -> Result<(), String> {
+    let check = args.iter().any(|a| a == "--check");
+    let lang_arg = args
+        .iter()
+        .find(|a| !a.starts_with("--"))
+        .ok_or_else(usage)?;
+
+    let language: SupportedLanguage = lang_arg.parse()?;
+    let slug = fixture_slug(language);
+    let mut manager = LanguageManager::new();
+
+    let (ext, template, scaffolded) = match find_template(&slug)? {
+        Some((ext, template)) => (ext, template, false),
+        None if check => {
+            return Err(format!("no canonical template for {}; rerun without --check to scaffold one", language));
+        }
+        None => {
+            let ext = fixture_extension(language)
+                .ok_or_else(|| format!("{} has no file extension in this build", language))?;
+            let captures = manager.capture_counts("", language)?.into_keys().collect::<Vec<_>>();
+            let template = scaffold_template(language, &captures);
+            write_file(&templates_dir().join(format!("{}.{}", slug, ext)), &template)?;
+            (ext.to_string(), template, true)
+        }
+    };
+
+    // 报告每个捕获的命中次数，未命中说明模板没有覆盖该查询
+    let counts = manager.capture_counts(&template, language)?;
+    let mut unmatched = Vec::new();
+    println!("{} captures:", language);
+    for (capture, count) in &counts {
+        println!("  {:<32} {}", capture, count);
+        if *count == 0 {
+            unmatched.push(capture.as_str());
+        }
+    }
+    if !unmatched.is_empty() && !scaffolded {
+        return Err(format!(
+            "template for {} does not exercise captures: {}",
+            language,
+            unmatched.join(", ")
+        ));
+    }
+
+    let file_name = format!("canonical.{}", ext);
+    let result = manager.parse_with_language(&file_name, &template, language)?;
+    let expected = format!("{}\n", serde_json::to_string_pretty(&fixture_snapshot(&result)).map_err(|e| e.to_string())?);
+
+    let dir = workspace_root().join("crates/parser/tests/fixtures").join(&slug);
+    let source_path = dir.join(&file_name);
+    let expected_path = dir.join("canonical.expected.json");
+
+    if check {
+        for (path, content) in [(&source_path, template.as_str()), (&expected_path, expected.as_str())] {
+            let current = fs::read_to_string(path).unwrap_or_default();
+            if current != content {
+                return Err(format!("{} is out of date; rerun without --check", path.display()));
+            }
+        }
+        println!("fixtures for {} are up to date", language);
+        return Ok(());
+    }
+
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    write_file(&source_path, &template)?;
+    write_file(&expected_path, &expected)?;
+    if scaffolded {
+        println!(
+            "scaffolded a template for {}; fill in {} to cover the captures above and rerun",
+            language,
+            templates_dir().join(format!("{}.{}", slug, ext)).display()
+        );
+    }
+    Ok(())
+}
+
+/// 语言的模板（`xtask/templates/<slug>.<ext>`）：(扩展名, 源码)
+fn find_template(slug: &str) -> Result<Option<(String, String)>, String> {
+    let Ok(entries) = fs::read_dir(templates_dir()) else {
+        return Ok(None);
+    };
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.file_stem().is_some_and(|stem| stem == slug) {
+            let ext = path.extension().map(|ext| ext.to_string_lossy().to_string()).unwrap_or_default();
+            let template = fs::read_to_string(&path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+            return Ok(Some((ext, template)));
+        }
+    }
+    Ok(None)
+}
+
+/// 骨架模板：注释中列出 query 的捕获（不支持注释的格式只生成空文件）
+fn scaffold_template(language: SupportedLanguage, captures: &[String]) -> String {
+    let (open, close) = match language.to_string().as_str() {
+        "Python" | "Ruby" | "Elixir" | "Nim" | "YAML" | "TOML" => ("#", ""),
+        "SQL" => ("--", ""),
+        "Erlang" => ("%", ""),
+        "CSS" => ("/*", " */"),
+        "Vue" | "Markdown" => ("<!--", " -->"),
+        "JSON" => return String::new(),
+        _ => ("//", ""),
+    };
+    let mut lines = vec![format!("{} Canonical {} fixture: add code that exercises each capture below.{}", open, language, close)];
+    lines.extend(captures.iter().map(|capture| format!("{}   @{}{}", open, capture, close)));
+    lines.join("\n") + "\n"
+}
+
+fn quality(args: &[String]) -> Result<(), String> {
+    let as_json = args.iter().any(|a| a == "--json");
+    let corpus_dir = args
+        .iter()
+        .find(|a| !a.starts_with("--"))
+        .ok_or_else(usage)?;
+
+    let report = quality_report(corpus_dir)?;
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?);
+        return Ok(());
+    }
+
+    println!("{:<12} {:<16} {:>8} {:>9} {:>7} {:>9} {:>7}", "language", "kind", "expected", "extracted", "matched", "precision", "recall");
+    for (language, quality) in &report.languages {
+        for (kind, score) in quality.kinds.iter().chain([(&"(total)".to_string(), &quality.total)]) {
+            println!(
+                "{:<12} {:<16} {:>8} {:>9} {:>7} {:>9.3} {:>7.3}",
+                language, kind, score.expected, score.extracted, score.matched, score.precision, score.recall
+            );
+        }
+    }
+    for error in &report.errors {
+        eprintln!("error: {}", error);
+    }
+    Ok(())
+}
+
+/// 样例目录名（语言显示名的小写形式，C++ / C# 用 cpp / csharp）
+fn fixture_slug(language: SupportedLanguage) -> String {
+    language
+        .to_string()
+        .to_lowercase()
+        .replace("++", "pp")
+        .replace('#', "sharp")
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn templates_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("templates")
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
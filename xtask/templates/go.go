@@ -0,0 +1,22 @@
+// Canonical Go fixture: exercises every capture in GO_QUERY.
+package server
+
+import "fmt"
+
+type Server struct {
+	Addr string
+}
+
+func NewServer(addr string) *Server {
+	return &Server{Addr: addr}
+}
+
+func (s *Server) Start() error {
+	fmt.Println("listening on", s.Addr)
+	return nil
+}
+
+type Cache[K comparable, V any] struct {
+	*Server
+	items map[K]V
+}
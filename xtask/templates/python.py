@@ -0,0 +1,12 @@
+# Canonical Python fixture: exercises every capture in PYTHON_QUERY.
+import os
+from typing import Optional
+
+
+class Greeter:
+    def greet(self, name: str) -> str:
+        return f"Hello, {name}!"
+
+
+def find_config(path: Optional[str] = None) -> str:
+    return path or os.getcwd()
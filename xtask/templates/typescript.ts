@@ -0,0 +1,33 @@
+// Canonical TypeScript fixture: exercises every capture in TYPESCRIPT_QUERY.
+import { readFile } from 'fs/promises';
+
+export interface Repository<T> {
+  findById(id: string): Promise<T>;
+}
+
+export type UserId = string;
+
+export enum Role {
+  Admin,
+  Member,
+}
+
+export class UserService {
+  constructor(private readonly repo: Repository<User>) {}
+
+  async getUser(id: UserId): Promise<User> {
+    return this.repo.findById(id);
+  }
+}
+
+export function loadConfig(path: string): Promise<string> {
+  return readFile(path, 'utf8');
+}
+
+declare function legacyInit(): void;
+
+declare module 'virtual:config' {
+  export const value: string;
+}
+
+const DEFAULT_TIMEOUT = 30;